@@ -4,6 +4,7 @@
 //! - Tilesets (.tsx)
 //! - Maps (.tmx)
 //! - Worlds (.world)
+//! - A single layer of a map, as a labeled sub-asset
 //!
 //! Run with: `cargo run --example basic_loading`
 
@@ -29,6 +30,7 @@ struct LoadedAssets {
     tileset: Handle<TiledTilesetAsset>,
     map: Handle<TiledMapAsset>,
     world: Handle<TiledWorldAsset>,
+    ground: Handle<TiledLayerAsset>,
     logged: bool,
 }
 
@@ -39,11 +41,15 @@ fn load_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
     let tileset = asset_server.load("orthogonal_1.tsx");
     let map = asset_server.load("simple_map.tmx");
     let world = asset_server.load("world.world");
+    // A single layer, same as loading "simple_map.tmx#layer:Ground"
+    let ground =
+        asset_server.load(TiledMapAssetLabel::Layer("Ground".into()).from_asset("simple_map.tmx"));
 
     commands.insert_resource(LoadedAssets {
         tileset,
         map,
         world,
+        ground,
         logged: false,
     });
 
@@ -55,6 +61,7 @@ fn check_assets_loaded(
     tilesets: Res<Assets<TiledTilesetAsset>>,
     maps: Res<Assets<TiledMapAsset>>,
     worlds: Res<Assets<TiledWorldAsset>>,
+    layers: Res<Assets<TiledLayerAsset>>,
     mut exit: MessageWriter<AppExit>,
 ) {
     // Only log once when all assets are ready
@@ -65,9 +72,10 @@ fn check_assets_loaded(
     let tileset = tilesets.get(&loaded_assets.tileset);
     let map = maps.get(&loaded_assets.map);
     let world = worlds.get(&loaded_assets.world);
+    let ground = layers.get(&loaded_assets.ground);
 
     // Check if all assets are loaded
-    if tileset.is_none() || map.is_none() || world.is_none() {
+    if tileset.is_none() || map.is_none() || world.is_none() || ground.is_none() {
         return;
     }
 
@@ -186,6 +194,14 @@ fn check_assets_loaded(
         }
     }
 
+    // single layer sub-asset
+    if let Some(layer_asset) = ground {
+        info!("🧱 LAYER SUB-ASSET:");
+        info!("  Name: {} (id: {})", layer_asset.name, layer_asset.id);
+        info!("  Kind: {:?}", layer_asset.kind);
+        info!("  Tiles: {}", layer_asset.tiles.len());
+    }
+
     info!("\n=== Example Complete ===");
     info!("All assets loaded and logged successfully!\n");
 
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use crate::assets::object::TiledObjectAsset;
+
+/// A single layer of a map, loaded as a labeled sub-asset of the map.
+///
+/// Load one with a `layer:` label on the map path, without spawning the map:
+///
+/// ```rust,ignore
+/// let ground: Handle<TiledLayerAsset> = asset_server.load("maps/level1.tmx#layer:Ground");
+/// // Or by ID, for unnamed layers or layers sharing a name
+/// let ground: Handle<TiledLayerAsset> = asset_server.load("maps/level1.tmx#layer_id:1");
+/// ```
+///
+/// See [`TiledMapAssetLabel`](crate::assets::map::TiledMapAssetLabel) for all labels.
+#[derive(TypePath, Asset, Debug, Clone)]
+pub struct TiledLayerAsset {
+    /// Layer ID, unique within the map
+    pub id: u32,
+
+    /// The layer's name
+    pub name: String,
+
+    /// The layer's class (Tiled's "Class" field), if set
+    pub user_type: Option<String>,
+
+    /// What kind of layer this is
+    pub kind: TiledLayerKind,
+
+    /// Whether the layer is visible in Tiled
+    pub visible: bool,
+
+    /// The layer's opacity
+    pub opacity: f32,
+
+    /// The layer's offset in pixels
+    pub offset: Vec2,
+
+    /// The layer's parallax factor
+    pub parallax: Vec2,
+
+    /// Custom properties, with file paths resolved to asset paths
+    pub properties: crate::properties::Properties,
+
+    // ===== LAYER CONTENTS =====
+    /// Tile layers: the non-empty tiles
+    pub tiles: Vec<TiledLayerTile>,
+
+    /// Object layers: the layer's objects, in draw order
+    pub objects: Vec<Handle<TiledObjectAsset>>,
+
+    /// Image layers: the layer's image, if set
+    pub image: Option<Handle<Image>>,
+
+    /// Group layers: the child layers, bottom to top
+    pub layers: Vec<Handle<TiledLayerAsset>>,
+}
+
+/// The kind of a [`TiledLayerAsset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TiledLayerKind {
+    /// A tile layer
+    Tiles,
+    /// An object layer (object group)
+    Objects,
+    /// An image layer
+    Image,
+    /// A group layer
+    Group,
+}
+
+/// A tile of a tile layer in a [`TiledLayerAsset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TiledLayerTile {
+    /// Position in Tiled's tile coordinates (Y down, negative in infinite maps)
+    pub position: IVec2,
+
+    /// Index of the tileset in the map (key of [`TiledMapAsset::tilesets`](crate::assets::map::TiledMapAsset::tilesets))
+    pub tileset_index: u32,
+
+    /// Local tile ID within the tileset
+    pub id: u32,
+
+    /// Whether the tile is flipped horizontally
+    pub flip_h: bool,
+
+    /// Whether the tile is flipped vertically
+    pub flip_v: bool,
+
+    /// Whether the tile is flipped diagonally
+    pub flip_d: bool,
+}
@@ -1,4 +1,10 @@
-use bevy::{platform::collections::HashMap, prelude::*};
+use std::time::Duration;
+
+use bevy::{
+    asset::AssetPath, diagnostic::DiagnosticPath, platform::collections::HashMap,
+    platform::time::Instant, prelude::*,
+};
+use thiserror::Error;
 
 use crate::assets::{template::TiledTemplateAsset, tileset::TiledTilesetAsset};
 
@@ -47,6 +53,195 @@ pub struct TiledMapAsset {
     /// Key: Object ID
     /// Value: Properties for that object
     pub object_properties: HashMap<u32, crate::properties::Properties>,
+
+    // ===== VERSIONING =====
+    /// TMX format version the map was saved with (e.g. "1.10")
+    pub format_version: String,
+
+    /// Version of the Tiled editor that saved the map (e.g. "1.11.2"), if recorded
+    pub tiled_version: Option<String>,
+
+    /// Features used by the map that aren't supported yet
+    pub compatibility_issues: Vec<MapCompatibilityIssue>,
+
+    // ===== LENIENT LOADING =====
+    /// Problems skipped over while loading
+    ///
+    /// Only [`TiledMapLoadIssue::InvalidTile`] is recorded unless the map was loaded with
+    /// [`TiledMapLoaderSettings::lenient`](crate::loaders::map::TiledMapLoaderSettings::lenient).
+    pub load_issues: TiledMapLoadIssues,
+
+    /// How long the map loader took, see [`TiledMapAsset::LOAD_TIME`]
+    pub load_timings: TiledMapLoadTimings,
+}
+
+impl TiledMapAsset {
+    /// Diagnostic of the time from the start of a map load until the map and all its
+    /// dependencies (tilesets, images) are loaded, in milliseconds.
+    pub const LOAD_TIME: DiagnosticPath = DiagnosticPath::const_new("tiled/map/load_time");
+
+    /// Diagnostic of the time spent in the map loader itself, in milliseconds.
+    ///
+    /// Dependencies keep loading concurrently after the loader returns, so this is
+    /// at most [`LOAD_TIME`](Self::LOAD_TIME).
+    pub const LOADER_TIME: DiagnosticPath = DiagnosticPath::const_new("tiled/map/loader_time");
+}
+
+/// Timings of a map load.
+#[derive(Debug, Clone, Copy)]
+pub struct TiledMapLoadTimings {
+    /// When the map loader started
+    pub started: Instant,
+
+    /// Time spent parsing the map with the tiled crate, which reads external
+    /// tilesets and templates one after the other
+    pub parse: Duration,
+
+    /// Time spent in the map loader, including `parse`
+    pub loader: Duration,
+}
+
+/// Labels of the sub-assets of a [`TiledMapAsset`].
+///
+/// Every layer and object of a map is also a labeled sub-asset, a
+/// [`TiledLayerAsset`](crate::assets::layer::TiledLayerAsset) or a
+/// [`TiledObjectAsset`](crate::assets::object::TiledObjectAsset), so parts of a map
+/// can be loaded and inspected without spawning it. Name labels are only added for
+/// non-empty names, and go to the first layer or object with that name in map order.
+///
+/// ```rust,ignore
+/// let ground: Handle<TiledLayerAsset> =
+///     asset_server.load(TiledMapAssetLabel::Layer("Ground".into()).from_asset("level1.tmx"));
+/// // Same as
+/// let ground: Handle<TiledLayerAsset> = asset_server.load("level1.tmx#layer:Ground");
+/// ```
+///
+/// Sub-assets aren't added for maps loaded with
+/// [`TiledMapLoaderSettings::skip_sub_assets`](crate::loaders::map::TiledMapLoaderSettings::skip_sub_assets).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TiledMapAssetLabel {
+    /// `layer:{name}`: the first layer with this name
+    Layer(String),
+    /// `layer_id:{id}`: the layer with this ID
+    LayerId(u32),
+    /// `object:{name}`: the first object with this name
+    Object(String),
+    /// `object_id:{id}`: the object with this ID
+    ObjectId(u32),
+}
+
+impl std::fmt::Display for TiledMapAssetLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TiledMapAssetLabel::Layer(name) => write!(f, "layer:{name}"),
+            TiledMapAssetLabel::LayerId(id) => write!(f, "layer_id:{id}"),
+            TiledMapAssetLabel::Object(name) => write!(f, "object:{name}"),
+            TiledMapAssetLabel::ObjectId(id) => write!(f, "object_id:{id}"),
+        }
+    }
+}
+
+impl TiledMapAssetLabel {
+    /// Add this label to a map's asset path.
+    ///
+    /// Prefer this to formatting the path by hand for names containing `#`.
+    pub fn from_asset(&self, path: impl Into<AssetPath<'static>>) -> AssetPath<'static> {
+        path.into().with_label(self.to_string())
+    }
+}
+
+/// Newest TMX format version this crate has been tested with.
+pub const SUPPORTED_FORMAT_VERSION: &str = "1.10";
+
+/// A map feature that isn't supported yet and may render or behave incorrectly.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MapCompatibilityIssue {
+    /// Only orthogonal maps are laid out correctly.
+    #[error("{orientation} orientation is not supported, tiles will be laid out as orthogonal")]
+    NonOrthogonal {
+        /// The map's orientation
+        orientation: String,
+    },
+
+    /// Infinite maps are only supported with orthogonal orientation.
+    #[error("infinite {orientation} maps are not supported, chunks will be misplaced")]
+    InfiniteNonOrthogonal {
+        /// The map's orientation
+        orientation: String,
+    },
+
+    /// Parallax factors on group layers are not applied to their children.
+    #[error("parallax on group layer {layer_id} ('{name}') is not supported and will be ignored")]
+    GroupParallax {
+        /// The group layer's ID
+        layer_id: u32,
+        /// The group layer's name
+        name: String,
+    },
+
+    /// The map was saved with a newer TMX format than this crate has been tested with.
+    #[error(
+        "map format version {version} is newer than supported version {SUPPORTED_FORMAT_VERSION}"
+    )]
+    NewerFormatVersion {
+        /// The map's format version
+        version: String,
+    },
+}
+
+/// Problems recorded while loading a map.
+#[derive(Debug, Clone, Default, Deref, DerefMut, PartialEq, Eq)]
+pub struct TiledMapLoadIssues(pub Vec<TiledMapLoadIssue>);
+
+/// A problem found while loading a map.
+///
+/// Except for [`InvalidTile`](Self::InvalidTile), these would have failed a
+/// strict map load. Lenient loading records the problem and substitutes a
+/// placeholder so the map can still be spawned.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TiledMapLoadIssue {
+    /// An external tileset file couldn't be read. Its tiles render as placeholders.
+    #[error("missing tileset {path}, its tiles will render as placeholders")]
+    MissingTileset {
+        /// Asset path of the tileset
+        path: String,
+    },
+
+    /// An image referenced by a tileset or image layer doesn't exist.
+    #[error("missing image {path}, a placeholder will be used")]
+    MissingImage {
+        /// Asset path of the image
+        path: String,
+    },
+
+    /// A tile layer's data uses an encoding the tiled crate can't decode.
+    /// The layer is loaded empty.
+    #[error(
+        "layer '{layer}' uses unsupported tile data encoding {encoding:?} with compression {compression:?}, its tiles were dropped"
+    )]
+    UnsupportedEncoding {
+        /// The layer's name
+        layer: String,
+        /// The `encoding` attribute, if any
+        encoding: Option<String>,
+        /// The `compression` attribute, if any
+        compression: Option<String>,
+    },
+
+    /// A tile references a GID past the end of its tileset, e.g. after tiles
+    /// were removed from the tileset. Recorded in strict mode too; how core
+    /// spawns the tile is configurable.
+    #[error("layer '{layer}' has tile GID {gid} at ({x}, {y}), which is outside its tileset")]
+    InvalidTile {
+        /// The layer's name
+        layer: String,
+        /// Tile X in Tiled's tile coordinates (negative in infinite maps)
+        x: i32,
+        /// Tile Y in Tiled's tile coordinates (negative in infinite maps)
+        y: i32,
+        /// The tile's GID, without flip flags
+        gid: u32,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -56,3 +251,36 @@ pub struct TilesetReference {
     /// First GID of this tileset in the map
     pub first_gid: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_format() {
+        let labels = [
+            (TiledMapAssetLabel::Layer("Ground".into()), "layer:Ground"),
+            (TiledMapAssetLabel::LayerId(3), "layer_id:3"),
+            (TiledMapAssetLabel::Object("Boss".into()), "object:Boss"),
+            (TiledMapAssetLabel::ObjectId(12), "object_id:12"),
+        ];
+        for (label, expected) in labels {
+            assert_eq!(label.to_string(), expected);
+            assert_eq!(
+                label.from_asset("maps/level1.tmx"),
+                AssetPath::parse(&format!("maps/level1.tmx#{expected}"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_label_from_asset_keeps_names_with_hashes() {
+        let path = TiledMapAssetLabel::Object("door#2".into()).from_asset("maps/level1.tmx");
+        assert_eq!(path.path(), std::path::Path::new("maps/level1.tmx"));
+        assert_eq!(path.label(), Some("object:door#2"));
+
+        let path = TiledMapAssetLabel::Layer("Ground".into())
+            .from_asset(AssetPath::from("maps/level1.tmx").with_source("embedded"));
+        assert_eq!(path.to_string(), "embedded://maps/level1.tmx#layer:Ground");
+    }
+}
@@ -1,4 +1,6 @@
+pub mod layer;
 pub mod map;
+pub mod object;
 pub mod template;
 pub mod tileset;
 pub mod world;
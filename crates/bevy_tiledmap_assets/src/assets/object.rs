@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+/// A single object of a map, loaded as a labeled sub-asset of the map.
+///
+/// Load one with an `object:` label on the map path, without spawning the map:
+///
+/// ```rust,ignore
+/// let boss: Handle<TiledObjectAsset> = asset_server.load("maps/level1.tmx#object:Boss");
+/// // Or by ID, for unnamed objects or objects sharing a name
+/// let boss: Handle<TiledObjectAsset> = asset_server.load("maps/level1.tmx#object_id:12");
+/// ```
+///
+/// See [`TiledMapAssetLabel`](crate::assets::map::TiledMapAssetLabel) for all labels.
+#[derive(TypePath, Asset, Debug, Clone)]
+pub struct TiledObjectAsset {
+    /// The raw Tiled object data (PRESERVE AS-IS)
+    ///
+    /// Template properties are already merged in by the tiled crate.
+    pub object: tiled::ObjectData,
+
+    /// ID of the object layer containing the object
+    pub layer_id: u32,
+
+    /// Custom properties, with file paths resolved to asset paths
+    pub properties: crate::properties::Properties,
+}
+
+impl TiledObjectAsset {
+    /// Object ID, unique within the map
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.object.id()
+    }
+
+    /// The object's name
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.object.name
+    }
+}
@@ -1,5 +1,8 @@
 use bevy::{platform::collections::HashMap, prelude::*};
 
+/// Color used for placeholder tiles and images when a texture is missing.
+pub const MISSING_TEXTURE_COLOR: Color = Color::srgb(1.0, 0.0, 1.0);
+
 /// Bevy asset wrapper for Tiled tilesets (.tsx files)
 ///
 /// Supports both texture atlas tilesets (single spritesheet) and image collection
@@ -47,6 +50,16 @@ pub struct TiledTilesetAsset {
     ///
     /// Only relevant for texture atlas tilesets.
     pub margin: u32,
+
+    /// Drawing offset applied to every tile of this tileset (pixels, Tiled's Y-down)
+    ///
+    /// Copied from the tileset's `<tileoffset>`.
+    pub tile_offset: IVec2,
+
+    /// Which point of a tile object's image its position refers to
+    ///
+    /// Read from the tileset's `objectalignment` attribute.
+    pub object_alignment: ObjectAlignment,
     // ===== CUSTOM PROPERTIES =====
     /// Custom properties set on the tileset in Tiled
     pub properties: crate::properties::Properties,
@@ -56,7 +69,82 @@ pub struct TiledTilesetAsset {
     pub tile_properties: HashMap<u32, crate::properties::Properties>,
 }
 
+/// Alignment of tile objects, from a tileset's `objectalignment` attribute.
+///
+/// Tiled positions a tile object by one point of its image; this says which.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum ObjectAlignment {
+    /// Not set: bottom-left on orthogonal maps
+    #[default]
+    Unspecified,
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl ObjectAlignment {
+    /// Parse an `objectalignment` attribute value (`"topleft"`, `"bottom"`, ...).
+    ///
+    /// Unknown values are treated as unspecified.
+    pub fn from_attribute(value: &str) -> Self {
+        match value {
+            "topleft" => Self::TopLeft,
+            "top" => Self::Top,
+            "topright" => Self::TopRight,
+            "left" => Self::Left,
+            "center" => Self::Center,
+            "right" => Self::Right,
+            "bottomleft" => Self::BottomLeft,
+            "bottom" => Self::Bottom,
+            "bottomright" => Self::BottomRight,
+            _ => Self::Unspecified,
+        }
+    }
+
+    /// The aligned point as a fraction of the object's size, from its top-left
+    /// corner in Tiled's Y-down coordinates (`(0, 1)` is bottom-left).
+    pub fn origin(self) -> Vec2 {
+        match self {
+            Self::TopLeft => Vec2::new(0.0, 0.0),
+            Self::Top => Vec2::new(0.5, 0.0),
+            Self::TopRight => Vec2::new(1.0, 0.0),
+            Self::Left => Vec2::new(0.0, 0.5),
+            Self::Center => Vec2::new(0.5, 0.5),
+            Self::Right => Vec2::new(1.0, 0.5),
+            Self::Unspecified | Self::BottomLeft => Vec2::new(0.0, 1.0),
+            Self::Bottom => Vec2::new(0.5, 1.0),
+            Self::BottomRight => Vec2::new(1.0, 1.0),
+        }
+    }
+}
+
 impl TiledTilesetAsset {
+    /// Build a tileset asset with no images.
+    ///
+    /// Used by lenient map loading in place of a missing tileset. Renderers draw
+    /// its tiles as [`MISSING_TEXTURE_COLOR`] placeholders.
+    pub fn placeholder(tileset: tiled::Tileset) -> Self {
+        Self {
+            tile_size: UVec2::new(tileset.tile_width, tileset.tile_height),
+            grid_size: UVec2::ZERO,
+            spacing: 0,
+            margin: 0,
+            tile_offset: IVec2::new(tileset.offset_x, tileset.offset_y),
+            object_alignment: ObjectAlignment::Unspecified,
+            properties: tileset.properties.clone(),
+            tile_properties: HashMap::default(),
+            atlas_image: None,
+            tile_images: HashMap::default(),
+            tileset,
+        }
+    }
+
     /// Check if this is an image collection tileset (vs. texture atlas)
     ///
     /// Returns `true` if each tile has its own image file, `false` if the tileset
@@ -86,4 +174,25 @@ impl TiledTilesetAsset {
             self.tile_images.get(&local_tile_id)
         }
     }
+
+    /// Check whether a local tile ID (0-based, NOT a GID) exists in this tileset.
+    ///
+    /// Maps edited after their tileset lost tiles can reference IDs past its end.
+    pub fn contains_tile(&self, local_tile_id: u32) -> bool {
+        tileset_contains_tile(&self.tileset, local_tile_id)
+    }
+
+    /// Drawing offset of this tileset's tiles in Bevy's Y-up space.
+    #[inline]
+    pub fn render_offset(&self) -> Vec2 {
+        Vec2::new(self.tile_offset.x as f32, -self.tile_offset.y as f32)
+    }
+}
+
+/// Check whether a local tile ID exists in a tileset.
+///
+/// Atlas tiles exist up to the tile count; image collection tiles are listed
+/// explicitly and their IDs can have gaps.
+pub(crate) fn tileset_contains_tile(tileset: &tiled::Tileset, local_tile_id: u32) -> bool {
+    local_tile_id < tileset.tilecount || tileset.get_tile(local_tile_id).is_some()
 }
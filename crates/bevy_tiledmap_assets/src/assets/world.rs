@@ -20,6 +20,23 @@ pub struct TiledWorldAsset {
     /// Key: Map file name (as specified in the world file)
     /// Value: Handle to the loaded map asset
     pub maps: HashMap<String, Handle<TiledMapAsset>>,
+
+    // ===== CUSTOM PROPERTIES =====
+    /// Custom properties of the world
+    ///
+    /// Tiled has no properties for worlds, so these are read from a
+    /// `"properties"` array in the world file (Tiled's JSON property format),
+    /// and from an optional `<name>.world.properties.json` sidecar next to it,
+    /// which takes precedence (Tiled drops unknown keys when it saves a world).
+    pub properties: tiled::Properties,
+
+    /// Custom properties of maps in this world, overriding the maps' own
+    ///
+    /// Read like [`properties`](Self::properties), from a `"properties"` array
+    /// in each map entry of the world file, and from the sidecar's `"maps"`
+    /// object keyed by map file name.
+    /// Key: Map file name (as specified in the world file)
+    pub map_properties: HashMap<String, tiled::Properties>,
 }
 
 impl TiledWorldAsset {
@@ -53,4 +70,17 @@ impl TiledWorldAsset {
     pub fn get_map(&self, map_name: &str) -> Option<&Handle<TiledMapAsset>> {
         self.maps.get(map_name)
     }
+
+    /// Get the property overrides the world sets for a map
+    ///
+    /// # Arguments
+    /// * `map_name` - The map file name
+    ///
+    /// # Returns
+    /// * `Some(&tiled::Properties)` - The map's overrides
+    /// * `None` - If the world sets no properties for the map
+    #[inline]
+    pub fn get_map_properties(&self, map_name: &str) -> Option<&tiled::Properties> {
+        self.map_properties.get(map_name)
+    }
 }
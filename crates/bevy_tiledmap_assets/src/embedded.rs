@@ -0,0 +1,158 @@
+//! Tiled files embedded in the binary.
+//!
+//! Jam games and WASM builds often ship without a loose `assets` folder.
+//! [`embed_tiled_assets!`](crate::embed_tiled_assets) compiles maps, tilesets,
+//! templates, worlds and their images into the binary with `include_bytes!`,
+//! and registers them with Bevy's `embedded://` asset source. Loading an
+//! embedded map goes through the same loaders as a map on disk, and the files
+//! it references are looked up among the app's embedded files first:
+//!
+//! ```rust,ignore
+//! // Paths are relative to the crate's `assets` folder
+//! embed_tiled_assets!(
+//!     app,
+//!     "maps/level1.tmx",
+//!     "tilesets/terrain.tsx",
+//!     "tilesets/terrain.png",
+//! );
+//!
+//! let map = asset_server.load("embedded://maps/level1.tmx");
+//! ```
+//!
+//! Every file the map references must be embedded too; files that aren't are
+//! read from the `assets` folder like for non-embedded maps. Embedded files
+//! belong to the app they were embedded in ([`EmbeddedTiledFiles`]) and are
+//! only looked up for `embedded://` assets, so they never shadow files on disk.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use bevy::asset::LoadContext;
+use bevy::asset::io::embedded::EmbeddedAssetRegistry;
+use bevy::prelude::*;
+use normalize_path::NormalizePath;
+
+/// Name of Bevy's embedded asset source.
+const EMBEDDED_SOURCE: &str = "embedded";
+
+/// The Tiled files embedded in an app, by path relative to the `assets` folder.
+///
+/// Filled by [`embed`] and shared with the Tiled asset loaders. Only assets
+/// loaded from the `embedded://` source look files up here.
+#[derive(Resource, Clone, Default, Debug)]
+pub struct EmbeddedTiledFiles(Arc<RwLock<HashMap<PathBuf, &'static [u8]>>>);
+
+impl EmbeddedTiledFiles {
+    /// Get an embedded file by its path relative to the `assets` folder.
+    pub fn get(&self, path: &Path) -> Option<&'static [u8]> {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&normalize(path))
+            .copied()
+    }
+
+    fn insert(&self, path: &Path, bytes: &'static [u8]) {
+        self.0
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(normalize(path), bytes);
+    }
+
+    /// A resource reader for the asset being loaded, serving these files if
+    /// it comes from the `embedded://` source.
+    pub(crate) fn reader(&self, load_context: &LoadContext) -> EmbeddedResourceReader {
+        let source = load_context.asset_path().source().as_str();
+        EmbeddedResourceReader {
+            files: (source == Some(EMBEDDED_SOURCE)).then(|| self.clone()),
+        }
+    }
+}
+
+/// Embed a Tiled file or image and register it with the `embedded://` asset source.
+///
+/// `path` is relative to the `assets` folder, and `full_path` is the file's
+/// path on disk (used by Bevy to hot reload embedded assets). Usually called
+/// through [`embed_tiled_assets!`](crate::embed_tiled_assets). Requires
+/// Bevy's `AssetPlugin`.
+pub fn embed(app: &mut App, path: &str, full_path: &str, bytes: &'static [u8]) {
+    app.world_mut()
+        .get_resource_or_init::<EmbeddedTiledFiles>()
+        .insert(Path::new(path), bytes);
+    app.world()
+        .resource::<EmbeddedAssetRegistry>()
+        .insert_asset(PathBuf::from(full_path), Path::new(path), bytes);
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    PathBuf::from(path.normalize().to_string_lossy().replace('\\', "/"))
+}
+
+/// `tiled` resource reader serving embedded files, and other files from disk.
+///
+/// Built with [`EmbeddedTiledFiles::reader`]; assets from other sources than
+/// `embedded://` only read from disk.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EmbeddedResourceReader {
+    files: Option<EmbeddedTiledFiles>,
+}
+
+impl EmbeddedResourceReader {
+    /// Check whether a file relative to the `assets` folder is embedded or on disk.
+    pub(crate) fn exists(&self, path: &Path) -> bool {
+        self.get(path).is_some() || Path::new("assets").join(path).exists()
+    }
+
+    /// Read a file at a path as the `tiled` crate sees it (`assets/...`),
+    /// embedded files first.
+    pub(crate) fn read(&self, path: &Path) -> std::io::Result<Vec<u8>> {
+        match path
+            .strip_prefix("assets")
+            .ok()
+            .and_then(|path| self.get(path))
+        {
+            Some(bytes) => Ok(bytes.to_vec()),
+            None => std::fs::read(path),
+        }
+    }
+
+    fn get(&self, path: &Path) -> Option<&'static [u8]> {
+        self.files.as_ref().and_then(|files| files.get(path))
+    }
+}
+
+impl tiled::ResourceReader for EmbeddedResourceReader {
+    type Resource = Cursor<Vec<u8>>;
+    type Error = std::io::Error;
+
+    fn read_from(&mut self, path: &Path) -> Result<Self::Resource, Self::Error> {
+        self.read(path).map(Cursor::new)
+    }
+}
+
+/// Embed Tiled files and images in the binary and register them with the
+/// `embedded://` asset source.
+///
+/// Paths are relative to the calling crate's `assets` folder. Call this after
+/// adding Bevy's `AssetPlugin` (e.g. with `DefaultPlugins`), then load the
+/// files with `embedded://` paths. See the [`embedded`](crate::embedded) module.
+///
+/// ```rust,ignore
+/// embed_tiled_assets!(app, "maps/level1.tmx", "tilesets/terrain.tsx", "tilesets/terrain.png");
+/// ```
+#[macro_export]
+macro_rules! embed_tiled_assets {
+    ($app:expr, $($path:literal),+ $(,)?) => {{
+        let app = $app;
+        $(
+            $crate::embedded::embed(
+                app,
+                $path,
+                concat!(env!("CARGO_MANIFEST_DIR"), "/assets/", $path),
+                include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/", $path)),
+            );
+        )+
+    }};
+}
@@ -1,4 +1,5 @@
 pub mod assets;
+pub mod embedded;
 pub mod loaders;
 pub mod plugin;
 
@@ -22,10 +23,17 @@ pub use plugin::TiledmapAssetsPlugin;
 /// ```
 pub mod prelude {
     pub use crate::assets::{
-        map::{TiledMapAsset, TilesetReference},
+        layer::{TiledLayerAsset, TiledLayerKind, TiledLayerTile},
+        map::{
+            MapCompatibilityIssue, TiledMapAsset, TiledMapAssetLabel, TiledMapLoadIssue,
+            TiledMapLoadIssues, TiledMapLoadTimings, TilesetReference,
+        },
+        object::TiledObjectAsset,
         template::TiledTemplateAsset,
-        tileset::TiledTilesetAsset,
+        tileset::{MISSING_TEXTURE_COLOR, ObjectAlignment, TiledTilesetAsset},
         world::TiledWorldAsset,
     };
+    pub use crate::embed_tiled_assets;
+    pub use crate::loaders::{map::TiledMapLoaderSettings, tileset::TiledTilesetLoaderSettings};
     pub use crate::plugin::TiledmapAssetsPlugin;
 }
@@ -1,35 +1,102 @@
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
 use bevy::{
-    asset::{AssetLoader, LoadContext, io::Reader},
-    platform::collections::HashMap,
+    asset::{AssetLoader, LoadContext, RenderAssetUsages, io::Reader},
+    diagnostic::Diagnostics,
+    platform::collections::{HashMap, HashSet},
+    platform::time::Instant,
     prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
     tasks::ConditionalSendFuture,
 };
 use normalize_path::NormalizePath;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use xml::attribute::OwnedAttribute;
+use xml::reader::XmlEvent;
+use xml::writer::XmlEvent as WriterEvent;
 
 use crate::assets::{
-    map::{TiledMapAsset, TilesetReference},
-    tileset::TiledTilesetAsset,
+    layer::{TiledLayerAsset, TiledLayerKind, TiledLayerTile},
+    map::{
+        MapCompatibilityIssue, SUPPORTED_FORMAT_VERSION, TiledMapAsset, TiledMapAssetLabel,
+        TiledMapLoadIssue, TiledMapLoadIssues, TiledMapLoadTimings, TilesetReference,
+    },
+    object::TiledObjectAsset,
+    tileset::{MISSING_TEXTURE_COLOR, ObjectAlignment, TiledTilesetAsset, tileset_contains_tile},
+};
+use crate::embedded::{EmbeddedResourceReader, EmbeddedTiledFiles};
+use crate::loaders::tileset::{
+    TiledTilesetLoaderSettings, TilesetLoaderError, build_tileset_asset, parse_object_alignment,
 };
-use crate::loaders::TiledResourceCache;
+use crate::loaders::{TiledResourceCache, in_source, xml_attribute};
 
 /// Asset loader for Tiled maps (.tmx files)
 ///
 /// This loader handles all map dependencies:
-/// - Tilesets (.tsx files)
+/// - Tilesets (.tsx files, or embedded in the map as labeled sub-assets)
 /// - Templates (.tx files) referenced by objects
 /// - Images for image layers
 ///
+/// Each layer and object is also added as a labeled sub-asset (see
+/// [`TiledMapAssetLabel`]), e.g. `level1.tmx#layer:Ground` or `level1.tmx#object:Boss`.
+///
 /// It also calculates processed data for infinite maps.
 #[derive(Default)]
 pub struct TiledMapAssetLoader {
     pub cache: TiledResourceCache,
+    pub embedded: EmbeddedTiledFiles,
+}
+
+/// Settings for [`TiledMapAssetLoader`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let map: Handle<TiledMapAsset> = asset_server.load_with_settings(
+///     "maps/level1.tmx",
+///     |settings: &mut TiledMapLoaderSettings| settings.lenient = true,
+/// );
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TiledMapLoaderSettings {
+    /// Load what's possible instead of failing the whole map.
+    ///
+    /// Missing tilesets and images are replaced with magenta placeholders, and
+    /// tile layers with unsupported data encodings are loaded empty. Each
+    /// problem is logged and recorded in [`TiledMapAsset::load_issues`].
+    pub lenient: bool,
+
+    /// Don't add the map's layers and objects as labeled sub-assets.
+    ///
+    /// Saves time and memory for maps with many objects that are only spawned.
+    /// See [`TiledMapAssetLabel`].
+    pub skip_sub_assets: bool,
 }
 
 #[derive(Debug, Error)]
 pub enum MapLoaderError {
     #[error("Failed to load map: {0}")]
-    TiledError(#[from] tiled::Error),
+    TiledError(tiled::Error),
+
+    #[error(
+        "Unsupported tile layer data: encoding {}, compression {}{}",
+        encoding.as_deref().unwrap_or("none (XML)"),
+        compression.as_deref().unwrap_or("none"),
+        if compression.as_deref() == Some("zstd") {
+            " (enable the `zstd` feature of bevy_tiledmap_assets)"
+        } else {
+            ""
+        }
+    )]
+    UnsupportedCompression {
+        encoding: Option<String>,
+        compression: Option<String>,
+    },
+
+    #[error("Failed to load embedded tileset: {0}")]
+    EmbeddedTileset(#[from] TilesetLoaderError),
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -38,30 +105,81 @@ pub enum MapLoaderError {
     InvalidPath(String),
 }
 
+impl From<tiled::Error> for MapLoaderError {
+    fn from(error: tiled::Error) -> Self {
+        match error {
+            tiled::Error::InvalidEncodingFormat {
+                encoding,
+                compression,
+            } => MapLoaderError::UnsupportedCompression {
+                encoding,
+                compression,
+            },
+            error => MapLoaderError::TiledError(error),
+        }
+    }
+}
+
 impl AssetLoader for TiledMapAssetLoader {
     type Asset = TiledMapAsset;
-    type Settings = ();
+    type Settings = TiledMapLoaderSettings;
     type Error = MapLoaderError;
 
     fn load(
         &self,
-        _reader: &mut dyn Reader,
-        _settings: &Self::Settings,
+        reader: &mut dyn Reader,
+        settings: &Self::Settings,
         load_context: &mut LoadContext,
     ) -> impl ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
         async move {
+            let started = Instant::now();
+
             // Parse TMX using tiled crate
-            let asset_path = load_context.asset_path().path();
+            let asset_path = load_context.asset_path().path().to_path_buf();
 
             // Construct full filesystem path
             // Bevy loads assets from the "assets" directory by default
-            let full_path = std::path::Path::new("assets").join(asset_path);
+            let full_path = Path::new("assets").join(&asset_path);
+
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            // Start loading external tilesets while the tiled crate parses the map
+            let header = MapHeader::read(&bytes);
+            let files = self.embedded.reader(load_context);
+            preload_external_tilesets(&header, settings, &files, load_context)?;
 
             // Create loader with default cache
             // TODO: Implement shared cache once we figure out the correct API
-            let mut loader = tiled::Loader::new();
+            let parse_started = Instant::now();
+            let mut load_issues = TiledMapLoadIssues::default();
+            let (map, missing_tilesets) = if settings.lenient {
+                let mut lenient_reader = LenientResourceReader::new(
+                    full_path.clone(),
+                    &bytes,
+                    &header,
+                    files.clone(),
+                    &mut load_issues,
+                );
+                let mut loader = tiled::Loader::with_reader(lenient_reader);
+                let map = loader.load_tmx_map(&full_path)?;
+                lenient_reader = loader.into_inner().1;
+                (map, lenient_reader.missing_tilesets)
+            } else {
+                let mut loader = tiled::Loader::with_reader(files.clone());
+                (loader.load_tmx_map(&full_path)?, Vec::new())
+            };
+            let parse = parse_started.elapsed();
+
+            // The tiled crate doesn't expose the editor version, so read it from the TMX header
+            let tiled_version = xml_attribute(&header.map, "tiledversion").map(str::to_string);
+            let format_version = map.version().to_string();
 
-            let map = loader.load_tmx_map(&full_path)?;
+            // Warn early about features that would otherwise silently render wrong
+            let compatibility_issues = check_map_compatibility(&map);
+            for issue in &compatibility_issues {
+                warn!("{}: {}", asset_path.display(), issue);
+            }
 
             // 3. Load tileset dependencies
             // Key by tileset_index (iteration order matches tiled's tileset_index())
@@ -69,10 +187,61 @@ impl AssetLoader for TiledMapAssetLoader {
             let mut current_gid = 1u32; // GIDs start at 1
 
             for (tileset_index, tileset) in map.tilesets().iter().enumerate() {
-                // External tileset: load as dependency
-                let tileset_path =
-                    resolve_relative_path(load_context, &tileset.source.to_string_lossy())?;
-                let handle: Handle<TiledTilesetAsset> = load_context.load(tileset_path);
+                let handle: Handle<TiledTilesetAsset> = if tileset.source == full_path {
+                    // Embedded tileset: tiled records the map itself as its source,
+                    // so build the tileset asset inline as a labeled sub-asset
+                    if settings.lenient {
+                        collect_missing_tileset_images(
+                            tileset,
+                            &files,
+                            load_context,
+                            &mut load_issues,
+                        )?;
+                    }
+                    // The tiled crate doesn't expose objectalignment; the map's
+                    // <tileset> elements are in the same order as map.tilesets()
+                    let object_alignment = header
+                        .tilesets
+                        .get(tileset_index)
+                        .map_or(ObjectAlignment::Unspecified, |attributes| {
+                            parse_object_alignment(attributes)
+                        });
+                    let asset = build_tileset_asset(
+                        tileset.as_ref().clone(),
+                        load_context,
+                        object_alignment,
+                        settings.lenient,
+                        &files,
+                    )?;
+                    load_context.add_labeled_asset(format!("tileset_{tileset_index}"), asset)
+                } else {
+                    // External tileset: load as dependency
+                    let tileset_path =
+                        resolve_relative_path(load_context, &tileset.source.to_string_lossy())?;
+                    if missing_tilesets.contains(&tileset.source) {
+                        load_issues.push(TiledMapLoadIssue::MissingTileset { path: tileset_path });
+                        load_context.add_labeled_asset(
+                            format!("missing_tileset_{tileset_index}"),
+                            TiledTilesetAsset::placeholder(tileset.as_ref().clone()),
+                        )
+                    } else if settings.lenient {
+                        collect_missing_tileset_images(
+                            tileset,
+                            &files,
+                            load_context,
+                            &mut load_issues,
+                        )?;
+                        let tileset_path = in_source(load_context, tileset_path);
+                        load_context
+                            .loader()
+                            .with_settings(|settings: &mut TiledTilesetLoaderSettings| {
+                                settings.lenient = true;
+                            })
+                            .load(tileset_path)
+                    } else {
+                        load_context.load(in_source(load_context, tileset_path))
+                    }
+                };
 
                 // Key by tileset_index for direct lookup from LayerTile::tileset_index()
                 tilesets.insert(
@@ -87,13 +256,21 @@ impl AssetLoader for TiledMapAssetLoader {
                 current_gid += tileset.tilecount;
             }
 
+            // Tiles past the end of their tileset are reported, core decides how to spawn them
+            collect_invalid_tiles(&map, &tilesets, &missing_tilesets, &mut load_issues);
+
             // 4. Templates are handled internally by tiled crate's ResourceCache
             // when objects are parsed. No need to track them separately.
             let templates = HashMap::default();
 
             // 5. Load image layer dependencies (recursively searches group layers)
             let mut images = HashMap::default();
-            collect_image_layers(&map, load_context, &mut images)?;
+            let mut missing_images = settings.lenient.then_some(&mut load_issues);
+            collect_image_layers(&map, &files, load_context, &mut images, &mut missing_images)?;
+
+            for issue in load_issues.iter() {
+                warn!("{}: {}", asset_path.display(), issue);
+            }
 
             // 6. Calculate processed data
             let (tilemap_size, largest_tile_size, rect) = calculate_map_bounds(&map, &tilesets);
@@ -102,6 +279,11 @@ impl AssetLoader for TiledMapAssetLoader {
             let (tiled_offset, topleft_chunk, bottomright_chunk) =
                 calculate_infinite_map_data(&map);
 
+            // Layers and objects as labeled sub-assets
+            if !settings.skip_sub_assets {
+                add_sub_assets(&map, &images, load_context);
+            }
+
             // 8. Extract and normalize custom properties
             // Normalize FileValue paths to be asset-root-relative (resolves ../foo paths)
             let mut properties = map.properties.clone();
@@ -130,6 +312,15 @@ impl AssetLoader for TiledMapAssetLoader {
                 properties,
                 layer_properties,
                 object_properties,
+                format_version,
+                tiled_version,
+                compatibility_issues,
+                load_issues,
+                load_timings: TiledMapLoadTimings {
+                    started,
+                    parse,
+                    loader: started.elapsed(),
+                },
             })
         }
     }
@@ -139,6 +330,508 @@ impl AssetLoader for TiledMapAssetLoader {
     }
 }
 
+/// Start loading the map's external tilesets before the map is parsed.
+///
+/// The tiled crate reads each external tileset in turn while parsing the map.
+/// Requesting the tileset assets up front lets Bevy load them concurrently
+/// meanwhile, instead of only after the map is parsed. Requesting them again later
+/// returns the same handles.
+///
+/// In lenient mode, tilesets that don't exist are skipped; they are replaced by
+/// placeholders once the map is parsed.
+fn preload_external_tilesets(
+    header: &MapHeader,
+    settings: &TiledMapLoaderSettings,
+    files: &EmbeddedResourceReader,
+    load_context: &mut LoadContext,
+) -> Result<(), MapLoaderError> {
+    for source in header.external_tileset_sources() {
+        let tileset_path = resolve_relative_path(load_context, source)?;
+        if settings.lenient {
+            if !files.exists(Path::new(&tileset_path)) {
+                continue;
+            }
+            let tileset_path = in_source(load_context, tileset_path);
+            let _: Handle<TiledTilesetAsset> = load_context
+                .loader()
+                .with_settings(|settings: &mut TiledTilesetLoaderSettings| {
+                    settings.lenient = true;
+                })
+                .load(tileset_path);
+        } else {
+            let _: Handle<TiledTilesetAsset> =
+                load_context.load(in_source(load_context, tileset_path));
+        }
+    }
+    Ok(())
+}
+
+/// The `<map>` element and its `<tileset>` elements, read from the TMX for
+/// what the tiled crate doesn't expose.
+///
+/// Embedded tilesets have no `source`, and `<tileset>` elements nested deeper
+/// don't count. Tiled writes tilesets before layers, so reading stops at the
+/// first layer instead of going through the tile data. Malformed XML stops
+/// reading too, and is reported by the tiled crate's parse.
+#[derive(Debug, Default)]
+struct MapHeader {
+    /// Attributes of `<map>`
+    map: Vec<OwnedAttribute>,
+    /// Attributes of each of the map's `<tileset>` elements, in order
+    tilesets: Vec<Vec<OwnedAttribute>>,
+}
+
+impl MapHeader {
+    fn read(bytes: &[u8]) -> Self {
+        let mut header = Self::default();
+        let mut depth = 0;
+        for event in xml::EventReader::new(bytes) {
+            match event {
+                Ok(XmlEvent::StartElement {
+                    name, attributes, ..
+                }) => {
+                    match (depth, name.local_name.as_str()) {
+                        (0, _) => header.map = attributes,
+                        (1, "tileset") => header.tilesets.push(attributes),
+                        (1, "layer" | "objectgroup" | "imagelayer" | "group") => break,
+                        _ => {}
+                    }
+                    depth += 1;
+                }
+                Ok(XmlEvent::EndElement { .. }) => depth -= 1,
+                Ok(XmlEvent::EndDocument) | Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+        header
+    }
+
+    /// The `source` of the map's external tilesets, in order.
+    fn external_tileset_sources(&self) -> impl Iterator<Item = &str> {
+        self.tilesets
+            .iter()
+            .filter_map(|attributes| xml_attribute(attributes, "source"))
+    }
+}
+
+/// Record [`TiledMapAsset::LOAD_TIME`] and [`TiledMapAsset::LOADER_TIME`] when maps
+/// finish loading with their dependencies.
+pub fn record_map_load_times(
+    mut events: MessageReader<AssetEvent<TiledMapAsset>>,
+    maps: Res<Assets<TiledMapAsset>>,
+    asset_server: Res<AssetServer>,
+    mut diagnostics: Diagnostics,
+) {
+    for event in events.read() {
+        let AssetEvent::LoadedWithDependencies { id } = event else {
+            continue;
+        };
+        let Some(map) = maps.get(*id) else {
+            continue;
+        };
+        let timings = map.load_timings;
+        let load_time = timings.started.elapsed();
+        if let Some(path) = asset_server.get_path(*id) {
+            debug!(
+                "Map {} loaded with dependencies in {:?} ({:?} in the loader, {:?} parsing)",
+                path, load_time, timings.loader, timings.parse
+            );
+        }
+        diagnostics.add_measurement(&TiledMapAsset::LOAD_TIME, || {
+            load_time.as_secs_f64() * 1000.0
+        });
+        diagnostics.add_measurement(&TiledMapAsset::LOADER_TIME, || {
+            timings.loader.as_secs_f64() * 1000.0
+        });
+    }
+}
+
+/// `tiled` resource reader used for lenient loading.
+///
+/// Serves the map from memory with unsupported tile data replaced, and
+/// substitutes an empty placeholder for external tilesets that can't be read.
+struct LenientResourceReader {
+    map_path: PathBuf,
+    map_source: Vec<u8>,
+    tile_size: (u32, u32),
+    /// Tile count of each external tileset, from the gaps between `firstgid`s
+    tile_counts: Vec<(PathBuf, u32)>,
+    /// Tilesets that were replaced by a placeholder
+    missing_tilesets: Vec<PathBuf>,
+    /// Reads everything but the map
+    files: EmbeddedResourceReader,
+}
+
+impl LenientResourceReader {
+    fn new(
+        map_path: PathBuf,
+        bytes: &[u8],
+        header: &MapHeader,
+        files: EmbeddedResourceReader,
+        issues: &mut TiledMapLoadIssues,
+    ) -> Self {
+        let map_number = |name| {
+            xml_attribute(&header.map, name)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0)
+        };
+        let tile_size = (map_number("tilewidth"), map_number("tileheight"));
+
+        let map_dir = map_path.parent().unwrap_or(Path::new(""));
+        let mut tilesets: Vec<(u32, Option<&str>)> = header
+            .tilesets
+            .iter()
+            .filter_map(|attributes| {
+                let first_gid = xml_attribute(attributes, "firstgid")?.parse().ok()?;
+                Some((first_gid, xml_attribute(attributes, "source")))
+            })
+            .collect();
+        tilesets.sort_by_key(|(first_gid, _)| *first_gid);
+        let tile_counts = tilesets
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (first_gid, source))| {
+                let next_gid = tilesets.get(index + 1).map_or(*first_gid, |next| next.0);
+                Some((map_dir.join((*source)?), next_gid - first_gid))
+            })
+            .collect();
+
+        let infinite = xml_attribute(&header.map, "infinite") == Some("1");
+        let map_source = patch_unsupported_encodings(bytes, infinite, issues);
+
+        Self {
+            map_path,
+            map_source,
+            tile_size,
+            tile_counts,
+            missing_tilesets: Vec::new(),
+            files,
+        }
+    }
+}
+
+impl tiled::ResourceReader for LenientResourceReader {
+    type Resource = Cursor<Vec<u8>>;
+    type Error = std::io::Error;
+
+    fn read_from(&mut self, path: &Path) -> Result<Self::Resource, Self::Error> {
+        if path == self.map_path {
+            return Ok(Cursor::new(self.map_source.clone()));
+        }
+        match self.files.read(path) {
+            Ok(bytes) => Ok(Cursor::new(bytes)),
+            Err(_) if path.extension().is_some_and(|extension| extension == "tsx") => {
+                self.missing_tilesets.push(path.to_path_buf());
+                let tile_count = self
+                    .tile_counts
+                    .iter()
+                    .find(|(source, _)| source == path)
+                    .map_or(0, |(_, count)| *count);
+                let (tile_width, tile_height) = self.tile_size;
+                let placeholder = format!(
+                    r#"<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" name="missing" tilewidth="{tile_width}" tileheight="{tile_height}" tilecount="{tile_count}" columns="0"></tileset>"#
+                );
+                Ok(Cursor::new(placeholder.into_bytes()))
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// Replace tile layer data the tiled crate can't decode with empty CSV data.
+///
+/// Records an [`TiledMapLoadIssue::UnsupportedEncoding`] for each replaced layer.
+/// The map is rewritten through an XML writer only when a layer was replaced;
+/// malformed XML is returned as is, for the tiled crate's parse to report.
+fn patch_unsupported_encodings(
+    bytes: &[u8],
+    infinite: bool,
+    issues: &mut TiledMapLoadIssues,
+) -> Vec<u8> {
+    let mut writer = xml::EventWriter::new(Vec::with_capacity(bytes.len()));
+    let mut patched = false;
+    // Attributes of the tile layer being read
+    let mut layer: Option<Vec<OwnedAttribute>> = None;
+    // Elements left to close in the replaced data, 0 outside of it
+    let mut skipping = 0;
+
+    for event in xml::EventReader::new(bytes) {
+        let Ok(event) = event else {
+            return bytes.to_vec();
+        };
+        if skipping > 0 {
+            match event {
+                XmlEvent::StartElement { .. } => skipping += 1,
+                XmlEvent::EndElement { .. } => skipping -= 1,
+                _ => {}
+            }
+            if skipping == 0 && writer.write(WriterEvent::end_element()).is_err() {
+                return bytes.to_vec();
+            }
+            continue;
+        }
+
+        match &event {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } if name.local_name == "layer" => layer = Some(attributes.clone()),
+            XmlEvent::EndElement { name } if name.local_name == "layer" => layer = None,
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } if name.local_name == "data" => {
+                if let Some(layer) = &layer
+                    && let Some(replacement) =
+                        unsupported_data_replacement(layer, attributes, infinite, issues)
+                {
+                    patched = true;
+                    skipping = 1;
+                    let written = writer
+                        .write(WriterEvent::start_element("data").attr("encoding", "csv"))
+                        .and_then(|()| writer.write(WriterEvent::characters(&replacement)));
+                    if written.is_err() {
+                        return bytes.to_vec();
+                    }
+                    continue;
+                }
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+
+        if let Some(event) = event.as_writer_event()
+            && writer.write(event).is_err()
+        {
+            return bytes.to_vec();
+        }
+    }
+
+    if patched {
+        writer.into_inner()
+    } else {
+        bytes.to_vec()
+    }
+}
+
+/// The empty CSV tile data replacing a tile layer's `<data>` element, if the
+/// tiled crate can't decode it.
+///
+/// Records an [`TiledMapLoadIssue::UnsupportedEncoding`] for replaced data.
+fn unsupported_data_replacement(
+    layer: &[OwnedAttribute],
+    data: &[OwnedAttribute],
+    infinite: bool,
+    issues: &mut TiledMapLoadIssues,
+) -> Option<String> {
+    let encoding = xml_attribute(data, "encoding");
+    let compression = xml_attribute(data, "compression");
+    let zstd = cfg!(feature = "zstd") && compression == Some("zstd");
+    let supported = matches!(
+        (encoding, compression),
+        (Some("csv"), None) | (Some("base64"), None | Some("zlib" | "gzip"))
+    ) || (encoding == Some("base64") && zstd);
+    if supported {
+        return None;
+    }
+
+    issues.push(TiledMapLoadIssue::UnsupportedEncoding {
+        layer: xml_attribute(layer, "name").unwrap_or_default().to_string(),
+        encoding: encoding.map(str::to_string),
+        compression: compression.map(str::to_string),
+    });
+
+    // Finite layers need one entry per tile; infinite layers just have no chunks
+    let tile_count = if infinite {
+        0
+    } else {
+        let dimension = |name| {
+            xml_attribute(layer, name)
+                .and_then(|value| value.parse::<usize>().ok())
+                .unwrap_or(0)
+        };
+        dimension("width") * dimension("height")
+    };
+    Some(vec!["0"; tile_count].join(","))
+}
+
+/// Record missing images referenced by a tileset.
+fn collect_missing_tileset_images(
+    tileset: &tiled::Tileset,
+    files: &EmbeddedResourceReader,
+    load_context: &LoadContext,
+    issues: &mut TiledMapLoadIssues,
+) -> Result<(), MapLoaderError> {
+    let sources: Vec<PathBuf> = tileset
+        .image
+        .iter()
+        .map(|image| image.source.clone())
+        .chain(
+            tileset
+                .tiles()
+                .filter_map(|(_, tile)| tile.image.as_ref().map(|image| image.source.clone())),
+        )
+        .collect();
+    for source in sources {
+        let path = resolve_relative_path(load_context, &source.to_string_lossy())?;
+        if !files.exists(Path::new(&path)) {
+            issues.push(TiledMapLoadIssue::MissingImage { path });
+        }
+    }
+    Ok(())
+}
+
+/// A 1x1 [`MISSING_TEXTURE_COLOR`] image, stretched by image layers to their size.
+fn missing_texture_image() -> Image {
+    let color = MISSING_TEXTURE_COLOR.to_srgba().to_u8_array();
+    Image::new_fill(
+        Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &color,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// Collect map features this crate doesn't support yet.
+fn check_map_compatibility(map: &tiled::Map) -> Vec<MapCompatibilityIssue> {
+    let mut issues = Vec::new();
+
+    if map.orientation != tiled::Orientation::Orthogonal {
+        let orientation = map.orientation.to_string();
+        issues.push(if map.infinite() {
+            MapCompatibilityIssue::InfiniteNonOrthogonal { orientation }
+        } else {
+            MapCompatibilityIssue::NonOrthogonal { orientation }
+        });
+    }
+
+    if is_newer_version(map.version(), SUPPORTED_FORMAT_VERSION) {
+        issues.push(MapCompatibilityIssue::NewerFormatVersion {
+            version: map.version().to_string(),
+        });
+    }
+
+    fn collect_group_parallax<'a>(
+        layers: impl Iterator<Item = tiled::Layer<'a>>,
+        issues: &mut Vec<MapCompatibilityIssue>,
+    ) {
+        for layer in layers {
+            if let Some(group) = layer.as_group_layer() {
+                if layer.parallax_x != 1.0 || layer.parallax_y != 1.0 {
+                    issues.push(MapCompatibilityIssue::GroupParallax {
+                        layer_id: layer.id(),
+                        name: layer.name.clone(),
+                    });
+                }
+                collect_group_parallax(group.layers(), issues);
+            }
+        }
+    }
+    collect_group_parallax(map.layers(), &mut issues);
+
+    issues
+}
+
+/// Record a [`TiledMapLoadIssue::InvalidTile`] for each tile outside its tileset.
+///
+/// Tiles of missing tilesets are skipped: they're already reported with their
+/// tileset, whose placeholder can't know its tile count.
+fn collect_invalid_tiles(
+    map: &tiled::Map,
+    tilesets: &HashMap<u32, TilesetReference>,
+    missing_tilesets: &[PathBuf],
+    issues: &mut TiledMapLoadIssues,
+) {
+    let mut check = |layer: &tiled::Layer, x: i32, y: i32, tile: tiled::LayerTile| {
+        let tileset = tile.get_tileset();
+        if !missing_tilesets.contains(&tileset.source) && !tileset_contains_tile(tileset, tile.id())
+        {
+            let first_gid = tilesets
+                .get(&(tile.tileset_index() as u32))
+                .map_or(1, |tileset| tileset.first_gid);
+            issues.push(TiledMapLoadIssue::InvalidTile {
+                layer: layer.name.clone(),
+                x,
+                y,
+                gid: first_gid + tile.id(),
+            });
+        }
+    };
+
+    fn tile_layers<'a>(
+        layers: impl Iterator<Item = tiled::Layer<'a>>,
+        out: &mut Vec<tiled::Layer<'a>>,
+    ) {
+        for layer in layers {
+            if let Some(group) = layer.as_group_layer() {
+                tile_layers(group.layers(), out);
+            } else if layer.as_tile_layer().is_some() {
+                out.push(layer);
+            }
+        }
+    }
+    let mut layers = Vec::new();
+    tile_layers(map.layers(), &mut layers);
+
+    for layer in &layers {
+        if let Some(tile_layer) = layer.as_tile_layer() {
+            for_each_tile(&tile_layer, |x, y, tile| check(layer, x, y, tile));
+        }
+    }
+}
+
+/// Call `f` with the position (in Tiled's tile coordinates) of each non-empty tile
+/// of a finite or infinite tile layer.
+pub fn for_each_tile<'map>(
+    tile_layer: &tiled::TileLayer<'map>,
+    mut f: impl FnMut(i32, i32, tiled::LayerTile<'map>),
+) {
+    match tile_layer {
+        tiled::TileLayer::Finite(finite) => {
+            'rows: for y in 0..finite.height() as i32 {
+                for x in 0..finite.width() as i32 {
+                    // Malformed layer data makes the tiled crate panic, like in core
+                    let tile = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                        finite.get_tile(x, y)
+                    }));
+                    match tile {
+                        Ok(Some(tile)) => f(x, y, tile),
+                        Ok(None) => {}
+                        Err(_) => break 'rows,
+                    }
+                }
+            }
+        }
+        tiled::TileLayer::Infinite(infinite) => {
+            let (width, height) = (
+                tiled::ChunkData::WIDTH as i32,
+                tiled::ChunkData::HEIGHT as i32,
+            );
+            for ((chunk_x, chunk_y), _) in infinite.chunks() {
+                for local_y in 0..height {
+                    for local_x in 0..width {
+                        let (x, y) = (chunk_x * width + local_x, chunk_y * height + local_y);
+                        if let Some(tile) = infinite.get_tile(x, y) {
+                            f(x, y, tile);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Compare dotted version strings numerically (`"1.10" > "1.9"`).
+fn is_newer_version(version: &str, reference: &str) -> bool {
+    let parse =
+        |v: &str| -> Vec<u32> { v.split('.').filter_map(|part| part.parse().ok()).collect() };
+    parse(version) > parse(reference)
+}
+
 /// Calculate map bounds and tilemap size
 ///
 /// For finite maps, uses the map dimensions directly.
@@ -326,33 +1019,146 @@ fn calculate_infinite_map_data(map: &tiled::Map) -> (Vec2, (i32, i32), (i32, i32
 ///
 /// Tiled maps can have image layers nested inside group layers. This function
 /// recursively traverses all layers to find and load all image dependencies.
+///
+/// When `missing_images` is set (lenient mode), images that don't exist are
+/// recorded there and replaced with a placeholder.
 fn collect_image_layers(
     map: &tiled::Map,
+    files: &EmbeddedResourceReader,
     load_context: &mut LoadContext,
     images: &mut HashMap<u32, Handle<Image>>,
+    missing_images: &mut Option<&mut TiledMapLoadIssues>,
 ) -> Result<(), MapLoaderError> {
     fn collect_from_layers<'a>(
         layers: impl Iterator<Item = tiled::Layer<'a>>,
+        files: &EmbeddedResourceReader,
         load_context: &mut LoadContext,
         images: &mut HashMap<u32, Handle<Image>>,
+        missing_images: &mut Option<&mut TiledMapLoadIssues>,
     ) -> Result<(), MapLoaderError> {
         for layer in layers {
             if let Some(image_layer) = layer.as_image_layer() {
                 if let Some(ref image) = image_layer.image {
                     let image_path =
                         resolve_relative_path(load_context, &image.source.to_string_lossy())?;
-                    let handle: Handle<Image> = load_context.load(image_path);
+                    let handle: Handle<Image> = match missing_images {
+                        Some(issues) if !files.exists(Path::new(&image_path)) => {
+                            issues.push(TiledMapLoadIssue::MissingImage { path: image_path });
+                            load_context.add_labeled_asset(
+                                format!("missing_image_{}", layer.id()),
+                                missing_texture_image(),
+                            )
+                        }
+                        _ => load_context.load(in_source(load_context, image_path)),
+                    };
                     images.insert(layer.id(), handle);
                 }
             } else if let Some(group) = layer.as_group_layer() {
                 // Recursively process group layer children
-                collect_from_layers(group.layers(), load_context, images)?;
+                collect_from_layers(group.layers(), files, load_context, images, missing_images)?;
             }
         }
         Ok(())
     }
 
-    collect_from_layers(map.layers(), load_context, images)
+    collect_from_layers(map.layers(), files, load_context, images, missing_images)
+}
+
+/// Add every layer and object of the map as labeled sub-assets.
+///
+/// Each gets an ID label, and a name label unless the name is empty or an earlier
+/// layer or object (in map order) already took it. See [`TiledMapAssetLabel`].
+fn add_sub_assets(
+    map: &tiled::Map,
+    images: &HashMap<u32, Handle<Image>>,
+    load_context: &mut LoadContext,
+) {
+    fn add_layers<'a>(
+        layers: impl Iterator<Item = tiled::Layer<'a>>,
+        images: &HashMap<u32, Handle<Image>>,
+        load_context: &mut LoadContext,
+        taken_names: &mut HashSet<String>,
+    ) -> Vec<Handle<TiledLayerAsset>> {
+        let mut handles = Vec::new();
+        for layer in layers {
+            // Claim the name before the children to keep map order
+            let name_label = TiledMapAssetLabel::Layer(layer.name.clone());
+            let name_label = (!layer.name.is_empty() && taken_names.insert(name_label.to_string()))
+                .then_some(name_label);
+
+            let mut properties = layer.properties.clone();
+            normalize_property_paths(&mut properties, load_context);
+            let mut asset = TiledLayerAsset {
+                id: layer.id(),
+                name: layer.name.clone(),
+                user_type: layer.user_type.clone(),
+                kind: TiledLayerKind::Tiles,
+                visible: layer.visible,
+                opacity: layer.opacity,
+                offset: Vec2::new(layer.offset_x, layer.offset_y),
+                parallax: Vec2::new(layer.parallax_x, layer.parallax_y),
+                properties,
+                tiles: Vec::new(),
+                objects: Vec::new(),
+                image: None,
+                layers: Vec::new(),
+            };
+            match layer.layer_type() {
+                tiled::LayerType::Tiles(tile_layer) => {
+                    for_each_tile(&tile_layer, |x, y, tile| {
+                        asset.tiles.push(TiledLayerTile {
+                            position: IVec2::new(x, y),
+                            tileset_index: tile.tileset_index() as u32,
+                            id: tile.id(),
+                            flip_h: tile.flip_h,
+                            flip_v: tile.flip_v,
+                            flip_d: tile.flip_d,
+                        });
+                    });
+                }
+                tiled::LayerType::Objects(object_layer) => {
+                    asset.kind = TiledLayerKind::Objects;
+                    for object in object_layer.objects() {
+                        let mut properties = object.properties.clone();
+                        normalize_property_paths(&mut properties, load_context);
+                        let object_asset = TiledObjectAsset {
+                            object: (*object).clone(),
+                            layer_id: layer.id(),
+                            properties,
+                        };
+                        let name_label = TiledMapAssetLabel::Object(object.name.clone());
+                        if !object.name.is_empty() && taken_names.insert(name_label.to_string()) {
+                            load_context
+                                .add_labeled_asset(name_label.to_string(), object_asset.clone());
+                        }
+                        asset.objects.push(load_context.add_labeled_asset(
+                            TiledMapAssetLabel::ObjectId(object.id()).to_string(),
+                            object_asset,
+                        ));
+                    }
+                }
+                tiled::LayerType::Image(_) => {
+                    asset.kind = TiledLayerKind::Image;
+                    asset.image = images.get(&layer.id()).cloned();
+                }
+                tiled::LayerType::Group(group) => {
+                    asset.kind = TiledLayerKind::Group;
+                    asset.layers = add_layers(group.layers(), images, load_context, taken_names);
+                }
+            }
+
+            if let Some(name_label) = name_label {
+                load_context.add_labeled_asset(name_label.to_string(), asset.clone());
+            }
+            handles.push(
+                load_context
+                    .add_labeled_asset(TiledMapAssetLabel::LayerId(layer.id()).to_string(), asset),
+            );
+        }
+        handles
+    }
+
+    add_layers(map.layers(), images, load_context, &mut HashSet::new());
 }
 
 /// Recursively collect layer properties from all layers including nested groups.
@@ -441,7 +1247,7 @@ fn resolve_relative_path(
     // If path starts with "assets/", strip it and normalize what remains
     // (tiled crate returns paths like "assets/maps/../art/foo.png")
     if let Some(stripped) = relative_path.strip_prefix("assets/") {
-        let normalized = std::path::Path::new(stripped).normalize();
+        let normalized = Path::new(stripped).normalize();
         return normalized
             .to_str()
             .map(|s| s.replace('\\', "/"))
@@ -470,7 +1276,7 @@ fn resolve_relative_path(
     };
 
     // Normalize to resolve .. and . components
-    let normalized = std::path::Path::new(&full_path).normalize();
+    let normalized = Path::new(&full_path).normalize();
 
     // Convert to Bevy asset path (forward slashes)
     normalized
@@ -516,3 +1322,526 @@ fn normalize_property_value(value: &mut tiled::PropertyValue, load_context: &Loa
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use bevy::asset::LoadState;
+
+    use super::*;
+    use crate::embedded;
+
+    #[test]
+    fn test_external_tileset_sources() {
+        let map = br#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="2" height="2" tilewidth="16" tileheight="16">
+ <tileset firstgid="1" source="../tilesets/terrain.tsx"/>
+ <!-- <tileset firstgid="5" source="commented.tsx"/> -->
+ <tileset firstgid="9" name="embedded" tilewidth="16" tileheight="16" tilecount="4" columns="2">
+  <image source="embedded.png" width="32" height="32"/>
+ </tileset>
+ <tileset
+   firstgid='13'
+   source = 'single_quoted.tsx' />
+ <layer id="1" name="Ground" width="2" height="2">
+  <data encoding="csv">1,2,3,4</data>
+ </layer>
+ <tileset firstgid="17" source="after_layers.tsx"/>
+</map>"#;
+
+        assert_eq!(
+            MapHeader::read(map)
+                .external_tileset_sources()
+                .collect::<Vec<_>>(),
+            ["../tilesets/terrain.tsx", "single_quoted.tsx"]
+        );
+    }
+
+    /// Records the tilesets it's asked to load, without loading them.
+    #[derive(TypePath)]
+    struct RecordingTilesetLoader(Arc<Mutex<Vec<String>>>);
+
+    impl AssetLoader for RecordingTilesetLoader {
+        type Asset = TiledTilesetAsset;
+        type Settings = TiledTilesetLoaderSettings;
+        type Error = std::io::Error;
+
+        async fn load(
+            &self,
+            _reader: &mut dyn Reader,
+            _settings: &Self::Settings,
+            load_context: &mut LoadContext<'_>,
+        ) -> Result<Self::Asset, Self::Error> {
+            self.0.lock().unwrap().push(
+                load_context
+                    .asset_path()
+                    .path()
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            Err(std::io::ErrorKind::Unsupported.into())
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["tsx"]
+        }
+    }
+
+    #[test]
+    fn test_external_tilesets_requested_before_parse() {
+        // The tile data is invalid, so the map fails in the tiled crate's parse:
+        // the tileset can only have been requested before it
+        const MAP: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="1" height="1" tilewidth="16" tileheight="16">
+ <tileset firstgid="1" source='../tilesets/preload.tsx'/>
+ <layer id="1" name="Ground" width="1" height="1">
+  <data encoding="csv">not a tile</data>
+ </layer>
+</map>"#;
+        const TILESET: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" name="preload" tilewidth="16" tileheight="16" tilecount="1" columns="1">
+ <image source="preload.png" width="16" height="16"/>
+</tileset>"#;
+
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<TiledMapAsset>()
+            .init_asset::<TiledTilesetAsset>();
+        embedded::embed(&mut app, "preload/maps/preload.tmx", "preload.tmx", MAP);
+        embedded::embed(
+            &mut app,
+            "preload/tilesets/preload.tsx",
+            "preload.tsx",
+            TILESET,
+        );
+        let embedded = app.world().resource::<EmbeddedTiledFiles>().clone();
+        app.register_asset_loader(TiledMapAssetLoader {
+            embedded,
+            ..default()
+        })
+        .register_asset_loader(RecordingTilesetLoader(requested.clone()));
+
+        let map: Handle<TiledMapAsset> = app
+            .world()
+            .resource::<AssetServer>()
+            .load("embedded://preload/maps/preload.tmx");
+        for _ in 0..1000 {
+            let failed = app
+                .world()
+                .resource::<AssetServer>()
+                .get_load_state(&map)
+                .is_some_and(|state| matches!(state, LoadState::Failed(_)));
+            if failed && !requested.lock().unwrap().is_empty() {
+                break;
+            }
+            app.update();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        assert!(matches!(
+            app.world().resource::<AssetServer>().get_load_state(&map),
+            Some(LoadState::Failed(_))
+        ));
+        assert_eq!(*requested.lock().unwrap(), ["preload/tilesets/preload.tsx"]);
+    }
+
+    /// An app with the asset plugin and an embedded map.
+    fn embedded_app(path: &str, bytes: &'static [u8]) -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            crate::TiledmapAssetsPlugin,
+        ))
+        .init_asset::<Image>();
+        let file_name = Path::new(path).file_name().unwrap().to_str().unwrap();
+        embedded::embed(&mut app, path, file_name, bytes);
+        app
+    }
+
+    /// Load an embedded map and wait until it's loaded or failed.
+    fn load_embedded(
+        app: &mut App,
+        path: &str,
+        settings: impl Fn(&mut TiledMapLoaderSettings) + Send + Sync + 'static,
+    ) -> Handle<TiledMapAsset> {
+        let map: Handle<TiledMapAsset> = app
+            .world()
+            .resource::<AssetServer>()
+            .load_with_settings(format!("embedded://{path}"), settings);
+        for _ in 0..1000 {
+            let state = app.world().resource::<AssetServer>().get_load_state(&map);
+            if state.is_some_and(|state| state.is_loaded() || state.is_failed()) {
+                break;
+            }
+            app.update();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        map
+    }
+
+    /// Load an embedded map leniently.
+    fn load_lenient(path: &str, bytes: &'static [u8]) -> TiledMapAsset {
+        let mut app = embedded_app(path, bytes);
+        let map = load_embedded(&mut app, path, |settings| settings.lenient = true);
+        app.world_mut()
+            .resource_mut::<Assets<TiledMapAsset>>()
+            .remove(&map)
+            .expect("the lenient map should load")
+    }
+
+    const EXTERNAL_TILESET_MAP: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="2" height="1" tilewidth="16" tileheight="16">
+ <tileset firstgid="1" source="../tilesets/terrain.tsx"/>
+ <layer id="1" name="Ground" width="2" height="1">
+  <data encoding="csv">1,2</data>
+ </layer>
+</map>"#;
+    const EXTERNAL_TILESET: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" name="terrain" tilewidth="16" tileheight="16" tilecount="2" columns="0">
+ <tile id="0"/>
+ <tile id="1"/>
+</tileset>"#;
+
+    #[test]
+    fn test_embedded_map_with_embedded_external_tileset() {
+        let mut app = embedded_app("external/maps/level.tmx", EXTERNAL_TILESET_MAP);
+        embedded::embed(
+            &mut app,
+            "external/tilesets/terrain.tsx",
+            "terrain.tsx",
+            EXTERNAL_TILESET,
+        );
+        let map = load_embedded(&mut app, "external/maps/level.tmx", |_| {});
+        for _ in 0..1000 {
+            let server = app.world().resource::<AssetServer>();
+            if server.is_loaded_with_dependencies(&map) {
+                break;
+            }
+            app.update();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let map = app
+            .world()
+            .resource::<Assets<TiledMapAsset>>()
+            .get(&map)
+            .expect("the embedded map should load");
+        let tileset = &map.tilesets[&0];
+        assert_eq!(tileset.first_gid, 1);
+        assert_eq!(
+            tileset.handle.path().map(ToString::to_string).as_deref(),
+            Some("embedded://external/tilesets/terrain.tsx")
+        );
+        let tileset = app
+            .world()
+            .resource::<Assets<TiledTilesetAsset>>()
+            .get(&tileset.handle)
+            .expect("the embedded tileset should load");
+        assert_eq!(tileset.tileset.tilecount, 2);
+    }
+
+    #[test]
+    fn test_embedded_files_belong_to_their_app() {
+        let mut app = embedded_app("isolated/maps/level.tmx", EXTERNAL_TILESET_MAP);
+        embedded::embed(
+            &mut app,
+            "isolated/tilesets/terrain.tsx",
+            "terrain.tsx",
+            EXTERNAL_TILESET,
+        );
+        let map = load_embedded(&mut app, "isolated/maps/level.tmx", |_| {});
+        assert!(app.world().resource::<AssetServer>().is_loaded(&map));
+
+        // Another app only embeds the map, so its tileset can't be found
+        let mut other = embedded_app("isolated/maps/level.tmx", EXTERNAL_TILESET_MAP);
+        let map = load_embedded(&mut other, "isolated/maps/level.tmx", |_| {});
+        assert!(matches!(
+            other.world().resource::<AssetServer>().get_load_state(&map),
+            Some(LoadState::Failed(_))
+        ));
+    }
+
+    const SUB_ASSETS_MAP: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="1" height="1" tilewidth="16" tileheight="16">
+ <layer id="1" name="Ground" width="1" height="1">
+  <data encoding="csv">0</data>
+ </layer>
+ <group id="2" name="Group">
+  <layer id="3" name="Ground" width="1" height="1">
+   <data encoding="csv">0</data>
+  </layer>
+  <objectgroup id="4" name="Objects">
+   <object id="1" name="Boss" x="0" y="0"/>
+   <object id="2" name="Boss" x="16" y="0"/>
+   <object id="3" name="Group" x="32" y="0"/>
+  </objectgroup>
+ </group>
+ <layer id="5" name="" width="1" height="1">
+  <data encoding="csv">0</data>
+ </layer>
+</map>"#;
+
+    /// Load a labeled sub-asset of an embedded map, or `None` if the map has no
+    /// such label.
+    ///
+    /// Sub-assets only live as long as their handles, so each test loads the ones
+    /// it inspects by label, like apps do.
+    fn load_sub_asset<A: Asset + Clone>(
+        app: &mut App,
+        path: &str,
+        label: TiledMapAssetLabel,
+    ) -> Option<(Handle<A>, A)> {
+        let handle: Handle<A> = app
+            .world()
+            .resource::<AssetServer>()
+            .load(label.from_asset(format!("embedded://{path}")));
+        for _ in 0..1000 {
+            let state = app
+                .world()
+                .resource::<AssetServer>()
+                .get_load_state(&handle);
+            if state.is_some_and(|state| state.is_loaded() || state.is_failed()) {
+                break;
+            }
+            app.update();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        let asset = app.world().resource::<Assets<A>>().get(&handle)?.clone();
+        Some((handle, asset))
+    }
+
+    #[test]
+    fn test_duplicate_names_label_the_first_in_map_order() {
+        const PATH: &str = "sub_assets/names.tmx";
+        let mut app = embedded_app(PATH, SUB_ASSETS_MAP);
+        let mut layer = |label| {
+            load_sub_asset::<TiledLayerAsset>(&mut app, PATH, label).map(|(_, layer)| layer)
+        };
+
+        // The top-level Ground layer comes before the one in the group
+        assert_eq!(
+            layer(TiledMapAssetLabel::Layer("Ground".into()))
+                .unwrap()
+                .id,
+            1
+        );
+        assert_eq!(
+            layer(TiledMapAssetLabel::LayerId(3)).unwrap().name,
+            "Ground"
+        );
+
+        assert_eq!(
+            layer(TiledMapAssetLabel::Layer("Group".into())).unwrap().id,
+            2
+        );
+
+        // Unnamed layers only get an ID label
+        assert!(
+            layer(TiledMapAssetLabel::LayerId(5))
+                .unwrap()
+                .name
+                .is_empty()
+        );
+        assert!(layer(TiledMapAssetLabel::Layer(String::new())).is_none());
+
+        let mut object = |label| {
+            load_sub_asset::<TiledObjectAsset>(&mut app, PATH, label).map(|(_, object)| object)
+        };
+        let boss = object(TiledMapAssetLabel::Object("Boss".into())).unwrap();
+        assert_eq!((boss.object.id(), boss.layer_id), (1, 4));
+        assert_eq!(
+            object(TiledMapAssetLabel::ObjectId(2)).unwrap().object.name,
+            "Boss"
+        );
+        // Layer and object names don't collide
+        assert_eq!(
+            object(TiledMapAssetLabel::Object("Group".into()))
+                .unwrap()
+                .object
+                .id(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_group_layer_sub_assets_hold_their_children() {
+        const PATH: &str = "sub_assets/groups.tmx";
+        let mut app = embedded_app(PATH, SUB_ASSETS_MAP);
+
+        let (_handle, group) =
+            load_sub_asset::<TiledLayerAsset>(&mut app, PATH, TiledMapAssetLabel::LayerId(2))
+                .unwrap();
+        assert_eq!(group.kind, TiledLayerKind::Group);
+
+        let layers = app.world().resource::<Assets<TiledLayerAsset>>();
+        let children: Vec<_> = group
+            .layers
+            .iter()
+            .map(|handle| layers.get(handle).map(|layer| (layer.id, layer.kind)))
+            .collect();
+        assert_eq!(
+            children,
+            [
+                Some((3, TiledLayerKind::Tiles)),
+                Some((4, TiledLayerKind::Objects))
+            ]
+        );
+
+        let objects = app.world().resource::<Assets<TiledObjectAsset>>();
+        let object_ids: Vec<_> = layers
+            .get(&group.layers[1])
+            .unwrap()
+            .objects
+            .iter()
+            .map(|handle| objects.get(handle).map(|object| object.object.id()))
+            .collect();
+        assert_eq!(object_ids, [Some(1), Some(2), Some(3)]);
+    }
+
+    #[test]
+    fn test_skip_sub_assets() {
+        /// Number of layer sub-assets added, even if dropped right away for lack of handles
+        #[derive(Resource, Default)]
+        struct AddedLayers(usize);
+
+        fn count_added_layers(
+            mut events: MessageReader<AssetEvent<TiledLayerAsset>>,
+            mut added: ResMut<AddedLayers>,
+        ) {
+            added.0 += events
+                .read()
+                .filter(|event| matches!(event, AssetEvent::Added { .. }))
+                .count();
+        }
+
+        // 5 layers by ID, and Ground, Group and Objects by name
+        for (path, skip_sub_assets, expected) in [
+            ("sub_assets/kept.tmx", false, 8),
+            ("sub_assets/skipped.tmx", true, 0),
+        ] {
+            let mut app = embedded_app(path, SUB_ASSETS_MAP);
+            app.init_resource::<AddedLayers>()
+                .add_systems(Update, count_added_layers);
+            let map = load_embedded(&mut app, path, move |settings| {
+                settings.skip_sub_assets = skip_sub_assets;
+            });
+            app.update();
+            assert!(
+                app.world()
+                    .resource::<Assets<TiledMapAsset>>()
+                    .contains(&map)
+            );
+            assert_eq!(app.world().resource::<AddedLayers>().0, expected, "{path}");
+        }
+    }
+
+    #[test]
+    fn test_lenient_missing_tileset() {
+        const MAP: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="2" height="1" tilewidth="16" tileheight="16">
+ <tileset firstgid="1" source="../tilesets/missing.tsx"/>
+ <layer id="1" name="Ground" width="2" height="1">
+  <data encoding="csv">1,2</data>
+ </layer>
+</map>"#;
+
+        let map = load_lenient("lenient_tileset/maps/map.tmx", MAP);
+
+        assert_eq!(
+            *map.load_issues,
+            [TiledMapLoadIssue::MissingTileset {
+                path: "lenient_tileset/tilesets/missing.tsx".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_lenient_missing_image() {
+        const MAP: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="1" height="1" tilewidth="16" tileheight="16">
+ <tileset firstgid="1" name="terrain" tilewidth="16" tileheight="16" tilecount="1" columns="1">
+  <image source="../images/missing.png" width="16" height="16"/>
+ </tileset>
+ <layer id="1" name="Ground" width="1" height="1">
+  <data encoding="csv">1</data>
+ </layer>
+</map>"#;
+
+        let map = load_lenient("lenient_image/maps/map.tmx", MAP);
+
+        assert_eq!(
+            *map.load_issues,
+            [TiledMapLoadIssue::MissingImage {
+                path: "lenient_image/images/missing.png".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_lenient_unsupported_encoding() {
+        // XML <tile> elements have no encoding, which the tiled crate can't decode;
+        // the supported layer after it must survive the rewrite
+        const MAP: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="2" height="1" tilewidth="16" tileheight="16">
+ <tileset firstgid="1" name="terrain" tilewidth="16" tileheight="16" tilecount="1" columns="0">
+  <tile id="0"/>
+ </tileset>
+ <!-- <layer name="Commented"><data encoding="base64" compression="lzma"/></layer> -->
+ <layer id="1" name='Legacy' width="2" height="1">
+  <data>
+   <tile gid="1"/>
+   <tile/>
+  </data>
+ </layer>
+ <layer id="2" name="Ground" width="2" height="1">
+  <data encoding="csv">0,1</data>
+ </layer>
+</map>"#;
+
+        let map = load_lenient("lenient_encoding/maps/map.tmx", MAP);
+
+        assert_eq!(
+            *map.load_issues,
+            [TiledMapLoadIssue::UnsupportedEncoding {
+                layer: "Legacy".to_string(),
+                encoding: None,
+                compression: None,
+            }]
+        );
+        let tiles = |index: usize| {
+            let layer = map.map.get_layer(index).unwrap().as_tile_layer().unwrap();
+            [
+                layer.get_tile(0, 0).is_some(),
+                layer.get_tile(1, 0).is_some(),
+            ]
+        };
+        assert_eq!(tiles(0), [false, false]);
+        assert_eq!(tiles(1), [false, true]);
+    }
+
+    #[cfg(not(feature = "zstd"))]
+    #[test]
+    fn test_lenient_zstd_without_feature() {
+        const MAP: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="1" height="1" tilewidth="16" tileheight="16">
+ <layer id="1" name="Ground" width="1" height="1">
+  <data encoding="base64" compression="zstd">KLUv/SAEIQAAAQAAAA==</data>
+ </layer>
+</map>"#;
+
+        let map = load_lenient("lenient_zstd/maps/map.tmx", MAP);
+
+        assert_eq!(
+            *map.load_issues,
+            [TiledMapLoadIssue::UnsupportedEncoding {
+                layer: "Ground".to_string(),
+                encoding: Some("base64".to_string()),
+                compression: Some("zstd".to_string()),
+            }]
+        );
+    }
+}
@@ -1,7 +1,10 @@
+use bevy::asset::{AssetPath, LoadContext};
 use bevy::prelude::*;
 use std::sync::Arc;
 use std::sync::Mutex;
 use tiled::DefaultResourceCache;
+use xml::attribute::OwnedAttribute;
+use xml::reader::XmlEvent;
 
 pub mod map;
 pub mod template;
@@ -21,3 +24,34 @@ pub mod world;
 /// unnecessary file I/O and parsing overhead.
 #[derive(Resource, Clone, Default, Debug)]
 pub struct TiledResourceCache(pub Arc<Mutex<DefaultResourceCache>>);
+
+/// Make an asset-root-relative path refer to the asset source of the asset being loaded.
+///
+/// Keeps the dependencies of maps loaded from another source (e.g.
+/// `embedded://`, see [`crate::embedded`]) in that source.
+pub(crate) fn in_source(load_context: &LoadContext, path: String) -> AssetPath<'static> {
+    AssetPath::from(path).with_source(load_context.asset_path().source().clone_owned())
+}
+
+/// Read the attributes of a document's root element.
+///
+/// Used for the few attributes the tiled crate doesn't expose. Malformed XML
+/// gives no attributes; the tiled crate's parse reports it.
+pub(crate) fn root_attributes(bytes: &[u8]) -> Vec<OwnedAttribute> {
+    for event in xml::EventReader::new(bytes) {
+        match event {
+            Ok(XmlEvent::StartElement { attributes, .. }) => return attributes,
+            Ok(XmlEvent::EndDocument) | Err(_) => break,
+            Ok(_) => {}
+        }
+    }
+    Vec::new()
+}
+
+/// Read an attribute value by name.
+pub(crate) fn xml_attribute<'a>(attributes: &'a [OwnedAttribute], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|attribute| attribute.name.local_name == name)
+        .map(|attribute| attribute.value.as_str())
+}
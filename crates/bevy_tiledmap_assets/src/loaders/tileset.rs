@@ -5,10 +5,13 @@ use bevy::{
     tasks::ConditionalSendFuture,
 };
 use normalize_path::NormalizePath;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use xml::attribute::OwnedAttribute;
 
-use crate::assets::tileset::TiledTilesetAsset;
-use crate::loaders::TiledResourceCache;
+use crate::assets::tileset::{ObjectAlignment, TiledTilesetAsset};
+use crate::embedded::{EmbeddedResourceReader, EmbeddedTiledFiles};
+use crate::loaders::{TiledResourceCache, in_source, root_attributes, xml_attribute};
 
 /// Asset loader for Tiled tilesets (.tsx files)
 ///
@@ -17,6 +20,17 @@ use crate::loaders::TiledResourceCache;
 #[derive(Default)]
 pub struct TiledTilesetAssetLoader {
     pub cache: TiledResourceCache,
+    pub embedded: EmbeddedTiledFiles,
+}
+
+/// Settings for [`TiledTilesetAssetLoader`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TiledTilesetLoaderSettings {
+    /// Skip images that don't exist instead of loading them.
+    ///
+    /// Tiles without an image are drawn as placeholders by the renderer. Set
+    /// automatically for tilesets of maps loaded in lenient mode.
+    pub lenient: bool,
 }
 
 #[derive(Debug, Error)]
@@ -33,80 +47,43 @@ pub enum TilesetLoaderError {
 
 impl AssetLoader for TiledTilesetAssetLoader {
     type Asset = TiledTilesetAsset;
-    type Settings = ();
+    type Settings = TiledTilesetLoaderSettings;
     type Error = TilesetLoaderError;
 
     fn load(
         &self,
-        _reader: &mut dyn Reader,
-        _settings: &Self::Settings,
+        reader: &mut dyn Reader,
+        settings: &Self::Settings,
         load_context: &mut LoadContext,
     ) -> impl ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
         async move {
             // Parse TSX using tiled crate
-            // The tiled loader reads directly from the filesystem
+            // The tiled loader reads embedded files, or directly from the filesystem
             let asset_path = load_context.asset_path().path();
 
             // Construct full filesystem path
             // Bevy loads assets from the "assets" directory by default
             let full_path = std::path::Path::new("assets").join(asset_path);
 
-            // Create loader with default cache and filesystem reader
+            // Create loader with default cache, reading embedded files first
             // TODO: Implement shared cache once we figure out the correct API
-            let mut loader = tiled::Loader::new();
+            let files = self.embedded.reader(load_context);
+            let mut loader = tiled::Loader::with_reader(files.clone());
 
             let tileset = loader.load_tsx_tileset(&full_path)?;
 
-            // 3. Determine if texture atlas or image collection
-            let (atlas_image, tile_images) = if let Some(ref image) = tileset.image {
-                // TEXTURE ATLAS MODE: Single spritesheet
-                let image_path =
-                    resolve_relative_path(load_context, &image.source.to_string_lossy())?;
-                let handle = load_context.load(image_path);
-                (Some(handle), HashMap::default())
-            } else {
-                // IMAGE COLLECTION MODE: Per-tile images
-                let mut tile_images = HashMap::new();
-                for (tile_id, tile) in tileset.tiles() {
-                    if let Some(ref tile_image) = tile.image {
-                        let image_path = resolve_relative_path(
-                            load_context,
-                            &tile_image.source.to_string_lossy(),
-                        )?;
-                        let handle = load_context.load(image_path);
-                        tile_images.insert(tile_id, handle);
-                    }
-                }
-                (None, tile_images)
-            };
-
-            // 4. Extract processed data
-            let tile_size = UVec2::new(tileset.tile_width, tileset.tile_height);
-            let grid_size = calculate_grid_size(&tileset);
-            let spacing = tileset.spacing;
-            let margin = tileset.margin;
-
-            // 5. Extract custom properties
-            let properties = tileset.properties.clone();
-
-            // Extract per-tile properties
-            let tile_properties: HashMap<u32, crate::properties::Properties> = tileset
-                .tiles()
-                .map(|(tile_id, tile)| (tile_id, tile.properties.clone()))
-                .collect();
-
-            // 6. Build asset
-            Ok(TiledTilesetAsset {
+            // The tiled crate doesn't expose objectalignment, so read it from the TSX header
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let object_alignment = parse_object_alignment(&root_attributes(&bytes));
+
+            build_tileset_asset(
                 tileset,
-                atlas_image,
-                tile_images,
-                tile_size,
-                grid_size,
-                spacing,
-                margin,
-                properties,
-                tile_properties,
-            })
+                load_context,
+                object_alignment,
+                settings.lenient,
+                &files,
+            )
         }
     }
 
@@ -115,6 +92,88 @@ impl AssetLoader for TiledTilesetAssetLoader {
     }
 }
 
+/// Build a tileset asset from parsed tileset data, loading its images.
+///
+/// Shared by the `.tsx` loader and by maps with embedded tilesets. With
+/// `lenient`, images that don't exist (among `files` or on disk) are skipped
+/// instead of loaded.
+pub(crate) fn build_tileset_asset(
+    tileset: tiled::Tileset,
+    load_context: &mut LoadContext,
+    object_alignment: ObjectAlignment,
+    lenient: bool,
+    files: &EmbeddedResourceReader,
+) -> Result<TiledTilesetAsset, TilesetLoaderError> {
+    // 1. Determine if texture atlas or image collection
+    let (atlas_image, tile_images) = if let Some(ref image) = tileset.image {
+        // TEXTURE ATLAS MODE: Single spritesheet
+        let image_path = resolve_relative_path(load_context, &image.source.to_string_lossy())?;
+        if lenient && !files.exists(std::path::Path::new(&image_path)) {
+            // No atlas: every tile falls back to a placeholder
+            warn!("Missing tileset image {image_path}, using placeholders");
+            (None, HashMap::default())
+        } else {
+            let handle = load_context.load(in_source(load_context, image_path));
+            (Some(handle), HashMap::default())
+        }
+    } else {
+        // IMAGE COLLECTION MODE: Per-tile images
+        let mut tile_images = HashMap::new();
+        for (tile_id, tile) in tileset.tiles() {
+            if let Some(ref tile_image) = tile.image {
+                let image_path =
+                    resolve_relative_path(load_context, &tile_image.source.to_string_lossy())?;
+                if lenient && !files.exists(std::path::Path::new(&image_path)) {
+                    warn!("Missing tile image {image_path}, using a placeholder");
+                    continue;
+                }
+                let handle = load_context.load(in_source(load_context, image_path));
+                tile_images.insert(tile_id, handle);
+            }
+        }
+        (None, tile_images)
+    };
+
+    // 2. Extract processed data
+    let tile_size = UVec2::new(tileset.tile_width, tileset.tile_height);
+    let grid_size = calculate_grid_size(&tileset);
+    let spacing = tileset.spacing;
+    let margin = tileset.margin;
+    let tile_offset = IVec2::new(tileset.offset_x, tileset.offset_y);
+
+    // 3. Extract custom properties
+    let properties = tileset.properties.clone();
+
+    // Extract per-tile properties
+    let tile_properties: HashMap<u32, crate::properties::Properties> = tileset
+        .tiles()
+        .map(|(tile_id, tile)| (tile_id, tile.properties.clone()))
+        .collect();
+
+    // 4. Build asset
+    Ok(TiledTilesetAsset {
+        tileset,
+        atlas_image,
+        tile_images,
+        tile_size,
+        grid_size,
+        spacing,
+        margin,
+        tile_offset,
+        object_alignment,
+        properties,
+        tile_properties,
+    })
+}
+
+/// Read the `objectalignment` attribute of a `<tileset>` element.
+pub(crate) fn parse_object_alignment(attributes: &[OwnedAttribute]) -> ObjectAlignment {
+    xml_attribute(attributes, "objectalignment").map_or(
+        ObjectAlignment::Unspecified,
+        ObjectAlignment::from_attribute,
+    )
+}
+
 /// Resolve relative path from Tiled file to Bevy asset path
 ///
 /// Tiled uses relative paths like `../path/to/image.png`, but Bevy's asset system
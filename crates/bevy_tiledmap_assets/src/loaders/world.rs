@@ -8,7 +8,8 @@ use normalize_path::NormalizePath;
 use thiserror::Error;
 
 use crate::assets::{map::TiledMapAsset, world::TiledWorldAsset};
-use crate::loaders::TiledResourceCache;
+use crate::embedded::EmbeddedTiledFiles;
+use crate::loaders::{TiledResourceCache, in_source};
 
 /// Asset loader for Tiled worlds (.world files)
 ///
@@ -16,6 +17,7 @@ use crate::loaders::TiledResourceCache;
 #[derive(Default)]
 pub struct TiledWorldAssetLoader {
     pub cache: TiledResourceCache,
+    pub embedded: EmbeddedTiledFiles,
 }
 
 #[derive(Debug, Error)]
@@ -28,6 +30,9 @@ pub enum WorldLoaderError {
 
     #[error("Invalid path: {0}")]
     InvalidPath(String),
+
+    #[error("Invalid world properties: {0}")]
+    Properties(#[from] serde_json::Error),
 }
 
 impl AssetLoader for TiledWorldAssetLoader {
@@ -37,7 +42,7 @@ impl AssetLoader for TiledWorldAssetLoader {
 
     fn load(
         &self,
-        _reader: &mut dyn Reader,
+        reader: &mut dyn Reader,
         _settings: &Self::Settings,
         load_context: &mut LoadContext,
     ) -> impl ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
@@ -51,7 +56,7 @@ impl AssetLoader for TiledWorldAssetLoader {
 
             // Create loader with default cache
             // TODO: Implement shared cache once we figure out the correct API
-            let mut loader = tiled::Loader::new();
+            let mut loader = tiled::Loader::with_reader(self.embedded.reader(load_context));
 
             let world = loader.load_world(&full_path)?;
 
@@ -60,14 +65,36 @@ impl AssetLoader for TiledWorldAssetLoader {
             for map_ref in &world.maps {
                 // Resolve relative path to the map file
                 let map_path = resolve_relative_path(load_context, &map_ref.filename)?;
-                let handle: Handle<TiledMapAsset> = load_context.load(map_path);
+                let handle: Handle<TiledMapAsset> =
+                    load_context.load(in_source(load_context, map_path));
 
                 // Use the map file name as the key
                 maps.insert(map_ref.filename.clone(), handle);
             }
 
-            // 4. Build asset
-            Ok(TiledWorldAsset { world, maps })
+            // 4. Read custom properties from the world file, then its sidecar
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let mut properties = WorldProperties::from_world_json(&bytes)?;
+            let sidecar_path = format!(
+                "{}.properties.json",
+                load_context.asset_path().path().to_string_lossy()
+            )
+            .replace('\\', "/");
+            if let Ok(sidecar) = load_context
+                .read_asset_bytes(in_source(load_context, sidecar_path))
+                .await
+            {
+                properties.merge(WorldProperties::from_sidecar_json(&sidecar)?);
+            }
+
+            // 5. Build asset
+            Ok(TiledWorldAsset {
+                world,
+                maps,
+                properties: properties.world,
+                map_properties: properties.maps,
+            })
         }
     }
 
@@ -76,6 +103,166 @@ impl AssetLoader for TiledWorldAssetLoader {
     }
 }
 
+/// Custom properties of a world and its maps.
+#[derive(Default)]
+struct WorldProperties {
+    world: tiled::Properties,
+    maps: HashMap<String, tiled::Properties>,
+}
+
+impl WorldProperties {
+    /// Read properties from a world file: a top-level `"properties"` array,
+    /// and one in each entry of `"maps"`.
+    fn from_world_json(bytes: &[u8]) -> Result<Self, WorldLoaderError> {
+        let json: serde_json::Value = serde_json::from_slice(bytes)?;
+        let maps = json
+            .get("maps")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|map| {
+                let file_name = map.get("fileName")?.as_str()?;
+                let properties = json_properties(map.get("properties")?);
+                Some((file_name.to_string(), properties))
+            })
+            .collect();
+        Ok(Self {
+            world: json
+                .get("properties")
+                .map(json_properties)
+                .unwrap_or_default(),
+            maps,
+        })
+    }
+
+    /// Read properties from a sidecar file: a top-level `"properties"` array,
+    /// and a `"maps"` object of arrays keyed by map file name.
+    fn from_sidecar_json(bytes: &[u8]) -> Result<Self, WorldLoaderError> {
+        let json: serde_json::Value = serde_json::from_slice(bytes)?;
+        let maps = json
+            .get("maps")
+            .and_then(serde_json::Value::as_object)
+            .into_iter()
+            .flatten()
+            .map(|(file_name, properties)| (file_name.clone(), json_properties(properties)))
+            .collect();
+        Ok(Self {
+            world: json
+                .get("properties")
+                .map(json_properties)
+                .unwrap_or_default(),
+            maps,
+        })
+    }
+
+    /// Add properties from `other`, replacing existing ones with the same name.
+    fn merge(&mut self, other: Self) {
+        self.world.extend(other.world);
+        for (file_name, properties) in other.maps {
+            self.maps.entry(file_name).or_default().extend(properties);
+        }
+    }
+}
+
+/// Convert a JSON array of Tiled properties (`{"name", "type", "value"}`) into
+/// `tiled::Properties`, skipping entries that don't parse.
+///
+/// Without a `"type"`, the type is inferred from the JSON value. Class
+/// properties (`"type": "class"`, with a `"propertytype"`) hold an object of
+/// member values, whose types are inferred.
+fn json_properties(json: &serde_json::Value) -> tiled::Properties {
+    let Some(entries) = json.as_array() else {
+        warn!("World properties must be an array of {{\"name\", \"type\", \"value\"}} objects");
+        return tiled::Properties::default();
+    };
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let name = entry.get("name")?.as_str()?;
+            let value = entry.get("value")?;
+            let property = match entry.get("type").and_then(serde_json::Value::as_str) {
+                Some("bool") => value.as_bool().map(tiled::PropertyValue::BoolValue),
+                Some("int") => value
+                    .as_i64()
+                    .and_then(|int| i32::try_from(int).ok())
+                    .map(tiled::PropertyValue::IntValue),
+                Some("float") => value
+                    .as_f64()
+                    .map(|float| tiled::PropertyValue::FloatValue(float as f32)),
+                Some("color") => value
+                    .as_str()
+                    .and_then(|color| color.parse().ok())
+                    .map(tiled::PropertyValue::ColorValue),
+                Some("file") => value
+                    .as_str()
+                    .map(|file| tiled::PropertyValue::FileValue(file.to_string())),
+                Some("object") => value
+                    .as_u64()
+                    .and_then(|id| u32::try_from(id).ok())
+                    .map(tiled::PropertyValue::ObjectValue),
+                Some("string") => value
+                    .as_str()
+                    .map(|string| tiled::PropertyValue::StringValue(string.to_string())),
+                Some("class") => json_class(
+                    entry
+                        .get("propertytype")
+                        .and_then(serde_json::Value::as_str)
+                        .unwrap_or_default(),
+                    value,
+                ),
+                Some(_) => None,
+                None => json_inferred_value(value),
+            };
+            if property.is_none() {
+                warn!("Skipping world property '{name}': unsupported type or value");
+            }
+            Some((name.to_string(), property?))
+        })
+        .collect()
+}
+
+/// Convert a JSON object of member values into a class property value.
+fn json_class(property_type: &str, json: &serde_json::Value) -> Option<tiled::PropertyValue> {
+    let properties = json
+        .as_object()?
+        .iter()
+        .filter_map(|(name, value)| {
+            let property = json_inferred_value(value);
+            if property.is_none() {
+                warn!("Skipping world property member '{name}': unsupported value");
+            }
+            Some((name.clone(), property?))
+        })
+        .collect();
+    Some(tiled::PropertyValue::ClassValue {
+        property_type: property_type.to_string(),
+        properties,
+    })
+}
+
+/// Infer a property value from a JSON value without a `"type"`.
+///
+/// Whole numbers that fit become ints, other numbers floats, and objects
+/// classes without a type name.
+fn json_inferred_value(value: &serde_json::Value) -> Option<tiled::PropertyValue> {
+    match value {
+        serde_json::Value::Bool(bool) => Some(tiled::PropertyValue::BoolValue(*bool)),
+        serde_json::Value::Number(number) => Some(
+            match number.as_i64().and_then(|int| i32::try_from(int).ok()) {
+                Some(int) => tiled::PropertyValue::IntValue(int),
+                None => {
+                    tiled::PropertyValue::FloatValue(number.as_f64().unwrap_or_default() as f32)
+                }
+            },
+        ),
+        serde_json::Value::String(string) => {
+            Some(tiled::PropertyValue::StringValue(string.clone()))
+        }
+        serde_json::Value::Object(_) => json_class("", value),
+        _ => None,
+    }
+}
+
 /// Resolve relative path from Tiled file to Bevy asset path
 ///
 /// Tiled uses relative paths like `../path/to/map.tmx`, but Bevy's asset system
@@ -143,3 +330,154 @@ fn resolve_relative_path(
             WorldLoaderError::InvalidPath(format!("Invalid UTF-8 in path: {:?}", normalized))
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORLD: &[u8] = br##"{
+        "type": "world",
+        "properties": [
+            { "name": "biome", "type": "string", "value": "desert" },
+            { "name": "gravity", "type": "float", "value": 9 },
+            { "name": "level", "type": "int", "value": 3 },
+            { "name": "haze", "type": "color", "value": "#80ff0000" },
+            { "name": "music", "type": "file", "value": "audio/dunes.ogg" },
+            { "name": "spawn", "type": "object", "value": 12 },
+            { "name": "night", "type": "bool", "value": false },
+            { "name": "weather", "type": "class", "propertytype": "Weather",
+              "value": { "wind": 2.5, "rain": true, "sky": { "clouds": 4 } } }
+        ],
+        "maps": [
+            { "fileName": "oasis.tmx", "x": 0, "y": 0,
+              "properties": [{ "name": "music", "type": "file", "value": "audio/oasis.ogg" }] },
+            { "fileName": "dunes.tmx", "x": 480, "y": 0 }
+        ]
+    }"##;
+
+    #[test]
+    fn test_typed_world_properties() {
+        let properties = WorldProperties::from_world_json(WORLD).unwrap();
+        let world = &properties.world;
+
+        assert_eq!(
+            world["biome"],
+            tiled::PropertyValue::StringValue("desert".into())
+        );
+        // Typed floats accept whole numbers
+        assert_eq!(world["gravity"], tiled::PropertyValue::FloatValue(9.0));
+        assert_eq!(world["level"], tiled::PropertyValue::IntValue(3));
+        assert_eq!(
+            world["haze"],
+            tiled::PropertyValue::ColorValue(tiled::Color {
+                alpha: 0x80,
+                red: 0xff,
+                green: 0,
+                blue: 0,
+            })
+        );
+        assert_eq!(
+            world["music"],
+            tiled::PropertyValue::FileValue("audio/dunes.ogg".into())
+        );
+        assert_eq!(world["spawn"], tiled::PropertyValue::ObjectValue(12));
+        assert_eq!(world["night"], tiled::PropertyValue::BoolValue(false));
+        assert_eq!(
+            properties.maps["oasis.tmx"]["music"],
+            tiled::PropertyValue::FileValue("audio/oasis.ogg".into())
+        );
+        assert!(!properties.maps.contains_key("dunes.tmx"));
+    }
+
+    #[test]
+    fn test_class_world_property() {
+        let properties = WorldProperties::from_world_json(WORLD).unwrap();
+
+        let tiled::PropertyValue::ClassValue {
+            property_type,
+            properties: members,
+        } = &properties.world["weather"]
+        else {
+            panic!("expected a class, got {:?}", properties.world["weather"]);
+        };
+        assert_eq!(property_type, "Weather");
+        assert_eq!(members["wind"], tiled::PropertyValue::FloatValue(2.5));
+        assert_eq!(members["rain"], tiled::PropertyValue::BoolValue(true));
+        assert_eq!(
+            members["sky"],
+            tiled::PropertyValue::ClassValue {
+                property_type: String::new(),
+                properties: [("clouds".to_string(), tiled::PropertyValue::IntValue(4))].into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_inferred_world_properties() {
+        let properties = WorldProperties::from_sidecar_json(
+            br#"{
+                "properties": [
+                    { "name": "biome", "value": "desert" },
+                    { "name": "level", "value": 3 },
+                    { "name": "gravity", "value": 9.5 },
+                    { "name": "huge", "value": 3000000000 },
+                    { "name": "night", "value": true },
+                    { "name": "tags", "value": ["a", "b"] },
+                    { "name": "bad_int", "type": "int", "value": "three" },
+                    { "name": "unknown", "type": "vector", "value": 1 }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let world = &properties.world;
+
+        assert_eq!(
+            world["biome"],
+            tiled::PropertyValue::StringValue("desert".into())
+        );
+        assert_eq!(world["level"], tiled::PropertyValue::IntValue(3));
+        assert_eq!(world["gravity"], tiled::PropertyValue::FloatValue(9.5));
+        // Out of i32 range
+        assert_eq!(world["huge"], tiled::PropertyValue::FloatValue(3.0e9));
+        assert_eq!(world["night"], tiled::PropertyValue::BoolValue(true));
+        // Arrays, mistyped values and unknown types are skipped
+        assert_eq!(world.len(), 5);
+    }
+
+    #[test]
+    fn test_sidecar_overrides_world() {
+        let mut properties = WorldProperties::from_world_json(WORLD).unwrap();
+        properties.merge(
+            WorldProperties::from_sidecar_json(
+                br#"{
+                    "properties": [
+                        { "name": "biome", "type": "string", "value": "tundra" },
+                        { "name": "snow", "type": "bool", "value": true }
+                    ],
+                    "maps": {
+                        "oasis.tmx": [{ "name": "music", "type": "file", "value": "audio/ice.ogg" }],
+                        "dunes.tmx": [{ "name": "storm", "type": "bool", "value": true }]
+                    }
+                }"#,
+            )
+            .unwrap(),
+        );
+
+        let world = &properties.world;
+        assert_eq!(
+            world["biome"],
+            tiled::PropertyValue::StringValue("tundra".into())
+        );
+        assert_eq!(world["snow"], tiled::PropertyValue::BoolValue(true));
+        // Untouched world file properties are kept
+        assert_eq!(world["level"], tiled::PropertyValue::IntValue(3));
+        assert_eq!(
+            properties.maps["oasis.tmx"]["music"],
+            tiled::PropertyValue::FileValue("audio/ice.ogg".into())
+        );
+        assert_eq!(
+            properties.maps["dunes.tmx"]["storm"],
+            tiled::PropertyValue::BoolValue(true)
+        );
+    }
+}
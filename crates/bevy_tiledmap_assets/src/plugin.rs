@@ -1,12 +1,17 @@
+use bevy::diagnostic::{Diagnostic, RegisterDiagnostic};
 use bevy::prelude::*;
 
 use crate::assets::{
-    map::TiledMapAsset, template::TiledTemplateAsset, tileset::TiledTilesetAsset,
-    world::TiledWorldAsset,
+    layer::TiledLayerAsset, map::TiledMapAsset, object::TiledObjectAsset,
+    template::TiledTemplateAsset, tileset::TiledTilesetAsset, world::TiledWorldAsset,
 };
+use crate::embedded::EmbeddedTiledFiles;
 use crate::loaders::{
-    TiledResourceCache, map::TiledMapAssetLoader, template::TiledTemplateAssetLoader,
-    tileset::TiledTilesetAssetLoader, world::TiledWorldAssetLoader,
+    TiledResourceCache,
+    map::{TiledMapAssetLoader, record_map_load_times},
+    template::TiledTemplateAssetLoader,
+    tileset::TiledTilesetAssetLoader,
+    world::TiledWorldAssetLoader,
 };
 
 /// Plugin that registers all Tiled asset types and loaders
@@ -26,9 +31,12 @@ use crate::loaders::{
 ///
 /// # What this plugin does
 ///
-/// - Registers 4 asset types: `TiledMapAsset`, `TiledTilesetAsset`, `TiledTemplateAsset`, `TiledWorldAsset`
+/// - Registers 4 asset types: `TiledMapAsset`, `TiledTilesetAsset`, `TiledTemplateAsset`, `TiledWorldAsset`,
+///   plus the map sub-asset types `TiledLayerAsset` and `TiledObjectAsset`
 /// - Registers 4 asset loaders for `.tmx`, `.tsx`, `.tx`, and `.world` files
 /// - Initializes a shared resource cache to prevent duplicate file parsing
+/// - Shares the app's [`EmbeddedTiledFiles`] with the loaders
+/// - Records map load time diagnostics ([`TiledMapAsset::LOAD_TIME`])
 ///
 /// # What this plugin does NOT do
 ///
@@ -45,27 +53,43 @@ impl Plugin for TiledmapAssetsPlugin {
         // This prevents re-parsing the same .tsx or .tx file multiple times
         let cache = TiledResourceCache::default();
 
-        // Register all 4 asset types
+        // Files embedded before or after this plugin is added are seen by the loaders
+        let embedded = app
+            .world_mut()
+            .get_resource_or_init::<EmbeddedTiledFiles>()
+            .clone();
+
+        // Register all 4 asset types, and the map's sub-asset types
         app.init_asset::<TiledMapAsset>()
             .init_asset::<TiledTilesetAsset>()
             .init_asset::<TiledTemplateAsset>()
-            .init_asset::<TiledWorldAsset>();
+            .init_asset::<TiledWorldAsset>()
+            .init_asset::<TiledLayerAsset>()
+            .init_asset::<TiledObjectAsset>();
 
         // Register all 4 asset loaders with shared cache
         app.register_asset_loader(TiledTilesetAssetLoader {
             cache: cache.clone(),
+            embedded: embedded.clone(),
         })
         .register_asset_loader(TiledTemplateAssetLoader {
             cache: cache.clone(),
         })
         .register_asset_loader(TiledMapAssetLoader {
             cache: cache.clone(),
+            embedded: embedded.clone(),
         })
         .register_asset_loader(TiledWorldAssetLoader {
             cache: cache.clone(),
+            embedded,
         });
 
         // Store cache as resource for potential future use
         app.insert_resource(cache);
+
+        // Map load time diagnostics, e.g. for `LogDiagnosticsPlugin`
+        app.register_diagnostic(Diagnostic::new(TiledMapAsset::LOAD_TIME).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(TiledMapAsset::LOADER_TIME).with_suffix("ms"))
+            .add_systems(PostUpdate, record_map_load_times);
     }
 }
@@ -0,0 +1,188 @@
+//! Static colliders along the outer edge of maps.
+//!
+//! With [`PhysicsConfig::map_boundary`] set, every spawned map gets a static
+//! collider along each enabled side of its rect ([`MapGeometry`]), so bodies
+//! can't leave the playable area even if the map has no border collision:
+//!
+//! ```rust,ignore
+//! // Walls and floor, open to the sky
+//! PhysicsConfig::default().with_map_boundary(MapBoundary::new(32.0).without(MapSide::Top))
+//! ```
+//!
+//! The colliders lie outside the map rect, with the side walls extended over the
+//! corners. They are children of the map entity marked [`MapBoundaryCollider`].
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_tiledmap_core::components::MapGeometry;
+use bevy_tiledmap_core::events::MapSpawned;
+
+use crate::config::PhysicsConfig;
+
+/// A side of a map's rect.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MapSide {
+    /// Left edge (min x)
+    Left,
+    /// Right edge (max x)
+    Right,
+    /// Top edge (max y)
+    Top,
+    /// Bottom edge (min y)
+    Bottom,
+}
+
+impl MapSide {
+    /// All sides, in spawn order.
+    pub const ALL: [MapSide; 4] = [MapSide::Left, MapSide::Right, MapSide::Top, MapSide::Bottom];
+}
+
+/// Boundary colliders generated around maps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapBoundary {
+    /// Thickness of the colliders, extending outwards from the map rect.
+    ///
+    /// Default: `16.0`
+    pub thickness: f32,
+    /// Generate a collider along the left edge.
+    pub left: bool,
+    /// Generate a collider along the right edge.
+    pub right: bool,
+    /// Generate a collider along the top edge.
+    pub top: bool,
+    /// Generate a collider along the bottom edge.
+    pub bottom: bool,
+}
+
+impl Default for MapBoundary {
+    fn default() -> Self {
+        Self::new(16.0)
+    }
+}
+
+impl MapBoundary {
+    /// Colliders of `thickness` on all four sides.
+    pub fn new(thickness: f32) -> Self {
+        Self {
+            thickness,
+            left: true,
+            right: true,
+            top: true,
+            bottom: true,
+        }
+    }
+
+    /// Builder method: Leave a side open.
+    pub fn without(mut self, side: MapSide) -> Self {
+        *self.side_mut(side) = false;
+        self
+    }
+
+    /// Check whether a side gets a collider.
+    pub fn has_side(&self, side: MapSide) -> bool {
+        match side {
+            MapSide::Left => self.left,
+            MapSide::Right => self.right,
+            MapSide::Top => self.top,
+            MapSide::Bottom => self.bottom,
+        }
+    }
+
+    fn side_mut(&mut self, side: MapSide) -> &mut bool {
+        match side {
+            MapSide::Left => &mut self.left,
+            MapSide::Right => &mut self.right,
+            MapSide::Top => &mut self.top,
+            MapSide::Bottom => &mut self.bottom,
+        }
+    }
+
+    /// Get the rect of each enabled side's collider around `bounds`.
+    pub fn side_rects(&self, bounds: Rect) -> Vec<(MapSide, Rect)> {
+        let t = self.thickness;
+        MapSide::ALL
+            .into_iter()
+            .filter(|side| self.has_side(*side))
+            .map(|side| {
+                let rect = match side {
+                    MapSide::Left => Rect::new(
+                        bounds.min.x - t,
+                        bounds.min.y - t,
+                        bounds.min.x,
+                        bounds.max.y + t,
+                    ),
+                    MapSide::Right => Rect::new(
+                        bounds.max.x,
+                        bounds.min.y - t,
+                        bounds.max.x + t,
+                        bounds.max.y + t,
+                    ),
+                    MapSide::Top => {
+                        Rect::new(bounds.min.x, bounds.max.y, bounds.max.x, bounds.max.y + t)
+                    }
+                    MapSide::Bottom => {
+                        Rect::new(bounds.min.x, bounds.min.y - t, bounds.max.x, bounds.min.y)
+                    }
+                };
+                (side, rect)
+            })
+            .collect()
+    }
+}
+
+/// Marker for a map boundary collider, with the side it covers.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct MapBoundaryCollider(pub MapSide);
+
+/// Observer that spawns boundary colliders around spawned maps.
+pub fn on_map_spawned(
+    trigger: On<MapSpawned>,
+    map_query: Query<&MapGeometry>,
+    config: Res<PhysicsConfig>,
+    mut commands: Commands,
+) {
+    let Some(boundary) = &config.map_boundary else {
+        return;
+    };
+    let map_entity = trigger.event().entity;
+    let Ok(geometry) = map_query.get(map_entity) else {
+        return;
+    };
+
+    for (side, rect) in boundary.side_rects(geometry.bounds) {
+        commands.spawn((
+            Name::new(format!("Map Boundary ({side:?})")),
+            MapBoundaryCollider(side),
+            RigidBody::Static,
+            Collider::rectangle(rect.width(), rect.height()),
+            Friction::new(config.default_friction),
+            Restitution::new(config.default_restitution),
+            config.default_collision_layers,
+            Transform::from_translation(rect.center().extend(0.0)),
+            ChildOf(map_entity),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_side_rects() {
+        let bounds = Rect::new(0.0, 0.0, 320.0, 160.0);
+        let rects = MapBoundary::new(10.0)
+            .without(MapSide::Top)
+            .side_rects(bounds);
+
+        assert_eq!(
+            rects,
+            [
+                (MapSide::Left, Rect::new(-10.0, -10.0, 0.0, 170.0)),
+                (MapSide::Right, Rect::new(320.0, -10.0, 330.0, 170.0)),
+                (MapSide::Bottom, Rect::new(0.0, -10.0, 320.0, 0.0)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Runtime collision toggling for layers and objects.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_tiledmap_core::components::TiledLayer;
+
+use crate::config::PhysicsConfig;
+
+/// Disables every collider generated for a layer or object.
+///
+/// Insert on a layer entity to disable its tile colliders and the colliders of
+/// all objects in it, or on a single object entity. Remove it to re-enable them.
+/// Colliders stay in place (only [`ColliderDisabled`] is toggled), so re-enabling
+/// is cheap.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // Open a gate authored as a collision object
+/// commands.entity(gate).insert(TiledCollisionDisabled);
+///
+/// // Close it again
+/// commands.entity(gate).remove::<TiledCollisionDisabled>();
+/// ```
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct TiledCollisionDisabled;
+
+/// Observer that disables colliders when `TiledCollisionDisabled` is added.
+pub fn on_collision_disabled(trigger: On<Add, TiledCollisionDisabled>, mut commands: Commands) {
+    let entity = trigger.event().entity;
+    commands.queue(move |world: &mut World| refresh_collider_state(world, entity));
+}
+
+/// Observer that re-enables colliders when `TiledCollisionDisabled` is removed.
+///
+/// The refresh is queued, so it runs once the component is actually gone.
+pub fn on_collision_enabled(trigger: On<Remove, TiledCollisionDisabled>, mut commands: Commands) {
+    let entity = trigger.event().entity;
+    commands.queue(move |world: &mut World| refresh_collider_state(world, entity));
+}
+
+/// Recompute [`ColliderDisabled`] for every collider under `root` (inclusive).
+///
+/// A collider is disabled if it or any ancestor has [`TiledCollisionDisabled`], or,
+/// with `PhysicsConfig::disable_hidden_layer_colliders`, if any ancestor layer is hidden.
+pub fn refresh_collider_state(world: &mut World, root: Entity) {
+    let mut pending = vec![root];
+    let mut colliders = Vec::new();
+    while let Some(entity) = pending.pop() {
+        let Ok(entity_ref) = world.get_entity(entity) else {
+            continue;
+        };
+        if entity_ref.contains::<Collider>() {
+            colliders.push(entity);
+        }
+        if let Some(children) = entity_ref.get::<Children>() {
+            pending.extend(children.iter());
+        }
+    }
+
+    for entity in colliders {
+        let disabled = is_collision_disabled(world, entity);
+        let mut entity_mut = world.entity_mut(entity);
+        if disabled {
+            entity_mut.insert(ColliderDisabled);
+        } else {
+            entity_mut.remove::<ColliderDisabled>();
+        }
+    }
+}
+
+/// Check if `entity` or one of its ancestors disables collision.
+fn is_collision_disabled(world: &World, entity: Entity) -> bool {
+    let hide_layers = world
+        .get_resource::<PhysicsConfig>()
+        .is_some_and(|config| config.disable_hidden_layer_colliders);
+
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        let Ok(entity_ref) = world.get_entity(entity) else {
+            break;
+        };
+        if entity_ref.contains::<TiledCollisionDisabled>() {
+            return true;
+        }
+        if hide_layers
+            && entity_ref.contains::<TiledLayer>()
+            && entity_ref.get::<Visibility>() == Some(&Visibility::Hidden)
+        {
+            return true;
+        }
+        current = entity_ref.get::<ChildOf>().map(ChildOf::parent);
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_layer_and_object_collision() {
+        let mut app = App::new();
+        app.insert_resource(PhysicsConfig::default());
+        app.add_observer(on_collision_disabled);
+        app.add_observer(on_collision_enabled);
+
+        let world = app.world_mut();
+        let layer = world
+            .spawn((TiledLayer::Objects, Collider::rectangle(1.0, 1.0)))
+            .id();
+        let object = world.spawn((Collider::circle(1.0), ChildOf(layer))).id();
+        let other = world.spawn((Collider::circle(1.0), ChildOf(layer))).id();
+
+        world.entity_mut(layer).insert(TiledCollisionDisabled);
+        world.flush();
+        assert!(world.entity(layer).contains::<ColliderDisabled>());
+        assert!(world.entity(object).contains::<ColliderDisabled>());
+
+        world.entity_mut(object).insert(TiledCollisionDisabled);
+        world.entity_mut(layer).remove::<TiledCollisionDisabled>();
+        world.flush();
+        assert!(!world.entity(layer).contains::<ColliderDisabled>());
+        assert!(!world.entity(other).contains::<ColliderDisabled>());
+        // Still disabled by its own flag
+        assert!(world.entity(object).contains::<ColliderDisabled>());
+
+        world.entity_mut(object).remove::<TiledCollisionDisabled>();
+        world.flush();
+        assert!(!world.entity(object).contains::<ColliderDisabled>());
+    }
+}
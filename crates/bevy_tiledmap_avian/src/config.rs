@@ -2,6 +2,12 @@
 
 use avian2d::prelude::*;
 use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+pub use crate::boundary::{MapBoundary, MapSide};
+pub use bevy_tiledmap_core::physics::{
+    LayerCollision, ShapeSimplification, TileColliderSource, TileColliderStrategy, TileMaterialKeys,
+};
 
 /// Global physics configuration resource.
 ///
@@ -146,6 +152,86 @@ pub struct PhysicsConfig {
     ///
     /// Default: [`TileColliderStrategy::CompoundMerged`]
     pub tile_collider_strategy: TileColliderStrategy,
+
+    /// Which tiles get colliders, and whether from their collision shapes or as
+    /// full-tile rectangles.
+    ///
+    /// Default: [`TileColliderSource::CollisionShapes`]
+    pub tile_collider_source: TileColliderSource,
+
+    /// Disable the colliders of layers hidden at runtime.
+    ///
+    /// When enabled, hiding a layer with `SetLayerVisibility` adds [`ColliderDisabled`]
+    /// to every collider in that layer (tile colliders and object colliders), and
+    /// showing it again removes it.
+    ///
+    /// Default: `false`
+    pub disable_hidden_layer_colliders: bool,
+
+    /// Write [`TiledCollision`](crate::events::TiledCollision) messages for
+    /// collisions between colliders generated from Tiled content.
+    ///
+    /// When enabled, these colliders get Avian's [`CollisionEventsEnabled`].
+    ///
+    /// Default: `false`
+    pub collision_events: bool,
+
+    /// Friction for slope tile colliders (see [`Slope`](crate::surfaces::Slope)).
+    ///
+    /// `None` leaves slopes with Avian's default friction, like other tile colliders.
+    ///
+    /// Default: `None`
+    pub slope_friction: Option<f32>,
+
+    /// Corner rounding radius for slope tile colliders, in pixels.
+    ///
+    /// Rounding smooths the seam where a slope meets flat ground, so characters
+    /// don't catch on it. `0.0` disables rounding.
+    ///
+    /// Default: `0.0`
+    pub slope_rounding: f32,
+
+    /// Simplification of custom tile collision shapes (vertex welding,
+    /// Douglas-Peucker simplification, duplicate polyline edge removal).
+    ///
+    /// Reduces physics cost on dense maps with hand-drawn collision shapes.
+    /// `None` builds tile colliders from the shapes as drawn. See
+    /// [`crate::simplify`].
+    ///
+    /// Default: `None`
+    pub shape_simplification: Option<ShapeSimplification>,
+
+    /// Tile collider behavior of layers by Tiled layer class.
+    ///
+    /// Layers without a class, or with a class missing here, get
+    /// [`LayerCollision::TileShapes`].
+    ///
+    /// Default: `"Solid"` → [`LayerCollision::Solid`], `"Decoration"` and
+    /// `"Water"` → [`LayerCollision::Disabled`]
+    pub layer_class_collision: HashMap<String, LayerCollision>,
+
+    /// Names of the tile properties that set the friction, restitution and
+    /// density of tile colliders.
+    ///
+    /// Tiles with different values get separate colliders (one compound per
+    /// material with [`TileColliderStrategy::CompoundMerged`]); values a tile
+    /// doesn't set fall back to `default_friction`, `default_restitution` and
+    /// `default_density`.
+    ///
+    /// Default: `"friction"`, `"restitution"`, `"density"`
+    pub tile_material_keys: TileMaterialKeys,
+
+    /// Tiled classes of objects and tile layers that become fluid volumes
+    /// (see [`crate::water`]).
+    ///
+    /// Default: `"Water"`
+    pub fluid_classes: HashSet<String>,
+
+    /// Static colliders along the outer edge of every spawned map (see
+    /// [`crate::boundary`]).
+    ///
+    /// Default: `None`
+    pub map_boundary: Option<MapBoundary>,
 }
 
 impl Default for PhysicsConfig {
@@ -160,6 +246,20 @@ impl Default for PhysicsConfig {
             collision_layers_fn: default_collision_layers_fn,
             enable_tile_colliders: true,
             tile_collider_strategy: TileColliderStrategy::CompoundMerged,
+            tile_collider_source: TileColliderSource::CollisionShapes,
+            disable_hidden_layer_colliders: false,
+            collision_events: false,
+            slope_friction: None,
+            slope_rounding: 0.0,
+            shape_simplification: None,
+            layer_class_collision: HashMap::from([
+                ("Solid".to_string(), LayerCollision::Solid),
+                ("Decoration".to_string(), LayerCollision::Disabled),
+                ("Water".to_string(), LayerCollision::Disabled),
+            ]),
+            tile_material_keys: TileMaterialKeys::default(),
+            fluid_classes: HashSet::from(["Water".to_string()]),
+            map_boundary: None,
         }
     }
 }
@@ -229,59 +329,76 @@ impl PhysicsConfig {
         self.tile_collider_strategy = strategy;
         self
     }
-}
 
-/// Strategy for generating tile colliders from tileset collision shapes.
-///
-/// Different strategies offer trade-offs between performance and flexibility.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum TileColliderStrategy {
-    /// Don't generate tile colliders.
-    ///
-    /// Use this when you only want object colliders, or when you're handling
-    /// tile colliders manually.
-    Disabled,
+    /// Builder method: Set tile collider source.
+    pub fn with_tile_collider_source(mut self, source: TileColliderSource) -> Self {
+        self.tile_collider_source = source;
+        self
+    }
 
-    /// Spawn individual entities for each tile with a collision shape.
-    ///
-    /// **Pros:**
-    /// - Maximum flexibility (each tile can move independently)
-    /// - Tiles can have different physics properties
-    ///
-    /// **Cons:**
-    /// - High entity count
-    /// - Poor performance for large collision layers
-    ///
-    /// **Use case:** Moving platforms, destructible terrain
-    PerTileEntity,
+    /// Builder method: Enable or disable collider toggling for hidden layers.
+    pub fn with_hidden_layer_colliders_disabled(mut self, disable: bool) -> Self {
+        self.disable_hidden_layer_colliders = disable;
+        self
+    }
 
-    /// Create optimized compound collider with rectangle merging (Recommended).
-    ///
-    /// Follows `bevy_ecs_tiled` approach: merges contiguous rectangular tiles into larger shapes.
-    ///
-    /// **Pros:**
-    /// - Excellent performance
-    /// - Drastically reduced collider count (5-100x reduction)
-    ///
-    /// **Cons:**
-    /// - All tiles in layer share one rigid body (can't move individually)
-    ///
-    /// **Use case:** Static terrain, walls, platforms
-    CompoundMerged,
+    /// Builder method: Enable or disable Tiled collision events.
+    pub fn with_collision_events(mut self, enable: bool) -> Self {
+        self.collision_events = enable;
+        self
+    }
 
-    /// Create chunked compound colliders based on Tiled's chunk settings.
-    ///
-    /// Combines chunking with rectangle merging optimization.
-    ///
-    /// **Pros:**
-    /// - Balance of performance and flexibility
-    /// - Enables spatial culling
-    /// - Aligns with Tiled's internal organization
-    ///
-    /// **Cons:**
-    /// - More complex
-    /// - Multiple rigid bodies per layer
-    ///
-    /// **Use case:** Large/infinite maps, streaming levels
-    CompoundChunked,
+    /// Builder method: Set slope tile friction.
+    pub fn with_slope_friction(mut self, friction: f32) -> Self {
+        self.slope_friction = Some(friction);
+        self
+    }
+
+    /// Builder method: Set slope tile corner rounding.
+    pub fn with_slope_rounding(mut self, border_radius: f32) -> Self {
+        self.slope_rounding = border_radius;
+        self
+    }
+
+    /// Builder method: Enable tile collision shape simplification.
+    pub fn with_shape_simplification(mut self, simplification: ShapeSimplification) -> Self {
+        self.shape_simplification = Some(simplification);
+        self
+    }
+
+    /// Builder method: Set the tile collider behavior of layers with a Tiled class.
+    pub fn with_layer_class_collision(
+        mut self,
+        class: impl Into<String>,
+        collision: LayerCollision,
+    ) -> Self {
+        self.layer_class_collision.insert(class.into(), collision);
+        self
+    }
+
+    /// Builder method: Set the tile properties tile materials are read from.
+    pub fn with_tile_material_keys(mut self, keys: TileMaterialKeys) -> Self {
+        self.tile_material_keys = keys;
+        self
+    }
+
+    /// Builder method: Make objects and tile layers with a Tiled class fluid volumes.
+    pub fn with_fluid_class(mut self, class: impl Into<String>) -> Self {
+        self.fluid_classes.insert(class.into());
+        self
+    }
+
+    /// Builder method: Enable boundary colliders around maps.
+    pub fn with_map_boundary(mut self, boundary: MapBoundary) -> Self {
+        self.map_boundary = Some(boundary);
+        self
+    }
+
+    /// Get the tile collider behavior of a layer from its Tiled class.
+    pub fn layer_collision(&self, class: Option<&str>) -> LayerCollision {
+        class
+            .and_then(|class| self.layer_class_collision.get(class))
+            .copied()
+            .unwrap_or_default()
+    }
 }
@@ -0,0 +1,215 @@
+//! Collision events enriched with Tiled metadata.
+//!
+//! With [`PhysicsConfig::collision_events`] enabled, colliders generated from
+//! Tiled objects and tile layers get Avian's [`CollisionEventsEnabled`], and
+//! every [`CollisionStart`] / [`CollisionEnd`] between two of them is also
+//! written as a [`TiledCollision`] message. It carries both colliders' Tiled
+//! ids, classes and properties, so gameplay code doesn't have to look them up.
+//!
+//! Colliders of slope and climbable tiles are reported with their tile layer's
+//! metadata.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn on_collisions(mut collisions: MessageReader<TiledCollision>) {
+//!     for collision in collisions.read() {
+//!         if collision.started() && collision.involves_class("Spikes") {
+//!             // ...
+//!         }
+//!     }
+//! }
+//! ```
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_tiledmap_core::components::{LayerClass, LayerId, ObjectClass, ObjectId};
+use bevy_tiledmap_core::properties::MergedProperties;
+
+use crate::config::PhysicsConfig;
+
+/// Whether two colliders started or stopped touching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TiledCollisionPhase {
+    /// The colliders started touching ([`CollisionStart`]).
+    Started,
+    /// The colliders stopped touching ([`CollisionEnd`]).
+    Ended,
+}
+
+/// Tiled metadata of a collider in a [`TiledCollision`].
+#[derive(Debug, Clone)]
+pub struct TiledColliderInfo {
+    /// The collider entity
+    pub entity: Entity,
+    /// Tiled id of the collider's object (`None` for tile colliders)
+    pub object_id: Option<u32>,
+    /// Tiled id of the collider's tile layer (`None` for object colliders)
+    pub layer_id: Option<u32>,
+    /// Tiled class of the object or tile layer
+    pub class: Option<String>,
+    /// Merged properties of the object (`None` for tile colliders)
+    pub properties: Option<MergedProperties>,
+}
+
+/// Written when two colliders generated from Tiled content start or stop touching.
+///
+/// Requires [`PhysicsConfig::collision_events`]. See the [module docs](self).
+#[derive(Message, Debug, Clone)]
+pub struct TiledCollision {
+    /// Whether the colliders started or stopped touching
+    pub phase: TiledCollisionPhase,
+    /// The first collider
+    pub collider1: TiledColliderInfo,
+    /// The second collider
+    pub collider2: TiledColliderInfo,
+}
+
+impl TiledCollision {
+    /// Check whether the colliders started touching.
+    pub fn started(&self) -> bool {
+        self.phase == TiledCollisionPhase::Started
+    }
+
+    /// Check whether either collider has the Tiled class `class`.
+    pub fn involves_class(&self, class: &str) -> bool {
+        [&self.collider1, &self.collider2]
+            .into_iter()
+            .any(|info| info.class.as_deref() == Some(class))
+    }
+
+    /// Get the collider with the Tiled class `class` and the other collider.
+    pub fn split_by_class(&self, class: &str) -> Option<(&TiledColliderInfo, &TiledColliderInfo)> {
+        if self.collider1.class.as_deref() == Some(class) {
+            Some((&self.collider1, &self.collider2))
+        } else if self.collider2.class.as_deref() == Some(class) {
+            Some((&self.collider2, &self.collider1))
+        } else {
+            None
+        }
+    }
+}
+
+/// Query data describing a Tiled collider.
+type TiledColliderData = (
+    Option<&'static ObjectId>,
+    Option<&'static LayerId>,
+    Option<&'static ObjectClass>,
+    Option<&'static LayerClass>,
+    Option<&'static MergedProperties>,
+);
+
+/// Observer that enables collision events on colliders generated from Tiled content.
+///
+/// Object and tile layer colliders are recognized by their [`ObjectId`] /
+/// [`LayerId`]; slope and climbable tile colliders by their parent layer.
+pub fn on_collider_added(
+    trigger: On<Add, Collider>,
+    config: Res<PhysicsConfig>,
+    tiled_query: Query<(), Or<(With<ObjectId>, With<LayerId>)>>,
+    parent_query: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    if !config.collision_events {
+        return;
+    }
+    let entity = trigger.event().entity;
+    let is_tiled = tiled_query.contains(entity)
+        || parent_query
+            .get(entity)
+            .is_ok_and(|child_of| tiled_query.contains(child_of.parent()));
+    if is_tiled {
+        commands.entity(entity).insert(CollisionEventsEnabled);
+    }
+}
+
+/// System writing a [`TiledCollision`] for each collision between two Tiled colliders.
+pub fn write_tiled_collisions(
+    mut started: MessageReader<CollisionStart>,
+    mut ended: MessageReader<CollisionEnd>,
+    collider_query: Query<TiledColliderData>,
+    parent_query: Query<&ChildOf>,
+    mut collisions: MessageWriter<TiledCollision>,
+) {
+    let info = |entity: Entity| {
+        collider_info(entity, &collider_query).or_else(|| {
+            let parent = parent_query.get(entity).ok()?.parent();
+            let mut info = collider_info(parent, &collider_query)?;
+            info.entity = entity;
+            Some(info)
+        })
+    };
+
+    let pairs = started
+        .read()
+        .map(|event| {
+            (
+                TiledCollisionPhase::Started,
+                event.collider1,
+                event.collider2,
+            )
+        })
+        .chain(
+            ended
+                .read()
+                .map(|event| (TiledCollisionPhase::Ended, event.collider1, event.collider2)),
+        );
+    for (phase, collider1, collider2) in pairs {
+        if let (Some(collider1), Some(collider2)) = (info(collider1), info(collider2)) {
+            collisions.write(TiledCollision {
+                phase,
+                collider1,
+                collider2,
+            });
+        }
+    }
+}
+
+/// Get the Tiled metadata of an object or tile layer collider.
+fn collider_info(entity: Entity, query: &Query<TiledColliderData>) -> Option<TiledColliderInfo> {
+    let (object_id, layer_id, object_class, layer_class, properties) = query.get(entity).ok()?;
+    if object_id.is_none() && layer_id.is_none() {
+        return None;
+    }
+    Some(TiledColliderInfo {
+        entity,
+        object_id: object_id.map(|id| id.0),
+        layer_id: layer_id.map(|id| id.0),
+        class: object_class
+            .map(|class| class.0.clone())
+            .or_else(|| layer_class.map(|class| class.0.clone())),
+        properties: properties.cloned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(class: Option<&str>) -> TiledColliderInfo {
+        TiledColliderInfo {
+            entity: Entity::PLACEHOLDER,
+            object_id: Some(1),
+            layer_id: None,
+            class: class.map(str::to_string),
+            properties: None,
+        }
+    }
+
+    #[test]
+    fn test_split_by_class() {
+        let collision = TiledCollision {
+            phase: TiledCollisionPhase::Started,
+            collider1: info(None),
+            collider2: info(Some("Spikes")),
+        };
+        assert!(collision.started());
+        assert!(collision.involves_class("Spikes"));
+        assert!(!collision.involves_class("Coin"));
+
+        let (spikes, other) = collision.split_by_class("Spikes").unwrap();
+        assert_eq!(spikes.class.as_deref(), Some("Spikes"));
+        assert_eq!(other.class, None);
+        assert!(collision.split_by_class("Coin").is_none());
+    }
+}
@@ -0,0 +1,268 @@
+//! Door/gate convention: objects whose collider can be opened and closed.
+//!
+//! Give an object the `avian::Gate` class in Tiled and it gets a static collider
+//! from its shape. Open or close it at runtime by triggering [`SetGateOpen`] with
+//! the object's name or id; [`GateOpened`] and [`GateClosed`] fire on the gate
+//! entity so animations and sounds can follow.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn open_vault(mut commands: Commands) {
+//!     commands.trigger(SetGateOpen::open("vault_door"));
+//! }
+//!
+//! fn animate_gates(trigger: On<GateOpened>, mut sprites: Query<&mut Sprite>) {
+//!     // play the opening animation on trigger.event().entity
+//! }
+//! ```
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_tiledmap_core::components::{ObjectId, ObjectName, TiledObject, TiledObjectMapOf};
+use bevy_tiledmap_core::events::ObjectSpawned;
+use bevy_tiledmap_macros::TiledClass;
+
+use crate::collision::TiledCollisionDisabled;
+use crate::shapes;
+
+/// A door or gate whose collider can be toggled at runtime.
+///
+/// Attached automatically to objects with the `avian::Gate` class. While open,
+/// the gate's collider is disabled with [`TiledCollisionDisabled`].
+///
+/// `open` reflects the current state; change it with [`SetGateOpen`] rather
+/// than mutating it directly, so the collider and events stay in sync.
+#[derive(Component, Reflect, TiledClass, Debug, Clone, Default)]
+#[reflect(Component)]
+#[tiled(name = "avian::Gate")]
+pub struct Gate {
+    /// Whether the gate is open (no collision).
+    ///
+    /// Default: false
+    #[tiled(default = false)]
+    pub open: bool,
+}
+
+/// Selects gate objects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GateSelector {
+    /// Every gate with this Tiled object name.
+    Name(String),
+    /// The gate with this Tiled object id.
+    Id(u32),
+    /// A specific gate entity.
+    Entity(Entity),
+}
+
+impl From<&str> for GateSelector {
+    fn from(name: &str) -> Self {
+        GateSelector::Name(name.to_string())
+    }
+}
+
+impl From<String> for GateSelector {
+    fn from(name: String) -> Self {
+        GateSelector::Name(name)
+    }
+}
+
+impl From<u32> for GateSelector {
+    fn from(id: u32) -> Self {
+        GateSelector::Id(id)
+    }
+}
+
+impl From<Entity> for GateSelector {
+    fn from(entity: Entity) -> Self {
+        GateSelector::Entity(entity)
+    }
+}
+
+/// Open, close or toggle gates.
+///
+/// Trigger with `commands.trigger(...)`.
+#[derive(Event, Debug, Clone)]
+pub struct SetGateOpen {
+    /// Which gates to change.
+    pub gate: GateSelector,
+    /// Restrict the change to gates in this map (object ids are only unique per map).
+    pub map: Option<Entity>,
+    /// The new state, or `None` to toggle each matching gate.
+    pub open: Option<bool>,
+}
+
+impl SetGateOpen {
+    /// Open the matching gates.
+    pub fn open(gate: impl Into<GateSelector>) -> Self {
+        Self {
+            gate: gate.into(),
+            map: None,
+            open: Some(true),
+        }
+    }
+
+    /// Close the matching gates.
+    pub fn close(gate: impl Into<GateSelector>) -> Self {
+        Self {
+            gate: gate.into(),
+            map: None,
+            open: Some(false),
+        }
+    }
+
+    /// Toggle the matching gates.
+    pub fn toggle(gate: impl Into<GateSelector>) -> Self {
+        Self {
+            gate: gate.into(),
+            map: None,
+            open: None,
+        }
+    }
+
+    /// Builder method: Only change gates in `map`.
+    pub fn in_map(mut self, map: Entity) -> Self {
+        self.map = Some(map);
+        self
+    }
+}
+
+/// Fired on a gate entity when it opens.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct GateOpened {
+    /// The gate entity
+    #[event_target]
+    pub entity: Entity,
+    /// The gate's object ID from Tiled
+    pub object_id: u32,
+}
+
+/// Fired on a gate entity when it closes.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct GateClosed {
+    /// The gate entity
+    #[event_target]
+    pub entity: Entity,
+    /// The gate's object ID from Tiled
+    pub object_id: u32,
+}
+
+/// Observer that gives spawned gate objects a static collider.
+///
+/// Gates that already got a collider from `physics_settings` keep it; either way
+/// the collider starts disabled if the gate is authored as open.
+pub fn on_gate_spawned(
+    trigger: On<ObjectSpawned>,
+    gate_query: Query<(&Gate, &TiledObject, Has<Collider>)>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let Ok((gate, object, has_collider)) = gate_query.get(event.entity) else {
+        return;
+    };
+
+    let mut entity_cmds = commands.entity(event.entity);
+    if !has_collider {
+        let Some(collider) = shapes::object_to_collider(object) else {
+            warn!(
+                "Gate object {} has an unsupported shape, skipping collider",
+                event.object_id
+            );
+            return;
+        };
+        entity_cmds.insert((RigidBody::Static, collider));
+    }
+    if gate.open {
+        entity_cmds.insert(TiledCollisionDisabled);
+    }
+}
+
+/// Observer that applies [`SetGateOpen`] to the matching gates.
+pub fn on_set_gate_open(
+    trigger: On<SetGateOpen>,
+    mut gate_query: Query<(Entity, &mut Gate, &ObjectId, &ObjectName, &TiledObjectMapOf)>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+
+    for (entity, mut gate, id, name, map_of) in &mut gate_query {
+        let selected = match &event.gate {
+            GateSelector::Name(selected) => *selected == name.0,
+            GateSelector::Id(selected) => *selected == id.0,
+            GateSelector::Entity(selected) => *selected == entity,
+        };
+        if !selected || event.map.is_some_and(|map| map != map_of.0) {
+            continue;
+        }
+
+        let open = event.open.unwrap_or(!gate.open);
+        if open == gate.open {
+            continue;
+        }
+        gate.open = open;
+
+        if open {
+            commands.entity(entity).insert(TiledCollisionDisabled);
+            commands.trigger(GateOpened {
+                entity,
+                object_id: id.0,
+            });
+        } else {
+            commands.entity(entity).remove::<TiledCollisionDisabled>();
+            commands.trigger(GateClosed {
+                entity,
+                object_id: id.0,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{on_collision_disabled, on_collision_enabled};
+    use crate::config::PhysicsConfig;
+
+    #[derive(Resource, Default)]
+    struct Opened(u32);
+
+    #[test]
+    fn test_gate_open_close_by_name_and_id() {
+        let mut app = App::new();
+        app.insert_resource(PhysicsConfig::default());
+        app.init_resource::<Opened>();
+        app.add_observer(on_collision_disabled);
+        app.add_observer(on_collision_enabled);
+        app.add_observer(on_set_gate_open);
+        app.add_observer(|_: On<GateOpened>, mut opened: ResMut<Opened>| {
+            opened.0 += 1;
+        });
+
+        let world = app.world_mut();
+        let map = world.spawn_empty().id();
+        let gate = world
+            .spawn((
+                Gate::default(),
+                ObjectId(7),
+                ObjectName("vault".to_string()),
+                TiledObjectMapOf(map),
+                Collider::rectangle(16.0, 32.0),
+            ))
+            .id();
+
+        world.trigger(SetGateOpen::open("vault"));
+        world.flush();
+        assert!(world.get::<Gate>(gate).unwrap().open);
+        assert!(world.entity(gate).contains::<ColliderDisabled>());
+
+        // Already open: no second event
+        world.trigger(SetGateOpen::open(7).in_map(map));
+        world.flush();
+        assert_eq!(world.resource::<Opened>().0, 1);
+
+        world.trigger(SetGateOpen::toggle(gate));
+        world.flush();
+        assert!(!world.get::<Gate>(gate).unwrap().open);
+        assert!(!world.entity(gate).contains::<ColliderDisabled>());
+    }
+}
@@ -8,7 +8,18 @@
 //! - **Tile Colliders**: Generate optimized colliders from tileset collision shapes with rectangle merging
 //! - **Property-Based Configuration**: Configure physics parameters via `PhysicsSettings` `TiledClass`
 //! - **Collision Layers**: User-provided callback for converting string collision groups to Avian's `CollisionLayers`
+//! - **Tile Materials**: `friction`, `restitution` and `density` tile properties, with one compound per material
 //! - **Multiple Strategies**: Choose between `PerTileEntity`, `CompoundMerged`, or `CompoundChunked` for tile colliders
+//! - **Surfaces**: `climbable` tiles/objects become `Climbable` sensors, angled polygons get a `Slope` marker
+//! - **Gates**: Objects classed `avian::Gate` get colliders that open and close via `SetGateOpen`
+//! - **Fluid Volumes**: Objects and tile layers classed `Water` become `FluidVolume` sensors with `FluidEntered`/`FluidExited` events
+//! - **Moving Platforms**: Objects classed `avian::MovingPlatform` follow a referenced polyline as kinematic bodies
+//! - **Map Boundaries**: Optionally wall maps in with static colliders along their edges
+//! - **Collision Toggling**: Disable a layer's or object's colliders at runtime with `TiledCollisionDisabled`
+//! - **Shape Simplification**: Optionally weld and simplify tile collision shapes, merging shared polyline edges
+//! - **Layer Visibility**: Optionally disable colliders of layers hidden at runtime
+//! - **Shared Backend Core**: Built on `bevy_tiledmap_core::physics` through `AvianBackend`, so other engines reuse the same merging and property conventions
+//! - **Collision Events**: Optionally receive `TiledCollision` messages with both colliders' Tiled ids, classes and properties
 //!
 //! # Quick Start
 //!
@@ -54,22 +65,45 @@
 //!     .run();
 //! ```
 
+pub mod boundary;
+pub mod collision;
 pub mod config;
+pub mod events;
+pub mod gate;
 pub mod objects;
+pub mod platform;
 pub mod plugin;
 pub mod properties;
+pub mod scheduling;
 pub mod shapes;
+pub mod simplify;
+pub mod surfaces;
 pub mod tiles;
+pub mod visibility;
+pub mod water;
 
 pub mod prelude {
     //! Common imports for `bevy_tiledmap_avian`.
 
+    pub use crate::boundary::MapBoundaryCollider;
+    pub use crate::collision::TiledCollisionDisabled;
     pub use crate::config::*;
+    pub use crate::events::{TiledColliderInfo, TiledCollision, TiledCollisionPhase};
+    pub use crate::gate::{Gate, GateClosed, GateOpened, GateSelector, SetGateOpen};
+    pub use crate::platform::{MovingPlatform, MovingPlatformPath, PlatformLoopMode};
     pub use crate::plugin::TiledmapAvianPlugin;
     pub use crate::properties::*;
+    pub use crate::scheduling::TiledPhysicsSystems;
+    pub use crate::shapes::AvianBackend;
+    pub use crate::simplify::ShapeSimplification;
+    pub use crate::surfaces::{Climbable, Slope};
+    pub use crate::tiles::TileColliderChild;
+    pub use crate::water::{FluidEntered, FluidExited, FluidVolume, TileFluidVolume};
 }
 
 // Re-export at crate root for convenience
+pub use collision::TiledCollisionDisabled;
 pub use config::PhysicsConfig;
 pub use plugin::TiledmapAvianPlugin;
 pub use properties::{BodyType, PhysicsSettings};
+pub use scheduling::TiledPhysicsSystems;
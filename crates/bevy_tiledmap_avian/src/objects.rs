@@ -54,6 +54,9 @@ pub fn on_object_spawned(
         return;
     };
 
+    #[cfg(feature = "trace")]
+    let _span = info_span!("build_object_collider", object = ?event.entity).entered();
+
     // Step 1: Resolve physics_settings and collider based on object type
     let (physics_settings, collider) = match object {
         TiledObject::Tile {
@@ -61,29 +64,19 @@ pub fn on_object_spawned(
             tileset_handle,
             width,
             height,
+            ..
         } => {
-            // For tile objects, merge properties from multiple sources:
-            // 1. Tile properties (from tileset) - base for all objects in the tile
-            // 2. Collision object properties (from tileset) - per-object override
-            // 3. Tile object properties (from map) - instance-level override
+            // Tile properties and collision object properties are already merged
+            // into the event properties by core, in the order set by `PropertyMergeConfig`
             let Some(tileset) = tileset_assets.get(tileset_handle) else {
                 return;
             };
 
-            // Get collision shape and collision object properties
-            let (collider, collision_props) =
-                get_tile_collision_with_properties(tileset, *tile_id, *width, *height);
-
-            // Merge properties: tile props (base) → collision props → object props (override)
-            let merged_props = merge_tile_object_properties(
-                tileset.tile_properties.get(tile_id), // base
-                collision_props,                      // per-collision-object
-                &event.properties,                    // instance override
-            );
+            let collider = get_tile_collider(tileset, *tile_id, *width, *height);
 
             // Resolve physics_settings from merged properties
             let Some(physics_settings) =
-                resolve_physics_settings(&merged_props, &registry, &type_registry)
+                resolve_physics_settings(&event.properties, &registry, &type_registry)
             else {
                 return;
             };
@@ -210,72 +203,15 @@ fn resolve_physics_settings(
     }
 }
 
-/// Get tile collision shape and the collision object's properties.
+/// Get the collider for a tile object from its tileset collision shapes.
 ///
-/// Returns the collider and cloned properties from the first collision object in the tile.
-/// If no collision shapes exist, returns a rectangle fallback and None.
-fn get_tile_collision_with_properties(
+/// Falls back to a rectangle of the object's size if the tile has no collision shapes.
+pub(crate) fn get_tile_collider(
     tileset: &TiledTilesetAsset,
     tile_id: u32,
     width: f32,
     height: f32,
-) -> (Collider, Option<tiled::Properties>) {
-    // Try to get tile collision data
-    let Some(tile) = tileset.tileset.get_tile(tile_id) else {
-        return (Collider::rectangle(width, height), None);
-    };
-
-    let Some(collision) = tile.collision.as_ref() else {
-        return (Collider::rectangle(width, height), None);
-    };
-
-    let objects = collision.object_data();
-    if objects.is_empty() {
-        return (Collider::rectangle(width, height), None);
-    }
-
-    // Clone properties from first collision object (for single-shape tiles)
-    // TODO: For compound shapes, consider merging properties from all objects
-    let first_object_props = Some(objects[0].properties.clone());
-
-    // Get the collider using existing shape logic
-    let collider = shapes::get_tile_collision_shape(tileset, tile_id)
-        .unwrap_or_else(|| Collider::rectangle(width, height));
-
-    (collider, first_object_props)
-}
-
-/// Merge properties from multiple sources for tile objects.
-///
-/// Properties are merged with later sources overriding earlier:
-/// 1. Tile properties (base for all objects in the tile)
-/// 2. Collision object properties (per-object override)
-/// 3. Tile object properties (instance-level override)
-fn merge_tile_object_properties(
-    tile_props: Option<&tiled::Properties>,
-    collision_props: Option<tiled::Properties>,
-    object_props: &tiled::Properties,
-) -> tiled::Properties {
-    let mut merged = tiled::Properties::default();
-
-    // Apply tile properties first (base)
-    if let Some(props) = tile_props {
-        for (key, value) in props.iter() {
-            merged.insert(key.clone(), value.clone());
-        }
-    }
-
-    // Apply collision object properties (override)
-    if let Some(props) = collision_props {
-        for (key, value) in props.iter() {
-            merged.insert(key.clone(), value.clone());
-        }
-    }
-
-    // Apply tile object properties (highest priority)
-    for (key, value) in object_props.iter() {
-        merged.insert(key.clone(), value.clone());
-    }
-
-    merged
+) -> Collider {
+    shapes::get_tile_collision_shape(tileset, tile_id)
+        .unwrap_or_else(|| Collider::rectangle(width, height))
 }
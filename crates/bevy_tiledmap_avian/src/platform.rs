@@ -0,0 +1,272 @@
+//! Moving platforms that follow a polyline path.
+//!
+//! Give a (usually tile) object the `avian::MovingPlatform` class and point its
+//! `path` member at a polyline object in the same map. The platform becomes a
+//! kinematic body and [`move_platforms`] drives it along the polyline's points
+//! at `speed` pixels per second, so bodies standing on it are carried along.
+//!
+//! The object's center follows the path, starting by moving to the first point.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::transform::helper::TransformHelper;
+use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+use bevy_tiledmap_core::components::{ObjectId, TiledObject, TiledObjectMapOf, TiledObjectRef};
+use bevy_tiledmap_macros::TiledClass;
+
+use crate::objects::get_tile_collider;
+use crate::shapes;
+
+/// A kinematic platform moving along a polyline object.
+#[derive(Component, Reflect, TiledClass, Debug, Clone)]
+#[reflect(Component)]
+#[tiled(name = "avian::MovingPlatform")]
+pub struct MovingPlatform {
+    /// The polyline object to follow.
+    pub path: Option<TiledObjectRef>,
+
+    /// Movement speed in pixels per second.
+    ///
+    /// Default: 50.0
+    #[tiled(default = 50.0)]
+    pub speed: f32,
+
+    /// What to do at the end of the path.
+    ///
+    /// Default: `PingPong`
+    #[tiled(default = PlatformLoopMode::PingPong)]
+    pub loop_mode: PlatformLoopMode,
+}
+
+impl Default for MovingPlatform {
+    fn default() -> Self {
+        Self {
+            path: None,
+            speed: 50.0,
+            loop_mode: PlatformLoopMode::PingPong,
+        }
+    }
+}
+
+/// How a moving platform continues at the end of its path.
+#[derive(Reflect, TiledClass, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[tiled(name = "avian::PlatformLoopMode")]
+pub enum PlatformLoopMode {
+    /// Reverse direction at either end.
+    #[default]
+    PingPong,
+
+    /// Jump back to the first point after the last (use with closed paths).
+    Loop,
+
+    /// Stop at the last point.
+    Once,
+}
+
+/// Runtime state of a moving platform, added once its path is resolved.
+///
+/// Points are in the platform's parent space.
+#[derive(Component, Debug, Clone)]
+pub struct MovingPlatformPath {
+    /// Path points, in the platform's parent space.
+    pub points: Vec<Vec2>,
+    /// Index of the point the platform is moving towards.
+    pub target: usize,
+    /// Whether the platform is moving towards later points (for `PingPong`).
+    pub forward: bool,
+    /// Whether the platform has stopped (for `Once`).
+    pub finished: bool,
+}
+
+impl MovingPlatformPath {
+    /// Create a path state moving towards the first point.
+    pub fn new(points: Vec<Vec2>) -> Self {
+        Self {
+            points,
+            target: 0,
+            forward: true,
+            finished: false,
+        }
+    }
+
+    /// Pick the next target point after reaching the current one.
+    pub fn advance(&mut self, mode: PlatformLoopMode) {
+        let last = self.points.len().saturating_sub(1);
+        if last == 0 {
+            self.finished = true;
+            return;
+        }
+
+        match mode {
+            PlatformLoopMode::PingPong => {
+                if self.forward && self.target == last {
+                    self.forward = false;
+                } else if !self.forward && self.target == 0 {
+                    self.forward = true;
+                }
+                if self.forward {
+                    self.target += 1;
+                } else {
+                    self.target -= 1;
+                }
+            }
+            PlatformLoopMode::Loop => self.target = (self.target + 1) % self.points.len(),
+            PlatformLoopMode::Once => {
+                if self.target == last {
+                    self.finished = true;
+                } else {
+                    self.target += 1;
+                }
+            }
+        }
+    }
+}
+
+/// System that turns newly spawned moving platforms into kinematic bodies.
+///
+/// Resolves the `path` reference to a polyline in the same map, converts its
+/// points to the platform's parent space, and adds a collider if the platform
+/// doesn't have one yet. Platforms whose path can't be resolved are skipped
+/// with a warning.
+pub fn setup_moving_platforms(
+    platform_query: Query<
+        (
+            Entity,
+            &MovingPlatform,
+            &ObjectId,
+            &TiledObject,
+            &TiledObjectMapOf,
+            Option<&ChildOf>,
+            Has<Collider>,
+        ),
+        Without<MovingPlatformPath>,
+    >,
+    path_query: Query<(Entity, &ObjectId, &TiledObject, &TiledObjectMapOf)>,
+    tileset_assets: Res<Assets<TiledTilesetAsset>>,
+    transform_helper: TransformHelper,
+    mut commands: Commands,
+) {
+    for (entity, platform, object_id, object, map_of, parent, has_collider) in &platform_query {
+        let path = platform.path.and_then(|path| {
+            path_query
+                .iter()
+                .find(|(_, id, _, path_map_of)| id.0 == path.0 && path_map_of.0 == map_of.0)
+        });
+        let Some((path_entity, _, TiledObject::Polyline { vertices }, _)) = path else {
+            warn!(
+                "Moving platform {} has no polyline path, skipping",
+                object_id.0
+            );
+            commands.entity(entity).insert(MovingPlatformPath {
+                finished: true,
+                ..MovingPlatformPath::new(Vec::new())
+            });
+            continue;
+        };
+
+        let Ok(path_transform) = transform_helper.compute_global_transform(path_entity) else {
+            continue;
+        };
+        let parent_inverse = parent
+            .and_then(|parent| {
+                transform_helper
+                    .compute_global_transform(parent.parent())
+                    .ok()
+            })
+            .map(|transform| transform.affine().inverse())
+            .unwrap_or_default();
+        let points = vertices
+            .iter()
+            .map(|vertex| {
+                let world = path_transform.transform_point(vertex.extend(0.0));
+                parent_inverse.transform_point3(world).truncate()
+            })
+            .collect();
+
+        let mut entity_cmds = commands.entity(entity);
+        entity_cmds.insert((
+            RigidBody::Kinematic,
+            LinearVelocity::ZERO,
+            MovingPlatformPath::new(points),
+        ));
+
+        if !has_collider {
+            let collider = match object {
+                TiledObject::Tile {
+                    tile_id,
+                    tileset_handle,
+                    width,
+                    height,
+                    ..
+                } => tileset_assets
+                    .get(tileset_handle)
+                    .map(|tileset| get_tile_collider(tileset, *tile_id, *width, *height)),
+                _ => shapes::object_to_collider(object),
+            };
+            if let Some(collider) = collider {
+                entity_cmds.insert(collider);
+            }
+        }
+    }
+}
+
+/// System that moves platforms along their paths by setting their velocity.
+///
+/// Runs in `FixedUpdate`, so movement stays in step with the physics simulation.
+pub fn move_platforms(
+    mut platform_query: Query<(
+        &MovingPlatform,
+        &mut MovingPlatformPath,
+        &Transform,
+        &mut LinearVelocity,
+    )>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+
+    for (platform, mut path, transform, mut velocity) in &mut platform_query {
+        if path.finished {
+            velocity.0 = Vec2::ZERO;
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let to_target = path.points[path.target] - position;
+        let step = platform.speed * delta;
+        if to_target.length() <= step {
+            // Arrive exactly this step, then head for the next point
+            velocity.0 = to_target / delta;
+            path.advance(platform.loop_mode);
+        } else {
+            velocity.0 = to_target.normalize() * platform.speed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn visit(mode: PlatformLoopMode, steps: usize) -> Vec<usize> {
+        let mut path = MovingPlatformPath::new(vec![Vec2::ZERO, Vec2::X, Vec2::Y]);
+        let mut targets = vec![path.target];
+        for _ in 0..steps {
+            path.advance(mode);
+            if path.finished {
+                break;
+            }
+            targets.push(path.target);
+        }
+        targets
+    }
+
+    #[test]
+    fn test_platform_loop_modes() {
+        assert_eq!(visit(PlatformLoopMode::PingPong, 5), vec![0, 1, 2, 1, 0, 1]);
+        assert_eq!(visit(PlatformLoopMode::Loop, 4), vec![0, 1, 2, 0, 1]);
+        assert_eq!(visit(PlatformLoopMode::Once, 5), vec![0, 1, 2]);
+    }
+}
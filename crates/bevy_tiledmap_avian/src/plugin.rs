@@ -1,17 +1,41 @@
 //! Plugin for `Avian2D` physics integration.
 
+use avian2d::prelude::Collider;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
 use bevy::prelude::*;
+use bevy_tiledmap_core::footprint::FootprintAppExt;
+use bevy_tiledmap_core::project::ConventionClasses;
+use bevy_tiledmap_core::TiledSpawnSystems;
 
+use crate::boundary;
+use crate::collision::{self, TiledCollisionDisabled};
 use crate::config::PhysicsConfig;
+use crate::gate;
 use crate::objects;
+use crate::platform;
+use crate::scheduling::{self, TiledPhysicsSystems};
+use crate::surfaces;
 use crate::tiles;
+use crate::visibility;
+use crate::water;
 
 /// Plugin that integrates `Avian2D` physics with `bevy_tiled`.
 ///
 /// This plugin:
 /// - Registers the [`PhysicsConfig`] resource for global configuration
 /// - Adds observers for object collider generation
-/// - Optionally adds observers for tile collider generation (if enabled)
+/// - Optionally adds observers for tile collider generation (if enabled), rebuilt
+///   when tiles change at runtime
+/// - Adds observers for climbable and slope objects ([`surfaces`](crate::surfaces))
+/// - Adds observers for gate objects ([`Gate`](crate::gate::Gate))
+/// - Adds observers for fluid volumes ([`FluidVolume`](crate::water::FluidVolume))
+/// - Adds systems for moving platforms ([`MovingPlatform`](crate::platform::MovingPlatform))
+/// - Adds observers for runtime collision toggling ([`TiledCollisionDisabled`])
+/// - Optionally adds boundary colliders around maps ([`boundary`](crate::boundary))
+/// - Optionally disables colliders of layers hidden at runtime (if enabled)
+/// - Counts colliders in [`map_footprint`](bevy_tiledmap_core::footprint::map_footprint)
+/// - Places freshly spawned colliders right after map spawning, so they are in
+///   position for the next physics step ([`scheduling`](crate::scheduling))
 ///
 /// # Example
 ///
@@ -43,16 +67,51 @@ use crate::tiles;
 ///     ))
 ///     .run();
 /// ```
-#[derive(Default)]
+///
+/// # Collider Scheduling
+///
+/// Spawned colliders are placed in `PreUpdate`, after `bevy_tiledmap_core` spawns
+/// maps and before the physics step in `FixedPostUpdate`. If maps are spawned in
+/// another schedule (e.g. by running core's spawning systems manually), move
+/// collider placement there too:
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_tiledmap_avian::TiledmapAvianPlugin;
+///
+/// App::new()
+///     .add_plugins(TiledmapAvianPlugin::default().with_collider_sync_schedule(FixedPreUpdate))
+///     .run();
+/// ```
 pub struct TiledmapAvianPlugin {
     /// Physics configuration
     pub config: PhysicsConfig,
+
+    /// Schedule placing freshly spawned colliders.
+    ///
+    /// Default: `PreUpdate`
+    pub collider_sync_schedule: InternedScheduleLabel,
+}
+
+impl Default for TiledmapAvianPlugin {
+    fn default() -> Self {
+        Self::new(PhysicsConfig::default())
+    }
 }
 
 impl TiledmapAvianPlugin {
     /// Create a new plugin with custom configuration.
     pub fn new(config: PhysicsConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            collider_sync_schedule: PreUpdate.intern(),
+        }
+    }
+
+    /// Set the schedule placing freshly spawned colliders.
+    pub fn with_collider_sync_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.collider_sync_schedule = schedule.intern();
+        self
     }
 }
 
@@ -61,16 +120,94 @@ impl Plugin for TiledmapAvianPlugin {
         // Insert resources
         app.insert_resource(self.config.clone());
 
+        // Layer and fluid classes don't need to be declared by the Tiled project
+        app.world_mut()
+            .get_resource_or_init::<ConventionClasses>()
+            .extend(
+                self.config
+                    .layer_class_collision
+                    .keys()
+                    .chain(&self.config.fluid_classes)
+                    .cloned(),
+            );
+
         // Register types for reflection
         app.register_type::<crate::properties::PhysicsSettings>();
         app.register_type::<crate::properties::BodyType>();
+        app.register_type::<TiledCollisionDisabled>();
+        app.register_type::<gate::Gate>();
+        app.register_type::<platform::MovingPlatform>();
+        app.register_type::<platform::PlatformLoopMode>();
+        app.register_type::<surfaces::Climbable>();
+        app.register_type::<surfaces::Slope>();
+        app.register_type::<tiles::TileColliderChild>();
+        app.register_type::<water::FluidVolume>();
+        app.register_type::<water::TileFluidVolume>();
+        app.register_type::<boundary::MapBoundaryCollider>();
+        app.register_type::<boundary::MapSide>();
+
+        // Count colliders in map footprints
+        app.register_footprint_collider::<Collider>();
 
         // Add observers for object colliders
         app.add_observer(objects::on_object_spawned);
+        app.add_observer(surfaces::on_surface_object_spawned);
+
+        // Add observers for gate objects
+        app.add_observer(gate::on_gate_spawned);
+        app.add_observer(gate::on_set_gate_open);
+
+        // Add observers for fluid volumes
+        app.add_observer(water::on_fluid_object_spawned);
+        app.add_observer(water::on_fluid_layer_spawned);
+        app.add_observer(water::on_fluid_tiles_modified);
+        app.add_observer(water::on_fluid_collision_start);
+        app.add_observer(water::on_fluid_collision_end);
 
         // Add observers for tile colliders if enabled
         if self.config.enable_tile_colliders {
             app.add_observer(tiles::on_tile_layer_spawned);
+            app.add_observer(tiles::on_tiles_modified);
+        }
+
+        // Add boundary colliders around maps if enabled
+        if self.config.map_boundary.is_some() {
+            app.add_observer(boundary::on_map_spawned);
+        }
+
+        // Add systems for moving platforms
+        app.add_systems(
+            FixedUpdate,
+            (platform::setup_moving_platforms, platform::move_platforms)
+                .chain()
+                .in_set(TiledPhysicsSystems),
+        );
+
+        // Place freshly spawned colliders before the next physics step
+        app.add_systems(
+            self.collider_sync_schedule,
+            scheduling::sync_spawned_collider_transforms
+                .in_set(TiledPhysicsSystems)
+                .after(TiledSpawnSystems),
+        );
+
+        // Add observers for runtime collision toggling
+        app.add_observer(collision::on_collision_disabled);
+        app.add_observer(collision::on_collision_enabled);
+
+        // Write collision events with Tiled metadata if enabled
+        app.add_message::<crate::events::TiledCollision>();
+        if self.config.collision_events {
+            app.add_observer(crate::events::on_collider_added);
+            app.add_systems(
+                PostUpdate,
+                crate::events::write_tiled_collisions.in_set(TiledPhysicsSystems),
+            );
+        }
+
+        // Follow runtime layer visibility changes if enabled
+        if self.config.disable_hidden_layer_colliders {
+            app.add_observer(visibility::on_layer_visibility_changed);
         }
 
         info!("TiledmapAvianPlugin initialized");
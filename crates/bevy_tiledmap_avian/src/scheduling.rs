@@ -0,0 +1,92 @@
+//! Collider spawn scheduling.
+//!
+//! Map colliders are inserted by observers while `bevy_tiledmap_core` spawns a
+//! map in `PreUpdate`. Avian initializes a collider's [`Position`] from its
+//! ancestors' transforms at insertion time, but object colliders are inserted
+//! before they are parented to their layer, and `GlobalTransform` isn't
+//! propagated until `PostUpdate`. Without a fix-up, the physics step running in
+//! between could see freshly spawned terrain in the wrong place for a frame.
+//!
+//! [`sync_spawned_collider_transforms`] runs right after map spawning (in the
+//! schedule set with [`TiledmapAvianPlugin::with_collider_sync_schedule`](crate::TiledmapAvianPlugin::with_collider_sync_schedule))
+//! and places every new collider from the full hierarchy, so a map and its
+//! physics are in place before the next physics step.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::transform::helper::TransformHelper;
+
+/// System set containing the avian plugin's collider systems.
+///
+/// Used in several schedules: spawned collider placement runs in the collider
+/// sync schedule (`PreUpdate` by default, after `TiledSpawnSystems`), moving
+/// platforms in `FixedUpdate`. Colliders themselves are built by observers
+/// during `TiledSpawnSystems`.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TiledPhysicsSystems;
+
+/// Place newly added colliders from their full transform hierarchy.
+///
+/// Updates `GlobalTransform`, `Position` and `Rotation` of every entity whose
+/// [`Collider`] was added since the last run.
+pub fn sync_spawned_collider_transforms(
+    mut collider_query: Query<
+        (
+            Entity,
+            &mut GlobalTransform,
+            Option<&mut Position>,
+            Option<&mut Rotation>,
+        ),
+        Added<Collider>,
+    >,
+    transform_helper: TransformHelper,
+) {
+    for (entity, mut global_transform, position, rotation) in &mut collider_query {
+        let Ok(computed) = transform_helper.compute_global_transform(entity) else {
+            continue;
+        };
+        *global_transform = computed;
+
+        let (_, computed_rotation, translation) = computed.to_scale_rotation_translation();
+        if let Some(mut position) = position {
+            position.0 = translation.truncate();
+        }
+        if let Some(mut rotation) = rotation {
+            *rotation = Rotation::from(computed_rotation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawned_colliders_use_parent_transforms() {
+        let mut app = App::new();
+        app.add_systems(Update, sync_spawned_collider_transforms);
+
+        let layer = app
+            .world_mut()
+            .spawn(Transform::from_xyz(100.0, 50.0, 0.0))
+            .id();
+        let collider = app
+            .world_mut()
+            .spawn((
+                Collider::rectangle(16.0, 16.0),
+                Transform::from_xyz(8.0, 8.0, 0.0),
+                GlobalTransform::default(),
+                Position::default(),
+                Rotation::default(),
+            ))
+            .id();
+        // Parented after the collider was inserted, like objects during map spawning
+        app.world_mut().entity_mut(layer).add_child(collider);
+        app.update();
+
+        let position = app.world().get::<Position>(collider).unwrap();
+        assert_eq!(position.0, Vec2::new(108.0, 58.0));
+        let global = app.world().get::<GlobalTransform>(collider).unwrap();
+        assert_eq!(global.translation(), Vec3::new(108.0, 58.0, 0.0));
+    }
+}
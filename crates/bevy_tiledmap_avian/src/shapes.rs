@@ -1,9 +1,58 @@
-//! Shape conversion utilities for Tiled objects to `Avian2D` colliders.
+//! Shape conversion from Tiled objects and tileset collision shapes to `Avian2D` colliders.
+//!
+//! These functions are what the plugin uses to build map colliders, and can be
+//! used on their own, e.g. to give a runtime-spawned entity the collider of a
+//! tile. All shapes are converted to Bevy's coordinate system (Y-up, centered);
+//! tile shapes are offset from the tile center.
+//!
+//! The same geometry is available without any physics engine through
+//! [`bevy_tiledmap_core::geometry`] ([`object_shape`], [`tile_collision_shapes`]),
+//! for other physics backends. [`AvianBackend`] is this crate's implementation of
+//! [`TiledPhysicsBackend`], the conversion those backends share the rest of
+//! collider generation through.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let shapes = shapes::get_tile_collision_shapes(&tileset, tile_id);
+//! if let Some(collider) = shapes::compound_collider(shapes) {
+//!     commands.spawn((RigidBody::Static, collider, Transform::from_translation(tile_center)));
+//! }
+//! ```
 
 use avian2d::prelude::*;
 use bevy::prelude::*;
 use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
 use bevy_tiledmap_core::components::object::TiledObject;
+use bevy_tiledmap_core::geometry::tile_rectangle_size;
+pub use bevy_tiledmap_core::geometry::{
+    flip_y, object_shape, tile_collision_shapes, tile_has_collision_shape, CollisionShape,
+    PlacedShape,
+};
+use bevy_tiledmap_core::physics::TiledPhysicsBackend;
+
+/// The Avian [`TiledPhysicsBackend`], building [`Collider`]s with the functions
+/// of this module.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AvianBackend;
+
+impl TiledPhysicsBackend for AvianBackend {
+    type Collider = Collider;
+
+    fn collider(&self, shape: &CollisionShape) -> Collider {
+        shape_to_collider(shape)
+    }
+
+    /// Polylines and triangle meshes are split with [`compound_parts`].
+    fn compound(&self, parts: Vec<(Vec2, f32, Collider)>) -> Collider {
+        Collider::compound(
+            parts
+                .into_iter()
+                .flat_map(|(offset, rotation, collider)| compound_parts(offset, rotation, collider))
+                .collect(),
+        )
+    }
+}
 
 /// Convert a `TiledObject` to an `Avian2D` collider.
 ///
@@ -18,57 +67,81 @@ use bevy_tiledmap_core::components::object::TiledObject;
 /// |-------------|----------------|
 /// | Rectangle | `Collider::rectangle(width, height)` |
 /// | Ellipse | `Collider::circle(radius)` (approximation) |
-/// | Polygon | `Collider::convex_hull(vertices)` or `Collider::triangle_mesh()` |
+/// | Polygon | `Collider::convex_hull(vertices)` or `Collider::trimesh()` |
 /// | Polyline | `Collider::polyline(vertices, None)` |
 /// | Point | `Collider::circle(1.0)` (small sensor) |
-/// | Tile | Fallback to rectangle (tileset shapes in Phase 4) |
+/// | Tile | `Collider::rectangle(width, height)` (object bounds) |
 /// | Text | `None` (no collider) |
 pub fn object_to_collider(object: &TiledObject) -> Option<Collider> {
-    match object {
-        TiledObject::Rectangle { width, height } => Some(Collider::rectangle(*width, *height)),
-
-        TiledObject::Ellipse { width, height } => {
-            // Use the maximum dimension as diameter for the circle
-            // This ensures the circle fully contains the ellipse bounds
-            let radius = width.max(*height) / 2.0;
-            Some(Collider::circle(radius))
-        }
-
-        TiledObject::Polygon { vertices } => {
-            // Try convex hull first (more performant)
-            if let Some(collider) = Collider::convex_hull(vertices.clone()) {
-                Some(collider)
-            } else {
-                // Fall back to triangle mesh for concave polygons
-                warn!(
-                    "Failed to create convex hull for polygon, using triangle mesh (less performant)"
-                );
-                Some(polygon_to_trimesh(vertices))
-            }
-        }
-
-        TiledObject::Polyline { vertices } => {
-            // Polylines don't form closed shapes, so we use Avian's polyline collider
-            // The `None` parameter means no joints are rounded
-            Some(Collider::polyline(vertices.clone(), None))
-        }
+    AvianBackend.object_collider(object)
+}
 
-        TiledObject::Point => {
-            // Point objects become small circle sensors (1.0 radius)
-            // Typically used for spawn points, triggers, etc.
-            Some(Collider::circle(1.0))
-        }
+/// Convert an engine-agnostic [`CollisionShape`] to an `Avian2D` collider.
+///
+/// Polygons become convex hulls when they are convex, and triangle meshes
+/// (less performant) otherwise.
+pub fn shape_to_collider(shape: &CollisionShape) -> Collider {
+    match shape {
+        CollisionShape::Rectangle { size } => Collider::rectangle(size.x, size.y),
+        CollisionShape::Circle { radius } => Collider::circle(*radius),
+        CollisionShape::Polygon { vertices } => Collider::convex_hull(vertices.clone())
+            .unwrap_or_else(|| {
+                debug!("Polygon is not convex, using a triangle mesh collider");
+                polygon_to_trimesh(vertices)
+            }),
+        // The `None` parameter means no joints are rounded
+        CollisionShape::Polyline { vertices } => Collider::polyline(vertices.clone(), None),
+    }
+}
 
-        TiledObject::Tile { width, height, .. } => {
-            // Phase 1: Use object bounds as rectangle collider
-            // Phase 4: Extract collision shape from tileset
-            Some(Collider::rectangle(*width, *height))
+/// Combine positioned colliders into one collider.
+///
+/// Returns `None` for an empty list. A single collider without offset or
+/// rotation is returned as-is, since compounds can't be nested in other
+/// compounds. Polylines and triangle meshes are split with [`compound_parts`].
+pub fn compound_collider(colliders: Vec<(Vec2, f32, Collider)>) -> Option<Collider> {
+    match colliders.as_slice() {
+        [] => None,
+        [(offset, rotation, _)] if offset.length_squared() < 0.01 && rotation.abs() < 0.01 => {
+            colliders
+                .into_iter()
+                .next()
+                .map(|(_, _, collider)| collider)
         }
+        _ => Some(AvianBackend.compound(colliders)),
+    }
+}
 
-        TiledObject::Text { .. } => {
-            // Text objects don't have physics colliders
-            None
-        }
+/// Split a positioned collider into shapes that can be part of a compound.
+///
+/// Polylines and triangle meshes are composite shapes, which parry doesn't
+/// allow inside compounds, so they become their segments and triangles.
+/// Other colliders are returned unchanged.
+pub fn compound_parts(
+    offset: Vec2,
+    rotation: f32,
+    collider: Collider,
+) -> Vec<(Vec2, f32, Collider)> {
+    let point = |p: &avian2d::parry::math::Point<f32>| Vec2::new(p.x, p.y);
+    if let Some(polyline) = collider.shape().as_polyline() {
+        polyline
+            .segments()
+            .map(|segment| {
+                let part = Collider::segment(point(&segment.a), point(&segment.b));
+                (offset, rotation, part)
+            })
+            .collect()
+    } else if let Some(trimesh) = collider.shape().as_trimesh() {
+        trimesh
+            .triangles()
+            .map(|triangle| {
+                let part =
+                    Collider::triangle(point(&triangle.a), point(&triangle.b), point(&triangle.c));
+                (offset, rotation, part)
+            })
+            .collect()
+    } else {
+        vec![(offset, rotation, collider)]
     }
 }
 
@@ -79,7 +152,7 @@ pub fn object_to_collider(object: &TiledObject) -> Option<Collider> {
 ///
 /// # Implementation Note
 ///
-/// Currently uses a simple ear clipping triangulation. For complex polygons,
+/// Currently uses a simple fan triangulation. For complex polygons,
 /// consider using a more robust triangulation library like `earcutr` or `lyon`.
 fn polygon_to_trimesh(vertices: &[Vec2]) -> Collider {
     // Simple triangulation: fan from first vertex
@@ -102,7 +175,8 @@ fn polygon_to_trimesh(vertices: &[Vec2]) -> Collider {
 /// Get collision shape from a tileset tile.
 ///
 /// Extracts collision shape data defined in the tileset editor for a specific tile.
-/// If the tile has multiple collision objects, they are combined into a compound collider.
+/// If the tile has multiple collision objects, they are combined into a compound
+/// collider (see [`compound_collider`]).
 ///
 /// # Arguments
 ///
@@ -117,134 +191,14 @@ pub fn get_tile_collision_shape(
     tileset: &TiledTilesetAsset,
     local_tile_id: u32,
 ) -> Option<Collider> {
-    // Get the tile data from the tileset
-    let tile = tileset.tileset.get_tile(local_tile_id)?;
-
-    // Get the collision object group
-    let collision_group = tile.collision.as_ref()?;
-
-    // Tile center (for calculating offsets relative to tile center)
-    // Since we'll be placing colliders at tile centers (via grid_to_world),
-    // collision shape offsets should be relative to tile center, not top-left
-    let tile_center_x = tileset.tile_size.x as f32 / 2.0;
-    let tile_center_y = tileset.tile_size.y as f32 / 2.0;
-
-    // Convert each collision object to a collider
-    let mut colliders: Vec<(Vec2, f32, Collider)> = Vec::new();
-
-    for object in collision_group.object_data() {
-        // Convert tiled::ObjectShape to Collider
-        // Tiled uses TOP-LEFT anchor for objects, Avian uses CENTER
-        // We calculate offsets relative to TILE CENTER (not tile top-left)
-        let rotation = -object.rotation.to_radians();
-
-        let (collider, offset) = match &object.shape {
-            tiled::ObjectShape::Rect { width, height } => {
-                // Rect center in Tiled coords (relative to tile top-left)
-                let shape_center_x = object.x + width / 2.0;
-                let shape_center_y = object.y + height / 2.0;
-
-                // Offset from tile center to shape center
-                // Y is flipped for Bevy's Y-up coordinate system
-                let offset_x = shape_center_x - tile_center_x;
-                let offset_y = -(shape_center_y - tile_center_y);
-
-                (
-                    Collider::rectangle(*width, *height),
-                    Vec2::new(offset_x, offset_y),
-                )
-            }
-
-            tiled::ObjectShape::Ellipse { width, height } => {
-                // Same as rect - anchor is TOP-LEFT of bounding box
-                let radius = width.max(*height) / 2.0;
-                let shape_center_x = object.x + width / 2.0;
-                let shape_center_y = object.y + height / 2.0;
-
-                let offset_x = shape_center_x - tile_center_x;
-                let offset_y = -(shape_center_y - tile_center_y);
-
-                (Collider::circle(radius), Vec2::new(offset_x, offset_y))
-            }
-
-            tiled::ObjectShape::Polygon { points } => {
-                // Polygon vertices are relative to object origin (object.x, object.y)
-                // Offset polygon origin from tile center, then flip Y for Bevy
-                let offset_x = object.x - tile_center_x;
-                let offset_y = -(object.y - tile_center_y);
-
-                // Flip Y for Bevy's Y-up coordinate system
-                let vertices: Vec<Vec2> = points.iter().map(|(x, y)| Vec2::new(*x, -*y)).collect();
-
-                let collider = if let Some(convex) = Collider::convex_hull(vertices.clone()) {
-                    convex
-                } else {
-                    polygon_to_trimesh(&vertices)
-                };
-
-                (collider, Vec2::new(offset_x, offset_y))
-            }
-
-            tiled::ObjectShape::Polyline { points } => {
-                // Same offset logic as polygon
-                let offset_x = object.x - tile_center_x;
-                let offset_y = -(object.y - tile_center_y);
-
-                let vertices: Vec<Vec2> = points.iter().map(|(x, y)| Vec2::new(*x, -*y)).collect();
-
-                (
-                    Collider::polyline(vertices, None),
-                    Vec2::new(offset_x, offset_y),
-                )
-            }
-
-            tiled::ObjectShape::Point(x, y) => {
-                // Point position relative to tile center
-                let offset_x = *x - tile_center_x;
-                let offset_y = -(*y - tile_center_y);
-                colliders.push((
-                    Vec2::new(offset_x, offset_y),
-                    rotation,
-                    Collider::circle(1.0),
-                ));
-                continue;
-            }
-
-            tiled::ObjectShape::Text { .. } => {
-                // Text objects don't have colliders
-                continue;
-            }
-        };
-
-        colliders.push((offset, rotation, collider));
-    }
-
-    // Return the collider(s)
-    match colliders.len() {
-        0 => None,
-        1 => {
-            let (offset, rotation, collider) = colliders.into_iter().next().unwrap();
-
-            // If offset is zero (or nearly zero), return just the collider
-            // This avoids creating a compound which can't be nested in other compounds
-            if offset.length_squared() < 0.01 && rotation.abs() < 0.01 {
-                Some(collider)
-            } else {
-                // Non-zero offset - must use compound
-                Some(Collider::compound(vec![(offset, rotation, collider)]))
-            }
-        }
-        _ => {
-            // Multiple colliders - create a compound
-            Some(Collider::compound(colliders))
-        }
-    }
+    compound_collider(get_tile_collision_shapes(tileset, local_tile_id))
 }
 
 /// Get tile collision shapes as individual colliders with offsets.
 ///
 /// Unlike `get_tile_collision_shape` which may return a compound collider,
 /// this returns the individual shapes that can be added directly to another compound.
+/// Offsets are relative to the tile center.
 ///
 /// # Arguments
 ///
@@ -258,92 +212,16 @@ pub fn get_tile_collision_shapes(
     tileset: &TiledTilesetAsset,
     local_tile_id: u32,
 ) -> Vec<(Vec2, f32, Collider)> {
-    let Some(tile) = tileset.tileset.get_tile(local_tile_id) else {
-        return Vec::new();
-    };
-
-    let Some(collision_group) = tile.collision.as_ref() else {
-        return Vec::new();
-    };
-
-    let tile_center_x = tileset.tile_size.x as f32 / 2.0;
-    let tile_center_y = tileset.tile_size.y as f32 / 2.0;
-
-    let mut colliders = Vec::new();
-
-    for object in collision_group.object_data() {
-        let rotation = -object.rotation.to_radians();
-
-        let (collider, offset) = match &object.shape {
-            tiled::ObjectShape::Rect { width, height } => {
-                let shape_center_x = object.x + width / 2.0;
-                let shape_center_y = object.y + height / 2.0;
-                let offset_x = shape_center_x - tile_center_x;
-                let offset_y = -(shape_center_y - tile_center_y);
-                (
-                    Collider::rectangle(*width, *height),
-                    Vec2::new(offset_x, offset_y),
-                )
-            }
-            tiled::ObjectShape::Ellipse { width, height } => {
-                let radius = width.max(*height) / 2.0;
-                let shape_center_x = object.x + width / 2.0;
-                let shape_center_y = object.y + height / 2.0;
-                let offset_x = shape_center_x - tile_center_x;
-                let offset_y = -(shape_center_y - tile_center_y);
-                (Collider::circle(radius), Vec2::new(offset_x, offset_y))
-            }
-            tiled::ObjectShape::Polygon { points } => {
-                let offset_x = object.x - tile_center_x;
-                let offset_y = -(object.y - tile_center_y);
-                let vertices: Vec<Vec2> = points.iter().map(|(x, y)| Vec2::new(*x, -*y)).collect();
-                let collider = Collider::convex_hull(vertices.clone())
-                    .unwrap_or_else(|| polygon_to_trimesh(&vertices));
-                (collider, Vec2::new(offset_x, offset_y))
-            }
-            tiled::ObjectShape::Polyline { points } => {
-                let offset_x = object.x - tile_center_x;
-                let offset_y = -(object.y - tile_center_y);
-                let vertices: Vec<Vec2> = points.iter().map(|(x, y)| Vec2::new(*x, -*y)).collect();
-                (
-                    Collider::polyline(vertices, None),
-                    Vec2::new(offset_x, offset_y),
-                )
-            }
-            tiled::ObjectShape::Point(x, y) => {
-                let offset_x = *x - tile_center_x;
-                let offset_y = -(*y - tile_center_y);
-                (Collider::circle(1.0), Vec2::new(offset_x, offset_y))
-            }
-            tiled::ObjectShape::Text { .. } => continue,
-        };
-
-        colliders.push((offset, rotation, collider));
-    }
-
-    colliders
-}
-
-/// Check if a tile has collision shapes defined.
-///
-/// This is a faster check than `get_tile_collision_shape` when you only need
-/// to know if a tile has collision data.
-///
-/// # Arguments
-///
-/// * `tileset` - The tileset asset containing the tile
-/// * `local_tile_id` - The local tile ID (0-based, NOT a GID)
-///
-/// # Returns
-///
-/// `true` if the tile has collision shapes, `false` otherwise
-pub fn tile_has_collision_shape(tileset: &TiledTilesetAsset, local_tile_id: u32) -> bool {
-    if let Some(tile) = tileset.tileset.get_tile(local_tile_id) {
-        if let Some(collision) = &tile.collision {
-            return !collision.object_data().is_empty();
-        }
-    }
-    false
+    tile_collision_shapes(tileset, local_tile_id)
+        .into_iter()
+        .map(|placed| {
+            (
+                placed.offset,
+                placed.rotation,
+                shape_to_collider(&placed.shape),
+            )
+        })
+        .collect()
 }
 
 /// Check if a tile's collision shape is a simple rectangle.
@@ -364,32 +242,13 @@ pub fn get_tile_rectangle_collision_size(
     tileset: &TiledTilesetAsset,
     local_tile_id: u32,
 ) -> Option<(f32, f32)> {
-    let tile = tileset.tileset.get_tile(local_tile_id)?;
-    let collision_group = tile.collision.as_ref()?;
-
-    // Only return size if there's exactly one collision object and it's a rectangle
-    let objects = collision_group.object_data();
-    if objects.len() != 1 {
-        return None;
-    }
-
-    let obj = &objects[0];
-
-    // Check that the rect is at the tile origin (within tolerance)
-    // Rects with offsets can't be merged with the simple grid merging algorithm
-    if obj.x.abs() > 0.1 || obj.y.abs() > 0.1 || obj.rotation.abs() > 0.1 {
-        return None;
-    }
-
-    match obj.shape {
-        tiled::ObjectShape::Rect { width, height } => Some((width, height)),
-        _ => None,
-    }
+    tile_rectangle_size(tileset, local_tile_id).map(|size| (size.x, size.y))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bevy_tiledmap_core::components::{TextHorizontalAlign, TextVerticalAlign};
 
     #[test]
     fn test_rectangle_to_collider() {
@@ -445,9 +304,49 @@ mod tests {
         assert!(collider.is_some());
     }
 
+    #[test]
+    fn test_compound_parts() {
+        let polyline = Collider::polyline(vec![Vec2::ZERO, Vec2::X, Vec2::ONE], None);
+        let parts = compound_parts(Vec2::ZERO, 0.0, polyline);
+        assert_eq!(parts.len(), 2);
+        assert!(parts
+            .iter()
+            .all(|(_, _, part)| part.shape().as_segment().is_some()));
+
+        let circle = compound_parts(Vec2::ZERO, 0.0, Collider::circle(4.0));
+        assert_eq!(circle.len(), 1);
+    }
+
+    #[test]
+    fn test_compound_collider() {
+        assert!(compound_collider(Vec::new()).is_none());
+
+        let single = compound_collider(vec![(Vec2::ZERO, 0.0, Collider::circle(4.0))]).unwrap();
+        assert!(single.shape().as_compound().is_none());
+
+        let offset =
+            compound_collider(vec![(Vec2::new(4.0, 0.0), 0.0, Collider::circle(4.0))]).unwrap();
+        assert!(offset.shape().as_compound().is_some());
+    }
+
     #[test]
     fn test_text_no_collider() {
-        let object = TiledObject::Text {};
+        let object = TiledObject::Text {
+            text: "Hello".to_string(),
+            pixel_size: 16.0,
+            color: Color::WHITE,
+            width: 64.0,
+            height: 16.0,
+            font_family: String::new(),
+            wrap: false,
+            bold: false,
+            italic: false,
+            underline: false,
+            strikeout: false,
+            kerning: true,
+            halign: TextHorizontalAlign::Left,
+            valign: TextVerticalAlign::Top,
+        };
         let collider = object_to_collider(&object);
         assert!(collider.is_none());
     }
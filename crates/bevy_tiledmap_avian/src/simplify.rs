@@ -0,0 +1,8 @@
+//! Simplification of tile collision geometry.
+//!
+//! Set [`PhysicsConfig::shape_simplification`](crate::config::PhysicsConfig::shape_simplification)
+//! to simplify the custom (non-rectangle) shapes of tile layer colliders. The
+//! simplification itself is backend-agnostic and lives in
+//! [`bevy_tiledmap_core::physics::simplify`].
+
+pub use bevy_tiledmap_core::physics::simplify::*;
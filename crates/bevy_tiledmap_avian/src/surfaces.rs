@@ -0,0 +1,85 @@
+//! Climbable and slope surface conventions.
+//!
+//! Recognized properties (on tiles or objects):
+//!
+//! - `climbable = true`: the collider becomes a [`Sensor`] marked [`Climbable`]
+//!   (ladders, vines, ropes).
+//! - `slope = false`: opts an angled polygon out of slope detection.
+//!
+//! Both names can be remapped with [`PropertyKeyConfig`].
+//!
+//! Polygon collision shapes with an angled, upward-facing edge are detected as
+//! slopes and marked with [`Slope`], so character controllers can query surface
+//! normals without per-game glue code.
+//!
+//! The conventions themselves are backend-agnostic and live in
+//! [`bevy_tiledmap_core::physics::surfaces`]; this module builds the Avian
+//! colliders for them.
+
+use avian2d::parry::math::Point;
+use avian2d::parry::shape::SharedShape;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_tiledmap_core::components::TiledObject;
+use bevy_tiledmap_core::events::ObjectSpawned;
+pub use bevy_tiledmap_core::physics::surfaces::{
+    is_climbable, slope_disabled, tile_is_climbable, tile_slope, Climbable, Slope,
+    CLIMBABLE_PROPERTY, SLOPE_PROPERTY,
+};
+use bevy_tiledmap_core::properties::PropertyKeyConfig;
+
+use crate::shapes;
+
+/// Round the corners of a convex polygon collider.
+///
+/// Other shapes, and a radius of `0.0`, leave the collider unchanged.
+pub fn round_collider(collider: Collider, border_radius: f32) -> Collider {
+    if border_radius <= 0.0 {
+        return collider;
+    }
+    let Some(polygon) = collider.shape().as_convex_polygon() else {
+        return collider;
+    };
+    let points: Vec<Point<f32>> = polygon.points().to_vec();
+    SharedShape::round_convex_hull(&points, border_radius)
+        .map(Collider::from)
+        .unwrap_or(collider)
+}
+
+/// Observer that applies surface conventions to spawned objects.
+///
+/// - Climbable objects get a sensor collider (from their shape, unless
+///   `physics_settings` already provides one) and a [`Climbable`] marker.
+/// - Polygon objects with `physics_settings` and an angled top edge get a [`Slope`].
+pub fn on_surface_object_spawned(
+    trigger: On<ObjectSpawned>,
+    object_query: Query<&TiledObject>,
+    keys: Res<PropertyKeyConfig>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let Ok(object) = object_query.get(event.entity) else {
+        return;
+    };
+    let has_physics = event.properties.contains_key("physics_settings");
+
+    if is_climbable(&event.properties, &keys) {
+        let mut entity_cmds = commands.entity(event.entity);
+        entity_cmds.insert((Sensor, Climbable));
+        if !has_physics {
+            if let Some(collider) = shapes::object_to_collider(object) {
+                entity_cmds.insert((RigidBody::Static, collider));
+            }
+        }
+        return;
+    }
+
+    if !has_physics || slope_disabled(&event.properties, &keys) {
+        return;
+    }
+    if let TiledObject::Polygon { vertices } = object {
+        if let Some(slope) = Slope::from_polygon(vertices) {
+            commands.entity(event.entity).insert(slope);
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! Collider toggling for layers hidden at runtime.
+
+use bevy::prelude::*;
+use bevy_tiledmap_core::events::LayerVisibilityChanged;
+
+use crate::collision::refresh_collider_state;
+
+/// Observer that disables or re-enables a layer's colliders when its visibility changes.
+///
+/// Covers the layer entity and all of its descendants, so both tile colliders
+/// (on the layer or per-tile children) and object colliders are affected.
+/// Colliders disabled with `TiledCollisionDisabled` stay disabled when shown.
+/// Only added when `PhysicsConfig::disable_hidden_layer_colliders` is enabled.
+pub fn on_layer_visibility_changed(trigger: On<LayerVisibilityChanged>, mut commands: Commands) {
+    let entity = trigger.event().entity;
+    commands.queue(move |world: &mut World| refresh_collider_state(world, entity));
+}
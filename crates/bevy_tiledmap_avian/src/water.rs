@@ -0,0 +1,360 @@
+//! Water and other fluid volumes.
+//!
+//! Objects and tile layers with a fluid class (`"Water"` by default, see
+//! [`PhysicsConfig::fluid_classes`]) become [`Sensor`] volumes with a
+//! [`FluidVolume`] component:
+//!
+//! - Objects get a sensor collider of their shape (or keep the one from
+//!   `physics_settings`, turned into a sensor).
+//! - Tile layers get a child entity with a merged sensor covering every tile of
+//!   the layer, rebuilt when the layer's tiles change. `"Water"` layers get no
+//!   regular tile colliders by default (see [`PhysicsConfig::layer_class_collision`]).
+//!
+//! Recognized properties (on the object or layer, remappable with
+//! [`PropertyKeyConfig`]):
+//!
+//! - `density` (float): fluid density, for buoyancy. Default: `1.0`
+//! - `flow_x`, `flow_y` (float): current in pixels per second. Default: `0.0`
+//!
+//! Colliders entering or leaving a volume trigger [`FluidEntered`] and
+//! [`FluidExited`] on the volume entity, so buoyancy or swim-state systems don't
+//! need custom map parsing:
+//!
+//! ```rust,ignore
+//! fn start_swimming(trigger: On<FluidEntered>, mut players: Query<&mut Swimming>) {
+//!     if let Ok(mut swimming) = players.get_mut(trigger.event().collider) {
+//!         swimming.0 = true;
+//!     }
+//! }
+//!
+//! app.add_observer(start_swimming);
+//! ```
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+use bevy_tiledmap_core::components::tile::TileLayerData;
+use bevy_tiledmap_core::components::{LayerClass, ObjectClass, TiledObject};
+use bevy_tiledmap_core::events::{ObjectSpawned, TileLayerSpawned, TilesModified};
+use bevy_tiledmap_core::physics::merge_tile_rectangles;
+use bevy_tiledmap_core::properties::PropertyKeyConfig;
+use tiled::{Properties, PropertyValue};
+
+use crate::config::PhysicsConfig;
+use crate::shapes;
+
+/// Property setting a fluid volume's density.
+pub const FLUID_DENSITY_PROPERTY: &str = "density";
+
+/// Property setting the horizontal current of a fluid volume.
+pub const FLOW_X_PROPERTY: &str = "flow_x";
+
+/// Property setting the vertical current of a fluid volume.
+pub const FLOW_Y_PROPERTY: &str = "flow_y";
+
+/// A sensor volume of fluid (water, lava, ...), from an object or tile layer
+/// with a fluid class.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct FluidVolume {
+    /// Fluid density, for buoyancy.
+    ///
+    /// Default: `1.0`
+    pub density: f32,
+    /// Current in pixels per second.
+    ///
+    /// Default: [`Vec2::ZERO`]
+    pub flow: Vec2,
+}
+
+impl Default for FluidVolume {
+    fn default() -> Self {
+        Self {
+            density: 1.0,
+            flow: Vec2::ZERO,
+        }
+    }
+}
+
+impl FluidVolume {
+    /// Read a fluid volume from object or layer properties.
+    ///
+    /// Missing or non-numeric properties keep their defaults.
+    pub fn from_properties(properties: &Properties, keys: &PropertyKeyConfig) -> Self {
+        let number = |key: &str| match keys.get(properties, key)? {
+            PropertyValue::FloatValue(value) => Some(*value),
+            PropertyValue::IntValue(value) => Some(*value as f32),
+            _ => None,
+        };
+        let default = Self::default();
+        Self {
+            density: number(FLUID_DENSITY_PROPERTY).unwrap_or(default.density),
+            flow: Vec2::new(
+                number(FLOW_X_PROPERTY).unwrap_or(default.flow.x),
+                number(FLOW_Y_PROPERTY).unwrap_or(default.flow.y),
+            ),
+        }
+    }
+}
+
+/// Marker for the fluid volume entity generated for a tile layer.
+///
+/// A child of the layer entity, rebuilt when the layer's tiles change.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct TileFluidVolume;
+
+/// Fired on a fluid volume when a collider starts touching it.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct FluidEntered {
+    /// The fluid volume entity
+    #[event_target]
+    pub volume: Entity,
+    /// The collider that entered the volume
+    pub collider: Entity,
+    /// The rigid body of the collider, if it has one
+    pub body: Option<Entity>,
+}
+
+/// Fired on a fluid volume when a collider stops touching it.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct FluidExited {
+    /// The fluid volume entity
+    #[event_target]
+    pub volume: Entity,
+    /// The collider that left the volume
+    pub collider: Entity,
+    /// The rigid body of the collider, if it has one
+    pub body: Option<Entity>,
+}
+
+/// Observer that turns spawned objects with a fluid class into fluid volumes.
+pub fn on_fluid_object_spawned(
+    trigger: On<ObjectSpawned>,
+    object_query: Query<(&TiledObject, &ObjectClass, Has<Collider>)>,
+    config: Res<PhysicsConfig>,
+    keys: Res<PropertyKeyConfig>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let Ok((object, class, has_collider)) = object_query.get(event.entity) else {
+        return;
+    };
+    if !config.fluid_classes.contains(&class.0) {
+        return;
+    }
+
+    let mut entity_cmds = commands.entity(event.entity);
+    if !has_collider {
+        let Some(collider) = shapes::object_to_collider(object) else {
+            warn!(
+                "Fluid object {} has an unsupported shape, skipping volume",
+                event.object_id
+            );
+            return;
+        };
+        entity_cmds.insert((RigidBody::Static, collider));
+    }
+    entity_cmds.insert((
+        FluidVolume::from_properties(&event.properties, &keys),
+        Sensor,
+        CollisionEventsEnabled,
+    ));
+}
+
+/// Observer that gives spawned tile layers with a fluid class a fluid volume.
+pub fn on_fluid_layer_spawned(
+    trigger: On<TileLayerSpawned>,
+    layer_query: Query<(&TileLayerData, &LayerClass)>,
+    tileset_assets: Res<Assets<TiledTilesetAsset>>,
+    config: Res<PhysicsConfig>,
+    keys: Res<PropertyKeyConfig>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let Ok((tile_data, class)) = layer_query.get(event.entity) else {
+        return;
+    };
+    if !config.fluid_classes.contains(&class.0) {
+        return;
+    }
+
+    let volume = FluidVolume::from_properties(&event.properties, &keys);
+    spawn_tile_fluid_volume(
+        event.entity,
+        tile_data,
+        volume,
+        &tileset_assets,
+        &mut commands,
+    );
+}
+
+/// Observer that rebuilds a fluid tile layer's volume when its tiles change.
+pub fn on_fluid_tiles_modified(
+    trigger: On<TilesModified>,
+    layer_query: Query<(&TileLayerData, &LayerClass, Option<&Children>)>,
+    volume_query: Query<&FluidVolume, With<TileFluidVolume>>,
+    tileset_assets: Res<Assets<TiledTilesetAsset>>,
+    config: Res<PhysicsConfig>,
+    mut commands: Commands,
+) {
+    let layer_entity = trigger.event().entity;
+    let Ok((tile_data, class, children)) = layer_query.get(layer_entity) else {
+        return;
+    };
+    if !config.fluid_classes.contains(&class.0) {
+        return;
+    }
+
+    // Keep the volume's parameters, which may have been changed at runtime
+    let mut volume = None;
+    for child in children.into_iter().flatten() {
+        if let Ok(existing) = volume_query.get(*child) {
+            volume = Some(*existing);
+            commands.entity(*child).despawn();
+        }
+    }
+    let Some(volume) = volume else {
+        return;
+    };
+    spawn_tile_fluid_volume(
+        layer_entity,
+        tile_data,
+        volume,
+        &tileset_assets,
+        &mut commands,
+    );
+}
+
+/// Spawn a merged sensor covering every tile of a layer as a child of the layer.
+fn spawn_tile_fluid_volume(
+    layer_entity: Entity,
+    tile_data: &TileLayerData,
+    volume: FluidVolume,
+    tileset_assets: &Assets<TiledTilesetAsset>,
+    commands: &mut Commands,
+) {
+    // Assume all tilesets have the layer's tile size, like tile colliders do
+    let tile_size = tile_data
+        .iter_tiles()
+        .find_map(|(_, _, tile)| tileset_assets.get(&tile.tileset_handle))
+        .map_or(Vec2::splat(16.0), |tileset| tileset.tile_size.as_vec2());
+    let positions = tile_data.iter_tiles().map(|(x, y, _)| (x, y)).collect();
+    let parts = merge_tile_rectangles(positions, tile_size, tile_data.height)
+        .into_iter()
+        .map(|(center, size)| (center, 0.0, Collider::rectangle(size.x, size.y)))
+        .collect();
+
+    // Layers always get a volume entity, so it survives the layer being emptied
+    let mut volume_cmds = commands.spawn((
+        Name::new("Fluid Volume"),
+        TileFluidVolume,
+        volume,
+        Transform::default(),
+        ChildOf(layer_entity),
+    ));
+    if let Some(collider) = shapes::compound_collider(parts) {
+        volume_cmds.insert((collider, Sensor, CollisionEventsEnabled));
+    }
+}
+
+/// Observer that triggers [`FluidEntered`] when a collider starts touching a fluid volume.
+pub fn on_fluid_collision_start(
+    trigger: On<CollisionStart>,
+    volume_query: Query<(), With<FluidVolume>>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    if volume_query.contains(event.collider1) {
+        commands.trigger(FluidEntered {
+            volume: event.collider1,
+            collider: event.collider2,
+            body: event.body2,
+        });
+    }
+}
+
+/// Observer that triggers [`FluidExited`] when a collider stops touching a fluid volume.
+pub fn on_fluid_collision_end(
+    trigger: On<CollisionEnd>,
+    volume_query: Query<(), With<FluidVolume>>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    if volume_query.contains(event.collider1) {
+        commands.trigger(FluidExited {
+            volume: event.collider1,
+            collider: event.collider2,
+            body: event.body2,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<(Entity, Entity, bool)>);
+
+    #[test]
+    fn test_fluid_volume_from_properties() {
+        let mut properties = Properties::new();
+        properties.insert("density".to_string(), PropertyValue::FloatValue(1.5));
+        properties.insert("flow_x".to_string(), PropertyValue::IntValue(-20));
+
+        let volume = FluidVolume::from_properties(&properties, &PropertyKeyConfig::default());
+        assert_eq!(volume.density, 1.5);
+        assert_eq!(volume.flow, Vec2::new(-20.0, 0.0));
+        assert_eq!(
+            FluidVolume::from_properties(&Properties::new(), &PropertyKeyConfig::default()),
+            FluidVolume::default()
+        );
+    }
+
+    #[test]
+    fn test_fluid_enter_exit_events() {
+        let mut app = App::new();
+        app.init_resource::<Log>();
+        app.add_observer(on_fluid_collision_start);
+        app.add_observer(on_fluid_collision_end);
+        app.add_observer(|trigger: On<FluidEntered>, mut log: ResMut<Log>| {
+            log.0
+                .push((trigger.event().volume, trigger.event().collider, true));
+        });
+        app.add_observer(|trigger: On<FluidExited>, mut log: ResMut<Log>| {
+            log.0
+                .push((trigger.event().volume, trigger.event().collider, false));
+        });
+
+        let world = app.world_mut();
+        let water = world.spawn(FluidVolume::default()).id();
+        let player = world.spawn_empty().id();
+
+        world.trigger(CollisionStart {
+            collider1: water,
+            collider2: player,
+            body1: None,
+            body2: Some(player),
+        });
+        // Collisions reported on the other collider aren't fluid events
+        world.trigger(CollisionStart {
+            collider1: player,
+            collider2: water,
+            body1: Some(player),
+            body2: None,
+        });
+        world.trigger(CollisionEnd {
+            collider1: water,
+            collider2: player,
+            body1: None,
+            body2: Some(player),
+        });
+        world.flush();
+
+        assert_eq!(
+            world.resource::<Log>().0,
+            [(water, player, true), (water, player, false)]
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Collider regression suite.
+//!
+//! Loads the QA maps in `assets/maps/qa` (slopes, one-way platforms, isometric
+//! and infinite maps, many tile kinds and materials) headlessly and compares every
+//! generated collider - its shape count and world-space AABB - against
+//! `assets/maps/qa/colliders.golden`. Any change in collider generation,
+//! including one caused by a `tiled` or Avian upgrade, shows up as a diff of
+//! that file. Loading the maps repeatedly also checks that compound colliders
+//! list their shapes in the same order every time.
+//!
+//! After an intended change, regenerate the golden data and review its diff:
+//!
+//! ```text
+//! UPDATE_GOLDEN=1 cargo test -p bevy_tiledmap_avian --test collider_golden
+//! ```
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::*;
+use bevy_tiledmap_avian::prelude::*;
+use bevy_tiledmap_core::prelude::*;
+
+/// QA maps in `assets/maps/qa`.
+const MAPS: &[&str] = &[
+    "slopes.tmx",
+    "one_way.tmx",
+    "isometric.tmx",
+    "infinite.tmx",
+    "mixed_blocks.tmx",
+];
+
+/// Updates to run before giving up on the maps loading.
+const MAX_UPDATES: usize = 1000;
+
+#[derive(Resource, Default)]
+struct SpawnedMaps(usize);
+
+fn qa_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets/maps/qa")
+}
+
+/// Load `map` headlessly, until its colliders are in place.
+fn load_map(map: &str) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        TransformPlugin,
+        TiledmapAssetsPlugin,
+        TiledmapCorePlugin::default(),
+        TiledmapAvianPlugin::default(),
+    ))
+    .init_resource::<SpawnedMaps>()
+    .add_observer(|_: On<MapSpawned>, mut spawned: ResMut<SpawnedMaps>| {
+        spawned.0 += 1;
+    });
+
+    let handle = app
+        .world()
+        .resource::<AssetServer>()
+        .load(format!("maps/qa/{map}"));
+    app.world_mut().spawn((
+        Transform::default(),
+        Visibility::default(),
+        TiledMap { handle },
+    ));
+
+    let mut updates = 0;
+    while app.world().resource::<SpawnedMaps>().0 == 0 {
+        assert!(updates < MAX_UPDATES, "{map} did not spawn");
+        app.update();
+        updates += 1;
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    // Let observers' commands apply and transforms propagate
+    app.update();
+    app.update();
+    app
+}
+
+/// Load `map` headlessly and describe its colliders, one sorted line each.
+fn collider_report(map: &str) -> Vec<String> {
+    let mut app = load_map(map);
+    let world = app.world_mut();
+    let mut query = world.query::<(
+        Entity,
+        &Collider,
+        &GlobalTransform,
+        Option<&Name>,
+        Option<&ChildOf>,
+        Has<Sensor>,
+    )>();
+    let mut lines: Vec<String> = query
+        .iter(world)
+        .map(|(_, collider, transform, name, child_of, sensor)| {
+            let parent = child_of
+                .and_then(|child_of| world.get::<Name>(child_of.parent()))
+                .map_or(String::new(), |name| format!("{name} / "));
+            let name = name.map_or("<unnamed>".to_string(), ToString::to_string);
+            let (_, rotation, translation) = transform.to_scale_rotation_translation();
+            let aabb = collider.aabb(
+                translation.truncate(),
+                Rotation::radians(rotation.to_euler(EulerRot::XYZ).2),
+            );
+            let shapes = collider
+                .shape()
+                .as_compound()
+                .map_or(1, |compound| compound.shapes().len());
+            format!(
+                "{map}: {parent}{name}{} shapes={shapes} min=({:.2}, {:.2}) max=({:.2}, {:.2})",
+                if sensor { " [sensor]" } else { "" },
+                aabb.min.x,
+                aabb.min.y,
+                aabb.max.x,
+                aabb.max.y,
+            )
+        })
+        .collect();
+    lines.sort();
+    lines
+}
+
+#[test]
+fn test_colliders_match_golden_data() {
+    let report: String = MAPS
+        .iter()
+        .flat_map(|map| collider_report(map))
+        .map(|line| line.replace("-0.00", "0.00") + "\n")
+        .collect();
+    let golden_path = qa_dir().join("colliders.golden");
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(&golden_path, &report).unwrap();
+        return;
+    }
+    let golden = std::fs::read_to_string(&golden_path).unwrap_or_default();
+    assert!(
+        report == golden,
+        "Generated colliders differ from {}.\n\nExpected:\n{golden}\nGot:\n{report}\n\
+         Rerun with UPDATE_GOLDEN=1 if the change is intended.",
+        golden_path.display(),
+    );
+}
+
+/// Load `map` headlessly and list the shapes of each compound collider, in order.
+fn compound_layouts(map: &str) -> Vec<String> {
+    let mut app = load_map(map);
+    let world = app.world_mut();
+    let mut query = world.query::<(&Collider, &GlobalTransform)>();
+    let mut layouts: Vec<String> = query
+        .iter(world)
+        .filter_map(|(collider, transform)| {
+            let compound = collider.shape().as_compound()?;
+            let shapes: Vec<String> = compound
+                .shapes()
+                .iter()
+                .map(|(isometry, shape)| format!("{isometry:?} {:?}", shape.compute_local_aabb()))
+                .collect();
+            Some(format!(
+                "{:?}: {}",
+                transform.translation(),
+                shapes.join(", ")
+            ))
+        })
+        .collect();
+    layouts.sort();
+    layouts
+}
+
+#[test]
+fn test_compound_shape_order_is_deterministic() {
+    for map in MAPS {
+        let first = compound_layouts(map);
+        assert!(!first.is_empty(), "{map} has no compound colliders");
+        for _ in 0..3 {
+            assert_eq!(
+                compound_layouts(map),
+                first,
+                "{map} compounds changed order"
+            );
+        }
+    }
+}
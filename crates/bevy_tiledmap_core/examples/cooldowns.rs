@@ -0,0 +1,109 @@
+//! Demonstrates `TiledClass` field ranges with cooldown timers.
+//!
+//! This example shows:
+//! 1. Constraining numeric fields with `#[tiled(min = ..., max = ...)]`
+//! 2. Reading the ranges back from the `TiledClassRegistry`
+//! 3. Finding authored values outside their range (maps warn about these when they spawn)
+//! 4. Visualizing the resulting cooldown timers with gizmos
+//!
+//! Run with: `cargo run --example cooldowns`
+
+use bevy::prelude::*;
+use bevy_tiledmap_core::prelude::*;
+use tiled::{Properties, PropertyValue};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TiledmapCorePlugin::default())
+        .register_type::<Cooldown>()
+        .add_systems(Startup, (setup, spawn_turrets))
+        .add_systems(Update, (tick_cooldowns, draw_cooldowns).chain())
+        .run();
+}
+
+/// A repeating ability cooldown, as authored on a turret object in Tiled.
+#[derive(Component, Reflect, TiledClass, Debug, Clone)]
+#[reflect(Component)]
+#[tiled(name = "demo::Cooldown")]
+struct Cooldown {
+    /// Seconds between activations
+    #[tiled(default = 2.0, min = 0.1, max = 30.0)]
+    duration: f32,
+    /// Activations per cooldown
+    #[tiled(default = 1, min = 1)]
+    charges: i32,
+    /// Elapsed time, not authored in Tiled
+    #[tiled(skip)]
+    elapsed: f32,
+}
+
+impl Default for Cooldown {
+    fn default() -> Self {
+        Self {
+            duration: 2.0,
+            charges: 1,
+            elapsed: 0.0,
+        }
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+/// Deserialize turrets from properties the way map spawning does.
+fn spawn_turrets(mut commands: Commands, registry: Res<TiledClassRegistry>) {
+    let Some(info) = registry.get("demo::Cooldown") else {
+        return;
+    };
+
+    for field in info.fields {
+        info!(
+            "demo::Cooldown.{}: min {:?}, max {:?}",
+            field.name, field.min, field.max
+        );
+    }
+
+    // The last turret was authored with a duration outside the allowed range
+    for (i, duration) in [0.5, 1.5, 4.0, 45.0].into_iter().enumerate() {
+        let mut properties = Properties::new();
+        properties.insert("duration".to_string(), PropertyValue::FloatValue(duration));
+
+        for (field, value) in info.out_of_range(&properties) {
+            warn!("Turret {i}: {} = {value:?} is out of range", field.name);
+        }
+
+        let Ok(component) = (info.from_properties)(&properties, None) else {
+            continue;
+        };
+        let Some(cooldown) = component.downcast_ref::<Cooldown>() else {
+            continue;
+        };
+        commands.spawn((
+            cooldown.clone(),
+            Transform::from_xyz(i as f32 * 120.0 - 180.0, 0.0, 0.0),
+        ));
+    }
+}
+
+fn tick_cooldowns(time: Res<Time>, mut cooldowns: Query<&mut Cooldown>) {
+    for mut cooldown in &mut cooldowns {
+        cooldown.elapsed = (cooldown.elapsed + time.delta_secs()) % cooldown.duration;
+    }
+}
+
+/// Draw each cooldown as a ring that fills up over its duration.
+fn draw_cooldowns(mut gizmos: Gizmos, cooldowns: Query<(&Cooldown, &Transform)>) {
+    for (cooldown, transform) in &cooldowns {
+        let position = transform.translation.truncate();
+        let progress = cooldown.elapsed / cooldown.duration;
+        gizmos.circle_2d(position, 40.0, Color::srgb(0.3, 0.3, 0.3));
+        gizmos.arc_2d(
+            Isometry2d::from_translation(position),
+            progress * std::f32::consts::TAU,
+            40.0,
+            Color::srgb(0.2, 0.8, 1.0),
+        );
+    }
+}
@@ -16,6 +16,7 @@
 use bevy::prelude::*;
 use bevy_tiledmap_assets::TiledmapAssetsPlugin;
 use bevy_tiledmap_core::prelude::*;
+use tiled::PropertyValue;
 
 fn main() {
     App::new()
@@ -190,6 +191,22 @@ pub struct Enemy {
     /// Enemy color tint
     #[tiled(default = Color::srgb(1.0, 0.0, 0.0))]
     pub color: Color,
+
+    /// Hours of the day the enemy patrols, written in Tiled as `"8-20"`
+    #[tiled(with = "parse_hour_range")]
+    pub patrol_hours: (u32, u32),
+
+    /// Patrol waypoint offsets, exported as a class with members `0` and `1`
+    pub patrol_offset: (f32, f32),
+}
+
+/// Custom field parser for `#[tiled(with = "...")]`: parses `"start-end"` hour ranges.
+fn parse_hour_range(value: &PropertyValue) -> Option<(u32, u32)> {
+    let PropertyValue::StringValue(s) = value else {
+        return None;
+    };
+    let (start, end) = s.split_once('-')?;
+    Some((start.trim().parse().ok()?, end.trim().parse().ok()?))
 }
 
 /// Enemy type enum
@@ -203,9 +220,9 @@ pub enum EnemyType {
 
 // Implement FromTiledProperty for EnemyType to deserialize from string
 impl FromTiledProperty for EnemyType {
-    fn from_property(value: &tiled::PropertyValue) -> Option<Self> {
+    fn from_property(value: &PropertyValue) -> Option<Self> {
         match value {
-            tiled::PropertyValue::StringValue(s) => match s.as_str() {
+            PropertyValue::StringValue(s) => match s.as_str() {
                 "Grunt" => Some(EnemyType::Grunt),
                 "Elite" => Some(EnemyType::Elite),
                 "Boss" => Some(EnemyType::Boss),
@@ -216,6 +233,13 @@ impl FromTiledProperty for EnemyType {
     }
 }
 
+// Implement ToTiledProperty for EnemyType so edits can be written back as a string
+impl ToTiledProperty for EnemyType {
+    fn to_property(&self) -> Option<PropertyValue> {
+        Some(PropertyValue::StringValue(format!("{:?}", self)))
+    }
+}
+
 /// Collectible component - for items that can be picked up
 #[derive(Component, Reflect, Default, TiledClass, Debug)]
 #[reflect(Component)]
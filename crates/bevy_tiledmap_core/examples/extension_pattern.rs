@@ -189,6 +189,9 @@ fn on_object_spawned(trigger: On<ObjectSpawned>, object_query: Query<(&TiledObje
         TiledObject::Text { .. } => {
             info!("  ℹ Skipped text object");
         }
+        _ => {
+            info!("  ℹ Skipped unknown object shape");
+        }
     }
 }
 
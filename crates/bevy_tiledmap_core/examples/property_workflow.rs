@@ -92,6 +92,8 @@ fn simulate_object_spawn(world: &mut World, component_type: &str, props: tiled::
                     && let Ok(mut entity_mut) = world.get_entity_mut(entity)
                 {
                     reflect_component.insert(&mut entity_mut, &*component_box, &registry_lock);
+                    // Components listed in #[tiled(requires(...))]
+                    (info.insert_requires)(&mut entity_mut);
                     info!("   ✓ Attached component to entity {:?}", entity);
                 }
             }
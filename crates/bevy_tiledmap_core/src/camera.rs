@@ -0,0 +1,422 @@
+//! Keeping cameras inside maps and camera regions.
+//!
+//! Spawned maps get [`CameraBounds`] covering their pixel rect ([`MapGeometry`]),
+//! and rectangle objects classed `"CameraBounds"` get bounds covering the
+//! rectangle (rooms, arenas, screens). Cameras with [`CameraConfinement`] are
+//! clamped so their orthographic view stays inside the bounds:
+//!
+//! - With [`CameraConfinement::to`], inside those bounds.
+//! - Otherwise inside the smallest bounds containing the camera, so regions win
+//!   over the map around them, or the nearest bounds if none contains it.
+//!
+//! Bounds smaller than the view center the camera on them. Cameras without an
+//! orthographic projection are not clamped.
+//!
+//! Clamping runs in `PostUpdate` in [`CameraConfinementSystems`], before
+//! transform propagation. Camera controllers (including third-party crates)
+//! that move the camera in `PostUpdate` should run before that set:
+//!
+//! ```rust,ignore
+//! commands.spawn((Camera2d, CameraConfinement::default()));
+//!
+//! app.add_systems(PostUpdate, follow_player.before(CameraConfinementSystems));
+//! ```
+//!
+//! Custom controllers can also clamp positions themselves with
+//! [`CameraBounds::clamp_view`].
+//!
+//! Cameras with [`PixelPerfectCamera`] are kept at an integer zoom of a map's
+//! pixels, to avoid blurry and seamy tiles.
+
+use bevy::camera::ScalingMode;
+use bevy::prelude::*;
+
+use crate::components::MapGeometry;
+use crate::components::object::{ObjectClass, TiledObject};
+use crate::events::ObjectSpawned;
+
+/// Class of rectangle objects that become camera regions.
+pub const CAMERA_BOUNDS_CLASS: &str = "CameraBounds";
+
+/// A rect cameras with [`CameraConfinement`] are kept inside.
+///
+/// The rect is in the entity's local space, and follows the entity's
+/// [`GlobalTransform`].
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct CameraBounds {
+    /// Bounds in the entity's local space.
+    pub rect: Rect,
+}
+
+impl CameraBounds {
+    /// Get the bounds in world space.
+    pub fn world_rect(&self, transform: &GlobalTransform) -> Rect {
+        let corners = [
+            self.rect.min,
+            Vec2::new(self.rect.max.x, self.rect.min.y),
+            self.rect.max,
+            Vec2::new(self.rect.min.x, self.rect.max.y),
+        ]
+        .map(|corner| transform.transform_point(corner.extend(0.0)).truncate());
+        corners.into_iter().fold(
+            Rect::from_center_size(corners[0], Vec2::ZERO),
+            |rect, corner| rect.union_point(corner),
+        )
+    }
+
+    /// Clamp a view centered on `center` with half extents `half_size` inside
+    /// `bounds` (both in world space).
+    ///
+    /// On axes where the view is larger than the bounds, the view is centered
+    /// on the bounds.
+    pub fn clamp_view(bounds: Rect, center: Vec2, half_size: Vec2) -> Vec2 {
+        let min = bounds.min + half_size;
+        let max = bounds.max - half_size;
+        let axis = |center: f32, min: f32, max: f32| {
+            if min > max {
+                (min + max) / 2.0
+            } else {
+                center.clamp(min, max)
+            }
+        };
+        Vec2::new(axis(center.x, min.x, max.x), axis(center.y, min.y, max.y))
+    }
+}
+
+/// Keeps a camera's view inside [`CameraBounds`]. See the [module docs](self).
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct CameraConfinement {
+    /// The entity whose bounds confine the camera, or `None` for the smallest
+    /// bounds containing it.
+    pub bounds: Option<Entity>,
+}
+
+impl CameraConfinement {
+    /// Confine the camera to the bounds of `entity` (a map or region object).
+    pub fn to(entity: Entity) -> Self {
+        Self {
+            bounds: Some(entity),
+        }
+    }
+}
+
+/// System set clamping confined cameras, in `PostUpdate`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CameraConfinementSystems;
+
+/// Observer giving maps bounds from their geometry.
+pub fn on_map_geometry_added(
+    trigger: On<Add, MapGeometry>,
+    geometry_query: Query<&MapGeometry>,
+    mut commands: Commands,
+) {
+    let entity = trigger.event().entity;
+    if let Ok(geometry) = geometry_query.get(entity) {
+        commands.entity(entity).insert(CameraBounds {
+            rect: geometry.bounds,
+        });
+    }
+}
+
+/// Observer giving rectangle objects classed [`CAMERA_BOUNDS_CLASS`] bounds.
+pub fn on_camera_bounds_object_spawned(
+    trigger: On<ObjectSpawned>,
+    object_query: Query<(&TiledObject, &ObjectClass)>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let Ok((object, class)) = object_query.get(event.entity) else {
+        return;
+    };
+    if class.0 != CAMERA_BOUNDS_CLASS {
+        return;
+    }
+    let TiledObject::Rectangle { width, height } = object else {
+        warn!(
+            "Camera bounds object {} is not a rectangle, ignoring it",
+            event.object_id
+        );
+        return;
+    };
+    // Rectangle objects are centered on their entity
+    commands.entity(event.entity).insert(CameraBounds {
+        rect: Rect::from_center_size(Vec2::ZERO, Vec2::new(*width, *height)),
+    });
+}
+
+/// System clamping cameras with [`CameraConfinement`] inside their bounds.
+pub fn confine_cameras(
+    mut camera_query: Query<(&CameraConfinement, &Projection, &mut Transform)>,
+    bounds_query: Query<(&CameraBounds, &GlobalTransform)>,
+) {
+    for (confinement, projection, mut transform) in &mut camera_query {
+        let Projection::Orthographic(orthographic) = projection else {
+            continue;
+        };
+        let center = transform.translation.truncate();
+
+        let bounds = match confinement.bounds {
+            Some(entity) => bounds_query
+                .get(entity)
+                .ok()
+                .map(|(bounds, global)| bounds.world_rect(global)),
+            None => select_bounds(
+                center,
+                bounds_query
+                    .iter()
+                    .map(|(bounds, global)| bounds.world_rect(global)),
+            ),
+        };
+        let Some(bounds) = bounds else {
+            continue;
+        };
+
+        let clamped = CameraBounds::clamp_view(bounds, center, orthographic.area.half_size());
+        if clamped != center {
+            transform.translation.x = clamped.x;
+            transform.translation.y = clamped.y;
+        }
+    }
+}
+
+/// Keeps an orthographic camera at a pixel-perfect zoom of a map.
+///
+/// Blurry or seamy tiles come from a zoom that isn't a whole number of screen
+/// pixels per map pixel, or from a camera position between two screen pixels.
+/// Cameras with this component get the largest integer zoom that still shows
+/// [`min_tiles`](Self::min_tiles) tiles of the map (but at least 1), and their
+/// position is snapped to the screen's pixel grid after [`CameraConfinementSystems`].
+/// The zoom follows window resizes and display scale factors.
+///
+/// Pixel art also needs nearest-neighbor sampling (`ImagePlugin::default_nearest()`),
+/// and MSAA can blur tile edges:
+///
+/// ```rust,ignore
+/// commands.spawn((Camera2d, Msaa::Off, PixelPerfectCamera::for_map(map_entity)));
+/// ```
+///
+/// Cameras without an orthographic projection, and cameras before a map is
+/// spawned, are left alone.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct PixelPerfectCamera {
+    /// The map whose tile size sets the zoom, or `None` for any spawned map
+    pub map: Option<Entity>,
+
+    /// Tiles that must fit in the view along each axis (default: 16x9)
+    pub min_tiles: UVec2,
+
+    /// Largest zoom, or `None` for no limit
+    pub max_zoom: Option<u32>,
+}
+
+impl Default for PixelPerfectCamera {
+    fn default() -> Self {
+        Self {
+            map: None,
+            min_tiles: UVec2::new(16, 9),
+            max_zoom: None,
+        }
+    }
+}
+
+impl PixelPerfectCamera {
+    /// Zoom pixel-perfectly on the map `entity`.
+    pub fn for_map(entity: Entity) -> Self {
+        Self {
+            map: Some(entity),
+            ..default()
+        }
+    }
+
+    /// Fit at least `min_tiles` tiles in the view.
+    pub fn with_min_tiles(mut self, min_tiles: UVec2) -> Self {
+        self.min_tiles = min_tiles;
+        self
+    }
+
+    /// Never zoom in further than `max_zoom`.
+    pub fn with_max_zoom(mut self, max_zoom: u32) -> Self {
+        self.max_zoom = Some(max_zoom);
+        self
+    }
+
+    /// Get the zoom (screen pixels per map pixel) for a viewport size in
+    /// physical pixels and a tile size in map pixels.
+    pub fn zoom(&self, viewport: UVec2, tile_size: Vec2) -> u32 {
+        let fit = viewport.as_vec2() / (self.min_tiles.max(UVec2::ONE).as_vec2() * tile_size);
+        let zoom = (fit.min_element().floor() as u32).max(1);
+        self.max_zoom
+            .map_or(zoom, |max_zoom| zoom.min(max_zoom.max(1)))
+    }
+
+    /// Snap a camera position to the pixel grid of a viewport (physical pixels)
+    /// at `zoom`.
+    ///
+    /// With an odd viewport size, the view's center is in the middle of a
+    /// pixel, so the position is snapped to pixel centers on that axis.
+    pub fn snap(position: Vec2, viewport: UVec2, zoom: u32) -> Vec2 {
+        let zoom = zoom as f32;
+        let offset = (viewport % 2).as_vec2() * 0.5;
+        ((position * zoom - offset).round() + offset) / zoom
+    }
+}
+
+/// System applying [`PixelPerfectCamera`] zooms and snapping.
+pub fn apply_pixel_perfect_cameras(
+    mut camera_query: Query<(
+        &PixelPerfectCamera,
+        &Camera,
+        &mut Projection,
+        &mut Transform,
+    )>,
+    map_query: Query<&MapGeometry>,
+) {
+    for (pixel_perfect, camera, mut projection, mut transform) in &mut camera_query {
+        let Projection::Orthographic(orthographic) = projection.as_ref() else {
+            continue;
+        };
+        let (Some(physical), Some(logical)) = (
+            camera.physical_viewport_size(),
+            camera.logical_viewport_size(),
+        ) else {
+            continue;
+        };
+        let geometry = match pixel_perfect.map {
+            Some(entity) => map_query.get(entity).ok(),
+            None => map_query.iter().next(),
+        };
+        let Some(geometry) = geometry else {
+            continue;
+        };
+        if physical.x == 0 || logical.x <= 0.0 {
+            continue;
+        }
+
+        // `WindowSize` maps one world unit to `1 / scale` logical pixels
+        let zoom = pixel_perfect.zoom(physical, geometry.tile_size);
+        let scale = physical.x as f32 / logical.x / zoom as f32;
+        if (orthographic.scale != scale
+            || !matches!(orthographic.scaling_mode, ScalingMode::WindowSize))
+            && let Projection::Orthographic(orthographic) = projection.as_mut()
+        {
+            orthographic.scale = scale;
+            orthographic.scaling_mode = ScalingMode::WindowSize;
+        }
+
+        let position = transform.translation.truncate();
+        let snapped = PixelPerfectCamera::snap(position, physical, zoom);
+        if snapped != position {
+            transform.translation.x = snapped.x;
+            transform.translation.y = snapped.y;
+        }
+    }
+}
+
+/// Pick the smallest rect containing `point`, or the nearest one if none does.
+fn select_bounds(point: Vec2, candidates: impl Iterator<Item = Rect>) -> Option<Rect> {
+    let area = |rect: &Rect| rect.width() * rect.height();
+    let distance = |rect: &Rect| point.distance_squared(point.clamp(rect.min, rect.max));
+    candidates.min_by(|a, b| {
+        distance(a)
+            .total_cmp(&distance(b))
+            .then_with(|| area(a).total_cmp(&area(b)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_view() {
+        let bounds = Rect::new(0.0, 0.0, 320.0, 180.0);
+        let half = Vec2::new(80.0, 45.0);
+
+        // Inside: unchanged
+        let center = Vec2::new(100.0, 90.0);
+        assert_eq!(CameraBounds::clamp_view(bounds, center, half), center);
+        // Past the bottom-left corner
+        assert_eq!(
+            CameraBounds::clamp_view(bounds, Vec2::new(-50.0, 10.0), half),
+            Vec2::new(80.0, 45.0)
+        );
+        // View wider than the bounds: centered horizontally
+        assert_eq!(
+            CameraBounds::clamp_view(bounds, Vec2::new(300.0, 500.0), Vec2::new(200.0, 45.0)),
+            Vec2::new(160.0, 135.0)
+        );
+    }
+
+    #[test]
+    fn test_select_bounds_prefers_smallest_containing() {
+        let map = Rect::new(0.0, 0.0, 1000.0, 1000.0);
+        let room = Rect::new(100.0, 100.0, 300.0, 300.0);
+        let other_room = Rect::new(2000.0, 0.0, 2100.0, 100.0);
+        let all = || [map, room, other_room].into_iter();
+
+        assert_eq!(select_bounds(Vec2::splat(200.0), all()), Some(room));
+        assert_eq!(select_bounds(Vec2::splat(500.0), all()), Some(map));
+        // Outside everything: nearest
+        assert_eq!(
+            select_bounds(Vec2::new(1900.0, 50.0), all()),
+            Some(other_room)
+        );
+        assert_eq!(select_bounds(Vec2::ZERO, std::iter::empty()), None);
+    }
+
+    #[test]
+    fn test_pixel_perfect_zoom_and_snap() {
+        let camera = PixelPerfectCamera::default();
+        let tile_size = Vec2::splat(16.0);
+
+        // 1920x1080 fits 16x9 tiles of 16 pixels 7 times
+        assert_eq!(camera.zoom(UVec2::new(1920, 1080), tile_size), 7);
+        assert_eq!(
+            camera
+                .with_max_zoom(4)
+                .zoom(UVec2::new(1920, 1080), tile_size),
+            4
+        );
+        // Too small a window still shows the map at 1x
+        assert_eq!(camera.zoom(UVec2::new(100, 100), tile_size), 1);
+
+        // At 4x, positions snap to quarter map pixels, offset by half a pixel
+        // on odd-sized axes
+        assert_eq!(
+            PixelPerfectCamera::snap(Vec2::new(10.1, 10.1), UVec2::new(1920, 1081), 4),
+            Vec2::new(10.0, 10.125)
+        );
+    }
+
+    #[test]
+    fn test_confine_camera_to_region() {
+        let mut app = App::new();
+        app.add_systems(Update, confine_cameras);
+
+        let world = app.world_mut();
+        let region = world
+            .spawn((
+                CameraBounds {
+                    rect: Rect::from_center_size(Vec2::ZERO, Vec2::new(200.0, 100.0)),
+                },
+                GlobalTransform::from_translation(Vec3::new(500.0, 500.0, 0.0)),
+            ))
+            .id();
+        let mut projection = OrthographicProjection::default_2d();
+        projection.area = Rect::from_center_size(Vec2::ZERO, Vec2::new(100.0, 50.0));
+        let camera = world
+            .spawn((
+                CameraConfinement::to(region),
+                Projection::Orthographic(projection),
+                Transform::from_xyz(0.0, 0.0, 10.0),
+            ))
+            .id();
+
+        app.update();
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, Vec3::new(450.0, 475.0, 10.0));
+    }
+}
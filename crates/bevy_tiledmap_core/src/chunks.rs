@@ -0,0 +1,856 @@
+//! Chunk streaming for infinite maps, with a live-chunk budget.
+//!
+//! Chunks of infinite maps are streamed around [`ChunkViewer`] entities (e.g.
+//! the camera or the player):
+//!
+//! - Every saved chunk within [`ChunkStreamingConfig::load_radius`] chunks of a
+//!   viewer gets a [`TiledChunk`] entity, a child of the map placed at the
+//!   chunk's center, and [`ChunkSpawned`] fires on it.
+//! - Once more than [`ChunkStreamingConfig::max_live_chunks`] chunks are live,
+//!   the least recently viewed ones are evicted: [`ChunkEvicted`] fires on
+//!   them, then they are despawned with everything attached to them. Chunks in
+//!   view are never evicted, so the budget should cover what viewers can see.
+//!
+//! With [`ChunkStreamingConfig::stream_tiles`], the tiles of infinite layers are
+//! streamed too. Instead of one [`TileLayerData`] grid per layer, each layer
+//! keeps its tiles per chunk in a [`StreamedTileLayer`], and every chunk entity
+//! gets a 16x16 tile layer child per layer with tiles in it, marked with
+//! [`TiledChunkLayer`]. [`TileLayerSpawned`] fires for each of them, so render
+//! and physics backends build tilemaps and colliders per chunk, and evicting a
+//! chunk despawns them. Tile changes are written back to the layer on eviction,
+//! so they survive the chunk streaming back in. Long sessions wandering a huge
+//! map therefore keep a bounded number of tile entities.
+//!
+//! Without it, tiles are spawned whole and only the chunk entities are streamed,
+//! for content that belongs to an area of the map (AI, ambient sounds, ...).
+//! Both chunk events are delivered to observers and as messages, like the spawn
+//! events (see [`crate::events`]).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! app.insert_resource(ChunkStreamingConfig {
+//!     load_radius: 2,
+//!     max_live_chunks: 100,
+//!     stream_tiles: true,
+//! });
+//! commands.spawn((Camera2d, ChunkViewer));
+//!
+//! app.add_observer(|spawned: On<ChunkSpawned>, mut commands: Commands| {
+//!     commands.entity(spawned.entity).insert(Wildlife::for_chunk(spawned.chunk));
+//! });
+//! ```
+
+use bevy::math::Affine3A;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::TiledMapAsset;
+use tiled::Properties;
+
+use crate::components::tile::TileLayerData;
+use crate::components::{LayerClass, LayerId, LayerName, TiledLayer, TiledLayerMapOf, TiledMap};
+use crate::events::{MapSpawned, TileLayerSpawned, trigger_spawn_event};
+
+/// Side length of Tiled's chunks, in tiles.
+const CHUNK_SIZE: i32 = tiled::ChunkData::WIDTH as i32;
+
+/// Settings for streaming chunks around [`ChunkViewer`]s.
+#[derive(Resource, Debug, Clone)]
+pub struct ChunkStreamingConfig {
+    /// How many chunks around the viewer's chunk are kept live in each direction
+    /// (0 for only the viewer's chunk)
+    pub load_radius: u32,
+    /// Budget of live chunks across all maps; beyond it, the least recently
+    /// viewed chunks are evicted
+    pub max_live_chunks: usize,
+    /// Spawn the tiles of infinite layers per chunk, under the chunk entities,
+    /// instead of whole (read when maps spawn). Infinite maps then only show
+    /// tiles around viewers.
+    pub stream_tiles: bool,
+}
+
+impl Default for ChunkStreamingConfig {
+    fn default() -> Self {
+        Self {
+            load_radius: 1,
+            max_live_chunks: 64,
+            stream_tiles: false,
+        }
+    }
+}
+
+/// Marks entities that chunks of infinite maps are streamed around.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct ChunkViewer;
+
+/// The chunks of an infinite map, and where they are in the map's space.
+///
+/// Inserted on infinite maps when they spawn. Chunk coordinates are Tiled's:
+/// chunk `(x, y)` holds tiles `16x..16x+16` by `16y..16y+16`, Y down.
+#[derive(Component, Debug, Clone)]
+pub struct MapChunks {
+    /// Chunks saved in any tile layer of the map
+    pub chunks: HashSet<IVec2>,
+    /// The top-left chunk, at the origin of the layers' tile grids
+    pub origin: IVec2,
+    /// Height of the layers' tile grids in tiles
+    pub grid_height: u32,
+    /// Tile dimensions in world units
+    pub tile_size: Vec2,
+}
+
+impl MapChunks {
+    /// Index the chunks of an infinite map (`None` for finite maps).
+    pub fn from_map(map_asset: &TiledMapAsset) -> Option<Self> {
+        let map = &map_asset.map;
+        if !map.infinite() {
+            return None;
+        }
+        let mut chunks = HashSet::default();
+        collect_chunks(map.layers(), &mut chunks);
+        Some(Self {
+            chunks,
+            origin: IVec2::from(map_asset.topleft_chunk),
+            grid_height: map_asset.tilemap_size.y,
+            tile_size: Vec2::new(map.tile_width as f32, map.tile_height as f32),
+        })
+    }
+
+    /// The chunk at a position local to the map entity.
+    ///
+    /// Positions outside the saved chunks still get a chunk coordinate.
+    pub fn chunk_at(&self, local_position: Vec2) -> IVec2 {
+        let column = (local_position.x / self.tile_size.x).floor() as i32;
+        let row = (local_position.y / self.tile_size.y).floor() as i32;
+        // Grid rows are flipped: Tiled's y=0 is the top row
+        let tile = IVec2::new(column, self.grid_height as i32 - 1 - row);
+        (tile + self.origin * CHUNK_SIZE).div_euclid(IVec2::splat(CHUNK_SIZE))
+    }
+
+    /// The area covered by a chunk, local to the map entity.
+    pub fn chunk_rect(&self, chunk: IVec2) -> Rect {
+        let first_tile = (chunk - self.origin) * CHUNK_SIZE;
+        let min = Vec2::new(
+            first_tile.x as f32,
+            (self.grid_height as i32 - first_tile.y - CHUNK_SIZE) as f32,
+        ) * self.tile_size;
+        Rect::from_corners(min, min + self.tile_size * CHUNK_SIZE as f32)
+    }
+}
+
+fn collect_chunks<'a>(layers: impl Iterator<Item = tiled::Layer<'a>>, chunks: &mut HashSet<IVec2>) {
+    for layer in layers {
+        // Hidden layers aren't spawned
+        if !layer.visible {
+            continue;
+        }
+        match layer.layer_type() {
+            tiled::LayerType::Tiles(tiled::TileLayer::Infinite(infinite_layer)) => {
+                chunks.extend(infinite_layer.chunks().map(|(chunk, _)| IVec2::from(chunk)));
+            }
+            tiled::LayerType::Group(group) => collect_chunks(group.layers(), chunks),
+            _ => {}
+        }
+    }
+}
+
+/// The tiles of an infinite tile layer, per chunk, when tiles are streamed.
+///
+/// Inserted on the layer entity instead of [`TileLayerData`]; chunk entities
+/// spawn their tile layers from it.
+#[derive(Component, Debug, Clone)]
+pub struct StreamedTileLayer {
+    /// 16x16 tile grids by chunk coordinate (see [`MapChunks`])
+    pub chunks: HashMap<IVec2, TileLayerData>,
+    /// The group layer containing this layer (`None` for top-level layers)
+    pub parent_group: Option<Entity>,
+    /// How many groups the layer is nested in (0 for top-level layers)
+    pub depth: u32,
+    /// Layer properties, passed on to the chunks' [`TileLayerSpawned`] events
+    pub properties: Properties,
+}
+
+/// A live chunk of an infinite map.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TiledChunk {
+    /// The map the chunk belongs to
+    pub map_entity: Entity,
+    /// The chunk's coordinate (see [`MapChunks`])
+    pub chunk: IVec2,
+}
+
+/// A tile layer of a live chunk, holding the chunk's tiles of a
+/// [`StreamedTileLayer`].
+///
+/// A child of the [`TiledChunk`] entity, placed where the chunk is in the
+/// layer.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TiledChunkLayer {
+    /// The layer entity holding the [`StreamedTileLayer`]
+    pub layer_entity: Entity,
+}
+
+/// Fired when a chunk entity is spawned near a [`ChunkViewer`].
+///
+/// Fires after the [`TileLayerSpawned`] events of the chunk's tile layers.
+#[derive(EntityEvent, Message, Debug, Clone)]
+pub struct ChunkSpawned {
+    /// The chunk entity
+    #[event_target]
+    pub entity: Entity,
+    /// The map the chunk belongs to
+    pub map_entity: Entity,
+    /// The chunk's coordinate (see [`MapChunks`])
+    pub chunk: IVec2,
+}
+
+/// Fired when a chunk entity is evicted to stay within the live-chunk budget.
+///
+/// Observers run before the chunk entity and its tile layers are despawned,
+/// so their components are still there.
+#[derive(EntityEvent, Message, Debug, Clone)]
+pub struct ChunkEvicted {
+    /// The chunk entity
+    #[event_target]
+    pub entity: Entity,
+    /// The map the chunk belongs to
+    pub map_entity: Entity,
+    /// The chunk's coordinate (see [`MapChunks`])
+    pub chunk: IVec2,
+}
+
+/// Live chunk entities and when they were last in view.
+#[derive(Resource, Debug, Default)]
+pub struct LiveChunks {
+    chunks: HashMap<(Entity, IVec2), LiveChunk>,
+    frame: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LiveChunk {
+    entity: Entity,
+    last_viewed: u64,
+}
+
+impl LiveChunks {
+    /// Number of live chunk entities.
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Whether no chunk entities are live.
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// The entity of a live chunk.
+    pub fn get(&self, map_entity: Entity, chunk: IVec2) -> Option<Entity> {
+        self.chunks
+            .get(&(map_entity, chunk))
+            .map(|live| live.entity)
+    }
+}
+
+/// Streamed tile layers with what their chunks' tile layers copy.
+type StreamedLayers<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut StreamedTileLayer,
+        &'static TiledLayerMapOf,
+        &'static GlobalTransform,
+        &'static LayerId,
+        &'static LayerName,
+        Option<&'static LayerClass>,
+    ),
+>;
+
+/// Observer indexing the chunks of infinite maps when they spawn.
+pub fn on_map_spawned_index_chunks(
+    spawned: On<MapSpawned>,
+    map_query: Query<&TiledMap>,
+    map_assets: Res<Assets<TiledMapAsset>>,
+    mut commands: Commands,
+) {
+    let Some(map_asset) = map_query
+        .get(spawned.entity)
+        .ok()
+        .and_then(|tiled_map| map_assets.get(&tiled_map.handle))
+    else {
+        return;
+    };
+    if let Some(chunks) = MapChunks::from_map(map_asset) {
+        commands.entity(spawned.entity).insert(chunks);
+    }
+}
+
+/// Observer forgetting chunks despawned other than by eviction (e.g. with their map).
+pub fn on_chunk_removed(
+    removed: On<Remove, TiledChunk>,
+    chunk_query: Query<&TiledChunk>,
+    mut live_chunks: ResMut<LiveChunks>,
+) {
+    let Ok(chunk) = chunk_query.get(removed.entity) else {
+        return;
+    };
+    let key = (chunk.map_entity, chunk.chunk);
+    if live_chunks
+        .chunks
+        .get(&key)
+        .is_some_and(|live| live.entity == removed.entity)
+    {
+        live_chunks.chunks.remove(&key);
+    }
+}
+
+/// Spawn the chunks around [`ChunkViewer`]s and evict the least recently viewed
+/// ones beyond [`ChunkStreamingConfig::max_live_chunks`].
+pub fn stream_map_chunks(
+    config: Res<ChunkStreamingConfig>,
+    mut live_chunks: ResMut<LiveChunks>,
+    viewers: Query<&GlobalTransform, With<ChunkViewer>>,
+    maps: Query<(Entity, &MapChunks, &GlobalTransform)>,
+    mut layers: StreamedLayers,
+    chunk_layers: Query<(&TiledChunkLayer, &TileLayerData)>,
+    children: Query<&Children>,
+    mut commands: Commands,
+) {
+    if viewers.is_empty() && live_chunks.is_empty() {
+        return;
+    }
+    live_chunks.frame += 1;
+    let frame = live_chunks.frame;
+    let radius = config.load_radius as i32;
+
+    for (map_entity, map_chunks, map_transform) in &maps {
+        let to_local = map_transform.affine().inverse();
+        for viewer in &viewers {
+            let local = to_local.transform_point3(viewer.translation()).truncate();
+            let center = map_chunks.chunk_at(local);
+            for y in -radius..=radius {
+                for x in -radius..=radius {
+                    let chunk = center + IVec2::new(x, y);
+                    if !map_chunks.chunks.contains(&chunk) {
+                        continue;
+                    }
+                    let live = live_chunks
+                        .chunks
+                        .entry((map_entity, chunk))
+                        .or_insert_with(|| LiveChunk {
+                            entity: spawn_chunk(
+                                &mut commands,
+                                map_entity,
+                                map_chunks,
+                                to_local,
+                                &layers,
+                                chunk,
+                            ),
+                            last_viewed: frame,
+                        });
+                    live.last_viewed = frame;
+                }
+            }
+        }
+    }
+
+    let excess = live_chunks.len().saturating_sub(config.max_live_chunks);
+    if excess == 0 {
+        return;
+    }
+    let mut out_of_view: Vec<_> = live_chunks
+        .chunks
+        .iter()
+        .filter(|(_, live)| live.last_viewed < frame)
+        .map(|(key, live)| (live.last_viewed, *key))
+        .collect();
+    out_of_view.sort_unstable_by_key(|(last_viewed, (map_entity, chunk))| {
+        (*last_viewed, *map_entity, chunk.x, chunk.y)
+    });
+    for (_, (map_entity, chunk)) in out_of_view.into_iter().take(excess) {
+        let Some(live) = live_chunks.chunks.remove(&(map_entity, chunk)) else {
+            continue;
+        };
+        // Keep tile changes for when the chunk streams back in
+        for &child in children.get(live.entity).into_iter().flatten() {
+            let Ok((chunk_layer, tile_data)) = chunk_layers.get(child) else {
+                continue;
+            };
+            if let Ok((_, mut streamed, ..)) = layers.get_mut(chunk_layer.layer_entity) {
+                streamed.chunks.insert(chunk, tile_data.clone());
+            }
+        }
+        trigger_spawn_event(
+            &mut commands,
+            ChunkEvicted {
+                entity: live.entity,
+                map_entity,
+                chunk,
+            },
+        );
+        commands.entity(live.entity).try_despawn();
+    }
+}
+
+/// Spawn a chunk entity with a tile layer per streamed layer with tiles in it.
+fn spawn_chunk(
+    commands: &mut Commands,
+    map_entity: Entity,
+    map_chunks: &MapChunks,
+    to_map_local: Affine3A,
+    layers: &StreamedLayers,
+    chunk: IVec2,
+) -> Entity {
+    let rect = map_chunks.chunk_rect(chunk);
+    let center = rect.center();
+    let entity = commands
+        .spawn((
+            TiledChunk { map_entity, chunk },
+            Transform::from_translation(center.extend(0.0)),
+            Visibility::default(),
+            Name::new(format!("Chunk ({}, {})", chunk.x, chunk.y)),
+            ChildOf(map_entity),
+        ))
+        .id();
+
+    for (layer_entity, streamed, map_of, layer_transform, layer_id, layer_name, class) in layers {
+        if map_of.0 != map_entity {
+            continue;
+        }
+        let Some(tile_data) = streamed.chunks.get(&chunk) else {
+            continue;
+        };
+        // Where the chunk's corner is in the layer, relative to the chunk entity
+        let placement = Affine3A::from_translation(-center.extend(0.0))
+            * to_map_local
+            * layer_transform.affine()
+            * Affine3A::from_translation(rect.min.extend(0.0));
+        let mut chunk_layer = commands.spawn((
+            TiledLayer::Tiles,
+            TiledChunkLayer { layer_entity },
+            *layer_id,
+            layer_name.clone(),
+            tile_data.clone(),
+            Transform::from_matrix(placement.into()),
+            Name::new(format!(
+                "Layer: {} ({}, {})",
+                layer_name.0, chunk.x, chunk.y
+            )),
+            ChildOf(entity),
+        ));
+        if let Some(class) = class {
+            chunk_layer.insert(class.clone());
+        }
+        let chunk_layer = chunk_layer.id();
+        trigger_spawn_event(
+            commands,
+            TileLayerSpawned {
+                entity: chunk_layer,
+                map_entity,
+                layer_id: layer_id.0,
+                parent_group: streamed.parent_group,
+                depth: streamed.depth,
+                properties: streamed.properties.clone(),
+            },
+        );
+    }
+
+    trigger_spawn_event(
+        commands,
+        ChunkSpawned {
+            entity,
+            map_entity,
+            chunk,
+        },
+    );
+    entity
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::asset::AssetPlugin;
+    use bevy_tiledmap_assets::{TiledmapAssetsPlugin, embedded};
+
+    use crate::plugin::TiledmapCorePlugin;
+
+    use super::*;
+
+    /// Chunk events in the order observers saw them.
+    #[derive(Resource, Default)]
+    struct ChunkEvents(Vec<(&'static str, IVec2)>);
+
+    fn test_map_chunks(chunks: impl IntoIterator<Item = IVec2>) -> MapChunks {
+        MapChunks {
+            chunks: chunks.into_iter().collect(),
+            origin: IVec2::new(-1, -1),
+            grid_height: 32,
+            tile_size: Vec2::splat(8.0),
+        }
+    }
+
+    /// A 16x16 chunk of CSV tile data with `gid` at the given chunk-local tiles.
+    fn csv_chunk(chunk: IVec2, tiles: &[(usize, usize)], gid: u32) -> String {
+        let mut data = vec![0; 256];
+        for &(x, y) in tiles {
+            data[y * 16 + x] = gid;
+        }
+        let data: Vec<_> = data.iter().map(u32::to_string).collect();
+        format!(
+            r#"<chunk x="{}" y="{}" width="16" height="16">{}</chunk>"#,
+            chunk.x * 16,
+            chunk.y * 16,
+            data.join(",")
+        )
+    }
+
+    /// Spawn an infinite map from memory with the core plugins, returning the
+    /// app and the map entity once its chunks are indexed.
+    fn spawn_infinite_map(config: ChunkStreamingConfig, map: String) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            TransformPlugin,
+            TiledmapAssetsPlugin,
+            TiledmapCorePlugin::default(),
+        ))
+        .init_asset::<Image>()
+        .insert_resource(config);
+        embedded::embed(
+            &mut app,
+            "chunks/infinite.tmx",
+            "infinite.tmx",
+            map.leak().as_bytes(),
+        );
+        let handle = app
+            .world()
+            .resource::<AssetServer>()
+            .load("embedded://chunks/infinite.tmx");
+        let map = app.world_mut().spawn(TiledMap { handle }).id();
+        for _ in 0..1000 {
+            if app.world().get::<MapChunks>(map).is_some() {
+                break;
+            }
+            app.update();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(
+            app.world().get::<MapChunks>(map).is_some(),
+            "infinite map was not indexed"
+        );
+        (app, map)
+    }
+
+    /// Move the viewer to the center of a chunk and run until its chunks spawned.
+    fn view_chunk(app: &mut App, viewer: Entity, map: Entity, chunk: IVec2) {
+        let center = app
+            .world()
+            .get::<MapChunks>(map)
+            .unwrap()
+            .chunk_rect(chunk)
+            .center();
+        *app.world_mut().get_mut::<Transform>(viewer).unwrap() =
+            Transform::from_translation(center.extend(0.0));
+        app.update();
+        // Chunks spawn after transform propagation
+        app.update();
+    }
+
+    #[test]
+    fn test_chunk_geometry() {
+        let map_chunks = test_map_chunks([]);
+
+        // The top-left chunk covers the top half of the 32-tile-high grid
+        let top_left = map_chunks.chunk_rect(IVec2::new(-1, -1));
+        assert_eq!(top_left.min, Vec2::new(0.0, 128.0));
+        assert_eq!(top_left.max, Vec2::new(128.0, 256.0));
+        assert_eq!(
+            map_chunks.chunk_at(Vec2::new(4.0, 252.0)),
+            IVec2::new(-1, -1)
+        );
+        assert_eq!(map_chunks.chunk_at(Vec2::new(130.0, 4.0)), IVec2::new(0, 0));
+        // Positions past the grid still map to chunks
+        assert_eq!(map_chunks.chunk_at(Vec2::new(-1.0, 4.0)), IVec2::new(-2, 0));
+        for chunk in [IVec2::new(-1, -1), IVec2::new(0, 0), IVec2::new(3, -2)] {
+            let center = map_chunks.chunk_rect(chunk).center();
+            assert_eq!(map_chunks.chunk_at(center), chunk);
+        }
+    }
+
+    #[test]
+    fn test_least_recently_viewed_chunks_are_evicted() {
+        let mut app = App::new();
+        app.insert_resource(ChunkStreamingConfig {
+            load_radius: 0,
+            max_live_chunks: 3,
+            ..default()
+        })
+        .init_resource::<LiveChunks>()
+        .init_resource::<ChunkEvents>()
+        .add_message::<ChunkSpawned>()
+        .add_message::<ChunkEvicted>()
+        .add_observer(on_chunk_removed)
+        .add_observer(
+            |spawned: On<ChunkSpawned>, mut events: ResMut<ChunkEvents>| {
+                events.0.push(("spawned", spawned.chunk));
+            },
+        )
+        .add_observer(
+            |evicted: On<ChunkEvicted>,
+             chunks: Query<&TiledChunk>,
+             mut events: ResMut<ChunkEvents>| {
+                // The chunk entity still exists for observers
+                assert_eq!(chunks.get(evicted.entity).unwrap().chunk, evicted.chunk);
+                events.0.push(("evicted", evicted.chunk));
+            },
+        )
+        .add_systems(Update, stream_map_chunks);
+
+        // A row of chunks along the top of the map
+        let map_chunks = test_map_chunks((-1..6).map(|x| IVec2::new(x, -1)));
+        let map = app
+            .world_mut()
+            .spawn((map_chunks.clone(), GlobalTransform::default()))
+            .id();
+        let viewer = app
+            .world_mut()
+            .spawn((ChunkViewer, GlobalTransform::default()))
+            .id();
+        let view = |app: &mut App, x: i32| {
+            let center = map_chunks.chunk_rect(IVec2::new(x, -1)).center();
+            *app.world_mut().get_mut::<GlobalTransform>(viewer).unwrap() =
+                GlobalTransform::from_translation(center.extend(0.0));
+            app.update();
+            app.world_mut()
+                .resource_mut::<ChunkEvents>()
+                .0
+                .drain(..)
+                .collect::<Vec<_>>()
+        };
+        let chunk = |x| IVec2::new(x, -1);
+
+        for x in -1..2 {
+            assert_eq!(view(&mut app, x), [("spawned", chunk(x))]);
+        }
+        // Viewing chunk -1 again makes 0 the least recently viewed
+        assert!(view(&mut app, -1).is_empty());
+        assert_eq!(
+            view(&mut app, 2),
+            [("spawned", chunk(2)), ("evicted", chunk(0))]
+        );
+        assert_eq!(
+            view(&mut app, 3),
+            [("spawned", chunk(3)), ("evicted", chunk(1))]
+        );
+
+        let live = app.world().resource::<LiveChunks>();
+        assert_eq!(live.len(), 3);
+        assert!(live.get(map, chunk(0)).is_none());
+        let kept = live.get(map, chunk(-1)).unwrap();
+        let world = app.world_mut();
+        assert_eq!(world.query::<&TiledChunk>().iter(world).count(), 3);
+        assert_eq!(world.get::<ChildOf>(kept).map(ChildOf::parent), Some(map));
+        assert_eq!(
+            world.resource::<Messages<ChunkEvicted>>().len(),
+            2,
+            "evictions are also written as messages"
+        );
+
+        // Despawning the map forgets its chunks
+        app.world_mut().entity_mut(map).despawn();
+        assert!(app.world().resource::<LiveChunks>().is_empty());
+    }
+
+    #[test]
+    fn test_chunks_stream_on_spawned_infinite_maps() {
+        let chunk = |x: i32, y: i32| csv_chunk(IVec2::new(x, y), &[], 0);
+        let map = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" renderorder="right-down" width="30" height="20" tilewidth="8" tileheight="8" infinite="1" nextlayerid="5" nextobjectid="1">
+ <layer id="1" name="Ground" width="30" height="20">
+  <data encoding="csv">{}{}</data>
+ </layer>
+ <group id="2" name="Decor">
+  <layer id="3" name="Props" width="30" height="20">
+   <data encoding="csv">{}</data>
+  </layer>
+ </group>
+ <layer id="4" name="Hidden" width="30" height="20" visible="0">
+  <data encoding="csv">{}</data>
+ </layer>
+</map>"#,
+            chunk(-1, -1),
+            chunk(1, 0),
+            chunk(0, 0),
+            chunk(2, 2),
+        );
+
+        let (mut app, map) = spawn_infinite_map(
+            ChunkStreamingConfig {
+                load_radius: 0,
+                max_live_chunks: 8,
+                ..default()
+            },
+            map,
+        );
+
+        let map_chunks = app.world().get::<MapChunks>(map).unwrap().clone();
+        // Chunks of hidden layers aren't indexed
+        assert_eq!(
+            map_chunks.chunks,
+            HashSet::from_iter([IVec2::new(-1, -1), IVec2::new(1, 0), IVec2::new(0, 0)])
+        );
+        assert_eq!(map_chunks.origin, IVec2::new(-1, -1));
+
+        let viewer = app
+            .world_mut()
+            .spawn((ChunkViewer, Transform::default()))
+            .id();
+        view_chunk(&mut app, viewer, map, IVec2::new(1, 0));
+        let center = map_chunks.chunk_rect(IVec2::new(1, 0)).center();
+
+        let world = app.world_mut();
+        let chunks: Vec<_> = world
+            .query::<(&TiledChunk, &GlobalTransform)>()
+            .iter(world)
+            .map(|(chunk, transform)| (*chunk, transform.translation().truncate()))
+            .collect();
+        assert_eq!(
+            chunks,
+            [(
+                TiledChunk {
+                    map_entity: map,
+                    chunk: IVec2::new(1, 0),
+                },
+                center
+            )]
+        );
+    }
+
+    #[test]
+    fn test_evicted_chunks_despawn_their_tiles() {
+        let map = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" renderorder="right-down" width="30" height="20" tilewidth="8" tileheight="8" infinite="1" nextlayerid="4" nextobjectid="1">
+ <tileset firstgid="1" name="Tiles" tilewidth="8" tileheight="8" tilecount="1" columns="0">
+  <grid orientation="orthogonal" width="1" height="1"/>
+  <tile id="0"/>
+ </tileset>
+ <layer id="1" name="Ground" width="30" height="20">
+  <data encoding="csv">{}{}</data>
+ </layer>
+ <group id="2" name="Decor">
+  <layer id="3" name="Props" width="30" height="20">
+   <data encoding="csv">{}</data>
+  </layer>
+ </group>
+</map>"#,
+            csv_chunk(IVec2::new(0, 0), &[(0, 0), (1, 0), (2, 3)], 1),
+            csv_chunk(IVec2::new(1, 0), &[(5, 5)], 1),
+            csv_chunk(IVec2::new(0, 0), &[(4, 4)], 1),
+        );
+        let (mut app, map) = spawn_infinite_map(
+            ChunkStreamingConfig {
+                load_radius: 0,
+                max_live_chunks: 1,
+                stream_tiles: true,
+            },
+            map,
+        );
+
+        // Streamed layers keep their tiles per chunk instead of spawning them
+        let world = app.world_mut();
+        let mut streamed = world.query::<(Entity, &LayerName, &StreamedTileLayer)>();
+        let (ground, _, ground_tiles) = streamed
+            .iter(world)
+            .find(|(_, name, _)| name.0 == "Ground")
+            .unwrap();
+        assert_eq!(streamed.iter(world).count(), 2);
+        assert!(world.get::<TileLayerData>(ground).is_none());
+        assert_eq!(
+            ground_tiles.chunks[&IVec2::new(0, 0)].iter_tiles().count(),
+            3
+        );
+
+        let viewer = app
+            .world_mut()
+            .spawn((ChunkViewer, Transform::default()))
+            .id();
+        view_chunk(&mut app, viewer, map, IVec2::new(0, 0));
+        let first_chunk = app
+            .world()
+            .resource::<LiveChunks>()
+            .get(map, IVec2::new(0, 0))
+            .unwrap();
+        let world = app.world_mut();
+        let mut chunk_layers = world.query::<(
+            Entity,
+            &TiledChunkLayer,
+            &TileLayerData,
+            &ChildOf,
+            &GlobalTransform,
+        )>();
+        let spawned: Vec<_> = chunk_layers
+            .iter(world)
+            .map(|(entity, chunk_layer, tiles, child_of, transform)| {
+                assert_eq!(child_of.parent(), first_chunk);
+                assert_eq!((tiles.width, tiles.height), (16, 16));
+                (
+                    entity,
+                    chunk_layer.layer_entity,
+                    tiles.iter_tiles().count(),
+                    transform.translation(),
+                )
+            })
+            .collect();
+        assert_eq!(spawned.len(), 2, "one tile layer per streamed layer");
+        let (ground_layer, _, ground_count, ground_position) = spawned
+            .iter()
+            .copied()
+            .find(|(_, layer, ..)| *layer == ground)
+            .unwrap();
+        assert_eq!(ground_count, 3);
+        // The chunk's tiles sit where they do in the whole layer
+        let rect = app
+            .world()
+            .get::<MapChunks>(map)
+            .unwrap()
+            .chunk_rect(IVec2::new(0, 0));
+        let layer_z = app.world().get::<Transform>(ground).unwrap().translation.z;
+        assert_eq!(ground_position, rect.min.extend(layer_z));
+
+        // Change a tile, then evict the chunk by viewing another one
+        app.world_mut()
+            .get_mut::<TileLayerData>(ground_layer)
+            .unwrap()
+            .set(0, 0, None);
+        view_chunk(&mut app, viewer, map, IVec2::new(1, 0));
+        for (entity, ..) in &spawned {
+            assert!(
+                app.world().get_entity(*entity).is_err(),
+                "evicted chunk's tile layers are despawned"
+            );
+        }
+        assert!(app.world().get_entity(first_chunk).is_err());
+        let world = app.world_mut();
+        assert_eq!(chunk_layers.iter(world).count(), 1);
+        assert_eq!(
+            world.resource::<Messages<TileLayerSpawned>>().len(),
+            3,
+            "each chunk's tile layers fire TileLayerSpawned"
+        );
+
+        // The change survives the chunk streaming back in
+        view_chunk(&mut app, viewer, map, IVec2::new(0, 0));
+        let world = app.world_mut();
+        let (_, _, tiles, ..) = chunk_layers
+            .iter(world)
+            .find(|(_, chunk_layer, ..)| chunk_layer.layer_entity == ground)
+            .unwrap();
+        assert_eq!(tiles.iter_tiles().count(), 2);
+        assert!(tiles.get(0, 0).is_none());
+    }
+}
@@ -26,9 +26,27 @@ pub enum TiledLayer {
 #[reflect(Component)]
 pub struct LayerId(pub u32);
 
+/// Tiled's original layer name.
+///
+/// Unlike the entity's `Name` (which is prefixed with `"Layer: "`), this holds the
+/// name exactly as written in Tiled, for lookups such as [`LayerSelector::Name`](crate::visibility::LayerSelector::Name).
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct LayerName(pub String);
+
+/// Tiled class of a layer.
+///
+/// Only inserted on layers with a class, so plugins can key behavior off it
+/// (e.g. physics colliders for layers classed `"Solid"`).
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct LayerClass(pub String);
+
 /// Image layer data component.
 ///
-/// Attached to image layer entities. Layer 3 rendering plugins add Sprite components.
+/// Attached to image layer entities. Layer 3 rendering plugins add Sprite components;
+/// custom rendering or physics can be built from the image handle, size, offset and
+/// repeat flags here instead.
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component)]
 pub struct ImageLayerData {
@@ -41,6 +59,18 @@ pub struct ImageLayerData {
     /// Image height (if specified in Tiled, otherwise use image dimensions)
     pub height: Option<f32>,
 
+    /// Size of the image in pixels, as recorded in the map
+    pub size: Vec2,
+
+    /// The layer's offset in pixels (Tiled coordinates, Y-down)
+    pub offset: Vec2,
+
+    /// Whether the image repeats horizontally
+    pub repeat_x: bool,
+
+    /// Whether the image repeats vertically
+    pub repeat_y: bool,
+
     /// Tint color for the image layer (from Tiled's tintcolor attribute)
     pub tint_color: Option<Color>,
 
@@ -3,6 +3,8 @@
 use bevy::prelude::*;
 use bevy_tiledmap_assets::prelude::{TiledMapAsset, TiledWorldAsset};
 
+use crate::properties::TiledColor;
+
 /// Marker component for scene roots (both maps and worlds).
 ///
 /// This component is automatically added to both `TiledMap` and `TiledWorld` entities,
@@ -71,6 +73,17 @@ pub struct TiledMap {
     pub handle: Handle<TiledMapAsset>,
 }
 
+/// Instance number of a map among all entities spawned from the same `.tmx`.
+///
+/// Every `TiledMap` entity spawns its own independent hierarchy, so the same
+/// map can be spawned many times (e.g. repeated dungeon rooms). Instances are
+/// numbered from 0 in spawn order, starting over once every instance of the
+/// map is despawned; insert this component yourself alongside `TiledMap` to
+/// choose the number (for example to keep save files stable).
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub struct MapInstanceId(pub u32);
+
 // ===== RELATIONSHIP COMPONENTS =====
 //
 // These components implement bidirectional relationships using Bevy's relationship system.
@@ -122,6 +135,106 @@ pub struct TiledWorldOf(pub Entity);
 #[reflect(Component)]
 pub struct MapsInWorld(pub Vec<Entity>);
 
+/// Properties a world gives one of its maps.
+///
+/// Inserted on the map entities a `TiledWorld` spawns, from the world's custom
+/// properties ([`TiledWorldAsset::properties`] and
+/// [`TiledWorldAsset::map_properties`]). The map's properties are merged in
+/// order: `inherited`, the map's own properties, then `overrides`; the result
+/// is the map's `MergedProperties`, and what its class component is read from.
+#[derive(Component, Debug, Clone, Default)]
+pub struct WorldMapProperties {
+    /// The world's properties
+    pub inherited: tiled::Properties,
+    /// The world's properties for this map
+    pub overrides: tiled::Properties,
+}
+
+impl WorldMapProperties {
+    /// Merge a map's own properties between the inherited ones and the overrides.
+    pub fn merge(&self, map_properties: &tiled::Properties) -> tiled::Properties {
+        let mut properties = self.inherited.clone();
+        properties.extend(map_properties.clone());
+        properties.extend(self.overrides.clone());
+        properties
+    }
+}
+
+// ===== METADATA COMPONENT =====
+
+/// Orientation of a Tiled map's grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum MapOrientation {
+    /// Square grid
+    Orthogonal,
+    /// Diamond-shaped isometric grid
+    Isometric,
+    /// Staggered isometric grid
+    Staggered,
+    /// Hexagonal grid
+    Hexagonal,
+}
+
+impl From<tiled::Orientation> for MapOrientation {
+    fn from(orientation: tiled::Orientation) -> Self {
+        match orientation {
+            tiled::Orientation::Orthogonal => Self::Orthogonal,
+            tiled::Orientation::Isometric => Self::Isometric,
+            tiled::Orientation::Staggered => Self::Staggered,
+            tiled::Orientation::Hexagonal => Self::Hexagonal,
+        }
+    }
+}
+
+/// Metadata of a spawned map, copied from its `TiledMapAsset`.
+///
+/// Attached to map entities during spawning, so systems can read a map's
+/// dimensions without going through `Assets<TiledMapAsset>`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_tiledmap_core::components::map::TiledMapInfo;
+/// fn log_maps(map_query: Query<&TiledMapInfo, Added<TiledMapInfo>>) {
+///     for info in &map_query {
+///         info!("{}: {}x{} tiles", info.path, info.size.x, info.size.y);
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct TiledMapInfo {
+    /// Map dimensions in tiles (for infinite maps, as saved by Tiled)
+    pub size: UVec2,
+    /// Tile dimensions in pixels
+    pub tile_size: UVec2,
+    /// Grid orientation
+    pub orientation: MapOrientation,
+    /// Whether the map is infinite (chunk-based)
+    pub infinite: bool,
+    /// Background color, if set in Tiled
+    pub background_color: Option<Color>,
+    /// Asset path of the map file
+    pub path: String,
+}
+
+impl TiledMapInfo {
+    /// Read the metadata of a map loaded from `path`.
+    pub fn new(map: &tiled::Map, path: impl Into<String>) -> Self {
+        Self {
+            size: UVec2::new(map.width, map.height),
+            tile_size: UVec2::new(map.tile_width, map.tile_height),
+            orientation: map.orientation.into(),
+            infinite: map.infinite(),
+            background_color: map
+                .background_color
+                .map(|color| TiledColor(color).to_color()),
+            path: path.into(),
+        }
+    }
+}
+
 // ===== GEOMETRY COMPONENT =====
 
 /// World-space geometry information for a Tiled map.
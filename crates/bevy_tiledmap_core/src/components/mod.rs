@@ -6,10 +6,14 @@ pub mod object;
 pub mod tile;
 
 // Re-export commonly used components
-pub use layer::{ImageLayerData, LayerId, TiledLayer};
+pub use layer::{ImageLayerData, LayerClass, LayerId, LayerName, TiledLayer};
 pub use map::{
-    LayersInMap, MapGeometry, MapsInWorld, ObjectsInMap, TiledLayerMapOf, TiledMap,
-    TiledObjectMapOf, TiledSceneRoot, TiledWorld, TiledWorldOf,
+    LayersInMap, MapGeometry, MapInstanceId, MapOrientation, MapsInWorld, ObjectsInMap,
+    TiledLayerMapOf, TiledMap, TiledMapInfo, TiledObjectMapOf, TiledSceneRoot, TiledWorld,
+    TiledWorldOf, WorldMapProperties,
 };
-pub use object::{ObjectId, TiledObject};
-pub use tile::{TileInstance, TileLayerData};
+pub use object::{
+    ObjectClass, ObjectId, ObjectName, ObjectPlacement, TextHorizontalAlign, TextVerticalAlign,
+    TiledObject, TiledObjectRef,
+};
+pub use tile::{TileClassGrid, TileInstance, TileLayerData};
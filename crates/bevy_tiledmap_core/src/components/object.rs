@@ -10,13 +10,83 @@ use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
 #[reflect(Component)]
 pub struct ObjectId(pub u32);
 
+/// Tiled's original object name.
+///
+/// Unlike the entity's `Name` (which is prefixed with `"Object: "`), this holds the
+/// name exactly as written in Tiled. Object names aren't required to be unique.
+/// See [`crate::names`] for lookups by name.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct ObjectName(pub String);
+
+/// Tiled class of an object (tile objects without a class inherit the tile's class).
+///
+/// Only inserted on objects with a class.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct ObjectClass(pub String);
+
+/// A reference to another object in the same map (Tiled's `object` property type).
+///
+/// Holds the referenced object's Tiled ID; match it against [`ObjectId`] to find
+/// the entity. Use `Option<TiledObjectRef>` for `TiledClass` fields, since Tiled
+/// stores an unset reference as `0`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub struct TiledObjectRef(pub u32);
+
+/// An object's position and rotation exactly as stored in Tiled.
+///
+/// The position is in Tiled pixels (Y-down) within the object's layer, before
+/// any conversion to Bevy coordinates; for tile objects it is the alignment
+/// anchor rather than the top-left corner. The entity's `Transform`
+/// holds the converted placement.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ObjectPlacement {
+    /// Tiled pixel position (Y-down)
+    pub position: Vec2,
+    /// Clockwise rotation in degrees
+    pub rotation: f32,
+}
+
+/// Horizontal alignment of a text object's text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum TextHorizontalAlign {
+    /// Align to the left edge
+    #[default]
+    Left,
+    /// Center horizontally
+    Center,
+    /// Align to the right edge
+    Right,
+    /// Stretch lines to both edges
+    Justify,
+}
+
+/// Vertical alignment of a text object's text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum TextVerticalAlign {
+    /// Align to the top edge
+    #[default]
+    Top,
+    /// Center vertically
+    Center,
+    /// Align to the bottom edge
+    Bottom,
+}
+
 /// Object component with pre-computed shape data.
 ///
 /// Vertices are pre-computed during spawning (NOT raw points from Tiled).
 /// Layer 3 physics/rendering plugins can use this data directly without recomputation.
+///
+/// Every variant keeps all of the shape's Tiled data, so plugins never need to
+/// re-read the map asset; the object's original position and rotation are in
+/// [`ObjectPlacement`]. New variants or fields may be added as Tiled grows.
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component)]
 #[require(Transform, Visibility)]
+#[non_exhaustive]
 pub enum TiledObject {
     /// Point object (no dimensions)
     Point,
@@ -52,10 +122,62 @@ pub enum TiledObject {
 
         /// Object height (may differ from tile height)
         height: f32,
+
+        /// Whether the tile is flipped horizontally
+        flip_h: bool,
+
+        /// Whether the tile is flipped vertically
+        flip_v: bool,
+
+        /// Whether the tile is flipped diagonally
+        flip_d: bool,
     },
 
-    /// Text object (placeholder for now)
+    /// Text object
+    ///
+    /// Centered like a rectangle object. The text is localized at spawn time
+    /// when it is a localization key (see [`crate::localization`]).
     Text {
-        // Phase 3+: text content, font, alignment, etc.
+        /// Text content
+        text: String,
+
+        /// Font size in pixels
+        pixel_size: f32,
+
+        /// Text color
+        color: Color,
+
+        /// Text box width
+        width: f32,
+
+        /// Text box height
+        height: f32,
+
+        /// Font family name (empty for the default font)
+        font_family: String,
+
+        /// Whether lines wrap at the text box width
+        wrap: bool,
+
+        /// Bold text
+        bold: bool,
+
+        /// Italic text
+        italic: bool,
+
+        /// Underlined text
+        underline: bool,
+
+        /// Struck-out text
+        strikeout: bool,
+
+        /// Whether kerning is used
+        kerning: bool,
+
+        /// Horizontal alignment in the text box
+        halign: TextHorizontalAlign,
+
+        /// Vertical alignment in the text box
+        valign: TextVerticalAlign,
     },
 }
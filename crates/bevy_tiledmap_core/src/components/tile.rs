@@ -139,7 +139,7 @@ impl TileLayerData {
 /// Pre-processed tile data (NOT a component, stored in `TileLayerData`).
 ///
 /// Contains all data needed for rendering and physics, pre-resolved from the map.
-#[derive(Debug, Clone, Reflect)]
+#[derive(Debug, Clone, PartialEq, Reflect)]
 pub struct TileInstance {
     /// Original GID from map (for reference/debugging)
     pub gid: u32,
@@ -158,4 +158,204 @@ pub struct TileInstance {
 
     /// Diagonal flip flag (used for rotation in some contexts)
     pub flipped_d: bool,
+
+    /// Visual variant drawn instead of `tile_id`, chosen at spawn time
+    /// (see [`crate::variation`]). Collisions and properties still use `tile_id`.
+    pub variant: Option<u32>,
+}
+
+impl TileInstance {
+    /// Local tile ID to draw: the variant if one was chosen, else `tile_id`.
+    pub fn render_tile_id(&self) -> u32 {
+        self.variant.unwrap_or(self.tile_id)
+    }
+}
+
+/// Compact grid of tile classes for a tile layer (Tiled's equivalent of an `LDtk` `IntGrid`).
+///
+/// Each cell stores a small index into a palette of class names, derived from the
+/// class set on each tile in its tileset (e.g. `Water`, `Lava`, `Grass`). Attached
+/// to tile layer entities next to [`TileLayerData`] when any tile has a class, so
+/// gameplay rules can sample the grid in O(1) without touching tileset assets.
+///
+/// Kept in sync with [`TileLayerData`] edits by [`sync_tile_class_grids`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_tiledmap_core::components::tile::TileClassGrid;
+/// fn swimming(layers: Query<&TileClassGrid>, player: Single<&Transform>) {
+///     let position = player.translation.truncate();
+///     for grid in &layers {
+///         if grid.get_at(position, Vec2::splat(16.0)) == Some("Water") {
+///             // apply swimming physics
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct TileClassGrid {
+    /// Grid width in tiles
+    pub width: u32,
+
+    /// Grid height in tiles
+    pub height: u32,
+
+    /// Class names; cell value `i` (for `i > 0`) refers to `classes[i - 1]`
+    pub classes: Vec<String>,
+
+    /// Flattened grid of class indices: index = y * width + x
+    /// 0 = empty tile or tile without a class
+    pub cells: Vec<u16>,
+}
+
+impl TileClassGrid {
+    /// Build a class grid from tile data, looking up each tile's class in its tileset.
+    ///
+    /// Tiles whose tileset isn't loaded are treated as having no class.
+    pub fn from_tile_data(data: &TileLayerData, tilesets: &Assets<TiledTilesetAsset>) -> Self {
+        let mut grid = Self {
+            width: data.width,
+            height: data.height,
+            classes: Vec::new(),
+            cells: vec![0; data.tiles.len()],
+        };
+
+        for (cell, tile) in grid.cells.iter_mut().zip(&data.tiles) {
+            let class = tile.as_ref().and_then(|tile| {
+                let tileset = tilesets.get(&tile.tileset_handle)?;
+                let user_type = tileset.tileset.get_tile(tile.tile_id)?.user_type.clone()?;
+                (!user_type.is_empty()).then_some(user_type)
+            });
+            if let Some(class) = class {
+                *cell = match grid.classes.iter().position(|c| *c == class) {
+                    Some(position) => position as u16 + 1,
+                    None => {
+                        grid.classes.push(class);
+                        grid.classes.len() as u16
+                    }
+                };
+            }
+        }
+
+        grid
+    }
+
+    /// Check if no tile in the grid has a class.
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    /// Get the palette index for a class name (`None` if no tile has this class).
+    pub fn class_index(&self, class: &str) -> Option<u16> {
+        self.classes
+            .iter()
+            .position(|c| c == class)
+            .map(|position| position as u16 + 1)
+    }
+
+    /// Get the raw class index at a grid position (0 if out of bounds or unclassed).
+    pub fn index(&self, x: u32, y: u32) -> u16 {
+        if x >= self.width || y >= self.height {
+            return 0;
+        }
+        self.cells
+            .get((y * self.width + x) as usize)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Get the class name at a grid position.
+    ///
+    /// Coordinates are in Tiled's grid (Y-down, origin at top-left).
+    pub fn get(&self, x: u32, y: u32) -> Option<&str> {
+        let index = self.index(x, y);
+        (index > 0).then(|| self.classes[index as usize - 1].as_str())
+    }
+
+    /// Check if the tile at a grid position has the given class.
+    pub fn is(&self, x: u32, y: u32, class: &str) -> bool {
+        self.get(x, y) == Some(class)
+    }
+
+    /// Convert a layer-local position to a grid position.
+    ///
+    /// Inverse of [`TileLayerData::grid_to_world`]: expects Bevy's coordinate system
+    /// (Y-up, bottom-left origin). Returns `None` outside the grid.
+    pub fn world_to_grid(&self, position: Vec2, tile_size: Vec2) -> Option<UVec2> {
+        let x = (position.x / tile_size.x).floor();
+        let flipped_y = (position.y / tile_size.y).floor();
+        if x < 0.0 || flipped_y < 0.0 {
+            return None;
+        }
+        let (x, flipped_y) = (x as u32, flipped_y as u32);
+        if x >= self.width || flipped_y >= self.height {
+            return None;
+        }
+        Some(UVec2::new(x, self.height - 1 - flipped_y))
+    }
+
+    /// Get the class name at a layer-local position (see [`Self::world_to_grid`]).
+    pub fn get_at(&self, position: Vec2, tile_size: Vec2) -> Option<&str> {
+        let cell = self.world_to_grid(position, tile_size)?;
+        self.get(cell.x, cell.y)
+    }
+}
+
+/// System that rebuilds [`TileClassGrid`]s when their layer's [`TileLayerData`] changes.
+///
+/// Layers without a class grid get one once a classed tile is placed in them.
+pub fn sync_tile_class_grids(
+    layer_query: Query<(Entity, &TileLayerData, Option<&TileClassGrid>), Changed<TileLayerData>>,
+    tilesets: Res<Assets<TiledTilesetAsset>>,
+    mut commands: Commands,
+) {
+    for (entity, data, current) in &layer_query {
+        let grid = TileClassGrid::from_tile_data(data, &tilesets);
+        match current {
+            Some(current) if *current == grid => {}
+            Some(_) => {
+                commands.entity(entity).insert(grid);
+            }
+            None if !grid.is_empty() => {
+                commands.entity(entity).insert(grid);
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_class_grid_sampling() {
+        // 2x2 grid: top row Water, Lava; bottom row empty, Water
+        let grid = TileClassGrid {
+            width: 2,
+            height: 2,
+            classes: vec!["Water".to_string(), "Lava".to_string()],
+            cells: vec![1, 2, 0, 1],
+        };
+
+        assert_eq!(grid.get(0, 0), Some("Water"));
+        assert!(grid.is(1, 0, "Lava"));
+        assert_eq!(grid.get(0, 1), None);
+        assert_eq!(grid.get(5, 0), None);
+        assert_eq!(grid.class_index("Lava"), Some(2));
+        assert_eq!(grid.class_index("Grass"), None);
+
+        // Bevy Y-up: the bottom-left tile is grid (0, 1)
+        let tile_size = Vec2::splat(16.0);
+        assert_eq!(
+            grid.world_to_grid(Vec2::new(4.0, 4.0), tile_size),
+            Some(UVec2::new(0, 1))
+        );
+        assert_eq!(grid.get_at(Vec2::new(20.0, 28.0), tile_size), Some("Lava"));
+        assert_eq!(grid.world_to_grid(Vec2::new(-1.0, 4.0), tile_size), None);
+        assert_eq!(grid.world_to_grid(Vec2::new(4.0, 40.0), tile_size), None);
+    }
 }
@@ -0,0 +1,193 @@
+//! Conditional spawning based on game state.
+//!
+//! Objects and layers with a `condition` string property (e.g.
+//! `"quest:ch2_started"` or `"difficulty>=hard"`) are only spawned normally when
+//! the game's registered [`SpawnConditionEvaluator`] accepts the condition. The
+//! condition syntax is entirely up to the evaluator.
+//!
+//! Entities failing their condition are skipped, or spawned with Bevy's
+//! [`Disabled`] component and a [`SpawnConditionUnmet`] marker, depending on
+//! [`SpawnConditions::on_fail`]. Without an evaluator every condition passes.
+//!
+//! Conditions are evaluated once, at spawn time; respawn the map
+//! (`RespawnTiledMap`) to re-evaluate them after the game state changes.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! struct Quests(Arc<RwLock<HashSet<String>>>);
+//!
+//! impl SpawnConditionEvaluator for Quests {
+//!     fn evaluate(&self, condition: &str) -> bool {
+//!         match condition.strip_prefix("quest:") {
+//!             Some(quest) => self.0.read().unwrap().contains(quest),
+//!             None => true,
+//!         }
+//!     }
+//! }
+//!
+//! app.set_spawn_condition_evaluator(Quests(started_quests.clone()));
+//! ```
+
+use std::any::Any;
+
+use bevy::ecs::entity_disabling::Disabled;
+use bevy::prelude::*;
+use tiled::{Properties, PropertyValue};
+
+use crate::properties::PropertyKeyConfig;
+
+/// Object or layer property holding a spawn condition.
+pub const SPAWN_CONDITION_PROPERTY: &str = "condition";
+
+/// Decides whether a spawn condition authored in Tiled currently holds.
+///
+/// Implemented for `Fn(&str) -> bool` closures.
+pub trait SpawnConditionEvaluator: Any + Send + Sync {
+    /// Return `true` if entities with this condition should spawn.
+    fn evaluate(&self, condition: &str) -> bool;
+}
+
+impl<F> SpawnConditionEvaluator for F
+where
+    F: Fn(&str) -> bool + Send + Sync + 'static,
+{
+    fn evaluate(&self, condition: &str) -> bool {
+        self(condition)
+    }
+}
+
+/// What happens to objects and layers whose condition fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum ConditionFailBehavior {
+    /// Don't spawn the entity at all.
+    #[default]
+    Skip,
+    /// Spawn the entity with [`Disabled`] and a [`SpawnConditionUnmet`] marker.
+    ///
+    /// Layers are also hidden, and everything spawned inside them is disabled.
+    SpawnDisabled,
+}
+
+/// Resource holding the registered spawn condition evaluator.
+#[derive(Resource, Default)]
+pub struct SpawnConditions {
+    evaluator: Option<Box<dyn SpawnConditionEvaluator>>,
+    /// What happens to entities whose condition fails.
+    pub on_fail: ConditionFailBehavior,
+}
+
+impl SpawnConditions {
+    /// Register the evaluator, replacing any previous one.
+    pub fn set_evaluator(&mut self, evaluator: impl SpawnConditionEvaluator) {
+        self.evaluator = Some(Box::new(evaluator));
+    }
+
+    /// Get the registered evaluator if it is a `T`, e.g. to update its state.
+    pub fn evaluator_mut<T: SpawnConditionEvaluator>(&mut self) -> Option<&mut T> {
+        let evaluator: &mut dyn Any = self.evaluator.as_deref_mut()?;
+        evaluator.downcast_mut()
+    }
+
+    /// Evaluate a condition (always `true` without an evaluator).
+    pub fn evaluate(&self, condition: &str) -> bool {
+        self.evaluator
+            .as_ref()
+            .is_none_or(|evaluator| evaluator.evaluate(condition))
+    }
+
+    /// Check the `condition` property of an object or layer.
+    ///
+    /// Returns the failed condition, or `None` if there is no condition or it holds.
+    pub fn check(&self, properties: &Properties, keys: &PropertyKeyConfig) -> Option<String> {
+        match keys.get(properties, SPAWN_CONDITION_PROPERTY) {
+            Some(PropertyValue::StringValue(condition))
+                if !condition.is_empty() && !self.evaluate(condition) =>
+            {
+                Some(condition.clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Marks an entity spawned disabled because its spawn condition failed.
+#[derive(Component, Reflect, Debug, Clone, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct SpawnConditionUnmet {
+    /// The condition that failed (empty for entities inside a failed layer)
+    pub condition: String,
+}
+
+/// Extension trait for registering the spawn condition evaluator.
+pub trait SpawnConditionAppExt {
+    /// Register the evaluator for `condition` properties, replacing any previous one.
+    fn set_spawn_condition_evaluator(
+        &mut self,
+        evaluator: impl SpawnConditionEvaluator,
+    ) -> &mut Self;
+}
+
+impl SpawnConditionAppExt for App {
+    fn set_spawn_condition_evaluator(
+        &mut self,
+        evaluator: impl SpawnConditionEvaluator,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<SpawnConditions>()
+            .set_evaluator(evaluator);
+        self
+    }
+}
+
+/// Components for an entity spawned disabled by a failed condition.
+pub(crate) fn disabled_bundle(condition: String) -> (Disabled, SpawnConditionUnmet) {
+    (Disabled, SpawnConditionUnmet { condition })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Difficulty(u32);
+
+    impl SpawnConditionEvaluator for Difficulty {
+        fn evaluate(&self, condition: &str) -> bool {
+            condition
+                .strip_prefix("difficulty>=")
+                .and_then(|level| level.parse::<u32>().ok())
+                .is_none_or(|level| self.0 >= level)
+        }
+    }
+
+    fn condition(value: &str) -> Properties {
+        let mut properties = Properties::new();
+        properties.insert(
+            SPAWN_CONDITION_PROPERTY.to_string(),
+            PropertyValue::StringValue(value.to_string()),
+        );
+        properties
+    }
+
+    #[test]
+    fn test_spawn_conditions() {
+        let keys = PropertyKeyConfig::builtin();
+        let mut conditions = SpawnConditions::default();
+        assert_eq!(conditions.check(&condition("difficulty>=2"), keys), None);
+
+        conditions.set_evaluator(Difficulty(1));
+        assert_eq!(
+            conditions.check(&condition("difficulty>=2"), keys),
+            Some("difficulty>=2".to_string())
+        );
+        assert_eq!(conditions.check(&Properties::new(), keys), None);
+
+        conditions.evaluator_mut::<Difficulty>().unwrap().0 = 2;
+        assert_eq!(conditions.check(&condition("difficulty>=2"), keys), None);
+
+        conditions.set_evaluator(|condition: &str| condition == "quest:ch2_started");
+        assert!(conditions.evaluate("quest:ch2_started"));
+        assert!(!conditions.evaluate("quest:ch3_started"));
+        assert!(conditions.evaluator_mut::<Difficulty>().is_none());
+    }
+}
@@ -0,0 +1,270 @@
+//! Destructible terrain: per-tile health and damage.
+//!
+//! Tiles whose tileset tile has a numeric `health` property can be damaged with
+//! [`DamageTile`]. Once a tile's health reaches zero it is removed from the
+//! layer's [`TileLayerData`], [`TileDestroyed`] fires on the layer entity, and
+//! [`TilesModified`] lets the renderer and physics plugins rebuild the layer.
+//! Tiles without the property are indestructible.
+//!
+//! Health is tracked sparsely in [`TileHealth`]: a tile only gets an entry once
+//! it has been damaged, starting from its tileset's `health` value.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn dig(mut commands: Commands, layer: Single<Entity, With<TileLayerData>>) {
+//!     commands.trigger(DamageTile::new(*layer, UVec2::new(3, 4), 25.0));
+//! }
+//!
+//! fn on_destroyed(trigger: On<TileDestroyed>) {
+//!     let event = trigger.event();
+//!     info!("Tile {} at {} destroyed", event.tile_id, event.position);
+//! }
+//! ```
+
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+use tiled::PropertyValue;
+
+use crate::components::tile::{TileInstance, TileLayerData};
+use crate::events::TilesModified;
+use crate::properties::PropertyKeyConfig;
+
+/// Tileset tile property holding a tile's starting health.
+pub const TILE_HEALTH_PROPERTY: &str = "health";
+
+/// Current health of damaged tiles in a tile layer.
+///
+/// Inserted on the layer entity by the first [`DamageTile`]. Undamaged tiles
+/// have no entry. Entries are cleared when their tile changes.
+#[derive(Component, Debug, Clone, Default)]
+pub struct TileHealth {
+    health: HashMap<UVec2, f32>,
+}
+
+impl TileHealth {
+    /// Get the current health of a damaged tile (`None` if undamaged).
+    pub fn get(&self, position: UVec2) -> Option<f32> {
+        self.health.get(&position).copied()
+    }
+
+    /// Iterate damaged tiles and their current health.
+    pub fn iter(&self) -> impl Iterator<Item = (UVec2, f32)> + '_ {
+        self.health
+            .iter()
+            .map(|(position, health)| (*position, *health))
+    }
+
+    /// Forget the health of a tile, restoring it to full on the next hit.
+    pub fn reset(&mut self, position: UVec2) {
+        self.health.remove(&position);
+    }
+}
+
+/// Deal damage to a tile.
+///
+/// Trigger with `commands.trigger(...)`.
+#[derive(Event, Debug, Clone)]
+pub struct DamageTile {
+    /// The tile layer entity
+    pub layer: Entity,
+    /// Grid position of the tile (Tiled coordinates, Y-down)
+    pub position: UVec2,
+    /// Amount of health to remove
+    pub amount: f32,
+}
+
+impl DamageTile {
+    /// Create a damage event for the tile at `position` in `layer`.
+    pub fn new(layer: Entity, position: UVec2, amount: f32) -> Self {
+        Self {
+            layer,
+            position,
+            amount,
+        }
+    }
+}
+
+/// Fired on a tile layer entity when a tile takes damage but survives.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct TileDamaged {
+    /// The tile layer entity
+    #[event_target]
+    pub entity: Entity,
+    /// Grid position of the tile (Tiled coordinates, Y-down)
+    pub position: UVec2,
+    /// Local tile ID within its tileset
+    pub tile_id: u32,
+    /// Remaining health
+    pub health: f32,
+}
+
+/// Fired on a tile layer entity when a tile's health reaches zero.
+///
+/// The tile has already been removed from the layer's [`TileLayerData`].
+#[derive(EntityEvent, Debug, Clone)]
+pub struct TileDestroyed {
+    /// The tile layer entity
+    #[event_target]
+    pub entity: Entity,
+    /// Grid position of the tile (Tiled coordinates, Y-down)
+    pub position: UVec2,
+    /// Local tile ID within its tileset
+    pub tile_id: u32,
+    /// The destroyed tile
+    pub tile: TileInstance,
+}
+
+/// Look up a tile's starting health from its tileset's `health` property.
+///
+/// Returns `None` for indestructible tiles (no numeric `health` property).
+pub fn tile_max_health(
+    tileset: &TiledTilesetAsset,
+    local_tile_id: u32,
+    keys: &PropertyKeyConfig,
+) -> Option<f32> {
+    let tile = tileset.tileset.get_tile(local_tile_id)?;
+    match keys.get(&tile.properties, TILE_HEALTH_PROPERTY)? {
+        PropertyValue::IntValue(value) => Some(*value as f32),
+        PropertyValue::FloatValue(value) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Observer that applies [`DamageTile`].
+pub fn on_damage_tile(
+    trigger: On<DamageTile>,
+    mut layer_query: Query<(&mut TileLayerData, Option<&mut TileHealth>)>,
+    tileset_assets: Res<Assets<TiledTilesetAsset>>,
+    keys: Res<PropertyKeyConfig>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let position = event.position;
+    let Ok((mut data, tile_health)) = layer_query.get_mut(event.layer) else {
+        return;
+    };
+    let Some(tile) = data.get(position.x, position.y).cloned() else {
+        return;
+    };
+    let Some(max_health) = tileset_assets
+        .get(&tile.tileset_handle)
+        .and_then(|tileset| tile_max_health(tileset, tile.tile_id, &keys))
+    else {
+        return;
+    };
+
+    let current = tile_health
+        .as_ref()
+        .and_then(|tile_health| tile_health.get(position))
+        .unwrap_or(max_health);
+    let health = current - event.amount;
+
+    if health > 0.0 {
+        match tile_health {
+            Some(mut tile_health) => {
+                tile_health.health.insert(position, health);
+            }
+            None => {
+                let mut tile_health = TileHealth::default();
+                tile_health.health.insert(position, health);
+                commands.entity(event.layer).insert(tile_health);
+            }
+        }
+        commands.trigger(TileDamaged {
+            entity: event.layer,
+            position,
+            tile_id: tile.tile_id,
+            health,
+        });
+        return;
+    }
+
+    if let Some(mut tile_health) = tile_health {
+        tile_health.reset(position);
+    }
+    data.set(position.x, position.y, None);
+    commands.trigger(TileDestroyed {
+        entity: event.layer,
+        position,
+        tile_id: tile.tile_id,
+        tile,
+    });
+    commands.trigger(TilesModified {
+        entity: event.layer,
+        tiles: vec![position],
+    });
+}
+
+/// Observer that forgets the health of tiles replaced at runtime.
+pub fn on_tiles_modified(trigger: On<TilesModified>, mut layer_query: Query<&mut TileHealth>) {
+    let event = trigger.event();
+    if let Ok(mut tile_health) = layer_query.get_mut(event.entity) {
+        for position in &event.tiles {
+            tile_health.reset(*position);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Destroyed(Vec<UVec2>);
+
+    #[test]
+    fn test_tiles_without_health_are_indestructible() {
+        let mut app = App::new();
+        app.init_resource::<Assets<TiledTilesetAsset>>();
+        app.init_resource::<PropertyKeyConfig>();
+        app.init_resource::<Destroyed>();
+        app.add_observer(on_damage_tile);
+        app.add_observer(
+            |trigger: On<TileDestroyed>, mut destroyed: ResMut<Destroyed>| {
+                destroyed.0.push(trigger.event().position);
+            },
+        );
+
+        let mut data = TileLayerData::empty(2, 2);
+        data.set(
+            0,
+            0,
+            Some(TileInstance {
+                gid: 1,
+                tileset_handle: Handle::default(),
+                tile_id: 0,
+                flipped_h: false,
+                flipped_v: false,
+                flipped_d: false,
+                variant: None,
+            }),
+        );
+        let layer = app.world_mut().spawn(data).id();
+
+        app.world_mut()
+            .trigger(DamageTile::new(layer, UVec2::ZERO, 1000.0));
+        app.world_mut().flush();
+
+        // No tileset (and so no health property): the tile survives untouched
+        assert!(app.world().resource::<Destroyed>().0.is_empty());
+        assert!(
+            app.world()
+                .get::<TileLayerData>(layer)
+                .unwrap()
+                .get(0, 0)
+                .is_some()
+        );
+        assert!(app.world().get::<TileHealth>(layer).is_none());
+    }
+
+    #[test]
+    fn test_tile_health_reset() {
+        let mut tile_health = TileHealth::default();
+        tile_health.health.insert(UVec2::new(1, 2), 5.0);
+        assert_eq!(tile_health.get(UVec2::new(1, 2)), Some(5.0));
+        tile_health.reset(UVec2::new(1, 2));
+        assert_eq!(tile_health.iter().count(), 0);
+    }
+}
@@ -0,0 +1,441 @@
+//! Undo-friendly runtime map edits.
+//!
+//! An optional command-pattern layer on top of the mutation APIs
+//! ([`TileLayerData::set`], object `Transform`s and [`MergedProperties::insert`]).
+//! Each edit is applied through [`ApplyTiledEdit`], which records the change as a
+//! reversible [`TiledEditCommand`] in the map entity's [`TiledEditHistory`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn paint(mut commands: Commands, map: Single<Entity, With<TiledMap>>, layer: Entity) {
+//!     commands.queue(ApplyTiledEdit::new(
+//!         *map,
+//!         TiledEdit::SetTile { layer, x: 3, y: 4, tile: None },
+//!     ));
+//! }
+//!
+//! fn undo(mut commands: Commands, map: Single<Entity, With<TiledMap>>) {
+//!     commands.queue(UndoTiledEdit { map: *map });
+//! }
+//! ```
+//!
+//! Mutating components directly still works, but isn't recorded.
+
+use bevy::prelude::*;
+use tiled::PropertyValue;
+
+use crate::components::tile::{TileInstance, TileLayerData};
+use crate::events::TilesModified;
+use crate::properties::MergedProperties;
+
+/// A requested map edit, before it has been applied.
+#[derive(Debug, Clone)]
+pub enum TiledEdit {
+    /// Set (or clear, with `None`) a tile in a tile layer.
+    SetTile {
+        layer: Entity,
+        x: u32,
+        y: u32,
+        tile: Option<TileInstance>,
+    },
+    /// Move an object to a new local translation.
+    MoveObject { object: Entity, translation: Vec3 },
+    /// Set (or remove, with `None`) a property in an entity's `MergedProperties`.
+    SetProperty {
+        entity: Entity,
+        key: String,
+        value: Option<PropertyValue>,
+    },
+}
+
+/// A recorded, reversible map edit.
+///
+/// Stores both the previous and new state, so it can be undone and redone.
+#[derive(Debug, Clone)]
+pub enum TiledEditCommand {
+    /// A tile change in a tile layer.
+    SetTile {
+        layer: Entity,
+        x: u32,
+        y: u32,
+        before: Option<TileInstance>,
+        after: Option<TileInstance>,
+    },
+    /// An object translation change.
+    MoveObject {
+        object: Entity,
+        before: Vec3,
+        after: Vec3,
+    },
+    /// A property change.
+    SetProperty {
+        entity: Entity,
+        key: String,
+        before: Option<PropertyValue>,
+        after: Option<PropertyValue>,
+    },
+}
+
+impl TiledEditCommand {
+    /// Record an edit against the current world state.
+    ///
+    /// Returns `None` if the target entity or component no longer exists.
+    pub fn capture(world: &World, edit: TiledEdit) -> Option<Self> {
+        Some(match edit {
+            TiledEdit::SetTile { layer, x, y, tile } => {
+                let data = world.get::<TileLayerData>(layer)?;
+                if x >= data.width || y >= data.height {
+                    return None;
+                }
+                TiledEditCommand::SetTile {
+                    layer,
+                    x,
+                    y,
+                    before: data.get(x, y).cloned(),
+                    after: tile,
+                }
+            }
+            TiledEdit::MoveObject {
+                object,
+                translation,
+            } => TiledEditCommand::MoveObject {
+                object,
+                before: world.get::<Transform>(object)?.translation,
+                after: translation,
+            },
+            TiledEdit::SetProperty { entity, key, value } => {
+                let before = world.get::<MergedProperties>(entity)?.get(&key).cloned();
+                TiledEditCommand::SetProperty {
+                    entity,
+                    key,
+                    before,
+                    after: value,
+                }
+            }
+        })
+    }
+
+    /// The command that undoes this one.
+    pub fn inverse(&self) -> Self {
+        match self.clone() {
+            TiledEditCommand::SetTile {
+                layer,
+                x,
+                y,
+                before,
+                after,
+            } => TiledEditCommand::SetTile {
+                layer,
+                x,
+                y,
+                before: after,
+                after: before,
+            },
+            TiledEditCommand::MoveObject {
+                object,
+                before,
+                after,
+            } => TiledEditCommand::MoveObject {
+                object,
+                before: after,
+                after: before,
+            },
+            TiledEditCommand::SetProperty {
+                entity,
+                key,
+                before,
+                after,
+            } => TiledEditCommand::SetProperty {
+                entity,
+                key,
+                before: after,
+                after: before,
+            },
+        }
+    }
+
+    /// Apply the `after` state to the world.
+    ///
+    /// Targets that no longer exist are skipped.
+    pub fn apply(&self, world: &mut World) {
+        match self {
+            TiledEditCommand::SetTile {
+                layer, x, y, after, ..
+            } => {
+                if let Some(mut data) = world.get_mut::<TileLayerData>(*layer) {
+                    data.set(*x, *y, after.clone());
+                    world.trigger(TilesModified {
+                        entity: *layer,
+                        tiles: vec![UVec2::new(*x, *y)],
+                    });
+                }
+            }
+            TiledEditCommand::MoveObject { object, after, .. } => {
+                if let Some(mut transform) = world.get_mut::<Transform>(*object) {
+                    transform.translation = *after;
+                }
+            }
+            TiledEditCommand::SetProperty {
+                entity, key, after, ..
+            } => {
+                if let Some(mut properties) = world.get_mut::<MergedProperties>(*entity) {
+                    match after {
+                        Some(value) => {
+                            properties.insert(key.clone(), value.clone());
+                        }
+                        None => {
+                            properties.remove(key);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Undo and redo stacks for runtime edits, stored on the map entity.
+///
+/// Inserted automatically by the first [`ApplyTiledEdit`] on a map. Insert it
+/// yourself with [`TiledEditHistory::with_limit`] to cap memory use.
+#[derive(Component, Debug, Clone, Default)]
+pub struct TiledEditHistory {
+    undo: Vec<TiledEditCommand>,
+    redo: Vec<TiledEditCommand>,
+    limit: Option<usize>,
+}
+
+impl TiledEditHistory {
+    /// Create a history that keeps at most `limit` undo steps.
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            limit: Some(limit),
+            ..default()
+        }
+    }
+
+    /// Record an applied command. Clears the redo stack.
+    pub fn push(&mut self, command: TiledEditCommand) {
+        self.redo.clear();
+        self.undo.push(command);
+        if let Some(limit) = self.limit
+            && self.undo.len() > limit
+        {
+            self.undo.drain(..self.undo.len() - limit);
+        }
+    }
+
+    /// Check if there is anything to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Check if there is anything to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Recorded commands, oldest first.
+    pub fn undo_stack(&self) -> &[TiledEditCommand] {
+        &self.undo
+    }
+
+    /// Undone commands, in the order they will be redone last-first.
+    pub fn redo_stack(&self) -> &[TiledEditCommand] {
+        &self.redo
+    }
+
+    /// Forget all recorded commands.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+/// Apply a map edit and record it in the map's [`TiledEditHistory`].
+#[derive(Debug, Clone)]
+pub struct ApplyTiledEdit {
+    /// The map entity owning the history.
+    pub map: Entity,
+    /// The edit to apply.
+    pub edit: TiledEdit,
+}
+
+impl ApplyTiledEdit {
+    /// Create an edit command for `map`.
+    pub fn new(map: Entity, edit: TiledEdit) -> Self {
+        Self { map, edit }
+    }
+}
+
+impl Command for ApplyTiledEdit {
+    fn apply(self, world: &mut World) {
+        let Some(command) = TiledEditCommand::capture(world, self.edit) else {
+            warn!("Skipping Tiled edit: target entity or component not found");
+            return;
+        };
+        if world.get_entity(self.map).is_err() {
+            warn!("Skipping Tiled edit: map entity {:?} not found", self.map);
+            return;
+        }
+
+        command.apply(world);
+        let mut map = world.entity_mut(self.map);
+        if let Some(mut history) = map.get_mut::<TiledEditHistory>() {
+            history.push(command);
+        } else {
+            let mut history = TiledEditHistory::default();
+            history.push(command);
+            map.insert(history);
+        }
+    }
+}
+
+/// Undo the most recent edit on a map.
+#[derive(Debug, Clone, Copy)]
+pub struct UndoTiledEdit {
+    /// The map entity owning the history.
+    pub map: Entity,
+}
+
+impl Command for UndoTiledEdit {
+    fn apply(self, world: &mut World) {
+        let Some(command) = world
+            .get_mut::<TiledEditHistory>(self.map)
+            .and_then(|mut history| history.undo.pop())
+        else {
+            return;
+        };
+
+        command.inverse().apply(world);
+        if let Some(mut history) = world.get_mut::<TiledEditHistory>(self.map) {
+            history.redo.push(command);
+        }
+    }
+}
+
+/// Redo the most recently undone edit on a map.
+#[derive(Debug, Clone, Copy)]
+pub struct RedoTiledEdit {
+    /// The map entity owning the history.
+    pub map: Entity,
+}
+
+impl Command for RedoTiledEdit {
+    fn apply(self, world: &mut World) {
+        let Some(command) = world
+            .get_mut::<TiledEditHistory>(self.map)
+            .and_then(|mut history| history.redo.pop())
+        else {
+            return;
+        };
+
+        command.apply(world);
+        if let Some(mut history) = world.get_mut::<TiledEditHistory>(self.map) {
+            history.undo.push(command);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile(tile_id: u32) -> TileInstance {
+        TileInstance {
+            gid: tile_id + 1,
+            tileset_handle: Handle::default(),
+            tile_id,
+            flipped_h: false,
+            flipped_v: false,
+            flipped_d: false,
+            variant: None,
+        }
+    }
+
+    #[test]
+    fn test_undo_redo_edits() {
+        let mut world = World::new();
+        let map = world.spawn_empty().id();
+        let layer = world.spawn(TileLayerData::empty(2, 2)).id();
+        let object = world
+            .spawn((Transform::default(), MergedProperties::new(default())))
+            .id();
+
+        ApplyTiledEdit::new(
+            map,
+            TiledEdit::SetTile {
+                layer,
+                x: 1,
+                y: 0,
+                tile: Some(tile(7)),
+            },
+        )
+        .apply(&mut world);
+        ApplyTiledEdit::new(
+            map,
+            TiledEdit::MoveObject {
+                object,
+                translation: Vec3::X,
+            },
+        )
+        .apply(&mut world);
+        ApplyTiledEdit::new(
+            map,
+            TiledEdit::SetProperty {
+                entity: object,
+                key: "open".to_string(),
+                value: Some(PropertyValue::BoolValue(true)),
+            },
+        )
+        .apply(&mut world);
+
+        assert_eq!(world.get::<TiledEditHistory>(map).unwrap().undo.len(), 3);
+        assert_eq!(
+            world
+                .get::<MergedProperties>(object)
+                .unwrap()
+                .get_bool("open"),
+            Some(true)
+        );
+
+        for _ in 0..3 {
+            UndoTiledEdit { map }.apply(&mut world);
+        }
+        assert!(
+            world
+                .get::<TileLayerData>(layer)
+                .unwrap()
+                .get(1, 0)
+                .is_none()
+        );
+        assert_eq!(
+            world.get::<Transform>(object).unwrap().translation,
+            Vec3::ZERO
+        );
+        assert!(
+            world
+                .get::<MergedProperties>(object)
+                .unwrap()
+                .get("open")
+                .is_none()
+        );
+
+        RedoTiledEdit { map }.apply(&mut world);
+        let data = world.get::<TileLayerData>(layer).unwrap();
+        assert_eq!(data.get(1, 0).map(|t| t.tile_id), Some(7));
+
+        // A new edit clears the redo stack
+        ApplyTiledEdit::new(
+            map,
+            TiledEdit::MoveObject {
+                object,
+                translation: Vec3::Y,
+            },
+        )
+        .apply(&mut world);
+        let history = world.get::<TiledEditHistory>(map).unwrap();
+        assert!(!history.can_redo());
+        assert_eq!(history.undo_stack().len(), 2);
+    }
+}
@@ -0,0 +1,238 @@
+//! Particle/VFX spawn points.
+//!
+//! Objects with the `core::EffectEmitter` class get a child effect entity placed
+//! at the object. This crate handles placement and lifecycle (spawning, enabling
+//! and disabling, cleanup); the actual emitter is built by a system registered
+//! with [`EffectEmitterAppExt::set_effect_builder`], so `bevy_hanabi` or a custom
+//! particle system can plug in.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn build_effect(
+//!     In(request): In<EffectSpawnRequest>,
+//!     asset_server: Res<AssetServer>,
+//!     mut commands: Commands,
+//! ) {
+//!     let effect: Handle<EffectAsset> = asset_server.load(&request.settings.effect);
+//!     commands.entity(request.effect).insert(ParticleEffect::new(effect));
+//! }
+//!
+//! app.set_effect_builder(build_effect);
+//! ```
+
+use bevy::ecs::system::SystemId;
+use bevy::prelude::*;
+use bevy_tiledmap_macros::TiledClass;
+
+/// An effect spawn point, authored as an object with the `core::EffectEmitter` class.
+#[derive(Component, Reflect, TiledClass, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+#[tiled(name = "core::EffectEmitter")]
+pub struct EffectEmitter {
+    /// Asset path of the effect, interpreted by the registered builder.
+    #[tiled(default = String::new())]
+    pub effect: String,
+
+    /// Emission rate, interpreted by the registered builder (e.g. particles per second).
+    ///
+    /// Default: 1.0
+    #[tiled(default = 1.0)]
+    pub rate: f32,
+
+    /// Whether the effect is active. Disabled effects are hidden.
+    ///
+    /// Default: true
+    #[tiled(default = true)]
+    pub enabled: bool,
+}
+
+impl Default for EffectEmitter {
+    fn default() -> Self {
+        Self {
+            effect: String::new(),
+            rate: 1.0,
+            enabled: true,
+        }
+    }
+}
+
+/// The effect entity spawned for an [`EffectEmitter`].
+///
+/// Child of the emitter entity, so it follows the object's transform.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct TiledEffect {
+    /// The emitter entity this effect belongs to
+    pub emitter: Entity,
+}
+
+/// Points from an emitter entity to its spawned [`TiledEffect`] entity.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct EmitterEffect(pub Entity);
+
+/// Input passed to the effect builder system.
+#[derive(Debug, Clone)]
+pub struct EffectSpawnRequest {
+    /// The emitter (object) entity
+    pub emitter: Entity,
+    /// The effect entity to build the emitter on
+    pub effect: Entity,
+    /// The emitter settings from Tiled
+    pub settings: EffectEmitter,
+}
+
+/// The registered effect builder system.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct EffectBuilder(pub SystemId<In<EffectSpawnRequest>>);
+
+/// Extension trait for registering how effects are built.
+pub trait EffectEmitterAppExt {
+    /// Register the system that builds an effect for each spawned emitter.
+    ///
+    /// The system runs once per emitter with an [`EffectSpawnRequest`], after the
+    /// effect entity has been spawned. Replaces any previously registered builder.
+    fn set_effect_builder<M>(
+        &mut self,
+        builder: impl IntoSystem<In<EffectSpawnRequest>, (), M> + 'static,
+    ) -> &mut Self;
+}
+
+impl EffectEmitterAppExt for App {
+    fn set_effect_builder<M>(
+        &mut self,
+        builder: impl IntoSystem<In<EffectSpawnRequest>, (), M> + 'static,
+    ) -> &mut Self {
+        let id = self.world_mut().register_system(builder);
+        self.insert_resource(EffectBuilder(id));
+        self
+    }
+}
+
+/// Observer that spawns the effect entity when an [`EffectEmitter`] is added.
+pub fn on_effect_emitter_added(
+    trigger: On<Add, EffectEmitter>,
+    emitter_query: Query<&EffectEmitter>,
+    builder: Option<Res<EffectBuilder>>,
+    mut commands: Commands,
+) {
+    let emitter = trigger.event().entity;
+    let Ok(settings) = emitter_query.get(emitter) else {
+        return;
+    };
+
+    let visibility = if settings.enabled {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    let effect = commands
+        .spawn((
+            Name::new("Effect"),
+            TiledEffect { emitter },
+            Transform::default(),
+            visibility,
+            ChildOf(emitter),
+        ))
+        .id();
+    commands.entity(emitter).insert(EmitterEffect(effect));
+
+    if let Some(builder) = builder {
+        commands.run_system_with(
+            builder.0,
+            EffectSpawnRequest {
+                emitter,
+                effect,
+                settings: settings.clone(),
+            },
+        );
+    } else {
+        debug!("No effect builder registered, effect entity {effect:?} left empty");
+    }
+}
+
+/// Observer that despawns the effect entity when an [`EffectEmitter`] is removed.
+pub fn on_effect_emitter_removed(
+    trigger: On<Remove, EffectEmitter>,
+    emitter_query: Query<&EmitterEffect>,
+    mut commands: Commands,
+) {
+    let emitter = trigger.event().entity;
+    if let Ok(effect) = emitter_query.get(emitter) {
+        commands.entity(effect.0).try_despawn();
+        commands.entity(emitter).try_remove::<EmitterEffect>();
+    }
+}
+
+/// System that shows or hides effects when their emitter is enabled or disabled.
+pub fn sync_effect_enabled(
+    emitter_query: Query<(&EffectEmitter, &EmitterEffect), Changed<EffectEmitter>>,
+    mut effect_query: Query<&mut Visibility, With<TiledEffect>>,
+) {
+    for (settings, effect) in &emitter_query {
+        let Ok(mut visibility) = effect_query.get_mut(effect.0) else {
+            continue;
+        };
+        let target = if settings.enabled {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        visibility.set_if_neq(target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component)]
+    struct Built(f32);
+
+    fn build(In(request): In<EffectSpawnRequest>, mut commands: Commands) {
+        commands
+            .entity(request.effect)
+            .insert(Built(request.settings.rate));
+    }
+
+    #[test]
+    fn test_effect_lifecycle() {
+        let mut app = App::new();
+        app.add_observer(on_effect_emitter_added);
+        app.add_observer(on_effect_emitter_removed);
+        app.add_systems(Update, sync_effect_enabled);
+        app.set_effect_builder(build);
+
+        let emitter = app
+            .world_mut()
+            .spawn(EffectEmitter {
+                effect: "effects/torch.ron".to_string(),
+                rate: 4.0,
+                enabled: true,
+            })
+            .id();
+        app.update();
+
+        let effect = app.world().get::<EmitterEffect>(emitter).unwrap().0;
+        assert_eq!(app.world().get::<Built>(effect).map(|b| b.0), Some(4.0));
+        assert_eq!(
+            app.world().get::<ChildOf>(effect).map(ChildOf::parent),
+            Some(emitter)
+        );
+
+        app.world_mut()
+            .get_mut::<EffectEmitter>(emitter)
+            .unwrap()
+            .enabled = false;
+        app.update();
+        assert_eq!(
+            app.world().get::<Visibility>(effect),
+            Some(&Visibility::Hidden)
+        );
+
+        app.world_mut()
+            .entity_mut(emitter)
+            .remove::<EffectEmitter>();
+        app.world_mut().flush();
+        assert!(app.world().get_entity(effect).is_err());
+    }
+}
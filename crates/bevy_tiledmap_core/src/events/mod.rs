@@ -2,10 +2,91 @@
 //!
 //! These events allow Layer 3 plugins (rendering, physics) to hook into the spawning
 //! process and access property data for conditional logic and component attachment.
+//!
+//! # Observers and messages
+//!
+//! Every spawn event ([`ObjectSpawned`], [`ObjectsSpawnedBatch`], the
+//! `*LayerSpawned` events, [`MapSpawned`], [`MapSpawnReport`], [`WorldSpawned`],
+//! [`MapCompatibilityWarning`] and `PrefabSpawned`) is delivered both ways, so
+//! plugins can pick their style:
+//!
+//! - As an `EntityEvent` targeting the spawned entity, for global observers
+//!   (`app.add_observer`) and entity observers (`EntityCommands::observe`).
+//! - As a `Message`, for systems reading it with a `MessageReader`.
+//!
+//! Ordering guarantees:
+//!
+//! - Spawn events are emitted by systems in
+//!   [`TiledSpawnSystems`](crate::TiledSpawnSystems) (`PreUpdate`). When the
+//!   spawn commands are applied, each event's message is written and then its
+//!   observers run, before any system ordered after `TiledSpawnSystems`.
+//! - The event's entity and everything it was spawned with already exist when
+//!   either is delivered.
+//! - Within a map, layers are delivered in draw order (bottom to top), and a
+//!   group's [`GroupLayerSpawned`] comes before the events of the layers
+//!   inside it, which name the group as their `parent_group`. The layers
+//!   inside a group aren't spawned yet when its event is delivered.
+//! - An object layer's [`ObjectSpawned`] events come before its
+//!   [`ObjectLayerSpawned`], and all layers before [`MapSpawned`], which is
+//!   followed by the map's [`MapSpawnReport`] and [`MapCompatibilityWarning`]s.
+//!   [`WorldSpawned`] comes after the `MapSpawned` of every map in the world.
+//!   Messages are read in the same order.
+//! - Messages can be read in the same frame by systems after
+//!   `TiledSpawnSystems` (e.g. in `Update`), and stay readable for one more
+//!   frame, like any Bevy message. Components inserted by observers are present
+//!   by then.
+//!
+//! Use [`trigger_spawn_event`] when emitting spawn events yourself so both
+//! kinds of listeners see them.
+//!
+//! # Batched object events
+//!
+//! Triggering observers once per object gets slow for maps with thousands of
+//! objects. With [`SpawnEventMode::Batched`] (set through
+//! `TiledmapCoreConfig::spawn_events`), each object layer fires one
+//! [`ObjectsSpawnedBatch`] instead, right before its [`ObjectLayerSpawned`].
+//! [`SpawnEventMode::Both`] fires both kinds.
+//!
+//! Every spawned map also fires a [`MapSpawnReport`] right after its
+//! [`MapSpawned`], with how many layers, objects and tiles it spawned and how
+//! long that took.
+//!
+//! # Despawn events
+//!
+//! [`LayerDespawned`] and [`ObjectDespawned`] are fired for every layer and
+//! object entity that is despawned, however that happens: the map being
+//! despawned or unloaded, a reload replacing it, or game code despawning single
+//! entities. They carry the identity of the spawn events, so code that created
+//! resources outside the entities in response to a spawn (audio handles,
+//! navigation data, ...) can free them.
+//!
+//! They are global events and messages, delivered when the despawning commands
+//! have been applied. By then the entities are gone, so entity observers don't
+//! see them.
+
+use std::sync::Arc;
+use std::time::Duration;
 
 use bevy::prelude::*;
 use tiled::Properties;
 
+use crate::components::{
+    LayerId, LayerName, ObjectId, TiledLayer, TiledLayerMapOf, TiledObject, TiledObjectMapOf,
+};
+use crate::properties::MergedProperties;
+
+/// Deliver a spawn event to its observers and write it as a message.
+///
+/// The message is written first, then the observers run, when `commands` is
+/// applied.
+pub fn trigger_spawn_event<'a, E>(commands: &mut Commands, event: E)
+where
+    E: EntityEvent<Trigger<'a>: Default> + Message + Clone,
+{
+    commands.write_message(event.clone());
+    commands.trigger(event);
+}
+
 /// Fired when an object entity is spawned.
 ///
 /// Layer 3 plugins can use this event to:
@@ -17,7 +98,7 @@ use tiled::Properties;
 ///
 /// ```ignore
 /// fn handle_object_spawned(
-///     mut events: EventReader<ObjectSpawned>,
+///     mut events: MessageReader<ObjectSpawned>,
 ///     mut commands: Commands,
 /// ) {
 ///     for event in events.read() {
@@ -28,18 +109,132 @@ use tiled::Properties;
 ///     }
 /// }
 /// ```
-#[derive(Event, Debug, Clone)]
+#[derive(EntityEvent, Message, Debug, Clone)]
 pub struct ObjectSpawned {
     /// The spawned object entity
+    #[event_target]
     pub entity: Entity,
     /// The parent map entity
     pub map_entity: Entity,
     /// The object's ID from Tiled
     pub object_id: u32,
-    /// Merged properties (template + object overrides)
+    /// Merged properties (project class defaults + template + object overrides)
     pub properties: Properties,
 }
 
+/// Which object spawn events are fired. See the [module docs](self).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum SpawnEventMode {
+    /// One [`ObjectSpawned`] per object.
+    #[default]
+    PerEntity,
+    /// One [`ObjectsSpawnedBatch`] per object layer.
+    Batched,
+    /// Both [`ObjectSpawned`] and [`ObjectsSpawnedBatch`].
+    Both,
+}
+
+impl SpawnEventMode {
+    /// Whether [`ObjectSpawned`] is fired for each object.
+    pub fn per_entity(self) -> bool {
+        matches!(self, Self::PerEntity | Self::Both)
+    }
+
+    /// Whether [`ObjectsSpawnedBatch`] is fired for each object layer.
+    pub fn batched(self) -> bool {
+        matches!(self, Self::Batched | Self::Both)
+    }
+}
+
+/// Resource selecting the fired object spawn events.
+///
+/// Inserted from `TiledmapCoreConfig::spawn_events`.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SpawnEventConfig {
+    /// Which object spawn events are fired (default: [`SpawnEventMode::PerEntity`])
+    pub mode: SpawnEventMode,
+}
+
+/// An object in an [`ObjectsSpawnedBatch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchedObject {
+    /// The spawned object entity
+    pub entity: Entity,
+    /// The object's ID from Tiled
+    pub object_id: u32,
+    /// Index of the object's merged properties in [`ObjectsSpawnedBatch::properties`]
+    pub properties: usize,
+}
+
+/// Fired once per object layer with all of its spawned objects, in
+/// [`SpawnEventMode::Batched`] and [`SpawnEventMode::Both`].
+///
+/// Targets the layer entity. The object list and properties are shared, so the
+/// event is cheap to clone.
+///
+/// # Example
+///
+/// ```ignore
+/// app.add_observer(|trigger: On<ObjectsSpawnedBatch>, mut commands: Commands| {
+///     let batch = trigger.event();
+///     for object in batch.objects.iter() {
+///         if batch.object_properties(object).contains_key("has_physics") {
+///             commands.entity(object.entity).insert(RigidBody::Dynamic);
+///         }
+///     }
+/// });
+/// ```
+#[derive(EntityEvent, Message, Debug, Clone)]
+pub struct ObjectsSpawnedBatch {
+    /// The object layer entity
+    #[event_target]
+    pub entity: Entity,
+    /// The parent map entity
+    pub map_entity: Entity,
+    /// The layer's ID from Tiled
+    pub layer_id: u32,
+    /// The spawned objects, in Tiled order
+    pub objects: Arc<[BatchedObject]>,
+    /// Merged properties of the objects (consecutive objects with equal
+    /// properties share an entry)
+    pub properties: Arc<[Properties]>,
+}
+
+impl ObjectsSpawnedBatch {
+    /// Build a batch from `(entity, object_id, properties)` of each spawned object.
+    pub fn new(
+        entity: Entity,
+        map_entity: Entity,
+        layer_id: u32,
+        spawned: impl IntoIterator<Item = (Entity, u32, Properties)>,
+    ) -> Self {
+        let mut objects = Vec::new();
+        let mut properties: Vec<Properties> = Vec::new();
+        for (object_entity, object_id, object_properties) in spawned {
+            if properties.last() != Some(&object_properties) {
+                properties.push(object_properties);
+            }
+            objects.push(BatchedObject {
+                entity: object_entity,
+                object_id,
+                properties: properties.len() - 1,
+            });
+        }
+        Self {
+            entity,
+            map_entity,
+            layer_id,
+            objects: objects.into(),
+            properties: properties.into(),
+        }
+    }
+
+    /// Get an object's merged properties.
+    pub fn object_properties(&self, object: &BatchedObject) -> &Properties {
+        &self.properties[object.properties]
+    }
+}
+
 /// Fired when a tile layer is spawned.
 ///
 /// Layer 3 plugins can use this event to:
@@ -51,7 +246,7 @@ pub struct ObjectSpawned {
 ///
 /// ```ignore
 /// fn handle_tile_layer_spawned(
-///     mut events: EventReader<TileLayerSpawned>,
+///     mut events: MessageReader<TileLayerSpawned>,
 ///     mut commands: Commands,
 /// ) {
 ///     for event in events.read() {
@@ -62,53 +257,77 @@ pub struct ObjectSpawned {
 ///     }
 /// }
 /// ```
-#[derive(Event, Debug, Clone)]
+#[derive(EntityEvent, Message, Debug, Clone)]
 pub struct TileLayerSpawned {
     /// The spawned layer entity
+    #[event_target]
     pub entity: Entity,
     /// The parent map entity
     pub map_entity: Entity,
     /// The layer's ID from Tiled
     pub layer_id: u32,
+    /// The group layer containing this layer (`None` for top-level layers)
+    pub parent_group: Option<Entity>,
+    /// How many groups the layer is nested in (0 for top-level layers)
+    pub depth: u32,
     /// Layer properties
     pub properties: Properties,
 }
 
 /// Fired when an object layer is spawned.
-#[derive(Event, Debug, Clone)]
+#[derive(EntityEvent, Message, Debug, Clone)]
 pub struct ObjectLayerSpawned {
     /// The spawned layer entity
+    #[event_target]
     pub entity: Entity,
     /// The parent map entity
     pub map_entity: Entity,
     /// The layer's ID from Tiled
     pub layer_id: u32,
+    /// The group layer containing this layer (`None` for top-level layers)
+    pub parent_group: Option<Entity>,
+    /// How many groups the layer is nested in (0 for top-level layers)
+    pub depth: u32,
     /// Layer properties
     pub properties: Properties,
 }
 
 /// Fired when an image layer is spawned.
-#[derive(Event, Debug, Clone)]
+#[derive(EntityEvent, Message, Debug, Clone)]
 pub struct ImageLayerSpawned {
     /// The spawned layer entity
+    #[event_target]
     pub entity: Entity,
     /// The parent map entity
     pub map_entity: Entity,
     /// The layer's ID from Tiled
     pub layer_id: u32,
+    /// The group layer containing this layer (`None` for top-level layers)
+    pub parent_group: Option<Entity>,
+    /// How many groups the layer is nested in (0 for top-level layers)
+    pub depth: u32,
     /// Layer properties
     pub properties: Properties,
 }
 
-/// Fired when a group layer is spawned.
-#[derive(Event, Debug, Clone)]
+/// Fired when a group layer is spawned, before the layers inside it.
+///
+/// Those layers' events follow with this group as their `parent_group` and a
+/// `depth` one greater, so plugins can build a hierarchy (e.g. a render graph)
+/// top-down as the events arrive.
+#[derive(EntityEvent, Message, Debug, Clone)]
 pub struct GroupLayerSpawned {
     /// The spawned layer entity
+    #[event_target]
     pub entity: Entity,
     /// The parent map entity
     pub map_entity: Entity,
     /// The layer's ID from Tiled
     pub layer_id: u32,
+    /// The group layer containing this layer (`None` for top-level layers)
+    pub parent_group: Option<Entity>,
+    /// How many groups the layer is nested in (0 for top-level layers)
+    pub depth: u32,
     /// Layer properties
     pub properties: Properties,
 }
@@ -119,7 +338,8 @@ pub struct GroupLayerSpawned {
 /// for a map. Use this for initialization that requires the complete map
 /// structure to be in place.
 ///
-/// This is an `EntityEvent` that can be observed on the spawned entity.
+/// This is an `EntityEvent` that can be observed on the spawned entity, and a
+/// `Message` that can be read with `MessageReader<MapSpawned>`.
 ///
 /// # Example
 ///
@@ -130,20 +350,50 @@ pub struct GroupLayerSpawned {
 ///         readiness.map_ready = true;
 ///     });
 /// ```
-#[derive(EntityEvent, Debug, Clone)]
+#[derive(EntityEvent, Message, Debug, Clone)]
 pub struct MapSpawned {
     /// The map entity
     #[event_target]
     pub entity: Entity,
 }
 
+/// Counts of the entities spawned for a map.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpawnCounts {
+    /// Spawned layers (including group layers)
+    pub layers: usize,
+    /// Spawned objects
+    pub objects: usize,
+    /// Non-empty tiles of the spawned tile layers
+    pub tiles: usize,
+    /// Spawned objects that reused the components deserialized for an identical
+    /// object (e.g. another instance of the same template)
+    pub cached_objects: usize,
+}
+
+/// Fired on a map entity right after [`MapSpawned`], summarizing the spawn.
+///
+/// Useful for profiling large maps and for loading screens.
+#[derive(EntityEvent, Message, Debug, Clone)]
+pub struct MapSpawnReport {
+    /// The map entity
+    #[event_target]
+    pub entity: Entity,
+    /// What was spawned
+    pub counts: SpawnCounts,
+    /// Time spent building the map's spawn commands (excluding observers,
+    /// which run when the commands are applied)
+    pub duration: Duration,
+}
+
 /// Fired when a world and all its maps are fully spawned.
 ///
 /// This event is triggered after the world entity and all child map entities
 /// have been fully processed. Use this for initialization that requires
 /// the entire world structure to be in place.
 ///
-/// This is an `EntityEvent` that can be observed on the spawned entity.
+/// This is an `EntityEvent` that can be observed on the spawned entity, and a
+/// `Message` that can be read with `MessageReader<WorldSpawned>`.
 ///
 /// # Example
 ///
@@ -154,9 +404,254 @@ pub struct MapSpawned {
 ///         readiness.world_ready = true;
 ///     });
 /// ```
-#[derive(EntityEvent, Debug, Clone)]
+#[derive(EntityEvent, Message, Debug, Clone)]
 pub struct WorldSpawned {
     /// The world entity
     #[event_target]
     pub entity: Entity,
 }
+
+/// Fired when a layer is shown or hidden at runtime.
+///
+/// Triggered by [`SetLayerVisibility`](crate::visibility::SetLayerVisibility)
+/// for every layer whose visibility actually changed. Physics plugins use it
+/// to disable the colliders of hidden layers.
+#[derive(Event, Debug, Clone)]
+pub struct LayerVisibilityChanged {
+    /// The layer entity
+    pub entity: Entity,
+    /// The parent map entity
+    pub map_entity: Entity,
+    /// The layer's ID from Tiled
+    pub layer_id: u32,
+    /// Whether the layer is now visible
+    pub visible: bool,
+}
+
+/// Fired after a layer entity is despawned. See the [module docs](self).
+#[derive(Event, Message, Debug, Clone)]
+pub struct LayerDespawned {
+    /// The despawned layer entity
+    pub entity: Entity,
+    /// The map the layer belonged to
+    pub map_entity: Entity,
+    /// The layer's ID from Tiled
+    pub layer_id: u32,
+    /// What kind of layer it was
+    pub kind: TiledLayer,
+    /// The layer's name in Tiled
+    pub name: String,
+}
+
+/// Fired after an object entity is despawned. See the [module docs](self).
+#[derive(Event, Message, Debug, Clone)]
+pub struct ObjectDespawned {
+    /// The despawned object entity
+    pub entity: Entity,
+    /// The map the object belonged to
+    pub map_entity: Entity,
+    /// The object layer the object was in, if any
+    pub layer_entity: Option<Entity>,
+    /// The object's ID from Tiled
+    pub object_id: u32,
+    /// The object's merged properties
+    pub properties: Properties,
+}
+
+/// Observer firing [`LayerDespawned`] for despawned Tiled layers.
+pub fn on_layer_despawn(
+    trigger: On<Despawn, TiledLayer>,
+    layers: Query<(&TiledLayer, &LayerId, &LayerName, &TiledLayerMapOf)>,
+    mut commands: Commands,
+) {
+    let entity = trigger.event().entity;
+    let Ok((kind, layer_id, name, map_of)) = layers.get(entity) else {
+        return;
+    };
+    let event = LayerDespawned {
+        entity,
+        map_entity: map_of.0,
+        layer_id: layer_id.0,
+        kind: *kind,
+        name: name.0.clone(),
+    };
+    commands.write_message(event.clone());
+    commands.trigger(event);
+}
+
+/// Observer firing [`ObjectDespawned`] for despawned Tiled objects.
+pub fn on_object_despawn(
+    trigger: On<Despawn, TiledObject>,
+    objects: Query<(
+        &ObjectId,
+        &TiledObjectMapOf,
+        Option<&ChildOf>,
+        Option<&MergedProperties>,
+    )>,
+    mut commands: Commands,
+) {
+    let entity = trigger.event().entity;
+    let Ok((object_id, map_of, child_of, properties)) = objects.get(entity) else {
+        return;
+    };
+    let event = ObjectDespawned {
+        entity,
+        map_entity: map_of.0,
+        layer_entity: child_of.map(ChildOf::parent),
+        object_id: object_id.0,
+        properties: properties
+            .map(|properties| properties.properties().clone())
+            .unwrap_or_default(),
+    };
+    commands.write_message(event.clone());
+    commands.trigger(event);
+}
+
+/// Fired on a tile layer entity when its tiles change at runtime.
+///
+/// Triggered by tile edits ([`ApplyTiledEdit`](crate::edit::ApplyTiledEdit), undo
+/// and redo) and destroyed tiles. Rendering and physics plugins use it to rebuild
+/// the layer's tilemap and colliders. Trigger it yourself after mutating
+/// `TileLayerData` directly.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct TilesModified {
+    /// The tile layer entity
+    #[event_target]
+    pub entity: Entity,
+    /// Grid positions of the changed tiles (Tiled coordinates, Y-down)
+    pub tiles: Vec<UVec2>,
+}
+
+/// Fired on a map entity for each feature it uses that isn't supported yet.
+///
+/// Triggered right after [`MapSpawned`], so content problems (e.g. hexagonal
+/// infinite maps, group layer parallax) surface at load time instead of
+/// rendering wrong silently. Each issue is also logged when the map asset loads.
+#[derive(EntityEvent, Message, Debug, Clone)]
+pub struct MapCompatibilityWarning {
+    /// The map entity
+    #[event_target]
+    pub entity: Entity,
+    /// The unsupported feature
+    pub issue: bevy_tiledmap_assets::prelude::MapCompatibilityIssue,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Seen(Vec<&'static str>);
+
+    #[test]
+    fn test_spawn_events_reach_observers_and_readers() {
+        let mut app = App::new();
+        app.add_message::<MapSpawned>();
+        app.init_resource::<Seen>();
+
+        let map = app.world_mut().spawn_empty().id();
+        app.world_mut()
+            .entity_mut(map)
+            .observe(|_: On<MapSpawned>, mut seen: ResMut<Seen>| {
+                seen.0.push("entity observer");
+            });
+        app.add_observer(|_: On<MapSpawned>, mut seen: ResMut<Seen>| {
+            seen.0.push("global observer");
+        });
+        app.add_systems(PreUpdate, move |mut commands: Commands| {
+            trigger_spawn_event(&mut commands, MapSpawned { entity: map });
+        });
+        app.add_systems(
+            Update,
+            move |mut messages: MessageReader<MapSpawned>, mut seen: ResMut<Seen>| {
+                for message in messages.read() {
+                    assert_eq!(message.entity, map);
+                    seen.0.push("reader");
+                }
+            },
+        );
+        app.update();
+
+        let seen = &app.world().resource::<Seen>().0;
+        assert_eq!(seen.len(), 3);
+        assert!(seen.contains(&"entity observer") && seen.contains(&"global observer"));
+        // Observers run while spawning, before systems reading the message
+        assert_eq!(seen.last(), Some(&"reader"));
+    }
+
+    #[test]
+    fn test_batch_shares_equal_properties() {
+        let mut coin = Properties::new();
+        coin.insert("value".to_string(), tiled::PropertyValue::IntValue(1));
+        let entity = |index| Entity::from_raw_u32(index).unwrap();
+
+        let batch = ObjectsSpawnedBatch::new(
+            entity(1),
+            entity(2),
+            3,
+            [
+                (entity(10), 1, coin.clone()),
+                (entity(11), 2, coin.clone()),
+                (entity(12), 3, Properties::new()),
+            ],
+        );
+        assert_eq!(batch.properties.len(), 2);
+        assert_eq!(batch.objects[1].object_id, 2);
+        assert_eq!(batch.object_properties(&batch.objects[1]), &coin);
+        assert!(batch.object_properties(&batch.objects[2]).is_empty());
+    }
+
+    #[test]
+    fn test_despawn_events_identify_layers_and_objects() {
+        let mut app = App::new();
+        app.add_message::<LayerDespawned>()
+            .add_message::<ObjectDespawned>()
+            .add_observer(on_layer_despawn)
+            .add_observer(on_object_despawn);
+
+        let map = app.world_mut().spawn_empty().id();
+        let layer = app
+            .world_mut()
+            .spawn((
+                TiledLayer::Objects,
+                LayerId(2),
+                LayerName("Pickups".to_string()),
+                TiledLayerMapOf(map),
+            ))
+            .id();
+        let coin = app
+            .world_mut()
+            .spawn((
+                TiledObject::Point,
+                ObjectId(9),
+                TiledObjectMapOf(map),
+                ChildOf(layer),
+            ))
+            .id();
+        app.world_mut().despawn(map);
+        app.world_mut().despawn(layer);
+        app.world_mut().flush();
+
+        let layers: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Messages<LayerDespawned>>()
+            .drain()
+            .collect();
+        assert_eq!(layers.len(), 1);
+        assert_eq!(
+            (layers[0].entity, layers[0].map_entity, layers[0].layer_id),
+            (layer, map, 2)
+        );
+        assert_eq!(layers[0].name, "Pickups");
+
+        let objects: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Messages<ObjectDespawned>>()
+            .drain()
+            .collect();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].entity, coin);
+        assert_eq!(objects[0].layer_entity, Some(layer));
+        assert_eq!(objects[0].object_id, 9);
+    }
+}
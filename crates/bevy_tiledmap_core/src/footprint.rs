@@ -0,0 +1,286 @@
+//! Memory and entity footprint of spawned maps.
+//!
+//! [`map_footprint`] reports what a spawned map costs: the size of its tile
+//! data, how many entities it spawned by kind, the texture memory of the
+//! tilesets and images it references, and its collider count. Use it to budget
+//! content for low-end devices, e.g. from an exclusive system or a test:
+//!
+//! ```rust,ignore
+//! fn report_footprints(world: &mut World) {
+//!     let maps: Vec<Entity> = world
+//!         .query_filtered::<Entity, With<TiledMap>>()
+//!         .iter(world)
+//!         .collect();
+//!     for map in maps {
+//!         if let Some(footprint) = map_footprint(world, map) {
+//!             info!("{footprint}");
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Colliders belong to the physics backend, so core only counts entities with a
+//! component registered through
+//! [`FootprintAppExt::register_footprint_collider`]; the Avian integration
+//! registers its `Collider`.
+//!
+//! Byte sizes are estimates: tile data counts the tile grids of
+//! [`TileLayerData`], and texture memory counts each referenced image once,
+//! from its pixel data or, once that is moved to the GPU, from its size and
+//! format (without mipmaps).
+
+use std::fmt;
+use std::mem::size_of;
+
+use bevy::ecs::component::ComponentId;
+use bevy::platform::collections::HashSet;
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::{TiledMapAsset, TiledTilesetAsset};
+
+use crate::components::{TileInstance, TileLayerData, TiledLayer, TiledMap, TiledObject};
+
+/// What a spawned map costs.
+///
+/// Returned by [`map_footprint`]; see the [module docs](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MapFootprint {
+    /// Tile layers
+    pub tile_layers: usize,
+    /// Object layers
+    pub object_layers: usize,
+    /// Image layers
+    pub image_layers: usize,
+    /// Group layers
+    pub group_layers: usize,
+    /// Objects
+    pub objects: usize,
+    /// Entities with a registered collider component
+    pub colliders: usize,
+    /// Entities that aren't layers, objects or colliders (e.g. render entities)
+    pub other_entities: usize,
+    /// Every entity of the map's hierarchy, including the map itself
+    pub entities: usize,
+    /// Non-empty tiles across all tile layers
+    pub tiles: usize,
+    /// Estimated bytes of tile layer data
+    pub tile_data_bytes: usize,
+    /// Distinct tileset and image layer images referenced by the map
+    pub textures: usize,
+    /// Estimated bytes of those images (loaded ones only)
+    pub texture_bytes: usize,
+}
+
+impl fmt::Display for MapFootprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "layers: {} tile, {} object, {} image, {} group",
+            self.tile_layers, self.object_layers, self.image_layers, self.group_layers
+        )?;
+        writeln!(
+            f,
+            "entities: {} ({} objects, {} colliders, {} other)",
+            self.entities, self.objects, self.colliders, self.other_entities
+        )?;
+        writeln!(
+            f,
+            "tiles: {} ({})",
+            self.tiles,
+            format_bytes(self.tile_data_bytes)
+        )?;
+        write!(
+            f,
+            "textures: {} ({})",
+            self.textures,
+            format_bytes(self.texture_bytes)
+        )
+    }
+}
+
+/// Format a byte count with a binary unit.
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Collider components counted by [`map_footprint`].
+#[derive(Resource, Debug, Clone, Default)]
+pub struct FootprintColliders(Vec<ComponentId>);
+
+/// Extension trait for counting colliders in map footprints.
+pub trait FootprintAppExt {
+    /// Count entities with `C` as colliders in [`MapFootprint::colliders`].
+    fn register_footprint_collider<C: Component>(&mut self) -> &mut Self;
+}
+
+impl FootprintAppExt for App {
+    fn register_footprint_collider<C: Component>(&mut self) -> &mut Self {
+        let id = self.world_mut().register_component::<C>();
+        let mut colliders = self
+            .world_mut()
+            .get_resource_or_init::<FootprintColliders>();
+        if !colliders.0.contains(&id) {
+            colliders.0.push(id);
+        }
+        self
+    }
+}
+
+/// Report the footprint of a spawned map.
+///
+/// Returns `None` if `map` isn't a [`TiledMap`]. Texture memory is only known
+/// once the map asset is loaded; see the [module docs](self).
+pub fn map_footprint(world: &World, map: Entity) -> Option<MapFootprint> {
+    let handle = &world.get::<TiledMap>(map)?.handle;
+    let colliders = world
+        .get_resource::<FootprintColliders>()
+        .map(|colliders| colliders.0.as_slice())
+        .unwrap_or_default();
+
+    let mut footprint = MapFootprint::default();
+    let mut stack = vec![map];
+    while let Some(entity) = stack.pop() {
+        let Ok(entity) = world.get_entity(entity) else {
+            continue;
+        };
+        footprint.entities += 1;
+        let is_object = entity.contains::<TiledObject>();
+        let is_collider = colliders.iter().any(|id| entity.contains_id(*id));
+        footprint.objects += usize::from(is_object);
+        footprint.colliders += usize::from(is_collider);
+        match entity.get::<TiledLayer>() {
+            Some(TiledLayer::Tiles) => footprint.tile_layers += 1,
+            Some(TiledLayer::Objects) => footprint.object_layers += 1,
+            Some(TiledLayer::Image) => footprint.image_layers += 1,
+            Some(TiledLayer::Group) => footprint.group_layers += 1,
+            None if entity.id() != map && !is_object && !is_collider => {
+                footprint.other_entities += 1;
+            }
+            None => {}
+        }
+        if let Some(data) = entity.get::<TileLayerData>() {
+            footprint.tiles += data.tiles.iter().flatten().count();
+            footprint.tile_data_bytes += size_of::<TileLayerData>()
+                + data.tiles.capacity() * size_of::<Option<TileInstance>>();
+        }
+        if let Some(children) = entity.get::<Children>() {
+            stack.extend(children.iter());
+        }
+    }
+
+    let images = referenced_images(world, handle);
+    footprint.textures = images.len();
+    if let Some(assets) = world.get_resource::<Assets<Image>>() {
+        footprint.texture_bytes = images
+            .iter()
+            .filter_map(|id| assets.get(*id))
+            .map(image_bytes)
+            .sum();
+    }
+    Some(footprint)
+}
+
+/// Distinct images of a map's tilesets and image layers.
+fn referenced_images(world: &World, handle: &Handle<TiledMapAsset>) -> HashSet<AssetId<Image>> {
+    let mut images = HashSet::new();
+    let Some(map_asset) = world
+        .get_resource::<Assets<TiledMapAsset>>()
+        .and_then(|assets| assets.get(handle))
+    else {
+        return images;
+    };
+    images.extend(map_asset.images.values().map(Handle::id));
+    if let Some(tilesets) = world.get_resource::<Assets<TiledTilesetAsset>>() {
+        for tileset in map_asset
+            .tilesets
+            .values()
+            .filter_map(|reference| tilesets.get(&reference.handle))
+        {
+            images.extend(tileset.atlas_image.iter().map(Handle::id));
+            images.extend(tileset.tile_images.values().map(Handle::id));
+        }
+    }
+    images
+}
+
+/// Bytes of an image's pixels, estimated from its size and format once the
+/// pixel data only lives on the GPU.
+fn image_bytes(image: &Image) -> usize {
+    if let Some(data) = &image.data {
+        return data.len();
+    }
+    let size = image.texture_descriptor.size;
+    let texel = image
+        .texture_descriptor
+        .format
+        .block_copy_size(None)
+        .unwrap_or(4);
+    (size.width * size.height * size.depth_or_array_layers * texel) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component)]
+    struct TestCollider;
+
+    #[test]
+    fn test_map_footprint_counts_entities_by_kind() {
+        let mut app = App::new();
+        app.register_footprint_collider::<TestCollider>();
+        let world = app.world_mut();
+
+        let mut data = TileLayerData::empty(4, 4);
+        data.tiles[0] = Some(TileInstance {
+            gid: 1,
+            tileset_handle: Handle::default(),
+            tile_id: 0,
+            flipped_h: false,
+            flipped_v: false,
+            flipped_d: false,
+            variant: None,
+        });
+        let ground = world.spawn((TiledLayer::Tiles, data)).id();
+        let coin = world.spawn((TiledObject::Point, TestCollider)).id();
+        let marker = world.spawn(TiledObject::Point).id();
+        let items = world
+            .spawn(TiledLayer::Objects)
+            .add_children(&[coin, marker])
+            .id();
+        let group = world.spawn(TiledLayer::Group).add_child(items).id();
+        let boundary = world.spawn(TestCollider).id();
+        let label = world.spawn(Transform::default()).id();
+        let map = world
+            .spawn(TiledMap {
+                handle: Handle::default(),
+            })
+            .add_children(&[ground, group, boundary, label])
+            .id();
+
+        let footprint = map_footprint(world, map).unwrap();
+        assert_eq!(footprint.tile_layers, 1);
+        assert_eq!(footprint.object_layers, 1);
+        assert_eq!(footprint.group_layers, 1);
+        assert_eq!(footprint.objects, 2);
+        assert_eq!(footprint.colliders, 2);
+        assert_eq!(footprint.entities, 8);
+        assert_eq!(footprint.other_entities, 1);
+        assert_eq!(footprint.tiles, 1);
+        assert!(footprint.tile_data_bytes >= 16 * size_of::<Option<TileInstance>>());
+        // The map asset isn't loaded
+        assert_eq!(footprint.textures, 0);
+
+        assert!(map_footprint(world, ground).is_none());
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+    }
+}
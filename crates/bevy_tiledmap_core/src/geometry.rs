@@ -0,0 +1,270 @@
+//! Engine-agnostic collision geometry.
+//!
+//! Converts Tiled object shapes and tileset collision shapes into plain
+//! [`CollisionShape`]s in Bevy's coordinate system (Y-up, centered), so any
+//! physics backend can build its colliders from them. `bevy_tiledmap_avian`
+//! builds its colliders on top of these functions (see [`crate::physics`]).
+//!
+//! Conventions:
+//! - Rectangles and circles are centered on their position.
+//! - Polygon and polyline vertices are relative to the Tiled object origin, with
+//!   Y flipped (see [`flip_y`]).
+//! - Rotations are in radians, counter-clockwise.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! for placed in tile_collision_shapes(&tileset, tile_id) {
+//!     let body = my_physics.add_shape(to_backend_shape(&placed.shape));
+//!     body.set_local_pose(tile_center + placed.offset, placed.rotation);
+//! }
+//! ```
+
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+use tiled::ObjectShape;
+
+use crate::components::object::TiledObject;
+
+/// Radius of the circle standing in for point objects.
+pub const POINT_RADIUS: f32 = 1.0;
+
+/// A collision shape in Bevy's coordinate system.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollisionShape {
+    /// Axis-aligned rectangle centered on its position.
+    Rectangle {
+        /// Full size
+        size: Vec2,
+    },
+    /// Circle centered on its position. Ellipses are approximated by the
+    /// circle containing them, points by a circle of [`POINT_RADIUS`].
+    Circle {
+        /// Circle radius
+        radius: f32,
+    },
+    /// Closed polygon, possibly concave.
+    Polygon {
+        /// Vertices (Y-up)
+        vertices: Vec<Vec2>,
+    },
+    /// Open chain of line segments.
+    Polyline {
+        /// Vertices (Y-up)
+        vertices: Vec<Vec2>,
+    },
+}
+
+/// A [`CollisionShape`] placed relative to a parent (e.g. a tile center).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacedShape {
+    /// Position relative to the parent
+    pub offset: Vec2,
+    /// Rotation in radians (counter-clockwise)
+    pub rotation: f32,
+    /// The shape itself
+    pub shape: CollisionShape,
+}
+
+/// Convert Tiled points (Y-down) to Bevy vertices (Y-up).
+pub fn flip_y(points: &[(f32, f32)]) -> Vec<Vec2> {
+    points.iter().map(|(x, y)| Vec2::new(*x, -*y)).collect()
+}
+
+/// Get the collision shape of a spawned object.
+///
+/// Returns `None` for objects without a collision shape (text). Tile objects
+/// use their bounds; use [`tile_collision_shapes`] for the tile's own shapes.
+pub fn object_shape(object: &TiledObject) -> Option<CollisionShape> {
+    match object {
+        TiledObject::Rectangle { width, height } | TiledObject::Tile { width, height, .. } => {
+            Some(CollisionShape::Rectangle {
+                size: Vec2::new(*width, *height),
+            })
+        }
+        TiledObject::Ellipse { width, height } => Some(CollisionShape::Circle {
+            radius: width.max(*height) / 2.0,
+        }),
+        TiledObject::Polygon { vertices } => Some(CollisionShape::Polygon {
+            vertices: vertices.clone(),
+        }),
+        TiledObject::Polyline { vertices } => Some(CollisionShape::Polyline {
+            vertices: vertices.clone(),
+        }),
+        TiledObject::Point => Some(CollisionShape::Circle {
+            radius: POINT_RADIUS,
+        }),
+        _ => None,
+    }
+}
+
+/// Get the collision shapes a tileset defines for a tile.
+///
+/// Offsets are relative to the tile center. Returns an empty list if the tile
+/// has no collision shapes.
+///
+/// # Arguments
+///
+/// * `tileset` - The tileset asset containing the tile
+/// * `local_tile_id` - The local tile ID (0-based, NOT a GID)
+pub fn tile_collision_shapes(tileset: &TiledTilesetAsset, local_tile_id: u32) -> Vec<PlacedShape> {
+    let Some(tile) = tileset.tileset.get_tile(local_tile_id) else {
+        return Vec::new();
+    };
+    let Some(collision_group) = tile.collision.as_ref() else {
+        return Vec::new();
+    };
+
+    let tile_center = tileset.tile_size.as_vec2() / 2.0;
+    collision_group
+        .object_data()
+        .iter()
+        .filter_map(|object| {
+            tile_object_shape(
+                &object.shape,
+                Vec2::new(object.x, object.y),
+                object.rotation,
+                tile_center,
+            )
+        })
+        .collect()
+}
+
+/// Check if a tile has collision shapes defined.
+///
+/// This is a faster check than [`tile_collision_shapes`] when you only need
+/// to know if a tile has collision data.
+pub fn tile_has_collision_shape(tileset: &TiledTilesetAsset, local_tile_id: u32) -> bool {
+    tileset
+        .tileset
+        .get_tile(local_tile_id)
+        .and_then(|tile| tile.collision.as_ref().map(|c| !c.object_data().is_empty()))
+        .unwrap_or(false)
+}
+
+/// Get the size of a tile's collision shape if it is a single rectangle at the
+/// tile origin.
+///
+/// Only such tiles can be merged with their neighbors into larger rectangles.
+pub fn tile_rectangle_size(tileset: &TiledTilesetAsset, local_tile_id: u32) -> Option<Vec2> {
+    let tile = tileset.tileset.get_tile(local_tile_id)?;
+    let [object] = tile.collision.as_ref()?.object_data() else {
+        return None;
+    };
+
+    // Rects with offsets can't be merged with the simple grid merging algorithm
+    if object.x.abs() > 0.1 || object.y.abs() > 0.1 || object.rotation.abs() > 0.1 {
+        return None;
+    }
+
+    match object.shape {
+        ObjectShape::Rect { width, height } => Some(Vec2::new(width, height)),
+        _ => None,
+    }
+}
+
+/// Convert a tile collision object at `position` (Tiled tile coordinates,
+/// rotation in clockwise degrees), placing it relative to the tile center.
+fn tile_object_shape(
+    shape: &ObjectShape,
+    position: Vec2,
+    rotation: f32,
+    tile_center: Vec2,
+) -> Option<PlacedShape> {
+    // Offset from tile center to a point given in Tiled tile coordinates
+    let offset_to = |x: f32, y: f32| Vec2::new(x - tile_center.x, -(y - tile_center.y));
+
+    let (offset, shape) = match shape {
+        // Tiled anchors rectangles and ellipses at their top-left corner
+        ObjectShape::Rect { width, height } => (
+            offset_to(position.x + width / 2.0, position.y + height / 2.0),
+            CollisionShape::Rectangle {
+                size: Vec2::new(*width, *height),
+            },
+        ),
+        ObjectShape::Ellipse { width, height } => (
+            offset_to(position.x + width / 2.0, position.y + height / 2.0),
+            CollisionShape::Circle {
+                radius: width.max(*height) / 2.0,
+            },
+        ),
+        ObjectShape::Polygon { points } => (
+            offset_to(position.x, position.y),
+            CollisionShape::Polygon {
+                vertices: flip_y(points),
+            },
+        ),
+        ObjectShape::Polyline { points } => (
+            offset_to(position.x, position.y),
+            CollisionShape::Polyline {
+                vertices: flip_y(points),
+            },
+        ),
+        ObjectShape::Point(x, y) => (
+            offset_to(*x, *y),
+            CollisionShape::Circle {
+                radius: POINT_RADIUS,
+            },
+        ),
+        ObjectShape::Text { .. } => return None,
+    };
+
+    Some(PlacedShape {
+        offset,
+        rotation: -rotation.to_radians(),
+        shape,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_shape() {
+        let ellipse = TiledObject::Ellipse {
+            width: 32.0,
+            height: 16.0,
+        };
+        assert_eq!(
+            object_shape(&ellipse),
+            Some(CollisionShape::Circle { radius: 16.0 })
+        );
+        assert_eq!(
+            object_shape(&TiledObject::Point),
+            Some(CollisionShape::Circle {
+                radius: POINT_RADIUS
+            })
+        );
+    }
+
+    #[test]
+    fn test_tile_object_shape_offsets() {
+        let tile_center = Vec2::new(8.0, 8.0);
+        // Bottom half of a 16x16 tile
+        let rect = ObjectShape::Rect {
+            width: 16.0,
+            height: 8.0,
+        };
+        let placed = tile_object_shape(&rect, Vec2::new(0.0, 8.0), 0.0, tile_center).unwrap();
+        assert_eq!(placed.offset, Vec2::new(0.0, -4.0));
+        assert_eq!(
+            placed.shape,
+            CollisionShape::Rectangle {
+                size: Vec2::new(16.0, 8.0)
+            }
+        );
+
+        let polygon = ObjectShape::Polygon {
+            points: vec![(0.0, 0.0), (16.0, 0.0), (16.0, -16.0)],
+        };
+        let placed = tile_object_shape(&polygon, Vec2::new(0.0, 16.0), 0.0, tile_center).unwrap();
+        assert_eq!(placed.offset, Vec2::new(-8.0, -8.0));
+        assert_eq!(
+            placed.shape,
+            CollisionShape::Polygon {
+                vertices: vec![Vec2::ZERO, Vec2::new(16.0, 0.0), Vec2::new(16.0, 16.0)]
+            }
+        );
+    }
+}
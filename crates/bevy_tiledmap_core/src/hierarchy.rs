@@ -0,0 +1,149 @@
+//! Typed traversal of a spawned map's layers and objects.
+//!
+//! A map's top-level layers are listed in its [`LayersInMap`], the layers of a
+//! group are the group's children, and objects are children of their object
+//! layer. [`TiledMapChildren`] walks that hierarchy, so traversal code doesn't
+//! have to match on [`TiledLayer`] and follow `Children` itself:
+//!
+//! ```rust,ignore
+//! fn count_coins(map: Single<Entity, With<TiledMap>>, children: TiledMapChildren) {
+//!     for layer in children.tile_layers(*map) {
+//!         // ...
+//!     }
+//!     let objects = children.objects(*map).count();
+//! }
+//! ```
+//!
+//! Layers are visited in draw order (bottom to top), each group before the
+//! layers inside it, and objects in their layer's draw order. Objects added to
+//! an object layer at runtime are included.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::components::{LayersInMap, TiledLayer, TiledObject};
+
+/// System param iterating the layers and objects of spawned maps.
+///
+/// See the [module docs](self).
+#[derive(SystemParam)]
+pub struct TiledMapChildren<'w, 's> {
+    maps: Query<'w, 's, &'static LayersInMap>,
+    layers: Query<'w, 's, (&'static TiledLayer, Option<&'static Children>)>,
+    objects: Query<'w, 's, (), With<TiledObject>>,
+}
+
+impl TiledMapChildren<'_, '_> {
+    /// Iterate every layer of `map`, including the layers inside groups.
+    ///
+    /// Yields nothing if `map` isn't spawned yet.
+    pub fn layers(&self, map: Entity) -> impl Iterator<Item = Entity> + '_ {
+        let mut stack: Vec<Entity> = self
+            .maps
+            .get(map)
+            .map(|layers| layers.0.iter().rev().copied().collect())
+            .unwrap_or_default();
+        std::iter::from_fn(move || {
+            let layer = stack.pop()?;
+            if let Ok((TiledLayer::Group, Some(children))) = self.layers.get(layer) {
+                stack.extend(
+                    children
+                        .iter()
+                        .rev()
+                        .filter(|child| self.layers.contains(*child)),
+                );
+            }
+            Some(layer)
+        })
+    }
+
+    /// Iterate the layers of `map` of one kind.
+    pub fn layers_of_kind(
+        &self,
+        map: Entity,
+        kind: TiledLayer,
+    ) -> impl Iterator<Item = Entity> + '_ {
+        self.layers(map)
+            .filter(move |layer| self.layer_kind(*layer) == Some(kind))
+    }
+
+    /// Iterate the tile layers of `map`.
+    pub fn tile_layers(&self, map: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.layers_of_kind(map, TiledLayer::Tiles)
+    }
+
+    /// Iterate the object layers of `map`.
+    pub fn object_layers(&self, map: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.layers_of_kind(map, TiledLayer::Objects)
+    }
+
+    /// Iterate the image layers of `map`.
+    pub fn image_layers(&self, map: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.layers_of_kind(map, TiledLayer::Image)
+    }
+
+    /// Iterate the group layers of `map`.
+    pub fn group_layers(&self, map: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.layers_of_kind(map, TiledLayer::Group)
+    }
+
+    /// Iterate the objects of every object layer of `map`.
+    pub fn objects(&self, map: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.object_layers(map)
+            .flat_map(|layer| self.layer_objects(layer))
+    }
+
+    /// Iterate the objects of one object layer.
+    pub fn layer_objects(&self, layer: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.layers
+            .get(layer)
+            .ok()
+            .and_then(|(_, children)| children)
+            .into_iter()
+            .flat_map(RelationshipTarget::iter)
+            .filter(|child| self.objects.contains(*child))
+    }
+
+    /// Get the kind of a layer entity (`None` if it isn't a layer).
+    pub fn layer_kind(&self, layer: Entity) -> Option<TiledLayer> {
+        self.layers.get(layer).ok().map(|(kind, _)| *kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemState;
+
+    use super::*;
+
+    #[test]
+    fn test_map_children_visit_layers_in_draw_order() {
+        let mut world = World::new();
+        let ground = world.spawn(TiledLayer::Tiles).id();
+        let coin = world.spawn(TiledObject::Point).id();
+        let door = world.spawn(TiledObject::Point).id();
+        let items = world
+            .spawn(TiledLayer::Objects)
+            .add_children(&[coin, door])
+            .id();
+        let sky = world.spawn(TiledLayer::Image).id();
+        let group = world
+            .spawn(TiledLayer::Group)
+            .add_children(&[items, sky])
+            .id();
+        let top = world.spawn(TiledLayer::Tiles).id();
+        let map = world.spawn(LayersInMap(vec![ground, group, top])).id();
+
+        let mut state = SystemState::<TiledMapChildren>::new(&mut world);
+        let children = state.get(&world);
+        assert_eq!(
+            children.layers(map).collect::<Vec<_>>(),
+            [ground, group, items, sky, top]
+        );
+        assert_eq!(children.tile_layers(map).collect::<Vec<_>>(), [ground, top]);
+        assert_eq!(children.group_layers(map).collect::<Vec<_>>(), [group]);
+        assert_eq!(children.objects(map).collect::<Vec<_>>(), [coin, door]);
+        assert_eq!(children.layer_kind(sky), Some(TiledLayer::Image));
+        assert_eq!(children.layers(coin).count(), 0);
+    }
+}
@@ -0,0 +1,293 @@
+//! Laying out several maps side by side.
+//!
+//! A [`MapLayout`] entity positions its [`TiledMap`] children from their
+//! [`MapGeometry`]: in a row, a column, a grid, or at custom positions. Menus
+//! showing several levels and hub worlds stitched from separate maps don't need
+//! any bounds math:
+//!
+//! ```rust,ignore
+//! commands
+//!     .spawn(MapLayout::grid(3, Vec2::splat(64.0)))
+//!     .with_children(|layout| {
+//!         for level in ["maps/level1.tmx", "maps/level2.tmx", "maps/level3.tmx"] {
+//!             layout.spawn(TiledMap {
+//!                 handle: asset_server.load(level),
+//!             });
+//!         }
+//!     });
+//! ```
+//!
+//! Maps are placed in child order once all of them are spawned, so they don't
+//! move around while loading, and are moved again when the layout or a map's
+//! geometry changes. Row, column and grid layouts are centered on the layout
+//! entity. The layout entity gets [`CameraBounds`] covering all its maps, e.g.
+//! for [`CameraConfinement::to`](crate::camera::CameraConfinement::to).
+//!
+//! Layouts are applied in `PostUpdate`, before
+//! [`CameraConfinementSystems`](crate::camera::CameraConfinementSystems).
+
+use bevy::prelude::*;
+
+use crate::camera::CameraBounds;
+use crate::components::{MapGeometry, TiledMap};
+
+/// How the maps of a [`MapLayout`] are arranged.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub enum MapArrangement {
+    /// Left to right
+    Row,
+    /// Top to bottom
+    Column,
+    /// Rows of `columns` maps, left to right and top to bottom
+    Grid {
+        /// Maps per row
+        columns: u32,
+    },
+    /// The bottom-left corner of each map, in the layout's space
+    ///
+    /// Maps without a position are placed at the origin.
+    Custom(Vec<Vec2>),
+}
+
+/// Positions the [`TiledMap`] children of its entity next to each other.
+///
+/// See the [module docs](self).
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+#[require(Transform, Visibility)]
+pub struct MapLayout {
+    /// How the maps are arranged
+    pub arrangement: MapArrangement,
+
+    /// Space between rows and columns (ignored by custom arrangements)
+    pub spacing: Vec2,
+
+    /// Z offset between consecutive maps
+    pub z_step: f32,
+}
+
+impl MapLayout {
+    /// Arrange maps left to right, `spacing` apart.
+    pub fn row(spacing: f32) -> Self {
+        Self::new(MapArrangement::Row, Vec2::new(spacing, 0.0))
+    }
+
+    /// Arrange maps top to bottom, `spacing` apart.
+    pub fn column(spacing: f32) -> Self {
+        Self::new(MapArrangement::Column, Vec2::new(0.0, spacing))
+    }
+
+    /// Arrange maps in rows of `columns`, with `spacing` between rows and columns.
+    pub fn grid(columns: u32, spacing: Vec2) -> Self {
+        Self::new(MapArrangement::Grid { columns }, spacing)
+    }
+
+    /// Place the maps' bottom-left corners at `positions`.
+    pub fn custom(positions: Vec<Vec2>) -> Self {
+        Self::new(MapArrangement::Custom(positions), Vec2::ZERO)
+    }
+
+    fn new(arrangement: MapArrangement, spacing: Vec2) -> Self {
+        Self {
+            arrangement,
+            spacing,
+            z_step: 0.0,
+        }
+    }
+
+    /// Offset consecutive maps by `z_step` along Z.
+    pub fn with_z_step(mut self, z_step: f32) -> Self {
+        self.z_step = z_step;
+        self
+    }
+
+    /// Compute where maps of the given sizes go.
+    ///
+    /// Returns the bottom-left corner of each map and the bounds of all maps, in
+    /// the layout's space. Grid cells are as wide as their column's widest map
+    /// and as tall as their row's tallest map, with each map centered in its cell.
+    pub fn arrange(&self, sizes: &[Vec2]) -> (Vec<Vec2>, Rect) {
+        let columns = match &self.arrangement {
+            MapArrangement::Row => sizes.len().max(1),
+            MapArrangement::Column => 1,
+            MapArrangement::Grid { columns } => (*columns).max(1) as usize,
+            MapArrangement::Custom(positions) => {
+                let corners: Vec<Vec2> = (0..sizes.len())
+                    .map(|index| positions.get(index).copied().unwrap_or_default())
+                    .collect();
+                let bounds = corners
+                    .iter()
+                    .zip(sizes)
+                    .map(|(corner, size)| Rect::from_corners(*corner, *corner + *size))
+                    .reduce(|bounds, rect| bounds.union(rect))
+                    .unwrap_or_default();
+                return (corners, bounds);
+            }
+        };
+
+        let rows = sizes.len().div_ceil(columns);
+        let mut widths = vec![0.0_f32; columns];
+        let mut heights = vec![0.0_f32; rows];
+        for (index, size) in sizes.iter().enumerate() {
+            widths[index % columns] = widths[index % columns].max(size.x);
+            heights[index / columns] = heights[index / columns].max(size.y);
+        }
+
+        // Left edge of each column and top edge of each row, rows going down
+        let lefts: Vec<f32> = widths
+            .iter()
+            .scan(0.0, |x, width| {
+                let left = *x;
+                *x += width + self.spacing.x;
+                Some(left)
+            })
+            .collect();
+        let tops: Vec<f32> = heights
+            .iter()
+            .scan(0.0, |y, height| {
+                let top = *y;
+                *y -= height + self.spacing.y;
+                Some(top)
+            })
+            .collect();
+
+        let total = Vec2::new(
+            widths.iter().sum::<f32>() + self.spacing.x * columns.saturating_sub(1) as f32,
+            heights.iter().sum::<f32>() + self.spacing.y * rows.saturating_sub(1) as f32,
+        );
+        // Center the layout on its origin
+        let shift = Vec2::new(-total.x, total.y) / 2.0;
+
+        let corners = sizes
+            .iter()
+            .enumerate()
+            .map(|(index, size)| {
+                let (column, row) = (index % columns, index / columns);
+                let cell = Vec2::new(widths[column], heights[row]);
+                let cell_bottom_left = Vec2::new(lefts[column], tops[row] - cell.y);
+                cell_bottom_left + (cell - *size) / 2.0 + shift
+            })
+            .collect();
+        (corners, Rect::from_center_size(Vec2::ZERO, total))
+    }
+}
+
+/// System positioning the maps of every [`MapLayout`].
+pub fn apply_map_layouts(
+    mut commands: Commands,
+    layouts: Query<(Entity, &MapLayout, &Children, Option<&CameraBounds>)>,
+    mut maps: Query<(Option<&MapGeometry>, &mut Transform), With<TiledMap>>,
+) {
+    'layouts: for (layout_entity, layout, children, camera_bounds) in &layouts {
+        let mut map_entities = Vec::new();
+        let mut bounds = Vec::new();
+        for child in children {
+            let Ok((geometry, _)) = maps.get(*child) else {
+                continue;
+            };
+            // Wait until every map is spawned
+            let Some(geometry) = geometry else {
+                continue 'layouts;
+            };
+            map_entities.push(*child);
+            bounds.push(geometry.bounds);
+        }
+
+        let sizes: Vec<Vec2> = bounds.iter().map(Rect::size).collect();
+        let (corners, layout_bounds) = layout.arrange(&sizes);
+        for (index, ((map_entity, map_bounds), corner)) in
+            map_entities.iter().zip(&bounds).zip(corners).enumerate()
+        {
+            let Ok((_, mut transform)) = maps.get_mut(*map_entity) else {
+                continue;
+            };
+            let translation = (corner - map_bounds.min).extend(index as f32 * layout.z_step);
+            if transform.translation != translation {
+                transform.translation = translation;
+            }
+        }
+
+        if camera_bounds.is_none_or(|camera_bounds| camera_bounds.rect != layout_bounds) {
+            commands.entity(layout_entity).insert(CameraBounds {
+                rect: layout_bounds,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_layout_cells_fit_largest_maps() {
+        let layout = MapLayout::grid(2, Vec2::new(10.0, 20.0));
+        let sizes = [
+            Vec2::new(100.0, 100.0),
+            Vec2::new(50.0, 50.0),
+            Vec2::new(60.0, 200.0),
+        ];
+        let (corners, bounds) = layout.arrange(&sizes);
+
+        // Columns 100 and 50 wide, rows 100 and 200 tall
+        assert_eq!(bounds.size(), Vec2::new(160.0, 320.0));
+        assert_eq!(corners[0], Vec2::new(-80.0, 60.0));
+        assert_eq!(corners[1], Vec2::new(30.0, 85.0));
+        assert_eq!(corners[2], Vec2::new(-60.0, -160.0));
+    }
+
+    #[test]
+    fn test_custom_layout_places_maps_as_given() {
+        let layout = MapLayout::custom(vec![Vec2::new(0.0, 0.0), Vec2::new(320.0, 0.0)]);
+        let sizes = [Vec2::splat(320.0), Vec2::splat(320.0), Vec2::splat(32.0)];
+        let (corners, bounds) = layout.arrange(&sizes);
+
+        assert_eq!(corners, vec![Vec2::ZERO, Vec2::new(320.0, 0.0), Vec2::ZERO]);
+        assert_eq!(bounds, Rect::new(0.0, 0.0, 640.0, 320.0));
+    }
+
+    #[test]
+    fn test_layout_moves_spawned_maps() {
+        let mut app = App::new();
+        app.add_systems(Update, apply_map_layouts);
+        let layout = app.world_mut().spawn(MapLayout::column(0.0)).id();
+        let first = app
+            .world_mut()
+            .spawn((
+                TiledMap {
+                    handle: Handle::default(),
+                },
+                MapGeometry::new(10, 10, 16.0, 16.0),
+                ChildOf(layout),
+            ))
+            .id();
+        let second = app
+            .world_mut()
+            .spawn((
+                TiledMap {
+                    handle: Handle::default(),
+                },
+                ChildOf(layout),
+            ))
+            .id();
+
+        // The second map isn't spawned yet
+        app.update();
+        assert_eq!(
+            app.world().get::<Transform>(first).unwrap().translation,
+            Vec3::ZERO
+        );
+
+        app.world_mut()
+            .entity_mut(second)
+            .insert(MapGeometry::new(10, 10, 16.0, 16.0));
+        app.update();
+        let translation = |entity| app.world().get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation(first), Vec3::new(-80.0, 0.0, 0.0));
+        assert_eq!(translation(second), Vec3::new(-80.0, -160.0, 0.0));
+        assert_eq!(
+            app.world().get::<CameraBounds>(layout).unwrap().rect,
+            Rect::new(-80.0, -160.0, 80.0, 160.0)
+        );
+    }
+}
@@ -48,36 +48,123 @@
 //! }
 //! ```
 
+// Lets `#[derive(TiledClass)]` resolve `::bevy_tiledmap_core` paths inside this crate
+extern crate self as bevy_tiledmap_core;
+
+pub mod camera;
+pub mod chunks;
 pub mod components;
+pub mod conditions;
 pub mod debug;
+pub mod destructible;
+pub mod edit;
+pub mod effects;
 pub mod events;
+pub mod footprint;
+pub mod geometry;
+pub mod hierarchy;
+pub mod layout;
+pub mod localization;
+pub mod names;
+pub mod object_transform;
+pub mod occluders;
+pub mod patch;
+pub mod physics;
 pub mod plugin;
+pub mod prefab;
 pub mod project;
 pub mod properties;
+pub mod raycast;
+pub mod render_backend;
+pub mod replication;
+pub mod roads;
+pub mod save;
+pub mod simulation;
 pub mod spawn;
 pub mod systems;
+pub mod variation;
+pub mod visibility;
 
 pub mod prelude {
     //! Common imports for `bevy_tiledmap_core` users.
 
+    pub use crate::camera::{
+        CameraBounds, CameraConfinement, CameraConfinementSystems, PixelPerfectCamera,
+    };
+    pub use crate::chunks::{
+        ChunkEvicted, ChunkSpawned, ChunkStreamingConfig, ChunkViewer, MapChunks,
+        StreamedTileLayer, TiledChunk, TiledChunkLayer,
+    };
     pub use crate::components::{
-        LayerId, MapGeometry, ObjectId, TiledLayer, TiledLayerMapOf, TiledMap, TiledObject,
-        TiledObjectMapOf, TiledSceneRoot, TiledWorld,
+        LayerClass, LayerId, LayerName, MapGeometry, MapInstanceId, ObjectClass, ObjectId,
+        ObjectName, ObjectPlacement, TileClassGrid, TiledLayer, TiledLayerMapOf, TiledMap,
+        TiledMapInfo, TiledObject, TiledObjectMapOf, TiledObjectRef, TiledSceneRoot, TiledWorld,
+        WorldMapProperties,
+    };
+    pub use crate::conditions::{
+        ConditionFailBehavior, SpawnConditionAppExt, SpawnConditionEvaluator, SpawnConditionUnmet,
+        SpawnConditions,
     };
     pub use crate::debug::DebugMapGeometry;
+    pub use crate::destructible::{DamageTile, TileDamaged, TileDestroyed, TileHealth};
+    pub use crate::edit::{
+        ApplyTiledEdit, RedoTiledEdit, TiledEdit, TiledEditCommand, TiledEditHistory, UndoTiledEdit,
+    };
+    pub use crate::effects::{EffectEmitter, EffectEmitterAppExt, EffectSpawnRequest, TiledEffect};
     pub use crate::events::{
-        GroupLayerSpawned, ImageLayerSpawned, MapSpawned, ObjectLayerSpawned, ObjectSpawned,
-        TileLayerSpawned, WorldSpawned,
+        BatchedObject, GroupLayerSpawned, ImageLayerSpawned, LayerDespawned,
+        LayerVisibilityChanged, MapCompatibilityWarning, MapSpawnReport, MapSpawned,
+        ObjectDespawned, ObjectLayerSpawned, ObjectSpawned, ObjectsSpawnedBatch, SpawnCounts,
+        SpawnEventMode, TileLayerSpawned, TilesModified, WorldSpawned, trigger_spawn_event,
+    };
+    pub use crate::footprint::{FootprintAppExt, MapFootprint, map_footprint};
+    pub use crate::geometry::{CollisionShape, PlacedShape};
+    pub use crate::hierarchy::TiledMapChildren;
+    pub use crate::layout::{MapArrangement, MapLayout};
+    pub use crate::localization::{TextLocalizer, TextLocalizerAppExt, TiledLocalization};
+    pub use crate::names::{DuplicateNamePolicy, NameLookupConfig, TiledNames};
+    pub use crate::object_transform::{
+        GridSnap, ObjectTransformAppExt, ObjectTransformHook, ObjectTransformInput,
+        ObjectTransforms,
+    };
+    pub use crate::occluders::{OccluderConfig, OccluderOutline, OccluderSet};
+    pub use crate::patch::MapPatch;
+    pub use crate::physics::{
+        Climbable, LayerCollision, ShapeSimplification, Slope, TileColliderSource,
+        TileColliderStrategy, TiledPhysicsBackend,
     };
     pub use crate::plugin::{
-        LayerZConfig, TiledmapCoreConfig, TiledmapCorePlugin, TypeExportTarget,
+        LayerZConfig, TiledSpawnSystems, TiledmapCoreConfig, TiledmapCorePlugin, TypeExportTarget,
+    };
+    pub use crate::prefab::{PrefabSpawned, TiledPrefab};
+    pub use crate::project::{
+        ConventionClasses, ProjectDeserializeError, SchemaIssue, SchemaIssueKind,
+        TiledProjectAsset, TiledProjectProperties,
+    };
+    pub use crate::properties::{
+        ClassDrift, ClassDriftKind, ClassSync, FromTiledProperty, MergedProperties,
+        PropertyErrorContext, PropertyErrorKind, PropertyInternStats, PropertyInterner,
+        PropertyKeyConfig, PropertyMergeConfig, PropertyProvenance, PropertySource,
+        TiledClassRegistry, TiledColor, TiledColorSpace, TiledPropertyError, ToTiledProperty,
+        sync_class_to_properties,
+    };
+    pub use crate::raycast::{TileRayHit, grid_raycast};
+    pub use crate::render_backend::{RenderBackendAppExt, TiledRenderBackend};
+    pub use crate::replication::{
+        ApplyMapMutation, ApplyRemoteMapMutations, MapMutation, MapMutationEvent, ReplicatedMap,
     };
-    pub use crate::project::{ProjectDeserializeError, TiledProjectProperties};
-    pub use crate::properties::{FromTiledProperty, MergedProperties, TiledClassRegistry};
+    pub use crate::roads::{RoadEdge, RoadNetwork, RoadNetworkConfig, RoadNode, RoadSegment};
+    pub use crate::save::{MapDiff, RestoreMapDiff, TiledMapChanges};
+    pub use crate::simulation::{TileCell, TileSimulation};
+    pub use crate::spawn::tiles::{MissingTileConfig, MissingTileRecovery};
+    pub use crate::variation::{SpawnChanceConfig, TileVariationConfig};
+    pub use crate::visibility::{LayerSelector, SetLayerVisibility};
 
     // Re-export the TiledClass derive macro
     pub use bevy_tiledmap_macros::TiledClass;
 }
 
 // Re-export plugin types at crate root for convenience
-pub use plugin::{LayerZConfig, TiledmapCoreConfig, TiledmapCorePlugin, TypeExportTarget};
+pub use plugin::{
+    LayerZConfig, TiledSpawnSystems, TiledmapCoreConfig, TiledmapCorePlugin, TypeExportTarget,
+};
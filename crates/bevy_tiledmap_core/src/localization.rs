@@ -0,0 +1,173 @@
+//! Localization of Tiled text objects and string properties.
+//!
+//! Text object content and string properties starting with the key prefix
+//! (`@` by default, e.g. `@npc.guard.greeting`) are passed through the
+//! registered [`TextLocalizer`] before being stored in `TiledObject::Text`,
+//! `MergedProperties` and deserialized class components. The prefix is
+//! stripped before lookup. Strings without the prefix are left alone, and keys
+//! the localizer doesn't know keep their original text (with a warning).
+//!
+//! Any localization backend can be plugged in, e.g. a closure over a
+//! `bevy_fluent` bundle or a plain lookup table. Text is resolved once, at
+//! spawn time; respawn the map after switching languages.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let strings: HashMap<String, String> = load_strings("locales/fr.ron");
+//! app.set_text_localizer(move |key: &str| strings.get(key).cloned());
+//! ```
+
+use std::any::Any;
+
+use bevy::prelude::*;
+use tiled::{Properties, PropertyValue};
+
+/// Resolves localization keys to text.
+///
+/// Implemented for `Fn(&str) -> Option<String>` closures.
+pub trait TextLocalizer: Any + Send + Sync {
+    /// Look up the text for `key` (without the key prefix).
+    fn localize(&self, key: &str) -> Option<String>;
+}
+
+impl<F> TextLocalizer for F
+where
+    F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+{
+    fn localize(&self, key: &str) -> Option<String> {
+        self(key)
+    }
+}
+
+/// Resource holding the registered [`TextLocalizer`].
+#[derive(Resource)]
+pub struct TiledLocalization {
+    localizer: Option<Box<dyn TextLocalizer>>,
+    /// Prefix marking strings as localization keys.
+    ///
+    /// Default: `"@"`
+    pub key_prefix: String,
+}
+
+impl Default for TiledLocalization {
+    fn default() -> Self {
+        Self {
+            localizer: None,
+            key_prefix: "@".to_string(),
+        }
+    }
+}
+
+impl TiledLocalization {
+    /// Register the localizer, replacing any previous one.
+    pub fn set_localizer(&mut self, localizer: impl TextLocalizer) {
+        self.localizer = Some(Box::new(localizer));
+    }
+
+    /// Get the registered localizer if it is a `T`, e.g. to switch its language.
+    pub fn localizer_mut<T: TextLocalizer>(&mut self) -> Option<&mut T> {
+        let localizer: &mut dyn Any = self.localizer.as_deref_mut()?;
+        localizer.downcast_mut()
+    }
+
+    /// Localize a string if it is a key, returning `None` if it isn't one.
+    pub fn localize(&self, text: &str) -> Option<String> {
+        let localizer = self.localizer.as_ref()?;
+        let key = text.strip_prefix(self.key_prefix.as_str())?;
+        let localized = localizer.localize(key);
+        // With an empty prefix every string is a key, so misses are expected
+        if localized.is_none() && !self.key_prefix.is_empty() {
+            warn!("No localization found for key '{}'", key);
+        }
+        localized
+    }
+
+    /// Localize every string property that is a key, including nested class members.
+    pub fn localize_properties(&self, properties: &mut Properties) {
+        if self.localizer.is_none() {
+            return;
+        }
+        for value in properties.values_mut() {
+            match value {
+                PropertyValue::StringValue(text) => {
+                    if let Some(localized) = self.localize(text) {
+                        *text = localized;
+                    }
+                }
+                PropertyValue::ClassValue { properties, .. } => {
+                    self.localize_properties(properties);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Extension trait for registering the text localizer.
+pub trait TextLocalizerAppExt {
+    /// Register the localizer for text objects and string properties,
+    /// replacing any previous one.
+    fn set_text_localizer(&mut self, localizer: impl TextLocalizer) -> &mut Self;
+}
+
+impl TextLocalizerAppExt for App {
+    fn set_text_localizer(&mut self, localizer: impl TextLocalizer) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<TiledLocalization>()
+            .set_localizer(localizer);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_localize_properties() {
+        let mut localization = TiledLocalization::default();
+        localization
+            .set_localizer(|key: &str| (key == "sign.welcome").then(|| "Bienvenue".to_string()));
+
+        let mut nested = Properties::new();
+        nested.insert(
+            "title".to_string(),
+            PropertyValue::StringValue("@sign.welcome".to_string()),
+        );
+        let mut properties = Properties::new();
+        properties.insert(
+            "text".to_string(),
+            PropertyValue::StringValue("@sign.welcome".to_string()),
+        );
+        properties.insert(
+            "plain".to_string(),
+            PropertyValue::StringValue("sign.welcome".to_string()),
+        );
+        properties.insert(
+            "missing".to_string(),
+            PropertyValue::StringValue("@sign.missing".to_string()),
+        );
+        properties.insert(
+            "dialog".to_string(),
+            PropertyValue::ClassValue {
+                property_type: "Dialog".to_string(),
+                properties: nested,
+            },
+        );
+
+        localization.localize_properties(&mut properties);
+
+        let text = |value: Option<&PropertyValue>| match value {
+            Some(PropertyValue::StringValue(text)) => text.clone(),
+            _ => String::new(),
+        };
+        assert_eq!(text(properties.get("text")), "Bienvenue");
+        assert_eq!(text(properties.get("plain")), "sign.welcome");
+        assert_eq!(text(properties.get("missing")), "@sign.missing");
+        let Some(PropertyValue::ClassValue { properties, .. }) = properties.get("dialog") else {
+            panic!("dialog should stay a class value");
+        };
+        assert_eq!(text(properties.get("title")), "Bienvenue");
+    }
+}
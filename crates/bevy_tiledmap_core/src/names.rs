@@ -0,0 +1,240 @@
+//! Lookup of objects and layers by their Tiled name.
+//!
+//! Tiled doesn't require names to be unique, so a map can have many objects
+//! named `"Coin"`. [`TiledNames`] finds them, ordered by Tiled id (the order
+//! they were created in Tiled), and [`NameLookupConfig`] decides what lookups
+//! that expect one name to match one entity do with duplicates:
+//!
+//! - [`DuplicateNamePolicy::All`] (default): use every match
+//! - [`DuplicateNamePolicy::First`] / [`DuplicateNamePolicy::Last`]: use the
+//!   match with the lowest / highest Tiled id
+//! - [`DuplicateNamePolicy::Error`]: fail with a [`DuplicateNameError`]
+//!
+//! The policy also applies to [`LayerSelector::Name`](crate::visibility::LayerSelector::Name).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn collect_coins(names: TiledNames, map: Single<Entity, With<TiledMap>>) {
+//!     for coin in names.all_objects_named(*map, "Coin") {
+//!         // ...
+//!     }
+//!     if let Ok(Some(exit)) = names.object_named(*map, "Exit") {
+//!         // ...
+//!     }
+//! }
+//! ```
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use thiserror::Error;
+
+use crate::components::{
+    LayerId, LayerName, ObjectId, ObjectName, TiledLayerMapOf, TiledObjectMapOf,
+};
+
+/// What name lookups do when several entities share a name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum DuplicateNamePolicy {
+    /// Use the match with the lowest Tiled id.
+    First,
+    /// Use the match with the highest Tiled id.
+    Last,
+    /// Use every match; single-entity lookups return the first.
+    #[default]
+    All,
+    /// Fail the lookup with a [`DuplicateNameError`].
+    Error,
+}
+
+/// Error returned by lookups under [`DuplicateNamePolicy::Error`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{count} entities are named '{name}'")]
+pub struct DuplicateNameError {
+    /// The duplicated name
+    pub name: String,
+    /// How many entities have the name
+    pub count: usize,
+}
+
+impl DuplicateNamePolicy {
+    /// Apply the policy to the matches of a name, ordered by Tiled id.
+    pub fn apply(
+        self,
+        name: &str,
+        matches: Vec<Entity>,
+    ) -> Result<Vec<Entity>, DuplicateNameError> {
+        if matches.len() <= 1 {
+            return Ok(matches);
+        }
+        match self {
+            Self::First => Ok(matches.into_iter().take(1).collect()),
+            Self::Last => Ok(matches.into_iter().last().into_iter().collect()),
+            Self::All => Ok(matches),
+            Self::Error => Err(DuplicateNameError {
+                name: name.to_string(),
+                count: matches.len(),
+            }),
+        }
+    }
+}
+
+/// Configuration of name lookups. See the [module docs](self).
+#[derive(Resource, Debug, Clone, Default)]
+pub struct NameLookupConfig {
+    /// What lookups do with duplicate names (default: [`DuplicateNamePolicy::All`])
+    pub policy: DuplicateNamePolicy,
+}
+
+/// System param for finding objects and layers of a map by Tiled name.
+#[derive(SystemParam)]
+pub struct TiledNames<'w, 's> {
+    config: Res<'w, NameLookupConfig>,
+    object_query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static ObjectId,
+            &'static ObjectName,
+            &'static TiledObjectMapOf,
+        ),
+    >,
+    layer_query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static LayerId,
+            &'static LayerName,
+            &'static TiledLayerMapOf,
+        ),
+    >,
+}
+
+impl TiledNames<'_, '_> {
+    /// Get every object of `map` named `name`, ordered by Tiled id.
+    pub fn all_objects_named(&self, map: Entity, name: &str) -> Vec<Entity> {
+        sorted_by_id(
+            self.object_query
+                .iter()
+                .filter(|(_, _, object_name, map_of)| map_of.0 == map && object_name.0 == name)
+                .map(|(entity, id, _, _)| (id.0, entity)),
+        )
+    }
+
+    /// Get the objects of `map` named `name`, following the duplicate name policy.
+    pub fn objects_named(
+        &self,
+        map: Entity,
+        name: &str,
+    ) -> Result<Vec<Entity>, DuplicateNameError> {
+        self.config
+            .policy
+            .apply(name, self.all_objects_named(map, name))
+    }
+
+    /// Get the object of `map` named `name`, following the duplicate name policy.
+    pub fn object_named(
+        &self,
+        map: Entity,
+        name: &str,
+    ) -> Result<Option<Entity>, DuplicateNameError> {
+        Ok(self.objects_named(map, name)?.first().copied())
+    }
+
+    /// Get every layer of `map` named `name`, ordered by Tiled id.
+    pub fn all_layers_named(&self, map: Entity, name: &str) -> Vec<Entity> {
+        sorted_by_id(
+            self.layer_query
+                .iter()
+                .filter(|(_, _, layer_name, map_of)| map_of.0 == map && layer_name.0 == name)
+                .map(|(entity, id, _, _)| (id.0, entity)),
+        )
+    }
+
+    /// Get the layers of `map` named `name`, following the duplicate name policy.
+    pub fn layers_named(&self, map: Entity, name: &str) -> Result<Vec<Entity>, DuplicateNameError> {
+        self.config
+            .policy
+            .apply(name, self.all_layers_named(map, name))
+    }
+
+    /// Get the layer of `map` named `name`, following the duplicate name policy.
+    pub fn layer_named(
+        &self,
+        map: Entity,
+        name: &str,
+    ) -> Result<Option<Entity>, DuplicateNameError> {
+        Ok(self.layers_named(map, name)?.first().copied())
+    }
+}
+
+/// Sort `(tiled_id, entity)` matches by id.
+pub(crate) fn sorted_by_id(matches: impl Iterator<Item = (u32, Entity)>) -> Vec<Entity> {
+    let mut matches: Vec<_> = matches.collect();
+    matches.sort_by_key(|(id, _)| *id);
+    matches.into_iter().map(|(_, entity)| entity).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_name_policies() {
+        let coins = vec![
+            Entity::from_raw_u32(1).unwrap(),
+            Entity::from_raw_u32(2).unwrap(),
+            Entity::from_raw_u32(3).unwrap(),
+        ];
+
+        assert_eq!(
+            DuplicateNamePolicy::First.apply("Coin", coins.clone()),
+            Ok(vec![coins[0]])
+        );
+        assert_eq!(
+            DuplicateNamePolicy::Last.apply("Coin", coins.clone()),
+            Ok(vec![coins[2]])
+        );
+        assert_eq!(
+            DuplicateNamePolicy::All.apply("Coin", coins.clone()),
+            Ok(coins.clone())
+        );
+        assert_eq!(
+            DuplicateNamePolicy::Error.apply("Coin", coins.clone()),
+            Err(DuplicateNameError {
+                name: "Coin".to_string(),
+                count: 3,
+            })
+        );
+        assert_eq!(
+            DuplicateNamePolicy::Error.apply("Exit", vec![coins[0]]),
+            Ok(vec![coins[0]])
+        );
+    }
+
+    #[test]
+    fn test_lookup_orders_by_tiled_id() {
+        let mut world = World::new();
+        world.init_resource::<NameLookupConfig>();
+        let map = world.spawn_empty().id();
+        let spawn_coin = |world: &mut World, id: u32| {
+            world
+                .spawn((
+                    ObjectId(id),
+                    ObjectName("Coin".to_string()),
+                    TiledObjectMapOf(map),
+                ))
+                .id()
+        };
+        let late = spawn_coin(&mut world, 9);
+        let early = spawn_coin(&mut world, 2);
+
+        let mut state = bevy::ecs::system::SystemState::<TiledNames>::new(&mut world);
+        let names = state.get(&world);
+        assert_eq!(names.all_objects_named(map, "Coin"), vec![early, late]);
+        assert_eq!(names.object_named(map, "Coin"), Ok(Some(early)));
+        assert_eq!(names.object_named(map, "Key"), Ok(None));
+    }
+}
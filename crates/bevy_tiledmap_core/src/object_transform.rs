@@ -0,0 +1,213 @@
+//! Custom object transforms.
+//!
+//! Core computes each object's transform from its Tiled position, size and
+//! rotation. A registered [`ObjectTransformHook`] gets the object's data and
+//! that transform, and returns the transform the object is spawned with, e.g.
+//! to snap objects to a grid, apply a global scale or assign custom Z values.
+//!
+//! The transform is relative to the object's layer, in pixels with Y up (see
+//! [`crate::spawn::objects`]). Positions keep their sub-pixel precision.
+//!
+//! Objects can also be snapped to the map's tile grid before the hook runs:
+//! their Tiled position is rounded to the nearest tile corner. By default only
+//! objects with a `snap_to_grid = true` property are snapped; [`GridSnap`]
+//! changes that for all objects:
+//!
+//! ```rust,ignore
+//! // Snap every object, except those with `snap_to_grid = false`
+//! app.set_object_grid_snap(GridSnap::All);
+//! ```
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! // Snap every object to an 8 pixel grid
+//! app.set_object_transform_hook(|_: &ObjectTransformInput, mut transform: Transform| {
+//!     transform.translation.x = (transform.translation.x / 8.0).round() * 8.0;
+//!     transform.translation.y = (transform.translation.y / 8.0).round() * 8.0;
+//!     transform
+//! });
+//! ```
+
+use std::any::Any;
+
+use bevy::prelude::*;
+use tiled::Properties;
+
+use crate::components::TiledObject;
+
+/// Property snapping an object to the tile grid (`bool`).
+pub const SNAP_TO_GRID_PROPERTY: &str = "snap_to_grid";
+
+/// Which objects are snapped to the tile grid at spawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum GridSnap {
+    /// No object, whatever its `snap_to_grid` property says
+    Off,
+    /// Objects with `snap_to_grid = true`
+    #[default]
+    PerObject,
+    /// Every object, except those with `snap_to_grid = false`
+    All,
+}
+
+impl GridSnap {
+    /// Whether an object with the given `snap_to_grid` property is snapped.
+    pub fn snaps(self, property: Option<bool>) -> bool {
+        match self {
+            GridSnap::Off => false,
+            GridSnap::PerObject => property == Some(true),
+            GridSnap::All => property != Some(false),
+        }
+    }
+}
+
+/// Move an object's transform so its Tiled `position` lands on the nearest
+/// corner of a `grid` sized grid.
+pub fn snap_to_grid(position: Vec2, grid: Vec2, mut transform: Transform) -> Transform {
+    let snapped = (position / grid).round() * grid;
+    let delta = snapped - position;
+    // Tiled positions are Y down, transforms Y up
+    transform.translation.x += delta.x;
+    transform.translation.y -= delta.y;
+    transform
+}
+
+/// Object data passed to an [`ObjectTransformHook`].
+#[derive(Debug, Clone, Copy)]
+pub struct ObjectTransformInput<'a> {
+    /// The object's shape or tile
+    pub object: &'a TiledObject,
+    /// Tiled object id
+    pub object_id: u32,
+    /// Tiled object name
+    pub name: &'a str,
+    /// Tiled class (tile objects inherit the tile's class)
+    pub class: &'a str,
+    /// Tiled id of the object's layer
+    pub layer_id: u32,
+    /// Position in Tiled (pixels, top-left origin, Y down)
+    pub position: Vec2,
+    /// Merged properties of the object
+    pub properties: &'a Properties,
+}
+
+/// Adjusts the transforms objects are spawned with.
+///
+/// Implemented for `Fn(&ObjectTransformInput, Transform) -> Transform` closures.
+pub trait ObjectTransformHook: Any + Send + Sync {
+    /// Get the transform to spawn an object with, from the computed `transform`.
+    fn transform(&self, input: &ObjectTransformInput, transform: Transform) -> Transform;
+}
+
+impl<F> ObjectTransformHook for F
+where
+    F: Fn(&ObjectTransformInput, Transform) -> Transform + Send + Sync + 'static,
+{
+    fn transform(&self, input: &ObjectTransformInput, transform: Transform) -> Transform {
+        self(input, transform)
+    }
+}
+
+/// Resource holding the registered [`ObjectTransformHook`] and grid snapping.
+#[derive(Resource, Default)]
+pub struct ObjectTransforms {
+    hook: Option<Box<dyn ObjectTransformHook>>,
+
+    /// Which objects are snapped to the tile grid (default: [`GridSnap::PerObject`])
+    pub grid_snap: GridSnap,
+}
+
+impl ObjectTransforms {
+    /// Register the hook, replacing any previous one.
+    pub fn set_hook(&mut self, hook: impl ObjectTransformHook) {
+        self.hook = Some(Box::new(hook));
+    }
+
+    /// Get the registered hook if it is a `T`, e.g. to update its settings.
+    pub fn hook_mut<T: ObjectTransformHook>(&mut self) -> Option<&mut T> {
+        let hook: &mut dyn Any = self.hook.as_deref_mut()?;
+        hook.downcast_mut()
+    }
+
+    /// Apply the hook to an object's computed transform (unchanged without a hook).
+    pub fn apply(&self, input: &ObjectTransformInput, transform: Transform) -> Transform {
+        match &self.hook {
+            Some(hook) => hook.transform(input, transform),
+            None => transform,
+        }
+    }
+}
+
+/// Extension trait for registering the object transform hook.
+pub trait ObjectTransformAppExt {
+    /// Register the hook adjusting spawned object transforms, replacing any previous one.
+    fn set_object_transform_hook(&mut self, hook: impl ObjectTransformHook) -> &mut Self;
+
+    /// Set which objects are snapped to the tile grid at spawn.
+    fn set_object_grid_snap(&mut self, grid_snap: GridSnap) -> &mut Self;
+}
+
+impl ObjectTransformAppExt for App {
+    fn set_object_transform_hook(&mut self, hook: impl ObjectTransformHook) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<ObjectTransforms>()
+            .set_hook(hook);
+        self
+    }
+
+    fn set_object_grid_snap(&mut self, grid_snap: GridSnap) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<ObjectTransforms>()
+            .grid_snap = grid_snap;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_transform_hook() {
+        let object = TiledObject::Point;
+        let properties = Properties::new();
+        let input = ObjectTransformInput {
+            object: &object,
+            object_id: 1,
+            name: "Coin",
+            class: "",
+            layer_id: 2,
+            position: Vec2::new(13.0, 43.0),
+            properties: &properties,
+        };
+        let transform = Transform::from_xyz(13.0, 21.0, 0.0);
+
+        let mut transforms = ObjectTransforms::default();
+        assert_eq!(transforms.apply(&input, transform), transform);
+
+        transforms.set_hook(|input: &ObjectTransformInput, mut transform: Transform| {
+            transform.translation = (transform.translation / 8.0).round() * 8.0;
+            transform.translation.z = input.layer_id as f32;
+            transform
+        });
+        assert_eq!(
+            transforms.apply(&input, transform).translation,
+            Vec3::new(16.0, 24.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn test_grid_snap_moves_tiled_position_to_nearest_corner() {
+        // Tiled position (13.25, 43.5) snaps to (16, 48): right and down
+        let transform = Transform::from_xyz(21.25, 12.5, 0.0);
+        let snapped = snap_to_grid(Vec2::new(13.25, 43.5), Vec2::splat(16.0), transform);
+        assert_eq!(snapped.translation, Vec3::new(24.0, 8.0, 0.0));
+
+        assert!(!GridSnap::PerObject.snaps(None));
+        assert!(GridSnap::PerObject.snaps(Some(true)));
+        assert!(GridSnap::All.snaps(None));
+        assert!(!GridSnap::All.snaps(Some(false)));
+        assert!(!GridSnap::Off.snaps(Some(true)));
+    }
+}
@@ -0,0 +1,329 @@
+//! Light occluders from collision shapes.
+//!
+//! Shadows should match what the player collides with, so occluders are built
+//! from the same shapes as colliders:
+//!
+//! - Tile layers get the merged collision shapes of their tiles (the shapes of
+//!   [`plan_tile_layer_colliders`], without climbable tiles), rebuilt when their
+//!   tiles change
+//! - Objects classed `"Occluder"`, or in an object layer classed `"Occluder"`,
+//!   get the shape of the object
+//!
+//! Both end up in an [`OccluderSet`] on the layer or object entity, in its local
+//! space. The core crate doesn't depend on any lighting crate; a small system
+//! turns the outlines into the occluders of the one you use:
+//!
+//! ```rust,ignore
+//! fn add_light_occluders(mut commands: Commands, sets: Query<(Entity, &OccluderSet), Changed<OccluderSet>>) {
+//!     for (entity, set) in &sets {
+//!         for outline in set.outlines(16) {
+//!             commands.spawn((
+//!                 LightOccluder2d::polygon(outline.points),
+//!                 ChildOf(entity),
+//!             ));
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Tile layers use the same layer classes as physics by default (see
+//! [`OccluderConfig::layer_class_collision`]), so `"Solid"` layers occlude
+//! every tile and `"Decoration"` layers nothing.
+
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+
+use crate::components::tile::TileLayerData;
+use crate::components::{LayerClass, ObjectClass, TiledObject};
+use crate::events::{ObjectSpawned, TileLayerSpawned, TilesModified};
+use crate::geometry::{CollisionShape, PlacedShape, object_shape};
+use crate::physics::{
+    LayerCollision, TileColliderSource, TileMaterialKeys, plan_tile_layer_colliders,
+};
+use crate::properties::PropertyKeyConfig;
+
+/// Class of objects and object layers that occlude light.
+pub const OCCLUDER_CLASS: &str = "Occluder";
+
+/// Settings for generating [`OccluderSet`]s.
+#[derive(Resource, Debug, Clone)]
+pub struct OccluderConfig {
+    /// Generate occluders for tile layers (default: `true`)
+    pub tile_layers: bool,
+
+    /// Where tile occluders come from (default:
+    /// [`TileColliderSource::CollisionShapes`])
+    pub tile_source: TileColliderSource,
+
+    /// Tile occluder behavior of layers by Tiled layer class, like the
+    /// physics backend's layer classes.
+    ///
+    /// Default: `"Solid"` → [`LayerCollision::Solid`], `"Decoration"` and
+    /// `"Water"` → [`LayerCollision::Disabled`]
+    pub layer_class_collision: HashMap<String, LayerCollision>,
+}
+
+impl Default for OccluderConfig {
+    fn default() -> Self {
+        Self {
+            tile_layers: true,
+            tile_source: TileColliderSource::CollisionShapes,
+            layer_class_collision: HashMap::from_iter([
+                ("Solid".to_string(), LayerCollision::Solid),
+                ("Decoration".to_string(), LayerCollision::Disabled),
+                ("Water".to_string(), LayerCollision::Disabled),
+            ]),
+        }
+    }
+}
+
+impl OccluderConfig {
+    /// Get the tile occluder behavior of a layer from its Tiled class.
+    pub fn layer_collision(&self, class: Option<&str>) -> LayerCollision {
+        class
+            .and_then(|class| self.layer_class_collision.get(class))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Light occluding shapes of a tile layer or object, in its local space.
+///
+/// See the [module docs](self).
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct OccluderSet {
+    /// The occluding shapes
+    pub shapes: Vec<PlacedShape>,
+}
+
+/// The outline of an occluding shape, in its entity's local space.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OccluderOutline {
+    /// Vertices, counter-clockwise for rectangles and circles
+    pub points: Vec<Vec2>,
+    /// Whether the last point connects back to the first (`false` for
+    /// polylines)
+    pub closed: bool,
+}
+
+impl OccluderSet {
+    /// Outlines of the shapes, with circles approximated by `circle_segments`
+    /// sides (at least 3).
+    pub fn outlines(&self, circle_segments: u32) -> Vec<OccluderOutline> {
+        self.shapes
+            .iter()
+            .map(|placed| {
+                let (points, closed) = match &placed.shape {
+                    CollisionShape::Rectangle { size } => {
+                        let half = *size / 2.0;
+                        (
+                            vec![
+                                Vec2::new(-half.x, -half.y),
+                                Vec2::new(half.x, -half.y),
+                                Vec2::new(half.x, half.y),
+                                Vec2::new(-half.x, half.y),
+                            ],
+                            true,
+                        )
+                    }
+                    CollisionShape::Circle { radius } => {
+                        let segments = circle_segments.max(3);
+                        let points = (0..segments)
+                            .map(|index| {
+                                let angle = index as f32 / segments as f32 * std::f32::consts::TAU;
+                                Vec2::from_angle(angle) * *radius
+                            })
+                            .collect();
+                        (points, true)
+                    }
+                    CollisionShape::Polygon { vertices } => (vertices.clone(), true),
+                    CollisionShape::Polyline { vertices } => (vertices.clone(), false),
+                };
+                let rotation = Vec2::from_angle(placed.rotation);
+                OccluderOutline {
+                    points: points
+                        .into_iter()
+                        .map(|point| placed.offset + rotation.rotate(point))
+                        .collect(),
+                    closed,
+                }
+            })
+            .collect()
+    }
+
+    /// Line segments of all outlines, for lighting that casts shadows from edges.
+    pub fn edges(&self, circle_segments: u32) -> Vec<(Vec2, Vec2)> {
+        let mut edges = Vec::new();
+        for outline in self.outlines(circle_segments) {
+            edges.extend(outline.points.windows(2).map(|pair| (pair[0], pair[1])));
+            if outline.closed && outline.points.len() > 2 {
+                edges.push((outline.points[outline.points.len() - 1], outline.points[0]));
+            }
+        }
+        edges
+    }
+}
+
+/// Occluding shapes of a tile layer.
+fn tile_layer_occluders(
+    tile_data: &TileLayerData,
+    class: Option<&LayerClass>,
+    config: &OccluderConfig,
+    tileset_assets: &Assets<TiledTilesetAsset>,
+    keys: &PropertyKeyConfig,
+) -> OccluderSet {
+    let colliders = plan_tile_layer_colliders(
+        tile_data,
+        config.layer_collision(class.map(|class| class.0.as_str())),
+        &config.tile_source,
+        None,
+        tileset_assets,
+        keys,
+        &TileMaterialKeys::default(),
+    );
+    let mut shapes: Vec<PlacedShape> = colliders
+        .bodies
+        .into_iter()
+        .flat_map(|body| body.shapes)
+        .collect();
+    for slope in colliders.slopes {
+        shapes.extend(slope.shapes.into_iter().map(|placed| PlacedShape {
+            offset: slope.position + placed.offset,
+            ..placed
+        }));
+    }
+    OccluderSet { shapes }
+}
+
+/// Observer giving spawned tile layers an [`OccluderSet`].
+pub fn on_tile_layer_spawned_occluders(
+    trigger: On<TileLayerSpawned>,
+    layer_query: Query<(&TileLayerData, Option<&LayerClass>)>,
+    tileset_assets: Res<Assets<TiledTilesetAsset>>,
+    config: Res<OccluderConfig>,
+    keys: Res<PropertyKeyConfig>,
+    mut commands: Commands,
+) {
+    let layer = trigger.event().entity;
+    if !config.tile_layers {
+        return;
+    }
+    let Ok((tile_data, class)) = layer_query.get(layer) else {
+        return;
+    };
+    let occluders = tile_layer_occluders(tile_data, class, &config, &tileset_assets, &keys);
+    if !occluders.shapes.is_empty() {
+        commands.entity(layer).insert(occluders);
+    }
+}
+
+/// Observer rebuilding a tile layer's [`OccluderSet`] when its tiles change.
+pub fn on_tiles_modified_occluders(
+    trigger: On<TilesModified>,
+    layer_query: Query<(&TileLayerData, Option<&LayerClass>)>,
+    tileset_assets: Res<Assets<TiledTilesetAsset>>,
+    config: Res<OccluderConfig>,
+    keys: Res<PropertyKeyConfig>,
+    mut commands: Commands,
+) {
+    let layer = trigger.event().entity;
+    if !config.tile_layers {
+        return;
+    }
+    let Ok((tile_data, class)) = layer_query.get(layer) else {
+        return;
+    };
+    let occluders = tile_layer_occluders(tile_data, class, &config, &tileset_assets, &keys);
+    if occluders.shapes.is_empty() {
+        commands.entity(layer).remove::<OccluderSet>();
+    } else {
+        commands.entity(layer).insert(occluders);
+    }
+}
+
+/// Observer giving objects classed [`OCCLUDER_CLASS`], or in a layer classed
+/// [`OCCLUDER_CLASS`], an [`OccluderSet`] of their shape.
+pub fn on_occluder_object_spawned(
+    trigger: On<ObjectSpawned>,
+    object_query: Query<(&TiledObject, Option<&ObjectClass>, Option<&ChildOf>)>,
+    layer_query: Query<&LayerClass>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let Ok((object, class, parent)) = object_query.get(event.entity) else {
+        return;
+    };
+    let occluder_object = class.is_some_and(|class| class.0 == OCCLUDER_CLASS);
+    let occluder_layer = parent
+        .and_then(|parent| layer_query.get(parent.parent()).ok())
+        .is_some_and(|class| class.0 == OCCLUDER_CLASS);
+    if !occluder_object && !occluder_layer {
+        return;
+    }
+    // Points only have a stand-in collision shape, they don't cast shadows
+    let shape = match object {
+        TiledObject::Point => None,
+        object => object_shape(object),
+    };
+    let Some(shape) = shape else {
+        if occluder_object {
+            warn!(
+                "Occluder object {} has no shape, ignoring it",
+                event.object_id
+            );
+        }
+        return;
+    };
+    commands.entity(event.entity).insert(OccluderSet {
+        shapes: vec![PlacedShape {
+            offset: Vec2::ZERO,
+            rotation: 0.0,
+            shape,
+        }],
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_occluder_outlines_and_edges() {
+        let set = OccluderSet {
+            shapes: vec![
+                PlacedShape {
+                    offset: Vec2::new(10.0, 0.0),
+                    rotation: 0.0,
+                    shape: CollisionShape::Rectangle {
+                        size: Vec2::new(4.0, 2.0),
+                    },
+                },
+                PlacedShape {
+                    offset: Vec2::ZERO,
+                    rotation: 0.0,
+                    shape: CollisionShape::Circle { radius: 1.0 },
+                },
+                PlacedShape {
+                    offset: Vec2::ZERO,
+                    rotation: std::f32::consts::FRAC_PI_2,
+                    shape: CollisionShape::Polyline {
+                        vertices: vec![Vec2::ZERO, Vec2::X, Vec2::new(1.0, 1.0)],
+                    },
+                },
+            ],
+        };
+
+        let outlines = set.outlines(2);
+        assert_eq!(outlines[0].points[0], Vec2::new(8.0, -1.0));
+        assert_eq!(outlines[0].points[2], Vec2::new(12.0, 1.0));
+        assert!(outlines[0].closed);
+        // Circles get at least three sides
+        assert_eq!(outlines[1].points.len(), 3);
+        assert!(!outlines[2].closed);
+        assert!(outlines[2].points[1].distance(Vec2::Y) < 1e-5);
+
+        // 4 + 3 closed edges, 2 open ones
+        assert_eq!(set.edges(2).len(), 9);
+    }
+}
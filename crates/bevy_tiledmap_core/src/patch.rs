@@ -0,0 +1,100 @@
+//! Map composition: patch maps spawned over a base map.
+//!
+//! A patch is a regular map (e.g. seasonal decorations, DLC content) spawned
+//! on top of an already spawned map. Spawn it with a [`MapPatch`] next to its
+//! `TiledMap`:
+//!
+//! ```rust,ignore
+//! fn add_winter(mut commands: Commands, asset_server: Res<AssetServer>, base: Single<Entity, With<Town>>) {
+//!     commands.spawn((
+//!         TiledMap { handle: asset_server.load("maps/town_winter.tmx") },
+//!         MapPatch::new(*base, Vec2::new(64.0, 0.0)),
+//!     ));
+//! }
+//! ```
+//!
+//! The patch becomes a child of the base map, moved by [`MapPatch::offset`],
+//! and waits for the base to finish spawning. Its layers are numbered after
+//! the base's (or after those of the base's world), so with the same
+//! [`LayerZConfig`](crate::plugin::LayerZConfig) every patch layer is drawn
+//! above every base layer, in the patch's own order. Several patches on one
+//! base stack in the order they spawn.
+//!
+//! Despawning the patch entity removes its layers and objects, leaving the
+//! base as it was.
+
+use bevy::prelude::*;
+
+/// Spawns the map on this entity as a patch over another map.
+///
+/// See the [module docs](self).
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct MapPatch {
+    /// The map entity patched
+    pub base: Entity,
+    /// Position of the patch's bottom-left corner relative to the base's, in
+    /// pixels (Y-up)
+    pub offset: Vec2,
+}
+
+impl MapPatch {
+    /// Patch `base`, with the patch's bottom-left corner at `offset`.
+    pub fn new(base: Entity, offset: Vec2) -> Self {
+        Self { base, offset }
+    }
+
+    /// Offset in tiles from the base's top-left corner, as in Tiled (Y-down),
+    /// for a patch of `patch_height` tiles over a base of `base_height` tiles.
+    pub fn at_tile(
+        base: Entity,
+        tile: IVec2,
+        tile_size: Vec2,
+        base_height: u32,
+        patch_height: u32,
+    ) -> Self {
+        let bottom_row = tile.y + patch_height as i32;
+        Self::new(
+            base,
+            Vec2::new(
+                tile.x as f32 * tile_size.x,
+                (base_height as i32 - bottom_row) as f32 * tile_size.y,
+            ),
+        )
+    }
+}
+
+/// Observer placing patch maps under their base map.
+pub fn on_map_patch_added(
+    trigger: On<Add, MapPatch>,
+    patch_query: Query<(&MapPatch, Option<&Transform>)>,
+    mut commands: Commands,
+) {
+    let entity = trigger.event().entity;
+    let Ok((patch, transform)) = patch_query.get(entity) else {
+        return;
+    };
+    if commands.get_entity(patch.base).is_err() {
+        warn!("Map patch {entity:?} targets missing map {:?}", patch.base);
+        return;
+    }
+    let mut transform = transform.copied().unwrap_or_default();
+    transform.translation = patch.offset.extend(transform.translation.z);
+    commands
+        .entity(entity)
+        .insert((transform, ChildOf(patch.base)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patch_offset_from_tile() {
+        let base = Entity::from_raw_u32(1).unwrap();
+        // 4-tile tall patch at Tiled (2, 1) over a 10-tile tall base covers
+        // rows 1 to 4, so its bottom edge is 5 tiles above the base's
+        let patch = MapPatch::at_tile(base, IVec2::new(2, 1), Vec2::splat(16.0), 10, 4);
+        assert_eq!(patch.offset, Vec2::new(32.0, 80.0));
+    }
+}
@@ -0,0 +1,136 @@
+//! Physics materials authored as tile properties.
+//!
+//! Tiles can set `friction`, `restitution` and `density` (float or int
+//! properties, names configurable with [`TileMaterialKeys`]). Tiles with the
+//! same material are merged into the same collider; values a tile doesn't set
+//! are left to the backend's defaults.
+
+use std::hash::{Hash, Hasher};
+
+use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+use tiled::{Properties, PropertyValue};
+
+/// The physics material of a tile, from its properties.
+///
+/// `None` values aren't set by the tile.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TileMaterial {
+    /// Friction coefficient.
+    pub friction: Option<f32>,
+    /// Restitution (bounciness) coefficient.
+    pub restitution: Option<f32>,
+    /// Density.
+    pub density: Option<f32>,
+}
+
+// Materials group tiles, so they are compared by bits
+impl PartialEq for TileMaterial {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl Eq for TileMaterial {}
+
+impl Hash for TileMaterial {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state);
+    }
+}
+
+impl TileMaterial {
+    /// Read a material from properties.
+    pub fn from_properties(properties: &Properties, keys: &TileMaterialKeys) -> Self {
+        let number = |key: &str| match properties.get(key)? {
+            PropertyValue::FloatValue(value) => Some(*value),
+            PropertyValue::IntValue(value) => Some(*value as f32),
+            _ => None,
+        };
+        Self {
+            friction: number(&keys.friction),
+            restitution: number(&keys.restitution),
+            density: number(&keys.density),
+        }
+    }
+
+    /// Read the material of a tileset tile.
+    pub fn from_tile(
+        tileset: &TiledTilesetAsset,
+        local_tile_id: u32,
+        keys: &TileMaterialKeys,
+    ) -> Self {
+        tileset
+            .tileset
+            .get_tile(local_tile_id)
+            .map(|tile| Self::from_properties(&tile.properties, keys))
+            .unwrap_or_default()
+    }
+
+    fn bits(&self) -> [Option<u32>; 3] {
+        [self.friction, self.restitution, self.density].map(|value| value.map(f32::to_bits))
+    }
+
+    /// Whether the material sets no values.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Names of the tile properties a [`TileMaterial`] is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileMaterialKeys {
+    /// Friction property.
+    ///
+    /// Default: `"friction"`
+    pub friction: String,
+    /// Restitution property.
+    ///
+    /// Default: `"restitution"`
+    pub restitution: String,
+    /// Density property.
+    ///
+    /// Default: `"density"`
+    pub density: String,
+}
+
+impl Default for TileMaterialKeys {
+    fn default() -> Self {
+        Self {
+            friction: "friction".to_string(),
+            restitution: "restitution".to_string(),
+            density: "density".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_material_from_properties() {
+        let mut properties = Properties::new();
+        properties.insert("friction".to_string(), PropertyValue::FloatValue(0.1));
+        properties.insert("bounce".to_string(), PropertyValue::IntValue(1));
+        properties.insert(
+            "density".to_string(),
+            PropertyValue::StringValue("x".into()),
+        );
+
+        let keys = TileMaterialKeys {
+            restitution: "bounce".to_string(),
+            ..Default::default()
+        };
+        let material = TileMaterial::from_properties(&properties, &keys);
+        assert_eq!(
+            material,
+            TileMaterial {
+                friction: Some(0.1),
+                restitution: Some(1.0),
+                density: None,
+            }
+        );
+        assert!(!material.is_default());
+        assert!(TileMaterial::from_properties(&Properties::new(), &keys).is_default());
+    }
+}
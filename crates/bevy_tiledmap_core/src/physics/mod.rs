@@ -0,0 +1,95 @@
+//! Backend-agnostic collider generation.
+//!
+//! Everything about turning a map into colliders that doesn't depend on the
+//! physics engine lives here, so `bevy_tiledmap_avian` and other backends (Rapier,
+//! custom engines) share it instead of duplicating it:
+//!
+//! - [`plan_tile_layer_colliders`]: the collision shapes of a tile layer, with
+//!   rectangle merging, layer classes and collider sources ([`tiles`])
+//! - [`TileMaterial`]: friction, restitution and density tile properties ([`material`])
+//! - [`ShapeSimplification`]: vertex welding and polyline merging ([`simplify`])
+//! - [`Climbable`] and [`Slope`]: the surface property conventions ([`surfaces`])
+//! - Shape conversion itself is in [`crate::geometry`]
+//!
+//! A backend implements [`TiledPhysicsBackend`] to turn the resulting
+//! [`CollisionShape`]s into its own colliders, and decides which bodies to
+//! spawn for them:
+//!
+//! ```rust,ignore
+//! struct RapierBackend;
+//!
+//! impl TiledPhysicsBackend for RapierBackend {
+//!     type Collider = rapier2d::Collider;
+//!
+//!     fn collider(&self, shape: &CollisionShape) -> Self::Collider {
+//!         match shape {
+//!             CollisionShape::Rectangle { size } => Collider::cuboid(size.x / 2.0, size.y / 2.0),
+//!             // ...
+//!         }
+//!     }
+//!
+//!     fn compound(&self, parts: Vec<(Vec2, f32, Self::Collider)>) -> Self::Collider {
+//!         Collider::compound(parts)
+//!     }
+//! }
+//!
+//! let colliders = plan_tile_layer_colliders(tiles, collision, &source, None, &tilesets, &keys, &material_keys);
+//! for body in &colliders.bodies {
+//!     if let Some(collider) = RapierBackend.compound_shapes(&body.shapes) {
+//!         commands.spawn((collider, Friction::coefficient(body.material.friction.unwrap_or(0.5)), ChildOf(layer)));
+//!     }
+//! }
+//! ```
+
+pub mod material;
+pub mod simplify;
+pub mod surfaces;
+pub mod tiles;
+
+pub use material::{TileMaterial, TileMaterialKeys};
+pub use simplify::ShapeSimplification;
+pub use surfaces::{Climbable, Slope};
+pub use tiles::{
+    LayerCollision, MaterialShapes, SlopeTile, TileCollider, TileColliderSource,
+    TileColliderStrategy, TileLayerColliders, merge_tile_rectangles, plan_tile_colliders,
+    plan_tile_layer_colliders,
+};
+
+use bevy::prelude::*;
+
+use crate::components::object::TiledObject;
+use crate::geometry::{CollisionShape, PlacedShape, object_shape};
+
+/// A physics engine that colliders can be generated for.
+///
+/// Only the conversion of [`CollisionShape`]s into engine colliders is
+/// engine-specific; the provided methods build on it.
+pub trait TiledPhysicsBackend {
+    /// The engine's collider type.
+    type Collider;
+
+    /// Build a collider for a shape centered on the origin.
+    fn collider(&self, shape: &CollisionShape) -> Self::Collider;
+
+    /// Combine colliders positioned with an offset and a rotation (radians,
+    /// counter-clockwise) into one compound collider.
+    fn compound(&self, parts: Vec<(Vec2, f32, Self::Collider)>) -> Self::Collider;
+
+    /// Build the collider of a Tiled object, or `None` for objects without a
+    /// shape (e.g. text).
+    fn object_collider(&self, object: &TiledObject) -> Option<Self::Collider> {
+        object_shape(object).map(|shape| self.collider(&shape))
+    }
+
+    /// Build one compound collider from placed shapes, or `None` if there are none.
+    fn compound_shapes(&self, shapes: &[PlacedShape]) -> Option<Self::Collider> {
+        if shapes.is_empty() {
+            return None;
+        }
+        let parts = shapes
+            .iter()
+            .map(|placed| (placed.offset, placed.rotation, self.collider(&placed.shape)))
+            .collect();
+        Some(self.compound(parts))
+    }
+}
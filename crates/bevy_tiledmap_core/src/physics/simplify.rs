@@ -0,0 +1,305 @@
+//! Simplification of tile collision geometry.
+//!
+//! Hand-drawn tile collision shapes often carry redundant vertices, and
+//! polylines drawn along tile edges produce duplicate edges wherever two tiles
+//! meet. With a [`ShapeSimplification`] passed to
+//! [`plan_tile_layer_colliders`](super::plan_tile_layer_colliders), the custom
+//! (non-rectangle) shapes of tile layer colliders go through a simplification
+//! pass before their colliders are built:
+//!
+//! 1. Consecutive vertices closer than `weld_epsilon` are welded together.
+//! 2. Polygons and polylines are simplified with the Douglas-Peucker algorithm,
+//!    dropping vertices closer than `tolerance` to the simplified outline.
+//! 3. The unrotated polylines of a layer are merged: edges shared by adjacent
+//!    tiles are kept once, and connected edges are joined into longer chains.
+//!
+//! Slope tiles and object colliders are not simplified.
+
+use crate::geometry::CollisionShape;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Settings for the tile collision shape simplification pass.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeSimplification {
+    /// Douglas-Peucker tolerance in pixels: vertices closer than this to the
+    /// simplified outline are dropped. `0.0` only drops collinear vertices.
+    ///
+    /// Default: `0.5`
+    pub tolerance: f32,
+
+    /// Distance in pixels under which vertices are welded together.
+    ///
+    /// Default: `0.01`
+    pub weld_epsilon: f32,
+}
+
+impl Default for ShapeSimplification {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.5,
+            weld_epsilon: 0.01,
+        }
+    }
+}
+
+impl ShapeSimplification {
+    /// Simplify the vertices of a polygon or polyline shape.
+    ///
+    /// Other shapes are returned unchanged.
+    pub fn simplify_shape(&self, shape: CollisionShape) -> CollisionShape {
+        match shape {
+            CollisionShape::Polygon { vertices } => CollisionShape::Polygon {
+                vertices: self.simplify_polygon(&vertices),
+            },
+            CollisionShape::Polyline { vertices } => CollisionShape::Polyline {
+                vertices: self.simplify_polyline(&vertices),
+            },
+            shape => shape,
+        }
+    }
+
+    /// Weld and simplify an open chain of vertices, keeping its endpoints.
+    pub fn simplify_polyline(&self, vertices: &[Vec2]) -> Vec<Vec2> {
+        douglas_peucker(&weld_vertices(vertices, self.weld_epsilon), self.tolerance)
+    }
+
+    /// Weld and simplify a closed polygon.
+    ///
+    /// Returns the welded polygon unsimplified if simplification would leave
+    /// fewer than three vertices.
+    pub fn simplify_polygon(&self, vertices: &[Vec2]) -> Vec<Vec2> {
+        let mut welded = weld_vertices(vertices, self.weld_epsilon);
+        if welded.len() > 1 && welded[0].distance(welded[welded.len() - 1]) <= self.weld_epsilon {
+            welded.pop();
+        }
+        if welded.len() <= 3 {
+            return welded;
+        }
+
+        // Split the loop at the vertex farthest from the first one, and
+        // simplify both halves as open chains
+        let far = (1..welded.len())
+            .max_by(|&a, &b| {
+                welded[0]
+                    .distance_squared(welded[a])
+                    .total_cmp(&welded[0].distance_squared(welded[b]))
+            })
+            .unwrap_or(1);
+        let mut second_half = welded[far..].to_vec();
+        second_half.push(welded[0]);
+
+        let mut simplified = douglas_peucker(&welded[..=far], self.tolerance);
+        let second_half = douglas_peucker(&second_half, self.tolerance);
+        simplified.extend_from_slice(&second_half[1..second_half.len() - 1]);
+
+        if simplified.len() < 3 {
+            welded
+        } else {
+            simplified
+        }
+    }
+}
+
+/// Weld consecutive vertices closer than `epsilon` together.
+pub fn weld_vertices(vertices: &[Vec2], epsilon: f32) -> Vec<Vec2> {
+    let mut welded: Vec<Vec2> = Vec::with_capacity(vertices.len());
+    for &vertex in vertices {
+        if welded
+            .last()
+            .is_none_or(|last| last.distance(vertex) > epsilon)
+        {
+            welded.push(vertex);
+        }
+    }
+    welded
+}
+
+/// Simplify an open chain of vertices with the Douglas-Peucker algorithm.
+///
+/// Keeps both endpoints, and drops vertices closer than `tolerance` to the
+/// simplified chain.
+pub fn douglas_peucker(vertices: &[Vec2], tolerance: f32) -> Vec<Vec2> {
+    if vertices.len() < 3 {
+        return vertices.to_vec();
+    }
+
+    let mut keep = vec![false; vertices.len()];
+    keep[0] = true;
+    keep[vertices.len() - 1] = true;
+
+    let mut ranges = vec![(0, vertices.len() - 1)];
+    while let Some((start, end)) = ranges.pop() {
+        let (index, distance) = (start + 1..end)
+            .map(|i| {
+                (
+                    i,
+                    distance_to_segment(vertices[i], vertices[start], vertices[end]),
+                )
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((start, 0.0));
+        // Collinear vertices are dropped even with a zero tolerance
+        if index != start && distance > tolerance.max(f32::EPSILON) {
+            keep[index] = true;
+            ranges.push((start, index));
+            ranges.push((index, end));
+        }
+    }
+
+    vertices
+        .iter()
+        .zip(keep)
+        .filter_map(|(vertex, keep)| keep.then_some(*vertex))
+        .collect()
+}
+
+/// Distance from `point` to the segment `a`-`b`.
+fn distance_to_segment(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let segment = b - a;
+    let length_squared = segment.length_squared();
+    if length_squared <= f32::EPSILON {
+        return point.distance(a);
+    }
+    let t = ((point - a).dot(segment) / length_squared).clamp(0.0, 1.0);
+    point.distance(a + segment * t)
+}
+
+/// Merge polylines, removing duplicate edges and joining connected ones.
+///
+/// Vertices within `weld_epsilon` of each other (on a grid of that size) are
+/// treated as the same vertex. Each edge is kept once, whichever polyline and
+/// direction it came from, and edges meeting at a vertex are chained into as
+/// few polylines as possible.
+pub fn merge_polylines(polylines: &[Vec<Vec2>], weld_epsilon: f32) -> Vec<Vec<Vec2>> {
+    let epsilon = weld_epsilon.max(f32::EPSILON);
+    let mut vertex_ids: HashMap<(i64, i64), usize> = HashMap::new();
+    let mut positions: Vec<Vec2> = Vec::new();
+    let mut vertex_id = |vertex: Vec2| {
+        let key = (
+            (vertex.x / epsilon).round() as i64,
+            (vertex.y / epsilon).round() as i64,
+        );
+        *vertex_ids.entry(key).or_insert_with(|| {
+            positions.push(vertex);
+            positions.len() - 1
+        })
+    };
+
+    // Collect unique edges, in input order
+    let mut seen = HashSet::new();
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    for polyline in polylines {
+        let ids: Vec<usize> = polyline.iter().map(|vertex| vertex_id(*vertex)).collect();
+        for pair in ids.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if a != b && seen.insert((a.min(b), a.max(b))) {
+                edges.push((a, b));
+            }
+        }
+    }
+
+    let mut vertex_edges: Vec<Vec<usize>> = vec![Vec::new(); positions.len()];
+    for (index, &(a, b)) in edges.iter().enumerate() {
+        vertex_edges[a].push(index);
+        vertex_edges[b].push(index);
+    }
+
+    // Walk unused edges into chains, extending both ends as far as possible
+    let mut used = vec![false; edges.len()];
+    let next_edge = |vertex: usize, used: &mut Vec<bool>| -> Option<usize> {
+        let index = *vertex_edges[vertex].iter().find(|&&index| !used[index])?;
+        used[index] = true;
+        let (a, b) = edges[index];
+        Some(if a == vertex { b } else { a })
+    };
+    let mut merged = Vec::new();
+    for index in 0..edges.len() {
+        if used[index] {
+            continue;
+        }
+        used[index] = true;
+        let (a, b) = edges[index];
+        // Extend backwards from the start first, so the chain keeps the direction
+        // of its first edge
+        let mut chain = vec![b, a];
+        while let Some(vertex) = next_edge(chain[chain.len() - 1], &mut used) {
+            chain.push(vertex);
+        }
+        chain.reverse();
+        while let Some(vertex) = next_edge(chain[chain.len() - 1], &mut used) {
+            chain.push(vertex);
+        }
+        merged.push(chain.into_iter().map(|id| positions[id]).collect());
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_douglas_peucker_drops_redundant_vertices() {
+        let vertices = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(8.0, 0.0),
+            Vec2::new(16.0, 0.1),
+            Vec2::new(16.0, 16.0),
+        ];
+        assert_eq!(
+            douglas_peucker(&vertices, 0.5),
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(16.0, 0.1),
+                Vec2::new(16.0, 16.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_simplify_polygon() {
+        let simplification = ShapeSimplification::default();
+        // Square with a midpoint on each edge, a duplicate vertex and a closing vertex
+        let vertices = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(8.0, 0.0),
+            Vec2::new(16.0, 0.0),
+            Vec2::new(16.0, 0.0),
+            Vec2::new(16.0, 8.0),
+            Vec2::new(16.0, 16.0),
+            Vec2::new(8.0, 16.0),
+            Vec2::new(0.0, 16.0),
+            Vec2::new(0.0, 8.0),
+            Vec2::new(0.0, 0.0),
+        ];
+        assert_eq!(
+            simplification.simplify_polygon(&vertices),
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(16.0, 0.0),
+                Vec2::new(16.0, 16.0),
+                Vec2::new(0.0, 16.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_polylines() {
+        // Two tiles with a floor line each, and the second repeating the shared corner edge
+        let polylines = vec![
+            vec![Vec2::new(0.0, 0.0), Vec2::new(16.0, 0.0)],
+            vec![
+                Vec2::new(16.0, 0.0),
+                Vec2::new(32.0, 0.0),
+                Vec2::new(32.0, 16.0),
+            ],
+            vec![Vec2::new(32.0, 16.0), Vec2::new(32.0, 0.0)],
+        ];
+        let merged = merge_polylines(&polylines, 0.01);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].len(), 4);
+        assert_eq!(merged[0].first(), Some(&Vec2::new(0.0, 0.0)));
+        assert_eq!(merged[0].last(), Some(&Vec2::new(32.0, 16.0)));
+    }
+}
@@ -0,0 +1,152 @@
+//! Climbable and slope surface conventions.
+//!
+//! Recognized properties (on tiles or objects):
+//!
+//! - `climbable = true`: the collider becomes a sensor marked [`Climbable`]
+//!   (ladders, vines, ropes).
+//! - `slope = false`: opts an angled polygon out of slope detection.
+//!
+//! Both names can be remapped with [`PropertyKeyConfig`].
+//!
+//! Polygon collision shapes with an angled, upward-facing edge are detected as
+//! slopes and marked with [`Slope`], so character controllers can query surface
+//! normals without per-game glue code.
+
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+use tiled::{Properties, PropertyValue};
+
+use crate::properties::PropertyKeyConfig;
+
+/// Property marking a tile or object as climbable.
+pub const CLIMBABLE_PROPERTY: &str = "climbable";
+
+/// Property that disables slope detection when set to `false`.
+pub const SLOPE_PROPERTY: &str = "slope";
+
+/// Marker for climbable sensor colliders (ladders, vines, ropes).
+#[derive(Component, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct Climbable;
+
+/// Marker for slope colliders, with the slope's surface orientation.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct Slope {
+    /// Upward-facing unit normal of the sloped edge.
+    pub normal: Vec2,
+    /// Angle from horizontal, in radians.
+    pub angle: f32,
+}
+
+impl Slope {
+    /// Detect the sloped edge of a polygon.
+    ///
+    /// Returns the longest edge that faces upward and is neither horizontal nor
+    /// vertical, or `None` if the polygon has no such edge.
+    pub fn from_polygon(vertices: &[Vec2]) -> Option<Self> {
+        if vertices.len() < 3 {
+            return None;
+        }
+
+        // Outward normals depend on winding (positive area = counter-clockwise)
+        let area: f32 = vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(a, b)| a.perp_dot(*b))
+            .sum();
+        let winding = area.signum();
+
+        vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .filter_map(|(a, b)| {
+                let edge = *b - *a;
+                let normal = Vec2::new(edge.y, -edge.x).normalize_or_zero() * winding;
+                let angle = normal.y.clamp(-1.0, 1.0).acos();
+                let angled = normal.y > 0.0 && normal.x.abs() > 1e-3 && normal.y < 1.0 - 1e-3;
+                angled.then_some((edge.length(), Slope { normal, angle }))
+            })
+            .max_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, slope)| slope)
+    }
+}
+
+/// Check if properties mark a tile or object as climbable.
+pub fn is_climbable(properties: &Properties, keys: &PropertyKeyConfig) -> bool {
+    matches!(
+        keys.get(properties, CLIMBABLE_PROPERTY),
+        Some(PropertyValue::BoolValue(true))
+    )
+}
+
+/// Check if properties opt out of slope detection.
+pub fn slope_disabled(properties: &Properties, keys: &PropertyKeyConfig) -> bool {
+    matches!(
+        keys.get(properties, SLOPE_PROPERTY),
+        Some(PropertyValue::BoolValue(false))
+    )
+}
+
+/// Check if a tileset tile is climbable.
+pub fn tile_is_climbable(
+    tileset: &TiledTilesetAsset,
+    local_tile_id: u32,
+    keys: &PropertyKeyConfig,
+) -> bool {
+    tileset
+        .tileset
+        .get_tile(local_tile_id)
+        .is_some_and(|tile| is_climbable(&tile.properties, keys))
+}
+
+/// Detect a slope in a tile's polygon collision shapes.
+pub fn tile_slope(
+    tileset: &TiledTilesetAsset,
+    local_tile_id: u32,
+    keys: &PropertyKeyConfig,
+) -> Option<Slope> {
+    let tile = tileset.tileset.get_tile(local_tile_id)?;
+    if slope_disabled(&tile.properties, keys) {
+        return None;
+    }
+
+    tile.collision
+        .as_ref()?
+        .object_data()
+        .iter()
+        .find_map(|object| match &object.shape {
+            tiled::ObjectShape::Polygon { points } => {
+                let vertices: Vec<Vec2> = points.iter().map(|(x, y)| Vec2::new(*x, -*y)).collect();
+                Slope::from_polygon(&vertices)
+            }
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slope_from_polygon() {
+        // Right triangle rising to the right, counter-clockwise
+        let ramp = [Vec2::ZERO, Vec2::new(16.0, 0.0), Vec2::new(16.0, 16.0)];
+        let slope = Slope::from_polygon(&ramp).unwrap();
+        assert!((slope.angle - std::f32::consts::FRAC_PI_4).abs() < 1e-5);
+        assert!(slope.normal.x < 0.0 && slope.normal.y > 0.0);
+
+        // Same triangle clockwise gives the same outward normal
+        let reversed: Vec<Vec2> = ramp.iter().rev().copied().collect();
+        assert_eq!(Slope::from_polygon(&reversed), Some(slope));
+
+        // Axis-aligned boxes aren't slopes
+        let square = [
+            Vec2::ZERO,
+            Vec2::new(16.0, 0.0),
+            Vec2::new(16.0, 16.0),
+            Vec2::new(0.0, 16.0),
+        ];
+        assert_eq!(Slope::from_polygon(&square), None);
+    }
+}
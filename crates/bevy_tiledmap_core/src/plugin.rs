@@ -2,14 +2,37 @@
 
 use std::path::PathBuf;
 
+use bevy::diagnostic::{Diagnostic, RegisterDiagnostic};
 use bevy::prelude::*;
 use bevy_common_assets::json::JsonAssetPlugin;
 
+use bevy_tiledmap_assets::prelude::TiledMapAsset;
+
+use crate::components::TiledMap;
 use crate::debug::{DebugMapGeometry, draw_map_geometry_debug};
-use crate::project::{TiledProjectAsset, TiledProjectProperties};
-use crate::properties::{TiledClassRegistry, export_all_types_with_reflection};
+use crate::events::MapSpawned;
+use crate::project::{
+    ConventionClasses, SchemaIssue, SchemaIssueKind, TiledProjectAsset, TiledProjectProperties,
+};
+use crate::properties::{
+    PropertyMergeConfig, TiledClassRegistry, export_all_types_with_reflection,
+};
 use crate::systems::{check_world_spawn_complete, process_loaded_maps, process_loaded_worlds};
 
+/// System set containing map spawning (worlds, maps and prefabs) in `PreUpdate`.
+///
+/// Rendering and physics plugins attach their components from observers while
+/// this set runs, so systems ordered `.after(TiledSpawnSystems)` see spawned maps
+/// complete, with tilemaps and colliders, in the frame they were spawned.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// app.add_systems(PreUpdate, place_player_at_spawn_point.after(TiledSpawnSystems));
+/// ```
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TiledSpawnSystems;
+
 /// Configuration for layer Z-ordering.
 ///
 /// Controls how layer Z values are calculated for proper rendering order.
@@ -108,6 +131,9 @@ pub struct TiledmapCoreConfig {
     ///
     /// Also required when using `TypeExportTarget::TiledProject`.
     ///
+    /// Once loaded, the project is also used to validate spawned maps against
+    /// the project's schema (see `validate_against_project`).
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -124,6 +150,44 @@ pub struct TiledmapCoreConfig {
     /// This should match your `AssetPlugin::file_path` configuration.
     /// Defaults to "assets" (Bevy's default).
     pub asset_root: PathBuf,
+
+    /// Whether to validate spawned maps against the loaded `.tiled-project` schema.
+    ///
+    /// When enabled and a project is loaded, every map is checked after spawning
+    /// for unknown classes, mistyped class members and invalid enum values, and
+    /// each issue is logged as a warning. Classes in [`ConventionClasses`] and
+    /// registered `TiledClass` types don't need to be declared by the project.
+    /// Has no effect without `project_path`. Defaults to `true`.
+    pub validate_against_project: bool,
+
+    /// Seed for random visual tile variation (see [`crate::variation`]).
+    ///
+    /// When set, tiles with a `variants` property or Wang set alternatives are
+    /// drawn as a randomly chosen variant. The seed is mixed with each map's asset
+    /// path, so every map varies differently but reproducibly.
+    /// Defaults to `None` (no variation).
+    pub tile_variation_seed: Option<u64>,
+
+    /// Seed for object `spawn_chance` rolls (see [`crate::variation`]).
+    ///
+    /// When set, which objects spawn is reproducible per map. Defaults to `None`
+    /// (objects are rolled anew on every spawn).
+    pub spawn_chance_seed: Option<u64>,
+
+    /// Which object spawn events are fired (see [`crate::events`]).
+    ///
+    /// Use [`SpawnEventMode::Batched`](crate::events::SpawnEventMode::Batched)
+    /// for maps with thousands of objects, to fire one event per object layer
+    /// instead of one per object. Defaults to
+    /// [`SpawnEventMode::PerEntity`](crate::events::SpawnEventMode::PerEntity).
+    pub spawn_events: crate::events::SpawnEventMode,
+
+    /// How tiles referencing a GID outside their tileset are spawned (see
+    /// [`MissingTileRecovery`](crate::spawn::tiles::MissingTileRecovery)).
+    ///
+    /// The affected tiles are listed in the map asset's `load_issues`. Defaults to
+    /// [`MissingTileRecovery::Skip`](crate::spawn::tiles::MissingTileRecovery::Skip).
+    pub missing_tiles: crate::spawn::tiles::MissingTileRecovery,
 }
 
 impl Default for TiledmapCoreConfig {
@@ -132,6 +196,11 @@ impl Default for TiledmapCoreConfig {
             export_target: None,
             project_path: None,
             asset_root: PathBuf::from("assets"),
+            validate_against_project: true,
+            tile_variation_seed: None,
+            spawn_chance_seed: None,
+            spawn_events: crate::events::SpawnEventMode::PerEntity,
+            missing_tiles: crate::spawn::tiles::MissingTileRecovery::Skip,
         }
     }
 }
@@ -203,6 +272,7 @@ impl Plugin for TiledmapCorePlugin {
 
         // Initialize TiledProjectProperties resource (empty until project loads)
         app.init_resource::<TiledProjectProperties>();
+        app.init_resource::<ConventionClasses>();
 
         // Build the TiledClass registry from inventory
         let registry = TiledClassRegistry::build();
@@ -210,11 +280,170 @@ impl Plugin for TiledmapCorePlugin {
         // Insert registry as a resource
         app.insert_resource(registry);
 
+        // Spawn and manage effect entities for `EffectEmitter` objects
+        app.register_type::<crate::effects::EffectEmitter>();
+        app.add_observer(crate::effects::on_effect_emitter_added);
+        app.add_observer(crate::effects::on_effect_emitter_removed);
+        app.add_systems(PostUpdate, crate::effects::sync_effect_enabled);
+
+        // Keep tile class grids in sync with runtime tile edits
+        app.register_type::<crate::components::TileClassGrid>();
+        app.add_systems(PostUpdate, crate::components::tile::sync_tile_class_grids);
+
+        // Shared storage of identical object properties, with dedup diagnostics
+        app.init_resource::<crate::properties::PropertyInterner>();
+        app.register_diagnostic(Diagnostic::new(crate::properties::PropertyInterner::UNIQUE))
+            .register_diagnostic(Diagnostic::new(
+                crate::properties::PropertyInterner::DEDUP_RATIO,
+            ));
+        app.add_systems(Last, crate::properties::update_property_interner);
+
+        // Keep confined cameras inside map and region bounds
+        app.register_type::<crate::camera::CameraBounds>();
+        app.register_type::<crate::camera::CameraConfinement>();
+        app.add_observer(crate::camera::on_map_geometry_added);
+        app.add_observer(crate::camera::on_camera_bounds_object_spawned);
+        app.configure_sets(
+            PostUpdate,
+            crate::camera::CameraConfinementSystems.before(TransformSystems::Propagate),
+        );
+        app.add_systems(
+            PostUpdate,
+            crate::camera::confine_cameras.in_set(crate::camera::CameraConfinementSystems),
+        );
+        app.register_type::<crate::camera::PixelPerfectCamera>();
+        app.add_systems(
+            PostUpdate,
+            crate::camera::apply_pixel_perfect_cameras
+                .after(crate::camera::CameraConfinementSystems)
+                .before(TransformSystems::Propagate),
+        );
+
+        // Position the maps of map layouts
+        app.register_type::<crate::layout::MapLayout>();
+        app.add_systems(
+            PostUpdate,
+            crate::layout::apply_map_layouts
+                .before(crate::camera::CameraConfinementSystems)
+                .before(TransformSystems::Propagate),
+        );
+
+        // Place patch maps over their base maps
+        app.register_type::<crate::patch::MapPatch>();
+        app.add_observer(crate::patch::on_map_patch_added);
+
+        // Join road polylines into a road network
+        app.register_type::<crate::roads::RoadSegment>();
+        app.init_resource::<crate::roads::RoadNetworkConfig>();
+        app.init_resource::<crate::roads::RoadNetwork>();
+        app.add_observer(crate::roads::on_road_object_spawned);
+        app.add_systems(
+            PostUpdate,
+            crate::roads::rebuild_road_network.after(TransformSystems::Propagate),
+        );
+
+        // Light occluders from tile collision shapes and occluder objects
+        app.init_resource::<crate::occluders::OccluderConfig>();
+        app.add_observer(crate::occluders::on_tile_layer_spawned_occluders);
+        app.add_observer(crate::occluders::on_tiles_modified_occluders);
+        app.add_observer(crate::occluders::on_occluder_object_spawned);
+
+        // Chunk entities streamed around viewers on infinite maps
+        app.register_type::<crate::chunks::ChunkViewer>();
+        app.init_resource::<crate::chunks::ChunkStreamingConfig>();
+        app.init_resource::<crate::chunks::LiveChunks>();
+        app.add_message::<crate::chunks::ChunkSpawned>();
+        app.add_message::<crate::chunks::ChunkEvicted>();
+        app.add_observer(crate::chunks::on_map_spawned_index_chunks);
+        app.add_observer(crate::chunks::on_chunk_removed);
+        app.add_systems(
+            PostUpdate,
+            crate::chunks::stream_map_chunks.after(TransformSystems::Propagate),
+        );
+
+        // Per-tile health for destructible terrain
+        app.add_observer(crate::destructible::on_damage_tile);
+        app.add_observer(crate::destructible::on_tiles_modified);
+
+        // Track runtime map changes for save/load diffs
+        app.add_observer(crate::save::on_map_spawned_track_changes);
+        app.add_observer(crate::save::on_tiles_modified_track_changes);
+
+        // Publish tile changes of replicated maps
+        app.add_observer(crate::replication::on_tiles_modified_replicate);
+
+        // Spawn events are also written as messages for `MessageReader` users
+        app.add_message::<crate::events::ObjectSpawned>();
+        app.add_message::<crate::events::ObjectsSpawnedBatch>();
+        app.add_message::<crate::events::TileLayerSpawned>();
+        app.add_message::<crate::events::ObjectLayerSpawned>();
+        app.add_message::<crate::events::ImageLayerSpawned>();
+        app.add_message::<crate::events::GroupLayerSpawned>();
+        app.add_message::<MapSpawned>();
+        app.add_message::<crate::events::WorldSpawned>();
+        app.add_message::<crate::events::MapSpawnReport>();
+        app.add_message::<crate::events::MapCompatibilityWarning>();
+        app.add_message::<crate::prefab::PrefabSpawned>();
+
+        // Despawned layers and objects, for cleaning up after spawn handlers
+        app.add_message::<crate::events::LayerDespawned>();
+        app.add_message::<crate::events::ObjectDespawned>();
+        app.add_observer(crate::events::on_layer_despawn);
+        app.add_observer(crate::events::on_object_despawn);
+        app.add_message::<crate::properties::TiledPropertyError>();
+
         // Insert default layer Z config (can be overridden by user)
         app.init_resource::<LayerZConfig>();
 
+        // Insert default property merge order (can be overridden by user)
+        app.init_resource::<PropertyMergeConfig>();
+
+        // Insert default property names of built-in features (can be overridden by user)
+        app.init_resource::<crate::properties::PropertyKeyConfig>();
+
+        // Evaluator for object and layer spawn conditions (registered by the game)
+        app.init_resource::<crate::conditions::SpawnConditions>();
+        app.register_type::<crate::conditions::SpawnConditionUnmet>();
+
+        // Policy for duplicate object and layer names (can be overridden by user)
+        app.init_resource::<crate::names::NameLookupConfig>();
+
+        // Localizer for text objects and string properties (registered by the game)
+        app.init_resource::<crate::localization::TiledLocalization>();
+
+        // Hook adjusting spawned object transforms (registered by the game)
+        app.init_resource::<crate::object_transform::ObjectTransforms>();
+
+        // Seeds for random visual tile variation and object spawn chance
+        app.insert_resource(crate::variation::TileVariationConfig {
+            seed: self.config.tile_variation_seed,
+        });
+        app.insert_resource(crate::variation::SpawnChanceConfig {
+            seed: self.config.spawn_chance_seed,
+        });
+
+        // Which object spawn events are fired (per object or batched per layer)
+        app.insert_resource(crate::events::SpawnEventConfig {
+            mode: self.config.spawn_events,
+        });
+
+        // How tiles outside their tileset are spawned
+        app.insert_resource(crate::spawn::tiles::MissingTileConfig {
+            recovery: self.config.missing_tiles,
+        });
+
         // Initialize world Z counters for shared layer Z-ordering across maps
         app.init_resource::<crate::systems::spawn::WorldZCounters>();
+        app.add_observer(crate::systems::spawn::release_world_z_counter);
+
+        // Map metadata on map entities
+        app.register_type::<crate::components::TiledMapInfo>();
+
+        // Number repeated instances of the same map
+        app.register_type::<crate::components::MapInstanceId>();
+        app.init_resource::<crate::systems::spawn::MapInstanceCounters>();
+        app.add_observer(crate::systems::spawn::assign_map_instance_id);
+        app.add_observer(crate::systems::spawn::release_map_counters);
 
         // Schedule type export at startup if configured
         // Must be done at startup to have access to AppTypeRegistry for reflection
@@ -241,11 +470,16 @@ impl Plugin for TiledmapCorePlugin {
                 PreUpdate,
                 process_project_load.run_if(resource_exists::<PendingProjectLoad>),
             );
+
+            if self.config.validate_against_project {
+                app.add_observer(validate_spawned_map);
+            }
         }
 
         // Add reactive spawning systems (runs in PreUpdate before user systems)
         // World processing runs before map processing so spawned maps get processed in the same frame
         // check_world_spawn_complete runs after maps are processed to fire WorldSpawned events
+        // Spawning waits for a configured project to load so class defaults can be resolved
         app.add_systems(
             PreUpdate,
             (
@@ -253,7 +487,20 @@ impl Plugin for TiledmapCorePlugin {
                 process_loaded_maps,
                 check_world_spawn_complete,
             )
-                .chain(),
+                .chain()
+                .in_set(TiledSpawnSystems)
+                .after(process_project_load)
+                .run_if(not(resource_exists::<PendingProjectLoad>)),
+        );
+
+        // Spawn single layers of maps as prefabs (waits for the project like maps do)
+        app.register_type::<crate::prefab::TiledPrefab>();
+        app.add_systems(
+            PreUpdate,
+            crate::prefab::spawn_tiled_prefabs
+                .in_set(TiledSpawnSystems)
+                .after(process_project_load)
+                .run_if(not(resource_exists::<PendingProjectLoad>)),
         );
 
         // Enable debug visualization by default (remove this line to disable)
@@ -301,11 +548,17 @@ fn export_types_at_startup(world: &mut World) {
 fn process_project_load(
     mut commands: Commands,
     pending: Res<PendingProjectLoad>,
+    asset_server: Res<AssetServer>,
     project_assets: Res<Assets<TiledProjectAsset>>,
     mut project_props: ResMut<TiledProjectProperties>,
 ) {
     // Check if the asset has finished loading
     let Some(asset) = project_assets.get(&pending.handle) else {
+        // Don't hold up map spawning forever if the project can't be loaded
+        if asset_server.load_state(&pending.handle).is_failed() {
+            error!("Failed to load Tiled project; class defaults will not be resolved");
+            commands.remove_resource::<PendingProjectLoad>();
+        }
         return;
     };
 
@@ -321,3 +574,37 @@ fn process_project_load(
     // Remove the pending load marker
     commands.remove_resource::<PendingProjectLoad>();
 }
+
+/// Observer that validates a spawned map against the loaded project schema.
+fn validate_spawned_map(
+    trigger: On<MapSpawned>,
+    map_query: Query<&TiledMap>,
+    map_assets: Res<Assets<TiledMapAsset>>,
+    project_props: Res<TiledProjectProperties>,
+    conventions: Res<ConventionClasses>,
+    registry: Res<TiledClassRegistry>,
+) {
+    if !project_props.is_loaded() {
+        return;
+    }
+
+    let Ok(tiled_map) = map_query.get(trigger.event().entity) else {
+        return;
+    };
+    let Some(map_asset) = map_assets.get(&tiled_map.handle) else {
+        return;
+    };
+
+    // Convention and registered classes are known without the project declaring them
+    let known_class = |issue: &SchemaIssue| {
+        conventions.covers(issue)
+            || matches!(&issue.kind, SchemaIssueKind::UnknownClass(class) if registry.get(class).is_some())
+    };
+    for issue in project_props
+        .validate_map(&map_asset.map)
+        .into_iter()
+        .filter(|issue| !known_class(issue))
+    {
+        warn!("Tiled project schema mismatch: {}", issue);
+    }
+}
@@ -0,0 +1,245 @@
+//! Prefab composition: spawn a single layer of a map on its own.
+//!
+//! Reusable object groups can be authored as small `.tmx` files and stamped into
+//! procedurally generated levels. Spawn an entity with [`TiledPrefab`] naming the
+//! map and layer; once the map has loaded, only that layer is spawned beneath the
+//! prefab entity, going through the same pipeline (and triggering the same
+//! layer/object events) as full map spawning.
+//!
+//! The prefab entity stands in for the map: its `Transform` places the source
+//! map's bottom-left corner, and spawned layers and objects point back to it
+//! through `TiledLayerMapOf` and `TiledObjectMapOf`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn stamp_campfire(
+//!     mut commands: Commands,
+//!     asset_server: Res<AssetServer>,
+//!     level: Single<Entity, With<Level>>,
+//! ) {
+//!     commands.spawn((
+//!         TiledPrefab::new(asset_server.load("prefabs/campfire.tmx"), "Objects"),
+//!         Transform::from_xyz(320.0, 96.0, 0.0),
+//!         ChildOf(*level),
+//!     ));
+//! }
+//! ```
+
+use bevy::asset::RecursiveDependencyLoadState;
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::{TiledMapAsset, TiledTemplateAsset, TiledTilesetAsset};
+use tiled::LayerType;
+
+use crate::components::LayersInMap;
+use crate::conditions::SpawnConditions;
+use crate::events::{SpawnCounts, SpawnEventConfig, trigger_spawn_event};
+use crate::localization::TiledLocalization;
+use crate::object_transform::ObjectTransforms;
+use crate::plugin::LayerZConfig;
+use crate::project::TiledProjectProperties;
+use crate::properties::{
+    PropertyInterner, PropertyKeyConfig, PropertyMergeConfig, TiledClassRegistry,
+};
+use crate::spawn::spawn_layer;
+use crate::spawn::tiles::{MissingTileConfig, MissingTileRecovery, invalid_tiles};
+use crate::systems::SpawnContext;
+use crate::systems::spawn::map_path;
+use crate::variation::{SpawnChanceConfig, TileVariationConfig};
+
+/// Spawns one layer of a map beneath this entity.
+///
+/// The layer is looked up by name, searching group layers too; the first match
+/// in Tiled's layer order wins. Hidden layers can be stamped as well, since
+/// prefab maps often keep their groups hidden so they can't be spawned whole.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+#[require(Transform, Visibility)]
+pub struct TiledPrefab {
+    /// Handle to the map containing the layer.
+    pub handle: Handle<TiledMapAsset>,
+    /// Name of the layer to spawn.
+    pub layer: String,
+}
+
+impl TiledPrefab {
+    /// Create a prefab spawning the layer named `layer` from `handle`.
+    pub fn new(handle: Handle<TiledMapAsset>, layer: impl Into<String>) -> Self {
+        Self {
+            handle,
+            layer: layer.into(),
+        }
+    }
+}
+
+/// Fired on a prefab entity once its layer has been spawned.
+///
+/// Also written as a message, like the other spawn events.
+#[derive(EntityEvent, Message, Debug, Clone)]
+pub struct PrefabSpawned {
+    /// The prefab entity
+    #[event_target]
+    pub entity: Entity,
+    /// The spawned layer entity (child of the prefab entity)
+    pub layer_entity: Entity,
+}
+
+/// Find a layer by name, searching group layers depth-first in Tiled's order.
+pub fn find_layer_by_name<'map>(
+    layers: impl Iterator<Item = tiled::Layer<'map>>,
+    name: &str,
+) -> Option<tiled::Layer<'map>> {
+    for layer in layers {
+        if layer.name == name {
+            return Some(layer);
+        }
+        if let LayerType::Group(group) = layer.layer_type()
+            && let Some(found) = find_layer_by_name(group.layers(), name)
+        {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// Reactive system that spawns the layer of each [`TiledPrefab`] once its map has loaded.
+///
+/// Runs in `PreUpdate` alongside map spawning. A prefab whose layer doesn't
+/// exist logs an error and has its `TiledPrefab` removed.
+pub fn spawn_tiled_prefabs(
+    asset_server: Res<AssetServer>,
+    map_assets: Res<Assets<TiledMapAsset>>,
+    tileset_assets: Res<Assets<TiledTilesetAsset>>,
+    template_assets: Res<Assets<TiledTemplateAsset>>,
+    registry: Res<TiledClassRegistry>,
+    project: Res<TiledProjectProperties>,
+    merge_config: Res<PropertyMergeConfig>,
+    property_keys: Res<PropertyKeyConfig>,
+    type_registry: Res<AppTypeRegistry>,
+    z_config: Res<LayerZConfig>,
+    variation: Res<TileVariationConfig>,
+    spawn_chance: Res<SpawnChanceConfig>,
+    conditions: Res<SpawnConditions>,
+    (localization, object_transforms, spawn_events, property_interner, missing_tiles): (
+        Res<TiledLocalization>,
+        Res<ObjectTransforms>,
+        Res<SpawnEventConfig>,
+        Res<PropertyInterner>,
+        Res<MissingTileConfig>,
+    ),
+    prefab_query: Query<(Entity, &TiledPrefab), Without<LayersInMap>>,
+    mut commands: Commands,
+) {
+    for (prefab_entity, prefab) in &prefab_query {
+        let Some(RecursiveDependencyLoadState::Loaded) =
+            asset_server.get_recursive_dependency_load_state(&prefab.handle)
+        else {
+            continue;
+        };
+        let Some(map_asset) = map_assets.get(&prefab.handle) else {
+            continue;
+        };
+
+        let Some(layer) = find_layer_by_name(map_asset.map.layers(), &prefab.layer) else {
+            error!(
+                "Prefab layer '{}' not found in map {:?}",
+                prefab.layer,
+                asset_server.get_path(&prefab.handle)
+            );
+            commands.entity(prefab_entity).remove::<TiledPrefab>();
+            continue;
+        };
+
+        let path = map_path(&asset_server, &prefab.handle);
+        if missing_tiles.recovery == MissingTileRecovery::Fail
+            && invalid_tiles(map_asset).next().is_some()
+        {
+            for issue in invalid_tiles(map_asset) {
+                error!("{path}: {issue}");
+            }
+            error!("Not spawning prefab from {path}: it has tiles outside their tileset");
+            commands.entity(prefab_entity).remove::<TiledPrefab>();
+            continue;
+        }
+        let context = SpawnContext::new(
+            map_asset,
+            &tileset_assets,
+            &template_assets,
+            &registry,
+            &asset_server,
+            &project,
+            &merge_config,
+        )
+        .with_map_path(&path)
+        .with_property_keys(&property_keys)
+        .with_variation_seed(variation.map_seed(&path))
+        .with_spawn_chance_seed(spawn_chance.map_seed(&path))
+        .with_conditions(&conditions)
+        .with_localization(&localization)
+        .with_object_transforms(&object_transforms)
+        .with_event_mode(spawn_events.mode)
+        .with_property_interner(&property_interner)
+        .with_missing_tiles(missing_tiles.recovery);
+
+        // Each prefab is its own Z space, like a standalone map
+        let mut z_counter = 0;
+        let Some(layer_entity) = spawn_layer(
+            &mut commands,
+            &layer,
+            prefab_entity,
+            &context,
+            &type_registry,
+            &mut z_counter,
+            &z_config,
+            &mut SpawnCounts::default(),
+        ) else {
+            // The layer's spawn condition failed
+            commands.entity(prefab_entity).remove::<TiledPrefab>();
+            continue;
+        };
+        commands.entity(prefab_entity).add_child(layer_entity);
+        trigger_spawn_event(
+            &mut commands,
+            PrefabSpawned {
+                entity: prefab_entity,
+                layer_entity,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::path::Path;
+
+    const PREFAB_TMX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" renderorder="right-down" width="4" height="4" tilewidth="16" tileheight="16" infinite="0" nextlayerid="5" nextobjectid="2">
+ <objectgroup id="1" name="Campfire"/>
+ <group id="2" name="Rooms" visible="0">
+  <objectgroup id="3" name="Shrine">
+   <object id="1" name="Altar" x="16" y="16" width="16" height="16"/>
+  </objectgroup>
+ </group>
+ <objectgroup id="4" name="Shrine"/>
+</map>
+"#;
+
+    #[test]
+    fn test_find_layer_by_name() {
+        let mut loader = tiled::Loader::with_reader(|_: &Path| {
+            Ok::<_, std::io::Error>(Cursor::new(PREFAB_TMX.as_bytes()))
+        });
+        let map = loader.load_tmx_map("prefab.tmx").unwrap();
+
+        let campfire = find_layer_by_name(map.layers(), "Campfire").unwrap();
+        assert_eq!(campfire.id(), 1);
+
+        // Nested layers are found, and the first match in layer order wins
+        let shrine = find_layer_by_name(map.layers(), "Shrine").unwrap();
+        assert_eq!(shrine.id(), 3);
+
+        assert!(find_layer_by_name(map.layers(), "Missing").is_none());
+    }
+}
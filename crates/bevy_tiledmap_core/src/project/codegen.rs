@@ -0,0 +1,638 @@
+//! Generate Rust types from a Tiled project (the reverse of type export).
+//!
+//! Adopting this crate in an existing project means mirroring every custom
+//! class and enum of the `.tiled-project` as a `#[derive(TiledClass)]` type.
+//! [`RustTypeCodegen`] writes those mirror types from the project's
+//! `propertyTypes`:
+//!
+//! - Classes become components with one field per member, and an `impl Default`
+//!   holding the project's member defaults (also set with `#[tiled(default = ...)]`
+//!   for bool, int and float members, so exporting them again keeps the defaults)
+//! - Enums become unit-variant enums whose first value is the default, with
+//!   `#[tiled(storage = "int")]` for enums stored as int
+//! - Type names use the last segment of the Tiled name (`game::Door` → `Door`),
+//!   and `#[tiled(name = "...")]` keeps the full name
+//!
+//! Some project types can't be mirrored exactly; they are reported in
+//! [`GeneratedTypes::warnings`]:
+//!
+//! - Complex enums exported by this crate (classes with a variant tag member)
+//!   are skipped, since the project doesn't record which members belong to which
+//!   variant
+//! - Members and enum values that aren't valid Rust identifiers
+//! - Flag enums, which `TiledClass` enums don't support
+//!
+//! Nested class members use the nested type's `Default`, ignoring overrides the
+//! outer class stores for them, and `file` members become `String` paths.
+//!
+//! # Example
+//!
+//! From a build script:
+//!
+//! ```rust,ignore
+//! // build.rs
+//! use bevy_tiledmap_core::project::codegen::{RustTypeCodegen, write_rust_types};
+//!
+//! fn main() {
+//!     println!("cargo:rerun-if-changed=assets/game.tiled-project");
+//!     let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("tiled_types.rs");
+//!     write_rust_types(
+//!         &RustTypeCodegen::default().skip("avian::"),
+//!         "assets/game.tiled-project",
+//!         out,
+//!     )
+//!     .unwrap();
+//! }
+//!
+//! // src/main.rs
+//! mod tiled_types {
+//!     include!(concat!(env!("OUT_DIR"), "/tiled_types.rs"));
+//! }
+//! ```
+//!
+//! Or once, with `cargo run --features cli --bin tiled-codegen -- game.tiled-project -o src/tiled_types.rs`.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use super::{ClassDefinition, ClassMember, EnumDefinition, PropertyType, TiledProjectAsset};
+
+/// Rust keywords that can't be used as field or type names.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+    "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// Generates `#[derive(TiledClass)]` types from a Tiled project.
+///
+/// See the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct RustTypeCodegen {
+    prelude: String,
+    skip: Vec<String>,
+}
+
+impl Default for RustTypeCodegen {
+    fn default() -> Self {
+        Self {
+            prelude: "bevy_tiledmap::prelude".to_string(),
+            skip: Vec::new(),
+        }
+    }
+}
+
+/// Rust source generated by [`RustTypeCodegen`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedTypes {
+    /// The generated source, a module body with its own `use` items
+    pub code: String,
+    /// Project types and members that couldn't be mirrored exactly
+    pub warnings: Vec<String>,
+}
+
+/// Errors in [`write_rust_types`].
+#[derive(Debug, Error)]
+pub enum CodegenError {
+    /// The project or output file could not be read or written.
+    #[error("failed to access {path}: {source}")]
+    Io {
+        /// The file.
+        path: PathBuf,
+        /// The underlying error.
+        source: std::io::Error,
+    },
+
+    /// The project file is not a valid Tiled project.
+    #[error("failed to parse {path}: {source}")]
+    Parse {
+        /// The project file.
+        path: PathBuf,
+        /// The underlying error.
+        source: serde_json::Error,
+    },
+}
+
+impl RustTypeCodegen {
+    /// Import `TiledClass` and the other crate types from `path` instead of
+    /// `bevy_tiledmap::prelude` (e.g. `bevy_tiledmap_core::prelude`).
+    pub fn with_prelude(mut self, path: impl Into<String>) -> Self {
+        self.prelude = path.into();
+        self
+    }
+
+    /// Don't generate project types whose name starts with `prefix`, e.g. types
+    /// the project got from exporting this crate's own classes (`"avian::"`).
+    ///
+    /// Members of skipped types still refer to them by their last path segment.
+    pub fn skip(mut self, prefix: impl Into<String>) -> Self {
+        self.skip.push(prefix.into());
+        self
+    }
+
+    /// Generate Rust types for the classes and enums in `project`.
+    pub fn generate(&self, project: &TiledProjectAsset) -> GeneratedTypes {
+        let mut warnings = Vec::new();
+        let mut classes = Vec::new();
+        let mut enums = Vec::new();
+        for property_type in &project.property_types {
+            let name = property_type.name();
+            if self
+                .skip
+                .iter()
+                .any(|prefix| name.starts_with(prefix.as_str()))
+            {
+                continue;
+            }
+            match property_type {
+                // Variant selectors of complex enums
+                PropertyType::Enum(_) if name.ends_with(":::variant") => {}
+                // Complex enums have a tag member (`:variant` unless renamed) of their
+                // variant selector type
+                PropertyType::Class(class)
+                    if class.members.iter().any(|m| {
+                        m.property_type.as_deref() == Some(&format!("{name}:::variant"))
+                    }) =>
+                {
+                    warnings.push(format!(
+                        "class '{name}' is a complex enum and was skipped; write it by hand"
+                    ));
+                }
+                PropertyType::Class(_) if name.starts_with(['(', '[']) => {}
+                PropertyType::Class(class) => classes.push(class),
+                PropertyType::Enum(enum_def) => enums.push(enum_def),
+            }
+        }
+        classes.sort_by(|a, b| a.name.cmp(&b.name));
+        enums.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let names = TypeNames::new(
+            project
+                .property_types
+                .iter()
+                .map(PropertyType::name)
+                .filter(|name| !name.ends_with(":::variant")),
+        );
+        let enums_by_name: HashMap<&str, &EnumDefinition> = project
+            .property_types
+            .iter()
+            .filter_map(|property_type| match property_type {
+                PropertyType::Enum(enum_def) => Some((enum_def.name.as_str(), enum_def)),
+                PropertyType::Class(_) => None,
+            })
+            .collect();
+
+        let mut code = String::new();
+        let _ = writeln!(
+            code,
+            "// Generated from a Tiled project by `RustTypeCodegen`.\n\n\
+             use bevy::prelude::*;\n\
+             use {}::*;",
+            self.prelude
+        );
+        for enum_def in enums {
+            write_enum(&mut code, enum_def, &names, &mut warnings);
+        }
+        for class in classes {
+            write_class(&mut code, class, &names, &enums_by_name, &mut warnings);
+        }
+
+        GeneratedTypes { code, warnings }
+    }
+}
+
+/// Read the Tiled project at `project_path` and write its generated Rust types
+/// to `output_path`, returning the codegen warnings.
+///
+/// The output file is only written if its contents change, so builds that
+/// include it don't rebuild needlessly.
+pub fn write_rust_types(
+    codegen: &RustTypeCodegen,
+    project_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+) -> Result<Vec<String>, CodegenError> {
+    let (project_path, output_path) = (project_path.as_ref(), output_path.as_ref());
+    let json = std::fs::read_to_string(project_path).map_err(|source| CodegenError::Io {
+        path: project_path.to_path_buf(),
+        source,
+    })?;
+    let project: TiledProjectAsset =
+        serde_json::from_str(&json).map_err(|source| CodegenError::Parse {
+            path: project_path.to_path_buf(),
+            source,
+        })?;
+
+    let generated = codegen.generate(&project);
+    if std::fs::read_to_string(output_path).ok().as_deref() != Some(generated.code.as_str()) {
+        std::fs::write(output_path, &generated.code).map_err(|source| CodegenError::Io {
+            path: output_path.to_path_buf(),
+            source,
+        })?;
+    }
+    Ok(generated.warnings)
+}
+
+/// Rust type names for Tiled type names.
+struct TypeNames(HashMap<String, String>);
+
+impl TypeNames {
+    /// Name types by their last segment, or all segments where that collides.
+    fn new<'a>(tiled_names: impl Iterator<Item = &'a str>) -> Self {
+        let tiled_names: Vec<&str> = tiled_names.collect();
+        let mut short_counts: HashMap<String, usize> = HashMap::new();
+        for name in &tiled_names {
+            *short_counts.entry(short_type_name(name)).or_default() += 1;
+        }
+        let mut used = HashSet::new();
+        let names = tiled_names
+            .iter()
+            .map(|name| {
+                let short = short_type_name(name);
+                let mut rust_name = if short_counts[&short] > 1 {
+                    camel_case(name)
+                } else {
+                    short
+                };
+                while !used.insert(rust_name.clone()) {
+                    rust_name.push('_');
+                }
+                (name.to_string(), rust_name)
+            })
+            .collect();
+        Self(names)
+    }
+
+    /// The Rust name of a Tiled type (the last path segment for unknown types).
+    fn get(&self, tiled_name: &str) -> String {
+        self.0
+            .get(tiled_name)
+            .cloned()
+            .unwrap_or_else(|| short_type_name(tiled_name))
+    }
+}
+
+fn write_enum(
+    code: &mut String,
+    enum_def: &EnumDefinition,
+    names: &TypeNames,
+    warnings: &mut Vec<String>,
+) {
+    if enum_def.values_as_flags {
+        warnings.push(format!(
+            "enum '{}' stores flags, which TiledClass enums don't support",
+            enum_def.name
+        ));
+    }
+
+    let _ = write!(
+        code,
+        "\n#[derive(Reflect, TiledClass, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]\n\
+         #[tiled(name = {:?})]\n",
+        enum_def.name
+    );
+    if enum_def.storage_type == "int" {
+        code.push_str("#[tiled(storage = \"int\")]\n");
+    }
+    let _ = writeln!(code, "pub enum {} {{", names.get(&enum_def.name));
+    for (i, value) in enum_def.values.iter().enumerate() {
+        let variant = if is_identifier(value) {
+            value.clone()
+        } else {
+            let variant = camel_case(value);
+            warnings.push(format!(
+                "value '{}' of enum '{}' is not a valid identifier; generated as '{}', which won't match",
+                value, enum_def.name, variant
+            ));
+            variant
+        };
+        if i == 0 {
+            code.push_str("    #[default]\n");
+        }
+        let _ = writeln!(code, "    {variant},");
+    }
+    code.push_str("}\n");
+}
+
+fn write_class(
+    code: &mut String,
+    class: &ClassDefinition,
+    names: &TypeNames,
+    enums: &HashMap<&str, &EnumDefinition>,
+    warnings: &mut Vec<String>,
+) {
+    let type_name = names.get(&class.name);
+    let mut fields = String::new();
+    // Every field with its default expression, if it differs from the type's `Default`
+    let mut defaults = Vec::new();
+
+    for member in &class.members {
+        if !is_identifier(&member.name) {
+            warnings.push(format!(
+                "member '{}' of class '{}' is not a valid identifier and was skipped",
+                member.name, class.name
+            ));
+            continue;
+        }
+        let Some(field) = field_for_member(member, names, enums) else {
+            warnings.push(format!(
+                "member '{}' of class '{}' has unsupported type '{}' and was skipped",
+                member.name, class.name, member.member_type
+            ));
+            continue;
+        };
+
+        if member.member_type == "file" {
+            fields.push_str("    /// Asset path (Tiled `file` member)\n");
+        }
+        if let Some(attribute) = &field.attribute {
+            let _ = writeln!(fields, "    #[tiled(default = {attribute})]");
+        }
+        let _ = writeln!(fields, "    pub {}: {},", member.name, field.rust_type);
+        defaults.push((member.name.as_str(), field.default));
+    }
+
+    let custom_default = defaults.iter().any(|(_, default)| default.is_some());
+    let derive_default = if custom_default { "" } else { ", Default" };
+    let _ = write!(
+        code,
+        "\n#[derive(Component, Reflect, TiledClass, Debug, Clone, PartialEq{derive_default})]\n\
+         #[reflect(Component)]\n\
+         #[tiled(name = {:?})]\n",
+        class.name
+    );
+    if fields.is_empty() {
+        let _ = writeln!(code, "pub struct {type_name};");
+        return;
+    }
+    let _ = write!(code, "pub struct {type_name} {{\n{fields}}}\n");
+
+    if custom_default {
+        let _ = write!(
+            code,
+            "\nimpl Default for {type_name} {{\n    fn default() -> Self {{\n        Self {{\n"
+        );
+        for (name, default) in defaults {
+            let default = default.as_deref().unwrap_or("Default::default()");
+            let _ = writeln!(code, "            {name}: {default},");
+        }
+        code.push_str("        }\n    }\n}\n");
+    }
+}
+
+/// A generated field.
+struct Field {
+    rust_type: String,
+    /// Value of `#[tiled(default = ...)]`, for types that export it
+    attribute: Option<String>,
+    /// Expression for a default that differs from the type's `Default`
+    default: Option<String>,
+}
+
+fn field_for_member(
+    member: &ClassMember,
+    names: &TypeNames,
+    enums: &HashMap<&str, &EnumDefinition>,
+) -> Option<Field> {
+    let value = &member.value;
+    let field = |rust_type: &str, default: Option<String>| Field {
+        rust_type: rust_type.to_string(),
+        attribute: None,
+        default,
+    };
+
+    // Enum members are strings (or ints) with the enum as property type
+    if let Some(enum_def) = member
+        .property_type
+        .as_deref()
+        .and_then(|name| enums.get(name))
+    {
+        let index = match value {
+            serde_json::Value::String(s) => enum_def.values.iter().position(|v| v == s),
+            serde_json::Value::Number(n) => n.as_u64().map(|i| i as usize),
+            _ => None,
+        };
+        let rust_type = names.get(&enum_def.name);
+        let default = index
+            .filter(|&i| i > 0)
+            .and_then(|i| enum_def.values.get(i))
+            .filter(|v| is_identifier(v))
+            .map(|v| format!("{rust_type}::{v}"));
+        return Some(field(&rust_type, default));
+    }
+
+    Some(match member.member_type.as_str() {
+        "bool" => {
+            let b = value.as_bool().unwrap_or_default();
+            let mut field = field("bool", b.then(|| "true".to_string()));
+            field.attribute = field.default.clone();
+            field
+        }
+        "int" => {
+            let i = value.as_i64().unwrap_or_default();
+            let mut field = field("i32", (i != 0).then(|| i.to_string()));
+            field.attribute = field.default.clone();
+            field
+        }
+        "float" => {
+            let f = value.as_f64().unwrap_or_default() as f32;
+            let mut field = field("f32", (f != 0.0).then(|| format!("{f:?}")));
+            field.attribute = field.default.clone();
+            field
+        }
+        "string" => field(
+            "String",
+            value
+                .as_str()
+                .filter(|s| !s.is_empty())
+                .map(|s| format!("{s:?}.to_string()")),
+        ),
+        "file" => field(
+            "String",
+            value
+                .as_str()
+                .filter(|s| !s.is_empty())
+                .map(|s| format!("{s:?}.to_string()")),
+        ),
+        "color" => field("Color", value.as_str().and_then(color_expr)),
+        "object" => field("Option<TiledObjectRef>", None),
+        "class" => {
+            let class = member.property_type.as_deref()?;
+            if class.starts_with(['(', '[']) {
+                return None;
+            }
+            field(&names.get(class), None)
+        }
+        _ => return None,
+    })
+}
+
+/// Rust expression for a Tiled color (`#AARRGGBB` or `#RRGGBB`).
+fn color_expr(color: &str) -> Option<String> {
+    let hex = color.strip_prefix('#')?;
+    let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    let (a, r, g, b) = match hex.len() {
+        8 => (byte(0)?, byte(2)?, byte(4)?, byte(6)?),
+        6 => (255, byte(0)?, byte(2)?, byte(4)?),
+        _ => return None,
+    };
+    Some(format!("Color::srgba_u8({r}, {g}, {b}, {a})"))
+}
+
+fn short_type_name(tiled_name: &str) -> String {
+    camel_case(tiled_name.rsplit("::").next().unwrap_or(tiled_name))
+}
+
+/// Join the alphanumeric words of `s` in `UpperCamelCase`, keeping the case of
+/// the rest of each word.
+fn camel_case(s: &str) -> String {
+    let mut name: String = s
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.insert(0, 'T');
+    }
+    name
+}
+
+fn is_identifier(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && s != "_"
+        && !KEYWORDS.contains(&s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT: &str = r##"{
+        "propertyTypes": [
+            {
+                "id": 1, "name": "game::Door", "type": "class", "useAs": ["object"],
+                "members": [
+                    { "name": "locked", "type": "bool", "value": true },
+                    { "name": "facing", "type": "string", "propertyType": "game::Facing", "value": "South" },
+                    { "name": "tint", "type": "color", "value": "#80ff0000" },
+                    { "name": "hinge", "type": "class", "propertyType": "game::Hinge", "value": {} },
+                    { "name": "max speed", "type": "float", "value": 1.5 }
+                ]
+            },
+            { "id": 2, "name": "game::Facing", "type": "enum", "values": ["North", "South"] },
+            {
+                "id": 3, "name": "game::Hinge", "type": "class",
+                "members": [{ "name": "side", "type": "string", "value": "" }]
+            },
+            {
+                "id": 4, "name": "game::Attack", "type": "class",
+                "members": [{ "name": ":variant", "type": "string", "propertyType": "game::Attack:::variant", "value": "" }]
+            },
+            { "id": 5, "name": "game::Attack:::variant", "type": "enum", "values": ["None"] },
+            { "id": 6, "name": "avian::Body", "type": "class", "members": [] }
+        ]
+    }"##;
+
+    #[test]
+    fn test_generate_mirrors_classes_and_enums() {
+        let project: TiledProjectAsset = serde_json::from_str(PROJECT).unwrap();
+        let generated = RustTypeCodegen::default()
+            .skip("avian::")
+            .generate(&project);
+
+        assert_eq!(
+            generated.code,
+            r#"// Generated from a Tiled project by `RustTypeCodegen`.
+
+use bevy::prelude::*;
+use bevy_tiledmap::prelude::*;
+
+#[derive(Reflect, TiledClass, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[tiled(name = "game::Facing")]
+pub enum Facing {
+    #[default]
+    North,
+    South,
+}
+
+#[derive(Component, Reflect, TiledClass, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+#[tiled(name = "game::Door")]
+pub struct Door {
+    #[tiled(default = true)]
+    pub locked: bool,
+    pub facing: Facing,
+    pub tint: Color,
+    pub hinge: Hinge,
+}
+
+impl Default for Door {
+    fn default() -> Self {
+        Self {
+            locked: true,
+            facing: Facing::South,
+            tint: Color::srgba_u8(255, 0, 0, 128),
+            hinge: Default::default(),
+        }
+    }
+}
+
+#[derive(Component, Reflect, TiledClass, Debug, Clone, PartialEq, Default)]
+#[reflect(Component)]
+#[tiled(name = "game::Hinge")]
+pub struct Hinge {
+    pub side: String,
+}
+"#
+        );
+        assert_eq!(generated.warnings.len(), 2, "{:?}", generated.warnings);
+        assert!(generated.warnings[0].contains("game::Attack"));
+        assert!(generated.warnings[1].contains("max speed"));
+    }
+
+    #[test]
+    fn test_generate_int_storage_enum() {
+        let project: TiledProjectAsset = serde_json::from_str(
+            r#"{
+                "propertyTypes": [
+                    {
+                        "id": 1, "name": "game::Facing", "type": "enum",
+                        "storageType": "int", "values": ["North", "South"]
+                    },
+                    {
+                        "id": 2, "name": "game::Door", "type": "class",
+                        "members": [{ "name": "facing", "type": "int", "propertyType": "game::Facing", "value": 1 }]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let generated = RustTypeCodegen::default().generate(&project);
+
+        assert!(generated.code.contains(
+            "#[tiled(name = \"game::Facing\")]\n#[tiled(storage = \"int\")]\npub enum Facing {"
+        ));
+        assert!(generated.code.contains("facing: Facing::South,"));
+        assert!(generated.warnings.is_empty(), "{:?}", generated.warnings);
+    }
+
+    #[test]
+    fn test_type_names_disambiguate_collisions() {
+        let names = TypeNames::new(["a::Door", "b::Door", "game::enemy_kind"].into_iter());
+        assert_eq!(names.get("a::Door"), "ADoor");
+        assert_eq!(names.get("b::Door"), "BDoor");
+        assert_eq!(names.get("game::enemy_kind"), "EnemyKind");
+        assert_eq!(names.get("glam::Vec2"), "Vec2");
+    }
+}
@@ -1,7 +1,9 @@
 //! Tiled project file (.tiled-project) parsing and property access.
 //!
 //! Provides access to custom property type definitions from Tiled project files.
-//! These definitions include default values for classes and enum variants.
+//! These definitions include default values for classes and enum variants, and
+//! are used at runtime to validate maps against the project's schema (see [`schema`]),
+//! and can be turned into Rust types (see [`codegen`]).
 //!
 //! # Example
 //!
@@ -24,12 +26,17 @@
 //! }
 //! ```
 
+pub mod codegen;
+pub mod schema;
+
 use bevy::prelude::*;
 use serde::Deserialize;
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
 use thiserror::Error;
 
+pub use schema::{ConventionClasses, SchemaIssue, SchemaIssueKind};
+
 /// Raw JSON asset loaded from a `.tiled-project` file.
 ///
 /// This asset is loaded automatically via `bevy_common_assets::JsonAssetPlugin`.
@@ -188,6 +195,15 @@ pub struct TiledProjectProperties {
 
     /// Project-level property values indexed by name.
     properties: HashMap<String, serde_json::Value>,
+
+    /// Project folders (relative to the project file).
+    folders: Vec<String>,
+
+    /// Compatibility version of the project file.
+    compatibility_version: u32,
+
+    /// Whether a project asset has been loaded into this resource.
+    loaded: bool,
 }
 
 impl TiledProjectProperties {
@@ -226,9 +242,30 @@ impl TiledProjectProperties {
             classes,
             enums,
             properties,
+            folders: asset.folders.clone(),
+            compatibility_version: asset.compatibility_version,
+            loaded: true,
         }
     }
 
+    /// Whether a `.tiled-project` file has been loaded.
+    ///
+    /// Returns `false` until the configured project asset finishes loading, or
+    /// forever if no `project_path` was configured.
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+
+    /// Project folders, as listed in the project file.
+    pub fn folders(&self) -> &[String] {
+        &self.folders
+    }
+
+    /// Compatibility version declared by the project file.
+    pub fn compatibility_version(&self) -> u32 {
+        self.compatibility_version
+    }
+
     /// Get a class definition by name.
     ///
     /// # Example
@@ -0,0 +1,774 @@
+//! Schema operations backed by a loaded Tiled project.
+//!
+//! The project file is the source of truth for every custom class and enum the
+//! Tiled editor knows about. This module uses it for two things:
+//!
+//! 1. **Class defaults** - converting a class's member defaults into
+//!    `tiled::Properties`, the same values Tiled applies implicitly when a
+//!    member is not overridden in the map, and merging them beneath the values
+//!    a map actually stores (see [`TiledProjectProperties::resolve_class_properties`]).
+//! 2. **Validation** - checking map, layer, object and tile properties against
+//!    the class and enum definitions, reporting [`SchemaIssue`]s.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn check_map(
+//!     project: Res<TiledProjectProperties>,
+//!     maps: Res<Assets<TiledMapAsset>>,
+//! ) {
+//!     for (_, map) in maps.iter() {
+//!         for issue in project.validate_map(&map.map) {
+//!             warn!("{}", issue);
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::collections::HashSet;
+use std::fmt;
+
+use bevy::prelude::*;
+use thiserror::Error;
+use tiled::{LayerType, Properties, PropertyValue};
+
+use super::{ClassMember, TiledProjectProperties};
+use crate::camera::CAMERA_BOUNDS_CLASS;
+use crate::occluders::OCCLUDER_CLASS;
+use crate::properties::TiledColor;
+use crate::roads::ROAD_CLASS;
+
+/// Maximum depth when expanding nested class members.
+///
+/// Tiled rejects recursive class definitions, but a hand-edited project file
+/// could still contain one, so expansion stops here instead of overflowing.
+const MAX_CLASS_DEPTH: usize = 16;
+
+/// A single mismatch between map data and the project schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaIssue {
+    /// Where the issue was found (e.g., `"object 12 'door'"`).
+    pub context: String,
+    /// Dotted path of the offending property, if the issue concerns a property.
+    pub property: Option<String>,
+    /// What is wrong.
+    pub kind: SchemaIssueKind,
+}
+
+impl fmt::Display for SchemaIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.property {
+            Some(property) => write!(
+                f,
+                "{}, property '{}': {}",
+                self.context, property, self.kind
+            ),
+            None => write!(f, "{}: {}", self.context, self.kind),
+        }
+    }
+}
+
+/// The kind of a [`SchemaIssue`].
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SchemaIssueKind {
+    /// A class is referenced but not defined in the project.
+    #[error("class '{0}' is not defined in the Tiled project")]
+    UnknownClass(String),
+
+    /// A class value contains a member the class does not define.
+    #[error("class '{class}' has no member '{member}'")]
+    UnknownMember {
+        /// The class name.
+        class: String,
+        /// The unknown member name.
+        member: String,
+    },
+
+    /// A member value has a different type than the class defines.
+    #[error("expected {expected}, found {found}")]
+    TypeMismatch {
+        /// The type declared in the project.
+        expected: String,
+        /// The type found in the map.
+        found: String,
+    },
+
+    /// An enum value is not one of the enum's variants.
+    #[error("'{value}' is not a valid value of enum '{enum_name}'")]
+    InvalidEnumValue {
+        /// The enum name.
+        enum_name: String,
+        /// The offending value.
+        value: String,
+    },
+}
+
+/// Classes given meaning by name alone, which projects don't need to declare.
+///
+/// Convention classes like [`CAMERA_BOUNDS_CLASS`] or the `"Solid"` layer
+/// class only mark items for a built-in feature and have no members, so a
+/// project often doesn't define them. Validation of spawned maps skips
+/// [`SchemaIssueKind::UnknownClass`] issues for these classes, and for classes
+/// registered with `#[derive(TiledClass)]`.
+///
+/// Defaults to the core conventions; backend plugins add their own classes,
+/// and apps can add theirs.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Deref, DerefMut)]
+pub struct ConventionClasses(pub HashSet<String>);
+
+impl Default for ConventionClasses {
+    fn default() -> Self {
+        Self(
+            [
+                CAMERA_BOUNDS_CLASS,
+                OCCLUDER_CLASS,
+                ROAD_CLASS,
+                // Default layer classes of OccluderConfig
+                "Solid",
+                "Decoration",
+                "Water",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        )
+    }
+}
+
+impl ConventionClasses {
+    /// Check whether an issue only reports one of these classes as undefined.
+    pub fn covers(&self, issue: &SchemaIssue) -> bool {
+        matches!(&issue.kind, SchemaIssueKind::UnknownClass(class) if self.contains(class))
+    }
+}
+
+impl TiledProjectProperties {
+    /// Get a class's member defaults as `tiled::Properties`.
+    ///
+    /// Nested class members are expanded recursively, with any member overrides
+    /// stored in the outer class applied on top of the nested class's own defaults.
+    /// Members whose default cannot be represented (e.g. an empty color) are omitted.
+    ///
+    /// Returns `None` if the class is not defined in the project.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// if let Some(defaults) = project.class_defaults("avian::PhysicsSettings") {
+    ///     println!("friction defaults to {:?}", defaults.get("friction"));
+    /// }
+    /// ```
+    pub fn class_defaults(&self, name: &str) -> Option<Properties> {
+        self.has_class(name)
+            .then(|| self.class_properties_from_json(name, None, 0))
+    }
+
+    /// Merge project-defined class defaults beneath `properties`.
+    ///
+    /// Tiled only writes class members that differ from the class defaults, so a
+    /// map object of class `Enemy` may omit `health` entirely and still expect the
+    /// project's default. This resolves those implicit values:
+    ///
+    /// 1. Start from the defaults of `class_name` (nothing if empty or unknown)
+    /// 2. Apply `properties` on top (object, template and instance overrides)
+    /// 3. For every class-typed value, recursively fill in that class's defaults
+    ///    beneath the members that are present
+    ///
+    /// Class-typed members are only added by step 1 if the project gives them a
+    /// non-empty default. A nested class the map never sets stays absent, so an
+    /// `Option<NestedClass>` field can tell "not set" (`None`) from "set, with
+    /// defaults" (`Some`).
+    ///
+    /// Without a loaded project this returns `properties` unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Object stores only `{ physics: { friction: 0.2 } }`
+    /// let resolved = project.resolve_class_properties(&object.user_type, &object.properties);
+    /// // `resolved` now also contains `health` and `physics.restitution` defaults
+    /// ```
+    pub fn resolve_class_properties(
+        &self,
+        class_name: &str,
+        properties: &Properties,
+    ) -> Properties {
+        let base = if class_name.is_empty() {
+            Properties::new()
+        } else {
+            self.class_properties_from_json(class_name, None, 0)
+        };
+        self.merge_over_defaults(base, properties, 0)
+    }
+
+    /// Validate custom properties belonging to something of class `class_name`.
+    ///
+    /// Top-level keys that match a class member are type-checked against it; other
+    /// keys are allowed, since Tiled lets users add free-form custom properties next
+    /// to class members. Nested class values are checked strictly.
+    ///
+    /// Pass an empty `class_name` to only validate nested class values.
+    pub fn validate_properties(
+        &self,
+        class_name: &str,
+        properties: &Properties,
+    ) -> Vec<SchemaIssue> {
+        let mut issues = Vec::new();
+        self.validate_with_class(class_name, properties, "properties", &mut issues);
+        issues
+    }
+
+    /// Validate every class, enum and class-typed property in a map.
+    ///
+    /// Checks the map itself, its tilesets and their tiles, and all layers and
+    /// objects (recursing into group layers).
+    pub fn validate_map(&self, map: &tiled::Map) -> Vec<SchemaIssue> {
+        let mut issues = Vec::new();
+
+        self.validate_with_class(
+            map.user_type.as_deref().unwrap_or_default(),
+            &map.properties,
+            "map",
+            &mut issues,
+        );
+
+        for tileset in map.tilesets() {
+            let context = format!("tileset '{}'", tileset.name);
+            self.validate_with_class(
+                tileset.user_type.as_deref().unwrap_or_default(),
+                &tileset.properties,
+                &context,
+                &mut issues,
+            );
+
+            for (tile_id, tile) in tileset.tiles() {
+                let context = format!("tile {} in tileset '{}'", tile_id, tileset.name);
+                self.validate_with_class(
+                    tile.user_type.as_deref().unwrap_or_default(),
+                    &tile.properties,
+                    &context,
+                    &mut issues,
+                );
+            }
+        }
+
+        for layer in map.layers() {
+            self.validate_layer(&layer, &mut issues);
+        }
+
+        issues
+    }
+
+    fn validate_layer(&self, layer: &tiled::Layer, issues: &mut Vec<SchemaIssue>) {
+        let context = format!("layer '{}' (id {})", layer.name, layer.id());
+        self.validate_with_class(
+            layer.user_type.as_deref().unwrap_or_default(),
+            &layer.properties,
+            &context,
+            issues,
+        );
+
+        match layer.layer_type() {
+            LayerType::Objects(object_layer) => {
+                for object in object_layer.objects() {
+                    let context = format!("object {} '{}'", object.id(), object.name);
+                    self.validate_with_class(
+                        &object.user_type,
+                        &object.properties,
+                        &context,
+                        issues,
+                    );
+                }
+            }
+            LayerType::Group(group) => {
+                for child in group.layers() {
+                    self.validate_layer(&child, issues);
+                }
+            }
+            LayerType::Tiles(_) | LayerType::Image(_) => {}
+        }
+    }
+
+    /// Validate the properties of an item whose class is `class_name` (may be empty).
+    fn validate_with_class(
+        &self,
+        class_name: &str,
+        properties: &Properties,
+        context: &str,
+        issues: &mut Vec<SchemaIssue>,
+    ) {
+        let class = if class_name.is_empty() {
+            None
+        } else if let Some(class) = self.get_class(class_name) {
+            Some(class)
+        } else {
+            issues.push(SchemaIssue {
+                context: context.to_string(),
+                property: None,
+                kind: SchemaIssueKind::UnknownClass(class_name.to_string()),
+            });
+            None
+        };
+
+        for (key, value) in properties {
+            let member = class.and_then(|c| c.members.iter().find(|m| &m.name == key));
+            match member {
+                Some(member) => self.validate_member(member, value, key, context, issues, 0),
+                None => self.validate_free_value(value, key, context, issues, 0),
+            }
+        }
+    }
+
+    /// Validate a property that has no declared member type (only class values are checked).
+    fn validate_free_value(
+        &self,
+        value: &PropertyValue,
+        path: &str,
+        context: &str,
+        issues: &mut Vec<SchemaIssue>,
+        depth: usize,
+    ) {
+        if let PropertyValue::ClassValue {
+            property_type,
+            properties,
+        } = value
+        {
+            self.validate_class_value(property_type, properties, path, context, issues, depth);
+        }
+    }
+
+    /// Strictly validate the members of a nested class value.
+    fn validate_class_value(
+        &self,
+        class_name: &str,
+        properties: &Properties,
+        path: &str,
+        context: &str,
+        issues: &mut Vec<SchemaIssue>,
+        depth: usize,
+    ) {
+        if depth >= MAX_CLASS_DEPTH {
+            return;
+        }
+
+        let Some(class) = self.get_class(class_name) else {
+            issues.push(SchemaIssue {
+                context: context.to_string(),
+                property: Some(path.to_string()),
+                kind: SchemaIssueKind::UnknownClass(class_name.to_string()),
+            });
+            return;
+        };
+
+        for (key, value) in properties {
+            let member_path = format!("{}.{}", path, key);
+            if let Some(member) = class.members.iter().find(|m| &m.name == key) {
+                self.validate_member(member, value, &member_path, context, issues, depth + 1);
+            } else {
+                issues.push(SchemaIssue {
+                    context: context.to_string(),
+                    property: Some(member_path),
+                    kind: SchemaIssueKind::UnknownMember {
+                        class: class.name.clone(),
+                        member: key.clone(),
+                    },
+                });
+            }
+        }
+    }
+
+    /// Validate a value against the member it is declared as.
+    fn validate_member(
+        &self,
+        member: &ClassMember,
+        value: &PropertyValue,
+        path: &str,
+        context: &str,
+        issues: &mut Vec<SchemaIssue>,
+        depth: usize,
+    ) {
+        let issue = |kind| SchemaIssue {
+            context: context.to_string(),
+            property: Some(path.to_string()),
+            kind,
+        };
+
+        let found = property_value_type_name(value);
+        if found != member.member_type {
+            issues.push(issue(SchemaIssueKind::TypeMismatch {
+                expected: member.member_type.clone(),
+                found: found.to_string(),
+            }));
+            return;
+        }
+
+        let Some(property_type) = member.property_type.as_deref() else {
+            return;
+        };
+
+        if let PropertyValue::ClassValue {
+            property_type: value_type,
+            properties,
+        } = value
+        {
+            if value_type != property_type {
+                issues.push(issue(SchemaIssueKind::TypeMismatch {
+                    expected: property_type.to_string(),
+                    found: value_type.clone(),
+                }));
+                return;
+            }
+            self.validate_class_value(property_type, properties, path, context, issues, depth);
+            return;
+        }
+
+        // Any other property type refers to an enum
+        let Some(enum_def) = self.get_enum(property_type) else {
+            issues.push(issue(SchemaIssueKind::UnknownClass(
+                property_type.to_string(),
+            )));
+            return;
+        };
+
+        let valid = match value {
+            PropertyValue::StringValue(s) if enum_def.values_as_flags => s
+                .split(',')
+                .filter(|flag| !flag.is_empty())
+                .all(|flag| enum_def.values.iter().any(|v| v == flag)),
+            PropertyValue::StringValue(s) => enum_def.values.iter().any(|v| v == s),
+            PropertyValue::IntValue(i) if enum_def.values_as_flags => {
+                let bits = enum_def.values.len().min(31) as u32;
+                *i >= 0 && (*i as u32) >> bits == 0
+            }
+            PropertyValue::IntValue(i) => {
+                usize::try_from(*i).is_ok_and(|i| i < enum_def.values.len())
+            }
+            _ => true,
+        };
+
+        if !valid {
+            let value = match value {
+                PropertyValue::StringValue(s) => s.clone(),
+                PropertyValue::IntValue(i) => i.to_string(),
+                other => format!("{:?}", other),
+            };
+            issues.push(issue(SchemaIssueKind::InvalidEnumValue {
+                enum_name: enum_def.name.clone(),
+                value,
+            }));
+        }
+    }
+
+    /// Apply `overrides` on top of `base`, filling in defaults for nested class values.
+    fn merge_over_defaults(
+        &self,
+        mut base: Properties,
+        overrides: &Properties,
+        depth: usize,
+    ) -> Properties {
+        for (key, value) in overrides {
+            let resolved = match value {
+                PropertyValue::ClassValue {
+                    property_type,
+                    properties,
+                } if depth < MAX_CLASS_DEPTH => {
+                    let nested_base = match base.remove(key) {
+                        Some(PropertyValue::ClassValue {
+                            property_type: base_type,
+                            properties: base_props,
+                        }) if &base_type == property_type => base_props,
+                        _ => self.class_properties_from_json(property_type, None, depth + 1),
+                    };
+                    PropertyValue::ClassValue {
+                        property_type: property_type.clone(),
+                        properties: self.merge_over_defaults(nested_base, properties, depth + 1),
+                    }
+                }
+                other => other.clone(),
+            };
+            base.insert(key.clone(), resolved);
+        }
+        base
+    }
+
+    /// Build `Properties` for a class from its member defaults, with optional JSON overrides.
+    fn class_properties_from_json(
+        &self,
+        class_name: &str,
+        overrides: Option<&serde_json::Map<String, serde_json::Value>>,
+        depth: usize,
+    ) -> Properties {
+        let mut properties = Properties::new();
+        let Some(class) = self.get_class(class_name) else {
+            return properties;
+        };
+        if depth >= MAX_CLASS_DEPTH {
+            return properties;
+        }
+
+        for member in &class.members {
+            let value = overrides
+                .and_then(|o| o.get(&member.name))
+                .unwrap_or(&member.value);
+            if let Some(value) = self.member_json_to_property(member, value, depth) {
+                properties.insert(member.name.clone(), value);
+            }
+        }
+
+        properties
+    }
+
+    /// Convert a class member's JSON value into a `PropertyValue`.
+    fn member_json_to_property(
+        &self,
+        member: &ClassMember,
+        value: &serde_json::Value,
+        depth: usize,
+    ) -> Option<PropertyValue> {
+        match member.member_type.as_str() {
+            "bool" => value.as_bool().map(PropertyValue::BoolValue),
+            "int" => value
+                .as_i64()
+                .and_then(|i| i32::try_from(i).ok())
+                .map(PropertyValue::IntValue),
+            "float" => value.as_f64().map(|f| PropertyValue::FloatValue(f as f32)),
+            "string" => value
+                .as_str()
+                .map(|s| PropertyValue::StringValue(s.to_string())),
+            "file" => value
+                .as_str()
+                .map(|s| PropertyValue::FileValue(s.to_string())),
+            "object" => value
+                .as_u64()
+                .and_then(|id| u32::try_from(id).ok())
+                .map(PropertyValue::ObjectValue),
+            "color" => value
+                .as_str()
+                .and_then(TiledColor::from_hex)
+                .map(|color| PropertyValue::ColorValue(color.0)),
+            "class" => {
+                // Untouched class members are stored as `{}`. Leave them out so an
+                // absent nested class stays absent (`Option` fields resolve to `None`).
+                if value.as_object().is_none_or(serde_json::Map::is_empty) {
+                    return None;
+                }
+                let property_type = member.property_type.clone()?;
+                let properties =
+                    self.class_properties_from_json(&property_type, value.as_object(), depth + 1);
+                Some(PropertyValue::ClassValue {
+                    property_type,
+                    properties,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The Tiled type name of a property value, as used in project member definitions.
+fn property_value_type_name(value: &PropertyValue) -> &'static str {
+    match value {
+        PropertyValue::BoolValue(_) => "bool",
+        PropertyValue::FloatValue(_) => "float",
+        PropertyValue::IntValue(_) => "int",
+        PropertyValue::ColorValue(_) => "color",
+        PropertyValue::StringValue(_) => "string",
+        PropertyValue::FileValue(_) => "file",
+        PropertyValue::ObjectValue(_) => "object",
+        PropertyValue::ClassValue { .. } => "class",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::TiledProjectAsset;
+
+    fn project() -> TiledProjectProperties {
+        let json = serde_json::json!({
+            "propertyTypes": [
+                {
+                    "type": "enum",
+                    "id": 1,
+                    "name": "BodyType",
+                    "values": ["Static", "Dynamic"],
+                    "storageType": "string",
+                    "valuesAsFlags": false
+                },
+                {
+                    "type": "class",
+                    "id": 2,
+                    "name": "Physics",
+                    "members": [
+                        { "name": "friction", "type": "float", "value": 0.5 },
+                        { "name": "body", "type": "string", "propertyType": "BodyType", "value": "Static" }
+                    ]
+                },
+                {
+                    "type": "class",
+                    "id": 3,
+                    "name": "Enemy",
+                    "members": [
+                        { "name": "health", "type": "int", "value": 10 },
+                        { "name": "physics", "type": "class", "propertyType": "Physics", "value": { "friction": 0.9 } },
+                        { "name": "ragdoll", "type": "class", "propertyType": "Physics", "value": {} }
+                    ]
+                }
+            ],
+            "folders": ["."]
+        });
+        let asset: TiledProjectAsset = serde_json::from_value(json).unwrap();
+        TiledProjectProperties::from_asset(&asset)
+    }
+
+    #[test]
+    fn test_class_defaults_expand_nested_classes() {
+        let project = project();
+        let defaults = project.class_defaults("Enemy").unwrap();
+
+        assert_eq!(defaults.get("health"), Some(&PropertyValue::IntValue(10)));
+        let Some(PropertyValue::ClassValue { properties, .. }) = defaults.get("physics") else {
+            panic!("physics should be a class value");
+        };
+        // Outer override wins over the nested class default
+        assert_eq!(
+            properties.get("friction"),
+            Some(&PropertyValue::FloatValue(0.9))
+        );
+        assert_eq!(
+            properties.get("body"),
+            Some(&PropertyValue::StringValue("Static".to_string()))
+        );
+        assert!(project.class_defaults("Missing").is_none());
+    }
+
+    #[test]
+    fn test_empty_nested_class_defaults_stay_absent() {
+        let project = project();
+        let defaults = project.class_defaults("Enemy").unwrap();
+        assert!(!defaults.contains_key("ragdoll"));
+
+        // Present, even with no members set, resolves to the class defaults
+        let mut props = Properties::new();
+        props.insert(
+            "ragdoll".to_string(),
+            PropertyValue::ClassValue {
+                property_type: "Physics".to_string(),
+                properties: Properties::new(),
+            },
+        );
+        let resolved = project.resolve_class_properties("Enemy", &props);
+        let Some(PropertyValue::ClassValue { properties, .. }) = resolved.get("ragdoll") else {
+            panic!("ragdoll should be a class value");
+        };
+        assert_eq!(
+            properties.get("friction"),
+            Some(&PropertyValue::FloatValue(0.5))
+        );
+    }
+
+    #[test]
+    fn test_resolve_merges_defaults_beneath_overrides() {
+        let project = project();
+
+        let mut physics = Properties::new();
+        physics.insert(
+            "body".to_string(),
+            PropertyValue::StringValue("Dynamic".to_string()),
+        );
+        let mut props = Properties::new();
+        props.insert(
+            "physics".to_string(),
+            PropertyValue::ClassValue {
+                property_type: "Physics".to_string(),
+                properties: physics,
+            },
+        );
+        props.insert("extra".to_string(), PropertyValue::BoolValue(true));
+
+        let resolved = project.resolve_class_properties("Enemy", &props);
+        assert_eq!(resolved.get("health"), Some(&PropertyValue::IntValue(10)));
+        assert_eq!(resolved.get("extra"), Some(&PropertyValue::BoolValue(true)));
+        let Some(PropertyValue::ClassValue { properties, .. }) = resolved.get("physics") else {
+            panic!("physics should be a class value");
+        };
+        // Instance override wins, missing member falls back to the Enemy-level default
+        assert_eq!(
+            properties.get("body"),
+            Some(&PropertyValue::StringValue("Dynamic".to_string()))
+        );
+        assert_eq!(
+            properties.get("friction"),
+            Some(&PropertyValue::FloatValue(0.9))
+        );
+
+        // Class values on unclassed owners still receive their own class defaults
+        let resolved = project.resolve_class_properties("", &props);
+        assert!(!resolved.contains_key("health"));
+        let Some(PropertyValue::ClassValue { properties, .. }) = resolved.get("physics") else {
+            panic!("physics should be a class value");
+        };
+        assert_eq!(
+            properties.get("friction"),
+            Some(&PropertyValue::FloatValue(0.5))
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_schema_issues() {
+        let project = project();
+        let mut physics = Properties::new();
+        physics.insert(
+            "body".to_string(),
+            PropertyValue::StringValue("Kinematic".to_string()),
+        );
+        physics.insert("bogus".to_string(), PropertyValue::BoolValue(true));
+
+        let mut props = Properties::new();
+        props.insert("health".to_string(), PropertyValue::FloatValue(1.0));
+        props.insert("extra".to_string(), PropertyValue::BoolValue(true));
+        props.insert(
+            "physics".to_string(),
+            PropertyValue::ClassValue {
+                property_type: "Physics".to_string(),
+                properties: physics,
+            },
+        );
+
+        let issues = project.validate_properties("Enemy", &props);
+        assert_eq!(issues.len(), 3, "{:?}", issues);
+        assert!(issues.iter().any(|i| matches!(
+            &i.kind,
+            SchemaIssueKind::TypeMismatch { expected, .. } if expected == "int"
+        )));
+        assert!(issues.iter().any(|i| matches!(
+            &i.kind,
+            SchemaIssueKind::InvalidEnumValue { value, .. } if value == "Kinematic"
+        )));
+        assert!(issues.iter().any(|i| matches!(
+            &i.kind,
+            SchemaIssueKind::UnknownMember { member, .. } if member == "bogus"
+        )));
+
+        let issues = project.validate_properties("Boss", &Properties::new());
+        assert_eq!(
+            issues[0].kind,
+            SchemaIssueKind::UnknownClass("Boss".to_string())
+        );
+    }
+
+    #[test]
+    fn test_convention_classes_cover_unknown_classes() {
+        let project = project();
+        let conventions = ConventionClasses::default();
+
+        for class in [CAMERA_BOUNDS_CLASS, "Water"] {
+            let issues = project.validate_properties(class, &Properties::new());
+            assert_eq!(issues.len(), 1);
+            assert!(conventions.covers(&issues[0]), "{class}");
+        }
+
+        let issues = project.validate_properties("Boss", &Properties::new());
+        assert!(!conventions.covers(&issues[0]));
+    }
+}
@@ -0,0 +1,223 @@
+//! Tiled colors.
+//!
+//! Tiled writes colors as `#RRGGBB`, or `#AARRGGBB` with alpha first.
+//! [`TiledColor`] wraps the parsed `tiled::Color` and converts it to and from
+//! Bevy's [`Color`], so the raw type doesn't have to leak into game code:
+//!
+//! ```rust,ignore
+//! #[derive(Component, Reflect, TiledClass, Default)]
+//! #[reflect(Component)]
+//! #[tiled(name = "game::Lamp")]
+//! struct Lamp {
+//!     light: TiledColor,
+//! }
+//!
+//! let color: Color = lamp.light.into();
+//! ```
+//!
+//! The channels are sRGB, like colors in the Tiled editor. Use
+//! [`TiledColor::to_color_in`] with [`TiledColorSpace::Linear`] for colors that
+//! hold linear values (e.g. light intensities authored as plain numbers).
+
+use std::fmt;
+use std::str::FromStr;
+
+use bevy::prelude::*;
+
+/// How the channels of a [`TiledColor`] are interpreted.
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TiledColorSpace {
+    /// The channels are sRGB, as shown in Tiled
+    #[default]
+    Srgb,
+    /// The channels are linear
+    Linear,
+}
+
+/// A color as stored by Tiled, with 8 bits per channel.
+///
+/// Converts to Bevy's [`Color`] (as sRGB) with `From`/`Into`, parses both
+/// `#RRGGBB` and `#AARRGGBB` (the `#` is optional), and can be a `TiledClass`
+/// field, where it is exported as a Tiled `color`. Defaults to opaque white,
+/// like new color properties in Tiled.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(opaque)]
+#[reflect(Clone, Debug, Default, PartialEq)]
+pub struct TiledColor(pub tiled::Color);
+
+impl TiledColor {
+    /// Opaque white
+    pub const WHITE: Self = Self::rgba(255, 255, 255, 255);
+
+    /// Fully transparent black
+    pub const TRANSPARENT: Self = Self::rgba(0, 0, 0, 0);
+
+    /// Create a color from its channels.
+    pub const fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self(tiled::Color {
+            alpha,
+            red,
+            green,
+            blue,
+        })
+    }
+
+    /// Create an opaque color from its channels.
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self::rgba(red, green, blue, 255)
+    }
+
+    /// Parse `#RRGGBB` or `#AARRGGBB`, with or without the `#`.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        tiled::Color::from_str(hex).ok().map(Self)
+    }
+
+    /// Format as Tiled does: `#RRGGBB` for opaque colors, `#AARRGGBB` otherwise.
+    pub fn to_hex(&self) -> String {
+        let tiled::Color {
+            alpha,
+            red,
+            green,
+            blue,
+        } = self.0;
+        if alpha == 255 {
+            format!("#{red:02x}{green:02x}{blue:02x}")
+        } else {
+            format!("#{alpha:02x}{red:02x}{green:02x}{blue:02x}")
+        }
+    }
+
+    /// The channels, in `[red, green, blue, alpha]` order.
+    pub fn to_u8_array(&self) -> [u8; 4] {
+        [self.0.red, self.0.green, self.0.blue, self.0.alpha]
+    }
+
+    /// Convert to a Bevy color, interpreting the channels as sRGB.
+    pub fn to_color(&self) -> Color {
+        self.to_color_in(TiledColorSpace::Srgb)
+    }
+
+    /// Convert to a Bevy color, interpreting the channels in `space`.
+    pub fn to_color_in(&self, space: TiledColorSpace) -> Color {
+        let [red, green, blue, alpha] = self.to_u8_array().map(|channel| channel as f32 / 255.0);
+        match space {
+            TiledColorSpace::Srgb => Color::srgba(red, green, blue, alpha),
+            TiledColorSpace::Linear => Color::linear_rgba(red, green, blue, alpha),
+        }
+    }
+
+    /// Convert a Bevy color, storing its channels in `space`.
+    pub fn from_color_in(color: Color, space: TiledColorSpace) -> Self {
+        let [red, green, blue, alpha] = match space {
+            TiledColorSpace::Srgb => color.to_srgba().to_u8_array(),
+            TiledColorSpace::Linear => color.to_linear().to_u8_array(),
+        };
+        Self::rgba(red, green, blue, alpha)
+    }
+}
+
+impl Default for TiledColor {
+    fn default() -> Self {
+        Self::WHITE
+    }
+}
+
+impl From<tiled::Color> for TiledColor {
+    fn from(color: tiled::Color) -> Self {
+        Self(color)
+    }
+}
+
+impl From<TiledColor> for tiled::Color {
+    fn from(color: TiledColor) -> Self {
+        color.0
+    }
+}
+
+impl From<TiledColor> for Color {
+    fn from(color: TiledColor) -> Self {
+        color.to_color()
+    }
+}
+
+impl From<Color> for TiledColor {
+    fn from(color: Color) -> Self {
+        Self::from_color_in(color, TiledColorSpace::Srgb)
+    }
+}
+
+impl FromStr for TiledColor {
+    type Err = ();
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(hex).ok_or(())
+    }
+}
+
+impl fmt::Display for TiledColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_tiledmap_macros::TiledClass;
+    use tiled::{Properties, PropertyValue};
+
+    use super::*;
+    use crate::properties::{TiledClassRegistry, TiledTypeKind};
+
+    #[derive(Component, Reflect, TiledClass, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    #[tiled(name = "color_test::Lamp")]
+    struct Lamp {
+        light: TiledColor,
+    }
+
+    #[test]
+    fn test_tiled_color_class_field() {
+        let registry = TiledClassRegistry::build();
+        let info = registry.get("color_test::Lamp").unwrap();
+        assert_eq!(info.fields[0].tiled_type, TiledTypeKind::Color);
+
+        let properties = Properties::from([(
+            "light".to_string(),
+            PropertyValue::ColorValue(TiledColor::rgb(255, 200, 0).0),
+        )]);
+        let lamp = (info.from_properties)(&properties, None).unwrap();
+        assert_eq!(
+            lamp.downcast_ref::<Lamp>(),
+            Some(&Lamp {
+                light: TiledColor::rgb(255, 200, 0)
+            })
+        );
+    }
+
+    #[test]
+    fn test_tiled_color_hex_forms_and_conversions() {
+        let opaque = TiledColor::from_hex("#ff8000").unwrap();
+        assert_eq!(opaque, TiledColor::rgb(255, 128, 0));
+        assert_eq!(opaque.to_hex(), "#ff8000");
+
+        // Alpha comes first in Tiled's 8-digit form
+        let translucent = TiledColor::from_hex("80ff0000").unwrap();
+        assert_eq!(translucent, TiledColor::rgba(255, 0, 0, 128));
+        assert_eq!(translucent.to_string(), "#80ff0000");
+
+        assert!(TiledColor::from_hex("#ff80").is_none());
+
+        let color = TiledColor::rgba(255, 128, 0, 255);
+        assert_eq!(Color::from(color), Color::srgb_u8(255, 128, 0));
+        assert_eq!(TiledColor::from(Color::srgb_u8(255, 128, 0)), color);
+        assert_eq!(
+            color.to_color_in(TiledColorSpace::Linear),
+            Color::linear_rgb(1.0, 128.0 / 255.0, 0.0)
+        );
+        let linear = Color::linear_rgb(1.0, 0.5, 0.0);
+        assert_eq!(
+            TiledColor::from_color_in(linear, TiledColorSpace::Linear),
+            TiledColor::rgb(255, 128, 0)
+        );
+    }
+}
@@ -9,7 +9,10 @@ use bevy::prelude::*;
 use bevy::reflect::{ReflectMut, TypeInfo, TypeRegistration, TypeRegistry};
 use tiled::{Properties, PropertyValue};
 
+use super::color::TiledColor;
 use super::registry::TiledClassRegistry;
+use crate::components::TiledObjectRef;
+use crate::project::TiledProjectProperties;
 
 /// Trait for types that can be deserialized from Tiled properties.
 ///
@@ -85,7 +88,16 @@ impl FromTiledProperty for f64 {
 impl FromTiledProperty for String {
     fn from_property(value: &PropertyValue) -> Option<Self> {
         match value {
-            PropertyValue::StringValue(s) => Some(s.clone()),
+            PropertyValue::StringValue(s) | PropertyValue::FileValue(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl FromTiledProperty for TiledObjectRef {
+    fn from_property(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::ObjectValue(id) if *id != 0 => Some(TiledObjectRef(*id)),
             _ => None,
         }
     }
@@ -96,15 +108,16 @@ impl FromTiledProperty for String {
 impl FromTiledProperty for Color {
     fn from_property(value: &PropertyValue) -> Option<Self> {
         match value {
-            PropertyValue::ColorValue(color) => {
-                // tiled::Color has alpha, red, green, blue fields (u8)
-                let r = color.red as f32 / 255.0;
-                let g = color.green as f32 / 255.0;
-                let b = color.blue as f32 / 255.0;
-                let a = color.alpha as f32 / 255.0;
-
-                Some(Color::srgba(r, g, b, a))
-            }
+            PropertyValue::ColorValue(color) => Some(TiledColor(*color).to_color()),
+            _ => None,
+        }
+    }
+}
+
+impl FromTiledProperty for TiledColor {
+    fn from_property(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::ColorValue(color) => Some(TiledColor(*color)),
             _ => None,
         }
     }
@@ -174,6 +187,80 @@ impl FromTiledProperty for IVec2 {
     }
 }
 
+// Array and tuple implementations
+//
+// Exported to Tiled as a class with indexed members ("0", "1", ...), and also
+// accepted as a comma-separated string (e.g. "1.5, 2, 0").
+
+/// Parse the `index`-th element of an array or tuple property value.
+///
+/// Reads the member named after the index from a class value, or the
+/// comma-separated entry from a string value. String entries are tried as the
+/// most specific primitive first (so `"2"` still parses as a float), then as text.
+fn indexed_element<T: FromTiledProperty>(value: &PropertyValue, index: usize) -> Option<T> {
+    match value {
+        PropertyValue::ClassValue { properties, .. } => properties
+            .get(&index.to_string())
+            .and_then(T::from_property),
+        PropertyValue::StringValue(s) => {
+            let part = s.split(',').nth(index)?.trim();
+            let guessed = if let Ok(b) = part.parse::<bool>() {
+                PropertyValue::BoolValue(b)
+            } else if let Ok(i) = part.parse::<i32>() {
+                PropertyValue::IntValue(i)
+            } else if let Ok(f) = part.parse::<f32>() {
+                PropertyValue::FloatValue(f)
+            } else {
+                PropertyValue::StringValue(part.to_string())
+            };
+            T::from_property(&guessed)
+                .or_else(|| T::from_property(&PropertyValue::StringValue(part.to_string())))
+        }
+        _ => None,
+    }
+}
+
+/// Number of elements in an array or tuple property value, if it has a fixed count.
+///
+/// Class values are checked member by member instead, so only strings report a count.
+fn indexed_len(value: &PropertyValue) -> Option<usize> {
+    match value {
+        PropertyValue::StringValue(s) => Some(s.split(',').count()),
+        _ => None,
+    }
+}
+
+impl<T: FromTiledProperty, const N: usize> FromTiledProperty for [T; N] {
+    fn from_property(value: &PropertyValue) -> Option<Self> {
+        if indexed_len(value).is_some_and(|len| len != N) {
+            return None;
+        }
+        let elements = (0..N)
+            .map(|i| indexed_element(value, i))
+            .collect::<Option<Vec<T>>>()?;
+        elements.try_into().ok()
+    }
+}
+
+macro_rules! impl_tuple_from_tiled_property {
+    ($len:literal => $($name:ident $index:literal),+) => {
+        impl<$($name: FromTiledProperty),+> FromTiledProperty for ($($name,)+) {
+            fn from_property(value: &PropertyValue) -> Option<Self> {
+                if indexed_len(value).is_some_and(|len| len != $len) {
+                    return None;
+                }
+                Some(($(
+                    indexed_element::<$name>(value, $index)?,
+                )+))
+            }
+        }
+    };
+}
+
+impl_tuple_from_tiled_property!(2 => A 0, B 1);
+impl_tuple_from_tiled_property!(3 => A 0, B 1, C 2);
+impl_tuple_from_tiled_property!(4 => A 0, B 1, C 2, D 3);
+
 // Option<T> implementation
 impl<T: FromTiledProperty> FromTiledProperty for Option<T> {
     fn from_property(value: &PropertyValue) -> Option<Self> {
@@ -255,6 +342,9 @@ impl std::error::Error for DeserializeError {}
 
 /// Deserialize a class-typed property using hybrid lookup.
 ///
+/// If a Tiled project is loaded, the class's project-defined defaults are merged
+/// beneath `properties` first (see `TiledProjectProperties::resolve_class_properties`).
+///
 /// This function uses the following strategy:
 /// 1. Try `TiledClass` registry first (for manually registered types)
 /// 2. Fall back to Bevy reflection (for types with Reflect + Default)
@@ -274,6 +364,16 @@ pub fn deserialize_class(
     properties: &Properties,
     app: &App,
 ) -> Result<Box<dyn Reflect>, DeserializeError> {
+    // Fill in class defaults from the Tiled project, if one is loaded
+    let resolved;
+    let properties = match app.world().get_resource::<TiledProjectProperties>() {
+        Some(project) if project.is_loaded() => {
+            resolved = project.resolve_class_properties(property_type, properties);
+            &resolved
+        }
+        _ => properties,
+    };
+
     // 1. Try TiledClass registry first
     let tiled_registry = app.world().resource::<TiledClassRegistry>();
     if let Some(tiled_class) = tiled_registry.get(property_type) {
@@ -325,7 +425,7 @@ pub fn deserialize_enum_from_string(
         }
         // For complex enums, this function shouldn't be called (use ClassValue instead)
         return Err(DeserializeError::TypeError(format!(
-            "Enum '{}' is a complex enum and cannot be deserialized from a string. Use ClassValue with :variant field.",
+            "Enum '{}' is a complex enum and cannot be deserialized from a string. Use ClassValue with its variant tag field.",
             enum_name
         )));
     }
@@ -449,14 +549,7 @@ fn deserialize_property_value(
         PropertyValue::IntValue(i) => Ok(Box::new(*i)),
         PropertyValue::FloatValue(f) => Ok(Box::new(*f)),
         PropertyValue::StringValue(s) => Ok(Box::new(s.clone())),
-        PropertyValue::ColorValue(c) => {
-            // Convert tiled::Color to bevy::Color
-            let r = c.red as f32 / 255.0;
-            let g = c.green as f32 / 255.0;
-            let b = c.blue as f32 / 255.0;
-            let a = c.alpha as f32 / 255.0;
-            Ok(Box::new(Color::srgba(r, g, b, a)))
-        }
+        PropertyValue::ColorValue(c) => Ok(Box::new(TiledColor(*c).to_color())),
         PropertyValue::ClassValue {
             property_type,
             properties,
@@ -468,3 +561,189 @@ fn deserialize_property_value(
         PropertyValue::ObjectValue(id) => Ok(Box::new(*id)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_tiledmap_macros::TiledClass;
+
+    use super::*;
+
+    #[derive(Reflect, TiledClass, Debug, Clone, PartialEq)]
+    #[tiled(name = "deserialize_test::Ragdoll")]
+    struct Ragdoll {
+        friction: f32,
+    }
+
+    impl Default for Ragdoll {
+        fn default() -> Self {
+            Self { friction: 0.5 }
+        }
+    }
+
+    #[derive(Reflect, TiledClass, Debug, Clone, PartialEq, Default)]
+    #[tiled(name = "deserialize_test::Fuse")]
+    enum Fuse {
+        #[default]
+        Unlit,
+        Lit {
+            seconds: f32,
+        },
+    }
+
+    #[derive(Reflect, TiledClass, Debug, Clone, PartialEq, Default)]
+    #[tiled(name = "deserialize_test::Fall")]
+    enum Fall {
+        #[default]
+        Stand,
+        Tumble {
+            speed: f32,
+            ragdoll: Option<Ragdoll>,
+            fuse: Option<Fuse>,
+        },
+        Slide(f32, Option<Ragdoll>),
+    }
+
+    fn class(property_type: &str, members: &[(&str, PropertyValue)]) -> PropertyValue {
+        PropertyValue::ClassValue {
+            property_type: property_type.to_string(),
+            properties: members
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+        }
+    }
+
+    fn variant(name: &str) -> (&'static str, PropertyValue) {
+        (":variant", PropertyValue::StringValue(name.to_string()))
+    }
+
+    /// Read a `Fall` through both derived paths: `FromTiledProperty` and the
+    /// registry's error-reporting one.
+    fn read_fall(value: &PropertyValue) -> (Option<Fall>, Result<Fall, String>) {
+        let registry = TiledClassRegistry::build();
+        let info = registry.get_enum_for::<Fall>().unwrap();
+        let reflected =
+            (info.from_property)(value).map(|fall| fall.downcast_ref::<Fall>().unwrap().clone());
+        (Fall::from_property(value), reflected)
+    }
+
+    #[test]
+    fn test_variant_option_class_absent_is_none() {
+        let value = class(
+            "deserialize_test::Fall",
+            &[variant("Tumble"), ("speed", PropertyValue::FloatValue(2.0))],
+        );
+        let expected = Fall::Tumble {
+            speed: 2.0,
+            ragdoll: None,
+            fuse: None,
+        };
+        let (from_property, reflected) = read_fall(&value);
+        assert_eq!(from_property.as_ref(), Some(&expected));
+        assert_eq!(reflected, Ok(expected));
+
+        let value = class(
+            "deserialize_test::Fall",
+            &[variant("Slide"), ("0", PropertyValue::FloatValue(1.0))],
+        );
+        let (from_property, reflected) = read_fall(&value);
+        assert_eq!(from_property, Some(Fall::Slide(1.0, None)));
+        assert_eq!(reflected, Ok(Fall::Slide(1.0, None)));
+    }
+
+    #[test]
+    fn test_variant_option_class_empty_is_defaults() {
+        let value = class(
+            "deserialize_test::Fall",
+            &[
+                variant("Tumble"),
+                ("speed", PropertyValue::FloatValue(2.0)),
+                ("ragdoll", class("deserialize_test::Ragdoll", &[])),
+            ],
+        );
+        let expected = Fall::Tumble {
+            speed: 2.0,
+            ragdoll: Some(Ragdoll::default()),
+            fuse: None,
+        };
+        let (from_property, reflected) = read_fall(&value);
+        assert_eq!(from_property.as_ref(), Some(&expected));
+        assert_eq!(reflected, Ok(expected));
+
+        let value = class(
+            "deserialize_test::Fall",
+            &[
+                variant("Slide"),
+                ("0", PropertyValue::FloatValue(1.0)),
+                ("1", class("deserialize_test::Ragdoll", &[])),
+            ],
+        );
+        let (from_property, reflected) = read_fall(&value);
+        assert_eq!(
+            from_property,
+            Some(Fall::Slide(1.0, Some(Ragdoll::default())))
+        );
+        assert_eq!(reflected, Ok(Fall::Slide(1.0, Some(Ragdoll::default()))));
+    }
+
+    #[test]
+    fn test_variant_missing_required_field_is_error() {
+        // Missing required field of the variant itself
+        let value = class(
+            "deserialize_test::Fall",
+            &[
+                variant("Tumble"),
+                ("ragdoll", class("deserialize_test::Ragdoll", &[])),
+            ],
+        );
+        let (from_property, reflected) = read_fall(&value);
+        assert_eq!(from_property, None);
+        let error = reflected.unwrap_err();
+        assert!(error.contains("'speed'"), "{error}");
+
+        // A present optional class missing one of its own required fields is
+        // an error, not `None`
+        let value = class(
+            "deserialize_test::Fall",
+            &[
+                variant("Tumble"),
+                ("speed", PropertyValue::FloatValue(2.0)),
+                ("fuse", class("deserialize_test::Fuse", &[variant("Lit")])),
+            ],
+        );
+        let (from_property, reflected) = read_fall(&value);
+        assert_eq!(from_property, None);
+        let error = reflected.unwrap_err();
+        assert!(error.contains("'fuse'"), "{error}");
+    }
+
+    #[test]
+    fn test_array_and_tuple_from_string_and_class() {
+        let string = PropertyValue::StringValue("1.5, 2, -3".to_string());
+        assert_eq!(<[f32; 3]>::from_property(&string), Some([1.5, 2.0, -3.0]));
+        assert_eq!(<[f32; 2]>::from_property(&string), None);
+        assert_eq!(
+            <(i32, i32, i32)>::from_property(&string),
+            None,
+            "1.5 is not an int"
+        );
+
+        let class = PropertyValue::ClassValue {
+            property_type: "(f32, bool)".to_string(),
+            properties: [
+                ("0".to_string(), PropertyValue::FloatValue(0.25)),
+                ("1".to_string(), PropertyValue::BoolValue(true)),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        assert_eq!(<(f32, bool)>::from_property(&class), Some((0.25, true)));
+        assert_eq!(<[f32; 3]>::from_property(&class), None);
+
+        let text = PropertyValue::StringValue("door, 2".to_string());
+        assert_eq!(
+            <(String, String)>::from_property(&text),
+            Some(("door".to_string(), "2".to_string()))
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Property errors located in the map they came from.
+//!
+//! Deserializing registered components can fail, or silently fall back to a
+//! field's default, for many objects across a map. Every such problem found
+//! while spawning is reported once through [`report_property_error`]: logged as
+//! a warning and written as a [`TiledPropertyError`] message, with the map,
+//! layer, object, class and field it concerns:
+//!
+//! ```text
+//! map 'maps/level1.tmx', layer 'Enemies' (3), object 'Goblin' (412), class 'game::Enemy', field 'speed': "fast" doesn't fit the field's type, using its default
+//! ```
+//!
+//! Tools and tests can read the messages to list or assert on a map's problems:
+//!
+//! ```rust,ignore
+//! fn collect_property_errors(mut errors: MessageReader<TiledPropertyError>) {
+//!     for error in errors.read() {
+//!         if error.context.class.as_deref() == Some("game::Enemy") {
+//!             // ...
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::fmt;
+
+use bevy::prelude::*;
+use thiserror::Error;
+use tiled::PropertyValue;
+
+/// Where a property error occurred. Unknown parts are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PropertyErrorContext {
+    /// Asset path of the map
+    pub map_path: Option<String>,
+    /// ID of the layer (the object's layer for objects)
+    pub layer_id: Option<u32>,
+    /// Name of the layer
+    pub layer_name: Option<String>,
+    /// ID of the object
+    pub object_id: Option<u32>,
+    /// Name of the object
+    pub object_name: Option<String>,
+    /// Registered Tiled class being deserialized
+    pub class: Option<String>,
+    /// Field (or property) of the class
+    pub field: Option<String>,
+}
+
+impl PropertyErrorContext {
+    /// Context of an error in the map at `map_path`.
+    pub fn map(map_path: Option<&str>) -> Self {
+        Self {
+            map_path: map_path.map(str::to_string),
+            ..default()
+        }
+    }
+
+    /// Narrow the context to a layer.
+    pub fn with_layer(mut self, id: u32, name: &str) -> Self {
+        self.layer_id = Some(id);
+        self.layer_name = Some(name.to_string());
+        self
+    }
+
+    /// Narrow the context to an object.
+    pub fn with_object(mut self, id: u32, name: &str) -> Self {
+        self.object_id = Some(id);
+        self.object_name = Some(name.to_string());
+        self
+    }
+
+    /// Narrow the context to a class and, if known, one of its fields.
+    pub fn with_class(mut self, class: &str, field: Option<&str>) -> Self {
+        self.class = Some(class.to_string());
+        self.field = field.map(str::to_string);
+        self
+    }
+}
+
+impl fmt::Display for PropertyErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(map_path) = &self.map_path {
+            parts.push(format!("map '{map_path}'"));
+        }
+        if let Some(part) = named_part("layer", self.layer_name.as_deref(), self.layer_id) {
+            parts.push(part);
+        }
+        if let Some(part) = named_part("object", self.object_name.as_deref(), self.object_id) {
+            parts.push(part);
+        }
+        if let Some(class) = &self.class {
+            parts.push(format!("class '{class}'"));
+        }
+        if let Some(field) = &self.field {
+            parts.push(format!("field '{field}'"));
+        }
+        if parts.is_empty() {
+            parts.push("unknown location".to_string());
+        }
+        f.write_str(&parts.join(", "))
+    }
+}
+
+/// Format a layer or object as `kind 'name' (id)`, leaving out an empty name.
+fn named_part(kind: &str, name: Option<&str>, id: Option<u32>) -> Option<String> {
+    match (name.filter(|name| !name.is_empty()), id) {
+        (Some(name), Some(id)) => Some(format!("{kind} '{name}' ({id})")),
+        (Some(name), None) => Some(format!("{kind} '{name}'")),
+        (None, Some(id)) => Some(format!("{kind} {id}")),
+        (None, None) => None,
+    }
+}
+
+/// What went wrong with a property.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PropertyErrorKind {
+    /// The class or enum couldn't be deserialized; its component isn't inserted
+    #[error("failed to deserialize: {0}")]
+    Deserialize(String),
+
+    /// The value doesn't fit the field's type; the field keeps its default
+    #[error("{} doesn't fit the field's type, using its default", format_value(.0))]
+    InvalidValue(PropertyValue),
+
+    /// The value is outside the field's `#[tiled(min, max)]` range; it is still used
+    #[error("{} is outside the range {}..={}", format_value(.value), format_bound(*.min), format_bound(*.max))]
+    OutOfRange {
+        /// The authored value
+        value: PropertyValue,
+        /// Lower bound, if any
+        min: Option<f64>,
+        /// Upper bound, if any
+        max: Option<f64>,
+    },
+}
+
+fn format_value(value: &PropertyValue) -> String {
+    match value {
+        PropertyValue::BoolValue(value) => value.to_string(),
+        PropertyValue::FloatValue(value) => value.to_string(),
+        PropertyValue::IntValue(value) => value.to_string(),
+        PropertyValue::ObjectValue(id) => format!("object {id}"),
+        PropertyValue::StringValue(value) | PropertyValue::FileValue(value) => {
+            format!("{value:?}")
+        }
+        other => format!("{other:?}"),
+    }
+}
+
+fn format_bound(bound: Option<f64>) -> String {
+    bound.map(|bound| bound.to_string()).unwrap_or_default()
+}
+
+/// A property problem found while spawning a map, with where it occurred.
+///
+/// Written as a message by [`report_property_error`]; see the
+/// [module docs](self).
+#[derive(Message, Error, Debug, Clone, PartialEq)]
+#[error("{context}: {kind}")]
+pub struct TiledPropertyError {
+    /// Where the error occurred
+    pub context: PropertyErrorContext,
+    /// What went wrong
+    pub kind: PropertyErrorKind,
+}
+
+/// Report a property error: log it as a warning and write it as a
+/// [`TiledPropertyError`] message.
+pub fn report_property_error(commands: &mut Commands, error: TiledPropertyError) {
+    warn!("{error}");
+    commands.write_message(error);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_property_error_names_its_location() {
+        let error = TiledPropertyError {
+            context: PropertyErrorContext::map(Some("maps/level1.tmx"))
+                .with_layer(3, "Enemies")
+                .with_object(412, "Goblin")
+                .with_class("game::Enemy", Some("speed")),
+            kind: PropertyErrorKind::InvalidValue(PropertyValue::StringValue("fast".into())),
+        };
+        assert_eq!(
+            error.to_string(),
+            "map 'maps/level1.tmx', layer 'Enemies' (3), object 'Goblin' (412), \
+             class 'game::Enemy', field 'speed': \"fast\" doesn't fit the field's type, \
+             using its default"
+        );
+
+        let error = TiledPropertyError {
+            context: PropertyErrorContext::map(None)
+                .with_object(7, "")
+                .with_class("game::Door", Some("width")),
+            kind: PropertyErrorKind::OutOfRange {
+                value: PropertyValue::IntValue(-1),
+                min: Some(0.0),
+                max: None,
+            },
+        };
+        assert_eq!(
+            error.to_string(),
+            "object 7, class 'game::Door', field 'width': -1 is outside the range 0..="
+        );
+    }
+}
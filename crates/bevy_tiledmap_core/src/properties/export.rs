@@ -12,8 +12,8 @@ use bevy::prelude::*;
 use bevy::reflect::{TypeInfo, TypeRegistration, TypeRegistry};
 
 use super::registry::{
-    TiledClassRegistry, TiledDefaultValue, TiledEnumInfo, TiledEnumKind, TiledTypeKind,
-    TiledVariantKind,
+    TiledClassRegistry, TiledDefaultValue, TiledEnumInfo, TiledEnumRepr, TiledEnumStorage,
+    TiledFieldInfo, TiledTypeKind, TiledVariantInfo, TiledVariantKind,
 };
 
 /// Intermediate representation of a Tiled custom property type for serialization.
@@ -33,6 +33,10 @@ pub struct TiledMemberExport {
     /// For class types, the full type path (e.g., "`glam::Vec2`", "`game::Door`")
     pub property_type: Option<String>,
     pub value: TiledValueExport,
+    /// Smallest allowed value of a numeric member
+    pub min: Option<f64>,
+    /// Largest allowed value of a numeric member
+    pub max: Option<f64>,
 }
 
 /// Exportable default value (simplified for JSON serialization).
@@ -51,7 +55,8 @@ pub enum TiledValueExport {
 pub struct TiledEnumExport {
     pub id: usize,
     pub name: String,
-    pub values: Vec<String>,   // Variant names
+    pub values: Vec<String>, // Variant names
+    pub storage_type: TiledEnumStorage,
     pub values_as_flags: bool, // Always false for now
 }
 
@@ -124,6 +129,9 @@ pub fn build_export_data(registry: &TiledClassRegistry) -> Vec<TiledTypeExport>
                             None,
                             TiledValueExport::String(String::new()),
                         ),
+                        TiledTypeKind::Object => {
+                            ("object".to_string(), None, TiledValueExport::Int(0))
+                        }
                         TiledTypeKind::Class { property_type } => {
                             // Check if this is actually an enum type
                             // Try exact match first, then fuzzy match by suffix
@@ -143,11 +151,7 @@ pub fn build_export_data(registry: &TiledClassRegistry) -> Vec<TiledTypeExport>
                                             || *name == *property_type
                                     })
                                     .unwrap_or(property_type);
-                                (
-                                    "string".to_string(),
-                                    Some(full_name.to_string()),
-                                    TiledValueExport::String(String::new()),
-                                )
+                                enum_member_type(registry, full_name)
                             } else {
                                 // It's a class - use ClassDefault (empty object {})
                                 (
@@ -158,12 +162,8 @@ pub fn build_export_data(registry: &TiledClassRegistry) -> Vec<TiledTypeExport>
                             }
                         }
                         TiledTypeKind::Enum { property_type, .. } => {
-                            // Enum fields are exported as string type with propertyType
-                            (
-                                "string".to_string(),
-                                Some(property_type.to_string()),
-                                TiledValueExport::String(String::new()),
-                            )
+                            // Enum fields are exported with their storage type and propertyType
+                            enum_member_type(registry, property_type)
                         }
                     };
 
@@ -172,6 +172,8 @@ pub fn build_export_data(registry: &TiledClassRegistry) -> Vec<TiledTypeExport>
                         tiled_type,
                         property_type,
                         value,
+                        min: field.min,
+                        max: field.max,
                     }
                 })
                 .collect();
@@ -217,6 +219,7 @@ pub fn build_enum_export_data(registry: &TiledClassRegistry) -> Vec<TiledEnumExp
                         .iter()
                         .map(ToString::to_string)
                         .collect(),
+                    storage_type: enum_info.storage(),
                     values_as_flags: false,
                 })
             } else {
@@ -226,6 +229,37 @@ pub fn build_enum_export_data(registry: &TiledClassRegistry) -> Vec<TiledEnumExp
         .collect()
 }
 
+/// Get the member type, property type and default value of a field holding the enum `name`.
+///
+/// Complex enums are `class` members (of the default variant's class for class-name
+/// enums). Simple enums stored as int are `int` members defaulting to the first
+/// variant, others (and unregistered enums) `string` members.
+fn enum_member_type(
+    registry: &TiledClassRegistry,
+    name: &str,
+) -> (String, Option<String>, TiledValueExport) {
+    let info = registry.get_enum(name);
+    if let Some(class_name) = info.and_then(TiledEnumInfo::member_class_name) {
+        return (
+            "class".to_string(),
+            Some(class_name),
+            TiledValueExport::ClassDefault,
+        );
+    }
+    match info.map(TiledEnumInfo::storage) {
+        Some(TiledEnumStorage::Int) => (
+            "int".to_string(),
+            Some(name.to_string()),
+            TiledValueExport::Int(0),
+        ),
+        _ => (
+            "string".to_string(),
+            Some(name.to_string()),
+            TiledValueExport::String(String::new()),
+        ),
+    }
+}
+
 /// Convert `TiledDefaultValue` to `TiledValueExport` for serialization.
 fn convert_default_value(value: &TiledDefaultValue) -> TiledValueExport {
     match value {
@@ -239,11 +273,53 @@ fn convert_default_value(value: &TiledDefaultValue) -> TiledValueExport {
     }
 }
 
-/// Export a complex enum as a Tiled class type with `:variant` discriminant field.
+/// Export a complex enum as Tiled types, according to its [`TiledEnumRepr`].
+///
+/// - Tagged enums become a class with a discriminant field, plus the synthetic
+///   enum listing the variants (see [`export_complex_enum`]).
+/// - Untagged enums become a class with the fields of all variants.
+/// - Class-name enums become one class per variant, named `EnumName::Variant`.
 ///
-/// Complex enums (with struct/tuple variants) are exported as class types rather than
-/// simple enums. The class includes a `:variant` field (string type) that acts as the
-/// discriminant, plus the union of all fields from all variants.
+/// IDs are assigned from `id` upwards.
+fn export_complex_enum_types(
+    enum_info: &TiledEnumInfo,
+    id: usize,
+    registry: &TiledClassRegistry,
+) -> Vec<TiledTypeOrEnumExport> {
+    match enum_info.repr() {
+        Some(TiledEnumRepr::Tagged) => vec![
+            TiledTypeOrEnumExport::Type(export_complex_enum(enum_info, id, registry)),
+            TiledTypeOrEnumExport::Enum(generate_variant_names_enum(enum_info, id + 1)),
+        ],
+        Some(TiledEnumRepr::Untagged) => vec![TiledTypeOrEnumExport::Type(export_complex_enum(
+            enum_info, id, registry,
+        ))],
+        Some(TiledEnumRepr::ClassName) => enum_info
+            .variant_info()
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .map(|(i, variant)| {
+                TiledTypeOrEnumExport::Type(TiledTypeExport {
+                    id: id + i,
+                    name: enum_info.variant_class_name(variant.name),
+                    members: variant
+                        .fields()
+                        .iter()
+                        .map(|field| export_field_member(field, registry))
+                        .collect(),
+                })
+            })
+            .collect(),
+        None => panic!("export_complex_enum_types called on simple enum"),
+    }
+}
+
+/// Export a complex enum as a single Tiled class type.
+///
+/// The class has the union of all fields from all variants. For tagged enums, a
+/// tag field (`:variant` unless renamed with `#[tiled(tag = "...")]`, string type)
+/// comes first and acts as the discriminant.
 ///
 /// # Arguments
 ///
@@ -259,112 +335,40 @@ fn export_complex_enum(
     id: usize,
     registry: &TiledClassRegistry,
 ) -> TiledTypeExport {
-    let variant_info = match &enum_info.kind {
-        TiledEnumKind::Complex { variant_info } => variant_info,
-        TiledEnumKind::Simple { .. } => {
-            panic!("export_complex_enum called on simple enum");
-        }
+    let Some(variant_info) = enum_info.variant_info() else {
+        panic!("export_complex_enum called on simple enum");
     };
 
     let mut members = Vec::new();
     let mut field_types = HashMap::new();
 
-    // Add :variant discriminant field first
-    let default_variant = enum_info.default_variant_name().unwrap_or("");
-    members.push(TiledMemberExport {
-        name: ":variant".to_string(),
-        tiled_type: "string".to_string(),
-        property_type: Some(format!("{}:::variant", enum_info.name)),
-        value: TiledValueExport::String(default_variant.to_string()),
-    });
+    // Add the discriminant field first
+    if let Some(tag) = enum_info.tag() {
+        let default_variant = enum_info.default_variant_name().unwrap_or("");
+        members.push(TiledMemberExport {
+            name: tag.to_string(),
+            tiled_type: "string".to_string(),
+            property_type: Some(enum_info.variant_enum_name()),
+            value: TiledValueExport::String(default_variant.to_string()),
+            min: None,
+            max: None,
+        });
+    }
 
     // Collect union of all variant fields
-    for variant in variant_info.iter() {
-        if let Some(fields_info) = match &variant.kind {
-            TiledVariantKind::Unit => None,
-            TiledVariantKind::Struct { fields } | TiledVariantKind::Tuple { fields } => {
-                Some(*fields)
-            }
-        } {
-            for field in fields_info {
-                // Check for type conflicts
-                if let Some(existing_type) = field_types.get(field.name) {
-                    if !types_match(existing_type, &field.tiled_type) {
-                        warn!(
-                            "Field '{}' has conflicting types in enum '{}': {:?} vs {:?}. Using first type.",
-                            field.name, enum_info.name, existing_type, field.tiled_type
-                        );
-                        continue;
-                    }
-                } else {
-                    field_types.insert(field.name.to_string(), field.tiled_type.clone());
-
-                    // Export the field
-                    let (tiled_type, property_type, value) = match &field.tiled_type {
-                        TiledTypeKind::Bool => (
-                            "bool".to_string(),
-                            None,
-                            convert_default_value(&field.default_value),
-                        ),
-                        TiledTypeKind::Int => (
-                            "int".to_string(),
-                            None,
-                            convert_default_value(&field.default_value),
-                        ),
-                        TiledTypeKind::Float => (
-                            "float".to_string(),
-                            None,
-                            convert_default_value(&field.default_value),
-                        ),
-                        TiledTypeKind::String => (
-                            "string".to_string(),
-                            None,
-                            convert_default_value(&field.default_value),
-                        ),
-                        TiledTypeKind::Color => (
-                            "color".to_string(),
-                            None,
-                            convert_default_value(&field.default_value),
-                        ),
-                        TiledTypeKind::File => (
-                            "file".to_string(),
-                            None,
-                            TiledValueExport::String(String::new()),
-                        ),
-                        TiledTypeKind::Class { property_type } => {
-                            // Check if this is an enum
-                            let is_enum = registry.get_enum(property_type).is_some();
-
-                            if is_enum {
-                                (
-                                    "string".to_string(),
-                                    Some(property_type.to_string()),
-                                    TiledValueExport::String(String::new()),
-                                )
-                            } else {
-                                (
-                                    "class".to_string(),
-                                    Some(property_type.to_string()),
-                                    TiledValueExport::ClassDefault,
-                                )
-                            }
-                        }
-                        TiledTypeKind::Enum { property_type, .. } => (
-                            "string".to_string(),
-                            Some(property_type.to_string()),
-                            TiledValueExport::String(String::new()),
-                        ),
-                    };
-
-                    members.push(TiledMemberExport {
-                        name: field.name.to_string(),
-                        tiled_type,
-                        property_type,
-                        value,
-                    });
-                }
+    for field in variant_info.iter().flat_map(TiledVariantInfo::fields) {
+        // Check for type conflicts
+        if let Some(existing_type) = field_types.get(field.name) {
+            if !types_match(existing_type, &field.tiled_type) {
+                warn!(
+                    "Field '{}' has conflicting types in enum '{}': {:?} vs {:?}. Using first type.",
+                    field.name, enum_info.name, existing_type, field.tiled_type
+                );
             }
+            continue;
         }
+        field_types.insert(field.name.to_string(), field.tiled_type.clone());
+        members.push(export_field_member(field, registry));
     }
 
     TiledTypeExport {
@@ -374,6 +378,65 @@ fn export_complex_enum(
     }
 }
 
+/// Export a field of a complex enum variant as a class member.
+fn export_field_member(field: &TiledFieldInfo, registry: &TiledClassRegistry) -> TiledMemberExport {
+    let (tiled_type, property_type, value) = match &field.tiled_type {
+        TiledTypeKind::Bool => (
+            "bool".to_string(),
+            None,
+            convert_default_value(&field.default_value),
+        ),
+        TiledTypeKind::Int => (
+            "int".to_string(),
+            None,
+            convert_default_value(&field.default_value),
+        ),
+        TiledTypeKind::Float => (
+            "float".to_string(),
+            None,
+            convert_default_value(&field.default_value),
+        ),
+        TiledTypeKind::String => (
+            "string".to_string(),
+            None,
+            convert_default_value(&field.default_value),
+        ),
+        TiledTypeKind::Color => (
+            "color".to_string(),
+            None,
+            convert_default_value(&field.default_value),
+        ),
+        TiledTypeKind::File => (
+            "file".to_string(),
+            None,
+            TiledValueExport::String(String::new()),
+        ),
+        TiledTypeKind::Object => ("object".to_string(), None, TiledValueExport::Int(0)),
+        TiledTypeKind::Class { property_type } => {
+            // Check if this is an enum
+            if registry.get_enum(property_type).is_some() {
+                enum_member_type(registry, property_type)
+            } else {
+                (
+                    "class".to_string(),
+                    Some(property_type.to_string()),
+                    TiledValueExport::ClassDefault,
+                )
+            }
+        }
+        TiledTypeKind::Enum { property_type, .. } => enum_member_type(registry, property_type),
+    };
+
+    TiledMemberExport {
+        name: field.name.to_string(),
+        tiled_type,
+        property_type,
+        value,
+        min: field.min,
+        max: field.max,
+    }
+}
+
 /// Check if two `TiledTypeKind` values represent the same type.
 fn types_match(a: &TiledTypeKind, b: &TiledTypeKind) -> bool {
     match (a, b) {
@@ -397,10 +460,10 @@ fn types_match(a: &TiledTypeKind, b: &TiledTypeKind) -> bool {
     }
 }
 
-/// Generate a synthetic enum for the `:variant` discriminant field.
+/// Generate a synthetic enum for the discriminant field.
 ///
-/// This creates an enum named `EnumName:::variant` with all the variant names
-/// as values. This allows Tiled to show a dropdown for selecting variants.
+/// This creates an enum named `EnumName:::variant` (whatever the tag is) with all
+/// the variant names as values. This allows Tiled to show a dropdown for selecting variants.
 ///
 /// # Arguments
 ///
@@ -415,8 +478,9 @@ fn generate_variant_names_enum(enum_info: &TiledEnumInfo, id: usize) -> TiledEnu
 
     TiledEnumExport {
         id,
-        name: format!("{}:::variant", enum_info.name),
+        name: enum_info.variant_enum_name(),
         values: variant_names.iter().map(ToString::to_string).collect(),
+        storage_type: TiledEnumStorage::String,
         values_as_flags: false,
     }
 }
@@ -539,6 +603,14 @@ fn write_mixed_types_to_file(
 
                     writeln!(file, "        \"type\": \"{}\",", member.tiled_type)?;
 
+                    // Write the allowed range of numeric members
+                    if let Some(min) = member.min {
+                        writeln!(file, "        \"min\": {},", min)?;
+                    }
+                    if let Some(max) = member.max {
+                        writeln!(file, "        \"max\": {},", max)?;
+                    }
+
                     // Write default value
                     write!(file, "        \"value\": ")?;
                     write_value(&mut *file, &member.value)?;
@@ -559,7 +631,11 @@ fn write_mixed_types_to_file(
                 writeln!(file, "    \"id\": {},", enum_export.id)?;
                 writeln!(file, "    \"name\": \"{}\",", enum_export.name)?;
                 writeln!(file, "    \"type\": \"enum\",")?;
-                writeln!(file, "    \"storageType\": \"string\",")?;
+                writeln!(
+                    file,
+                    "    \"storageType\": \"{}\",",
+                    enum_export.storage_type.as_str()
+                )?;
                 writeln!(file, "    \"values\": [")?;
 
                 for (value_idx, variant) in enum_export.values.iter().enumerate() {
@@ -761,15 +837,10 @@ pub fn export_all_types_with_reflection(
 
     let mut next_id = all_exports.len() + 1;
     for enum_info in complex_enums {
-        // Export the complex enum as a class type
-        let complex_export = export_complex_enum(enum_info, next_id, tiled_registry);
-        all_exports.push(TiledTypeOrEnumExport::Type(complex_export));
-        next_id += 1;
-
-        // Export the synthetic :::variant enum
-        let variant_enum = generate_variant_names_enum(enum_info, next_id);
-        all_exports.push(TiledTypeOrEnumExport::Enum(variant_enum));
-        next_id += 1;
+        // Export the complex enum as class types (and the synthetic :::variant enum)
+        let complex_exports = export_complex_enum_types(enum_info, next_id, tiled_registry);
+        next_id += complex_exports.len();
+        all_exports.extend(complex_exports);
 
         // Recursively discover referenced types in variant fields
         if let Some(variant_info_slice) = enum_info.variant_info() {
@@ -963,13 +1034,9 @@ fn build_all_exports(world: &World) -> Vec<TiledTypeOrEnumExport> {
 
     let mut next_id = all_exports.len() + 1;
     for enum_info in complex_enums {
-        let complex_export = export_complex_enum(enum_info, next_id, tiled_registry);
-        all_exports.push(TiledTypeOrEnumExport::Type(complex_export));
-        next_id += 1;
-
-        let variant_enum = generate_variant_names_enum(enum_info, next_id);
-        all_exports.push(TiledTypeOrEnumExport::Enum(variant_enum));
-        next_id += 1;
+        let complex_exports = export_complex_enum_types(enum_info, next_id, tiled_registry);
+        next_id += complex_exports.len();
+        all_exports.extend(complex_exports);
 
         if let Some(variant_info_slice) = enum_info.variant_info() {
             for variant in variant_info_slice {
@@ -1011,6 +1078,12 @@ fn export_type_to_json(t: &TiledTypeExport) -> serde_json::Value {
             if let Some(ref pt) = m.property_type {
                 member["propertyType"] = serde_json::Value::String(pt.clone());
             }
+            if let Some(min) = m.min {
+                member["min"] = serde_json::json!(min);
+            }
+            if let Some(max) = m.max {
+                member["max"] = serde_json::json!(max);
+            }
             member
         })
         .collect();
@@ -1032,7 +1105,7 @@ fn export_enum_to_json(e: &TiledEnumExport) -> serde_json::Value {
         "id": e.id,
         "name": e.name,
         "type": "enum",
-        "storageType": "string",
+        "storageType": e.storage_type.as_str(),
         "values": e.values,
         "valuesAsFlags": e.values_as_flags
     })
@@ -1104,6 +1177,7 @@ fn discover_type_recursive(
                         None,
                         TiledValueExport::String(String::new()),
                     ),
+                    TiledTypeKind::Object => ("object".to_string(), None, TiledValueExport::Int(0)),
                     TiledTypeKind::Class { property_type } => {
                         // Check if this is actually an enum type
                         // Try exact match first, then fuzzy match by suffix
@@ -1123,11 +1197,7 @@ fn discover_type_recursive(
                                         || *name == *property_type
                                 })
                                 .unwrap_or(property_type);
-                            (
-                                "string".to_string(),
-                                Some(full_name.to_string()),
-                                TiledValueExport::String(String::new()),
-                            )
+                            enum_member_type(tiled_registry, full_name)
                         } else {
                             // It's a class - use ClassDefault (empty object {})
                             (
@@ -1138,12 +1208,8 @@ fn discover_type_recursive(
                         }
                     }
                     TiledTypeKind::Enum { property_type, .. } => {
-                        // Enum types are exported as string with propertyType reference
-                        (
-                            "string".to_string(),
-                            Some(property_type.to_string()),
-                            TiledValueExport::String(String::new()),
-                        )
+                        // Enum types are exported with their storage type and propertyType
+                        enum_member_type(tiled_registry, property_type)
                     }
                 };
 
@@ -1152,6 +1218,8 @@ fn discover_type_recursive(
                     tiled_type,
                     property_type,
                     value,
+                    min: field.min,
+                    max: field.max,
                 }
             })
             .collect();
@@ -1175,6 +1243,12 @@ fn discover_type_recursive(
         return;
     }
 
+    // Arrays and tuples of primitives get a class with indexed members
+    if let Some(export) = build_indexed_export(type_path) {
+        output.push(TiledTypeOrEnumExport::Type(export));
+        return;
+    }
+
     // Fall back to Bevy reflection
     let app_type_registry = world.resource::<AppTypeRegistry>();
     let registry = app_type_registry.read();
@@ -1243,6 +1317,8 @@ fn build_reflected_export(
                 tiled_type,
                 property_type,
                 value,
+                min: None,
+                max: None,
             }
         })
         .collect();
@@ -1254,6 +1330,64 @@ fn build_reflected_export(
     })
 }
 
+/// Build a `TiledTypeExport` for an array or tuple type path.
+///
+/// `"[f32; 3]"` and `"(f32, i32)"` become classes with members named `"0"`, `"1"`, ...
+/// Returns None for other type paths, or if any element is not a primitive.
+fn build_indexed_export(type_path: &str) -> Option<TiledTypeExport> {
+    let elements: Vec<&str> = if let Some(inner) = type_path
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+    {
+        let (element, len) = inner.split_once(';')?;
+        let len: usize = len.trim().parse().ok()?;
+        vec![element.trim(); len]
+    } else if let Some(inner) = type_path
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        inner.split(',').map(str::trim).collect()
+    } else {
+        return None;
+    };
+
+    if elements.is_empty()
+        || !elements
+            .iter()
+            .all(|e| is_primitive_type(e) || *e == "String")
+    {
+        return None;
+    }
+
+    let members = elements
+        .iter()
+        .enumerate()
+        .map(|(i, element)| {
+            let tiled_type = map_primitive_to_tiled(element);
+            let value = match tiled_type.as_str() {
+                "bool" => TiledValueExport::Bool(false),
+                "int" => TiledValueExport::Int(0),
+                "float" => TiledValueExport::Float(0.0),
+                _ => TiledValueExport::String(String::new()),
+            };
+            TiledMemberExport {
+                name: i.to_string(),
+                tiled_type,
+                property_type: None,
+                value,
+                min: None,
+                max: None,
+            }
+        })
+        .collect();
+
+    Some(TiledTypeExport {
+        id: 0, // Will be renumbered later
+        name: type_path.to_string(),
+        members,
+    })
+}
+
 /// Check if a type path represents a primitive Tiled type.
 fn is_primitive_type(type_path: &str) -> bool {
     matches!(
@@ -1277,7 +1411,11 @@ fn map_primitive_to_tiled(type_path: &str) -> String {
 mod tests {
     use std::f32::consts::PI;
 
+    use bevy_tiledmap_macros::TiledClass;
+    use tiled::PropertyValue;
+
     use super::*;
+    use crate::properties::{DEFAULT_VARIANT_TAG, FromTiledProperty, ToTiledProperty};
 
     #[test]
     fn test_convert_default_value_bool() {
@@ -1329,11 +1467,15 @@ mod tests {
                 name: "health",
                 tiled_type: TiledTypeKind::Float,
                 default_value: TiledDefaultValue::Float(100.0),
+                min: Some(0.0),
+                max: None,
             },
             TiledFieldInfo {
                 name: "enabled",
                 tiled_type: TiledTypeKind::Bool,
                 default_value: TiledDefaultValue::Bool(true),
+                min: None,
+                max: None,
             },
         ];
 
@@ -1351,6 +1493,8 @@ mod tests {
                     tiled_type,
                     property_type,
                     value: convert_default_value(&field.default_value),
+                    min: field.min,
+                    max: field.max,
                 }
             })
             .collect();
@@ -1365,6 +1509,16 @@ mod tests {
         assert_eq!(members[1].tiled_type, "bool");
         assert_eq!(members[1].property_type, None);
         assert_eq!(members[1].value, TiledValueExport::Bool(true));
+
+        // Ranges are exported as member keys
+        let json = export_type_to_json(&TiledTypeExport {
+            id: 1,
+            name: "game::Player".to_string(),
+            members,
+        });
+        assert_eq!(json["members"][0]["min"], serde_json::json!(0.0));
+        assert!(json["members"][0].get("max").is_none());
+        assert!(json["members"][1].get("min").is_none());
     }
 
     #[test]
@@ -1378,12 +1532,16 @@ mod tests {
                     tiled_type: "float".to_string(),
                     property_type: None,
                     value: TiledValueExport::Float(5.0),
+                    min: None,
+                    max: None,
                 },
                 TiledMemberExport {
                     name: "team".to_string(),
                     tiled_type: "int".to_string(),
                     property_type: None,
                     value: TiledValueExport::Int(0),
+                    min: None,
+                    max: None,
                 },
             ],
         };
@@ -1394,4 +1552,328 @@ mod tests {
         assert_eq!(export.members[0].name, "speed");
         assert_eq!(export.members[1].name, "team");
     }
+
+    #[test]
+    fn test_export_enum_storage_type() {
+        let export = TiledEnumExport {
+            id: 3,
+            name: "game::Facing".to_string(),
+            values: vec!["North".to_string(), "South".to_string()],
+            storage_type: TiledEnumStorage::Int,
+            values_as_flags: false,
+        };
+
+        let json = export_enum_to_json(&export);
+        assert_eq!(json["storageType"], "int");
+        assert_eq!(json["values"], serde_json::json!(["North", "South"]));
+    }
+
+    #[test]
+    fn test_build_indexed_export() {
+        let array = build_indexed_export("[f32; 3]").unwrap();
+        assert_eq!(array.name, "[f32; 3]");
+        let names: Vec<_> = array.members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["0", "1", "2"]);
+        assert!(array.members.iter().all(|m| m.tiled_type == "float"));
+
+        let tuple = build_indexed_export("(i32, bool, String)").unwrap();
+        let types: Vec<_> = tuple
+            .members
+            .iter()
+            .map(|m| m.tiled_type.as_str())
+            .collect();
+        assert_eq!(types, ["int", "bool", "string"]);
+
+        assert!(build_indexed_export("[glam::Vec2; 2]").is_none());
+        assert!(build_indexed_export("game::Player").is_none());
+    }
+
+    #[derive(Reflect, TiledClass, Debug, Clone, PartialEq, Default)]
+    #[tiled(name = "export_test::Trigger")]
+    enum Trigger {
+        #[default]
+        Never,
+        Timer {
+            seconds: f32,
+        },
+    }
+
+    #[derive(Reflect, TiledClass, Debug, Clone, PartialEq, Default)]
+    #[tiled(name = "export_test::Reward")]
+    #[tiled(tag = "kind")]
+    enum Reward {
+        #[default]
+        Nothing,
+        Coins(i32),
+        Item {
+            name: String,
+        },
+    }
+
+    #[derive(Reflect, TiledClass, Debug, Clone, PartialEq, Default)]
+    #[tiled(name = "export_test::Shape")]
+    #[tiled(repr = "untagged")]
+    enum Shape {
+        #[default]
+        Point,
+        Circle {
+            radius: f32,
+        },
+        Rect {
+            width: f32,
+            height: f32,
+        },
+    }
+
+    #[derive(Reflect, TiledClass, Debug, Clone, PartialEq, Default)]
+    #[tiled(name = "export_test::Pickup")]
+    #[tiled(repr = "class")]
+    enum Pickup {
+        Health {
+            amount: i32,
+        },
+        #[default]
+        Ammo,
+    }
+
+    /// Deserialize a class value the way Tiled stores an exported class: every
+    /// member at its exported default, with the tag set to `variant`.
+    fn class_from_export(
+        export: &TiledTypeExport,
+        variant: &str,
+        overrides: &[(&str, PropertyValue)],
+    ) -> PropertyValue {
+        let mut properties = tiled::Properties::new();
+        for member in &export.members {
+            let value = match &member.value {
+                TiledValueExport::Int(i) => PropertyValue::IntValue(*i),
+                TiledValueExport::Float(f) => PropertyValue::FloatValue(*f),
+                TiledValueExport::String(s) => PropertyValue::StringValue(s.clone()),
+                _ => continue,
+            };
+            properties.insert(member.name.clone(), value);
+        }
+        properties.insert(
+            export.members[0].name.clone(),
+            PropertyValue::StringValue(variant.to_string()),
+        );
+        for (name, value) in overrides {
+            properties.insert(name.to_string(), value.clone());
+        }
+        PropertyValue::ClassValue {
+            property_type: export.name.clone(),
+            properties,
+        }
+    }
+
+    #[test]
+    fn test_complex_enum_export_interop() {
+        let registry = TiledClassRegistry::build();
+
+        // Default tag
+        let info = registry.get_enum_for::<Trigger>().unwrap();
+        assert_eq!(info.tag(), Some(DEFAULT_VARIANT_TAG));
+        let export = export_complex_enum(info, 1, &registry);
+        assert_eq!(export.members[0].name, ":variant");
+        assert_eq!(
+            export.members[0].property_type.as_deref(),
+            Some("export_test::Trigger:::variant")
+        );
+        assert_eq!(
+            export.members[0].value,
+            TiledValueExport::String("Never".to_string())
+        );
+        let value = class_from_export(
+            &export,
+            "Timer",
+            &[("seconds", PropertyValue::FloatValue(1.5))],
+        );
+        assert_eq!(
+            Trigger::from_property(&value),
+            Some(Trigger::Timer { seconds: 1.5 })
+        );
+
+        // Custom tag: the exported member is the one read back
+        let info = registry.get_enum_for::<Reward>().unwrap();
+        assert_eq!(info.tag(), Some("kind"));
+        let export = export_complex_enum(info, 2, &registry);
+        let names: Vec<_> = export.members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["kind", "0", "name"]);
+        // The selector enum keeps its name whatever the tag
+        assert_eq!(
+            export.members[0].property_type.as_deref(),
+            Some("export_test::Reward:::variant")
+        );
+        assert_eq!(
+            generate_variant_names_enum(info, 3).name,
+            "export_test::Reward:::variant"
+        );
+
+        let value = class_from_export(&export, "Coins", &[("0", PropertyValue::IntValue(50))]);
+        assert_eq!(Reward::from_property(&value), Some(Reward::Coins(50)));
+        let reflected = (info.from_property)(&value).unwrap();
+        assert_eq!(reflected.downcast_ref::<Reward>(), Some(&Reward::Coins(50)));
+
+        // Written values round-trip through the tag
+        let item = Reward::Item {
+            name: "Key".to_string(),
+        };
+        let PropertyValue::ClassValue { properties, .. } = item.to_property().unwrap() else {
+            panic!("complex enums are written as class values");
+        };
+        assert_eq!(
+            properties.get("kind"),
+            Some(&PropertyValue::StringValue("Item".to_string()))
+        );
+        assert!(!properties.contains_key(":variant"));
+        let value = PropertyValue::ClassValue {
+            property_type: "export_test::Reward".to_string(),
+            properties,
+        };
+        assert_eq!(Reward::from_property(&value), Some(item));
+
+        // The default tag isn't read for enums with a custom one
+        let mut properties = tiled::Properties::new();
+        properties.insert(
+            ":variant".to_string(),
+            PropertyValue::StringValue("Nothing".to_string()),
+        );
+        let error = (info.from_property)(&PropertyValue::ClassValue {
+            property_type: "export_test::Reward".to_string(),
+            properties,
+        })
+        .unwrap_err();
+        assert!(error.contains("'kind'"), "{error}");
+    }
+
+    fn class_value(property_type: &str, members: &[(&str, PropertyValue)]) -> PropertyValue {
+        PropertyValue::ClassValue {
+            property_type: property_type.to_string(),
+            properties: members
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_untagged_enum_export_and_inference() {
+        let registry = TiledClassRegistry::build();
+        let info = registry.get_enum_for::<Shape>().unwrap();
+        assert_eq!(info.repr(), Some(TiledEnumRepr::Untagged));
+        assert_eq!(info.tag(), None);
+
+        // Only the class is exported, without a tag member or selector enum
+        let exports = export_complex_enum_types(info, 1, &registry);
+        let [TiledTypeOrEnumExport::Type(export)] = exports.as_slice() else {
+            panic!("untagged enums export a single class");
+        };
+        let names: Vec<_> = export.members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["radius", "width", "height"]);
+
+        let shape = "export_test::Shape";
+        assert_eq!(
+            Shape::from_property(&class_value(shape, &[])),
+            Some(Shape::Point)
+        );
+        assert_eq!(
+            Shape::from_property(&class_value(
+                shape,
+                &[("radius", PropertyValue::FloatValue(2.0))]
+            )),
+            Some(Shape::Circle { radius: 2.0 })
+        );
+        // Missing required fields are still an error once the variant is known
+        assert_eq!(
+            Shape::from_property(&class_value(
+                shape,
+                &[("width", PropertyValue::FloatValue(3.0))]
+            )),
+            None
+        );
+
+        // Writing leaves the variant implied by the fields
+        let rect = Shape::Rect {
+            width: 1.0,
+            height: 4.0,
+        };
+        let written = rect.to_property().unwrap();
+        let PropertyValue::ClassValue { properties, .. } = &written else {
+            panic!("complex enums are written as class values");
+        };
+        assert_eq!(properties.len(), 2);
+        assert_eq!(Shape::from_property(&written), Some(rect));
+
+        // Fields from different variants match none of them
+        let error = (info.from_property)(&class_value(
+            shape,
+            &[
+                ("radius", PropertyValue::FloatValue(1.0)),
+                ("width", PropertyValue::FloatValue(1.0)),
+            ],
+        ))
+        .unwrap_err();
+        assert!(error.contains("export_test::Shape"), "{error}");
+    }
+
+    #[test]
+    fn test_class_name_enum_export_and_lookup() {
+        let registry = TiledClassRegistry::build();
+        let info = registry.get_enum_for::<Pickup>().unwrap();
+        assert_eq!(info.repr(), Some(TiledEnumRepr::ClassName));
+        assert_eq!(info.tag(), None);
+        // Members can't change class in Tiled, so they use the default variant's
+        assert_eq!(
+            info.member_class_name().as_deref(),
+            Some("export_test::Pickup::Ammo")
+        );
+
+        // One class per variant
+        let exports = export_complex_enum_types(info, 1, &registry);
+        let classes: Vec<_> = exports
+            .iter()
+            .map(|export| match export {
+                TiledTypeOrEnumExport::Type(t) => (t.name.as_str(), t.members.len()),
+                TiledTypeOrEnumExport::Enum(_) => panic!("class-name enums export no enums"),
+            })
+            .collect();
+        assert_eq!(
+            classes,
+            [
+                ("export_test::Pickup::Health", 1),
+                ("export_test::Pickup::Ammo", 0)
+            ]
+        );
+
+        let found = registry
+            .get_enum_for_class("export_test::Pickup::Health")
+            .unwrap();
+        assert_eq!(found.name, "export_test::Pickup");
+        assert_eq!(
+            registry
+                .get_variant_class("export_test::Pickup::Ammo")
+                .map(|(_, variant)| variant),
+            Some("Ammo")
+        );
+        assert!(registry.get_variant_class("export_test::Pickup").is_none());
+
+        // The class name selects the variant, both ways
+        let value = class_value(
+            "export_test::Pickup::Health",
+            &[("amount", PropertyValue::IntValue(25))],
+        );
+        assert_eq!(
+            Pickup::from_property(&value),
+            Some(Pickup::Health { amount: 25 })
+        );
+        assert_eq!(Pickup::Health { amount: 25 }.to_property(), Some(value));
+        assert_eq!(
+            Pickup::Ammo.to_property(),
+            Some(class_value("export_test::Pickup::Ammo", &[]))
+        );
+
+        let error =
+            (info.from_property)(&class_value("export_test::Pickup::Armor", &[])).unwrap_err();
+        assert!(error.contains("'Armor'"), "{error}");
+    }
 }
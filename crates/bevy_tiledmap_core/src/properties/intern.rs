@@ -0,0 +1,179 @@
+//! Interning of merged object properties.
+//!
+//! Thousands of objects often share identical property sets (e.g. instances of
+//! one template). [`PropertyInterner`] hands out one shared `Arc` per distinct
+//! set, so the [`MergedProperties`](super::MergedProperties) of such objects
+//! share one allocation instead of each holding a copy. Editing an object's
+//! properties copies its set first, leaving the others untouched.
+//!
+//! Sets that no entity uses anymore are dropped by
+//! [`update_property_interner`], which also reports how well interning works
+//! as Bevy diagnostics ([`PropertyInterner::UNIQUE`],
+//! [`PropertyInterner::DEDUP_RATIO`]), e.g. for `LogDiagnosticsPlugin`.
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use bevy::diagnostic::{DiagnosticPath, Diagnostics};
+use bevy::prelude::*;
+use tiled::{Properties, PropertyValue};
+
+/// Statistics of a [`PropertyInterner`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PropertyInternStats {
+    /// Property sets passed to [`PropertyInterner::intern`]
+    pub interned: u64,
+    /// Interned sets that reused an existing set
+    pub deduplicated: u64,
+    /// Distinct sets currently held
+    pub unique: usize,
+}
+
+impl PropertyInternStats {
+    /// Fraction of interned sets that reused an existing set (0 when nothing was interned).
+    pub fn dedup_ratio(&self) -> f64 {
+        if self.interned == 0 {
+            0.0
+        } else {
+            self.deduplicated as f64 / self.interned as f64
+        }
+    }
+}
+
+#[derive(Default)]
+struct InternTable {
+    sets: HashMap<u64, Vec<Arc<Properties>>>,
+    stats: PropertyInternStats,
+}
+
+/// Resource sharing identical merged property sets between objects.
+///
+/// See the [module docs](self).
+#[derive(Resource, Default)]
+pub struct PropertyInterner {
+    table: Mutex<InternTable>,
+}
+
+impl PropertyInterner {
+    /// Diagnostic of the number of distinct property sets held.
+    pub const UNIQUE: DiagnosticPath = DiagnosticPath::const_new("tiled/properties/unique");
+
+    /// Diagnostic of the fraction of interned property sets that were deduplicated.
+    pub const DEDUP_RATIO: DiagnosticPath =
+        DiagnosticPath::const_new("tiled/properties/dedup_ratio");
+
+    /// Get the shared copy of `properties`, adding it if it is new.
+    pub fn intern(&self, properties: Properties) -> Arc<Properties> {
+        let mut table = self.table.lock().unwrap_or_else(PoisonError::into_inner);
+        table.stats.interned += 1;
+
+        let bucket = table.sets.entry(hash_properties(&properties)).or_default();
+        if let Some(shared) = bucket.iter().find(|shared| ***shared == properties) {
+            let shared = shared.clone();
+            table.stats.deduplicated += 1;
+            return shared;
+        }
+        let shared = Arc::new(properties);
+        bucket.push(shared.clone());
+        table.stats.unique += 1;
+        shared
+    }
+
+    /// Drop the sets that are only held by the interner.
+    pub fn prune(&self) {
+        let mut table = self.table.lock().unwrap_or_else(PoisonError::into_inner);
+        table.sets.retain(|_, bucket| {
+            bucket.retain(|shared| Arc::strong_count(shared) > 1);
+            !bucket.is_empty()
+        });
+        table.stats.unique = table.sets.values().map(Vec::len).sum();
+    }
+
+    /// Get the interning statistics.
+    pub fn stats(&self) -> PropertyInternStats {
+        self.table
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .stats
+    }
+}
+
+/// System that drops unused property sets and records the interning diagnostics.
+pub fn update_property_interner(interner: Res<PropertyInterner>, mut diagnostics: Diagnostics) {
+    interner.prune();
+    let stats = interner.stats();
+    diagnostics.add_measurement(&PropertyInterner::UNIQUE, || stats.unique as f64);
+    diagnostics.add_measurement(&PropertyInterner::DEDUP_RATIO, || stats.dedup_ratio());
+}
+
+/// Hash a property set independently of its iteration order.
+pub(crate) fn hash_properties(properties: &Properties) -> u64 {
+    let mut entries: Vec<_> = properties.iter().collect();
+    entries.sort_unstable_by_key(|(key, _)| *key);
+
+    let mut hasher = DefaultHasher::new();
+    for (key, value) in entries {
+        key.hash(&mut hasher);
+        hash_value(value, &mut hasher);
+    }
+    hasher.finish()
+}
+
+fn hash_value(value: &PropertyValue, hasher: &mut DefaultHasher) {
+    std::mem::discriminant(value).hash(hasher);
+    match value {
+        PropertyValue::BoolValue(value) => value.hash(hasher),
+        PropertyValue::FloatValue(value) => value.to_bits().hash(hasher),
+        PropertyValue::IntValue(value) => value.hash(hasher),
+        PropertyValue::ColorValue(color) => {
+            [color.red, color.green, color.blue, color.alpha].hash(hasher);
+        }
+        PropertyValue::StringValue(value) | PropertyValue::FileValue(value) => value.hash(hasher),
+        PropertyValue::ObjectValue(value) => value.hash(hasher),
+        PropertyValue::ClassValue {
+            property_type,
+            properties,
+        } => {
+            property_type.hash(hasher);
+            hash_properties(properties).hash(hasher);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_sets_are_shared() {
+        let coin: Properties = [
+            ("value".to_string(), PropertyValue::IntValue(5)),
+            (
+                "sound".to_string(),
+                PropertyValue::FileValue("coin.ogg".into()),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let interner = PropertyInterner::default();
+
+        let first = interner.intern(coin.clone());
+        let second = interner.intern(coin);
+        let key = interner.intern(Properties::new());
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &key));
+        assert_eq!(
+            interner.stats(),
+            PropertyInternStats {
+                interned: 3,
+                deduplicated: 1,
+                unique: 2,
+            }
+        );
+
+        drop((first, second));
+        interner.prune();
+        assert_eq!(interner.stats().unique, 1);
+    }
+}
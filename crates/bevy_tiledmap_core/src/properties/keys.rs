@@ -0,0 +1,96 @@
+//! Remapping of the property names read by built-in features.
+//!
+//! Built-in features key off fixed property names (`parallaxX`, `climbable`,
+//! `spawn_chance`, ...), each exported as a `*_PROPERTY` constant next to the
+//! feature. [`PropertyKeyConfig`] renames them for maps that follow different
+//! conventions, without editing the maps.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use bevy::prelude::*;
+//! use bevy_tiledmap_core::properties::PropertyKeyConfig;
+//!
+//! App::new().insert_resource(
+//!     PropertyKeyConfig::default()
+//!         .with_key("parallaxX", "scroll_x")
+//!         .with_key("parallaxY", "scroll_y")
+//!         .with_key("climbable", "is_ladder"),
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use tiled::{Properties, PropertyValue};
+
+/// Built-in property names remapped to the names used by the game's maps.
+///
+/// Insert this resource before spawning maps. Names that aren't remapped keep
+/// their built-in name. A remapped property still falls back to its built-in
+/// name when the custom one is absent, so maps can be migrated gradually.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PropertyKeyConfig {
+    keys: BTreeMap<String, String>,
+}
+
+/// Config without remapped names, for lookups outside of the ECS.
+static BUILTIN_KEYS: PropertyKeyConfig = PropertyKeyConfig {
+    keys: BTreeMap::new(),
+};
+
+impl PropertyKeyConfig {
+    /// Get a config without remapped names.
+    pub fn builtin() -> &'static Self {
+        &BUILTIN_KEYS
+    }
+
+    /// Builder method: Read the built-in property `builtin` from `custom` instead.
+    pub fn with_key(mut self, builtin: impl Into<String>, custom: impl Into<String>) -> Self {
+        self.remap(builtin, custom);
+        self
+    }
+
+    /// Read the built-in property `builtin` from `custom` instead.
+    pub fn remap(&mut self, builtin: impl Into<String>, custom: impl Into<String>) {
+        self.keys.insert(builtin.into(), custom.into());
+    }
+
+    /// Get the name a built-in property is read from.
+    pub fn key<'a>(&'a self, builtin: &'a str) -> &'a str {
+        self.keys.get(builtin).map_or(builtin, String::as_str)
+    }
+
+    /// Look up a built-in property, by its remapped name first.
+    pub fn get<'p>(&self, properties: &'p Properties, builtin: &str) -> Option<&'p PropertyValue> {
+        properties
+            .get(self.key(builtin))
+            .or_else(|| properties.get(builtin))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remapped_keys_fall_back_to_builtin_names() {
+        let keys = PropertyKeyConfig::default().with_key("climbable", "is_ladder");
+        assert_eq!(keys.key("climbable"), "is_ladder");
+        assert_eq!(keys.key("slope"), "slope");
+
+        let mut properties = Properties::new();
+        properties.insert("climbable".to_string(), PropertyValue::BoolValue(false));
+        assert_eq!(
+            keys.get(&properties, "climbable"),
+            Some(&PropertyValue::BoolValue(false))
+        );
+
+        properties.insert("is_ladder".to_string(), PropertyValue::BoolValue(true));
+        assert_eq!(
+            keys.get(&properties, "climbable"),
+            Some(&PropertyValue::BoolValue(true))
+        );
+        assert_eq!(keys.get(&properties, "slope"), None);
+    }
+}
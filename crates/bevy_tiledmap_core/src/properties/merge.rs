@@ -0,0 +1,205 @@
+//! Object property merging with configurable order and provenance tracking.
+//!
+//! An object's final properties are assembled from several sources. Each source
+//! is applied in turn, with later sources overriding keys set by earlier ones.
+//!
+//! # Default Merge Order (lowest to highest priority)
+//!
+//! 1. [`PropertySource::ClassDefaults`] - member defaults of the object's class
+//!    from the loaded `.tiled-project` (tile objects without their own class use
+//!    the tile's class)
+//! 2. [`PropertySource::Tile`] - the tile's properties in its tileset (tile objects only)
+//! 3. [`PropertySource::TileCollision`] - the tile's first collision object
+//!    (tile objects only)
+//! 4. [`PropertySource::Object`] - template + object instance properties
+//!
+//! [`PropertySource::Layer`] (the containing object layer's properties) is not part
+//! of the default order; add it to [`PropertyMergeConfig::order`] to let objects
+//! inherit layer-wide defaults.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use bevy::prelude::*;
+//! use bevy_tiledmap_core::properties::{PropertyMergeConfig, PropertySource};
+//!
+//! App::new().insert_resource(PropertyMergeConfig {
+//!     order: vec![
+//!         PropertySource::ClassDefaults,
+//!         PropertySource::Layer,
+//!         PropertySource::Tile,
+//!         PropertySource::TileCollision,
+//!         PropertySource::Object,
+//!     ],
+//!     record_provenance: true,
+//! });
+//! ```
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use tiled::Properties;
+
+/// A source of object properties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum PropertySource {
+    /// Member defaults of the object's class, from the loaded Tiled project.
+    ClassDefaults,
+    /// Properties of the object layer containing the object.
+    Layer,
+    /// Properties of the tile in its tileset (tile objects only).
+    Tile,
+    /// Properties of the tile's first collision object (tile objects only).
+    TileCollision,
+    /// Template + object instance properties.
+    ///
+    /// The tiled crate merges template properties into the object while parsing,
+    /// so the two cannot be told apart.
+    Object,
+}
+
+/// Configuration for object property merging.
+///
+/// Insert this resource before spawning maps to customize the merge order.
+/// See the [module docs](self) for the default order.
+#[derive(Resource, Debug, Clone)]
+pub struct PropertyMergeConfig {
+    /// Sources to merge, from lowest to highest priority.
+    ///
+    /// Sources not listed are ignored entirely.
+    pub order: Vec<PropertySource>,
+
+    /// Whether to attach a [`PropertyProvenance`] component to each object.
+    ///
+    /// Useful for debugging where a value came from. Defaults to `false`.
+    pub record_provenance: bool,
+}
+
+impl Default for PropertyMergeConfig {
+    fn default() -> Self {
+        Self {
+            order: vec![
+                PropertySource::ClassDefaults,
+                PropertySource::Tile,
+                PropertySource::TileCollision,
+                PropertySource::Object,
+            ],
+            record_provenance: false,
+        }
+    }
+}
+
+/// Records which [`PropertySource`] supplied each merged property.
+///
+/// Attached to object entities when [`PropertyMergeConfig::record_provenance`] is enabled.
+/// Only top-level keys are tracked; class-typed values are attributed as a whole.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// fn debug_properties(query: Query<(&Name, &PropertyProvenance)>) {
+///     for (name, provenance) in &query {
+///         for (key, source) in provenance.iter() {
+///             info!("{}: '{}' from {:?}", name, key, source);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct PropertyProvenance {
+    sources: HashMap<String, PropertySource>,
+}
+
+impl PropertyProvenance {
+    /// Get the source that supplied a property.
+    pub fn source(&self, key: &str) -> Option<PropertySource> {
+        self.sources.get(key).copied()
+    }
+
+    /// Iterate over all keys from a given source.
+    pub fn keys_from(&self, source: PropertySource) -> impl Iterator<Item = &str> {
+        self.sources
+            .iter()
+            .filter(move |(_, s)| **s == source)
+            .map(|(key, _)| key.as_str())
+    }
+
+    /// Iterate over all `(key, source)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &PropertySource)> {
+        self.sources.iter()
+    }
+
+    /// Number of tracked properties.
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Check if no properties are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+/// Merge property sources in the configured order.
+///
+/// `available` lists the sources that exist for this object; sources missing from
+/// `available` or from `order` are skipped. Later entries in `order` override earlier ones.
+pub fn merge_property_sources(
+    order: &[PropertySource],
+    available: &[(PropertySource, &Properties)],
+) -> (Properties, PropertyProvenance) {
+    let mut merged = Properties::new();
+    let mut provenance = PropertyProvenance::default();
+
+    for source in order {
+        let Some((_, properties)) = available.iter().find(|(s, _)| s == source) else {
+            continue;
+        };
+        for (key, value) in properties.iter() {
+            merged.insert(key.clone(), value.clone());
+            provenance.sources.insert(key.clone(), *source);
+        }
+    }
+
+    (merged, provenance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tiled::PropertyValue;
+
+    fn props(entries: &[(&str, i32)]) -> Properties {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), PropertyValue::IntValue(*v)))
+            .collect()
+    }
+
+    #[test]
+    fn test_later_sources_override_earlier() {
+        let tile = props(&[("a", 1), ("b", 1)]);
+        let object = props(&[("b", 2)]);
+        let available = [
+            (PropertySource::Object, &object),
+            (PropertySource::Tile, &tile),
+        ];
+
+        let (merged, provenance) =
+            merge_property_sources(&PropertyMergeConfig::default().order, &available);
+        assert_eq!(merged.get("a"), Some(&PropertyValue::IntValue(1)));
+        assert_eq!(merged.get("b"), Some(&PropertyValue::IntValue(2)));
+        assert_eq!(provenance.source("a"), Some(PropertySource::Tile));
+        assert_eq!(provenance.source("b"), Some(PropertySource::Object));
+
+        // Reversed order lets the tile win, and unlisted sources are ignored
+        let (merged, provenance) =
+            merge_property_sources(&[PropertySource::Object, PropertySource::Tile], &available);
+        assert_eq!(merged.get("b"), Some(&PropertyValue::IntValue(1)));
+        assert_eq!(provenance.keys_from(PropertySource::Object).count(), 0);
+
+        let (merged, _) = merge_property_sources(&[PropertySource::Layer], &available);
+        assert!(merged.is_empty());
+    }
+}
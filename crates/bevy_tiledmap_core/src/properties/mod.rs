@@ -4,29 +4,59 @@
 //! - Type registry for `#[derive(TiledClass)]` components
 //! - JSON export for Tiled editor integration
 //! - Property deserialization (Phase 2)
+//! - A typed wrapper for Tiled colors
+//! - Property errors located by map, layer, object, class and field
+//! - Property serialization for writing component changes back
 //! - Merged property data (Phase 4)
+//! - Configurable merge order and provenance tracking
+//! - Remapping of the property names read by built-in features
+//! - Interning of identical merged property sets
+//! - Checking and fixing Tiled files against the registered classes
+
+use std::sync::Arc;
 
 use bevy::prelude::*;
 
+pub mod color;
 pub mod deserialize;
+pub mod error;
 pub mod export;
+pub mod intern;
+pub mod keys;
+pub mod merge;
 pub mod registry;
+pub mod serialize;
+pub mod sync;
 
+pub use color::{TiledColor, TiledColorSpace};
 pub use deserialize::{DeserializeError, FromTiledProperty, deserialize_enum_from_string};
+pub use error::{
+    PropertyErrorContext, PropertyErrorKind, TiledPropertyError, report_property_error,
+};
 pub use export::{
     TiledEnumExport, TiledMemberExport, TiledTypeExport, TiledTypeOrEnumExport, TiledValueExport,
     build_enum_export_data, build_export_data, export_all_types_with_reflection,
     export_to_tiled_project, export_types_to_json,
 };
+pub use intern::{PropertyInternStats, PropertyInterner, update_property_interner};
+pub use keys::PropertyKeyConfig;
+pub use merge::{PropertyMergeConfig, PropertyProvenance, PropertySource, merge_property_sources};
 pub use registry::{
-    TiledClassInfo, TiledClassRegistry, TiledDefaultValue, TiledEnumInfo, TiledEnumKind,
-    TiledFieldInfo, TiledTypeKind, TiledVariantInfo, TiledVariantKind,
+    DEFAULT_VARIANT_TAG, TiledClassInfo, TiledClassRegistry, TiledDefaultValue, TiledEnumInfo,
+    TiledEnumKind, TiledEnumRepr, TiledEnumStorage, TiledFieldInfo, TiledTypeKind,
+    TiledVariantInfo, TiledVariantKind, infer_untagged_variant,
 };
+pub use serialize::{ToTiledProperty, sync_class_to_properties};
+pub use sync::{ClassDrift, ClassDriftKind, ClassSync, ClassSyncError, FileDrift};
 
 /// Pre-merged properties stored as a component.
 ///
 /// This component is automatically attached to objects and layers during spawning.
 /// It contains the merged properties from templates (if applicable) and the object/layer itself.
+/// For objects, the sources and their priority are controlled by [`PropertyMergeConfig`].
+///
+/// Objects with identical properties share one copy (see [`intern`]); mutating
+/// methods copy the set first, so they only affect this entity.
 ///
 /// # Use Cases
 ///
@@ -48,15 +78,25 @@ pub use registry::{
 #[derive(Component, Debug, Clone /*, Reflect */)]
 // #[reflect(Component)] // TODO: Reflect can't work on tiled::Properties
 pub struct MergedProperties {
-    properties: tiled::Properties,
+    properties: Arc<tiled::Properties>,
 }
 
 impl MergedProperties {
     /// Create a new `MergedProperties` from a Properties map.
     pub fn new(properties: tiled::Properties) -> Self {
+        Self::from_shared(Arc::new(properties))
+    }
+
+    /// Create a new `MergedProperties` sharing an interned Properties map.
+    pub fn from_shared(properties: Arc<tiled::Properties>) -> Self {
         Self { properties }
     }
 
+    /// Check whether these properties share their storage with `other`.
+    pub fn shares_storage_with(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.properties, &other.properties)
+    }
+
     /// Get a property value by key.
     pub fn get(&self, key: &str) -> Option<&tiled::PropertyValue> {
         self.properties.get(key)
@@ -96,9 +136,9 @@ impl MergedProperties {
     }
 
     /// Get a color property value.
-    pub fn get_color(&self, key: &str) -> Option<tiled::Color> {
+    pub fn get_color(&self, key: &str) -> Option<TiledColor> {
         match self.get(key)? {
-            tiled::PropertyValue::ColorValue(c) => Some(*c),
+            tiled::PropertyValue::ColorValue(c) => Some(TiledColor(*c)),
             _ => None,
         }
     }
@@ -117,4 +157,48 @@ impl MergedProperties {
     pub fn is_empty(&self) -> bool {
         self.properties.is_empty()
     }
+
+    /// Set a property value, returning the previous value if any.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: tiled::PropertyValue,
+    ) -> Option<tiled::PropertyValue> {
+        Arc::make_mut(&mut self.properties).insert(key.into(), value)
+    }
+
+    /// Remove a property, returning its value if it was present.
+    pub fn remove(&mut self, key: &str) -> Option<tiled::PropertyValue> {
+        Arc::make_mut(&mut self.properties).remove(key)
+    }
+
+    /// Get the underlying Tiled properties.
+    pub fn properties(&self) -> &tiled::Properties {
+        &self.properties
+    }
+
+    /// Write serialized class members back to these properties.
+    ///
+    /// If any property holds a class value of `class_name`, its members are
+    /// replaced. Otherwise the members are merged at the top level, as for
+    /// an entity whose own class is `class_name`.
+    pub fn write_class(&mut self, class_name: &str, members: tiled::Properties) {
+        let properties_mut = Arc::make_mut(&mut self.properties);
+        let mut nested = false;
+        for value in properties_mut.values_mut() {
+            if let tiled::PropertyValue::ClassValue {
+                property_type,
+                properties,
+            } = value
+                && property_type == class_name
+            {
+                properties.clone_from(&members);
+                nested = true;
+            }
+        }
+
+        if !nested {
+            properties_mut.extend(members);
+        }
+    }
 }
@@ -9,6 +9,8 @@ use std::collections::HashMap;
 use std::string::String;
 use tiled::{Properties, PropertyValue};
 
+use super::color::TiledColor;
+
 /// Tiled property type kind.
 ///
 /// Represents the type system used by Tiled for custom properties.
@@ -29,6 +31,8 @@ pub enum TiledTypeKind {
     ///
     /// When deserialized, this triggers asset loading via `AssetServer`.
     File,
+    /// Object reference type (`TiledObjectRef` fields)
+    Object,
     /// Class type (custom type with properties)
     ///
     /// The `property_type` field contains the full type path (e.g., "`glam::Vec2`", "`game::Door`")
@@ -42,6 +46,34 @@ pub enum TiledTypeKind {
     },
 }
 
+impl TiledTypeKind {
+    /// The type name Tiled uses for this kind (`"bool"`, `"int"`, `"class"`, ...).
+    ///
+    /// Enums are stored as strings in Tiled, so `Enum` reports `"string"`.
+    pub fn tiled_type_name(&self) -> &'static str {
+        match self {
+            TiledTypeKind::Bool => "bool",
+            TiledTypeKind::Int => "int",
+            TiledTypeKind::Float => "float",
+            TiledTypeKind::String | TiledTypeKind::Enum { .. } => "string",
+            TiledTypeKind::Color => "color",
+            TiledTypeKind::File => "file",
+            TiledTypeKind::Object => "object",
+            TiledTypeKind::Class { .. } => "class",
+        }
+    }
+
+    /// The custom type name for class and enum kinds (Tiled's `propertyType`).
+    pub fn property_type(&self) -> Option<&'static str> {
+        match self {
+            TiledTypeKind::Class { property_type } | TiledTypeKind::Enum { property_type, .. } => {
+                Some(property_type)
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Default value for a Tiled class field.
 ///
 /// Represents the default value in a format that can be exported to Tiled's JSON.
@@ -54,6 +86,21 @@ pub enum TiledDefaultValue {
     Color { r: u8, g: u8, b: u8, a: u8 },
 }
 
+impl TiledDefaultValue {
+    /// Convert to the `PropertyValue` Tiled would store for this default.
+    pub fn to_property_value(&self) -> PropertyValue {
+        match self {
+            TiledDefaultValue::Bool(b) => PropertyValue::BoolValue(*b),
+            TiledDefaultValue::Int(i) => PropertyValue::IntValue(*i),
+            TiledDefaultValue::Float(f) => PropertyValue::FloatValue(*f),
+            TiledDefaultValue::String(s) => PropertyValue::StringValue(s.to_string()),
+            TiledDefaultValue::Color { r, g, b, a } => {
+                PropertyValue::ColorValue(TiledColor::rgba(*r, *g, *b, *a).0)
+            }
+        }
+    }
+}
+
 /// Information about a single field in a `TiledClass`.
 ///
 /// Used for JSON export to provide autocomplete in Tiled editor.
@@ -67,6 +114,26 @@ pub struct TiledFieldInfo {
 
     /// Default value for this field
     pub default_value: TiledDefaultValue,
+
+    /// Smallest allowed value of a numeric field (`#[tiled(min = ...)]`)
+    pub min: Option<f64>,
+
+    /// Largest allowed value of a numeric field (`#[tiled(max = ...)]`)
+    pub max: Option<f64>,
+}
+
+impl TiledFieldInfo {
+    /// Check whether a value lies within the field's `min`/`max`.
+    ///
+    /// Non-numeric values are always in range.
+    pub fn in_range(&self, value: &PropertyValue) -> bool {
+        let value = match value {
+            PropertyValue::IntValue(value) => f64::from(*value),
+            PropertyValue::FloatValue(value) => f64::from(*value),
+            _ => return true,
+        };
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
 }
 
 /// Kind of enum variant (unit, struct, or tuple).
@@ -95,6 +162,89 @@ pub struct TiledVariantInfo {
     pub is_default: bool,
 }
 
+impl TiledVariantInfo {
+    /// Get the fields of this variant (empty for unit variants).
+    pub fn fields(&self) -> &'static [TiledFieldInfo] {
+        match self.kind {
+            TiledVariantKind::Unit => &[],
+            TiledVariantKind::Struct { fields } | TiledVariantKind::Tuple { fields } => fields,
+        }
+    }
+}
+
+/// Infer the variant of an untagged complex enum from the members present in a class value.
+///
+/// Picks the variant with the fewest fields among those having every present
+/// member, preferring the `#[default]` variant and then declaration order on ties.
+/// Members that aren't a field of any variant are ignored.
+///
+/// Returns `None` if no variant has all present members.
+pub fn infer_untagged_variant(
+    variants: &[TiledVariantInfo],
+    properties: &Properties,
+) -> Option<&'static str> {
+    let is_field = |name: &str| {
+        variants
+            .iter()
+            .any(|v| v.fields().iter().any(|f| f.name == name))
+    };
+    let present: Vec<&str> = properties
+        .keys()
+        .map(String::as_str)
+        .filter(|name| is_field(name))
+        .collect();
+
+    variants
+        .iter()
+        .enumerate()
+        .filter(|(_, variant)| {
+            present
+                .iter()
+                .all(|name| variant.fields().iter().any(|f| f.name == *name))
+        })
+        .min_by_key(|(index, variant)| (variant.fields().len(), !variant.is_default, *index))
+        .map(|(_, variant)| variant.name)
+}
+
+/// How a simple enum's values are stored in Tiled (the enum's `storageType`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TiledEnumStorage {
+    /// Values are the variant names (`StringValue`).
+    #[default]
+    String,
+    /// Values are the variant indices (`IntValue`), set with `#[tiled(storage = "int")]`.
+    Int,
+}
+
+impl TiledEnumStorage {
+    /// The `storageType` Tiled uses for this storage (`"string"` or `"int"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TiledEnumStorage::String => "string",
+            TiledEnumStorage::Int => "int",
+        }
+    }
+}
+
+/// Default name of the discriminant field of complex enums.
+pub const DEFAULT_VARIANT_TAG: &str = ":variant";
+
+/// How the variant of a complex enum is represented in Tiled, set with
+/// `#[tiled(repr = "...")]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TiledEnumRepr {
+    /// One class with a discriminant member (the tag) naming the variant, plus the
+    /// fields of all variants (`"tagged"`).
+    #[default]
+    Tagged,
+    /// One class with the fields of all variants; the variant is inferred from the
+    /// members that are set (`"untagged"`, see [`infer_untagged_variant`]).
+    Untagged,
+    /// One class per variant, named `EnumName::Variant`; the variant is the class
+    /// of the value (`"class"`).
+    ClassName,
+}
+
 /// Kind of enum (simple unit-variant or complex with struct/tuple variants).
 #[derive(Debug, Clone)]
 pub enum TiledEnumKind {
@@ -104,14 +254,22 @@ pub enum TiledEnumKind {
     Simple {
         /// List of variant names
         variants: &'static [&'static str],
-        /// Function to deserialize a string variant name into this enum type
+        /// How values are stored in Tiled properties
+        storage: TiledEnumStorage,
+        /// Function to deserialize a variant name (or index, e.g. `"2"`) into this enum type
         from_string: fn(&str) -> Result<Box<dyn Reflect>, String>,
     },
 
     /// Complex enum with struct and/or tuple variants (e.g., `Attack { None, Melee { damage: i32 } }`)
     ///
-    /// Exported as Tiled class type with `:variant` discriminant field.
+    /// Exported as Tiled class type(s) according to its [`TiledEnumRepr`]. Tagged enums
+    /// have a discriminant field ([`DEFAULT_VARIANT_TAG`] unless set with
+    /// `#[tiled(tag = "...")]`).
     Complex {
+        /// How the variant is represented
+        repr: TiledEnumRepr,
+        /// Name of the discriminant field holding the variant name (tagged enums)
+        tag: &'static str,
         /// Information about each variant
         variant_info: &'static [TiledVariantInfo],
     },
@@ -142,11 +300,81 @@ pub struct TiledClassInfo {
     ///
     /// Returns a boxed reflected component or an error message.
     pub from_properties: fn(&Properties, Option<&AssetServer>) -> Result<Box<dyn Reflect>, String>,
+
+    /// Function to serialize a component of this type back into Tiled properties.
+    ///
+    /// Returns `None` if the value is not of this type. Skipped fields and fields
+    /// with a custom `#[tiled(with = "...")]` parser are left out.
+    pub to_properties: fn(&dyn Reflect) -> Option<Properties>,
+
+    /// Function inserting the components listed in `#[tiled(requires(...))]` at their
+    /// defaults, keeping any the entity already has.
+    ///
+    /// Called after the component itself is inserted. Bevy's own required components
+    /// (`#[require(...)]`) are inserted by Bevy and don't need this.
+    pub insert_requires: fn(&mut EntityWorldMut),
+
+    /// Function listing the fields whose values in the given properties are set
+    /// but can't be parsed (wrong type, unknown enum variant, ...).
+    ///
+    /// Deserialization keeps the defaults of such fields.
+    pub invalid_fields: fn(&Properties) -> Vec<&'static str>,
 }
 
 // Collect all TiledClassInfo submissions at compile time
 inventory::collect!(TiledClassInfo);
 
+impl TiledClassInfo {
+    /// Get field information by name.
+    pub fn field(&self, name: &str) -> Option<&'static TiledFieldInfo> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Iterate the fields whose values in `properties` are outside their range.
+    pub fn out_of_range<'p>(
+        &self,
+        properties: &'p Properties,
+    ) -> impl Iterator<Item = (&'static TiledFieldInfo, &'p PropertyValue)> {
+        self.fields.iter().filter_map(|field| {
+            let value = properties.get(field.name)?;
+            (!field.in_range(value)).then_some((field, value))
+        })
+    }
+
+    /// Iterate field names in declaration order.
+    pub fn field_names(&self) -> impl Iterator<Item = &'static str> {
+        self.fields.iter().map(|field| field.name)
+    }
+
+    /// Build the properties an untouched instance of this class would have.
+    ///
+    /// Each primitive, enum and file field gets its default value. Enum fields
+    /// without a default use their first variant. Class-typed fields are left
+    /// out, so optional nested classes stay absent.
+    pub fn default_properties(&self) -> Properties {
+        self.fields
+            .iter()
+            .filter_map(|field| {
+                let value = match &field.tiled_type {
+                    TiledTypeKind::Class { .. } => return None,
+                    TiledTypeKind::File => PropertyValue::FileValue(String::new()),
+                    TiledTypeKind::Object => PropertyValue::ObjectValue(0),
+                    TiledTypeKind::Enum { variants, .. } => match &field.default_value {
+                        TiledDefaultValue::String(s) if !s.is_empty() => {
+                            PropertyValue::StringValue(s.to_string())
+                        }
+                        _ => PropertyValue::StringValue(
+                            variants.first().copied().unwrap_or_default().to_string(),
+                        ),
+                    },
+                    _ => field.default_value.to_property_value(),
+                };
+                Some((field.name.to_string(), value))
+            })
+            .collect()
+    }
+}
+
 /// Information about a registered `TiledClass` enum type.
 ///
 /// This struct is submitted via `inventory::submit!` by the `TiledClass` derive macro
@@ -168,8 +396,9 @@ pub struct TiledEnumInfo {
 
     /// Function to deserialize a property value into this enum type
     ///
-    /// For simple enums, accepts `StringValue` with variant name.
-    /// For complex enums, accepts `ClassValue` with `:variant` discriminant field.
+    /// For simple enums, accepts `StringValue` with variant name or `IntValue` with
+    /// variant index, whatever the enum's storage.
+    /// For complex enums, accepts `ClassValue` with the discriminant field ([`TiledEnumInfo::tag`]).
     /// Returns a boxed reflected enum or an error message.
     pub from_property: fn(&PropertyValue) -> Result<Box<dyn Reflect>, String>,
 }
@@ -184,12 +413,76 @@ impl TiledEnumInfo {
     pub fn variant_names(&self) -> Vec<&'static str> {
         match &self.kind {
             TiledEnumKind::Simple { variants, .. } => variants.to_vec(),
-            TiledEnumKind::Complex { variant_info } => {
+            TiledEnumKind::Complex { variant_info, .. } => {
                 variant_info.iter().map(|v| v.name).collect()
             }
         }
     }
 
+    /// Get how values of this enum are stored in Tiled.
+    ///
+    /// Complex enums are classes, and always report [`TiledEnumStorage::String`].
+    pub fn storage(&self) -> TiledEnumStorage {
+        match &self.kind {
+            TiledEnumKind::Simple { storage, .. } => *storage,
+            TiledEnumKind::Complex { .. } => TiledEnumStorage::String,
+        }
+    }
+
+    /// Get the name of the discriminant field of tagged complex enums.
+    ///
+    /// Returns `None` if this is a simple enum, or a complex enum that isn't
+    /// [`TiledEnumRepr::Tagged`].
+    pub fn tag(&self) -> Option<&'static str> {
+        match &self.kind {
+            TiledEnumKind::Complex {
+                repr: TiledEnumRepr::Tagged,
+                tag,
+                ..
+            } => Some(tag),
+            _ => None,
+        }
+    }
+
+    /// Get how the variant of complex enums is represented.
+    ///
+    /// Returns `None` if this is a simple enum.
+    pub fn repr(&self) -> Option<TiledEnumRepr> {
+        match &self.kind {
+            TiledEnumKind::Simple { .. } => None,
+            TiledEnumKind::Complex { repr, .. } => Some(*repr),
+        }
+    }
+
+    /// Get the class name of a variant of a [`TiledEnumRepr::ClassName`] enum
+    /// (`"EnumName::Variant"`).
+    pub fn variant_class_name(&self, variant: &str) -> String {
+        format!("{}::{}", self.name, variant)
+    }
+
+    /// Get the class that fields holding this enum are exported with: the enum's own
+    /// class, or for [`TiledEnumRepr::ClassName`] enums the class of the default (or
+    /// first) variant.
+    ///
+    /// Returns `None` if this is a simple enum.
+    pub fn member_class_name(&self) -> Option<String> {
+        match self.repr()? {
+            TiledEnumRepr::Tagged | TiledEnumRepr::Untagged => Some(self.name.to_string()),
+            TiledEnumRepr::ClassName => {
+                let variant = self
+                    .default_variant_name()
+                    .or_else(|| self.variant_names().first().copied())?;
+                Some(self.variant_class_name(variant))
+            }
+        }
+    }
+
+    /// Get the name of the synthetic enum listing the variants of complex enums
+    /// (`"EnumName:::variant"`), independent of the [tag](Self::tag).
+    pub fn variant_enum_name(&self) -> String {
+        format!("{}:::variant", self.name)
+    }
+
     /// Check if this is a simple (unit-variant only) enum.
     pub fn is_simple(&self) -> bool {
         matches!(self.kind, TiledEnumKind::Simple { .. })
@@ -206,7 +499,9 @@ impl TiledEnumInfo {
     pub fn get_variant(&self, name: &str) -> Option<&TiledVariantInfo> {
         match &self.kind {
             TiledEnumKind::Simple { .. } => None,
-            TiledEnumKind::Complex { variant_info } => variant_info.iter().find(|v| v.name == name),
+            TiledEnumKind::Complex { variant_info, .. } => {
+                variant_info.iter().find(|v| v.name == name)
+            }
         }
     }
 
@@ -216,7 +511,7 @@ impl TiledEnumInfo {
     pub fn default_variant_name(&self) -> Option<&'static str> {
         match &self.kind {
             TiledEnumKind::Simple { .. } => None,
-            TiledEnumKind::Complex { variant_info } => {
+            TiledEnumKind::Complex { variant_info, .. } => {
                 variant_info.iter().find(|v| v.is_default).map(|v| v.name)
             }
         }
@@ -228,7 +523,7 @@ impl TiledEnumInfo {
     pub fn variant_info(&self) -> Option<&[TiledVariantInfo]> {
         match &self.kind {
             TiledEnumKind::Simple { .. } => None,
-            TiledEnumKind::Complex { variant_info } => Some(variant_info),
+            TiledEnumKind::Complex { variant_info, .. } => Some(variant_info),
         }
     }
 
@@ -261,6 +556,8 @@ impl TiledEnumInfo {
 pub struct TiledClassRegistry {
     by_name: HashMap<String, &'static TiledClassInfo>,
     enums_by_name: HashMap<String, &'static TiledEnumInfo>,
+    /// Variant classes of `TiledEnumRepr::ClassName` enums, with the variant name
+    variant_classes: HashMap<String, (&'static TiledEnumInfo, &'static str)>,
 }
 
 impl TiledClassRegistry {
@@ -275,8 +572,14 @@ impl TiledClassRegistry {
             by_name.insert(info.name.to_string(), info);
         }
 
+        let mut variant_classes = HashMap::new();
         for info in inventory::iter::<TiledEnumInfo> {
             enums_by_name.insert(info.name.to_string(), info);
+            if info.repr() == Some(TiledEnumRepr::ClassName) {
+                for variant in info.variant_names() {
+                    variant_classes.insert(info.variant_class_name(variant), (info, variant));
+                }
+            }
         }
 
         info!(
@@ -288,6 +591,7 @@ impl TiledClassRegistry {
         Self {
             by_name,
             enums_by_name,
+            variant_classes,
         }
     }
 
@@ -314,6 +618,36 @@ impl TiledClassRegistry {
         self.by_name.values().copied()
     }
 
+    /// Get all registered class info, sorted by name.
+    ///
+    /// Useful for building stable UI lists (e.g. an in-game property panel).
+    pub fn sorted(&self) -> Vec<&'static TiledClassInfo> {
+        let mut infos: Vec<_> = self.iter().collect();
+        infos.sort_by_key(|info| info.name);
+        infos
+    }
+
+    /// Get type information by Rust `TypeId`.
+    ///
+    /// Use this to find the Tiled class of a component already on an entity.
+    pub fn get_by_type_id(&self, type_id: TypeId) -> Option<&'static TiledClassInfo> {
+        self.iter().find(|info| info.type_id == type_id)
+    }
+
+    /// Get type information for a Rust type.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let door_info = registry.get_for::<Door>().unwrap();
+    /// for field in door_info.fields {
+    ///     info!("{}: {}", field.name, field.tiled_type.tiled_type_name());
+    /// }
+    /// ```
+    pub fn get_for<T: 'static>(&self) -> Option<&'static TiledClassInfo> {
+        self.get_by_type_id(TypeId::of::<T>())
+    }
+
     /// Get the number of registered types.
     pub fn len(&self) -> usize {
         self.by_name.len()
@@ -337,6 +671,21 @@ impl TiledClassRegistry {
         self.enums_by_name.get(name).copied()
     }
 
+    /// Get the complex enum deserializing class values of class `name`.
+    ///
+    /// This is the enum named `name`, or for [`TiledEnumRepr::ClassName`] enums the
+    /// enum one of whose variant classes is `name`.
+    pub fn get_enum_for_class(&self, name: &str) -> Option<&'static TiledEnumInfo> {
+        self.get_enum(name)
+            .filter(|info| info.is_complex())
+            .or_else(|| self.get_variant_class(name).map(|(info, _)| info))
+    }
+
+    /// Get the enum and variant name of a [`TiledEnumRepr::ClassName`] variant class.
+    pub fn get_variant_class(&self, name: &str) -> Option<(&'static TiledEnumInfo, &'static str)> {
+        self.variant_classes.get(name).copied()
+    }
+
     /// Iterate all registered enum names.
     pub fn enum_names(&self) -> impl Iterator<Item = &str> {
         self.enums_by_name.keys().map(String::as_str)
@@ -351,4 +700,280 @@ impl TiledClassRegistry {
     pub fn enum_len(&self) -> usize {
         self.enums_by_name.len()
     }
+
+    /// Get all registered enum info, sorted by name.
+    pub fn sorted_enums(&self) -> Vec<&'static TiledEnumInfo> {
+        let mut infos: Vec<_> = self.iter_enums().collect();
+        infos.sort_by_key(|info| info.name);
+        infos
+    }
+
+    /// Get enum information by Rust `TypeId`.
+    pub fn get_enum_by_type_id(&self, type_id: TypeId) -> Option<&'static TiledEnumInfo> {
+        self.iter_enums().find(|info| info.type_id == type_id)
+    }
+
+    /// Get enum information for a Rust type.
+    pub fn get_enum_for<T: 'static>(&self) -> Option<&'static TiledEnumInfo> {
+        self.get_enum_by_type_id(TypeId::of::<T>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_tiledmap_macros::TiledClass;
+
+    fn no_deserialize(_: &Properties, _: Option<&AssetServer>) -> Result<Box<dyn Reflect>, String> {
+        Err("unused".to_string())
+    }
+
+    static FIELDS: &[TiledFieldInfo] = &[
+        TiledFieldInfo {
+            name: "locked",
+            tiled_type: TiledTypeKind::Bool,
+            default_value: TiledDefaultValue::Bool(true),
+            min: None,
+            max: None,
+        },
+        TiledFieldInfo {
+            name: "facing",
+            tiled_type: TiledTypeKind::Enum {
+                property_type: "game::Direction",
+                variants: &["North", "South"],
+            },
+            default_value: TiledDefaultValue::String(""),
+            min: None,
+            max: None,
+        },
+        TiledFieldInfo {
+            name: "hinge",
+            tiled_type: TiledTypeKind::Class {
+                property_type: "game::Hinge",
+            },
+            default_value: TiledDefaultValue::String(""),
+            min: None,
+            max: None,
+        },
+        TiledFieldInfo {
+            name: "weight",
+            tiled_type: TiledTypeKind::Float,
+            default_value: TiledDefaultValue::Float(10.0),
+            min: Some(0.0),
+            max: Some(100.0),
+        },
+    ];
+
+    static DOOR: TiledClassInfo = TiledClassInfo {
+        type_id: TypeId::of::<()>(),
+        name: "game::Door",
+        fields: FIELDS,
+        from_properties: no_deserialize,
+        to_properties: |_| None,
+        insert_requires: |_| {},
+        invalid_fields: |_| Vec::new(),
+    };
+
+    #[test]
+    fn test_class_info_introspection() {
+        let facing = DOOR.field("facing").unwrap();
+        assert_eq!(facing.tiled_type.tiled_type_name(), "string");
+        assert_eq!(facing.tiled_type.property_type(), Some("game::Direction"));
+        assert!(DOOR.field("missing").is_none());
+        assert_eq!(
+            DOOR.field_names().collect::<Vec<_>>(),
+            ["locked", "facing", "hinge", "weight"]
+        );
+
+        let defaults = DOOR.default_properties();
+        assert_eq!(
+            defaults.get("locked"),
+            Some(&PropertyValue::BoolValue(true))
+        );
+        assert_eq!(
+            defaults.get("facing"),
+            Some(&PropertyValue::StringValue("North".to_string()))
+        );
+        assert!(!defaults.contains_key("hinge"));
+    }
+
+    #[derive(Component, Reflect, TiledClass, Default)]
+    #[tiled(name = "registry_test::Lamp")]
+    struct Lamp {
+        #[tiled(default = 5, min = 1, max = 10)]
+        level: i32,
+        #[tiled(min = -0.5)]
+        flicker: f32,
+    }
+
+    #[test]
+    fn test_derive_field_ranges() {
+        let registry = TiledClassRegistry::build();
+        let lamp = registry.get_for::<Lamp>().unwrap();
+
+        let level = lamp.field("level").unwrap();
+        assert_eq!((level.min, level.max), (Some(1.0), Some(10.0)));
+        assert!(matches!(level.default_value, TiledDefaultValue::Int(5)));
+        let flicker = lamp.field("flicker").unwrap();
+        assert_eq!((flicker.min, flicker.max), (Some(-0.5), None));
+    }
+
+    #[derive(Component, Reflect, TiledClass, Debug, Default, PartialEq)]
+    #[tiled(name = "registry_test::Dial")]
+    struct Dial {
+        #[tiled(with = "parse_number::<u8>", default = 5, min = 1, max = 10)]
+        level: u8,
+        #[tiled(with = "parse_number::<f32>", default = -2.5)]
+        offset: f32,
+    }
+
+    fn parse_number<T: std::str::FromStr>(value: &PropertyValue) -> Option<T> {
+        let PropertyValue::StringValue(s) = value else {
+            return None;
+        };
+        s.trim().parse().ok()
+    }
+
+    #[test]
+    fn test_derive_with_default() {
+        let registry = TiledClassRegistry::build();
+        let dial = registry.get_for::<Dial>().unwrap();
+
+        // `with` fields are strings in Tiled, defaults included
+        let level = dial.field("level").unwrap();
+        assert!(matches!(level.tiled_type, TiledTypeKind::String));
+        assert!(matches!(
+            level.default_value,
+            TiledDefaultValue::String("5")
+        ));
+        assert_eq!((level.min, level.max), (Some(1.0), Some(10.0)));
+        let offset = dial.field("offset").unwrap();
+        assert!(matches!(
+            offset.default_value,
+            TiledDefaultValue::String("-2.5")
+        ));
+
+        let mut properties = Properties::new();
+        properties.insert("level".to_string(), level.default_value.to_property_value());
+        properties.insert(
+            "offset".to_string(),
+            offset.default_value.to_property_value(),
+        );
+        let parsed = (dial.from_properties)(&properties, None).unwrap();
+        assert_eq!(
+            parsed.downcast_ref::<Dial>(),
+            Some(&Dial {
+                level: 5,
+                offset: -2.5
+            })
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_fields() {
+        let mut properties = Properties::new();
+        properties.insert("locked".to_string(), PropertyValue::BoolValue(false));
+        properties.insert("weight".to_string(), PropertyValue::IntValue(100));
+        assert_eq!(DOOR.out_of_range(&properties).count(), 0);
+
+        properties.insert("weight".to_string(), PropertyValue::FloatValue(-0.5));
+        let out_of_range: Vec<_> = DOOR
+            .out_of_range(&properties)
+            .map(|(field, _)| field.name)
+            .collect();
+        assert_eq!(out_of_range, ["weight"]);
+    }
+
+    #[test]
+    fn test_infer_untagged_variant() {
+        static LOCKED: &[TiledFieldInfo] = FIELDS.split_at(1).0;
+        static HINGE_AND_WEIGHT: &[TiledFieldInfo] = FIELDS.split_at(2).1;
+        let variants = [
+            TiledVariantInfo {
+                name: "Fixed",
+                kind: TiledVariantKind::Unit,
+                is_default: false,
+            },
+            TiledVariantInfo {
+                name: "Hinged",
+                kind: TiledVariantKind::Struct {
+                    fields: HINGE_AND_WEIGHT,
+                },
+                is_default: false,
+            },
+            TiledVariantInfo {
+                name: "Locked",
+                kind: TiledVariantKind::Tuple { fields: LOCKED },
+                is_default: false,
+            },
+        ];
+        let infer = |members: &[&str]| {
+            let properties: Properties = members
+                .iter()
+                .map(|name| (name.to_string(), PropertyValue::BoolValue(true)))
+                .collect();
+            infer_untagged_variant(&variants, &properties)
+        };
+
+        assert_eq!(infer(&[]), Some("Fixed"));
+        assert_eq!(infer(&["weight"]), Some("Hinged"));
+        assert_eq!(infer(&["locked"]), Some("Locked"));
+        // Unknown members are ignored, fields of different variants match none
+        assert_eq!(infer(&["hinge", "unrelated"]), Some("Hinged"));
+        assert_eq!(infer(&["locked", "weight"]), None);
+    }
+
+    #[derive(Reflect, TiledClass, Debug, Clone, Copy, PartialEq, Default)]
+    #[tiled(name = "registry_test::Brightness")]
+    #[tiled(storage = "int")]
+    enum Brightness {
+        #[default]
+        Dim,
+        Bright,
+    }
+
+    #[test]
+    fn test_int_storage_enum() {
+        use crate::properties::{FromTiledProperty, ToTiledProperty};
+
+        let registry = TiledClassRegistry::build();
+        let info = registry.get_enum_for::<Brightness>().unwrap();
+        assert_eq!(info.storage(), TiledEnumStorage::Int);
+
+        // Both storage types are read, values are written as the index
+        assert_eq!(
+            Brightness::from_property(&PropertyValue::IntValue(1)),
+            Some(Brightness::Bright)
+        );
+        assert_eq!(
+            Brightness::from_property(&PropertyValue::StringValue("Bright".to_string())),
+            Some(Brightness::Bright)
+        );
+        assert_eq!(Brightness::from_property(&PropertyValue::IntValue(2)), None);
+        assert_eq!(
+            Brightness::Bright.to_property(),
+            Some(PropertyValue::IntValue(1))
+        );
+
+        let from_property = |value| {
+            (info.from_property)(&value)
+                .ok()
+                .and_then(|value| value.downcast_ref::<Brightness>().copied())
+        };
+        assert_eq!(
+            from_property(PropertyValue::IntValue(0)),
+            Some(Brightness::Dim)
+        );
+        assert!((info.from_property)(&PropertyValue::IntValue(-1)).is_err());
+
+        let from_string = info.from_string_fn().unwrap();
+        for s in ["Bright", "1"] {
+            let value = from_string(s).unwrap();
+            assert_eq!(
+                value.downcast_ref::<Brightness>(),
+                Some(&Brightness::Bright)
+            );
+        }
+        assert!(from_string("2").is_err());
+    }
 }
@@ -0,0 +1,258 @@
+//! Property serialization helpers.
+//!
+//! Provides the `ToTiledProperty` trait, the inverse of
+//! [`FromTiledProperty`](super::FromTiledProperty), for writing component
+//! changes back to Tiled properties (e.g. from an in-game editor).
+
+use bevy::prelude::*;
+use tiled::PropertyValue;
+
+use super::{MergedProperties, TiledClassRegistry, TiledColor};
+use crate::components::TiledObjectRef;
+
+/// Trait for types that can be serialized to Tiled properties.
+///
+/// This trait is automatically implemented by the `#[derive(TiledClass)]` macro,
+/// and used by the generated `to_properties` function registered in
+/// [`TiledClassInfo`](super::TiledClassInfo).
+///
+/// # Example
+///
+/// ```ignore
+/// use bevy_tiledmap_core::properties::ToTiledProperty;
+///
+/// assert_eq!(true.to_property(), Some(PropertyValue::BoolValue(true)));
+/// assert_eq!(None::<i32>.to_property(), None);
+/// ```
+pub trait ToTiledProperty {
+    /// Convert this value to a Tiled property value.
+    ///
+    /// Returns `None` if the property should be left out (e.g. `Option::None`,
+    /// or a `Handle<T>` without an asset path).
+    fn to_property(&self) -> Option<PropertyValue>;
+}
+
+// Primitive type implementations
+
+impl ToTiledProperty for bool {
+    fn to_property(&self) -> Option<PropertyValue> {
+        Some(PropertyValue::BoolValue(*self))
+    }
+}
+
+impl ToTiledProperty for i32 {
+    fn to_property(&self) -> Option<PropertyValue> {
+        Some(PropertyValue::IntValue(*self))
+    }
+}
+
+impl ToTiledProperty for u32 {
+    fn to_property(&self) -> Option<PropertyValue> {
+        i32::try_from(*self).ok().map(PropertyValue::IntValue)
+    }
+}
+
+impl ToTiledProperty for f32 {
+    fn to_property(&self) -> Option<PropertyValue> {
+        Some(PropertyValue::FloatValue(*self))
+    }
+}
+
+impl ToTiledProperty for f64 {
+    fn to_property(&self) -> Option<PropertyValue> {
+        Some(PropertyValue::FloatValue(*self as f32))
+    }
+}
+
+impl ToTiledProperty for String {
+    fn to_property(&self) -> Option<PropertyValue> {
+        Some(PropertyValue::StringValue(self.clone()))
+    }
+}
+
+impl ToTiledProperty for TiledObjectRef {
+    fn to_property(&self) -> Option<PropertyValue> {
+        Some(PropertyValue::ObjectValue(self.0))
+    }
+}
+
+// Bevy type implementations
+
+impl ToTiledProperty for Color {
+    fn to_property(&self) -> Option<PropertyValue> {
+        TiledColor::from(*self).to_property()
+    }
+}
+
+impl ToTiledProperty for TiledColor {
+    fn to_property(&self) -> Option<PropertyValue> {
+        Some(PropertyValue::ColorValue(self.0))
+    }
+}
+
+impl ToTiledProperty for Vec2 {
+    fn to_property(&self) -> Option<PropertyValue> {
+        Some(PropertyValue::StringValue(format!("{},{}", self.x, self.y)))
+    }
+}
+
+impl ToTiledProperty for Vec3 {
+    fn to_property(&self) -> Option<PropertyValue> {
+        Some(PropertyValue::StringValue(format!(
+            "{},{},{}",
+            self.x, self.y, self.z
+        )))
+    }
+}
+
+impl ToTiledProperty for IVec2 {
+    fn to_property(&self) -> Option<PropertyValue> {
+        Some(PropertyValue::StringValue(format!("{},{}", self.x, self.y)))
+    }
+}
+
+impl<A: Asset> ToTiledProperty for Handle<A> {
+    fn to_property(&self) -> Option<PropertyValue> {
+        self.path()
+            .map(|path| PropertyValue::FileValue(path.to_string()))
+    }
+}
+
+// Array and tuple implementations
+//
+// Written as a class with indexed members, named like the derive macro names
+// these types (e.g. "[f32; 3]", "(f32, String)").
+
+/// Short type name used in array and tuple class names (`"alloc::string::String"` -> `"String"`).
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Build an indexed class value from elements in order.
+fn indexed_class(property_type: String, elements: &[Option<PropertyValue>]) -> PropertyValue {
+    let properties = elements
+        .iter()
+        .enumerate()
+        .filter_map(|(i, value)| Some((i.to_string(), value.clone()?)))
+        .collect();
+    PropertyValue::ClassValue {
+        property_type,
+        properties,
+    }
+}
+
+impl<T: ToTiledProperty, const N: usize> ToTiledProperty for [T; N] {
+    fn to_property(&self) -> Option<PropertyValue> {
+        let elements: Vec<_> = self.iter().map(ToTiledProperty::to_property).collect();
+        Some(indexed_class(
+            format!("[{}; {}]", short_type_name::<T>(), N),
+            &elements,
+        ))
+    }
+}
+
+macro_rules! impl_tuple_to_tiled_property {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name: ToTiledProperty),+> ToTiledProperty for ($($name,)+) {
+            fn to_property(&self) -> Option<PropertyValue> {
+                let names = [$(short_type_name::<$name>()),+];
+                Some(indexed_class(
+                    format!("({})", names.join(", ")),
+                    &[$(self.$index.to_property()),+],
+                ))
+            }
+        }
+    };
+}
+
+impl_tuple_to_tiled_property!(A 0, B 1);
+impl_tuple_to_tiled_property!(A 0, B 1, C 2);
+impl_tuple_to_tiled_property!(A 0, B 1, C 2, D 3);
+
+// Option<T> implementation
+impl<T: ToTiledProperty> ToTiledProperty for Option<T> {
+    fn to_property(&self) -> Option<PropertyValue> {
+        self.as_ref().and_then(ToTiledProperty::to_property)
+    }
+}
+
+/// Write changed `TiledClass` components back to their entity's [`MergedProperties`].
+///
+/// Components attached from the entity's own class replace the matching
+/// top-level properties. Components attached from a class-typed property
+/// replace every property holding that class.
+///
+/// Not added by default; register it for each component an editor may change:
+///
+/// ```rust,ignore
+/// app.add_systems(PostUpdate, sync_class_to_properties::<Door>);
+/// ```
+pub fn sync_class_to_properties<T: Component + Reflect>(
+    mut query: Query<(&T, &mut MergedProperties), Changed<T>>,
+    registry: Res<TiledClassRegistry>,
+) {
+    let Some(info) = registry.get_for::<T>() else {
+        return;
+    };
+
+    for (component, mut merged) in &mut query {
+        let Some(properties) = (info.to_properties)(component.as_reflect()) else {
+            continue;
+        };
+        merged.write_class(info.name, properties);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::properties::FromTiledProperty;
+    use tiled::Properties;
+
+    #[test]
+    fn test_round_trip_through_properties() {
+        let tuple = (1.5f32, "door".to_string());
+        let value = tuple.to_property().unwrap();
+        let PropertyValue::ClassValue { property_type, .. } = &value else {
+            panic!("tuples should serialize as class values");
+        };
+        assert_eq!(property_type, "(f32, String)");
+        assert_eq!(<(f32, String)>::from_property(&value), Some(tuple));
+
+        let color = Color::srgba_u8(255, 128, 0, 255);
+        assert_eq!(
+            Color::from_property(&color.to_property().unwrap()),
+            Some(color)
+        );
+        assert_eq!(None::<i32>.to_property(), None);
+    }
+
+    #[test]
+    fn test_write_class_replaces_nested_or_top_level() {
+        let mut merged = MergedProperties::new(
+            [(
+                "hinge".to_string(),
+                PropertyValue::ClassValue {
+                    property_type: "game::Hinge".to_string(),
+                    properties: Properties::new(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        );
+
+        let members: Properties = [("angle".to_string(), PropertyValue::FloatValue(90.0))]
+            .into_iter()
+            .collect();
+        merged.write_class("game::Hinge", members.clone());
+        let Some(PropertyValue::ClassValue { properties, .. }) = merged.get("hinge") else {
+            panic!("hinge should stay a class value");
+        };
+        assert_eq!(properties, &members);
+        assert!(merged.get("angle").is_none());
+
+        merged.write_class("game::Door", members);
+        assert_eq!(merged.get_f32("angle"), Some(90.0));
+    }
+}
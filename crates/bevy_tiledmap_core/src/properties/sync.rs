@@ -0,0 +1,1011 @@
+//! Keep Tiled files in sync with the registered `TiledClass` types.
+//!
+//! Exporting types (see [`export`](super::export)) updates the project file, but
+//! maps, tilesets and templates keep the property values they were saved with.
+//! After a refactor of gameplay components they can still contain members that
+//! were removed, or enum values and class names that were renamed. [`ClassSync`]
+//! scans `.tmx`, `.tsx` and `.tx` files for such values and reports them as
+//! [`ClassDrift`], or fixes them in place:
+//!
+//! - Members a registered class no longer has are removed
+//! - Renamed classes, members and enum values (declared with
+//!   [`ClassSync::rename_class`] and friends) are rewritten
+//! - Invalid enum values and type mismatches are only reported
+//!
+//! Classes that aren't registered (e.g. project-only classes) are not checked,
+//! and top-level properties that aren't members of the item's class are left
+//! alone, since Tiled allows free-form properties next to class members.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn sync_classes(registry: Res<TiledClassRegistry>) {
+//!     let sync = ClassSync::new(&registry)
+//!         .rename_class("game::Gate", "game::Door")
+//!         .rename_member("game::Door", "key", "key_id")
+//!         .rename_enum_value("game::Facing", "Up", "North");
+//!
+//!     // Report only
+//!     for file in sync.check_dir("assets/maps").unwrap() {
+//!         for drift in &file.drift {
+//!             warn!("{}: {}", file.path.display(), drift);
+//!         }
+//!     }
+//!
+//!     // Rewrite the files
+//!     sync.fix_dir("assets/maps").unwrap();
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use quick_xml::Writer;
+use quick_xml::events::{BytesStart, Event};
+use thiserror::Error;
+
+use super::registry::{
+    TiledClassInfo, TiledClassRegistry, TiledEnumInfo, TiledEnumKind, TiledFieldInfo,
+    TiledTypeKind, TiledVariantKind,
+};
+
+/// File extensions scanned by [`ClassSync::check_dir`] and [`ClassSync::fix_dir`].
+const TILED_EXTENSIONS: &[&str] = &["tmx", "tsx", "tx"];
+
+/// A property value that doesn't match the registered classes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassDrift {
+    /// Where the value was found (e.g., `"object 12 'door'"`).
+    pub context: String,
+    /// Dotted path of the offending property, if the drift concerns a property.
+    pub property: Option<String>,
+    /// What doesn't match.
+    pub kind: ClassDriftKind,
+}
+
+impl fmt::Display for ClassDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.property {
+            Some(property) => write!(
+                f,
+                "{}: property '{}': {}",
+                self.context, property, self.kind
+            ),
+            None => write!(f, "{}: {}", self.context, self.kind),
+        }
+    }
+}
+
+/// The kind of a [`ClassDrift`].
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ClassDriftKind {
+    /// A class value contains a member the registered class no longer has.
+    #[error("class '{class}' has no member '{member}'")]
+    RemovedMember {
+        /// The class name.
+        class: String,
+        /// The removed member name.
+        member: String,
+    },
+
+    /// A member was renamed with [`ClassSync::rename_member`].
+    #[error("member '{from}' of class '{class}' was renamed to '{to}'")]
+    RenamedMember {
+        /// The class name.
+        class: String,
+        /// The old member name.
+        from: String,
+        /// The new member name.
+        to: String,
+    },
+
+    /// A class was renamed with [`ClassSync::rename_class`].
+    #[error("class '{from}' was renamed to '{to}'")]
+    RenamedClass {
+        /// The old class name.
+        from: String,
+        /// The new class name.
+        to: String,
+    },
+
+    /// An enum value was renamed with [`ClassSync::rename_enum_value`].
+    #[error("value '{from}' of enum '{enum_name}' was renamed to '{to}'")]
+    RenamedEnumValue {
+        /// The enum name.
+        enum_name: String,
+        /// The old value.
+        from: String,
+        /// The new value.
+        to: String,
+    },
+
+    /// An enum value is not one of the registered enum's variants.
+    #[error("'{value}' is not a valid value of enum '{enum_name}'")]
+    InvalidEnumValue {
+        /// The enum name.
+        enum_name: String,
+        /// The offending value.
+        value: String,
+    },
+
+    /// A member value has a different type than the registered field.
+    #[error("expected {expected}, found {found}")]
+    TypeMismatch {
+        /// The type of the registered field.
+        expected: String,
+        /// The type found in the file.
+        found: String,
+    },
+}
+
+impl ClassDriftKind {
+    /// Whether [`ClassSync`] fixes this drift when rewriting files.
+    pub fn is_fixable(&self) -> bool {
+        !matches!(
+            self,
+            ClassDriftKind::InvalidEnumValue { .. } | ClassDriftKind::TypeMismatch { .. }
+        )
+    }
+}
+
+/// Drift found in one file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileDrift {
+    /// Path of the file
+    pub path: PathBuf,
+    /// Everything that didn't match, in file order
+    pub drift: Vec<ClassDrift>,
+}
+
+/// Errors reading or writing Tiled files in [`ClassSync`].
+#[derive(Debug, Error)]
+pub enum ClassSyncError {
+    /// A file or directory could not be read or written.
+    #[error("failed to access {path}: {source}")]
+    Io {
+        /// The file or directory.
+        path: PathBuf,
+        /// The underlying error.
+        source: std::io::Error,
+    },
+
+    /// A file is not valid XML.
+    #[error("failed to parse {path}: {source}")]
+    Xml {
+        /// The file.
+        path: PathBuf,
+        /// The underlying error.
+        source: quick_xml::Error,
+    },
+}
+
+/// Checks and fixes Tiled files against the registered `TiledClass` types.
+///
+/// See the [module documentation](self).
+pub struct ClassSync<'a> {
+    registry: &'a TiledClassRegistry,
+    class_renames: HashMap<String, String>,
+    member_renames: HashMap<(String, String), String>,
+    enum_value_renames: HashMap<(String, String), String>,
+}
+
+impl<'a> ClassSync<'a> {
+    /// Create a checker for the classes and enums in `registry`.
+    pub fn new(registry: &'a TiledClassRegistry) -> Self {
+        Self {
+            registry,
+            class_renames: HashMap::new(),
+            member_renames: HashMap::new(),
+            enum_value_renames: HashMap::new(),
+        }
+    }
+
+    /// Rewrite the class (or enum) name `from` to `to`.
+    pub fn rename_class(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.class_renames.insert(from.into(), to.into());
+        self
+    }
+
+    /// Rewrite the member `from` of `class` (its current name) to `to`.
+    pub fn rename_member(
+        mut self,
+        class: impl Into<String>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self {
+        self.member_renames
+            .insert((class.into(), from.into()), to.into());
+        self
+    }
+
+    /// Rewrite the value (or complex enum variant) `from` of `enum_name` (its
+    /// current name) to `to`.
+    pub fn rename_enum_value(
+        mut self,
+        enum_name: impl Into<String>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self {
+        self.enum_value_renames
+            .insert((enum_name.into(), from.into()), to.into());
+        self
+    }
+
+    /// Check the contents of a `.tmx`, `.tsx` or `.tx` file.
+    pub fn check_str(&self, xml: &str) -> Result<Vec<ClassDrift>, quick_xml::Error> {
+        self.process(xml).map(|(_, drift)| drift)
+    }
+
+    /// Fix the contents of a `.tmx`, `.tsx` or `.tx` file, returning the fixed
+    /// contents and everything that didn't match.
+    ///
+    /// Parts of the file without fixes are kept byte for byte.
+    pub fn fix_str(&self, xml: &str) -> Result<(String, Vec<ClassDrift>), quick_xml::Error> {
+        let (fixed, drift) = self.process(xml)?;
+        Ok((String::from_utf8_lossy(&fixed).into_owned(), drift))
+    }
+
+    /// Check a `.tmx`, `.tsx` or `.tx` file.
+    pub fn check_file(&self, path: impl AsRef<Path>) -> Result<Vec<ClassDrift>, ClassSyncError> {
+        let path = path.as_ref();
+        let xml = read_file(path)?;
+        self.check_str(&xml).map_err(|source| ClassSyncError::Xml {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Fix a `.tmx`, `.tsx` or `.tx` file in place.
+    ///
+    /// The file is only written if something was fixed. Returns everything that
+    /// didn't match, including drift that can't be fixed.
+    pub fn fix_file(&self, path: impl AsRef<Path>) -> Result<Vec<ClassDrift>, ClassSyncError> {
+        let path = path.as_ref();
+        let xml = read_file(path)?;
+        let (fixed, drift) = self.fix_str(&xml).map_err(|source| ClassSyncError::Xml {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        if drift.iter().any(|drift| drift.kind.is_fixable()) {
+            std::fs::write(path, fixed).map_err(|source| ClassSyncError::Io {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        }
+        Ok(drift)
+    }
+
+    /// Check every `.tmx`, `.tsx` and `.tx` file in `dir` and its subdirectories.
+    ///
+    /// Only files with drift are returned, sorted by path.
+    pub fn check_dir(&self, dir: impl AsRef<Path>) -> Result<Vec<FileDrift>, ClassSyncError> {
+        self.for_each_file(dir.as_ref(), |path| self.check_file(path))
+    }
+
+    /// Fix every `.tmx`, `.tsx` and `.tx` file in `dir` and its subdirectories.
+    ///
+    /// Only files with drift are returned, sorted by path.
+    pub fn fix_dir(&self, dir: impl AsRef<Path>) -> Result<Vec<FileDrift>, ClassSyncError> {
+        self.for_each_file(dir.as_ref(), |path| self.fix_file(path))
+    }
+
+    fn for_each_file(
+        &self,
+        dir: &Path,
+        mut f: impl FnMut(&Path) -> Result<Vec<ClassDrift>, ClassSyncError>,
+    ) -> Result<Vec<FileDrift>, ClassSyncError> {
+        let mut paths = Vec::new();
+        collect_tiled_files(dir, &mut paths)?;
+        paths.sort();
+
+        let mut files = Vec::new();
+        for path in paths {
+            let drift = f(&path)?;
+            if !drift.is_empty() {
+                files.push(FileDrift { path, drift });
+            }
+        }
+        Ok(files)
+    }
+
+    /// Stream `xml` through a writer, fixing what can be fixed on the way.
+    fn process(&self, xml: &str) -> Result<(Vec<u8>, Vec<ClassDrift>), quick_xml::Error> {
+        let mut reader = quick_xml::Reader::from_str(xml);
+        let mut writer = Writer::new(Vec::with_capacity(xml.len()));
+        let mut state = SyncState::default();
+        // Indentation before an element, dropped along with a removed property
+        let mut whitespace: Option<Event<'_>> = None;
+
+        loop {
+            let event = reader.read_event()?;
+            if state.skip_depth > 0 {
+                match event {
+                    Event::Start(_) => state.skip_depth += 1,
+                    Event::End(_) => state.skip_depth -= 1,
+                    _ => {}
+                }
+                continue;
+            }
+
+            let event = match event {
+                Event::Eof => break,
+                Event::Text(text) if text.iter().all(u8::is_ascii_whitespace) => {
+                    if let Some(previous) = whitespace.replace(Event::Text(text)) {
+                        writer.write_event(previous)?;
+                    }
+                    continue;
+                }
+                Event::Start(element) => self.start_element(&mut state, element, false)?,
+                Event::Empty(element) => self.start_element(&mut state, element, true)?,
+                Event::End(element) => {
+                    state.frames.pop();
+                    Some(Event::End(element))
+                }
+                other => Some(other),
+            };
+
+            match event {
+                Some(event) => {
+                    if let Some(whitespace) = whitespace.take() {
+                        writer.write_event(whitespace)?;
+                    }
+                    writer.write_event(event)?;
+                }
+                // Removed property: drop its indentation too
+                None => whitespace = None,
+            }
+        }
+        if let Some(whitespace) = whitespace {
+            writer.write_event(whitespace)?;
+        }
+
+        Ok((writer.into_inner(), state.drift))
+    }
+
+    /// Handle an opening or empty tag, returning the event to write (`None` to remove it).
+    fn start_element<'x>(
+        &self,
+        state: &mut SyncState,
+        element: BytesStart<'x>,
+        empty: bool,
+    ) -> Result<Option<Event<'x>>, quick_xml::Error> {
+        let name = String::from_utf8_lossy(element.name().as_ref()).into_owned();
+        let mut attributes = read_attributes(&element)?;
+
+        let (frame, changed) = match name.as_str() {
+            "properties" => {
+                let scope = match state.frames.last() {
+                    Some(Frame::Property(Some(scope))) => scope.clone(),
+                    Some(Frame::Element { class, .. }) => Scope {
+                        class: self.scope_class(class),
+                        strict: false,
+                        path: String::new(),
+                    },
+                    _ => Scope::default(),
+                };
+                (Frame::Properties(scope), false)
+            }
+            "property" => {
+                let context = state.context();
+                let Some(Frame::Properties(scope)) = state.frames.last_mut() else {
+                    if !empty {
+                        state.frames.push(Frame::Property(None));
+                    }
+                    return Ok(Some(tag(element, empty)));
+                };
+                match self.property(scope, &mut attributes, &context, &mut state.drift) {
+                    PropertyAction::Keep { nested, changed } => (Frame::Property(nested), changed),
+                    PropertyAction::Remove => {
+                        if !empty {
+                            state.skip_depth = 1;
+                        }
+                        return Ok(None);
+                    }
+                }
+            }
+            _ => {
+                let context = element_context(&name, &attributes, &state.frames);
+                let class_key = if attribute(&attributes, "class").is_some() {
+                    "class"
+                } else {
+                    "type"
+                };
+                let mut class = attribute(&attributes, class_key)
+                    .unwrap_or_default()
+                    .to_string();
+                let mut changed = false;
+                if let Some(to) = self.class_renames.get(&class) {
+                    state.drift.push(ClassDrift {
+                        context: context.clone().unwrap_or_else(|| name.clone()),
+                        property: None,
+                        kind: ClassDriftKind::RenamedClass {
+                            from: class.clone(),
+                            to: to.clone(),
+                        },
+                    });
+                    set_attribute(&mut attributes, class_key, to);
+                    class = to.clone();
+                    changed = true;
+                }
+                (Frame::Element { context, class }, changed)
+            }
+        };
+
+        if !empty {
+            state.frames.push(frame);
+        }
+        Ok(Some(if changed {
+            let mut rebuilt = BytesStart::new(name);
+            for (key, value) in &attributes {
+                rebuilt.push_attribute((key.as_str(), value.as_str()));
+            }
+            tag(rebuilt, empty)
+        } else {
+            tag(element, empty)
+        }))
+    }
+
+    /// Check (and fix) one `<property>` inside a `<properties>` scope.
+    fn property(
+        &self,
+        scope: &mut Scope,
+        attributes: &mut [(String, String)],
+        context: &str,
+        drift: &mut Vec<ClassDrift>,
+    ) -> PropertyAction {
+        let mut name = attribute(attributes, "name")
+            .unwrap_or_default()
+            .to_string();
+        let mut changed = false;
+        let mut report = |path: &str, kind| {
+            drift.push(ClassDrift {
+                context: context.to_string(),
+                property: Some(path.to_string()),
+                kind,
+            });
+        };
+
+        let class_name = scope.class.name();
+        if let Some(to) =
+            class_name.and_then(|class| self.member_renames.get(&(class.to_string(), name.clone())))
+        {
+            report(
+                &scope.member_path(&name),
+                ClassDriftKind::RenamedMember {
+                    class: class_name.unwrap_or_default().to_string(),
+                    from: name.clone(),
+                    to: to.clone(),
+                },
+            );
+            set_attribute(attributes, "name", to);
+            name = to.clone();
+            changed = true;
+        }
+        let path = scope.member_path(&name);
+
+        let mut property_type = attribute(attributes, "propertytype").map(str::to_string);
+        if let Some((from, to)) = property_type
+            .as_ref()
+            .and_then(|from| Some((from.clone(), self.class_renames.get(from)?.clone())))
+        {
+            report(
+                &path,
+                ClassDriftKind::RenamedClass {
+                    from,
+                    to: to.clone(),
+                },
+            );
+            set_attribute(attributes, "propertytype", &to);
+            property_type = Some(to);
+            changed = true;
+        }
+
+        let value_type = attribute(attributes, "type")
+            .unwrap_or("string")
+            .to_string();
+
+        // The discriminant of a complex enum
+        if let ScopeClass::ComplexEnum { info, variant } = &mut scope.class
+            && info.tag() == Some(name.as_str())
+        {
+            if let Some(value) = attribute(attributes, "value").map(str::to_string) {
+                let value = self.enum_value(info, &value, &path, attributes, &mut report);
+                changed |= value.1;
+                *variant = Some(value.0);
+            }
+            return PropertyAction::Keep {
+                nested: None,
+                changed,
+            };
+        }
+
+        let field = scope.class.field(&name);
+        if field.is_none() && scope.strict {
+            report(
+                &path,
+                ClassDriftKind::RemovedMember {
+                    class: class_name.unwrap_or_default().to_string(),
+                    member: name,
+                },
+            );
+            return PropertyAction::Remove;
+        }
+
+        // The enum or class the value should have
+        let expected_type = field.and_then(|field| field.tiled_type.property_type());
+        let custom_type = expected_type.or(property_type.as_deref());
+        let simple_enum = custom_type
+            .and_then(|name| self.resolve_enum(name))
+            .filter(|info| info.is_simple());
+
+        if let Some(field) = field {
+            let expected = if simple_enum.is_some() {
+                "string"
+            } else {
+                field.tiled_type.tiled_type_name()
+            };
+            let int_enum = simple_enum.is_some() && value_type == "int";
+            if value_type != expected && !int_enum {
+                report(
+                    &path,
+                    ClassDriftKind::TypeMismatch {
+                        expected: expected.to_string(),
+                        found: value_type,
+                    },
+                );
+                return PropertyAction::Keep {
+                    nested: None,
+                    changed,
+                };
+            }
+            if let (TiledTypeKind::Class { .. }, Some(expected), Some(found)) = (
+                &field.tiled_type,
+                expected_type.and_then(|name| self.resolve_name(name)),
+                property_type
+                    .as_deref()
+                    .and_then(|name| self.resolve_name(name)),
+            ) && expected != found
+            {
+                report(
+                    &path,
+                    ClassDriftKind::TypeMismatch {
+                        expected: expected.to_string(),
+                        found: found.to_string(),
+                    },
+                );
+                return PropertyAction::Keep {
+                    nested: None,
+                    changed,
+                };
+            }
+        }
+
+        if let Some(info) = simple_enum
+            && value_type == "string"
+            && let Some(value) = attribute(attributes, "value").map(str::to_string)
+        {
+            changed |= self
+                .enum_value(info, &value, &path, attributes, &mut report)
+                .1;
+        }
+
+        let nested = (value_type == "class").then(|| {
+            let class = property_type
+                .as_deref()
+                .map_or(ScopeClass::None, |name| self.scope_class(name));
+            Scope {
+                strict: !matches!(class, ScopeClass::None),
+                class,
+                path,
+            }
+        });
+        PropertyAction::Keep { nested, changed }
+    }
+
+    /// Check (and rename) an enum value stored in the `value` attribute.
+    ///
+    /// Returns the (possibly renamed) value and whether it was renamed.
+    fn enum_value(
+        &self,
+        info: &TiledEnumInfo,
+        value: &str,
+        path: &str,
+        attributes: &mut [(String, String)],
+        report: &mut impl FnMut(&str, ClassDriftKind),
+    ) -> (String, bool) {
+        if let Some(to) = self
+            .enum_value_renames
+            .get(&(info.name.to_string(), value.to_string()))
+        {
+            report(
+                path,
+                ClassDriftKind::RenamedEnumValue {
+                    enum_name: info.name.to_string(),
+                    from: value.to_string(),
+                    to: to.clone(),
+                },
+            );
+            set_attribute(attributes, "value", to);
+            return (to.clone(), true);
+        }
+        if !info.variant_names().contains(&value) {
+            report(
+                path,
+                ClassDriftKind::InvalidEnumValue {
+                    enum_name: info.name.to_string(),
+                    value: value.to_string(),
+                },
+            );
+        }
+        (value.to_string(), false)
+    }
+
+    /// What the members of a value of class `name` are checked against.
+    fn scope_class(&self, name: &str) -> ScopeClass {
+        if name.is_empty() {
+            return ScopeClass::None;
+        }
+        if let Some(info) = self.resolve_class(name) {
+            return ScopeClass::Class(info);
+        }
+        if let Some((info, variant)) = self.registry.get_variant_class(name) {
+            return ScopeClass::ComplexEnum {
+                info,
+                variant: Some(variant.to_string()),
+            };
+        }
+        match self.resolve_enum(name) {
+            Some(info) if info.is_complex() => ScopeClass::ComplexEnum {
+                info,
+                variant: None,
+            },
+            _ => ScopeClass::None,
+        }
+    }
+
+    /// Find a registered class by its full name, or by its last path segment
+    /// (field types may only name the Rust type).
+    fn resolve_class(&self, name: &str) -> Option<&'static TiledClassInfo> {
+        self.registry.get(name).or_else(|| {
+            let suffix = format!("::{name}");
+            self.registry
+                .iter()
+                .find(|info| info.name.ends_with(&suffix))
+        })
+    }
+
+    /// Find a registered enum by its full name, or by its last path segment.
+    fn resolve_enum(&self, name: &str) -> Option<&'static TiledEnumInfo> {
+        self.registry.get_enum(name).or_else(|| {
+            let suffix = format!("::{name}");
+            self.registry
+                .iter_enums()
+                .find(|info| info.name.ends_with(&suffix))
+        })
+    }
+
+    /// The registered full name of a class or enum.
+    fn resolve_name(&self, name: &str) -> Option<&'static str> {
+        self.resolve_class(name)
+            .map(|info| info.name)
+            .or_else(|| self.resolve_enum(name).map(|info| info.name))
+    }
+}
+
+#[derive(Default)]
+struct SyncState {
+    frames: Vec<Frame>,
+    drift: Vec<ClassDrift>,
+    /// Open elements left to skip inside a removed property
+    skip_depth: usize,
+}
+
+impl SyncState {
+    /// Context of the innermost element with one (e.g. `"object 12 'door'"`).
+    fn context(&self) -> String {
+        self.frames
+            .iter()
+            .rev()
+            .find_map(|frame| match frame {
+                Frame::Element {
+                    context: Some(context),
+                    ..
+                } => Some(context.clone()),
+                _ => None,
+            })
+            .unwrap_or_else(|| "file".to_string())
+    }
+}
+
+/// An open element.
+enum Frame {
+    /// A map, tileset, tile, layer, object or other element
+    Element {
+        context: Option<String>,
+        class: String,
+    },
+    /// A `<properties>` list
+    Properties(Scope),
+    /// A `<property>`, with its members' scope if it's a class value
+    Property(Option<Scope>),
+}
+
+/// What the properties in a `<properties>` list are checked against.
+#[derive(Clone, Default)]
+struct Scope {
+    class: ScopeClass,
+    /// Whether unknown members are removed (class values) or allowed (top-level properties)
+    strict: bool,
+    /// Dotted path of the class value
+    path: String,
+}
+
+impl Scope {
+    fn member_path(&self, name: &str) -> String {
+        if self.path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", self.path, name)
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+enum ScopeClass {
+    /// Not a registered class: nothing to check against
+    #[default]
+    None,
+    Class(&'static TiledClassInfo),
+    /// A complex enum stored as a class (with a variant tag member if tagged, or as
+    /// the variant's own class)
+    ComplexEnum {
+        info: &'static TiledEnumInfo,
+        variant: Option<String>,
+    },
+}
+
+impl ScopeClass {
+    fn name(&self) -> Option<&'static str> {
+        match self {
+            ScopeClass::None => None,
+            ScopeClass::Class(info) => Some(info.name),
+            ScopeClass::ComplexEnum { info, .. } => Some(info.name),
+        }
+    }
+
+    /// The registered field `name`. For complex enums, the field of the
+    /// selected variant, or of any variant if the tag wasn't read yet.
+    fn field(&self, name: &str) -> Option<&'static TiledFieldInfo> {
+        match self {
+            ScopeClass::None => None,
+            ScopeClass::Class(info) => info.field(name),
+            ScopeClass::ComplexEnum { info, variant } => {
+                let TiledEnumKind::Complex { variant_info, .. } = &info.kind else {
+                    return None;
+                };
+                variant_info
+                    .iter()
+                    .filter(|v| variant.as_ref().is_none_or(|variant| v.name == variant))
+                    .filter_map(|v| match v.kind {
+                        TiledVariantKind::Unit => None,
+                        TiledVariantKind::Struct { fields }
+                        | TiledVariantKind::Tuple { fields } => Some(fields),
+                    })
+                    .flatten()
+                    .find(|field| field.name == name)
+            }
+        }
+    }
+}
+
+enum PropertyAction {
+    Keep {
+        /// Scope of the members if the property is a class value
+        nested: Option<Scope>,
+        /// Whether the attributes were changed
+        changed: bool,
+    },
+    Remove,
+}
+
+/// Context of an element as used in [`ClassDrift::context`], matching the
+/// wording of schema issues.
+fn element_context(
+    name: &str,
+    attributes: &[(String, String)],
+    frames: &[Frame],
+) -> Option<String> {
+    let get = |key| attribute(attributes, key).unwrap_or_default();
+    match name {
+        "map" => Some("map".to_string()),
+        "tileset" => Some(format!("tileset '{}'", get("name"))),
+        "tile" => {
+            let tileset = frames.iter().rev().find_map(|frame| match frame {
+                Frame::Element {
+                    context: Some(context),
+                    ..
+                } if context.starts_with("tileset ") => Some(context.as_str()),
+                _ => None,
+            });
+            Some(match tileset {
+                Some(tileset) => format!("tile {} in {}", get("id"), tileset),
+                None => format!("tile {}", get("id")),
+            })
+        }
+        "layer" | "objectgroup" | "imagelayer" | "group" => {
+            Some(format!("layer '{}' (id {})", get("name"), get("id")))
+        }
+        "object" => Some(format!("object {} '{}'", get("id"), get("name"))),
+        "template" => Some("template".to_string()),
+        "wangset" => Some(format!("wang set '{}'", get("name"))),
+        _ => None,
+    }
+}
+
+fn read_attributes(element: &BytesStart<'_>) -> Result<Vec<(String, String)>, quick_xml::Error> {
+    element
+        .attributes()
+        .map(|attribute| {
+            let attribute = attribute.map_err(quick_xml::Error::from)?;
+            Ok((
+                String::from_utf8_lossy(attribute.key.as_ref()).into_owned(),
+                attribute.unescape_value()?.into_owned(),
+            ))
+        })
+        .collect()
+}
+
+fn attribute<'x>(attributes: &'x [(String, String)], key: &str) -> Option<&'x str> {
+    attributes
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, value)| value.as_str())
+}
+
+fn set_attribute(attributes: &mut [(String, String)], key: &str, value: &str) {
+    if let Some((_, v)) = attributes.iter_mut().find(|(k, _)| k == key) {
+        *v = value.to_string();
+    }
+}
+
+fn tag(element: BytesStart<'_>, empty: bool) -> Event<'_> {
+    if empty {
+        Event::Empty(element)
+    } else {
+        Event::Start(element)
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, ClassSyncError> {
+    std::fs::read_to_string(path).map_err(|source| ClassSyncError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn collect_tiled_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), ClassSyncError> {
+    let io_error = |source| ClassSyncError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    for entry in std::fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.is_dir() {
+            collect_tiled_files(&path, paths)?;
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| TILED_EXTENSIONS.contains(&ext))
+        {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy_tiledmap_macros::TiledClass;
+
+    use super::*;
+
+    #[derive(Reflect, TiledClass, Debug, Clone, Default)]
+    #[tiled(name = "sync_test::Facing")]
+    enum Facing {
+        #[default]
+        North,
+        South,
+    }
+
+    #[derive(Component, Reflect, TiledClass, Debug, Clone, Default)]
+    #[tiled(name = "sync_test::Door")]
+    struct Door {
+        locked: bool,
+        facing: Facing,
+    }
+
+    const TILESET: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" name="doors" tilewidth="16" tileheight="16" tilecount="1" columns="1">
+ <tile id="0">
+  <properties>
+   <property name="door" type="class" propertytype="sync_test::Door">
+    <properties>
+     <property name="facing" propertytype="sync_test::Facing" value="Up"/>
+     <property name="hinge" type="class" propertytype="sync_test::Hinge">
+      <properties>
+       <property name="side" value="left"/>
+      </properties>
+     </property>
+     <property name="locked" type="bool" value="true"/>
+     <property name="open" type="bool" value="true"/>
+    </properties>
+   </property>
+   <property name="note" value="free-form"/>
+  </properties>
+ </tile>
+</tileset>
+"#;
+
+    #[test]
+    fn test_check_reports_drift() {
+        let registry = TiledClassRegistry::build();
+        let drift = ClassSync::new(&registry).check_str(TILESET).unwrap();
+
+        let kinds: Vec<_> = drift.iter().map(|drift| &drift.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                &ClassDriftKind::InvalidEnumValue {
+                    enum_name: "sync_test::Facing".to_string(),
+                    value: "Up".to_string(),
+                },
+                &ClassDriftKind::RemovedMember {
+                    class: "sync_test::Door".to_string(),
+                    member: "hinge".to_string(),
+                },
+                &ClassDriftKind::RemovedMember {
+                    class: "sync_test::Door".to_string(),
+                    member: "open".to_string(),
+                },
+            ]
+        );
+        assert_eq!(drift[0].context, "tile 0 in tileset 'doors'");
+        assert_eq!(drift[0].property.as_deref(), Some("door.facing"));
+    }
+
+    #[test]
+    fn test_fix_removes_members_and_applies_renames() {
+        let registry = TiledClassRegistry::build();
+        let sync = ClassSync::new(&registry)
+            .rename_class("sync_test::Gate", "sync_test::Door")
+            .rename_enum_value("sync_test::Facing", "Up", "North");
+        let renamed = TILESET.replace(
+            r#"propertytype="sync_test::Door""#,
+            r#"propertytype="sync_test::Gate""#,
+        );
+
+        let (fixed, drift) = sync.fix_str(&renamed).unwrap();
+        assert!(drift.iter().all(|drift| drift.kind.is_fixable()));
+        assert_eq!(
+            fixed,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" name="doors" tilewidth="16" tileheight="16" tilecount="1" columns="1">
+ <tile id="0">
+  <properties>
+   <property name="door" type="class" propertytype="sync_test::Door">
+    <properties>
+     <property name="facing" propertytype="sync_test::Facing" value="North"/>
+     <property name="locked" type="bool" value="true"/>
+    </properties>
+   </property>
+   <property name="note" value="free-form"/>
+  </properties>
+ </tile>
+</tileset>
+"#
+        );
+        assert_eq!(sync.check_str(&fixed).unwrap(), []);
+    }
+}
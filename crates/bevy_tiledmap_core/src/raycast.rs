@@ -0,0 +1,301 @@
+//! Grid raycasts over tile data.
+//!
+//! Line-of-sight checks for AI on tile-based maps don't need a physics engine:
+//! [`grid_raycast`] walks the tiles a ray crosses (a DDA traversal) and stops at
+//! the first blocking one. [`TileLayerData`] and [`TileClassGrid`] have
+//! shortcuts deciding which tiles block:
+//!
+//! ```rust,ignore
+//! fn can_see_player(
+//!     walls: Single<(&TileLayerData, &GlobalTransform), With<WallLayer>>,
+//!     guard: &GlobalTransform,
+//!     player: &GlobalTransform,
+//! ) -> bool {
+//!     let (walls, layer_transform) = *walls;
+//!     let to_layer = layer_transform.affine().inverse();
+//!     let from = to_layer.transform_point3(guard.translation()).truncate();
+//!     let to = to_layer.transform_point3(player.translation()).truncate();
+//!     walls.line_of_sight(from, to, Vec2::splat(16.0), |_| true)
+//! }
+//! ```
+//!
+//! Positions are layer-local, in Bevy's coordinate system (Y-up, origin at
+//! the bottom-left of the grid, as [`TileLayerData::grid_to_world`]); hit tiles
+//! are in Tiled's grid (Y-down, origin at the top-left). Rays only hit tiles
+//! inside the grid.
+
+use bevy::prelude::*;
+
+use crate::components::TileClassGrid;
+use crate::components::tile::{TileInstance, TileLayerData};
+
+/// The first blocking tile a ray hit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileRayHit {
+    /// Grid position of the tile (Tiled coordinates, Y-down)
+    pub tile: UVec2,
+    /// Layer-local position where the ray entered the tile
+    pub position: Vec2,
+    /// Normal of the tile side that was hit (zero if the ray started inside
+    /// the tile)
+    pub normal: Vec2,
+    /// Distance along the ray to [`position`](Self::position)
+    pub distance: f32,
+}
+
+/// Cast a ray over a grid, returning the first tile `is_blocking` accepts.
+///
+/// `size` is the grid size in tiles. The ray starts at `origin` and goes up to
+/// `max_distance` along `direction` (which doesn't need to be normalized);
+/// `is_blocking` gets tiles in Tiled coordinates, in the order the ray crosses
+/// them. Returns `None` if no blocking tile is hit, or `direction` is zero.
+pub fn grid_raycast(
+    size: UVec2,
+    tile_size: Vec2,
+    origin: Vec2,
+    direction: Vec2,
+    max_distance: f32,
+    mut is_blocking: impl FnMut(UVec2) -> bool,
+) -> Option<TileRayHit> {
+    let direction = direction.normalize_or_zero();
+    if direction == Vec2::ZERO || size.x == 0 || size.y == 0 {
+        return None;
+    }
+
+    // Clip the ray to the grid's rectangle
+    let grid_max = size.as_vec2() * tile_size;
+    let mut start = 0.0_f32;
+    let mut end = max_distance;
+    let mut normal = Vec2::ZERO;
+    for axis in 0..2 {
+        if direction[axis] == 0.0 {
+            if origin[axis] < 0.0 || origin[axis] > grid_max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let a = (0.0 - origin[axis]) / direction[axis];
+        let b = (grid_max[axis] - origin[axis]) / direction[axis];
+        let (near, far) = if a < b { (a, b) } else { (b, a) };
+        if near > start {
+            start = near;
+            normal = Vec2::ZERO;
+            normal[axis] = -direction[axis].signum();
+        }
+        end = end.min(far);
+    }
+    if start > end {
+        return None;
+    }
+
+    let entry = origin + direction * start;
+    let max_cell = size.as_ivec2() - IVec2::ONE;
+    let mut cell = (entry / tile_size)
+        .floor()
+        .as_ivec2()
+        .clamp(IVec2::ZERO, max_cell);
+    let step = IVec2::new(direction.x.signum() as i32, direction.y.signum() as i32);
+    let boundary = |cell: i32, axis: usize| {
+        let edge = if direction[axis] > 0.0 {
+            cell + 1
+        } else {
+            cell
+        };
+        (edge as f32 * tile_size[axis] - origin[axis]) / direction[axis]
+    };
+    let mut next = Vec2::new(
+        if direction.x != 0.0 {
+            boundary(cell.x, 0)
+        } else {
+            f32::INFINITY
+        },
+        if direction.y != 0.0 {
+            boundary(cell.y, 1)
+        } else {
+            f32::INFINITY
+        },
+    );
+    let delta = (tile_size / direction).abs();
+    let mut distance = start;
+
+    loop {
+        // Flip Y: Tiled y=0 is the top row
+        let tile = UVec2::new(cell.x as u32, (max_cell.y - cell.y) as u32);
+        if is_blocking(tile) {
+            return Some(TileRayHit {
+                tile,
+                position: origin + direction * distance,
+                normal,
+                distance,
+            });
+        }
+
+        let axis = if next.x < next.y { 0 } else { 1 };
+        distance = next[axis];
+        if distance > end {
+            return None;
+        }
+        cell[axis] += step[axis];
+        if cell[axis] < 0 || cell[axis] > max_cell[axis] {
+            return None;
+        }
+        next[axis] += delta[axis];
+        normal = Vec2::ZERO;
+        normal[axis] = -step[axis] as f32;
+    }
+}
+
+impl TileLayerData {
+    /// Cast a ray over the layer, returning the first non-empty tile
+    /// `is_blocking` accepts (see [`grid_raycast`]).
+    pub fn raycast(
+        &self,
+        origin: Vec2,
+        direction: Vec2,
+        max_distance: f32,
+        tile_size: Vec2,
+        mut is_blocking: impl FnMut(&TileInstance) -> bool,
+    ) -> Option<TileRayHit> {
+        grid_raycast(
+            UVec2::new(self.width, self.height),
+            tile_size,
+            origin,
+            direction,
+            max_distance,
+            |tile| self.get(tile.x, tile.y).is_some_and(&mut is_blocking),
+        )
+    }
+
+    /// Check that no tile `is_blocking` accepts lies between two positions.
+    pub fn line_of_sight(
+        &self,
+        from: Vec2,
+        to: Vec2,
+        tile_size: Vec2,
+        is_blocking: impl FnMut(&TileInstance) -> bool,
+    ) -> bool {
+        self.raycast(from, to - from, from.distance(to), tile_size, is_blocking)
+            .is_none()
+    }
+}
+
+impl TileClassGrid {
+    /// Cast a ray over the grid, returning the first tile whose class is in
+    /// `blocking` (see [`grid_raycast`]).
+    pub fn raycast(
+        &self,
+        origin: Vec2,
+        direction: Vec2,
+        max_distance: f32,
+        tile_size: Vec2,
+        blocking: &[&str],
+    ) -> Option<TileRayHit> {
+        let blocking: Vec<u16> = blocking
+            .iter()
+            .filter_map(|class| self.class_index(class))
+            .collect();
+        if blocking.is_empty() {
+            return None;
+        }
+        grid_raycast(
+            UVec2::new(self.width, self.height),
+            tile_size,
+            origin,
+            direction,
+            max_distance,
+            |tile| blocking.contains(&self.index(tile.x, tile.y)),
+        )
+    }
+
+    /// Check that no tile with a class in `blocking` lies between two positions.
+    pub fn line_of_sight(&self, from: Vec2, to: Vec2, tile_size: Vec2, blocking: &[&str]) -> bool {
+        self.raycast(from, to - from, from.distance(to), tile_size, blocking)
+            .is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_raycast_hits_first_blocking_tile() {
+        // 4x3 grid with a wall column at x = 2 (Tiled coordinates)
+        let size = UVec2::new(4, 3);
+        let tile_size = Vec2::splat(10.0);
+        let wall = |tile: UVec2| tile.x == 2;
+
+        let hit =
+            grid_raycast(size, tile_size, Vec2::new(5.0, 15.0), Vec2::X, 100.0, wall).unwrap();
+        assert_eq!(hit.tile, UVec2::new(2, 1));
+        assert_eq!(hit.position, Vec2::new(20.0, 15.0));
+        assert_eq!(hit.normal, Vec2::NEG_X);
+        assert_eq!(hit.distance, 15.0);
+
+        // Too short, or pointing away
+        assert!(grid_raycast(size, tile_size, Vec2::new(5.0, 15.0), Vec2::X, 10.0, wall).is_none());
+        assert!(
+            grid_raycast(
+                size,
+                tile_size,
+                Vec2::new(5.0, 15.0),
+                Vec2::NEG_X,
+                100.0,
+                wall
+            )
+            .is_none()
+        );
+
+        // Starting outside the grid, from the top: Bevy y = 25 is Tiled row 0
+        let hit = grid_raycast(
+            size,
+            tile_size,
+            Vec2::new(25.0, 50.0),
+            Vec2::NEG_Y,
+            100.0,
+            wall,
+        )
+        .unwrap();
+        assert_eq!(hit.tile, UVec2::new(2, 0));
+        assert_eq!(hit.position, Vec2::new(25.0, 30.0));
+        assert_eq!(hit.normal, Vec2::Y);
+
+        // Starting inside a blocking tile
+        let hit =
+            grid_raycast(size, tile_size, Vec2::new(25.0, 5.0), Vec2::X, 100.0, wall).unwrap();
+        assert_eq!(hit.tile, UVec2::new(2, 2));
+        assert_eq!(hit.distance, 0.0);
+        assert_eq!(hit.normal, Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_class_grid_line_of_sight() {
+        // 3x1 grid: Floor, Wall, Floor
+        let grid = TileClassGrid {
+            width: 3,
+            height: 1,
+            classes: vec!["Floor".to_string(), "Wall".to_string()],
+            cells: vec![1, 2, 1],
+        };
+        let tile_size = Vec2::splat(16.0);
+
+        assert!(!grid.line_of_sight(
+            Vec2::new(8.0, 8.0),
+            Vec2::new(40.0, 8.0),
+            tile_size,
+            &["Wall"]
+        ));
+        assert!(grid.line_of_sight(
+            Vec2::new(8.0, 8.0),
+            Vec2::new(12.0, 8.0),
+            tile_size,
+            &["Wall"]
+        ));
+        assert!(grid.line_of_sight(
+            Vec2::new(8.0, 8.0),
+            Vec2::new(40.0, 8.0),
+            tile_size,
+            &["Lava"]
+        ));
+    }
+}
@@ -0,0 +1,230 @@
+//! Stable contract for Layer 3 renderer backends.
+//!
+//! Layer 2 announces what needs drawing through events: [`TileLayerSpawned`]
+//! and [`ImageLayerSpawned`] when layers spawn (with [`TileLayerData`] and
+//! [`ImageLayerData`] already attached), [`TilesModified`] when tiles change at
+//! runtime, and layer despawns. [`TiledRenderBackend`] bundles those into one
+//! trait, so a renderer only implements what to do for each of them and
+//! [`RenderBackendAppExt::add_render_backend`] wires up the observers.
+//!
+//! `bevy_tiledmap_tilemap` (`bevy_ecs_tilemap`) and `bevy_tiledmap_native` are
+//! implemented this way; a custom renderer (e.g. instanced wgpu drawing) can
+//! replace them:
+//!
+//! ```rust,ignore
+//! struct InstancedRenderer;
+//!
+//! impl TiledRenderBackend for InstancedRenderer {
+//!     fn tile_layer_spawned(&self, world: &mut World, event: &TileLayerSpawned) {
+//!         let Some(tiles) = world.get::<TileLayerData>(event.entity) else {
+//!             return;
+//!         };
+//!         // Build instance buffers from `tiles`...
+//!     }
+//!
+//!     fn layer_despawned(&self, world: &mut World, layer: Entity) {
+//!         // Free the instance buffers of `layer`...
+//!     }
+//! }
+//!
+//! app.add_render_backend(InstancedRenderer);
+//! ```
+//!
+//! [`TileLayerData`]: crate::components::tile::TileLayerData
+//! [`ImageLayerData`]: crate::components::layer::ImageLayerData
+
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::components::TiledLayer;
+use crate::events::{ImageLayerSpawned, TileLayerSpawned, TilesModified};
+
+/// A renderer for Tiled layers.
+///
+/// Each method runs with exclusive world access, right after the observers of
+/// the corresponding event (when their commands are applied). Only tile layers
+/// must be handled; the other methods do nothing by default.
+pub trait TiledRenderBackend: Send + Sync + 'static {
+    /// Draw a spawned tile layer. Its `TileLayerData` is attached to `event.entity`.
+    fn tile_layer_spawned(&self, world: &mut World, event: &TileLayerSpawned);
+
+    /// Draw a spawned image layer. Its `ImageLayerData` is attached to `event.entity`.
+    fn image_layer_spawned(&self, world: &mut World, event: &ImageLayerSpawned) {
+        let _ = (world, event);
+    }
+
+    /// Redraw the tiles of a tile layer that changed at runtime.
+    fn tiles_changed(&self, world: &mut World, event: &TilesModified) {
+        let _ = (world, event);
+    }
+
+    /// Clean up after a despawned layer (of any kind).
+    ///
+    /// Runs after the layer entity and its children are gone, so only state kept
+    /// outside the layer's hierarchy (keyed by `layer`) is left to free.
+    fn layer_despawned(&self, world: &mut World, layer: Entity) {
+        let _ = (world, layer);
+    }
+}
+
+/// A registered render backend.
+#[derive(Resource)]
+struct RegisteredRenderBackend<B: TiledRenderBackend>(Arc<B>);
+
+/// Extension trait for registering render backends.
+pub trait RenderBackendAppExt {
+    /// Render Tiled layers with `backend`.
+    ///
+    /// Backends of different types can be registered side by side (e.g. a debug
+    /// overlay next to the main renderer); registering the same type again
+    /// replaces the previous instance.
+    fn add_render_backend<B: TiledRenderBackend>(&mut self, backend: B) -> &mut Self;
+}
+
+impl RenderBackendAppExt for App {
+    fn add_render_backend<B: TiledRenderBackend>(&mut self, backend: B) -> &mut Self {
+        let registered = self
+            .world()
+            .contains_resource::<RegisteredRenderBackend<B>>();
+        self.insert_resource(RegisteredRenderBackend(Arc::new(backend)));
+        if !registered {
+            self.add_observer(on_tile_layer_spawned::<B>)
+                .add_observer(on_image_layer_spawned::<B>)
+                .add_observer(on_tiles_modified::<B>)
+                .add_observer(on_layer_despawned::<B>);
+        }
+        self
+    }
+}
+
+/// Run `f` with the backend `B` and exclusive world access once commands apply.
+fn queue_backend<B: TiledRenderBackend>(
+    commands: &mut Commands,
+    f: impl FnOnce(&B, &mut World) + Send + 'static,
+) {
+    commands.queue(move |world: &mut World| {
+        let Some(backend) = world
+            .get_resource::<RegisteredRenderBackend<B>>()
+            .map(|registered| registered.0.clone())
+        else {
+            return;
+        };
+        f(&backend, world);
+    });
+}
+
+fn on_tile_layer_spawned<B: TiledRenderBackend>(
+    trigger: On<TileLayerSpawned>,
+    mut commands: Commands,
+) {
+    let event = trigger.event().clone();
+    queue_backend::<B>(&mut commands, move |backend, world| {
+        backend.tile_layer_spawned(world, &event);
+    });
+}
+
+fn on_image_layer_spawned<B: TiledRenderBackend>(
+    trigger: On<ImageLayerSpawned>,
+    mut commands: Commands,
+) {
+    let event = trigger.event().clone();
+    queue_backend::<B>(&mut commands, move |backend, world| {
+        backend.image_layer_spawned(world, &event);
+    });
+}
+
+fn on_tiles_modified<B: TiledRenderBackend>(trigger: On<TilesModified>, mut commands: Commands) {
+    let event = trigger.event().clone();
+    queue_backend::<B>(&mut commands, move |backend, world| {
+        backend.tiles_changed(world, &event);
+    });
+}
+
+fn on_layer_despawned<B: TiledRenderBackend>(
+    trigger: On<Despawn, TiledLayer>,
+    mut commands: Commands,
+) {
+    let layer = trigger.event().entity;
+    queue_backend::<B>(&mut commands, move |backend, world| {
+        backend.layer_despawned(world, layer);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::components::tile::TileLayerData;
+
+    /// Records the calls it receives.
+    #[derive(Default)]
+    struct RecordingBackend(Arc<Mutex<Vec<String>>>);
+
+    impl TiledRenderBackend for RecordingBackend {
+        fn tile_layer_spawned(&self, world: &mut World, event: &TileLayerSpawned) {
+            let has_data = world.get::<TileLayerData>(event.entity).is_some();
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("tile layer {} data={has_data}", event.layer_id));
+        }
+
+        fn tiles_changed(&self, _: &mut World, event: &TilesModified) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("tiles changed {}", event.tiles.len()));
+        }
+
+        fn layer_despawned(&self, world: &mut World, layer: Entity) {
+            let gone = world.get_entity(layer).is_err();
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("layer despawned gone={gone}"));
+        }
+    }
+
+    #[test]
+    fn test_backend_receives_layer_lifecycle() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut app = App::new();
+        app.add_render_backend(RecordingBackend(calls.clone()));
+
+        let world = app.world_mut();
+        let layer = world
+            .spawn((TiledLayer::Tiles, TileLayerData::empty(2, 2)))
+            .id();
+        world
+            .run_system_once(move |mut commands: Commands| {
+                commands.trigger(TileLayerSpawned {
+                    entity: layer,
+                    map_entity: layer,
+                    layer_id: 7,
+                    parent_group: None,
+                    depth: 0,
+                    properties: Default::default(),
+                });
+                commands.trigger(TilesModified {
+                    entity: layer,
+                    tiles: vec![UVec2::ZERO],
+                });
+            })
+            .unwrap();
+        world.despawn(layer);
+        world.flush();
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            [
+                "tile layer 7 data=true",
+                "tiles changed 1",
+                "layer despawned gone=true"
+            ]
+        );
+    }
+}
@@ -0,0 +1,441 @@
+//! Replication hooks for networked map state.
+//!
+//! Maps with a [`ReplicatedMap`] component publish every runtime tile change
+//! (edits, destroyed tiles, restored diffs) as an ordered, serde-serializable
+//! [`MapMutationEvent`]. Send those to other clients and apply them there with
+//! [`ApplyRemoteMapMutations`], which applies them in sequence order and skips
+//! duplicates, so every client ends up with the same map state.
+//!
+//! Tiles are identified by Tiled GID (with flip flags in the high bits, as in the
+//! map file), which is stable across clients loading the same map.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! // Server: forward mutations to clients
+//! fn send(trigger: On<MapMutationEvent>, mut outbox: ResMut<Outbox>) {
+//!     outbox.push(bincode::serialize(trigger.event()).unwrap());
+//! }
+//!
+//! // Server: authoritative object changes go through `ApplyMapMutation`
+//! commands.queue(ApplyMapMutation::new(map, MapMutation::RemoveObject { object_id: 12 }));
+//!
+//! // Client: apply received mutations
+//! commands.queue(ApplyRemoteMapMutations(received));
+//! ```
+
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::{TiledMapAsset, TilesetReference};
+use serde::{Deserialize, Serialize};
+
+use crate::components::tile::{TileInstance, TileLayerData};
+use crate::components::{LayerId, ObjectId, TiledLayerMapOf, TiledMap, TiledObjectMapOf};
+use crate::events::TilesModified;
+
+/// GID flag for horizontally flipped tiles.
+pub const FLIPPED_HORIZONTALLY_FLAG: u32 = 0x8000_0000;
+/// GID flag for vertically flipped tiles.
+pub const FLIPPED_VERTICALLY_FLAG: u32 = 0x4000_0000;
+/// GID flag for diagonally flipped tiles.
+pub const FLIPPED_DIAGONALLY_FLAG: u32 = 0x2000_0000;
+
+const FLIP_FLAGS: u32 =
+    FLIPPED_HORIZONTALLY_FLAG | FLIPPED_VERTICALLY_FLAG | FLIPPED_DIAGONALLY_FLAG;
+
+/// Opts a map into replication.
+///
+/// The `id` identifies the map across clients (entities differ per client), so
+/// it must be the same everywhere the map is spawned.
+#[derive(Component, Debug, Clone)]
+pub struct ReplicatedMap {
+    /// Network-stable map id.
+    pub id: u32,
+    next_sequence: u64,
+    last_applied: Option<u64>,
+    applying_remote: bool,
+}
+
+impl ReplicatedMap {
+    /// Replicate a map under the given network-stable id.
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            next_sequence: 0,
+            last_applied: None,
+            applying_remote: false,
+        }
+    }
+
+    /// Sequence number the next published mutation will get.
+    pub fn next_sequence(&self) -> u64 {
+        self.next_sequence
+    }
+
+    /// Sequence number of the last applied remote mutation.
+    pub fn last_applied(&self) -> Option<u64> {
+        self.last_applied
+    }
+}
+
+/// A single replicated change to a map.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum MapMutation {
+    /// Set a tile (Tiled coordinates, Y-down). A `gid` of 0 clears the tile.
+    SetTile {
+        /// The layer's ID from Tiled
+        layer_id: u32,
+        /// Grid X coordinate
+        x: u32,
+        /// Grid Y coordinate
+        y: u32,
+        /// Tiled GID, including flip flags
+        gid: u32,
+    },
+    /// Move an object to a new local translation.
+    MoveObject {
+        /// The object's ID from Tiled
+        object_id: u32,
+        /// New local translation
+        translation: [f32; 3],
+    },
+    /// Despawn an object.
+    RemoveObject {
+        /// The object's ID from Tiled
+        object_id: u32,
+    },
+}
+
+/// A map mutation published by a [`ReplicatedMap`].
+///
+/// Observe it to forward mutations over the network. Sequence numbers start at 0
+/// and increase by one per mutation of the same map.
+#[derive(Event, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MapMutationEvent {
+    /// The [`ReplicatedMap::id`] of the changed map
+    pub map_id: u32,
+    /// Position of this mutation in the map's mutation stream
+    pub sequence: u64,
+    /// The change
+    pub mutation: MapMutation,
+}
+
+/// Apply a mutation to a local map and publish it.
+///
+/// Tile changes made any other way (edits, destroyed tiles) are published
+/// automatically; use this for authoritative object changes.
+#[derive(Debug, Clone)]
+pub struct ApplyMapMutation {
+    /// The map entity
+    pub map: Entity,
+    /// The change to apply
+    pub mutation: MapMutation,
+}
+
+impl ApplyMapMutation {
+    /// Create a command applying `mutation` to `map`.
+    pub fn new(map: Entity, mutation: MapMutation) -> Self {
+        Self { map, mutation }
+    }
+}
+
+impl Command for ApplyMapMutation {
+    fn apply(self, world: &mut World) {
+        if !apply_mutation(world, self.map, &self.mutation) {
+            return;
+        }
+        // Tile changes are published by `on_tiles_modified_replicate`
+        if !matches!(self.mutation, MapMutation::SetTile { .. }) {
+            publish(world, self.map, self.mutation);
+        }
+    }
+}
+
+/// Apply mutations received from another client.
+///
+/// Mutations are grouped by map, sorted by sequence number, and applied in
+/// order; mutations at or before a map's last applied sequence are skipped.
+/// Applied mutations are not republished.
+#[derive(Debug, Clone, Default)]
+pub struct ApplyRemoteMapMutations(pub Vec<MapMutationEvent>);
+
+impl Command for ApplyRemoteMapMutations {
+    fn apply(mut self, world: &mut World) {
+        self.0
+            .sort_by_key(|mutation| (mutation.map_id, mutation.sequence));
+
+        let mut map_query = world.query::<(Entity, &ReplicatedMap)>();
+        let maps: HashMap<u32, Entity> = map_query
+            .iter(world)
+            .map(|(entity, replicated)| (replicated.id, entity))
+            .collect();
+
+        for event in self.0 {
+            let Some(&map) = maps.get(&event.map_id) else {
+                warn!("Mutation for unknown replicated map {}", event.map_id);
+                continue;
+            };
+            let Some(mut replicated) = world.get_mut::<ReplicatedMap>(map) else {
+                continue;
+            };
+            if replicated
+                .last_applied
+                .is_some_and(|last| event.sequence <= last)
+            {
+                continue;
+            }
+            let expected = replicated.last_applied.map_or(0, |last| last + 1);
+            if event.sequence != expected {
+                warn!(
+                    "Map {} mutation stream skipped from {} to {}",
+                    event.map_id, expected, event.sequence
+                );
+            }
+            replicated.last_applied = Some(event.sequence);
+            replicated.applying_remote = true;
+
+            apply_mutation(world, map, &event.mutation);
+
+            if let Some(mut replicated) = world.get_mut::<ReplicatedMap>(map) {
+                replicated.applying_remote = false;
+            }
+        }
+    }
+}
+
+/// Encode a tile as a Tiled GID, including flip flags.
+pub fn tile_gid(tile: &TileInstance, tilesets: &HashMap<u32, TilesetReference>) -> Option<u32> {
+    let tileset = tilesets
+        .values()
+        .find(|tileset| tileset.handle.id() == tile.tileset_handle.id())?;
+    let mut gid = tileset.first_gid + tile.tile_id;
+    if tile.flipped_h {
+        gid |= FLIPPED_HORIZONTALLY_FLAG;
+    }
+    if tile.flipped_v {
+        gid |= FLIPPED_VERTICALLY_FLAG;
+    }
+    if tile.flipped_d {
+        gid |= FLIPPED_DIAGONALLY_FLAG;
+    }
+    Some(gid)
+}
+
+/// Decode a Tiled GID into a tile, using the map's tilesets.
+///
+/// Returns `None` for the empty GID (0) and GIDs outside every tileset.
+pub fn tile_from_gid(gid: u32, tilesets: &HashMap<u32, TilesetReference>) -> Option<TileInstance> {
+    let id = gid & !FLIP_FLAGS;
+    if id == 0 {
+        return None;
+    }
+    let tileset = tilesets
+        .values()
+        .filter(|tileset| tileset.first_gid <= id)
+        .max_by_key(|tileset| tileset.first_gid)?;
+    let tile_id = id - tileset.first_gid;
+    Some(TileInstance {
+        gid: tile_id,
+        tileset_handle: tileset.handle.clone(),
+        tile_id,
+        flipped_h: gid & FLIPPED_HORIZONTALLY_FLAG != 0,
+        flipped_v: gid & FLIPPED_VERTICALLY_FLAG != 0,
+        flipped_d: gid & FLIPPED_DIAGONALLY_FLAG != 0,
+        variant: None,
+    })
+}
+
+/// Apply a mutation to `map`. Returns `false` if its target doesn't exist.
+fn apply_mutation(world: &mut World, map: Entity, mutation: &MapMutation) -> bool {
+    match mutation {
+        MapMutation::SetTile {
+            layer_id,
+            x,
+            y,
+            gid,
+        } => {
+            let Some(tilesets) = map_tilesets(world, map) else {
+                return false;
+            };
+            let mut layer_query = world.query::<(Entity, &LayerId, &TiledLayerMapOf)>();
+            let Some(layer) = layer_query
+                .iter(world)
+                .find(|(_, id, map_of)| id.0 == *layer_id && map_of.0 == map)
+                .map(|(entity, ..)| entity)
+            else {
+                return false;
+            };
+            let Some(mut data) = world.get_mut::<TileLayerData>(layer) else {
+                return false;
+            };
+            if *x >= data.width || *y >= data.height {
+                return false;
+            }
+            data.set(*x, *y, tile_from_gid(*gid, &tilesets));
+            world.trigger(TilesModified {
+                entity: layer,
+                tiles: vec![UVec2::new(*x, *y)],
+            });
+            true
+        }
+        MapMutation::MoveObject {
+            object_id,
+            translation,
+        } => {
+            let Some(object) = find_object(world, map, *object_id) else {
+                return false;
+            };
+            let Some(mut transform) = world.get_mut::<Transform>(object) else {
+                return false;
+            };
+            transform.translation = Vec3::from_array(*translation);
+            true
+        }
+        MapMutation::RemoveObject { object_id } => {
+            let Some(object) = find_object(world, map, *object_id) else {
+                return false;
+            };
+            world.entity_mut(object).despawn();
+            true
+        }
+    }
+}
+
+fn map_tilesets(world: &World, map: Entity) -> Option<HashMap<u32, TilesetReference>> {
+    let handle = &world.get::<TiledMap>(map)?.handle;
+    let map_asset = world.get_resource::<Assets<TiledMapAsset>>()?.get(handle)?;
+    Some(map_asset.tilesets.clone())
+}
+
+fn find_object(world: &mut World, map: Entity, object_id: u32) -> Option<Entity> {
+    let mut object_query = world.query::<(Entity, &ObjectId, &TiledObjectMapOf)>();
+    object_query
+        .iter(world)
+        .find(|(_, id, map_of)| id.0 == object_id && map_of.0 == map)
+        .map(|(entity, ..)| entity)
+}
+
+/// Publish a mutation of `map` with the map's next sequence number.
+fn publish(world: &mut World, map: Entity, mutation: MapMutation) {
+    let Some(mut replicated) = world.get_mut::<ReplicatedMap>(map) else {
+        return;
+    };
+    if replicated.applying_remote {
+        return;
+    }
+    let event = MapMutationEvent {
+        map_id: replicated.id,
+        sequence: replicated.next_sequence,
+        mutation,
+    };
+    replicated.next_sequence += 1;
+    world.trigger(event);
+}
+
+/// Observer that publishes tile changes of replicated maps.
+pub fn on_tiles_modified_replicate(
+    trigger: On<TilesModified>,
+    layer_query: Query<(&LayerId, &TiledLayerMapOf, &TileLayerData)>,
+    map_query: Query<(&ReplicatedMap, &TiledMap)>,
+    map_assets: Res<Assets<TiledMapAsset>>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let Ok((layer_id, map_of, data)) = layer_query.get(event.entity) else {
+        return;
+    };
+    let Ok((replicated, tiled_map)) = map_query.get(map_of.0) else {
+        return;
+    };
+    if replicated.applying_remote {
+        return;
+    }
+    let Some(map_asset) = map_assets.get(&tiled_map.handle) else {
+        return;
+    };
+
+    let map = map_of.0;
+    for position in &event.tiles {
+        let gid = data
+            .get(position.x, position.y)
+            .and_then(|tile| tile_gid(tile, &map_asset.tilesets))
+            .unwrap_or(0);
+        let mutation = MapMutation::SetTile {
+            layer_id: layer_id.0,
+            x: position.x,
+            y: position.y,
+            gid,
+        };
+        commands.queue(move |world: &mut World| publish(world, map, mutation));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tilesets() -> HashMap<u32, TilesetReference> {
+        [
+            (
+                0,
+                TilesetReference {
+                    handle: Handle::default(),
+                    first_gid: 1,
+                },
+            ),
+            (
+                1,
+                TilesetReference {
+                    handle: bevy::asset::uuid_handle!("1347c9b7-c46a-48e7-b7b8-023a354b7cac"),
+                    first_gid: 100,
+                },
+            ),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_gid_roundtrip() {
+        let tilesets = tilesets();
+        let tile = tile_from_gid(105 | FLIPPED_VERTICALLY_FLAG, &tilesets).unwrap();
+        assert_eq!(tile.tile_id, 5);
+        assert!(tile.flipped_v && !tile.flipped_h);
+        assert_eq!(tile.tileset_handle.id(), tilesets[&1].handle.id());
+        assert_eq!(
+            tile_gid(&tile, &tilesets),
+            Some(105 | FLIPPED_VERTICALLY_FLAG)
+        );
+        assert!(tile_from_gid(0, &tilesets).is_none());
+    }
+
+    #[test]
+    fn test_remote_mutations_apply_in_order() {
+        let mut world = World::new();
+        let map = world.spawn(ReplicatedMap::new(4)).id();
+        let object = world
+            .spawn((ObjectId(1), TiledObjectMapOf(map), Transform::default()))
+            .id();
+
+        let moved = |sequence, x| MapMutationEvent {
+            map_id: 4,
+            sequence,
+            mutation: MapMutation::MoveObject {
+                object_id: 1,
+                translation: [x, 0.0, 0.0],
+            },
+        };
+
+        // Out of order: applied by sequence, so the last move wins
+        ApplyRemoteMapMutations(vec![moved(1, 20.0), moved(0, 10.0)]).apply(&mut world);
+        assert_eq!(world.get::<Transform>(object).unwrap().translation.x, 20.0);
+        assert_eq!(
+            world.get::<ReplicatedMap>(map).unwrap().last_applied(),
+            Some(1)
+        );
+
+        // Duplicates are skipped
+        ApplyRemoteMapMutations(vec![moved(0, 10.0)]).apply(&mut world);
+        assert_eq!(world.get::<Transform>(object).unwrap().translation.x, 20.0);
+    }
+}
@@ -0,0 +1,402 @@
+//! Road networks from polyline objects.
+//!
+//! Polyline objects classed `"Road"`, or in an object layer classed `"Road"`,
+//! get a [`RoadSegment`], and all road segments of spawned maps are joined into
+//! the [`RoadNetwork`] resource: polylines whose endpoints are within
+//! [`RoadNetworkConfig::epsilon`] of each other share a node, and each polyline
+//! becomes an edge between its two end nodes. Traffic and NPC routing can then
+//! work on the graph instead of raw objects:
+//!
+//! ```rust,ignore
+//! fn route_car(network: Res<RoadNetwork>, mut cars: Query<(&Transform, &mut Route)>) {
+//!     for (transform, mut route) in &mut cars {
+//!         let Some(from) = network.nearest_node(transform.translation.truncate()) else {
+//!             continue;
+//!         };
+//!         if let Some(edges) = network.shortest_path(from, route.destination) {
+//!             route.edges = edges;
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Recognized properties (on the object, remappable with [`PropertyKeyConfig`]):
+//!
+//! - `speed_limit` (float): speed limit of the road, in game units
+//! - `width` (float): width of the road in pixels
+//!
+//! Only endpoints are joined: a road ending in the middle of another road
+//! needs a vertex split in Tiled. Roads are two-way. The network is rebuilt in
+//! `PostUpdate`, after transform propagation, whenever road segments are added,
+//! moved or removed, and [`RoadNetwork::build`] builds one from any polylines.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use bevy::prelude::*;
+use tiled::{Properties, PropertyValue};
+
+use crate::components::{LayerClass, ObjectClass, TiledObject};
+use crate::events::ObjectSpawned;
+use crate::properties::PropertyKeyConfig;
+
+/// Class of polyline objects and object layers that form roads.
+pub const ROAD_CLASS: &str = "Road";
+
+/// Property setting a road's speed limit.
+pub const SPEED_LIMIT_PROPERTY: &str = "speed_limit";
+
+/// Property setting a road's width.
+pub const ROAD_WIDTH_PROPERTY: &str = "width";
+
+/// A polyline object that is part of the [`RoadNetwork`].
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq)]
+#[reflect(Component)]
+pub struct RoadSegment {
+    /// Speed limit, if set
+    pub speed_limit: Option<f32>,
+    /// Road width in pixels, if set
+    pub width: Option<f32>,
+}
+
+impl RoadSegment {
+    /// Read a road segment from object properties.
+    pub fn from_properties(properties: &Properties, keys: &PropertyKeyConfig) -> Self {
+        let number = |key: &str| match keys.get(properties, key)? {
+            PropertyValue::FloatValue(value) => Some(*value),
+            PropertyValue::IntValue(value) => Some(*value as f32),
+            _ => None,
+        };
+        Self {
+            speed_limit: number(SPEED_LIMIT_PROPERTY),
+            width: number(ROAD_WIDTH_PROPERTY),
+        }
+    }
+}
+
+/// Settings for building the [`RoadNetwork`].
+#[derive(Resource, Debug, Clone)]
+pub struct RoadNetworkConfig {
+    /// Largest distance, in pixels, between endpoints that are joined
+    /// (default: 1.0)
+    pub epsilon: f32,
+}
+
+impl Default for RoadNetworkConfig {
+    fn default() -> Self {
+        Self { epsilon: 1.0 }
+    }
+}
+
+/// A junction or dead end of the [`RoadNetwork`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoadNode {
+    /// World position
+    pub position: Vec2,
+    /// Indices of the edges meeting here
+    pub edges: Vec<usize>,
+}
+
+/// A road between two nodes of the [`RoadNetwork`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoadEdge {
+    /// The polyline object
+    pub entity: Entity,
+    /// Node at the polyline's first vertex
+    pub start: usize,
+    /// Node at the polyline's last vertex
+    pub end: usize,
+    /// World positions of the polyline's vertices, from `start` to `end`
+    pub points: Vec<Vec2>,
+    /// Length along the points
+    pub length: f32,
+    /// Speed limit, if set
+    pub speed_limit: Option<f32>,
+    /// Road width in pixels, if set
+    pub width: Option<f32>,
+}
+
+impl RoadEdge {
+    /// The node at the other end of the edge from `node`.
+    pub fn other(&self, node: usize) -> usize {
+        if node == self.start {
+            self.end
+        } else {
+            self.start
+        }
+    }
+}
+
+/// Graph of the roads of all spawned maps.
+///
+/// See the [module docs](self).
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct RoadNetwork {
+    /// Junctions and dead ends
+    pub nodes: Vec<RoadNode>,
+    /// Roads between nodes
+    pub edges: Vec<RoadEdge>,
+}
+
+impl RoadNetwork {
+    /// Build a network from polylines in world space.
+    ///
+    /// Endpoints within `epsilon` of an existing node join it; polylines with
+    /// fewer than two points are skipped.
+    pub fn build(
+        polylines: impl IntoIterator<Item = (Entity, Vec<Vec2>, RoadSegment)>,
+        epsilon: f32,
+    ) -> Self {
+        let mut network = Self::default();
+        for (entity, points, segment) in polylines {
+            if points.len() < 2 {
+                continue;
+            }
+            let start = network.node_at(points[0], epsilon);
+            let end = network.node_at(points[points.len() - 1], epsilon);
+            let index = network.edges.len();
+            network.nodes[start].edges.push(index);
+            if end != start {
+                network.nodes[end].edges.push(index);
+            }
+            network.edges.push(RoadEdge {
+                entity,
+                start,
+                end,
+                length: points
+                    .windows(2)
+                    .map(|pair| pair[0].distance(pair[1]))
+                    .sum(),
+                points,
+                speed_limit: segment.speed_limit,
+                width: segment.width,
+            });
+        }
+        network
+    }
+
+    /// Index of the node within `epsilon` of `position`, added if there is none.
+    fn node_at(&mut self, position: Vec2, epsilon: f32) -> usize {
+        if let Some(index) = self
+            .nodes
+            .iter()
+            .position(|node| node.position.distance(position) <= epsilon)
+        {
+            return index;
+        }
+        self.nodes.push(RoadNode {
+            position,
+            edges: Vec::new(),
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Index of the node closest to `position`.
+    pub fn nearest_node(&self, position: Vec2) -> Option<usize> {
+        (0..self.nodes.len()).min_by(|a, b| {
+            let distance = |node: &usize| self.nodes[*node].position.distance_squared(position);
+            distance(a).total_cmp(&distance(b))
+        })
+    }
+
+    /// Iterate the edges at `node`, with the node at their other end.
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.nodes
+            .get(node)
+            .into_iter()
+            .flat_map(|node| &node.edges)
+            .map(move |edge| (*edge, self.edges[*edge].other(node)))
+    }
+
+    /// Shortest route from node `from` to node `to`, as edge indices in order.
+    ///
+    /// Returns an empty route if `from == to`, and `None` if `to` can't be
+    /// reached.
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        if from >= self.nodes.len() || to >= self.nodes.len() {
+            return None;
+        }
+        let mut distances = vec![f32::INFINITY; self.nodes.len()];
+        let mut previous: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut queue = BinaryHeap::new();
+        distances[from] = 0.0;
+        queue.push(Visit {
+            distance: 0.0,
+            node: from,
+        });
+
+        while let Some(Visit { distance, node }) = queue.pop() {
+            if node == to {
+                break;
+            }
+            if distance > distances[node] {
+                continue;
+            }
+            for (edge, next) in self.neighbors(node) {
+                let next_distance = distance + self.edges[edge].length;
+                if next_distance < distances[next] {
+                    distances[next] = next_distance;
+                    previous[next] = Some(edge);
+                    queue.push(Visit {
+                        distance: next_distance,
+                        node: next,
+                    });
+                }
+            }
+        }
+
+        if distances[to].is_infinite() {
+            return None;
+        }
+        let mut route = Vec::new();
+        let mut node = to;
+        while let Some(edge) = previous[node] {
+            route.push(edge);
+            node = self.edges[edge].other(node);
+        }
+        route.reverse();
+        Some(route)
+    }
+}
+
+/// A node to visit in [`RoadNetwork::shortest_path`], closest first.
+#[derive(PartialEq)]
+struct Visit {
+    distance: f32,
+    node: usize,
+}
+
+impl Eq for Visit {}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, as `BinaryHeap` is a max-heap
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Observer giving polyline objects classed [`ROAD_CLASS`], or in a layer
+/// classed [`ROAD_CLASS`], a [`RoadSegment`].
+pub fn on_road_object_spawned(
+    trigger: On<ObjectSpawned>,
+    object_query: Query<(&TiledObject, Option<&ObjectClass>, Option<&ChildOf>)>,
+    layer_query: Query<&LayerClass>,
+    keys: Res<PropertyKeyConfig>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let Ok((object, class, parent)) = object_query.get(event.entity) else {
+        return;
+    };
+    let road_object = class.is_some_and(|class| class.0 == ROAD_CLASS);
+    let road_layer = parent
+        .and_then(|parent| layer_query.get(parent.parent()).ok())
+        .is_some_and(|class| class.0 == ROAD_CLASS);
+    if !road_object && !road_layer {
+        return;
+    }
+    if !matches!(object, TiledObject::Polyline { .. }) {
+        // Road layers may hold other objects (signs, spawn points), only warn
+        // about objects classed as roads themselves
+        if road_object {
+            warn!(
+                "Road object {} is not a polyline, ignoring it",
+                event.object_id
+            );
+        }
+        return;
+    }
+    commands
+        .entity(event.entity)
+        .insert(RoadSegment::from_properties(&event.properties, &keys));
+}
+
+/// System rebuilding the [`RoadNetwork`] when road segments change.
+pub fn rebuild_road_network(
+    mut network: ResMut<RoadNetwork>,
+    config: Res<RoadNetworkConfig>,
+    segments: Query<(Entity, &TiledObject, &RoadSegment, &GlobalTransform)>,
+    changed: Query<
+        (),
+        (
+            With<RoadSegment>,
+            Or<(Changed<RoadSegment>, Changed<GlobalTransform>)>,
+        ),
+    >,
+    mut removed: RemovedComponents<RoadSegment>,
+) {
+    let removed = removed.read().count() > 0;
+    if changed.is_empty() && !removed && !config.is_changed() {
+        return;
+    }
+    let polylines = segments
+        .iter()
+        .filter_map(|(entity, object, segment, transform)| {
+            let TiledObject::Polyline { vertices } = object else {
+                return None;
+            };
+            let points = vertices
+                .iter()
+                .map(|vertex| transform.transform_point(vertex.extend(0.0)).truncate())
+                .collect();
+            Some((entity, points, *segment))
+        });
+    *network = RoadNetwork::build(polylines, config.epsilon);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_road_network_joins_endpoints_and_routes() {
+        let entity = |index| Entity::from_raw_u32(index).unwrap();
+        let segment = |speed_limit| RoadSegment {
+            speed_limit,
+            width: None,
+        };
+        // A - B - C, plus a long detour A - D - C, and a separate road E - F
+        let network = RoadNetwork::build(
+            [
+                (
+                    entity(1),
+                    vec![Vec2::ZERO, Vec2::new(10.0, 0.0)],
+                    segment(Some(50.0)),
+                ),
+                (
+                    entity(2),
+                    vec![Vec2::new(10.3, 0.2), Vec2::new(20.0, 0.0)],
+                    segment(None),
+                ),
+                (
+                    entity(3),
+                    vec![Vec2::ZERO, Vec2::new(0.0, 30.0), Vec2::new(20.0, 0.0)],
+                    segment(None),
+                ),
+                (
+                    entity(4),
+                    vec![Vec2::new(100.0, 0.0), Vec2::new(110.0, 0.0)],
+                    segment(None),
+                ),
+            ],
+            1.0,
+        );
+
+        assert_eq!(network.nodes.len(), 5);
+        assert_eq!(network.edges.len(), 4);
+        assert_eq!(network.edges[0].speed_limit, Some(50.0));
+        let a = network.nearest_node(Vec2::new(-1.0, 0.0)).unwrap();
+        let c = network.nearest_node(Vec2::new(21.0, 0.0)).unwrap();
+        assert_eq!(network.neighbors(a).count(), 2);
+        assert_eq!(network.shortest_path(a, c), Some(vec![0, 1]));
+        assert_eq!(network.shortest_path(a, a), Some(vec![]));
+        let e = network.nearest_node(Vec2::new(100.0, 0.0)).unwrap();
+        assert_eq!(network.shortest_path(a, e), None);
+    }
+}
@@ -0,0 +1,456 @@
+//! Save/load of runtime map changes as compact diffs.
+//!
+//! Save games usually only need what changed since the map was loaded. Every
+//! spawned map gets a [`TiledMapChanges`] tracker recording modified tiles (from
+//! [`TilesModified`]) and the spawn position of each object. [`MapDiff::capture`]
+//! turns that into a serde-serializable [`MapDiff`] holding only changed tiles,
+//! grouped into chunks, and moved or removed objects.
+//!
+//! To restore, spawn the map with a [`RestoreMapDiff`] component (or insert it
+//! before respawning); the diff is reapplied as soon as the map has spawned.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn save(world: &mut World) {
+//!     let map = world.query_filtered::<Entity, With<TiledMap>>().single(world).unwrap();
+//!     if let Some(diff) = MapDiff::capture(world, map) {
+//!         std::fs::write("save.json", serde_json::to_string(&diff).unwrap()).unwrap();
+//!     }
+//! }
+//!
+//! fn load(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     let diff: MapDiff = serde_json::from_str(&std::fs::read_to_string("save.json").unwrap()).unwrap();
+//!     commands.spawn((
+//!         TiledMap { handle: asset_server.load("maps/level1.tmx") },
+//!         RestoreMapDiff(diff),
+//!     ));
+//! }
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::TiledMapAsset;
+use serde::{Deserialize, Serialize};
+
+use crate::components::tile::{TileInstance, TileLayerData};
+use crate::components::{LayerId, ObjectId, TiledLayerMapOf, TiledMap, TiledObjectMapOf};
+use crate::events::{MapSpawned, TilesModified};
+
+/// Width and height of the tile chunks a [`MapDiff`] groups changes into.
+pub const MAP_DIFF_CHUNK_SIZE: u32 = 16;
+
+/// Runtime changes to a map relative to its source file.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct MapDiff {
+    /// Changed tiles, per tile layer
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layers: Vec<LayerDiff>,
+    /// Moved or removed objects
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<ObjectDiff>,
+}
+
+/// Changed tiles in one tile layer.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LayerDiff {
+    /// The layer's ID from Tiled
+    pub layer_id: u32,
+    /// Changed tiles, grouped into chunks
+    pub chunks: Vec<TileChunkDiff>,
+}
+
+/// Changed tiles in one [`MAP_DIFF_CHUNK_SIZE`]-sized chunk of a layer.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TileChunkDiff {
+    /// Chunk coordinates (tile position / chunk size)
+    pub chunk: [u32; 2],
+    /// Changed tiles in this chunk
+    pub tiles: Vec<TileDiff>,
+}
+
+/// A single changed tile.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TileDiff {
+    /// Position within the chunk (Tiled coordinates, Y-down)
+    pub offset: [u8; 2],
+    /// The new tile, or `None` if it was cleared
+    pub tile: Option<SavedTile>,
+}
+
+/// A tile in serializable form.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SavedTile {
+    /// Index of the tileset in the map (matches `LayerTile::tileset_index()`)
+    pub tileset: u32,
+    /// Local tile ID within the tileset
+    pub tile_id: u32,
+    /// Horizontal flip flag
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub flipped_h: bool,
+    /// Vertical flip flag
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub flipped_v: bool,
+    /// Diagonal flip flag
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub flipped_d: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// A changed object.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ObjectDiff {
+    /// The object moved to a new local translation.
+    Moved {
+        /// The object's ID from Tiled
+        object_id: u32,
+        /// New local translation
+        translation: [f32; 3],
+    },
+    /// The object was despawned.
+    Removed {
+        /// The object's ID from Tiled
+        object_id: u32,
+    },
+}
+
+/// Tracks what changed in a map since it spawned.
+///
+/// Inserted on map entities when [`MapSpawned`] fires, and reset on respawn.
+#[derive(Component, Debug, Clone, Default)]
+pub struct TiledMapChanges {
+    /// Modified tile positions, per layer ID
+    pub tiles: HashMap<u32, HashSet<UVec2>>,
+    /// Local translation of each object when the map spawned, per object ID
+    pub object_origins: HashMap<u32, Vec3>,
+}
+
+/// A diff to reapply once the map entity has spawned.
+///
+/// Removed from the map entity after it has been applied.
+#[derive(Component, Debug, Clone)]
+pub struct RestoreMapDiff(pub MapDiff);
+
+impl MapDiff {
+    /// Check if the diff has no changes.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty() && self.objects.is_empty()
+    }
+
+    /// Capture the changes to `map` since it spawned.
+    ///
+    /// Returns `None` if the map hasn't spawned yet (no [`TiledMapChanges`]) or its
+    /// asset is missing.
+    pub fn capture(world: &mut World, map: Entity) -> Option<Self> {
+        let changes = world.get::<TiledMapChanges>(map)?.clone();
+        let handle = world.get::<TiledMap>(map)?.handle.clone();
+        let tileset_indices: HashMap<_, _> = world
+            .resource::<Assets<TiledMapAsset>>()
+            .get(&handle)?
+            .tilesets
+            .iter()
+            .map(|(index, tileset)| (tileset.handle.id(), *index))
+            .collect();
+
+        let mut diff = MapDiff::default();
+
+        let mut layer_query = world.query::<(&LayerId, &TileLayerData, &TiledLayerMapOf)>();
+        for (layer_id, data, map_of) in layer_query.iter(world) {
+            let Some(positions) = changes.tiles.get(&layer_id.0) else {
+                continue;
+            };
+            if map_of.0 != map {
+                continue;
+            }
+
+            let mut chunks: BTreeMap<[u32; 2], Vec<TileDiff>> = BTreeMap::new();
+            for position in positions {
+                let tile = data.get(position.x, position.y).and_then(|tile| {
+                    let Some(tileset) = tileset_indices.get(&tile.tileset_handle.id()) else {
+                        warn!(
+                            "Tile at {} uses a tileset outside the map, not saved",
+                            position
+                        );
+                        return None;
+                    };
+                    Some(SavedTile {
+                        tileset: *tileset,
+                        tile_id: tile.tile_id,
+                        flipped_h: tile.flipped_h,
+                        flipped_v: tile.flipped_v,
+                        flipped_d: tile.flipped_d,
+                    })
+                });
+                chunks
+                    .entry([
+                        position.x / MAP_DIFF_CHUNK_SIZE,
+                        position.y / MAP_DIFF_CHUNK_SIZE,
+                    ])
+                    .or_default()
+                    .push(TileDiff {
+                        offset: [
+                            (position.x % MAP_DIFF_CHUNK_SIZE) as u8,
+                            (position.y % MAP_DIFF_CHUNK_SIZE) as u8,
+                        ],
+                        tile,
+                    });
+            }
+
+            diff.layers.push(LayerDiff {
+                layer_id: layer_id.0,
+                chunks: chunks
+                    .into_iter()
+                    .map(|(chunk, mut tiles)| {
+                        tiles.sort_by_key(|tile| (tile.offset[1], tile.offset[0]));
+                        TileChunkDiff { chunk, tiles }
+                    })
+                    .collect(),
+            });
+        }
+        diff.layers.sort_by_key(|layer| layer.layer_id);
+
+        let mut object_query = world.query::<(&ObjectId, &Transform, &TiledObjectMapOf)>();
+        let mut remaining: HashSet<u32> = changes.object_origins.keys().copied().collect();
+        for (object_id, transform, map_of) in object_query.iter(world) {
+            if map_of.0 != map {
+                continue;
+            }
+            remaining.remove(&object_id.0);
+            let moved = changes
+                .object_origins
+                .get(&object_id.0)
+                .is_some_and(|origin| *origin != transform.translation);
+            if moved {
+                diff.objects.push(ObjectDiff::Moved {
+                    object_id: object_id.0,
+                    translation: transform.translation.to_array(),
+                });
+            }
+        }
+        diff.objects.extend(
+            remaining
+                .into_iter()
+                .map(|object_id| ObjectDiff::Removed { object_id }),
+        );
+        diff.objects.sort_by_key(|object| match object {
+            ObjectDiff::Moved { object_id, .. } | ObjectDiff::Removed { object_id } => *object_id,
+        });
+
+        Some(diff)
+    }
+
+    /// Apply the diff to a spawned `map`.
+    ///
+    /// Changed tiles fire [`TilesModified`], so renderers and colliders rebuild.
+    /// Layers and objects that no longer exist in the map are skipped.
+    pub fn apply(&self, world: &mut World, map: Entity) {
+        let Some(handle) = world
+            .get::<TiledMap>(map)
+            .map(|tiled_map| tiled_map.handle.clone())
+        else {
+            return;
+        };
+        let Some(tilesets) = world
+            .resource::<Assets<TiledMapAsset>>()
+            .get(&handle)
+            .map(|map_asset| map_asset.tilesets.clone())
+        else {
+            return;
+        };
+
+        let mut layer_query = world.query::<(Entity, &LayerId, &TiledLayerMapOf)>();
+        let layers: HashMap<u32, Entity> = layer_query
+            .iter(world)
+            .filter(|(_, _, map_of)| map_of.0 == map)
+            .map(|(entity, layer_id, _)| (layer_id.0, entity))
+            .collect();
+
+        for layer_diff in &self.layers {
+            let Some(&layer) = layers.get(&layer_diff.layer_id) else {
+                warn!(
+                    "Map diff references missing layer {}, skipping",
+                    layer_diff.layer_id
+                );
+                continue;
+            };
+            let Some(mut data) = world.get_mut::<TileLayerData>(layer) else {
+                continue;
+            };
+
+            let mut modified = Vec::new();
+            for chunk in &layer_diff.chunks {
+                for tile_diff in &chunk.tiles {
+                    let position = UVec2::new(
+                        chunk.chunk[0] * MAP_DIFF_CHUNK_SIZE + u32::from(tile_diff.offset[0]),
+                        chunk.chunk[1] * MAP_DIFF_CHUNK_SIZE + u32::from(tile_diff.offset[1]),
+                    );
+                    let tile = tile_diff.tile.and_then(|saved| {
+                        let tileset = tilesets.get(&saved.tileset)?;
+                        Some(TileInstance {
+                            gid: saved.tile_id,
+                            tileset_handle: tileset.handle.clone(),
+                            tile_id: saved.tile_id,
+                            flipped_h: saved.flipped_h,
+                            flipped_v: saved.flipped_v,
+                            flipped_d: saved.flipped_d,
+                            variant: None,
+                        })
+                    });
+                    data.set(position.x, position.y, tile);
+                    modified.push(position);
+                }
+            }
+
+            world.trigger(TilesModified {
+                entity: layer,
+                tiles: modified,
+            });
+        }
+
+        let mut object_query = world.query::<(Entity, &ObjectId, &TiledObjectMapOf)>();
+        let objects: HashMap<u32, Entity> = object_query
+            .iter(world)
+            .filter(|(_, _, map_of)| map_of.0 == map)
+            .map(|(entity, object_id, _)| (object_id.0, entity))
+            .collect();
+
+        for object_diff in &self.objects {
+            match object_diff {
+                ObjectDiff::Moved {
+                    object_id,
+                    translation,
+                } => {
+                    if let Some(mut transform) = objects
+                        .get(object_id)
+                        .and_then(|entity| world.get_mut::<Transform>(*entity))
+                    {
+                        transform.translation = Vec3::from_array(*translation);
+                    }
+                }
+                ObjectDiff::Removed { object_id } => {
+                    if let Some(entity) = objects.get(object_id) {
+                        world.entity_mut(*entity).despawn();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Observer that starts tracking a map's changes once it has spawned, then
+/// reapplies any pending [`RestoreMapDiff`].
+pub fn on_map_spawned_track_changes(trigger: On<MapSpawned>, mut commands: Commands) {
+    let map = trigger.event().entity;
+    commands.queue(move |world: &mut World| {
+        let mut object_query = world.query::<(&ObjectId, &Transform, &TiledObjectMapOf)>();
+        let object_origins = object_query
+            .iter(world)
+            .filter(|(_, _, map_of)| map_of.0 == map)
+            .map(|(object_id, transform, _)| (object_id.0, transform.translation))
+            .collect();
+
+        let Ok(mut map_entity) = world.get_entity_mut(map) else {
+            return;
+        };
+        map_entity.insert(TiledMapChanges {
+            tiles: HashMap::new(),
+            object_origins,
+        });
+
+        if let Some(RestoreMapDiff(diff)) = map_entity.take::<RestoreMapDiff>() {
+            diff.apply(world, map);
+        }
+    });
+}
+
+/// Observer that records modified tiles in the map's [`TiledMapChanges`].
+pub fn on_tiles_modified_track_changes(
+    trigger: On<TilesModified>,
+    layer_query: Query<(&LayerId, &TiledLayerMapOf)>,
+    mut changes_query: Query<&mut TiledMapChanges>,
+) {
+    let event = trigger.event();
+    let Ok((layer_id, map_of)) = layer_query.get(event.entity) else {
+        return;
+    };
+    if let Ok(mut changes) = changes_query.get_mut(map_of.0) {
+        changes
+            .tiles
+            .entry(layer_id.0)
+            .or_default()
+            .extend(event.tiles.iter().copied());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_diff_roundtrip() {
+        let diff = MapDiff {
+            layers: vec![LayerDiff {
+                layer_id: 2,
+                chunks: vec![TileChunkDiff {
+                    chunk: [1, 0],
+                    tiles: vec![
+                        TileDiff {
+                            offset: [3, 4],
+                            tile: None,
+                        },
+                        TileDiff {
+                            offset: [5, 4],
+                            tile: Some(SavedTile {
+                                tileset: 0,
+                                tile_id: 12,
+                                flipped_h: true,
+                                flipped_v: false,
+                                flipped_d: false,
+                            }),
+                        },
+                    ],
+                }],
+            }],
+            objects: vec![
+                ObjectDiff::Moved {
+                    object_id: 7,
+                    translation: [16.0, 32.0, 0.0],
+                },
+                ObjectDiff::Removed { object_id: 9 },
+            ],
+        };
+
+        let json = serde_json::to_string(&diff).unwrap();
+        // Unset flip flags are omitted to keep saves small
+        assert!(!json.contains("flipped_v"));
+        assert_eq!(serde_json::from_str::<MapDiff>(&json).unwrap(), diff);
+        assert_eq!(
+            serde_json::from_str::<MapDiff>("{}").unwrap(),
+            MapDiff::default()
+        );
+    }
+
+    #[test]
+    fn test_track_modified_tiles() {
+        let mut app = App::new();
+        app.add_observer(on_tiles_modified_track_changes);
+
+        let world = app.world_mut();
+        let map = world.spawn(TiledMapChanges::default()).id();
+        let layer = world.spawn((LayerId(3), TiledLayerMapOf(map))).id();
+
+        world.trigger(TilesModified {
+            entity: layer,
+            tiles: vec![UVec2::new(1, 2), UVec2::new(40, 2)],
+        });
+        world.flush();
+
+        let changes = world.get::<TiledMapChanges>(map).unwrap();
+        assert_eq!(changes.tiles[&3].len(), 2);
+        assert!(changes.tiles[&3].contains(&UVec2::new(40, 2)));
+    }
+}
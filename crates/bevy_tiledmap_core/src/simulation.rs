@@ -0,0 +1,290 @@
+//! Tile simulations: cellular automata over tile layers.
+//!
+//! [`TileSimulation`] double-buffers a layer's [`TileLayerData`]: each step
+//! reads the tiles of frame N and writes the tiles of frame N + 1 into a back
+//! buffer, then swaps them, so rules never see half-updated neighbors. Steps
+//! return the tiles that changed, for [`TilesModified`], which updates the
+//! renderer and colliders of just those tiles:
+//!
+//! ```rust,ignore
+//! fn spread_fire(
+//!     mut layers: Query<(Entity, &mut TileLayerData, &mut TileSimulation), With<Forest>>,
+//!     tiles: Res<ForestTiles>,
+//!     mut commands: Commands,
+//! ) {
+//!     for (entity, mut data, mut simulation) in &mut layers {
+//!         let tiles_changed = simulation.par_step(&mut data, |cell| {
+//!             let burning = |tile: &TileInstance| tile.tile_id == tiles.fire;
+//!             match cell.tile() {
+//!                 Some(tile) if burning(tile) => Some(tiles.ash.clone()),
+//!                 Some(tile) if tile.tile_id == tiles.tree && cell.count_neighbors(burning) > 0 => {
+//!                     Some(tiles.fire_tile.clone())
+//!                 }
+//!                 tile => tile.cloned(),
+//!             }
+//!         });
+//!         if !tiles_changed.is_empty() {
+//!             commands.trigger(TilesModified { entity, tiles: tiles_changed });
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Rules are "gather" rules: each cell decides its own next tile from the
+//! current tiles around it. Moves, like falling sand, are written as two
+//! halves that agree (an empty cell below sand becomes sand, sand above an
+//! empty cell becomes empty). [`TileSimulation::step`] runs on the calling
+//! thread, [`TileSimulation::par_step`] spreads rows over the compute task
+//! pool, and [`TileSimulation::rows`] hands out the back buffer's rows for
+//! other schedulers (e.g. rayon).
+
+use bevy::prelude::*;
+use bevy::tasks::{ComputeTaskPool, ParallelSliceMut, TaskPool};
+
+use crate::components::tile::{TileInstance, TileLayerData};
+#[cfg(doc)]
+use crate::events::TilesModified;
+
+/// Offsets of the eight neighbors of a cell (Tiled coordinates, Y-down).
+const NEIGHBOR_OFFSETS: [IVec2; 8] = [
+    IVec2::new(-1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 0),
+    IVec2::new(1, 0),
+    IVec2::new(-1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(1, 1),
+];
+
+/// Read-only view of a cell and its surroundings in the current frame.
+#[derive(Debug, Clone, Copy)]
+pub struct TileCell<'a> {
+    data: &'a TileLayerData,
+    position: UVec2,
+}
+
+impl<'a> TileCell<'a> {
+    /// Grid position of the cell (Tiled coordinates, Y-down).
+    pub fn position(&self) -> UVec2 {
+        self.position
+    }
+
+    /// The cell's current tile.
+    pub fn tile(&self) -> Option<&'a TileInstance> {
+        self.data.get(self.position.x, self.position.y)
+    }
+
+    /// The current tile at an offset from the cell (Y-down, so `(0, 1)` is
+    /// the tile below). `None` for empty tiles and outside the layer.
+    pub fn get(&self, offset: IVec2) -> Option<&'a TileInstance> {
+        let position = self.position.as_ivec2() + offset;
+        if position.x < 0 || position.y < 0 {
+            return None;
+        }
+        self.data.get(position.x as u32, position.y as u32)
+    }
+
+    /// Whether the position at an offset is inside the layer.
+    pub fn in_bounds(&self, offset: IVec2) -> bool {
+        let position = self.position.as_ivec2() + offset;
+        position.x >= 0
+            && position.y >= 0
+            && (position.x as u32) < self.data.width
+            && (position.y as u32) < self.data.height
+    }
+
+    /// Count the eight surrounding tiles matching `predicate`.
+    pub fn count_neighbors(&self, predicate: impl Fn(&TileInstance) -> bool) -> usize {
+        NEIGHBOR_OFFSETS
+            .iter()
+            .filter(|offset| self.get(**offset).is_some_and(&predicate))
+            .count()
+    }
+
+    /// The whole layer, as of the current frame.
+    pub fn layer(&self) -> &'a TileLayerData {
+        self.data
+    }
+}
+
+/// A row of the next frame, handed out by [`TileSimulation::rows`].
+#[derive(Debug)]
+pub struct TileRow<'a> {
+    /// Row index (Tiled coordinates, Y-down)
+    pub y: u32,
+    /// Tiles of the next frame, starting as copies of the current ones
+    pub tiles: &'a mut [Option<TileInstance>],
+    current: &'a TileLayerData,
+}
+
+impl<'a> TileRow<'a> {
+    /// Read-only view of a cell of this row in the current frame.
+    pub fn cell(&self, x: u32) -> TileCell<'a> {
+        TileCell {
+            data: self.current,
+            position: UVec2::new(x, self.y),
+        }
+    }
+}
+
+/// Double buffer for simulating a tile layer, on the layer entity.
+///
+/// See the [module docs](self).
+#[derive(Component, Debug, Clone, Default)]
+pub struct TileSimulation {
+    back: Vec<Option<TileInstance>>,
+    steps: u64,
+}
+
+impl TileSimulation {
+    /// Number of steps run so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Run one step on the calling thread: `rule` gets each cell of the
+    /// current frame and returns its tile in the next one.
+    ///
+    /// Returns the positions of the tiles that changed.
+    pub fn step(
+        &mut self,
+        data: &mut TileLayerData,
+        rule: impl Fn(TileCell) -> Option<TileInstance>,
+    ) -> Vec<UVec2> {
+        self.resize(data);
+        let width = data.width.max(1) as usize;
+        for (index, next) in self.back.iter_mut().enumerate() {
+            *next = rule(TileCell {
+                data,
+                position: UVec2::new((index % width) as u32, (index / width) as u32),
+            });
+        }
+        self.swap(data)
+    }
+
+    /// Like [`step`](Self::step), with blocks of rows run in parallel on the
+    /// [`ComputeTaskPool`].
+    pub fn par_step(
+        &mut self,
+        data: &mut TileLayerData,
+        rule: impl Fn(TileCell) -> Option<TileInstance> + Send + Sync,
+    ) -> Vec<UVec2> {
+        self.resize(data);
+        if data.width > 0 {
+            let current = &*data;
+            let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+            let rows_per_task = data.height.div_ceil(pool.thread_num().max(1) as u32).max(1);
+            let width = data.width as usize;
+            self.back
+                .par_chunk_map_mut(pool, width * rows_per_task as usize, |block, tiles| {
+                    let first_row = block as u32 * rows_per_task;
+                    for (index, next) in tiles.iter_mut().enumerate() {
+                        *next = rule(TileCell {
+                            data: current,
+                            position: UVec2::new(
+                                (index % width) as u32,
+                                first_row + (index / width) as u32,
+                            ),
+                        });
+                    }
+                });
+        }
+        self.swap(data)
+    }
+
+    /// Hand out the rows of the next frame, for running a step with your own
+    /// scheduler. Rows are disjoint, so they can be filled in parallel; tiles
+    /// start as copies of the current frame. Call [`swap`](Self::swap) once
+    /// they are filled.
+    pub fn rows<'a>(&'a mut self, data: &'a TileLayerData) -> impl Iterator<Item = TileRow<'a>> {
+        self.back.clone_from(&data.tiles);
+        self.back
+            .chunks_mut(data.width.max(1) as usize)
+            .enumerate()
+            .map(move |(y, tiles)| TileRow {
+                y: y as u32,
+                tiles,
+                current: data,
+            })
+    }
+
+    /// Make the next frame current, returning the positions of the tiles that
+    /// changed.
+    pub fn swap(&mut self, data: &mut TileLayerData) -> Vec<UVec2> {
+        self.resize(data);
+        std::mem::swap(&mut data.tiles, &mut self.back);
+        self.steps += 1;
+        let width = data.width.max(1) as usize;
+        data.tiles
+            .iter()
+            .zip(&self.back)
+            .enumerate()
+            .filter(|(_, (next, previous))| next != previous)
+            .map(|(index, _)| UVec2::new((index % width) as u32, (index / width) as u32))
+            .collect()
+    }
+
+    /// Match the back buffer to the layer, e.g. the first time or after the
+    /// layer was resized.
+    fn resize(&mut self, data: &TileLayerData) {
+        if self.back.len() != data.tiles.len() {
+            self.back.clone_from(&data.tiles);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sand() -> TileInstance {
+        TileInstance {
+            gid: 1,
+            tileset_handle: Handle::default(),
+            tile_id: 0,
+            flipped_h: false,
+            flipped_v: false,
+            flipped_d: false,
+            variant: None,
+        }
+    }
+
+    /// Sand falls one tile per step into empty tiles below it.
+    fn falling_sand(cell: TileCell) -> Option<TileInstance> {
+        let below = IVec2::new(0, 1);
+        let above = IVec2::new(0, -1);
+        match cell.tile() {
+            Some(_) if cell.in_bounds(below) && cell.get(below).is_none() => None,
+            Some(tile) => Some(tile.clone()),
+            None => cell.get(above).cloned(),
+        }
+    }
+
+    #[test]
+    fn test_falling_sand_steps_and_reports_changes() {
+        // 2x3 layer with sand at the top of column 0
+        let mut data = TileLayerData::empty(2, 3);
+        data.set(0, 0, Some(sand()));
+        let mut parallel_data = data.clone();
+        let mut simulation = TileSimulation::default();
+        let mut parallel_simulation = TileSimulation::default();
+
+        let changed = simulation.step(&mut data, falling_sand);
+        assert_eq!(changed, vec![UVec2::new(0, 0), UVec2::new(0, 1)]);
+        assert!(data.get(0, 1).is_some());
+        assert!(data.get(0, 0).is_none());
+
+        // Resting on the bottom row, nothing changes anymore
+        assert_eq!(simulation.step(&mut data, falling_sand).len(), 2);
+        assert!(simulation.step(&mut data, falling_sand).is_empty());
+        assert!(data.get(0, 2).is_some());
+        assert_eq!(simulation.steps(), 3);
+
+        // The parallel step agrees
+        for _ in 0..3 {
+            parallel_simulation.par_step(&mut parallel_data, falling_sand);
+        }
+        assert_eq!(parallel_data.tiles, data.tiles);
+    }
+}
@@ -0,0 +1,630 @@
+//! Registered `TiledClass` component attachment.
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use bevy::prelude::*;
+use tiled::{Properties, PropertyValue};
+
+use crate::properties::error::{
+    PropertyErrorContext, PropertyErrorKind, TiledPropertyError, report_property_error,
+};
+use crate::properties::intern::hash_properties;
+use crate::properties::registry::{TiledClassInfo, TiledClassRegistry};
+use crate::systems::SpawnContext;
+
+/// Attach the component registered for an entity's Tiled class.
+///
+/// Tiled 1.10+ lets maps, layers, tiles and objects carry a "Class" field. If a
+/// `TiledClass` type is registered under that name, it is deserialized from the
+/// entity's own (merged) properties and inserted, even when no properties are set.
+/// Missing members fall back to the type's defaults.
+///
+/// Tile layers don't spawn per-tile entities, so a tile's class is applied to the
+/// tile objects that reference it instead.
+///
+/// Does nothing if `class_name` is empty or not registered. Problems with the
+/// properties are reported as [`TiledPropertyError`]s located at `location`.
+pub fn attach_class_component(
+    entity_cmd: &mut EntityCommands,
+    class_name: &str,
+    properties: &Properties,
+    location: &PropertyErrorContext,
+    context: &SpawnContext,
+    type_registry: &AppTypeRegistry,
+) {
+    let mut errors = Vec::new();
+    let component = class_component(
+        class_name,
+        properties,
+        None,
+        &mut errors,
+        context,
+        type_registry,
+    );
+    report_errors(entity_cmd, location, &errors);
+    if let Some(component) = component {
+        queue_components(
+            entity_cmd,
+            vec![Arc::from(component)],
+            context.registry,
+            type_registry,
+        );
+    }
+}
+
+/// Deserialize the component registered for a Tiled class.
+///
+/// Problems are added to `errors`, with fields prefixed by `property` for
+/// class-typed properties.
+fn class_component(
+    class_name: &str,
+    properties: &Properties,
+    property: Option<&str>,
+    errors: &mut Vec<TiledPropertyError>,
+    context: &SpawnContext,
+    type_registry: &AppTypeRegistry,
+) -> Option<Box<dyn Reflect>> {
+    if class_name.is_empty() {
+        return None;
+    }
+
+    let Some(info) = context.registry.get(class_name) else {
+        debug!(
+            "Class '{}' is not registered. Add #[derive(TiledClass)] to register it.",
+            class_name
+        );
+        return None;
+    };
+
+    check_fields(info, properties, property, errors);
+    match (info.from_properties)(properties, Some(context.asset_server)) {
+        Ok(component_box) => {
+            if has_reflect_component(&*component_box, class_name, type_registry) {
+                debug!("Queued component '{}' for attachment (class)", class_name);
+                Some(component_box)
+            } else {
+                None
+            }
+        }
+        Err(e) => {
+            errors.push(class_error(
+                class_name,
+                property,
+                PropertyErrorKind::Deserialize(e),
+            ));
+            None
+        }
+    }
+}
+
+/// Record authored values that don't fit their field's type (the field keeps
+/// its default) or are outside its `#[tiled(min, max)]` range (the value is
+/// still used as authored).
+fn check_fields(
+    info: &TiledClassInfo,
+    properties: &Properties,
+    property: Option<&str>,
+    errors: &mut Vec<TiledPropertyError>,
+) {
+    for field in (info.invalid_fields)(properties) {
+        let value = properties[field].clone();
+        errors.push(class_error(
+            info.name,
+            Some(&field_path(property, field)),
+            PropertyErrorKind::InvalidValue(value),
+        ));
+    }
+    for (field, value) in info.out_of_range(properties) {
+        errors.push(class_error(
+            info.name,
+            Some(&field_path(property, field.name)),
+            PropertyErrorKind::OutOfRange {
+                value: value.clone(),
+                min: field.min,
+                max: field.max,
+            },
+        ));
+    }
+}
+
+/// Name a field, prefixed by the class-typed property containing it, if any.
+fn field_path(property: Option<&str>, field: &str) -> String {
+    match property {
+        Some(property) => format!("{property}.{field}"),
+        None => field.to_string(),
+    }
+}
+
+/// Build an error of a class, to be located by [`report_errors`].
+fn class_error(class: &str, field: Option<&str>, kind: PropertyErrorKind) -> TiledPropertyError {
+    TiledPropertyError {
+        context: PropertyErrorContext::default().with_class(class, field),
+        kind,
+    }
+}
+
+/// Report errors of classes at the location of the entity they were attached to.
+fn report_errors(
+    entity_cmd: &mut EntityCommands,
+    location: &PropertyErrorContext,
+    errors: &[TiledPropertyError],
+) {
+    for error in errors {
+        let context = PropertyErrorContext {
+            class: error.context.class.clone(),
+            field: error.context.field.clone(),
+            ..location.clone()
+        };
+        report_property_error(
+            &mut entity_cmd.commands(),
+            TiledPropertyError {
+                context,
+                kind: error.kind.clone(),
+            },
+        );
+    }
+}
+
+/// Attach registered components from class-typed and enum-typed properties.
+///
+/// Iterates through the object's properties looking for:
+/// 1. Class-typed values (`PropertyValue::ClassValue`) - deserializes structs and
+///    complex enums (by the enum's class, or a variant class of class-name enums)
+/// 2. String values that match registered enum types - deserializes enums
+///
+/// For enum properties, the tiled crate loses the `propertytype` attribute, so we
+/// infer the type from the property key name by converting `snake_case` to `PascalCase`.
+///
+/// Problems with the properties are reported as [`TiledPropertyError`]s located
+/// at `location`.
+pub fn attach_registered_components(
+    entity_cmd: &mut EntityCommands,
+    properties: &Properties,
+    location: &PropertyErrorContext,
+    context: &SpawnContext,
+    type_registry: &AppTypeRegistry,
+) {
+    let mut errors = Vec::new();
+    let components = registered_components(properties, &mut errors, context, type_registry)
+        .into_iter()
+        .map(Arc::from)
+        .collect();
+    report_errors(entity_cmd, location, &errors);
+    queue_components(entity_cmd, components, context.registry, type_registry);
+}
+
+/// Deserialize the components of class-typed and enum-typed properties.
+///
+/// Problems are added to `errors`.
+fn registered_components(
+    properties: &Properties,
+    errors: &mut Vec<TiledPropertyError>,
+    context: &SpawnContext,
+    type_registry: &AppTypeRegistry,
+) -> Vec<Box<dyn Reflect>> {
+    // Collect components to insert (can't insert during iteration due to borrow checker)
+    let mut components_to_insert: Vec<Box<dyn Reflect>> = Vec::new();
+
+    // Iterate all properties looking for class-typed and enum-typed ones
+    for (key, value) in properties.iter() {
+        match value {
+            // Handle class-typed properties (structs)
+            PropertyValue::ClassValue {
+                property_type,
+                properties: class_props,
+            } => {
+                // Try to find this class in the registry
+                if context.registry.get(property_type).is_some() {
+                    components_to_insert.extend(class_component(
+                        property_type,
+                        class_props,
+                        Some(key),
+                        errors,
+                        context,
+                        type_registry,
+                    ));
+                } else if let Some(enum_info) = context.registry.get_enum_for_class(property_type) {
+                    // Complex enums, including variant classes of class-name enums
+                    match (enum_info.from_property)(value) {
+                        Ok(component_box) => {
+                            if has_reflect_component(&*component_box, enum_info.name, type_registry)
+                            {
+                                components_to_insert.push(component_box);
+                                debug!(
+                                    "Queued enum component '{}' for attachment (property: '{}')",
+                                    enum_info.name, key
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            errors.push(class_error(
+                                enum_info.name,
+                                Some(key),
+                                PropertyErrorKind::Deserialize(e),
+                            ));
+                        }
+                    }
+                } else {
+                    debug!(
+                        "Class property '{}' has type '{}' which is not registered. \
+                        Add #[derive(TiledClass)] to register it.",
+                        key, property_type
+                    );
+                }
+            }
+
+            // Handle string values that might be enum properties
+            // The tiled crate loses the `propertytype` attribute for non-class properties,
+            // so we infer the type from the property key name (snake_case -> PascalCase)
+            PropertyValue::StringValue(_) => {
+                let enum_type_name = snake_to_pascal_case(key);
+
+                if let Some(enum_info) = context.registry.get_enum(&enum_type_name) {
+                    // Try to deserialize the string value as this enum
+                    match (enum_info.from_property)(value) {
+                        Ok(component_box) => {
+                            if has_reflect_component(
+                                &*component_box,
+                                &enum_type_name,
+                                type_registry,
+                            ) {
+                                components_to_insert.push(component_box);
+                                debug!(
+                                    "Queued enum component '{}' for attachment (property: '{}')",
+                                    enum_type_name, key
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            errors.push(class_error(
+                                enum_info.name,
+                                Some(key),
+                                PropertyErrorKind::Deserialize(e),
+                            ));
+                        }
+                    }
+                }
+                // If no matching enum found, this is just a regular string property - no warning needed
+            }
+
+            // Other property types (bool, int, float, etc.) are not component types
+            _ => {}
+        }
+    }
+
+    components_to_insert
+}
+
+/// Attach an object's registered components: those of its class-typed and
+/// enum-typed properties, of its class, and of its tile's class.
+///
+/// A tile object with its own class still gets the tile's class component, built
+/// from the tile's properties with object-level overrides on top.
+///
+/// The components are deserialized once per distinct class and property set of a
+/// map spawn and shared through [`SpawnContext::object_components`], so instances
+/// of one template don't each run the deserializers. Their problems are still
+/// reported for every object, located at `location`.
+pub fn attach_object_components(
+    entity_cmd: &mut EntityCommands,
+    class_name: &str,
+    tile_class: Option<&str>,
+    properties: &Properties,
+    location: &PropertyErrorContext,
+    context: &SpawnContext,
+    type_registry: &AppTypeRegistry,
+) {
+    let tile_class = tile_class.filter(|tile_class| *tile_class != class_name);
+    let cache = &context.object_components;
+    let (components, errors) = match cache.get(class_name, tile_class, properties) {
+        Some(cached) => cached,
+        None => {
+            let mut errors = Vec::new();
+            let mut components =
+                registered_components(properties, &mut errors, context, type_registry);
+            components.extend(class_component(
+                class_name,
+                properties,
+                None,
+                &mut errors,
+                context,
+                type_registry,
+            ));
+            if let Some(tile_class) = tile_class {
+                let tile_class_props = context
+                    .project
+                    .resolve_class_properties(tile_class, properties);
+                components.extend(class_component(
+                    tile_class,
+                    &tile_class_props,
+                    None,
+                    &mut errors,
+                    context,
+                    type_registry,
+                ));
+            }
+            let components: Vec<Arc<dyn Reflect>> = components.into_iter().map(Arc::from).collect();
+            let errors: Arc<[TiledPropertyError]> = errors.into();
+            cache.insert(
+                class_name,
+                tile_class,
+                properties,
+                components.clone(),
+                errors.clone(),
+            );
+            (components, errors)
+        }
+    };
+    report_errors(entity_cmd, location, &errors);
+    queue_components(entity_cmd, components, context.registry, type_registry);
+}
+
+/// Object components deserialized during one map spawn.
+///
+/// Instances of one template usually differ only in their placement, so their
+/// merged properties, and the components deserialized from them, are identical.
+/// The cache keeps the components of each distinct class and property set, and
+/// the other objects insert copies of them. tiled doesn't record which template
+/// an object was placed from, so the properties themselves are the key; that also
+/// covers identical objects placed without a template.
+#[derive(Default)]
+pub struct ObjectComponentCache {
+    entries: Mutex<HashMap<u64, Vec<CachedComponents>>>,
+    hits: AtomicUsize,
+}
+
+struct CachedComponents {
+    class_name: String,
+    tile_class: Option<String>,
+    properties: Properties,
+    components: Vec<Arc<dyn Reflect>>,
+    errors: Arc<[TiledPropertyError]>,
+}
+
+impl ObjectComponentCache {
+    /// Get the components cached for a class and property set, and the errors
+    /// found deserializing them (without a map, layer or object).
+    pub fn get(
+        &self,
+        class_name: &str,
+        tile_class: Option<&str>,
+        properties: &Properties,
+    ) -> Option<(Vec<Arc<dyn Reflect>>, Arc<[TiledPropertyError]>)> {
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let cached = entries
+            .get(&cache_key(class_name, tile_class, properties))?
+            .iter()
+            .find(|cached| {
+                cached.class_name == class_name
+                    && cached.tile_class.as_deref() == tile_class
+                    && cached.properties == *properties
+            })?;
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some((cached.components.clone(), cached.errors.clone()))
+    }
+
+    /// Cache the components deserialized for a class and property set, and the
+    /// errors found deserializing them.
+    pub fn insert(
+        &self,
+        class_name: &str,
+        tile_class: Option<&str>,
+        properties: &Properties,
+        components: Vec<Arc<dyn Reflect>>,
+        errors: Arc<[TiledPropertyError]>,
+    ) {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(cache_key(class_name, tile_class, properties))
+            .or_default()
+            .push(CachedComponents {
+                class_name: class_name.to_string(),
+                tile_class: tile_class.map(str::to_string),
+                properties: properties.clone(),
+                components,
+                errors,
+            });
+    }
+
+    /// Number of objects that reused cached components.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+}
+
+fn cache_key(class_name: &str, tile_class: Option<&str>, properties: &Properties) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    class_name.hash(&mut hasher);
+    tile_class.hash(&mut hasher);
+    hash_properties(properties).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Check that a deserialized value can be inserted as a component.
+fn has_reflect_component(
+    value: &dyn Reflect,
+    type_name: &str,
+    type_registry: &AppTypeRegistry,
+) -> bool {
+    let registry_lock = type_registry.read();
+    if registry_lock
+        .get_type_data::<ReflectComponent>(value.type_id())
+        .is_some()
+    {
+        true
+    } else {
+        warn!(
+            "Type '{}' is registered but missing ReflectComponent. \
+            Did you forget #[reflect(Component)]?",
+            type_name
+        );
+        false
+    }
+}
+
+/// Insert reflected components via a queued world command.
+///
+/// Components go through `EntityWorldMut::insert`, so Bevy's required components,
+/// component hooks and observers run as for a regular insert. The components of
+/// `#[tiled(requires(...))]` are inserted afterwards, keeping any the entity already
+/// has (including ones attached from the same properties).
+fn queue_components(
+    entity_cmd: &mut EntityCommands,
+    components: Vec<Arc<dyn Reflect>>,
+    registry: &TiledClassRegistry,
+    type_registry: &AppTypeRegistry,
+) {
+    if components.is_empty() {
+        return;
+    }
+
+    let entity = entity_cmd.id();
+    let type_registry_clone = type_registry.clone();
+    let insert_requires: Vec<fn(&mut EntityWorldMut)> = components
+        .iter()
+        .filter_map(|component| registry.get_by_type_id((**component).type_id()))
+        .map(|info| info.insert_requires)
+        .collect();
+
+    entity_cmd.commands().queue(move |world: &mut World| {
+        let registry = type_registry_clone.read();
+        for component in components {
+            let type_id = (*component).type_id();
+            if let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(type_id)
+                && let Ok(mut entity_mut) = world.get_entity_mut(entity)
+            {
+                reflect_component.insert(&mut entity_mut, &*component, &registry);
+            }
+        }
+        drop(registry);
+
+        if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+            for insert in insert_requires {
+                insert(&mut entity_mut);
+            }
+        }
+    });
+}
+
+/// Convert a `snake_case` string to `PascalCase`.
+///
+/// Used to infer enum type names from property keys.
+/// For example: `"activation_condition"` -> `"ActivationCondition"`
+fn snake_to_pascal_case(s: &str) -> String {
+    s.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_tiledmap_macros::TiledClass;
+
+    use super::*;
+
+    #[derive(Component, Default, Debug, PartialEq)]
+    struct Health(i32);
+
+    #[derive(Component, Default, Debug, PartialEq)]
+    struct Team(u8);
+
+    #[derive(Component, Default)]
+    struct Targetable;
+
+    #[derive(Component, Reflect, TiledClass, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    #[require(Targetable)]
+    #[tiled(name = "components_test::Soldier")]
+    #[tiled(requires(Health, Team))]
+    struct Soldier {
+        rank: i32,
+    }
+
+    #[derive(Resource, Default)]
+    struct Added(usize);
+
+    #[test]
+    fn test_queued_components_honor_requires_and_observers() {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        type_registry.write().register::<Soldier>();
+        let registry = TiledClassRegistry::build();
+        world.init_resource::<Added>();
+        world.add_observer(|_: On<Add, Soldier>, mut added: ResMut<Added>| added.0 += 1);
+
+        let entity = world.spawn(Team(3)).id();
+        let mut commands = world.commands();
+        queue_components(
+            &mut commands.entity(entity),
+            vec![Arc::new(Soldier { rank: 2 })],
+            &registry,
+            &type_registry,
+        );
+        world.flush();
+
+        assert_eq!(world.get::<Soldier>(entity), Some(&Soldier { rank: 2 }));
+        assert!(world.get::<Targetable>(entity).is_some());
+        assert_eq!(world.get::<Health>(entity), Some(&Health(0)));
+        // Components the entity already has are kept
+        assert_eq!(world.get::<Team>(entity), Some(&Team(3)));
+        assert_eq!(world.resource::<Added>().0, 1);
+    }
+
+    #[test]
+    fn test_object_component_cache_shares_identical_property_sets() {
+        let cache = ObjectComponentCache::default();
+        let properties = Properties::from([("rank".to_string(), PropertyValue::IntValue(2))]);
+        let soldier: Arc<dyn Reflect> = Arc::new(Soldier { rank: 2 });
+        cache.insert(
+            "Soldier",
+            None,
+            &properties,
+            vec![soldier.clone()],
+            Arc::from([]),
+        );
+
+        let (cached, _) = cache.get("Soldier", None, &properties).unwrap();
+        assert!(Arc::ptr_eq(&cached[0], &soldier));
+        assert_eq!(cache.hits(), 1);
+
+        // A different class, tile class or property set is a miss
+        assert!(cache.get("Guard", None, &properties).is_none());
+        assert!(cache.get("Soldier", Some("Wall"), &properties).is_none());
+        let promoted = Properties::from([("rank".to_string(), PropertyValue::IntValue(3))]);
+        assert!(cache.get("Soldier", None, &promoted).is_none());
+        assert_eq!(cache.hits(), 1);
+    }
+
+    #[test]
+    fn test_invalid_field_values_are_reported() {
+        let registry = TiledClassRegistry::build();
+        let info = registry.get("components_test::Soldier").unwrap();
+        let properties = Properties::from([(
+            "rank".to_string(),
+            PropertyValue::StringValue("captain".to_string()),
+        )]);
+        assert_eq!((info.invalid_fields)(&properties), ["rank"]);
+
+        let mut errors = Vec::new();
+        check_fields(info, &properties, Some("leader"), &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].context.field.as_deref(), Some("leader.rank"),);
+        assert_eq!(
+            errors[0].kind,
+            PropertyErrorKind::InvalidValue(PropertyValue::StringValue("captain".to_string()))
+        );
+
+        let valid = Properties::from([("rank".to_string(), PropertyValue::IntValue(2))]);
+        assert!((info.invalid_fields)(&valid).is_empty());
+    }
+}
@@ -4,6 +4,7 @@ use bevy::prelude::*;
 use tiled::LayerType;
 
 use crate::components::layer::ImageLayerData;
+use crate::properties::TiledColor;
 use crate::systems::SpawnContext;
 
 /// Build `ImageLayerData` component from an image layer.
@@ -31,15 +32,7 @@ pub fn build_image_layer_data(
     // Look up the image handle from the map asset's images
     let image_handle = context.map_asset.images.get(&layer.id())?.clone();
 
-    // Convert tiled Color to Bevy Color
-    let tint_color = layer.tint_color.map(|c| {
-        Color::srgba(
-            c.red as f32 / 255.0,
-            c.green as f32 / 255.0,
-            c.blue as f32 / 255.0,
-            c.alpha as f32 / 255.0,
-        )
-    });
+    let tint_color = layer.tint_color.map(|color| TiledColor(color).to_color());
 
     // Calculate map pixel height for Layer 3 coordinate conversion
     let map_pixel_height =
@@ -49,6 +42,10 @@ pub fn build_image_layer_data(
         image_handle,
         width: Some(image.width as f32),
         height: Some(image.height as f32),
+        size: Vec2::new(image.width as f32, image.height as f32),
+        offset: Vec2::new(layer.offset_x, layer.offset_y),
+        repeat_x: image_layer.repeat_x,
+        repeat_y: image_layer.repeat_y,
         tint_color,
         map_pixel_height,
     })
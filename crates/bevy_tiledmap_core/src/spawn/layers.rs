@@ -3,10 +3,20 @@
 use bevy::prelude::*;
 use tiled::LayerType;
 
-use crate::components::{LayerId, TiledLayer, TiledLayerMapOf};
-use crate::events::{GroupLayerSpawned, ImageLayerSpawned, ObjectLayerSpawned, TileLayerSpawned};
+use crate::chunks::StreamedTileLayer;
+use crate::components::{
+    LayerClass, LayerId, LayerName, TileClassGrid, TiledLayer, TiledLayerMapOf,
+};
+use crate::conditions::{ConditionFailBehavior, disabled_bundle};
+use crate::events::{
+    GroupLayerSpawned, ImageLayerSpawned, ObjectLayerSpawned, SpawnCounts, TileLayerSpawned,
+    trigger_spawn_event,
+};
 use crate::plugin::LayerZConfig;
-use crate::spawn::{build_image_layer_data, build_tile_layer_data, spawn_objects_layer};
+use crate::spawn::{
+    attach_class_component, build_chunked_tile_layer_data, build_image_layer_data,
+    build_tile_layer_data, spawn_objects_layer,
+};
 use crate::systems::SpawnContext;
 
 /// Spawn a single layer entity with appropriate components.
@@ -21,6 +31,17 @@ use crate::systems::SpawnContext;
 ///
 /// Group layers get z=0 (they don't contribute to z-ordering, only their children do).
 ///
+/// # Spawn Conditions
+///
+/// A layer whose `condition` property fails (see [`crate::conditions`]) is skipped,
+/// or spawned hidden and disabled along with everything inside it.
+///
+/// # Streamed Chunks
+///
+/// When chunks are streamed, the tiles of infinite layers are kept in a
+/// [`StreamedTileLayer`] instead, and [`TileLayerSpawned`] fires for each
+/// chunk's tile layer as it spawns (see [`crate::chunks`]).
+///
 /// # Arguments
 ///
 /// * `commands` - Bevy commands for entity spawning
@@ -30,10 +51,11 @@ use crate::systems::SpawnContext;
 /// * `type_registry` - App type registry for reflection-based component insertion
 /// * `z_counter` - Mutable counter for flat z-ordering across all content layers
 /// * `z_config` - Configuration for z offset and multiplier
+/// * `counts` - Counts of spawned layers, objects and tiles, updated by this layer
 ///
 /// # Returns
 ///
-/// The spawned layer entity
+/// The spawned layer entity, or `None` if the layer's condition failed and it was skipped
 pub fn spawn_layer(
     commands: &mut Commands,
     layer: &tiled::Layer,
@@ -42,7 +64,35 @@ pub fn spawn_layer(
     type_registry: &AppTypeRegistry,
     z_counter: &mut usize,
     z_config: &LayerZConfig,
-) -> Entity {
+    counts: &mut SpawnCounts,
+) -> Option<Entity> {
+    #[cfg(feature = "trace")]
+    let _span = info_span!("spawn_tiled_layer", layer = %layer.name, id = layer.id()).entered();
+
+    // Layer properties with project class defaults filled in
+    let class_name = layer.user_type.as_deref().unwrap_or_default();
+    let mut properties = context
+        .project
+        .resolve_class_properties(class_name, &layer.properties);
+    context.localize_properties(&mut properties);
+
+    // Skip the layer, or spawn it and its contents disabled, if its condition fails
+    let failed_condition = context.failed_condition(&properties);
+    let disabled_context;
+    let context = match (&failed_condition, context.conditions) {
+        (Some(_), Some(conditions)) if conditions.on_fail == ConditionFailBehavior::Skip => {
+            return None;
+        }
+        (Some(_), _) => {
+            disabled_context = SpawnContext {
+                spawn_disabled: true,
+                ..context.clone()
+            };
+            &disabled_context
+        }
+        (None, _) => context,
+    };
+
     let layer_type = match layer.layer_type() {
         LayerType::Tiles(_) => TiledLayer::Tiles,
         LayerType::Objects(_) => TiledLayer::Objects,
@@ -67,48 +117,108 @@ pub fn spawn_layer(
     );
 
     // Spawn base layer entity and get ID immediately
-    let layer_entity = commands
-        .spawn((
-            layer_type,
-            LayerId(layer.id()),
-            TiledLayerMapOf(map_entity),
-            transform,
-            Name::new(format!("Layer: {}", layer.name)),
-        ))
-        .id();
+    let mut layer_cmd = commands.spawn((
+        layer_type,
+        LayerId(layer.id()),
+        LayerName(layer.name.clone()),
+        TiledLayerMapOf(map_entity),
+        transform,
+        Name::new(format!("Layer: {}", layer.name)),
+    ));
+    if !class_name.is_empty() {
+        layer_cmd.insert(LayerClass(class_name.to_string()));
+    }
+    if context.spawn_disabled {
+        layer_cmd.insert((
+            Visibility::Hidden,
+            disabled_bundle(failed_condition.unwrap_or_default()),
+        ));
+    }
+
+    // Insert the component registered for the layer's Tiled class, if any
+    attach_class_component(
+        &mut layer_cmd,
+        class_name,
+        &properties,
+        &context
+            .property_error_context()
+            .with_layer(layer.id(), &layer.name),
+        context,
+        type_registry,
+    );
+    let layer_entity = layer_cmd.id();
+    counts.layers += 1;
 
     // Add type-specific components/children and trigger events
     match layer.layer_type() {
+        LayerType::Tiles(_) if context.stream_chunks && context.map_asset.map.infinite() => {
+            // Chunk entities spawn the tiles around viewers, one layer each
+            if let Some(chunks) = build_chunked_tile_layer_data(layer, context) {
+                counts.tiles += chunks
+                    .values()
+                    .map(|tile_data| tile_data.iter_tiles().count())
+                    .sum::<usize>();
+                commands.entity(layer_entity).insert(StreamedTileLayer {
+                    chunks,
+                    parent_group: context.parent_group,
+                    depth: context.layer_depth,
+                    properties: properties.clone(),
+                });
+            }
+        }
+
         LayerType::Tiles(_) => {
             // Build tile data and attach to layer
             if let Some(tile_data) = build_tile_layer_data(layer, context) {
+                counts.tiles += tile_data.iter_tiles().count();
+                let class_grid = TileClassGrid::from_tile_data(&tile_data, context.tileset_assets);
+                if !class_grid.is_empty() {
+                    commands.entity(layer_entity).insert(class_grid);
+                }
                 commands.entity(layer_entity).insert(tile_data);
             }
 
             // Trigger TileLayerSpawned event
-            commands.trigger(TileLayerSpawned {
-                entity: layer_entity,
-                map_entity,
-                layer_id: layer.id(),
-                properties: layer.properties.clone(),
-            });
+            trigger_spawn_event(
+                commands,
+                TileLayerSpawned {
+                    entity: layer_entity,
+                    map_entity,
+                    layer_id: layer.id(),
+                    parent_group: context.parent_group,
+                    depth: context.layer_depth,
+                    properties: properties.clone(),
+                },
+            );
         }
 
         LayerType::Objects(_) => {
             // Spawn object entities as children
-            let object_entities =
-                spawn_objects_layer(commands, layer, map_entity, context, type_registry);
+            let object_entities = spawn_objects_layer(
+                commands,
+                layer,
+                layer_entity,
+                map_entity,
+                context,
+                type_registry,
+            );
+            counts.objects += object_entities.len();
             if !object_entities.is_empty() {
                 commands.entity(layer_entity).add_children(&object_entities);
             }
 
             // Trigger ObjectLayerSpawned event
-            commands.trigger(ObjectLayerSpawned {
-                entity: layer_entity,
-                map_entity,
-                layer_id: layer.id(),
-                properties: layer.properties.clone(),
-            });
+            trigger_spawn_event(
+                commands,
+                ObjectLayerSpawned {
+                    entity: layer_entity,
+                    map_entity,
+                    layer_id: layer.id(),
+                    parent_group: context.parent_group,
+                    depth: context.layer_depth,
+                    properties: properties.clone(),
+                },
+            );
         }
 
         LayerType::Image(_) => {
@@ -117,49 +227,175 @@ pub fn spawn_layer(
                 commands.entity(layer_entity).insert(image_data);
 
                 // Trigger ImageLayerSpawned event only when image data is present
-                commands.trigger(ImageLayerSpawned {
-                    entity: layer_entity,
-                    map_entity,
-                    layer_id: layer.id(),
-                    properties: layer.properties.clone(),
-                });
+                trigger_spawn_event(
+                    commands,
+                    ImageLayerSpawned {
+                        entity: layer_entity,
+                        map_entity,
+                        layer_id: layer.id(),
+                        parent_group: context.parent_group,
+                        depth: context.layer_depth,
+                        properties: properties.clone(),
+                    },
+                );
             }
         }
 
         LayerType::Group(group) => {
+            // Trigger GroupLayerSpawned before the child layers' events
+            trigger_spawn_event(
+                commands,
+                GroupLayerSpawned {
+                    entity: layer_entity,
+                    map_entity,
+                    layer_id: layer.id(),
+                    parent_group: context.parent_group,
+                    depth: context.layer_depth,
+                    properties: properties.clone(),
+                },
+            );
+
             // Recursively spawn child layers, skipping hidden ones
             // Children use is_top_level=false since their parent is already in positive Y space
+            let child_context = SpawnContext {
+                parent_group: Some(layer_entity),
+                layer_depth: context.layer_depth + 1,
+                ..context.clone()
+            };
             let mut child_layer_entities = Vec::new();
             for child_layer in group.layers() {
                 if !child_layer.visible {
                     continue;
                 }
-                let child_entity = spawn_layer(
+                if let Some(child_entity) = spawn_layer(
                     commands,
                     &child_layer,
                     map_entity,
-                    context,
+                    &child_context,
                     type_registry,
                     z_counter,
                     z_config,
-                );
-                child_layer_entities.push(child_entity);
+                    counts,
+                ) {
+                    child_layer_entities.push(child_entity);
+                }
             }
             if !child_layer_entities.is_empty() {
                 commands
                     .entity(layer_entity)
                     .add_children(&child_layer_entities);
             }
-
-            // Trigger GroupLayerSpawned event
-            commands.trigger(GroupLayerSpawned {
-                entity: layer_entity,
-                map_entity,
-                layer_id: layer.id(),
-                properties: layer.properties.clone(),
-            });
         }
     }
 
-    layer_entity
+    Some(layer_entity)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::asset::AssetPlugin;
+    use bevy_tiledmap_assets::{TiledmapAssetsPlugin, embedded};
+
+    use crate::components::TiledMap;
+    use crate::events::MapSpawned;
+    use crate::plugin::TiledmapCorePlugin;
+
+    use super::*;
+
+    /// Layer events in the order observers saw them: layer ID, whether it's a
+    /// group, parent group's layer ID and depth.
+    #[derive(Resource, Default)]
+    struct LayerEvents(Vec<(u32, bool, Option<Entity>, u32)>);
+
+    #[derive(Resource, Default)]
+    struct Spawned(bool);
+
+    #[test]
+    fn test_group_events_precede_their_layers() {
+        const MAP: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" renderorder="right-down" width="2" height="2" tilewidth="16" tileheight="16" infinite="0" nextlayerid="7" nextobjectid="1">
+ <objectgroup id="1" name="Below"/>
+ <group id="2" name="Outer">
+  <objectgroup id="3" name="OuterObjects"/>
+  <group id="4" name="Inner">
+   <objectgroup id="5" name="InnerObjects"/>
+  </group>
+ </group>
+ <objectgroup id="6" name="Above"/>
+</map>"#;
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            TransformPlugin,
+            TiledmapAssetsPlugin,
+            TiledmapCorePlugin::default(),
+        ))
+        .init_asset::<Image>()
+        .init_resource::<LayerEvents>()
+        .init_resource::<Spawned>()
+        .add_observer(
+            |event: On<GroupLayerSpawned>, mut events: ResMut<LayerEvents>| {
+                events
+                    .0
+                    .push((event.layer_id, true, event.parent_group, event.depth));
+            },
+        )
+        .add_observer(
+            |event: On<ObjectLayerSpawned>, mut events: ResMut<LayerEvents>| {
+                events
+                    .0
+                    .push((event.layer_id, false, event.parent_group, event.depth));
+            },
+        )
+        .add_observer(|_: On<MapSpawned>, mut spawned: ResMut<Spawned>| {
+            spawned.0 = true;
+        });
+        embedded::embed(
+            &mut app,
+            "groups/nested_groups.tmx",
+            "nested_groups.tmx",
+            MAP,
+        );
+
+        let handle = app
+            .world()
+            .resource::<AssetServer>()
+            .load("embedded://groups/nested_groups.tmx");
+        app.world_mut().spawn(TiledMap { handle });
+        for _ in 0..1000 {
+            if app.world().resource::<Spawned>().0 {
+                break;
+            }
+            app.update();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(app.world().resource::<Spawned>().0, "map did not spawn");
+
+        let world = app.world_mut();
+        let mut layers = world.query::<(Entity, &LayerId)>();
+        let mut group = |id| {
+            layers
+                .iter(world)
+                .find(|(_, layer_id)| layer_id.0 == id)
+                .map(|(entity, _)| entity)
+        };
+        let (outer, inner) = (group(2), group(4));
+        assert!(outer.is_some() && inner.is_some());
+
+        assert_eq!(
+            world.resource::<LayerEvents>().0,
+            [
+                (1, false, None, 0),
+                (2, true, None, 0),
+                (3, false, outer, 1),
+                (4, true, outer, 1),
+                (5, false, inner, 2),
+                (6, false, None, 0),
+            ]
+        );
+    }
 }
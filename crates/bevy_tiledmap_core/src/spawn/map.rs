@@ -3,8 +3,10 @@
 use bevy::prelude::*;
 
 use crate::components::{LayersInMap, MapGeometry};
+use crate::events::SpawnCounts;
 use crate::plugin::LayerZConfig;
-use crate::spawn::spawn_layer;
+use crate::properties::MergedProperties;
+use crate::spawn::{attach_class_component, spawn_layer};
 use crate::systems::SpawnContext;
 
 /// Spawn the entity hierarchy for a map.
@@ -23,6 +25,10 @@ use crate::systems::SpawnContext;
 /// * `type_registry` - App type registry for reflection-based component insertion
 /// * `z_config` - Configuration for layer z-ordering
 /// * `z_counter` - Mutable counter for sequential Z values (shared across maps in a world)
+///
+/// # Returns
+///
+/// Counts of the spawned layers, objects and tiles
 pub fn spawn_map(
     commands: &mut Commands,
     map_entity: Entity,
@@ -30,8 +36,9 @@ pub fn spawn_map(
     type_registry: &AppTypeRegistry,
     z_config: &LayerZConfig,
     z_counter: &mut usize,
-) {
+) -> SpawnCounts {
     let mut layer_entities = Vec::new();
+    let mut counts = SpawnCounts::default();
 
     // Spawn each top-level layer (spawn_layer handles recursion for groups)
     // Skip hidden layers - they won't be spawned at all
@@ -39,7 +46,7 @@ pub fn spawn_map(
         if !layer.visible {
             continue;
         }
-        let layer_entity = spawn_layer(
+        if let Some(layer_entity) = spawn_layer(
             commands,
             &layer,
             map_entity,
@@ -47,8 +54,10 @@ pub fn spawn_map(
             type_registry,
             z_counter,
             z_config,
-        );
-        layer_entities.push(layer_entity);
+            &mut counts,
+        ) {
+            layer_entities.push(layer_entity);
+        }
     }
 
     // Create MapGeometry for world-space boundary and coordinate conversion
@@ -61,8 +70,36 @@ pub fn spawn_map(
     );
 
     // Add components and set up parent-child hierarchy
-    commands
-        .entity(map_entity)
+    let mut map_cmd = commands.entity(map_entity);
+    map_cmd
         .insert((LayersInMap(layer_entities.clone()), map_geometry))
         .add_children(&layer_entities);
+
+    // Maps in a world also get the world's properties, exposed as `MergedProperties`
+    let map_properties = match context.world_properties {
+        Some(world_properties) => {
+            let merged = world_properties.merge(&map.properties);
+            map_cmd.insert(MergedProperties::new(merged.clone()));
+            merged
+        }
+        None => map.properties.clone(),
+    };
+
+    // Insert the component registered for the map's Tiled class, if any
+    let class_name = map.user_type.as_deref().unwrap_or_default();
+    let mut properties = context
+        .project
+        .resolve_class_properties(class_name, &map_properties);
+    context.localize_properties(&mut properties);
+    attach_class_component(
+        &mut map_cmd,
+        class_name,
+        &properties,
+        &context.property_error_context(),
+        context,
+        type_registry,
+    );
+
+    counts.cached_objects = context.object_components.hits();
+    counts
 }
@@ -1,13 +1,18 @@
 //! Entity spawning functions.
 
+pub mod components;
 pub mod images;
 pub mod layers;
 pub mod map;
 pub mod objects;
 pub mod tiles;
 
+pub use components::{
+    ObjectComponentCache, attach_class_component, attach_object_components,
+    attach_registered_components,
+};
 pub use images::build_image_layer_data;
 pub use layers::spawn_layer;
 pub use map::spawn_map;
 pub use objects::spawn_objects_layer;
-pub use tiles::build_tile_layer_data;
+pub use tiles::{build_chunked_tile_layer_data, build_tile_layer_data};
@@ -1,25 +1,42 @@
 //! Object layer spawning.
 
 use bevy::prelude::*;
-use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
-use tiled::{LayerType, ObjectShape, PropertyValue};
+use bevy_tiledmap_assets::prelude::{ObjectAlignment, TiledTilesetAsset};
+use tiled::{HorizontalAlignment, LayerType, ObjectShape, VerticalAlignment};
 
 use crate::components::TiledObjectMapOf;
-use crate::components::object::{ObjectId, TiledObject};
-use crate::events::ObjectSpawned;
-use crate::properties::MergedProperties;
+use crate::components::object::{
+    ObjectClass, ObjectId, ObjectName, ObjectPlacement, TextHorizontalAlign, TextVerticalAlign,
+    TiledObject,
+};
+use crate::conditions::{ConditionFailBehavior, disabled_bundle};
+use crate::events::{ObjectSpawned, ObjectsSpawnedBatch, trigger_spawn_event};
+use crate::geometry::flip_y;
+use crate::object_transform::ObjectTransformInput;
+use crate::properties::{PropertyProvenance, PropertySource, TiledColor, merge_property_sources};
+use crate::spawn::components::attach_object_components;
 use crate::systems::SpawnContext;
+use crate::variation::{roll_spawn_chance, spawn_chance};
 
 /// Spawn object entities for an object layer.
 ///
 /// Pre-computes shape vertices, resolves tile references, sets up transforms.
-/// Automatically attaches `MergedProperties` and any registered `TiledClass` components.
-/// Triggers `ObjectSpawned` events for Layer 3 integration via observers.
+/// Automatically attaches `MergedProperties` and any registered `TiledClass` components,
+/// both from class-typed properties and from the object's own Tiled class. Tile objects
+/// also receive the component for the referenced tile's class, so every instance of
+/// e.g. a "Tree" tile gets the `Tree` component with per-object overrides applied.
+/// Triggers `ObjectSpawned` events, or one `ObjectsSpawnedBatch` on the layer
+/// entity, for Layer 3 integration depending on the context's event mode.
+///
+/// Objects with a `spawn_chance` property (or in a layer with one) are only
+/// spawned if they win their roll; see [`crate::variation`]. Objects whose
+/// `condition` property fails are skipped or spawned disabled; see [`crate::conditions`].
 ///
 /// # Arguments
 ///
 /// * `commands` - Bevy commands for entity spawning
 /// * `layer` - The object layer from the map asset
+/// * `layer_entity` - The object layer entity (target of the batched event)
 /// * `map_entity` - Parent map entity (for relationship)
 /// * `context` - Spawn context for tileset lookups and property access
 /// * `type_registry` - App type registry for reflection-based component insertion
@@ -30,6 +47,7 @@ use crate::systems::SpawnContext;
 pub fn spawn_objects_layer(
     commands: &mut Commands,
     layer: &tiled::Layer,
+    layer_entity: Entity,
     map_entity: Entity,
     context: &SpawnContext,
     type_registry: &AppTypeRegistry,
@@ -40,10 +58,12 @@ pub fn spawn_objects_layer(
     };
 
     let mut object_entities = Vec::new();
+    let mut batch = Vec::new();
+    let layer_spawn_chance = spawn_chance(&layer.properties, context.property_keys);
 
     for object in object_layer.objects() {
         // Check if this is a tile object first
-        let tiled_object = if let Some(tile_data) = object.tile_data() {
+        let mut tiled_object = if let Some(tile_data) = object.tile_data() {
             // This is a tile object - get tile info
             let tile_id = tile_data.id();
 
@@ -65,6 +85,9 @@ pub fn spawn_objects_layer(
                     tileset_handle,
                     width: obj_width,
                     height: obj_height,
+                    flip_h: tile_data.flip_h,
+                    flip_v: tile_data.flip_v,
+                    flip_d: tile_data.flip_d,
                 },
                 None => {
                     warn!(
@@ -79,6 +102,11 @@ pub fn spawn_objects_layer(
             // Regular shape-based object
             convert_object_shape(&object.shape)
         };
+        if let TiledObject::Text { text, .. } = &mut tiled_object
+            && let Some(localized) = context.localize(text)
+        {
+            *text = localized;
+        }
 
         // Calculate transform
         // Tiled uses corner origin with Y increasing downward
@@ -91,217 +119,161 @@ pub fn spawn_objects_layer(
         // - For regular objects: Tiled anchor is TOP-left, extends DOWN
         // - For tile objects: Tiled anchor is BOTTOM-left, extends UP
         let (obj_width, obj_height) = match &object.shape {
-            ObjectShape::Rect { width, height } => (*width, *height),
+            ObjectShape::Rect { width, height } | ObjectShape::Text { width, height, .. } => {
+                (*width, *height)
+            }
             _ => (0.0, 0.0),
         };
 
         // Calculate center position in Bevy coordinates (using MapGeometry pattern)
         // Y-flip: Tiled Y=0 (top) → Bevy Y=map_height (top)
+        //
+        // Computed in f64 and rounded to f32 once, so sub-pixel positions are
+        // kept as exactly as f32 allows, even far from the map origin
         let map_pixel_height =
-            context.map_asset.map.height as f32 * context.map_asset.map.tile_height as f32;
+            f64::from(context.map_asset.map.height) * f64::from(context.map_asset.map.tile_height);
+        let (x, y) = (f64::from(object.x), f64::from(object.y));
+        let (width, height) = (f64::from(obj_width), f64::from(obj_height));
 
         let (center_x, center_y) = if object.tile_data().is_some() {
-            // Tile objects: anchor is at BOTTOM-left, tile extends UP
-            // Center X = x + width/2
-            // Tiled center Y = y - height/2 (since tile extends up from anchor)
+            // Tile objects: anchor is set by the tileset's object alignment
+            // (BOTTOM-left by default, tile extends UP), shifted by its tileoffset
+            // Center = anchor + (0.5 - alignment origin) * size + tileoffset
             // Bevy Y = map_height - tiled_y
+            let (origin, offset) = tile_object_alignment(context, &tiled_object);
             (
-                object.x + obj_width / 2.0,
-                map_pixel_height - (object.y - obj_height / 2.0),
+                x + (0.5 - f64::from(origin.x)) * width + f64::from(offset.x),
+                map_pixel_height - (y + (0.5 - f64::from(origin.y)) * height + f64::from(offset.y)),
             )
         } else {
             // Regular objects: anchor is at TOP-left, object extends DOWN
             // Center X = x + width/2
             // Tiled center Y = y + height/2
             // Bevy Y = map_height - tiled_y
-            (
-                object.x + obj_width / 2.0,
-                map_pixel_height - (object.y + obj_height / 2.0),
-            )
+            (x + width / 2.0, map_pixel_height - (y + height / 2.0))
         };
+        let (center_x, center_y) = (center_x as f32, center_y as f32);
 
         let transform = Transform::from_xyz(center_x, center_y, 0.0)
             // Tiled rotation is clockwise in degrees, Bevy is counter-clockwise in radians
             .with_rotation(Quat::from_rotation_z(-object.rotation.to_radians()));
 
-        // Get merged properties from multiple sources
-        // For tile objects: tile props → collision object props → template+object props
-        // For shape objects: template+object props (template already merged by tiled crate)
-        let merged_props = if let TiledObject::Tile {
-            tile_id,
-            tileset_handle,
-            ..
-        } = &tiled_object
-        {
-            merge_tile_object_properties(context, *tile_id, tileset_handle, object.id())
+        // Resolve the object's Tiled class (tile objects inherit the tile's class)
+        let tile_class = tile_class_name(context, &tiled_object);
+        let class_name = if object.user_type.is_empty() {
+            tile_class.clone().unwrap_or_default()
         } else {
-            context
-                .get_object_properties(object.id())
-                .cloned()
-                .unwrap_or_else(|| object.properties.clone())
+            object.user_type.clone()
         };
 
+        // Merge properties from all sources in the configured order
+        // (class defaults → tile → collision object → template+object by default)
+        let (mut merged_props, provenance) =
+            merge_object_properties(context, layer, &object, &tiled_object, &class_name);
+        context.localize_properties(&mut merged_props);
+
+        // Skip objects that lose their spawn chance roll (layer value is the default)
+        if let Some(chance) =
+            spawn_chance(&merged_props, context.property_keys).or(layer_spawn_chance)
+            && !roll_spawn_chance(chance, context.spawn_chance_seed, layer.id(), object.id())
+        {
+            continue;
+        }
+
+        // Skip the object, or spawn it disabled, if its condition fails
+        let failed_condition = context.failed_condition(&merged_props);
+        if failed_condition.is_some()
+            && context
+                .conditions
+                .is_some_and(|conditions| conditions.on_fail == ConditionFailBehavior::Skip)
+        {
+            continue;
+        }
+
+        // Let the game adjust the transform (snapping, scale, custom Z, ...)
+        let transform = context.transform_object(
+            &ObjectTransformInput {
+                object: &tiled_object,
+                object_id: object.id(),
+                name: &object.name,
+                class: &class_name,
+                layer_id: layer.id(),
+                position: Vec2::new(object.x, object.y),
+                properties: &merged_props,
+            },
+            transform,
+        );
+
         // Spawn object entity with base components
         let mut entity_cmd = commands.spawn((
             tiled_object,
             ObjectId(object.id()),
+            ObjectName(object.name.clone()),
+            ObjectPlacement {
+                position: Vec2::new(object.x, object.y),
+                rotation: object.rotation,
+            },
             TiledObjectMapOf(map_entity),
             transform,
             Name::new(format!("Object: {}", object.name)),
         ));
 
+        if !class_name.is_empty() {
+            entity_cmd.insert(ObjectClass(class_name.clone()));
+        }
+        if context.spawn_disabled || failed_condition.is_some() {
+            entity_cmd.insert(disabled_bundle(failed_condition.unwrap_or_default()));
+        }
+
         // Attach MergedProperties for raw property access
-        entity_cmd.insert(MergedProperties::new(merged_props.clone()));
+        entity_cmd.insert(context.merged_properties(merged_props.clone()));
+        if context.merge_config.record_provenance {
+            entity_cmd.insert(provenance);
+        }
 
         // Auto-attach registered TiledClass components
-        attach_registered_components(&mut entity_cmd, &merged_props, context, type_registry);
+        attach_object_components(
+            &mut entity_cmd,
+            &class_name,
+            tile_class.as_deref(),
+            &merged_props,
+            &context
+                .property_error_context()
+                .with_layer(layer.id(), &layer.name)
+                .with_object(object.id(), &object.name),
+            context,
+            type_registry,
+        );
 
         let entity_id = entity_cmd.id();
         object_entities.push(entity_id);
 
         // Trigger ObjectSpawned event for Layer 3 plugins (via observers)
-        commands.trigger(ObjectSpawned {
-            entity: entity_id,
-            map_entity,
-            object_id: object.id(),
-            properties: merged_props.clone(),
-        });
-    }
-
-    object_entities
-}
-
-/// Attach registered components from class-typed and enum-typed properties.
-///
-/// Iterates through the object's properties looking for:
-/// 1. Class-typed values (`PropertyValue::ClassValue`) - deserializes structs
-/// 2. String values that match registered enum types - deserializes enums
-///
-/// For enum properties, the tiled crate loses the `propertytype` attribute, so we
-/// infer the type from the property key name by converting `snake_case` to `PascalCase`.
-fn attach_registered_components(
-    entity_cmd: &mut EntityCommands,
-    properties: &tiled::Properties,
-    context: &SpawnContext,
-    type_registry: &AppTypeRegistry,
-) {
-    // Collect components to insert (can't insert during iteration due to borrow checker)
-    let mut components_to_insert: Vec<Box<dyn Reflect>> = Vec::new();
-
-    // Iterate all properties looking for class-typed and enum-typed ones
-    for (key, value) in properties.iter() {
-        match value {
-            // Handle class-typed properties (structs)
-            PropertyValue::ClassValue {
-                property_type,
-                properties: class_props,
-            } => {
-                // Try to find this class in the registry
-                if let Some(info) = context.registry.get(property_type) {
-                    // Call the generated deserialization function
-                    match (info.from_properties)(class_props, Some(context.asset_server)) {
-                        Ok(component_box) => {
-                            // Verify it has ReflectComponent
-                            let type_id = component_box.type_id();
-                            let registry_lock = type_registry.read();
-
-                            if registry_lock
-                                .get_type_data::<ReflectComponent>(type_id)
-                                .is_some()
-                            {
-                                components_to_insert.push(component_box);
-                                debug!(
-                                    "Queued component '{}' for attachment (property: '{}')",
-                                    property_type, key
-                                );
-                            } else {
-                                warn!(
-                                    "Type '{}' is registered but missing ReflectComponent. \
-                                    Did you forget #[reflect(Component)]?",
-                                    property_type
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            warn!(
-                                "Failed to deserialize component '{}' for property '{}': {}",
-                                property_type, key, e
-                            );
-                        }
-                    }
-                } else {
-                    debug!(
-                        "Class property '{}' has type '{}' which is not registered. \
-                        Add #[derive(TiledClass)] to register it.",
-                        key, property_type
-                    );
-                }
-            }
-
-            // Handle string values that might be enum properties
-            // The tiled crate loses the `propertytype` attribute for non-class properties,
-            // so we infer the type from the property key name (snake_case -> PascalCase)
-            PropertyValue::StringValue(_) => {
-                let enum_type_name = snake_to_pascal_case(key);
-
-                if let Some(enum_info) = context.registry.get_enum(&enum_type_name) {
-                    // Try to deserialize the string value as this enum
-                    match (enum_info.from_property)(value) {
-                        Ok(component_box) => {
-                            // Verify it has ReflectComponent
-                            let type_id = component_box.type_id();
-                            let registry_lock = type_registry.read();
-
-                            if registry_lock
-                                .get_type_data::<ReflectComponent>(type_id)
-                                .is_some()
-                            {
-                                components_to_insert.push(component_box);
-                                debug!(
-                                    "Queued enum component '{}' for attachment (property: '{}')",
-                                    enum_type_name, key
-                                );
-                            } else {
-                                warn!(
-                                    "Enum '{}' is registered but missing ReflectComponent. \
-                                    Did you forget #[reflect(Component)]?",
-                                    enum_type_name
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            warn!(
-                                "Failed to deserialize enum '{}' for property '{}': {}",
-                                enum_type_name, key, e
-                            );
-                        }
-                    }
-                }
-                // If no matching enum found, this is just a regular string property - no warning needed
-            }
-
-            // Other property types (bool, int, float, etc.) are not component types
-            _ => {}
+        if context.event_mode.per_entity() {
+            trigger_spawn_event(
+                commands,
+                ObjectSpawned {
+                    entity: entity_id,
+                    map_entity,
+                    object_id: object.id(),
+                    properties: merged_props.clone(),
+                },
+            );
+        }
+        if context.event_mode.batched() {
+            batch.push((entity_id, object.id(), merged_props.clone()));
         }
     }
 
-    // Insert all collected components via custom command
-    if !components_to_insert.is_empty() {
-        let entity = entity_cmd.id();
-        let type_registry_clone = type_registry.clone();
-
-        entity_cmd.commands().queue(move |world: &mut World| {
-            let registry = type_registry_clone.read();
-            for component_box in components_to_insert {
-                let type_id = component_box.type_id();
-                if let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(type_id)
-                    && let Ok(mut entity_mut) = world.get_entity_mut(entity)
-                {
-                    reflect_component.insert(&mut entity_mut, &*component_box, &registry);
-                }
-            }
-        });
+    // Trigger one ObjectsSpawnedBatch event for the whole layer
+    if context.event_mode.batched() {
+        trigger_spawn_event(
+            commands,
+            ObjectsSpawnedBatch::new(layer_entity, map_entity, layer.id(), batch),
+        );
     }
+
+    object_entities
 }
 
 /// Convert an `ObjectShape` to `TiledObject`.
@@ -322,19 +294,58 @@ fn convert_object_shape(shape: &ObjectShape) -> TiledObject {
 
         ObjectShape::Polyline { points } => {
             // Flip Y for Bevy's Y-up coordinate system
-            let vertices: Vec<Vec2> = points.iter().map(|(x, y)| Vec2::new(*x, -*y)).collect();
+            let vertices = flip_y(points);
             TiledObject::Polyline { vertices }
         }
 
         ObjectShape::Polygon { points } => {
             // Flip Y for Bevy's Y-up coordinate system
-            let vertices: Vec<Vec2> = points.iter().map(|(x, y)| Vec2::new(*x, -*y)).collect();
+            let vertices = flip_y(points);
             TiledObject::Polygon { vertices }
         }
 
         ObjectShape::Point(_, _) => TiledObject::Point,
 
-        ObjectShape::Text { .. } => TiledObject::Text {},
+        ObjectShape::Text {
+            font_family,
+            pixel_size,
+            wrap,
+            color,
+            bold,
+            italic,
+            underline,
+            strikeout,
+            kerning,
+            halign,
+            valign,
+            text,
+            width,
+            height,
+        } => TiledObject::Text {
+            text: text.clone(),
+            pixel_size: *pixel_size as f32,
+            color: TiledColor(*color).to_color(),
+            width: *width,
+            height: *height,
+            font_family: font_family.clone(),
+            wrap: *wrap,
+            bold: *bold,
+            italic: *italic,
+            underline: *underline,
+            strikeout: *strikeout,
+            kerning: *kerning,
+            halign: match halign {
+                HorizontalAlignment::Left => TextHorizontalAlign::Left,
+                HorizontalAlignment::Center => TextHorizontalAlign::Center,
+                HorizontalAlignment::Right => TextHorizontalAlign::Right,
+                HorizontalAlignment::Justify => TextHorizontalAlign::Justify,
+            },
+            valign: match valign {
+                VerticalAlignment::Top => TextVerticalAlign::Top,
+                VerticalAlignment::Center => TextVerticalAlign::Center,
+                VerticalAlignment::Bottom => TextVerticalAlign::Bottom,
+            },
+        },
     }
 }
 
@@ -370,66 +381,262 @@ fn find_tileset_for_tile_object(
     }
 }
 
-/// Merge properties from multiple sources for tile objects.
+/// Get a tile object's alignment origin and drawing offset from its tileset.
 ///
-/// Properties are merged in priority order (lowest to highest):
-/// 1. Tile properties from tileset (base for all instances of this tile)
-/// 2. Collision object properties (from the tile's collision shapes in the tileset)
-/// 3. Template + Object properties (template already merged by tiled crate)
+/// The origin is a fraction of the object's size from its top-left corner
+/// (see [`ObjectAlignment::origin`]); the offset is in Tiled pixels (Y-down).
+fn tile_object_alignment(context: &SpawnContext, tiled_object: &TiledObject) -> (Vec2, Vec2) {
+    let TiledObject::Tile { tileset_handle, .. } = tiled_object else {
+        return (ObjectAlignment::BottomLeft.origin(), Vec2::ZERO);
+    };
+
+    context.tileset_assets.get(tileset_handle).map_or(
+        (ObjectAlignment::BottomLeft.origin(), Vec2::ZERO),
+        |tileset| {
+            (
+                tileset.object_alignment.origin(),
+                tileset.tile_offset.as_vec2(),
+            )
+        },
+    )
+}
+
+/// Get the Tiled class of the tile referenced by a tile object.
 ///
-/// This ensures tile objects inherit properties defined at the tileset level
-/// while allowing per-instance overrides via templates or direct object properties.
-fn merge_tile_object_properties(
-    context: &SpawnContext,
-    tile_id: u32,
-    tileset_handle: &Handle<TiledTilesetAsset>,
-    object_id: u32,
-) -> tiled::Properties {
-    let mut merged = tiled::Properties::default();
-
-    // Layer 1: Tile properties (lowest priority - base for all instances)
-    if let Some(tileset) = context.tileset_assets.get(tileset_handle) {
-        if let Some(tile_props) = tileset.tile_properties.get(&tile_id) {
-            for (key, value) in tile_props.iter() {
-                merged.insert(key.clone(), value.clone());
-            }
-        }
+/// Returns `None` for non-tile objects and for tiles without a class.
+fn tile_class_name(context: &SpawnContext, tiled_object: &TiledObject) -> Option<String> {
+    let TiledObject::Tile {
+        tile_id,
+        tileset_handle,
+        ..
+    } = tiled_object
+    else {
+        return None;
+    };
 
-        // Layer 2: Collision object properties (from tileset)
-        if let Some(tile) = tileset.tileset.get_tile(tile_id)
-            && let Some(collision) = tile.collision.as_ref()
-        {
-            let objects = collision.object_data();
-            if let Some(first_obj) = objects.first() {
-                for (key, value) in first_obj.properties.iter() {
-                    merged.insert(key.clone(), value.clone());
-                }
-            }
-        }
+    context
+        .tileset_assets
+        .get(tileset_handle)
+        .and_then(|tileset| tileset.tileset.get_tile(*tile_id))
+        .and_then(|tile| tile.user_type.clone())
+        .filter(|class| !class.is_empty())
+}
+
+/// Merge an object's properties from all sources in the order set by `PropertyMergeConfig`.
+///
+/// Gathers every source that exists for this object:
+/// - Class defaults from the Tiled project for `class_name`
+/// - The containing layer's properties
+/// - Tile and first collision object properties from the tileset (tile objects only)
+/// - Template + object properties (template already merged by tiled crate)
+///
+/// When class defaults are merged, nested class values also get their defaults filled in.
+fn merge_object_properties(
+    context: &SpawnContext,
+    layer: &tiled::Layer,
+    object: &tiled::Object,
+    tiled_object: &TiledObject,
+    class_name: &str,
+) -> (tiled::Properties, PropertyProvenance) {
+    let object_props = context
+        .get_object_properties(object.id())
+        .unwrap_or(&object.properties);
+
+    let mut tile_props = None;
+    let mut collision_props = None;
+
+    if let TiledObject::Tile {
+        tile_id,
+        tileset_handle,
+        ..
+    } = tiled_object
+        && let Some(tileset) = context.tileset_assets.get(tileset_handle)
+    {
+        tile_props = tileset.tile_properties.get(tile_id);
+
+        collision_props = tileset.tileset.get_tile(*tile_id).and_then(|tile| {
+            tile.collision
+                .as_ref()
+                .and_then(|collision| collision.object_data().first())
+                .map(|first_obj| first_obj.properties.clone())
+        });
     }
 
-    // Layer 3: Object properties (highest priority - template already merged by tiled crate)
-    if let Some(obj_props) = context.get_object_properties(object_id) {
-        for (key, value) in obj_props.iter() {
-            merged.insert(key.clone(), value.clone());
-        }
+    let class_defaults = context.project.class_defaults(class_name);
+
+    let mut available = vec![
+        (PropertySource::Layer, &layer.properties),
+        (PropertySource::Object, object_props),
+    ];
+    if let Some(defaults) = &class_defaults {
+        available.push((PropertySource::ClassDefaults, defaults));
     }
+    if let Some(props) = tile_props {
+        available.push((PropertySource::Tile, props));
+    }
+    if let Some(props) = &collision_props {
+        available.push((PropertySource::TileCollision, props));
+    }
+
+    let order = &context.merge_config.order;
+    let (merged, provenance) = merge_property_sources(order, &available);
 
-    merged
+    if order.contains(&PropertySource::ClassDefaults) {
+        (
+            context.project.resolve_class_properties("", &merged),
+            provenance,
+        )
+    } else {
+        (merged, provenance)
+    }
 }
 
-/// Convert a `snake_case` string to `PascalCase`.
-///
-/// Used to infer enum type names from property keys.
-/// For example: `"activation_condition"` -> `"ActivationCondition"`
-fn snake_to_pascal_case(s: &str) -> String {
-    s.split('_')
-        .map(|word| {
-            let mut chars = word.chars();
-            match chars.next() {
-                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
-                None => String::new(),
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::asset::AssetPlugin;
+    use bevy::platform::collections::HashMap;
+    use bevy_tiledmap_assets::{TiledmapAssetsPlugin, embedded};
+    use bevy_tiledmap_macros::TiledClass;
+
+    use crate::components::TiledMap;
+    use crate::events::MapSpawned;
+    use crate::plugin::TiledmapCorePlugin;
+
+    use super::*;
+
+    #[derive(Component, Reflect, TiledClass, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    #[tiled(name = "objects_test::Crate")]
+    struct Crate {
+        weight: i32,
+        fragile: bool,
+    }
+
+    #[derive(Component, Reflect, TiledClass, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    #[tiled(name = "objects_test::Loot")]
+    struct Loot {
+        gold: i32,
+    }
+
+    /// Number of times a `Crate` was inserted on each entity.
+    #[derive(Resource, Default)]
+    struct CrateInserts(HashMap<Entity, usize>);
+
+    #[derive(Resource, Default)]
+    struct Spawned(bool);
+
+    #[test]
+    fn test_tile_objects_get_their_tile_class_component() {
+        const MAP: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" renderorder="right-down" width="4" height="4" tilewidth="16" tileheight="16" infinite="0" nextlayerid="2" nextobjectid="4">
+ <tileset firstgid="1" name="crates" tilewidth="16" tileheight="16" tilecount="1" columns="0">
+  <tile id="0" class="objects_test::Crate">
+   <properties>
+    <property name="weight" type="int" value="5"/>
+    <property name="fragile" type="bool" value="true"/>
+   </properties>
+  </tile>
+ </tileset>
+ <objectgroup id="1" name="Objects">
+  <object id="1" name="Unclassed" gid="1" x="0" y="16" width="16" height="16">
+   <properties>
+    <property name="weight" type="int" value="9"/>
+   </properties>
+  </object>
+  <object id="2" name="SameClass" class="objects_test::Crate" gid="1" x="16" y="16" width="16" height="16">
+   <properties>
+    <property name="weight" type="int" value="7"/>
+   </properties>
+  </object>
+  <object id="3" name="OwnClass" class="objects_test::Loot" gid="1" x="32" y="16" width="16" height="16">
+   <properties>
+    <property name="gold" type="int" value="3"/>
+   </properties>
+  </object>
+ </objectgroup>
+</map>"#;
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            TransformPlugin,
+            TiledmapAssetsPlugin,
+            TiledmapCorePlugin::default(),
+        ))
+        .init_asset::<Image>()
+        .register_type::<Crate>()
+        .register_type::<Loot>()
+        .init_resource::<CrateInserts>()
+        .init_resource::<Spawned>()
+        .add_observer(
+            |insert: On<Insert, Crate>, mut inserts: ResMut<CrateInserts>| {
+                *inserts.0.entry(insert.entity).or_default() += 1;
+            },
+        )
+        .add_observer(|_: On<MapSpawned>, mut spawned: ResMut<Spawned>| {
+            spawned.0 = true;
+        });
+        embedded::embed(&mut app, "objects/tile_class.tmx", "tile_class.tmx", MAP);
+
+        let handle = app
+            .world()
+            .resource::<AssetServer>()
+            .load("embedded://objects/tile_class.tmx");
+        app.world_mut().spawn(TiledMap { handle });
+        for _ in 0..1000 {
+            if app.world().resource::<Spawned>().0 {
+                break;
             }
-        })
-        .collect()
+            app.update();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(app.world().resource::<Spawned>().0, "map did not spawn");
+        app.update();
+
+        let world = app.world_mut();
+        let mut objects = world.query::<(Entity, &ObjectName)>();
+        let objects: HashMap<String, Entity> = objects
+            .iter(world)
+            .map(|(entity, name)| (name.0.clone(), entity))
+            .collect();
+        let crate_of = |name: &str| world.get::<Crate>(objects[name]);
+
+        // Tile properties with the object's overrides on top
+        assert_eq!(
+            crate_of("Unclassed"),
+            Some(&Crate {
+                weight: 9,
+                fragile: true
+            })
+        );
+        assert_eq!(
+            crate_of("SameClass"),
+            Some(&Crate {
+                weight: 7,
+                fragile: true
+            })
+        );
+        // Objects with their own class get both components
+        assert_eq!(
+            crate_of("OwnClass"),
+            Some(&Crate {
+                weight: 5,
+                fragile: true
+            })
+        );
+        assert_eq!(
+            world.get::<Loot>(objects["OwnClass"]),
+            Some(&Loot { gold: 3 })
+        );
+
+        // The tile class component isn't inserted again for objects of the same class
+        let inserts = &world.resource::<CrateInserts>().0;
+        for name in ["Unclassed", "SameClass", "OwnClass"] {
+            assert_eq!(inserts.get(&objects[name]), Some(&1), "{name}");
+        }
+    }
 }
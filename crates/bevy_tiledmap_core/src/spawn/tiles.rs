@@ -1,16 +1,77 @@
 //! Tile layer spawning.
 
+use std::collections::HashMap;
 use std::panic;
 
 use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::{TiledMapAsset, TiledMapLoadIssue, TiledTilesetAsset};
 use tiled::{LayerType, TileLayer};
 
 use crate::components::tile::{TileInstance, TileLayerData};
 use crate::systems::SpawnContext;
+use crate::variation::{pick_variant, tile_variants};
+
+/// How tiles referencing a GID outside their tileset are spawned.
+///
+/// Such tiles appear in maps edited after tiles were removed from a tileset.
+/// The map loader records each one as a [`TiledMapLoadIssue::InvalidTile`],
+/// with its layer and coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MissingTileRecovery {
+    /// Leave the tile empty (the map loader has already logged a warning)
+    #[default]
+    Skip,
+    /// Spawn this local tile ID of the tile's tileset instead
+    Placeholder(u32),
+    /// Don't spawn the map, logging an error for each invalid tile
+    Fail,
+}
+
+/// Resource selecting how tiles outside their tileset are spawned.
+///
+/// Inserted from `TiledmapCoreConfig::missing_tiles`.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MissingTileConfig {
+    /// How invalid tiles are spawned (default: [`MissingTileRecovery::Skip`])
+    pub recovery: MissingTileRecovery,
+}
+
+/// Get the tiles outside their tileset recorded while loading a map.
+pub fn invalid_tiles(map_asset: &TiledMapAsset) -> impl Iterator<Item = &TiledMapLoadIssue> {
+    map_asset
+        .load_issues
+        .iter()
+        .filter(|issue| matches!(issue, TiledMapLoadIssue::InvalidTile { .. }))
+}
+
+/// Picks visual tile variants while building a layer, caching candidates per tile.
+struct TileVariator {
+    seed: u64,
+    layer_id: u32,
+    candidates: HashMap<(AssetId<TiledTilesetAsset>, u32), Vec<(u32, f32)>>,
+}
+
+impl TileVariator {
+    fn pick(&mut self, tile: &TileInstance, x: u32, y: u32, context: &SpawnContext) -> Option<u32> {
+        let candidates = self
+            .candidates
+            .entry((tile.tileset_handle.id(), tile.tile_id))
+            .or_insert_with(|| {
+                context
+                    .tileset_assets
+                    .get(&tile.tileset_handle)
+                    .map(|tileset| tile_variants(tileset, tile.tile_id, context.property_keys))
+                    .unwrap_or_default()
+            });
+        let variant = pick_variant(candidates, self.seed, self.layer_id, UVec2::new(x, y));
+        (candidates.len() > 1 && variant != tile.tile_id).then_some(variant)
+    }
+}
 
 /// Build `TileLayerData` component from a tile layer.
 ///
-/// Pre-processes all tiles: looks up tilesets by index, extracts flip flags.
+/// Pre-processes all tiles: looks up tilesets by index, extracts flip flags and,
+/// when the context has a variation seed, picks visual tile variants.
 /// Handles both finite (bounded) and infinite (chunk-based) tile layers.
 ///
 /// # Arguments
@@ -30,10 +91,18 @@ pub fn build_tile_layer_data(
         return None;
     };
 
+    let mut variator = context.variation_seed.map(|seed| TileVariator {
+        seed,
+        layer_id: layer.id(),
+        candidates: HashMap::new(),
+    });
+
     match tile_layer {
-        TileLayer::Finite(finite_layer) => build_finite_tile_layer_data(finite_layer, context),
+        TileLayer::Finite(finite_layer) => {
+            build_finite_tile_layer_data(finite_layer, context, variator.as_mut())
+        }
         TileLayer::Infinite(infinite_layer) => {
-            build_infinite_tile_layer_data(infinite_layer, context)
+            build_infinite_tile_layer_data(infinite_layer, context, variator.as_mut())
         }
     }
 }
@@ -42,6 +111,7 @@ pub fn build_tile_layer_data(
 fn build_finite_tile_layer_data(
     tile_layer: tiled::FiniteTileLayer,
     context: &SpawnContext,
+    mut variator: Option<&mut TileVariator>,
 ) -> Option<TileLayerData> {
     let width = tile_layer.width();
     let height = tile_layer.height();
@@ -75,7 +145,9 @@ fn build_finite_tile_layer_data(
                 }
             };
 
-            if let Some(tile_instance) = create_tile_instance(&tile, x, y, context) {
+            if let Some(tile_instance) =
+                create_tile_instance(&tile, x, y, context, variator.as_deref_mut())
+            {
                 tile_data.set(x, y, Some(tile_instance));
             }
         }
@@ -94,11 +166,73 @@ fn build_finite_tile_layer_data(
 fn build_infinite_tile_layer_data(
     infinite_layer: tiled::InfiniteTileLayer,
     context: &SpawnContext,
+    variator: Option<&mut TileVariator>,
 ) -> Option<TileLayerData> {
     // Get pre-calculated dimensions from map asset
     let width = context.map_asset.tilemap_size.x;
     let height = context.map_asset.tilemap_size.y;
 
+    let mut tile_data = TileLayerData::empty(width, height);
+    for_each_infinite_tile(
+        infinite_layer,
+        context,
+        variator,
+        |_, tile_x, tile_y, tile| {
+            tile_data.set(tile_x, tile_y, Some(tile));
+        },
+    );
+
+    Some(tile_data)
+}
+
+/// Build one 16x16 `TileLayerData` per chunk of an infinite tile layer.
+///
+/// Used instead of [`build_tile_layer_data`] when chunks are streamed (see
+/// [`crate::chunks`]). Tiles are pre-processed the same way, and get the same
+/// variants as in the whole layer. Returns `None` for finite layers.
+pub fn build_chunked_tile_layer_data(
+    layer: &tiled::Layer,
+    context: &SpawnContext,
+) -> Option<bevy::platform::collections::HashMap<IVec2, TileLayerData>> {
+    let LayerType::Tiles(TileLayer::Infinite(infinite_layer)) = layer.layer_type() else {
+        return None;
+    };
+
+    let mut variator = context.variation_seed.map(|seed| TileVariator {
+        seed,
+        layer_id: layer.id(),
+        candidates: HashMap::new(),
+    });
+
+    let chunk_width = tiled::ChunkData::WIDTH;
+    let chunk_height = tiled::ChunkData::HEIGHT;
+    let mut chunks = bevy::platform::collections::HashMap::default();
+    for_each_infinite_tile(
+        infinite_layer,
+        context,
+        variator.as_mut(),
+        |chunk, tile_x, tile_y, tile| {
+            chunks
+                .entry(chunk)
+                .or_insert_with(|| TileLayerData::empty(chunk_width, chunk_height))
+                .set(tile_x % chunk_width, tile_y % chunk_height, Some(tile));
+        },
+    );
+
+    Some(chunks)
+}
+
+/// Call `f` with the chunk and normalized grid position of each tile of an
+/// infinite tile layer.
+///
+/// Grid positions are offset by the top-left chunk so that negative chunks map
+/// to positive tile indices.
+fn for_each_infinite_tile(
+    infinite_layer: tiled::InfiniteTileLayer,
+    context: &SpawnContext,
+    mut variator: Option<&mut TileVariator>,
+    mut f: impl FnMut(IVec2, u32, u32, TileInstance),
+) {
     // Get the topleft chunk to calculate offset
     let (min_chunk_x, min_chunk_y) = context.map_asset.topleft_chunk;
 
@@ -106,8 +240,6 @@ fn build_infinite_tile_layer_data(
     let chunk_width = tiled::ChunkData::WIDTH;
     let chunk_height = tiled::ChunkData::HEIGHT;
 
-    let mut tile_data = TileLayerData::empty(width, height);
-
     // Iterate all chunks in this layer
     for ((chunk_x, chunk_y), _chunk) in infinite_layer.chunks() {
         // Calculate the tile offset for this chunk
@@ -148,25 +280,29 @@ fn build_infinite_tile_layer_data(
                     let tile_x = chunk_offset_x + local_x;
                     let tile_y = chunk_offset_y + local_y;
 
-                    if let Some(tile_instance) =
-                        create_tile_instance(&tile, tile_x, tile_y, context)
-                    {
-                        tile_data.set(tile_x, tile_y, Some(tile_instance));
+                    if let Some(tile_instance) = create_tile_instance(
+                        &tile,
+                        tile_x,
+                        tile_y,
+                        context,
+                        variator.as_deref_mut(),
+                    ) {
+                        f(IVec2::new(chunk_x, chunk_y), tile_x, tile_y, tile_instance);
                     }
                 }
             }
         }
     }
-
-    Some(tile_data)
 }
 
-/// Create a `TileInstance` from a `LayerTile`, handling tileset lookup and flip flags.
+/// Create a `TileInstance` from a `LayerTile`, handling tileset lookup, flip flags
+/// and variant selection.
 fn create_tile_instance(
     tile: &tiled::LayerTile,
     x: u32,
     y: u32,
     context: &SpawnContext,
+    variator: Option<&mut TileVariator>,
 ) -> Option<TileInstance> {
     let tile_id = tile.id();
     let tileset_index = tile.tileset_index();
@@ -179,12 +315,27 @@ fn create_tile_instance(
         return None;
     };
 
-    Some(TileInstance {
+    // Tiles past the end of their tileset were reported by the map loader
+    let tile_id = match context.tileset_assets.get(&tileset_ref.handle) {
+        Some(tileset) if !tileset.contains_tile(tile_id) => match context.missing_tiles {
+            MissingTileRecovery::Placeholder(placeholder) => placeholder,
+            MissingTileRecovery::Skip | MissingTileRecovery::Fail => return None,
+        },
+        _ => tile_id,
+    };
+
+    let mut tile_instance = TileInstance {
         gid: tile_id, // Store local ID (we don't need GID anymore)
         tileset_handle: tileset_ref.handle.clone(),
         tile_id,
         flipped_h: tile.flip_h,
         flipped_v: tile.flip_v,
         flipped_d: tile.flip_d,
-    })
+        variant: None,
+    };
+    if let Some(variator) = variator {
+        tile_instance.variant = variator.pick(&tile_instance, x, y, context);
+    }
+
+    Some(tile_instance)
 }
@@ -1,17 +1,38 @@
 //! Spawn context for accessing asset data during entity spawning.
 
+use std::sync::Arc;
+
 use bevy::{asset::AssetServer, prelude::*};
 use bevy_tiledmap_assets::assets::map::TilesetReference;
 use bevy_tiledmap_assets::prelude::{TiledMapAsset, TiledTemplateAsset, TiledTilesetAsset};
-use tiled::Properties;
+use tiled::{Properties, PropertyValue};
+
+use crate::components::WorldMapProperties;
+use crate::conditions::SpawnConditions;
+use crate::events::SpawnEventMode;
+use crate::localization::TiledLocalization;
+use crate::object_transform::{
+    ObjectTransformInput, ObjectTransforms, SNAP_TO_GRID_PROPERTY, snap_to_grid,
+};
+use crate::project::TiledProjectProperties;
+use crate::properties::{
+    MergedProperties, PropertyErrorContext, PropertyInterner, PropertyKeyConfig,
+    PropertyMergeConfig,
+};
+use crate::spawn::components::ObjectComponentCache;
+use crate::spawn::tiles::MissingTileRecovery;
 
 /// Read-only context providing access to asset data during spawning.
 ///
 /// Used internally by the spawning system. Not passed to Layer 3 events.
+#[derive(Clone)]
 pub struct SpawnContext<'a> {
     /// The map asset being spawned
     pub map_asset: &'a TiledMapAsset,
 
+    /// Asset path of the map, for locating property errors
+    pub map_path: Option<&'a str>,
+
     /// Access to all tileset assets
     pub tileset_assets: &'a Assets<TiledTilesetAsset>,
 
@@ -23,6 +44,57 @@ pub struct SpawnContext<'a> {
 
     /// Asset server for loading `Handle<T>` fields during deserialization
     pub asset_server: &'a AssetServer,
+
+    /// Loaded Tiled project, for resolving class default values
+    pub project: &'a TiledProjectProperties,
+
+    /// Object property merge order
+    pub merge_config: &'a PropertyMergeConfig,
+
+    /// Property names read by built-in features
+    pub property_keys: &'a PropertyKeyConfig,
+
+    /// Seed for random tile variation (`None` disables it)
+    pub variation_seed: Option<u64>,
+
+    /// Seed for object `spawn_chance` rolls
+    pub spawn_chance_seed: u64,
+
+    /// Evaluator for `condition` properties (`None` lets everything spawn)
+    pub conditions: Option<&'a SpawnConditions>,
+
+    /// Whether entities are spawned disabled (inside a layer whose condition failed)
+    pub spawn_disabled: bool,
+
+    /// Group layer containing the layers being spawned (`None` at the top level)
+    pub parent_group: Option<Entity>,
+
+    /// How many groups the layers being spawned are nested in
+    pub layer_depth: u32,
+
+    /// Localizer for text objects and string properties
+    pub localization: Option<&'a TiledLocalization>,
+
+    /// Hook adjusting spawned object transforms
+    pub object_transforms: Option<&'a ObjectTransforms>,
+
+    /// Which object spawn events are fired
+    pub event_mode: SpawnEventMode,
+
+    /// Shared storage of identical object properties (`None` gives each object its own copy)
+    pub property_interner: Option<&'a PropertyInterner>,
+
+    /// How tiles outside their tileset are spawned
+    pub missing_tiles: MissingTileRecovery,
+
+    /// Whether the tiles of infinite layers are spawned per chunk, around viewers
+    pub stream_chunks: bool,
+
+    /// Object components already deserialized in this spawn (shared by clones of the context)
+    pub object_components: Arc<ObjectComponentCache>,
+
+    /// Properties the map's world gives it (`None` for maps outside a world)
+    pub world_properties: Option<&'a WorldMapProperties>,
 }
 
 impl<'a> SpawnContext<'a> {
@@ -33,16 +105,173 @@ impl<'a> SpawnContext<'a> {
         template_assets: &'a Assets<TiledTemplateAsset>,
         registry: &'a crate::properties::TiledClassRegistry,
         asset_server: &'a AssetServer,
+        project: &'a TiledProjectProperties,
+        merge_config: &'a PropertyMergeConfig,
     ) -> Self {
         Self {
             map_asset,
+            map_path: None,
             tileset_assets,
             template_assets,
             registry,
             asset_server,
+            project,
+            merge_config,
+            property_keys: PropertyKeyConfig::builtin(),
+            variation_seed: None,
+            spawn_chance_seed: 0,
+            conditions: None,
+            spawn_disabled: false,
+            parent_group: None,
+            layer_depth: 0,
+            localization: None,
+            object_transforms: None,
+            event_mode: SpawnEventMode::PerEntity,
+            property_interner: None,
+            missing_tiles: MissingTileRecovery::Skip,
+            stream_chunks: false,
+            object_components: Arc::default(),
+            world_properties: None,
+        }
+    }
+
+    /// Name the map's asset path in property errors.
+    pub fn with_map_path(mut self, map_path: &'a str) -> Self {
+        self.map_path = Some(map_path);
+        self
+    }
+
+    /// Context of a property error in the map being spawned.
+    pub fn property_error_context(&self) -> PropertyErrorContext {
+        PropertyErrorContext::map(self.map_path)
+    }
+
+    /// Read built-in properties (`spawn_chance`, `condition`, ...) by the names in `property_keys`.
+    pub fn with_property_keys(mut self, property_keys: &'a PropertyKeyConfig) -> Self {
+        self.property_keys = property_keys;
+        self
+    }
+
+    /// Enable random tile variation with the given per-map seed.
+    pub fn with_variation_seed(mut self, seed: Option<u64>) -> Self {
+        self.variation_seed = seed;
+        self
+    }
+
+    /// Set the per-map seed for object `spawn_chance` rolls.
+    pub fn with_spawn_chance_seed(mut self, seed: u64) -> Self {
+        self.spawn_chance_seed = seed;
+        self
+    }
+
+    /// Evaluate object and layer `condition` properties with `conditions`.
+    pub fn with_conditions(mut self, conditions: &'a SpawnConditions) -> Self {
+        self.conditions = Some(conditions);
+        self
+    }
+
+    /// Localize text objects and string properties with `localization`.
+    pub fn with_localization(mut self, localization: &'a TiledLocalization) -> Self {
+        self.localization = Some(localization);
+        self
+    }
+
+    /// Adjust spawned object transforms with the hook in `object_transforms`.
+    pub fn with_object_transforms(mut self, object_transforms: &'a ObjectTransforms) -> Self {
+        self.object_transforms = Some(object_transforms);
+        self
+    }
+
+    /// Fire the object spawn events selected by `event_mode`.
+    pub fn with_event_mode(mut self, event_mode: SpawnEventMode) -> Self {
+        self.event_mode = event_mode;
+        self
+    }
+
+    /// Share identical object properties through `property_interner`.
+    pub fn with_property_interner(mut self, property_interner: &'a PropertyInterner) -> Self {
+        self.property_interner = Some(property_interner);
+        self
+    }
+
+    /// Spawn tiles outside their tileset as selected by `missing_tiles`.
+    pub fn with_missing_tiles(mut self, missing_tiles: MissingTileRecovery) -> Self {
+        self.missing_tiles = missing_tiles;
+        self
+    }
+
+    /// Spawn the tiles of infinite layers per chunk (see [`crate::chunks`]).
+    pub fn with_chunk_streaming(mut self, stream_chunks: bool) -> Self {
+        self.stream_chunks = stream_chunks;
+        self
+    }
+
+    /// Merge the properties `world_properties` gives the map into its own.
+    pub fn with_world_properties(
+        mut self,
+        world_properties: Option<&'a WorldMapProperties>,
+    ) -> Self {
+        self.world_properties = world_properties;
+        self
+    }
+
+    /// Build the `MergedProperties` component of an object, interning its properties.
+    pub fn merged_properties(&self, properties: Properties) -> MergedProperties {
+        match self.property_interner {
+            Some(interner) => MergedProperties::from_shared(interner.intern(properties)),
+            None => MergedProperties::new(properties),
+        }
+    }
+
+    /// Localize a string if it is a localization key.
+    pub fn localize(&self, text: &str) -> Option<String> {
+        self.localization?.localize(text)
+    }
+
+    /// Localize string properties that are localization keys.
+    pub fn localize_properties(&self, properties: &mut Properties) {
+        if let Some(localization) = self.localization {
+            localization.localize_properties(properties);
         }
     }
 
+    /// Snap an object's computed transform to the tile grid if its
+    /// `snap_to_grid` property and the grid snap policy say so, then apply the
+    /// object transform hook.
+    pub fn transform_object(
+        &self,
+        input: &ObjectTransformInput,
+        mut transform: Transform,
+    ) -> Transform {
+        let snap = match self
+            .property_keys
+            .get(input.properties, SNAP_TO_GRID_PROPERTY)
+        {
+            Some(PropertyValue::BoolValue(snap)) => Some(*snap),
+            _ => None,
+        };
+        let grid_snap = self
+            .object_transforms
+            .map(|object_transforms| object_transforms.grid_snap)
+            .unwrap_or_default();
+        if grid_snap.snaps(snap) {
+            let map = &self.map_asset.map;
+            let grid = Vec2::new(map.tile_width as f32, map.tile_height as f32);
+            transform = snap_to_grid(input.position, grid, transform);
+        }
+        match self.object_transforms {
+            Some(object_transforms) => object_transforms.apply(input, transform),
+            None => transform,
+        }
+    }
+
+    /// Check an object's or layer's `condition` property.
+    ///
+    /// Returns the failed condition, or `None` if it holds (or there is none).
+    pub fn failed_condition(&self, properties: &Properties) -> Option<String> {
+        self.conditions?.check(properties, self.property_keys)
+    }
+
     /// Get tileset reference by index.
     ///
     /// The index corresponds to `LayerTile::tileset_index()` from the tiled crate.
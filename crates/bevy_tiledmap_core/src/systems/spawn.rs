@@ -1,23 +1,110 @@
 //! Main reactive spawning system.
 
 use bevy::asset::RecursiveDependencyLoadState;
+use bevy::platform::time::Instant;
 use bevy::prelude::*;
 use bevy_tiledmap_assets::prelude::{TiledMapAsset, TiledTilesetAsset, TiledWorldAsset};
 use std::collections::HashMap;
 
-use crate::components::{MapsInWorld, TiledMap, TiledWorld, TiledWorldOf};
-use crate::events::{MapSpawned, WorldSpawned};
+use crate::chunks::ChunkStreamingConfig;
+use crate::components::{
+    MapInstanceId, MapsInWorld, TiledMap, TiledMapInfo, TiledWorld, TiledWorldOf,
+    WorldMapProperties,
+};
+use crate::conditions::SpawnConditions;
+use crate::events::{
+    MapCompatibilityWarning, MapSpawnReport, MapSpawned, SpawnEventConfig, WorldSpawned,
+    trigger_spawn_event,
+};
+use crate::localization::TiledLocalization;
+use crate::object_transform::ObjectTransforms;
+use crate::patch::MapPatch;
 use crate::plugin::LayerZConfig;
+use crate::project::TiledProjectProperties;
+use crate::properties::{
+    MergedProperties, PropertyInterner, PropertyKeyConfig, PropertyMergeConfig,
+};
 use crate::spawn::spawn_map;
+use crate::spawn::tiles::{MissingTileConfig, MissingTileRecovery, invalid_tiles};
 use crate::systems::SpawnContext;
+use crate::variation::{SpawnChanceConfig, TileVariationConfig};
 
 /// Resource tracking Z-ordering counters per world.
 ///
 /// Maps in a world share a single Z counter so layers across all maps
-/// get unique, sequential Z values without collisions.
+/// get unique, sequential Z values without collisions. Entries are removed
+/// when their map or world entity is despawned.
 #[derive(Resource, Default)]
 pub struct WorldZCounters(pub HashMap<Entity, usize>);
 
+/// Resource counting spawned instances of each map asset.
+///
+/// Used to number [`MapInstanceId`]s; never decremented, so instance numbers
+/// aren't reused while any instance of the map is alive. An asset's entry is
+/// removed when its last instance is despawned, so numbering starts over.
+#[derive(Resource, Default)]
+pub struct MapInstanceCounters(pub HashMap<AssetId<TiledMapAsset>, u32>);
+
+/// Observer that numbers each new `TiledMap` entity among those sharing its asset.
+///
+/// Entities that already have a [`MapInstanceId`] keep it.
+pub fn assign_map_instance_id(
+    trigger: On<Add, TiledMap>,
+    map_query: Query<&TiledMap, Without<MapInstanceId>>,
+    mut counters: ResMut<MapInstanceCounters>,
+    mut commands: Commands,
+) {
+    let map_entity = trigger.event().entity;
+    let Ok(tiled_map) = map_query.get(map_entity) else {
+        return;
+    };
+
+    let counter = counters.0.entry(tiled_map.handle.id()).or_insert(0);
+    commands.entity(map_entity).insert(MapInstanceId(*counter));
+    *counter += 1;
+}
+
+/// Observer that drops the counters of a `TiledMap` entity being despawned.
+///
+/// Removes its [`WorldZCounters`] entry, and its asset's [`MapInstanceCounters`]
+/// entry if no other map entity uses that asset.
+pub fn release_map_counters(
+    trigger: On<Remove, TiledMap>,
+    map_query: Query<(Entity, &TiledMap)>,
+    mut world_z_counters: ResMut<WorldZCounters>,
+    mut instance_counters: ResMut<MapInstanceCounters>,
+) {
+    let map_entity = trigger.event().entity;
+    world_z_counters.0.remove(&map_entity);
+
+    let Ok((_, tiled_map)) = map_query.get(map_entity) else {
+        return;
+    };
+    let asset = tiled_map.handle.id();
+    let in_use = map_query
+        .iter()
+        .any(|(entity, other)| entity != map_entity && other.handle.id() == asset);
+    if !in_use {
+        instance_counters.0.remove(&asset);
+    }
+}
+
+/// Observer that drops the shared Z counter of a `TiledWorld` entity being despawned.
+pub fn release_world_z_counter(
+    trigger: On<Remove, TiledWorld>,
+    mut world_z_counters: ResMut<WorldZCounters>,
+) {
+    world_z_counters.0.remove(&trigger.event().entity);
+}
+
+/// Get the asset path of a map as a string (empty if the handle has no path).
+pub fn map_path(asset_server: &AssetServer, handle: &Handle<TiledMapAsset>) -> String {
+    asset_server
+        .get_path(handle)
+        .map(|path| path.to_string())
+        .unwrap_or_default()
+}
+
 /// Marker component to trigger map respawning.
 ///
 /// Add this component to force the map to be respawned even if it hasn't changed.
@@ -44,21 +131,61 @@ pub fn process_loaded_maps(
     tileset_assets: Res<Assets<TiledTilesetAsset>>,
     template_assets: Res<Assets<bevy_tiledmap_assets::prelude::TiledTemplateAsset>>,
     registry: Res<crate::properties::TiledClassRegistry>,
+    project: Res<TiledProjectProperties>,
+    (merge_config, property_keys): (Res<PropertyMergeConfig>, Res<PropertyKeyConfig>),
     type_registry: Res<AppTypeRegistry>,
     z_config: Res<LayerZConfig>,
-    mut world_z_counters: ResMut<WorldZCounters>,
+    variation: Res<TileVariationConfig>,
+    spawn_chance: Res<SpawnChanceConfig>,
+    conditions: Res<SpawnConditions>,
+    (localization, object_transforms, spawn_events, property_interner, missing_tiles, chunks): (
+        Res<TiledLocalization>,
+        Res<ObjectTransforms>,
+        Res<SpawnEventConfig>,
+        Res<PropertyInterner>,
+        Res<MissingTileConfig>,
+        Res<ChunkStreamingConfig>,
+    ),
+    (mut world_z_counters, base_query): (
+        ResMut<WorldZCounters>,
+        Query<(Has<crate::components::LayersInMap>, Option<&TiledWorldOf>)>,
+    ),
     mut commands: Commands,
     mut map_query: Query<
-        (Entity, &TiledMap, Option<&TiledWorldOf>),
+        (
+            Entity,
+            &TiledMap,
+            Option<&MapInstanceId>,
+            Option<&TiledWorldOf>,
+            Option<&WorldMapProperties>,
+            Option<&MapPatch>,
+        ),
         Or<(
             Without<crate::components::LayersInMap>,
             With<RespawnTiledMap>,
         )>,
     >,
 ) {
-    for (map_entity, tiled_map, world_of) in map_query.iter_mut() {
+    for (map_entity, tiled_map, instance, world_of, world_properties, patch) in map_query.iter_mut()
+    {
         info!("Processing map entity {:?}", map_entity);
 
+        // Patches wait for their base map to spawn, to stack their layers above it
+        let patch_base = match patch {
+            None => None,
+            Some(patch) => match base_query.get(patch.base) {
+                Ok((true, base_world)) => Some(base_world.map_or(patch.base, |world| world.0)),
+                Ok((false, _)) => continue,
+                Err(_) => {
+                    warn!("Not spawning map patch {map_entity:?}: its base map is gone");
+                    commands
+                        .entity(map_entity)
+                        .remove::<(TiledMap, RespawnTiledMap)>();
+                    continue;
+                }
+            },
+        };
+
         // Check if all dependencies have finished loading
         let load_state = asset_server.get_recursive_dependency_load_state(&tiled_map.handle);
         info!(
@@ -79,6 +206,20 @@ pub fn process_loaded_maps(
             continue;
         };
 
+        if missing_tiles.recovery == MissingTileRecovery::Fail
+            && invalid_tiles(map_asset).next().is_some()
+        {
+            let path = map_path(&asset_server, &tiled_map.handle);
+            for issue in invalid_tiles(map_asset) {
+                error!("{path}: {issue}");
+            }
+            error!("Not spawning map {path}: it has tiles outside their tileset");
+            commands
+                .entity(map_entity)
+                .remove::<(TiledMap, RespawnTiledMap)>();
+            continue;
+        }
+
         // Get map name from asset path (only if entity doesn't already have a name)
         let map_name = asset_server
             .get_path(&tiled_map.handle)
@@ -93,31 +234,64 @@ pub fn process_loaded_maps(
 
         info!("Spawning map hierarchy for '{}'", map_name);
 
-        // Add name to map entity if it doesn't have one
-        commands
-            .entity(map_entity)
-            .insert(Name::new(format!("Map: {}", map_name)));
+        // Add name to map entity, numbering repeated instances of the same map
+        let name = match instance {
+            Some(MapInstanceId(instance)) if *instance > 0 => {
+                format!("Map: {} #{}", map_name, instance)
+            }
+            _ => format!("Map: {}", map_name),
+        };
+        commands.entity(map_entity).insert(Name::new(name));
 
         // Create spawn context with asset references
+        let path = map_path(&asset_server, &tiled_map.handle);
+        commands
+            .entity(map_entity)
+            .insert(TiledMapInfo::new(&map_asset.map, path.clone()));
         let context = SpawnContext::new(
             map_asset,
             &tileset_assets,
             &template_assets,
             &registry,
             &asset_server,
-        );
+            &project,
+            &merge_config,
+        )
+        .with_map_path(&path)
+        .with_property_keys(&property_keys)
+        .with_variation_seed(variation.map_seed(&path))
+        .with_spawn_chance_seed(spawn_chance.map_seed(&path))
+        .with_conditions(&conditions)
+        .with_localization(&localization)
+        .with_object_transforms(&object_transforms)
+        .with_event_mode(spawn_events.mode)
+        .with_property_interner(&property_interner)
+        .with_missing_tiles(missing_tiles.recovery)
+        .with_chunk_streaming(chunks.stream_tiles)
+        .with_world_properties(world_properties);
 
         // Get or initialize z_counter: use world counter if in a world, else use 0
-        let z_counter = if let Some(TiledWorldOf(world_entity)) = world_of {
+        let z_counter = if let Some(base_key) = patch_base {
+            // Patch - numbered on from the layers of its base (or the base's world)
+            let start = world_z_counters.0.get(&base_key).copied().unwrap_or(0);
+            let counter = world_z_counters.0.entry(map_entity).or_insert(0);
+            *counter = start;
+            counter
+        } else if let Some(TiledWorldOf(world_entity)) = world_of {
             world_z_counters.0.entry(*world_entity).or_insert(0)
         } else {
-            // Standalone map - use a temporary counter
-            // (we store it anyway to simplify the borrow, but it won't persist)
-            world_z_counters.0.entry(map_entity).or_insert(0)
+            // Standalone map - counter keyed by this map entity alone, restarted on
+            // every (re)spawn so instances and respawns all get the same layer Z values
+            let counter = world_z_counters.0.entry(map_entity).or_insert(0);
+            *counter = 0;
+            counter
         };
 
         // Spawn the map hierarchy with shared z_counter
-        spawn_map(
+        #[cfg(feature = "trace")]
+        let _span = info_span!("spawn_tiled_map", map = %path).entered();
+        let started = Instant::now();
+        let counts = spawn_map(
             &mut commands,
             map_entity,
             &context,
@@ -129,9 +303,24 @@ pub fn process_loaded_maps(
         info!("Map hierarchy spawned successfully");
 
         // Trigger MapSpawned event on the entity for observers
-        commands
-            .entity(map_entity)
-            .trigger(|entity| MapSpawned { entity });
+        trigger_spawn_event(&mut commands, MapSpawned { entity: map_entity });
+        trigger_spawn_event(
+            &mut commands,
+            MapSpawnReport {
+                entity: map_entity,
+                counts,
+                duration: started.elapsed(),
+            },
+        );
+        for issue in &map_asset.compatibility_issues {
+            trigger_spawn_event(
+                &mut commands,
+                MapCompatibilityWarning {
+                    entity: map_entity,
+                    issue: issue.clone(),
+                },
+            );
+        }
 
         // Remove RespawnTiledMap marker if present
         commands.entity(map_entity).remove::<RespawnTiledMap>();
@@ -201,10 +390,11 @@ pub fn process_loaded_worlds(
             })
             .unwrap_or_else(|| "World".to_string());
 
-        // Add name to world entity
-        commands
-            .entity(world_entity)
-            .insert(Name::new(world_name.clone()));
+        // Add name and custom properties to world entity
+        commands.entity(world_entity).insert((
+            Name::new(world_name.clone()),
+            MergedProperties::new(world_asset.properties.clone()),
+        ));
 
         info!(
             "World '{}' found with {} maps, spawning map entities",
@@ -252,6 +442,13 @@ pub fn process_loaded_worlds(
                     },
                     Transform::from_translation(position),
                     TiledWorldOf(world_entity),
+                    WorldMapProperties {
+                        inherited: world_asset.properties.clone(),
+                        overrides: world_asset
+                            .get_map_properties(&world_map.filename)
+                            .cloned()
+                            .unwrap_or_default(),
+                    },
                 ))
                 .id();
 
@@ -296,10 +493,125 @@ pub fn check_world_spawn_complete(
                 "All maps in world {:?} are ready, firing WorldSpawned",
                 world_entity
             );
-            commands
-                .entity(world_entity)
-                .trigger(|entity| WorldSpawned { entity });
+            trigger_spawn_event(
+                &mut commands,
+                WorldSpawned {
+                    entity: world_entity,
+                },
+            );
             commands.entity(world_entity).remove::<PendingWorldSpawn>();
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_instance_ids_count_per_asset() {
+        let mut app = App::new();
+        app.init_resource::<MapInstanceCounters>();
+        app.add_observer(assign_map_instance_id);
+
+        let dungeon: Handle<TiledMapAsset> =
+            bevy::asset::uuid_handle!("0b7e3c52-6f1d-4c2a-9a57-1e8b1a3f4d01");
+        let town: Handle<TiledMapAsset> =
+            bevy::asset::uuid_handle!("0b7e3c52-6f1d-4c2a-9a57-1e8b1a3f4d02");
+
+        let first = app
+            .world_mut()
+            .spawn(TiledMap {
+                handle: dungeon.clone(),
+            })
+            .id();
+        let other = app.world_mut().spawn(TiledMap { handle: town }).id();
+        let second = app
+            .world_mut()
+            .spawn(TiledMap {
+                handle: dungeon.clone(),
+            })
+            .id();
+        let chosen = app
+            .world_mut()
+            .spawn((TiledMap { handle: dungeon }, MapInstanceId(7)))
+            .id();
+        app.world_mut().flush();
+
+        let id = |entity| app.world().get::<MapInstanceId>(entity).copied();
+        assert_eq!(id(first), Some(MapInstanceId(0)));
+        assert_eq!(id(other), Some(MapInstanceId(0)));
+        assert_eq!(id(second), Some(MapInstanceId(1)));
+        assert_eq!(id(chosen), Some(MapInstanceId(7)));
+    }
+
+    #[test]
+    fn test_despawned_maps_release_counters() {
+        let mut app = App::new();
+        app.init_resource::<MapInstanceCounters>()
+            .init_resource::<WorldZCounters>()
+            .add_observer(assign_map_instance_id)
+            .add_observer(release_map_counters)
+            .add_observer(release_world_z_counter);
+
+        let dungeon: Handle<TiledMapAsset> =
+            bevy::asset::uuid_handle!("0b7e3c52-6f1d-4c2a-9a57-1e8b1a3f4d01");
+        let spawn = |app: &mut App| {
+            let map = app
+                .world_mut()
+                .spawn(TiledMap {
+                    handle: dungeon.clone(),
+                })
+                .id();
+            app.world_mut().flush();
+            // Stand-in for the spawn system's per-map Z counter
+            app.world_mut()
+                .resource_mut::<WorldZCounters>()
+                .0
+                .insert(map, 3);
+            map
+        };
+        let id = |app: &App, entity| app.world().get::<MapInstanceId>(entity).copied();
+
+        let first = spawn(&mut app);
+        let second = spawn(&mut app);
+        app.world_mut().despawn(first);
+        assert!(
+            !app.world()
+                .resource::<WorldZCounters>()
+                .0
+                .contains_key(&first)
+        );
+
+        // Numbers aren't reused while another instance is alive
+        let third = spawn(&mut app);
+        assert_eq!(id(&app, third), Some(MapInstanceId(2)));
+
+        app.world_mut().despawn(second);
+        app.world_mut().despawn(third);
+        assert!(app.world().resource::<WorldZCounters>().0.is_empty());
+        assert!(app.world().resource::<MapInstanceCounters>().0.is_empty());
+
+        // Respawning after every instance is gone starts over
+        let respawned = spawn(&mut app);
+        assert_eq!(id(&app, respawned), Some(MapInstanceId(0)));
+
+        let world = app
+            .world_mut()
+            .spawn(TiledWorld {
+                handle: Handle::default(),
+            })
+            .id();
+        app.world_mut()
+            .resource_mut::<WorldZCounters>()
+            .0
+            .insert(world, 5);
+        app.world_mut().despawn(world);
+        assert!(
+            !app.world()
+                .resource::<WorldZCounters>()
+                .0
+                .contains_key(&world)
+        );
+    }
+}
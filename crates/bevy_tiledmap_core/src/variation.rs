@@ -0,0 +1,258 @@
+//! Random visual tile variation and probabilistic object spawning.
+//!
+//! # Tile variants
+//!
+//! When [`TiledmapCoreConfig::tile_variation_seed`](crate::TiledmapCoreConfig) is
+//! set, each spawned tile may be drawn as one of its interchangeable variants:
+//!
+//! - Tiles with a `variants` string property (e.g. `"12,13,14"`) pick between
+//!   themselves and the listed local tile ids of the same tileset.
+//! - Otherwise, tiles in a Wang set pick between all tiles of that set with the
+//!   same Wang id (the same terrain on every corner and edge).
+//!
+//! Candidates are weighted by their Tiled tile probability. The choice is stored
+//! in [`TileInstance::variant`](crate::components::TileInstance) and is purely
+//! visual: `tile_id` keeps the authored tile, so collisions, classes and
+//! properties are unchanged.
+//!
+//! Choices are a hash of the seed, the map's asset path, the layer id and the
+//! tile position, so the same seed always produces the same map.
+//!
+//! # Spawn chance
+//!
+//! Objects with a `spawn_chance` float property (0.0 to 1.0) are only spawned
+//! with that probability. An object layer's `spawn_chance` property is the
+//! default for objects that don't set their own. With
+//! [`TiledmapCoreConfig::spawn_chance_seed`](crate::TiledmapCoreConfig) set, the
+//! rolls are a hash of the seed, the map's asset path, the layer id and the
+//! object id; without it, every spawn rolls anew.
+
+use std::hash::{BuildHasher, RandomState};
+
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+use tiled::{Properties, PropertyValue};
+
+use crate::properties::PropertyKeyConfig;
+
+/// Tileset tile property listing a tile's variants as comma-separated local tile ids.
+pub const TILE_VARIANTS_PROPERTY: &str = "variants";
+
+/// Object (or object layer) property holding the probability that an object spawns.
+pub const SPAWN_CHANCE_PROPERTY: &str = "spawn_chance";
+
+/// Resource holding the tile variation seed.
+///
+/// Inserted by the plugin from `TiledmapCoreConfig::tile_variation_seed`.
+/// `None` disables variation.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct TileVariationConfig {
+    /// Base seed, mixed with each map's asset path to get a per-map seed.
+    pub seed: Option<u64>,
+}
+
+impl TileVariationConfig {
+    /// Get the variation seed for the map at `map_path`, if variation is enabled.
+    pub fn map_seed(&self, map_path: &str) -> Option<u64> {
+        self.seed.map(|seed| map_seed(seed, map_path))
+    }
+}
+
+/// Resource holding the spawn chance seed.
+///
+/// Inserted by the plugin from `TiledmapCoreConfig::spawn_chance_seed`.
+/// `None` rolls differently on every spawn.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct SpawnChanceConfig {
+    /// Base seed, mixed with each map's asset path to get a per-map seed.
+    pub seed: Option<u64>,
+}
+
+impl SpawnChanceConfig {
+    /// Get the spawn chance seed for the map at `map_path`.
+    ///
+    /// Without a configured seed, returns a fresh random seed on every call.
+    pub fn map_seed(&self, map_path: &str) -> u64 {
+        match self.seed {
+            Some(seed) => map_seed(seed, map_path),
+            None => RandomState::new().hash_one(map_path),
+        }
+    }
+}
+
+/// Mix a base seed with a map's asset path.
+fn map_seed(seed: u64, map_path: &str) -> u64 {
+    // FNV-1a, so seeds are stable across Rust versions and platforms
+    let path_hash = map_path
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    mix(seed ^ path_hash)
+}
+
+/// Read a numeric `spawn_chance` property.
+pub fn spawn_chance(properties: &Properties, keys: &PropertyKeyConfig) -> Option<f32> {
+    match keys.get(properties, SPAWN_CHANCE_PROPERTY)? {
+        PropertyValue::FloatValue(value) => Some(*value),
+        PropertyValue::IntValue(value) => Some(*value as f32),
+        _ => None,
+    }
+}
+
+/// Roll whether an object with the given spawn chance spawns.
+///
+/// Deterministic for a given seed, layer and object.
+pub fn roll_spawn_chance(chance: f32, seed: u64, layer_id: u32, object_id: u32) -> bool {
+    if chance >= 1.0 {
+        return true;
+    }
+    let hash = mix(seed ^ mix(u64::from(layer_id)) ^ mix(u64::from(object_id) << 32));
+    unit_interval(hash) < chance
+}
+
+/// Get the variant candidates of a tile as `(local_tile_id, weight)` pairs.
+///
+/// The tile itself is always included. Returns just the tile when it has no
+/// variants.
+pub fn tile_variants(
+    tileset: &TiledTilesetAsset,
+    tile_id: u32,
+    keys: &PropertyKeyConfig,
+) -> Vec<(u32, f32)> {
+    let weight = |id: u32| {
+        tileset
+            .tileset
+            .get_tile(id)
+            .map_or(1.0, |tile| tile.probability)
+    };
+    let mut candidates = vec![(tile_id, weight(tile_id))];
+
+    let listed = tileset.tileset.get_tile(tile_id).and_then(|tile| {
+        match keys.get(&tile.properties, TILE_VARIANTS_PROPERTY) {
+            Some(PropertyValue::StringValue(list)) => Some(list.clone()),
+            _ => None,
+        }
+    });
+
+    if let Some(list) = listed {
+        for id in list
+            .split(',')
+            .filter_map(|id| id.trim().parse::<u32>().ok())
+        {
+            if id < tileset.tileset.tilecount && !candidates.iter().any(|(c, _)| *c == id) {
+                candidates.push((id, weight(id)));
+            }
+        }
+        return candidates;
+    }
+
+    for wang_set in &tileset.tileset.wang_sets {
+        let Some(wang_tile) = wang_set.wang_tiles.get(&tile_id) else {
+            continue;
+        };
+        let mut matching: Vec<u32> = wang_set
+            .wang_tiles
+            .iter()
+            .filter(|(id, other)| **id != tile_id && other.wang_id == wang_tile.wang_id)
+            .map(|(id, _)| *id)
+            .collect();
+        // Wang tiles are stored in a hash map; sort so choices are reproducible
+        matching.sort_unstable();
+        for id in matching {
+            if !candidates.iter().any(|(c, _)| *c == id) {
+                candidates.push((id, weight(id)));
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Pick a variant for the tile at `position` in layer `layer_id`.
+///
+/// Deterministic for a given seed, layer and position.
+pub fn pick_variant(candidates: &[(u32, f32)], seed: u64, layer_id: u32, position: UVec2) -> u32 {
+    let total: f32 = candidates.iter().map(|(_, weight)| weight.max(0.0)).sum();
+    let Some(&(first, _)) = candidates.first() else {
+        return 0;
+    };
+    if candidates.len() == 1 || total <= 0.0 {
+        return first;
+    }
+
+    let hash = mix(seed
+        ^ mix(u64::from(layer_id))
+        ^ mix((u64::from(position.x) << 32) | u64::from(position.y)));
+    let mut roll = unit_interval(hash) * total;
+    for &(id, weight) in candidates {
+        roll -= weight.max(0.0);
+        if roll < 0.0 {
+            return id;
+        }
+    }
+    candidates.last().map_or(first, |(id, _)| *id)
+}
+
+/// Map a hash to an evenly distributed `f32` in `[0, 1)` using its top 24 bits.
+fn unit_interval(hash: u64) -> f32 {
+    (hash >> 40) as f32 / (1u64 << 24) as f32
+}
+
+/// `SplitMix64` finalizer.
+fn mix(mut value: u64) -> u64 {
+    value = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_variant_is_deterministic_and_weighted() {
+        let candidates = [(12, 1.0), (13, 1.0), (14, 0.0)];
+        let seed = TileVariationConfig { seed: Some(7) }
+            .map_seed("maps/level1.tmx")
+            .unwrap();
+
+        let picks: Vec<u32> = (0..64)
+            .map(|x| pick_variant(&candidates, seed, 1, UVec2::new(x, 0)))
+            .collect();
+        let again: Vec<u32> = (0..64)
+            .map(|x| pick_variant(&candidates, seed, 1, UVec2::new(x, 0)))
+            .collect();
+        assert_eq!(picks, again);
+
+        // Both weighted variants show up; the zero-weight one never does
+        assert!(picks.contains(&12) && picks.contains(&13));
+        assert!(!picks.contains(&14));
+
+        assert_eq!(pick_variant(&[(5, 1.0)], seed, 1, UVec2::ZERO), 5);
+    }
+
+    #[test]
+    fn test_map_seed() {
+        let config = TileVariationConfig { seed: Some(7) };
+        assert_eq!(config.map_seed("a.tmx"), config.map_seed("a.tmx"));
+        assert_ne!(config.map_seed("a.tmx"), config.map_seed("b.tmx"));
+        assert_eq!(TileVariationConfig::default().map_seed("a.tmx"), None);
+    }
+
+    #[test]
+    fn test_roll_spawn_chance() {
+        let seed = SpawnChanceConfig { seed: Some(3) }.map_seed("a.tmx");
+        let spawned = (0..1000)
+            .filter(|id| roll_spawn_chance(0.25, seed, 1, *id))
+            .count();
+        assert!((150..350).contains(&spawned));
+        assert!((0..100).all(|id| roll_spawn_chance(1.0, seed, 1, id)));
+        assert!((0..100).all(|id| !roll_spawn_chance(0.0, seed, 1, id)));
+        assert_eq!(
+            roll_spawn_chance(0.5, seed, 1, 42),
+            roll_spawn_chance(0.5, seed, 1, 42)
+        );
+    }
+}
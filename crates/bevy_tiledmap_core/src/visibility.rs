@@ -0,0 +1,224 @@
+//! Runtime layer visibility.
+//!
+//! Show, hide or toggle layers by name or id with [`SetLayerVisibility`]. The
+//! command flips the layer entity's `Visibility`, which Bevy propagates to its
+//! render children (tilemaps, sprites, objects), then triggers a
+//! [`LayerVisibilityChanged`] event so physics plugins can follow along.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn toggle_secrets(mut commands: Commands, map: Single<Entity, With<TiledMap>>) {
+//!     commands.queue(SetLayerVisibility::toggle(*map, "Secrets"));
+//! }
+//! ```
+//!
+//! Layers hidden in Tiled are not spawned at all, so layers meant to be toggled
+//! should be left visible in Tiled and hidden at startup (e.g. on `MapSpawned`).
+
+use bevy::prelude::*;
+
+use crate::components::{LayerId, LayerName, TiledLayerMapOf};
+use crate::events::LayerVisibilityChanged;
+use crate::names::{NameLookupConfig, sorted_by_id};
+
+/// Selects layers within a map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerSelector {
+    /// Layers with this Tiled name (layer names aren't required to be unique).
+    ///
+    /// Duplicates are resolved with the [`NameLookupConfig`] policy.
+    Name(String),
+    /// The layer with this Tiled layer id.
+    Id(u32),
+}
+
+impl LayerSelector {
+    /// Check if a layer matches this selector.
+    pub fn matches(&self, id: &LayerId, name: &LayerName) -> bool {
+        match self {
+            LayerSelector::Name(selected) => *selected == name.0,
+            LayerSelector::Id(selected) => *selected == id.0,
+        }
+    }
+}
+
+impl From<&str> for LayerSelector {
+    fn from(name: &str) -> Self {
+        LayerSelector::Name(name.to_string())
+    }
+}
+
+impl From<String> for LayerSelector {
+    fn from(name: String) -> Self {
+        LayerSelector::Name(name)
+    }
+}
+
+impl From<u32> for LayerSelector {
+    fn from(id: u32) -> Self {
+        LayerSelector::Id(id)
+    }
+}
+
+/// Show, hide or toggle the matching layers of a map.
+#[derive(Debug, Clone)]
+pub struct SetLayerVisibility {
+    /// The map entity owning the layers.
+    pub map: Entity,
+    /// Which layers to change.
+    pub layer: LayerSelector,
+    /// The new visibility, or `None` to toggle each matching layer.
+    pub visible: Option<bool>,
+}
+
+impl SetLayerVisibility {
+    /// Show the matching layers.
+    pub fn show(map: Entity, layer: impl Into<LayerSelector>) -> Self {
+        Self {
+            map,
+            layer: layer.into(),
+            visible: Some(true),
+        }
+    }
+
+    /// Hide the matching layers.
+    pub fn hide(map: Entity, layer: impl Into<LayerSelector>) -> Self {
+        Self {
+            map,
+            layer: layer.into(),
+            visible: Some(false),
+        }
+    }
+
+    /// Toggle the matching layers.
+    pub fn toggle(map: Entity, layer: impl Into<LayerSelector>) -> Self {
+        Self {
+            map,
+            layer: layer.into(),
+            visible: None,
+        }
+    }
+}
+
+impl Command for SetLayerVisibility {
+    fn apply(self, world: &mut World) {
+        let mut query = world.query::<(
+            Entity,
+            &LayerId,
+            &LayerName,
+            &TiledLayerMapOf,
+            &mut Visibility,
+        )>();
+
+        let matches = sorted_by_id(
+            query
+                .iter(world)
+                .filter(|(_, id, name, map_of, _)| {
+                    map_of.0 == self.map && self.layer.matches(id, name)
+                })
+                .map(|(entity, id, ..)| (id.0, entity)),
+        );
+        let matches = match &self.layer {
+            LayerSelector::Name(name) => {
+                let policy = world
+                    .get_resource::<NameLookupConfig>()
+                    .map(|config| config.policy)
+                    .unwrap_or_default();
+                match policy.apply(name, matches) {
+                    Ok(matches) => matches,
+                    Err(error) => {
+                        warn!("Can't change layer visibility: {}", error);
+                        return;
+                    }
+                }
+            }
+            LayerSelector::Id(_) => matches,
+        };
+
+        let mut changed = Vec::new();
+        for entity in matches {
+            let Ok((entity, id, _, _, mut visibility)) = query.get_mut(world, entity) else {
+                continue;
+            };
+
+            let visible = self.visible.unwrap_or(*visibility == Visibility::Hidden);
+            let new_visibility = if visible {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+            if *visibility == new_visibility {
+                continue;
+            }
+            *visibility = new_visibility;
+            changed.push(LayerVisibilityChanged {
+                entity,
+                map_entity: self.map,
+                layer_id: id.0,
+                visible,
+            });
+        }
+
+        for event in changed {
+            world.trigger(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::TiledLayer;
+
+    #[derive(Resource, Default)]
+    struct Seen(Vec<(u32, bool)>);
+
+    #[test]
+    fn test_toggle_layer_by_name_and_id() {
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+        world.add_observer(
+            |trigger: On<LayerVisibilityChanged>, mut seen: ResMut<Seen>| {
+                seen.0
+                    .push((trigger.event().layer_id, trigger.event().visible));
+            },
+        );
+
+        let map = world.spawn_empty().id();
+        let other_map = world.spawn_empty().id();
+        let spawn_layer = |world: &mut World, map: Entity, id: u32, name: &str| {
+            world
+                .spawn((
+                    TiledLayer::Tiles,
+                    LayerId(id),
+                    LayerName(name.to_string()),
+                    TiledLayerMapOf(map),
+                ))
+                .id()
+        };
+        let secrets = spawn_layer(&mut world, map, 3, "Secrets");
+        let ground = spawn_layer(&mut world, map, 4, "Ground");
+        let other_secrets = spawn_layer(&mut world, other_map, 3, "Secrets");
+
+        SetLayerVisibility::toggle(map, "Secrets").apply(&mut world);
+        assert_eq!(world.get::<Visibility>(secrets), Some(&Visibility::Hidden));
+        assert_eq!(
+            world.get::<Visibility>(other_secrets),
+            Some(&Visibility::Inherited)
+        );
+        assert_eq!(
+            world.get::<Visibility>(ground),
+            Some(&Visibility::Inherited)
+        );
+
+        // Hiding an already hidden layer is a no-op
+        SetLayerVisibility::hide(map, 3).apply(&mut world);
+        SetLayerVisibility::show(map, 3).apply(&mut world);
+        assert_eq!(
+            world.get::<Visibility>(secrets),
+            Some(&Visibility::Inherited)
+        );
+        assert_eq!(world.resource::<Seen>().0, vec![(3, false), (3, true)]);
+    }
+}
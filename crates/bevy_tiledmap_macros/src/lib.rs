@@ -60,6 +60,7 @@ fn get_crate_paths() -> (
 /// This macro generates:
 /// - An inventory submission to register the type at compile time
 /// - A deserialization function that converts Tiled properties to the component
+/// - A serialization function (and `ToTiledProperty` impl) for writing it back
 /// - Validation that the type implements `Component + Reflect`
 ///
 /// # Example
@@ -79,8 +80,23 @@ fn get_crate_paths() -> (
 /// # Attributes
 ///
 /// - `#[tiled(name = "...")]` - Set the exported name for Tiled (required)
+/// - `#[tiled(tag = "...")]` - Name of the field holding the variant of complex enums
+///   (default `":variant"`, enum-level, separate attribute)
+/// - `#[tiled(repr = "...")]` - How complex enums select their variant: `"tagged"`
+///   (default), `"untagged"` (inferred from the members present) or `"class"` (one
+///   Tiled class per variant) (enum-level, separate attribute)
+/// - `#[tiled(requires(A, B, ...))]` - Also insert these components at their defaults
+///   when the struct is attached from a map, unless the entity already has them
+///   (struct-level)
+/// - `#[tiled(storage = "int")]` - Store a unit-variant enum as its variant index
+///   instead of its name, matching Tiled's `storageType` (enum-level, separate attribute)
 /// - `#[tiled(default = ...)]` - Default value if property is missing (field-level)
 /// - `#[tiled(skip)]` - Don't deserialize this field (field-level)
+/// - `#[tiled(min = ..., max = ...)]` - Allowed range of a numeric field, exported to
+///   Tiled and checked when maps spawn (field-level, either bound is optional)
+/// - `#[tiled(with = "path::to::fn")]` - Parse this field with a custom function
+///   `fn(&PropertyValue) -> Option<T>` instead of `FromTiledProperty`; exported to
+///   Tiled as a string field, with a literal `default` as its text (field-level)
 #[proc_macro_derive(TiledClass, attributes(tiled))]
 pub fn derive_tiled_class(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -118,9 +134,9 @@ fn derive_tiled_class_impl(input: DeriveInput) -> syn::Result<TokenStream> {
             // Handle struct (including unit structs)
             match &data.fields {
                 Fields::Named(fields) => {
-                    handle_struct(type_name, &tiled_name, &fields.named, &paths)
+                    handle_struct(type_name, &tiled_name, &fields.named, &input.attrs, &paths)
                 }
-                Fields::Unit => handle_unit_struct(type_name, &tiled_name, &paths),
+                Fields::Unit => handle_unit_struct(type_name, &tiled_name, &input.attrs, &paths),
                 Fields::Unnamed(_) => Err(syn::Error::new_spanned(
                     type_name,
                     "TiledClass does not support tuple structs",
@@ -142,16 +158,20 @@ fn handle_struct(
     struct_name: &syn::Ident,
     tiled_name: &str,
     fields: &Punctuated<syn::Field, Comma>,
+    attrs: &[syn::Attribute],
     paths: &CratePaths,
 ) -> syn::Result<TokenStream> {
     let properties = &paths.properties;
     let tiled = &paths.tiled;
+    let insert_requires = generate_insert_requires(&parse_requires_attr(attrs)?);
 
     // Generate field overlay statements and metadata
     // Overlays are statements that modify a mutable default instance
     let mut field_overlays_result = Vec::new();
     let mut field_overlays_option = Vec::new();
     let mut field_metadata = Vec::new();
+    let mut field_serializers = Vec::new();
+    let mut field_checks = Vec::new();
 
     for field in fields {
         let field_name = field.ident.as_ref().unwrap();
@@ -168,6 +188,57 @@ fn handle_struct(
         // Check for #[tiled(default = ...)]
         let _default_value = parse_default_attr(&field.attrs)?;
 
+        // Check for #[tiled(with = "path")] - custom parser, exported as a string
+        // Check for #[tiled(min = ..., max = ...)]
+        let (min, max) = parse_range_attr(&field.attrs);
+
+        if let Some(with_fn) = parse_with_attr(&field.attrs)? {
+            let default_expr = generate_with_default(&_default_value, paths)?;
+
+            field_metadata.push(quote! {
+                #properties::TiledFieldInfo {
+                    name: #field_name_str,
+                    tiled_type: #properties::TiledTypeKind::String,
+                    default_value: #default_expr,
+                    min: #min,
+                    max: #max,
+                }
+            });
+
+            // For Option<T>, the function returns Option<T> for the inner type
+            let overlay = if extract_option_inner_type(field_type).is_some() {
+                quote! {
+                    instance.#field_name = __properties.get(#field_name_str)
+                        .and_then(|v| #with_fn(v));
+                }
+            } else {
+                quote! {
+                    if let ::std::option::Option::Some(v) = __properties.get(#field_name_str) {
+                        if let ::std::option::Option::Some(parsed) = #with_fn(v) {
+                            instance.#field_name = parsed;
+                        }
+                    }
+                }
+            };
+            field_overlays_result.push(overlay.clone());
+            field_overlays_option.push(overlay);
+            field_checks.push(generate_field_check(
+                &field_name_str,
+                quote! { #with_fn(v).is_none() },
+                paths,
+            ));
+            continue;
+        }
+
+        // Serialize back to properties (skipped and `with` fields are left out)
+        field_serializers.push(quote! {
+            if let ::std::option::Option::Some(v) =
+                <#field_type as #properties::ToTiledProperty>::to_property(&self.#field_name)
+            {
+                __properties.insert(#field_name_str.to_string(), v);
+            }
+        });
+
         // Generate field metadata for JSON export
         let tiled_type = map_rust_type_to_tiled(field_type, paths);
         let default_expr = generate_default_value_expr(field_type, &_default_value, paths)?;
@@ -177,6 +248,8 @@ fn handle_struct(
                 name: #field_name_str,
                 tiled_type: #tiled_type,
                 default_value: #default_expr,
+                min: #min,
+                max: #max,
             }
         });
 
@@ -225,6 +298,13 @@ fn handle_struct(
             continue;
         }
 
+        // Report authored values that don't parse (the field keeps its default)
+        field_checks.push(generate_field_check(
+            &field_name_str,
+            quote! { <#actual_type as #properties::FromTiledProperty>::from_property(v).is_none() },
+            paths,
+        ));
+
         // Generate overlay for regular fields
         if let Some(inner_type) = extract_option_inner_type(field_type) {
             // Option<T> fields: overlay the inner value if present
@@ -248,6 +328,8 @@ fn handle_struct(
         }
     }
 
+    let invalid_fields = generate_invalid_fields(&field_checks, paths);
+
     // Generate static field metadata array (uppercase for lint compliance)
     let fields_array_name =
         quote::format_ident!("__TILED_FIELDS_{}", struct_name.to_string().to_uppercase());
@@ -269,10 +351,17 @@ fn handle_struct(
                 name: #tiled_name,
                 fields: #fields_array_name,
                 from_properties: #struct_name::__tiled_from_properties,
+                to_properties: #struct_name::__tiled_to_properties,
+                insert_requires: #struct_name::__tiled_insert_requires,
+                invalid_fields: #struct_name::__tiled_invalid_fields,
             }
         }
 
         impl #struct_name {
+            #insert_requires
+
+            #invalid_fields
+
             #[doc(hidden)]
             fn __tiled_from_properties(
                 __properties: &#tiled::Properties,
@@ -286,6 +375,30 @@ fn handle_struct(
 
                 Ok(::std::boxed::Box::new(instance))
             }
+
+            #[doc(hidden)]
+            fn __tiled_to_properties(
+                __value: &dyn ::bevy::reflect::Reflect,
+            ) -> ::std::option::Option<#tiled::Properties> {
+                __value.downcast_ref::<Self>().map(Self::__tiled_properties)
+            }
+
+            #[doc(hidden)]
+            fn __tiled_properties(&self) -> #tiled::Properties {
+                let mut __properties = #tiled::Properties::new();
+                #(#field_serializers)*
+                __properties
+            }
+        }
+
+        // Implement ToTiledProperty to allow writing nested class fields back
+        impl #properties::ToTiledProperty for #struct_name {
+            fn to_property(&self) -> ::std::option::Option<#tiled::PropertyValue> {
+                ::std::option::Option::Some(#tiled::PropertyValue::ClassValue {
+                    property_type: #tiled_name.to_string(),
+                    properties: self.__tiled_properties(),
+                })
+            }
         }
 
         // Implement FromTiledProperty to allow nested class fields
@@ -314,8 +427,10 @@ fn handle_struct(
 fn handle_unit_struct(
     struct_name: &syn::Ident,
     tiled_name: &str,
+    attrs: &[syn::Attribute],
     paths: &CratePaths,
 ) -> syn::Result<TokenStream> {
+    let insert_requires = generate_insert_requires(&parse_requires_attr(attrs)?);
     // Generate static field metadata array (empty for unit structs)
     let fields_array_name =
         quote::format_ident!("__TILED_FIELDS_{}", struct_name.to_string().to_uppercase());
@@ -336,10 +451,15 @@ fn handle_unit_struct(
                 name: #tiled_name,
                 fields: #fields_array_name,
                 from_properties: #struct_name::__tiled_from_properties,
+                to_properties: #struct_name::__tiled_to_properties,
+                insert_requires: #struct_name::__tiled_insert_requires,
+                invalid_fields: |_| ::std::vec::Vec::new(),
             }
         }
 
         impl #struct_name {
+            #insert_requires
+
             #[doc(hidden)]
             fn __tiled_from_properties(
                 _properties: &#tiled::Properties,
@@ -347,6 +467,23 @@ fn handle_unit_struct(
             ) -> ::std::result::Result<::std::boxed::Box<dyn ::bevy::reflect::Reflect>, ::std::string::String> {
                 Ok(::std::boxed::Box::new(Self))
             }
+
+            #[doc(hidden)]
+            fn __tiled_to_properties(
+                __value: &dyn ::bevy::reflect::Reflect,
+            ) -> ::std::option::Option<#tiled::Properties> {
+                __value.downcast_ref::<Self>().map(|_| #tiled::Properties::new())
+            }
+        }
+
+        // Implement ToTiledProperty to allow writing nested class fields back
+        impl #properties::ToTiledProperty for #struct_name {
+            fn to_property(&self) -> ::std::option::Option<#tiled::PropertyValue> {
+                ::std::option::Option::Some(#tiled::PropertyValue::ClassValue {
+                    property_type: #tiled_name.to_string(),
+                    properties: #tiled::Properties::new(),
+                })
+            }
         }
 
         // Implement FromTiledProperty to allow nested class fields
@@ -378,15 +515,60 @@ fn handle_enum(
     // Check for #[tiled(enum = "struct")] attribute
     let enum_format = parse_enum_format_attr(attrs)?;
 
+    // Check for #[tiled(storage = "int")] attribute
+    let storage = parse_enum_storage_attr(attrs)?;
+
+    // Check for #[tiled(tag = "...")] and #[tiled(repr = "...")] attributes
+    let tag = parse_enum_tag_attr(attrs)?;
+    let repr = parse_enum_repr_attr(attrs)?;
+
+    if !parse_requires_attr(attrs)?.is_empty() {
+        return Err(syn::Error::new_spanned(
+            enum_name,
+            "#[tiled(requires(...))] is only supported on structs",
+        ));
+    }
+
     match (enum_kind, enum_format) {
         (EnumKind::UnitOnly, EnumFormat::Auto) => {
+            if tag.is_some() || repr.is_some() {
+                return Err(syn::Error::new_spanned(
+                    enum_name,
+                    "#[tiled(tag = \"...\")] and #[tiled(repr = \"...\")] are only supported on \
+                     complex enums (add #[tiled(enum = \"struct\")] to export a unit-variant enum as a class)",
+                ));
+            }
             // Generate unit-variant enum implementation
-            generate_unit_enum_impl(enum_name, tiled_name, &data.variants, paths)
+            generate_unit_enum_impl(enum_name, tiled_name, &data.variants, storage, paths)
         }
         (EnumKind::Complex, _) | (_, EnumFormat::Struct) => {
+            if storage == EnumStorage::Int {
+                return Err(syn::Error::new_spanned(
+                    enum_name,
+                    "#[tiled(storage = \"int\")] is only supported on unit-variant enums",
+                ));
+            }
             // Generate complex enum implementation (struct/tuple variants)
             let analysis = analyze_enum_variants_detailed(&data.variants)?;
-            generate_complex_enum_impl(enum_name, tiled_name, &analysis, paths)
+            let repr = match (repr.unwrap_or(EnumRepr::Tagged), tag) {
+                (EnumRepr::Tagged, tag) => {
+                    let tag = tag.unwrap_or_else(|| ":variant".to_string());
+                    check_tag_collisions(&tag, &analysis)?;
+                    ComplexRepr::Tagged(tag)
+                }
+                (_, Some(_)) => {
+                    return Err(syn::Error::new_spanned(
+                        enum_name,
+                        "#[tiled(tag = \"...\")] is only supported on tagged enums",
+                    ));
+                }
+                (EnumRepr::Untagged, None) => {
+                    check_untagged_variants(&analysis)?;
+                    ComplexRepr::Untagged
+                }
+                (EnumRepr::ClassName, None) => ComplexRepr::ClassName,
+            };
+            generate_complex_enum_impl(enum_name, tiled_name, &repr, &analysis, paths)
         }
     }
 }
@@ -403,6 +585,26 @@ enum EnumFormat {
     Struct,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EnumStorage {
+    String,
+    Int,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EnumRepr {
+    Tagged,
+    Untagged,
+    ClassName,
+}
+
+/// How a complex enum's variant is represented, with the tag of tagged enums
+enum ComplexRepr {
+    Tagged(String),
+    Untagged,
+    ClassName,
+}
+
 /// Information about a single variant analyzed from the enum
 #[derive(Clone)]
 struct VariantAnalysis {
@@ -532,11 +734,148 @@ fn parse_enum_format_attr(attrs: &[syn::Attribute]) -> syn::Result<EnumFormat> {
     Ok(EnumFormat::Auto)
 }
 
+/// Parse `#[tiled(storage = "string" | "int")]` attribute
+fn parse_enum_storage_attr(attrs: &[syn::Attribute]) -> syn::Result<EnumStorage> {
+    for attr in attrs {
+        if !attr.path().is_ident("tiled") {
+            continue;
+        }
+
+        if let Meta::List(list) = &attr.meta
+            && let Ok(nested) = syn::parse2::<MetaNameValue>(list.tokens.clone())
+            && nested.path.is_ident("storage")
+        {
+            if let syn::Expr::Lit(expr_lit) = &nested.value
+                && let Lit::Str(lit_str) = &expr_lit.lit
+            {
+                match lit_str.value().as_str() {
+                    "string" => return Ok(EnumStorage::String),
+                    "int" => return Ok(EnumStorage::Int),
+                    _ => {}
+                }
+            }
+            return Err(syn::Error::new_spanned(
+                &nested.value,
+                "Expected #[tiled(storage = \"string\")] or #[tiled(storage = \"int\")]",
+            ));
+        }
+    }
+    Ok(EnumStorage::String)
+}
+
+/// Parse `#[tiled(tag = "...")]` attribute (discriminant field of complex enums)
+fn parse_enum_tag_attr(attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
+    for attr in attrs {
+        if !attr.path().is_ident("tiled") {
+            continue;
+        }
+
+        if let Meta::List(list) = &attr.meta
+            && let Ok(nested) = syn::parse2::<MetaNameValue>(list.tokens.clone())
+            && nested.path.is_ident("tag")
+        {
+            if let syn::Expr::Lit(expr_lit) = &nested.value
+                && let Lit::Str(lit_str) = &expr_lit.lit
+                && !lit_str.value().is_empty()
+            {
+                return Ok(Some(lit_str.value()));
+            }
+            return Err(syn::Error::new_spanned(
+                &nested.value,
+                "Expected a non-empty string literal for tag, e.g. #[tiled(tag = \"kind\")]",
+            ));
+        }
+    }
+    Ok(None)
+}
+
+/// Parse `#[tiled(repr = "tagged" | "untagged" | "class")]` attribute
+fn parse_enum_repr_attr(attrs: &[syn::Attribute]) -> syn::Result<Option<EnumRepr>> {
+    for attr in attrs {
+        if !attr.path().is_ident("tiled") {
+            continue;
+        }
+
+        if let Meta::List(list) = &attr.meta
+            && let Ok(nested) = syn::parse2::<MetaNameValue>(list.tokens.clone())
+            && nested.path.is_ident("repr")
+        {
+            if let syn::Expr::Lit(expr_lit) = &nested.value
+                && let Lit::Str(lit_str) = &expr_lit.lit
+            {
+                match lit_str.value().as_str() {
+                    "tagged" => return Ok(Some(EnumRepr::Tagged)),
+                    "untagged" => return Ok(Some(EnumRepr::Untagged)),
+                    "class" => return Ok(Some(EnumRepr::ClassName)),
+                    _ => {}
+                }
+            }
+            return Err(syn::Error::new_spanned(
+                &nested.value,
+                "Expected #[tiled(repr = \"tagged\")], #[tiled(repr = \"untagged\")] or #[tiled(repr = \"class\")]",
+            ));
+        }
+    }
+    Ok(None)
+}
+
+/// Reject untagged enums with variants that can't be told apart by their fields
+fn check_untagged_variants(analysis: &EnumAnalysis) -> syn::Result<()> {
+    let field_names = |variant: &VariantAnalysis| {
+        let mut names: Vec<String> = match &variant.fields {
+            None => Vec::new(),
+            Some(VariantFields::Named(fields)) => {
+                fields.iter().map(|f| f.ident.to_string()).collect()
+            }
+            Some(VariantFields::Unnamed(fields)) => {
+                fields.iter().map(|f| f.index.to_string()).collect()
+            }
+        };
+        names.sort();
+        names
+    };
+    for (i, variant) in analysis.variants.iter().enumerate() {
+        let names = field_names(variant);
+        if let Some(other) = analysis.variants[..i]
+            .iter()
+            .find(|other| field_names(other) == names)
+        {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!(
+                    "Variants '{}' and '{}' have the same fields, so an untagged enum can't tell them apart",
+                    other.name, variant.name
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reject tags that would be overwritten by a variant field of the same name
+fn check_tag_collisions(tag: &str, analysis: &EnumAnalysis) -> syn::Result<()> {
+    for variant in &analysis.variants {
+        let Some(VariantFields::Named(fields)) = &variant.fields else {
+            continue;
+        };
+        if let Some(field) = fields.iter().find(|f| f.ident == tag) {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                format!(
+                    "Field '{tag}' collides with the enum's tag; rename it or set #[tiled(tag = \"...\")]"
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Generate implementation for unit-variant enum
 fn generate_unit_enum_impl(
     enum_name: &syn::Ident,
     tiled_name: &str,
     variants: &Punctuated<Variant, Comma>,
+    storage: EnumStorage,
     paths: &CratePaths,
 ) -> syn::Result<TokenStream> {
     let properties = &paths.properties;
@@ -546,30 +885,58 @@ fn generate_unit_enum_impl(
     // Extract variant names
     let variant_names: Vec<String> = variants.iter().map(|v| v.ident.to_string()).collect();
 
-    // Generate match arms for string → enum conversion
-    let variant_match_arms: Vec<_> = variants
+    // Generate match arms for FromTiledProperty
+    let from_property_arms: Vec<_> = variants
         .iter()
         .map(|variant| {
             let variant_ident = &variant.ident;
             let variant_name = variant_ident.to_string();
             quote! {
-                #variant_name => Ok(::std::boxed::Box::new(#enum_name::#variant_ident)),
+                #variant_name => ::std::option::Option::Some(Self::#variant_ident),
             }
         })
         .collect();
 
-    // Generate match arms for FromTiledProperty
-    let from_property_arms: Vec<_> = variants
+    // Generate match arms for variant index → enum conversion (int storage)
+    let index_match_arms: Vec<_> = variants
         .iter()
-        .map(|variant| {
+        .enumerate()
+        .map(|(index, variant)| {
+            let variant_ident = &variant.ident;
+            let index = index as i32;
+            quote! {
+                #index => ::std::option::Option::Some(#enum_name::#variant_ident),
+            }
+        })
+        .collect();
+
+    // Generate match arms for ToTiledProperty
+    let to_property_arms: Vec<_> = variants
+        .iter()
+        .enumerate()
+        .map(|(index, variant)| {
             let variant_ident = &variant.ident;
             let variant_name = variant_ident.to_string();
+            let value = match storage {
+                EnumStorage::String => quote! {
+                    #tiled::PropertyValue::StringValue(#variant_name.to_string())
+                },
+                EnumStorage::Int => {
+                    let index = index as i32;
+                    quote! { #tiled::PropertyValue::IntValue(#index) }
+                }
+            };
             quote! {
-                #variant_name => ::std::option::Option::Some(Self::#variant_ident),
+                Self::#variant_ident => #value,
             }
         })
         .collect();
 
+    let storage_tokens = match storage {
+        EnumStorage::String => quote!(#properties::TiledEnumStorage::String),
+        EnumStorage::Int => quote!(#properties::TiledEnumStorage::Int),
+    };
+
     // Generate static variants array name
     let variants_array_name = quote::format_ident!(
         "__TILED_ENUM_VARIANTS_{}",
@@ -583,7 +950,18 @@ fn generate_unit_enum_impl(
             #(#variant_names),*
         ];
 
-        // Implement FromTiledProperty for the enum
+        // Variant by index, for enums stored as int in Tiled
+        impl #enum_name {
+            #[doc(hidden)]
+            fn __tiled_from_index(index: i32) -> ::std::option::Option<Self> {
+                match index {
+                    #(#index_match_arms)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+
+        // Implement FromTiledProperty for the enum (either storage type is accepted)
         impl #properties::FromTiledProperty for #enum_name {
             fn from_property(value: &#tiled::PropertyValue) -> ::std::option::Option<Self> {
                 match value {
@@ -593,11 +971,21 @@ fn generate_unit_enum_impl(
                             _ => ::std::option::Option::None,
                         }
                     }
+                    #tiled::PropertyValue::IntValue(i) => Self::__tiled_from_index(*i),
                     _ => ::std::option::Option::None,
                 }
             }
         }
 
+        // Implement ToTiledProperty for the enum (written as the variant name or index)
+        impl #properties::ToTiledProperty for #enum_name {
+            fn to_property(&self) -> ::std::option::Option<#tiled::PropertyValue> {
+                ::std::option::Option::Some(match self {
+                    #(#to_property_arms)*
+                })
+            }
+        }
+
         // Submit to inventory for compile-time registration
         #inventory::submit! {
             #properties::TiledEnumInfo {
@@ -605,28 +993,34 @@ fn generate_unit_enum_impl(
                 name: #tiled_name,
                 kind: #properties::TiledEnumKind::Simple {
                     variants: #variants_array_name,
+                    storage: #storage_tokens,
                     from_string: |s: &str| -> ::std::result::Result<::std::boxed::Box<dyn ::bevy::reflect::Reflect>, ::std::string::String> {
-                        match s {
-                            #(#variant_match_arms)*
-                            _ => ::std::result::Result::Err(
+                        let variant = <#enum_name as #properties::FromTiledProperty>::from_property(
+                            &#tiled::PropertyValue::StringValue(s.to_string()),
+                        )
+                        .or_else(|| s.parse().ok().and_then(#enum_name::__tiled_from_index));
+                        match variant {
+                            ::std::option::Option::Some(variant) => ::std::result::Result::Ok(::std::boxed::Box::new(variant)),
+                            ::std::option::Option::None => ::std::result::Result::Err(
                                 ::std::format!("Invalid variant '{}' for enum '{}'", s, #tiled_name)
                             ),
                         }
                     },
                 },
                 from_property: |value: &#tiled::PropertyValue| -> ::std::result::Result<::std::boxed::Box<dyn ::bevy::reflect::Reflect>, ::std::string::String> {
-                    match value {
-                        #tiled::PropertyValue::StringValue(s) => {
-                            match s.as_str() {
-                                #(#variant_match_arms)*
-                                _ => ::std::result::Result::Err(
-                                    ::std::format!("Invalid variant '{}' for enum '{}'", s, #tiled_name)
-                                ),
-                            }
-                        }
-                        _ => ::std::result::Result::Err(
-                            ::std::format!("Expected StringValue for simple enum '{}'", #tiled_name)
-                        ),
+                    match <#enum_name as #properties::FromTiledProperty>::from_property(value) {
+                        ::std::option::Option::Some(variant) => ::std::result::Result::Ok(::std::boxed::Box::new(variant)),
+                        ::std::option::Option::None => match value {
+                            #tiled::PropertyValue::StringValue(s) => ::std::result::Result::Err(
+                                ::std::format!("Invalid variant '{}' for enum '{}'", s, #tiled_name)
+                            ),
+                            #tiled::PropertyValue::IntValue(i) => ::std::result::Result::Err(
+                                ::std::format!("Invalid variant index {} for enum '{}'", i, #tiled_name)
+                            ),
+                            _ => ::std::result::Result::Err(
+                                ::std::format!("Expected StringValue or IntValue for simple enum '{}'", #tiled_name)
+                            ),
+                        },
                     }
                 },
             }
@@ -640,6 +1034,7 @@ fn generate_unit_enum_impl(
 fn generate_complex_enum_impl(
     enum_name: &syn::Ident,
     tiled_name: &str,
+    repr: &ComplexRepr,
     analysis: &EnumAnalysis,
     paths: &CratePaths,
 ) -> syn::Result<TokenStream> {
@@ -651,12 +1046,32 @@ fn generate_complex_enum_impl(
     let variant_metadata_arrays =
         generate_variant_metadata_arrays(enum_name, &analysis.variants, paths)?;
 
-    // Generate FromTiledProperty implementation
-    let from_property_impl =
-        generate_complex_from_property_impl(enum_name, tiled_name, &analysis.variants, paths)?;
-
     // Generate TiledVariantInfo array
     let variant_info_array = generate_variant_info_array(enum_name, &analysis.variants, paths)?;
+    let variant_info_name = format_ident!(
+        "__TILED_ENUM_VARIANT_INFO_{}",
+        enum_name.to_string().to_uppercase()
+    );
+
+    // Selection of the variant name of a class value
+    let (select_variant, select_error) =
+        generate_variant_selection(repr, tiled_name, &variant_info_name, paths);
+
+    // Generate FromTiledProperty implementation
+    let from_property_impl = generate_complex_from_property_impl(
+        enum_name,
+        tiled_name,
+        &select_variant,
+        &select_error,
+        &analysis.variants,
+        paths,
+    )?;
+
+    let (repr_tokens, tag) = match repr {
+        ComplexRepr::Tagged(tag) => (quote!(#properties::TiledEnumRepr::Tagged), tag.as_str()),
+        ComplexRepr::Untagged => (quote!(#properties::TiledEnumRepr::Untagged), ""),
+        ComplexRepr::ClassName => (quote!(#properties::TiledEnumRepr::ClassName), ""),
+    };
 
     // Generate inventory submission
     let inventory_submission = quote! {
@@ -665,7 +1080,9 @@ fn generate_complex_enum_impl(
                 type_id: ::std::any::TypeId::of::<#enum_name>(),
                 name: #tiled_name,
                 kind: #properties::TiledEnumKind::Complex {
-                    variant_info: #variant_info_array,
+                    repr: #repr_tokens,
+                    tag: #tag,
+                    variant_info: #variant_info_name,
                 },
                 from_property: |value: &#tiled::PropertyValue| -> ::std::result::Result<::std::boxed::Box<dyn ::bevy::reflect::Reflect>, ::std::string::String> {
                     #from_property_impl
@@ -698,11 +1115,12 @@ fn generate_complex_enum_impl(
                             let field_name = field_ident.to_string();
                             let field_type = &field.ty;
 
-                            quote! {
-                                let #field_ident: #field_type = properties
-                                    .get(#field_name)
-                                    .and_then(|v| <#field_type as #properties::FromTiledProperty>::from_property(v))?;
-                            }
+                            generate_optional_variant_field_extraction(
+                                field_ident,
+                                &field_name,
+                                field_type,
+                                paths,
+                            )
                         })
                         .collect();
 
@@ -727,11 +1145,12 @@ fn generate_complex_enum_impl(
                             let field_type = &field.ty;
                             let field_var = format_ident!("field_{}", index);
 
-                            quote! {
-                                let #field_var: #field_type = properties
-                                    .get(#field_name)
-                                    .and_then(|v| <#field_type as #properties::FromTiledProperty>::from_property(v))?;
-                            }
+                            generate_optional_variant_field_extraction(
+                                &field_var,
+                                &field_name,
+                                field_type,
+                                paths,
+                            )
                         })
                         .collect();
 
@@ -752,21 +1171,107 @@ fn generate_complex_enum_impl(
         })
         .collect();
 
+    // Generate ToTiledProperty match arms (the tag plus each field)
+    let to_tiled_property_match_arms: Vec<_> = analysis
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_ident = &variant.ident;
+            let variant_name = &variant.name;
+
+            let (pattern, bindings): (proc_macro2::TokenStream, Vec<(syn::Ident, String, Type)>) =
+                match &variant.fields {
+                    None => (quote! { Self::#variant_ident }, Vec::new()),
+                    Some(VariantFields::Named(named_fields)) => {
+                        let idents: Vec<_> = named_fields.iter().map(|f| &f.ident).collect();
+                        (
+                            quote! { Self::#variant_ident { #(#idents),* } },
+                            named_fields
+                                .iter()
+                                .map(|f| (f.ident.clone(), f.ident.to_string(), f.ty.clone()))
+                                .collect(),
+                        )
+                    }
+                    Some(VariantFields::Unnamed(unnamed_fields)) => {
+                        let vars: Vec<_> = unnamed_fields
+                            .iter()
+                            .map(|f| format_ident!("field_{}", f.index))
+                            .collect();
+                        (
+                            quote! { Self::#variant_ident(#(#vars),*) },
+                            unnamed_fields
+                                .iter()
+                                .zip(vars.iter())
+                                .map(|(f, var)| (var.clone(), f.index.to_string(), f.ty.clone()))
+                                .collect(),
+                        )
+                    }
+                };
+
+            let field_inserts = bindings.iter().map(|(var, name, ty)| {
+                quote! {
+                    if let ::std::option::Option::Some(v) =
+                        <#ty as #properties::ToTiledProperty>::to_property(#var)
+                    {
+                        properties.insert(#name.to_string(), v);
+                    }
+                }
+            });
+
+            quote! {
+                #pattern => {
+                    #(#field_inserts)*
+                    #variant_name
+                }
+            }
+        })
+        .collect();
+
+    // The tag (tagged enums) and class name of written values
+    let write_variant = match repr {
+        ComplexRepr::Tagged(tag) => quote! {
+            properties.insert(
+                #tag.to_string(),
+                #tiled::PropertyValue::StringValue(variant_name.to_string()),
+            );
+            let property_type = #tiled_name.to_string();
+        },
+        ComplexRepr::Untagged => quote! {
+            let _ = variant_name;
+            let property_type = #tiled_name.to_string();
+        },
+        ComplexRepr::ClassName => quote! {
+            let property_type = ::std::format!("{}::{}", #tiled_name, variant_name);
+        },
+    };
+
     let expanded = quote! {
         #variant_metadata_arrays
 
+        #[doc(hidden)]
+        static #variant_info_name: &[#properties::TiledVariantInfo] = #variant_info_array;
+
+        // Implement ToTiledProperty for the enum (class value with the variant's fields)
+        impl #properties::ToTiledProperty for #enum_name {
+            fn to_property(&self) -> ::std::option::Option<#tiled::PropertyValue> {
+                let mut properties = #tiled::Properties::new();
+                let variant_name: &str = match self {
+                    #(#to_tiled_property_match_arms)*
+                };
+                #write_variant
+                ::std::option::Option::Some(#tiled::PropertyValue::ClassValue {
+                    property_type,
+                    properties,
+                })
+            }
+        }
+
         // Implement FromTiledProperty for the enum
         impl #properties::FromTiledProperty for #enum_name {
             fn from_property(value: &#tiled::PropertyValue) -> ::std::option::Option<Self> {
                 match value {
-                    #tiled::PropertyValue::ClassValue { properties, .. } => {
-                        // Extract :variant discriminant field
-                        let variant_name = properties
-                            .get(":variant")
-                            .and_then(|v| match v {
-                                #tiled::PropertyValue::StringValue(s) => ::std::option::Option::Some(s.as_str()),
-                                _ => ::std::option::Option::None,
-                            })?;
+                    #tiled::PropertyValue::ClassValue { property_type: _class_name, properties } => {
+                        let variant_name: &str = #select_variant?;
 
                         // Match on variant name and construct
                         match variant_name {
@@ -785,6 +1290,62 @@ fn generate_complex_enum_impl(
     Ok(expanded.into())
 }
 
+/// Generate the expression selecting the variant name (`Option<&str>`) of a class
+/// value, and the error message (`String`) when it selects none.
+///
+/// Both expressions have `_class_name: &String` and `properties: &Properties` in scope.
+fn generate_variant_selection(
+    repr: &ComplexRepr,
+    tiled_name: &str,
+    variant_info_name: &syn::Ident,
+    paths: &CratePaths,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let properties = &paths.properties;
+    let tiled = &paths.tiled;
+    match repr {
+        ComplexRepr::Tagged(tag) => (
+            quote! {
+                properties.get(#tag).and_then(|v| match v {
+                    #tiled::PropertyValue::StringValue(s) => ::std::option::Option::Some(s.as_str()),
+                    _ => ::std::option::Option::None,
+                })
+            },
+            quote! {
+                ::std::format!(
+                    "Missing or invalid '{}' field in ClassValue for enum '{}'",
+                    #tag,
+                    #tiled_name
+                )
+            },
+        ),
+        ComplexRepr::Untagged => (
+            quote! {
+                #properties::infer_untagged_variant(#variant_info_name, properties)
+            },
+            quote! {
+                ::std::format!(
+                    "No variant of enum '{}' has all of the members {:?}",
+                    #tiled_name,
+                    properties.keys().collect::<::std::vec::Vec<_>>()
+                )
+            },
+        ),
+        ComplexRepr::ClassName => {
+            let prefix = format!("{tiled_name}::");
+            (
+                quote! { _class_name.strip_prefix(#prefix) },
+                quote! {
+                    ::std::format!(
+                        "Class '{}' is not a variant class of enum '{}'",
+                        _class_name,
+                        #tiled_name
+                    )
+                },
+            )
+        }
+    }
+}
+
 /// Generate static field metadata arrays for all variants
 fn generate_variant_metadata_arrays(
     enum_name: &syn::Ident,
@@ -815,6 +1376,8 @@ fn generate_variant_metadata_arrays(
                                 name: #field_name,
                                 tiled_type: #tiled_type,
                                 default_value: #default_value,
+                                min: ::std::option::Option::None,
+                                max: ::std::option::Option::None,
                             }
                         })
                     })
@@ -831,6 +1394,8 @@ fn generate_variant_metadata_arrays(
                                 name: #field_name,
                                 tiled_type: #tiled_type,
                                 default_value: #default_value,
+                                min: ::std::option::Option::None,
+                                max: ::std::option::Option::None,
                             }
                         })
                     })
@@ -916,10 +1481,11 @@ fn generate_variant_info_array(
 fn generate_complex_from_property_impl(
     enum_name: &syn::Ident,
     tiled_name: &str,
+    select_variant: &proc_macro2::TokenStream,
+    select_error: &proc_macro2::TokenStream,
     variants: &[VariantAnalysis],
     paths: &CratePaths,
 ) -> syn::Result<proc_macro2::TokenStream> {
-    let properties = &paths.properties;
     let tiled = &paths.tiled;
     // Generate match arms for each variant
     let variant_match_arms: Vec<_> = variants
@@ -946,16 +1512,13 @@ fn generate_complex_from_property_impl(
                             let field_name = field_ident.to_string();
                             let field_type = &field.ty;
 
-                            quote! {
-                                let #field_ident: #field_type = properties
-                                    .get(#field_name)
-                                    .and_then(|v| <#field_type as #properties::FromTiledProperty>::from_property(v))
-                                    .ok_or_else(|| ::std::format!(
-                                        "Missing or invalid field '{}' for variant '{}'",
-                                        #field_name,
-                                        #variant_name
-                                    ))?;
-                            }
+                            generate_variant_field_extraction(
+                                field_ident,
+                                &field_name,
+                                field_type,
+                                variant_name,
+                                paths,
+                            )
                         })
                         .collect();
 
@@ -980,16 +1543,13 @@ fn generate_complex_from_property_impl(
                             let field_type = &field.ty;
                             let field_var = format_ident!("field_{}", index);
 
-                            quote! {
-                                let #field_var: #field_type = properties
-                                    .get(#field_name)
-                                    .and_then(|v| <#field_type as #properties::FromTiledProperty>::from_property(v))
-                                    .ok_or_else(|| ::std::format!(
-                                        "Missing or invalid field '{}' for variant '{}'",
-                                        #field_name,
-                                        #variant_name
-                                    ))?;
-                            }
+                            generate_variant_field_extraction(
+                                &field_var,
+                                &field_name,
+                                field_type,
+                                variant_name,
+                                paths,
+                            )
                         })
                         .collect();
 
@@ -1012,17 +1572,8 @@ fn generate_complex_from_property_impl(
 
     Ok(quote! {
         match value {
-            #tiled::PropertyValue::ClassValue { properties, .. } => {
-                // Extract :variant discriminant field
-                let variant_name = properties
-                    .get(":variant")
-                    .and_then(|v| match v {
-                        #tiled::PropertyValue::StringValue(s) => ::std::option::Option::Some(s.as_str()),
-                        _ => ::std::option::Option::None,
-                    })
-                    .ok_or_else(|| ::std::string::String::from(
-                        "Missing or invalid ':variant' field in ClassValue"
-                    ))?;
+            #tiled::PropertyValue::ClassValue { property_type: _class_name, properties } => {
+                let variant_name: &str = #select_variant.ok_or_else(|| #select_error)?;
 
                 // Match on variant name and construct
                 match variant_name {
@@ -1043,6 +1594,75 @@ fn generate_complex_from_property_impl(
     })
 }
 
+/// Generate a `let` statement extracting one complex enum variant field.
+///
+/// Required fields must be present and valid. `Option<T>` fields are `None` when
+/// the property is absent, and `Some` when present - for a nested class, even an
+/// empty one, that means `Some` with the class defaults.
+fn generate_variant_field_extraction(
+    var: &syn::Ident,
+    field_name: &str,
+    field_type: &Type,
+    variant_name: &str,
+    paths: &CratePaths,
+) -> proc_macro2::TokenStream {
+    let properties = &paths.properties;
+    let invalid_error = quote! {
+        ::std::format!(
+            "Missing or invalid field '{}' for variant '{}'",
+            #field_name,
+            #variant_name
+        )
+    };
+
+    if extract_option_inner_type(field_type).is_some() {
+        quote! {
+            let #var: #field_type = match properties.get(#field_name) {
+                ::std::option::Option::Some(v) => {
+                    <#field_type as #properties::FromTiledProperty>::from_property(v)
+                        .ok_or_else(|| #invalid_error)?
+                }
+                ::std::option::Option::None => ::std::option::Option::None,
+            };
+        }
+    } else {
+        quote! {
+            let #var: #field_type = properties
+                .get(#field_name)
+                .and_then(|v| <#field_type as #properties::FromTiledProperty>::from_property(v))
+                .ok_or_else(|| #invalid_error)?;
+        }
+    }
+}
+
+/// Like [`generate_variant_field_extraction`], for the `FromTiledProperty` impl
+/// (which returns `None` instead of an error message).
+fn generate_optional_variant_field_extraction(
+    var: &syn::Ident,
+    field_name: &str,
+    field_type: &Type,
+    paths: &CratePaths,
+) -> proc_macro2::TokenStream {
+    let properties = &paths.properties;
+
+    if extract_option_inner_type(field_type).is_some() {
+        quote! {
+            let #var: #field_type = match properties.get(#field_name) {
+                ::std::option::Option::Some(v) => {
+                    <#field_type as #properties::FromTiledProperty>::from_property(v)?
+                }
+                ::std::option::Option::None => ::std::option::Option::None,
+            };
+        }
+    } else {
+        quote! {
+            let #var: #field_type = properties
+                .get(#field_name)
+                .and_then(|v| <#field_type as #properties::FromTiledProperty>::from_property(v))?;
+        }
+    }
+}
+
 /// Parse #[tiled(name = "...")] attribute from struct
 fn parse_tiled_name_attr(attrs: &[syn::Attribute]) -> syn::Result<String> {
     for attr in attrs {
@@ -1050,15 +1670,13 @@ fn parse_tiled_name_attr(attrs: &[syn::Attribute]) -> syn::Result<String> {
             continue;
         }
 
-        let meta = &attr.meta;
-        if let Meta::List(list) = meta {
-            let nested: MetaNameValue = syn::parse2(list.tokens.clone())?;
-            if nested.path.is_ident("name")
-                && let syn::Expr::Lit(expr_lit) = &nested.value
-                && let Lit::Str(lit_str) = &expr_lit.lit
-            {
-                return Ok(lit_str.value());
-            }
+        if let Meta::List(list) = &attr.meta
+            && let Ok(nested) = syn::parse2::<MetaNameValue>(list.tokens.clone())
+            && nested.path.is_ident("name")
+            && let syn::Expr::Lit(expr_lit) = &nested.value
+            && let Lit::Str(lit_str) = &expr_lit.lit
+        {
+            return Ok(lit_str.value());
         }
     }
 
@@ -1068,6 +1686,88 @@ fn parse_tiled_name_attr(attrs: &[syn::Attribute]) -> syn::Result<String> {
     ))
 }
 
+/// Parse `#[tiled(requires(A, B, ...))]` attributes from a struct
+fn parse_requires_attr(attrs: &[syn::Attribute]) -> syn::Result<Vec<Type>> {
+    let mut requires = Vec::new();
+    for attr in attrs {
+        if !attr.path().is_ident("tiled") {
+            continue;
+        }
+
+        if let Meta::List(list) = &attr.meta
+            && let Ok(Meta::List(nested)) = syn::parse2::<Meta>(list.tokens.clone())
+            && nested.path.is_ident("requires")
+        {
+            requires.extend(nested.parse_args_with(Punctuated::<Type, Comma>::parse_terminated)?);
+        }
+    }
+    Ok(requires)
+}
+
+/// Generate the `__tiled_insert_requires` function of a struct, inserting the
+/// `#[tiled(requires(...))]` components unless the entity already has them
+fn generate_insert_requires(requires: &[Type]) -> proc_macro2::TokenStream {
+    let body = if requires.is_empty() {
+        quote! { let _ = __entity; }
+    } else {
+        quote! {
+            __entity.insert_if_new((#(<#requires as ::std::default::Default>::default(),)*));
+        }
+    };
+    quote! {
+        #[doc(hidden)]
+        fn __tiled_insert_requires(__entity: &mut ::bevy::ecs::world::EntityWorldMut) {
+            #body
+        }
+    }
+}
+
+/// Generate a check adding a field's name to `__invalid` if its authored value
+/// is present but `is_invalid` (e.g. doesn't parse). Unset object references
+/// (`ObjectValue(0)`) are never invalid.
+fn generate_field_check(
+    field_name: &str,
+    is_invalid: proc_macro2::TokenStream,
+    paths: &CratePaths,
+) -> proc_macro2::TokenStream {
+    let tiled = &paths.tiled;
+    quote! {
+        if let ::std::option::Option::Some(v) = __properties.get(#field_name) {
+            if !::std::matches!(v, #tiled::PropertyValue::ObjectValue(0)) && #is_invalid {
+                __invalid.push(#field_name);
+            }
+        }
+    }
+}
+
+/// Generate the `__tiled_invalid_fields` function of a struct from its field checks
+fn generate_invalid_fields(
+    field_checks: &[proc_macro2::TokenStream],
+    paths: &CratePaths,
+) -> proc_macro2::TokenStream {
+    let tiled = &paths.tiled;
+    let body = if field_checks.is_empty() {
+        quote! {
+            let _ = __properties;
+            ::std::vec::Vec::new()
+        }
+    } else {
+        quote! {
+            let mut __invalid = ::std::vec::Vec::new();
+            #(#field_checks)*
+            __invalid
+        }
+    };
+    quote! {
+        #[doc(hidden)]
+        fn __tiled_invalid_fields(
+            __properties: &#tiled::Properties,
+        ) -> ::std::vec::Vec<&'static str> {
+            #body
+        }
+    }
+}
+
 /// Check if field has #[tiled(skip)] attribute
 fn has_skip_attr(attrs: &[syn::Attribute]) -> bool {
     for attr in attrs {
@@ -1092,17 +1792,82 @@ fn parse_default_attr(attrs: &[syn::Attribute]) -> syn::Result<Option<proc_macro
             continue;
         }
 
+        // `default` may be combined with other keys, e.g. `#[tiled(default = 1.0, min = 0.0)]`
         if let Meta::List(list) = &attr.meta
-            && let Ok(nested) = syn::parse2::<MetaNameValue>(list.tokens.clone())
-            && nested.path.is_ident("default")
+            && let Ok(nested) =
+                list.parse_args_with(Punctuated::<MetaNameValue, Comma>::parse_terminated)
+            && let Some(default) = nested.iter().find(|nv| nv.path.is_ident("default"))
         {
-            let value = &nested.value;
+            let value = &default.value;
             return Ok(Some(quote! { #value }));
         }
     }
     Ok(None)
 }
 
+/// Parse `#[tiled(with = "path::to::fn")]` attribute from field
+fn parse_with_attr(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::Path>> {
+    for attr in attrs {
+        if !attr.path().is_ident("tiled") {
+            continue;
+        }
+
+        // `with` may be combined with other keys, e.g. `#[tiled(with = "parse", default = 1)]`
+        if let Meta::List(list) = &attr.meta
+            && let Ok(nested) =
+                list.parse_args_with(Punctuated::<MetaNameValue, Comma>::parse_terminated)
+            && let Some(with) = nested.iter().find(|nv| nv.path.is_ident("with"))
+        {
+            let syn::Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) = &with.value
+            else {
+                return Err(syn::Error::new_spanned(
+                    &with.value,
+                    "Expected string literal for with, e.g. #[tiled(with = \"parse_schedule\")]",
+                ));
+            };
+            return lit_str.parse().map(Some);
+        }
+    }
+    Ok(None)
+}
+
+/// Parse `#[tiled(min = ..., max = ...)]` from a field.
+///
+/// Both bounds may be given in one attribute or separately. Returns the tokens of
+/// each bound as an `Option<f64>` expression.
+fn parse_range_attr(
+    attrs: &[syn::Attribute],
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let mut min = quote!(::std::option::Option::None);
+    let mut max = quote!(::std::option::Option::None);
+    for attr in attrs {
+        if !attr.path().is_ident("tiled") {
+            continue;
+        }
+
+        let Meta::List(list) = &attr.meta else {
+            continue;
+        };
+        let Ok(nested) = list.parse_args_with(Punctuated::<MetaNameValue, Comma>::parse_terminated)
+        else {
+            continue;
+        };
+        for name_value in nested {
+            let value = &name_value.value;
+            let bound = quote!(::std::option::Option::Some((#value) as f64));
+            if name_value.path.is_ident("min") {
+                min = bound;
+            } else if name_value.path.is_ident("max") {
+                max = bound;
+            }
+        }
+    }
+    (min, max)
+}
+
 /// Extract inner type T from Option<T>, returns None if not an Option
 fn extract_option_inner_type(ty: &Type) -> Option<&Type> {
     if let Type::Path(type_path) = ty
@@ -1191,7 +1956,8 @@ fn map_rust_type_to_tiled(ty: &Type, paths: &CratePaths) -> proc_macro2::TokenSt
             "String" | "str" => {
                 return quote! { #properties::TiledTypeKind::String };
             }
-            "Color" => return quote! { #properties::TiledTypeKind::Color },
+            "Color" | "TiledColor" => return quote! { #properties::TiledTypeKind::Color },
+            "TiledObjectRef" => return quote! { #properties::TiledTypeKind::Object },
             _ => {
                 // Not a primitive - it's a referenced type (Vec2, custom types, etc.)
                 let full_path = extract_full_type_path(type_path);
@@ -1204,10 +1970,51 @@ fn map_rust_type_to_tiled(ty: &Type, paths: &CratePaths) -> proc_macro2::TokenSt
         }
     }
 
+    // Arrays and tuples become a class with indexed members ("0", "1", ...)
+    if let Some(indexed_name) = indexed_type_name(actual_type) {
+        return quote! {
+            #properties::TiledTypeKind::Class {
+                property_type: #indexed_name
+            }
+        };
+    }
+
     // Fallback for complex types
     quote! { #properties::TiledTypeKind::String }
 }
 
+/// Get the class name for an array or tuple field type.
+///
+/// Produces `"[f32; 3]"` or `"(f32, i32)"`, matching Bevy's reflected type paths,
+/// so the exporter can synthesize a class with indexed members.
+/// Returns `None` for other types.
+fn indexed_type_name(ty: &Type) -> Option<String> {
+    let element_name = |ty: &Type| match ty {
+        Type::Path(type_path) => Some(extract_type_name(type_path)),
+        _ => None,
+    };
+
+    match ty {
+        Type::Array(array) => {
+            let len = &array.len;
+            Some(format!(
+                "[{}; {}]",
+                element_name(&array.elem)?,
+                quote!(#len)
+            ))
+        }
+        Type::Tuple(tuple) if !tuple.elems.is_empty() => {
+            let elements = tuple
+                .elems
+                .iter()
+                .map(element_name)
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("({})", elements.join(", ")))
+        }
+        _ => None,
+    }
+}
+
 /// Generate `TiledDefaultValue` expression for a field
 fn generate_default_value_expr(
     ty: &Type,
@@ -1248,7 +2055,7 @@ fn generate_default_from_tokens(
             "f32" | "f64" => quote! {
                 #properties::TiledDefaultValue::Float(#tokens as f32)
             },
-            "Color" => {
+            "Color" | "TiledColor" => {
                 // Color defaults need special handling
                 quote! {
                     #properties::TiledDefaultValue::Color { r: 255, g: 255, b: 255, a: 255 }
@@ -1265,6 +2072,47 @@ fn generate_default_from_tokens(
     })
 }
 
+/// Generate `TiledDefaultValue` for a `#[tiled(with = ...)]` field
+///
+/// These fields are exported as strings whatever their Rust type, so a literal
+/// default is exported as its text, e.g. `default = 5` as `"5"`.
+fn generate_with_default(
+    default_attr: &Option<proc_macro2::TokenStream>,
+    paths: &CratePaths,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let properties = &paths.properties;
+    let Some(tokens) = default_attr else {
+        return Ok(quote! { #properties::TiledDefaultValue::String("") });
+    };
+
+    let literal_text = |expr: &syn::Expr| match expr {
+        syn::Expr::Lit(syn::ExprLit { lit, .. }) => match lit {
+            Lit::Str(lit) => Some(lit.value()),
+            Lit::Int(lit) => Some(lit.base10_digits().to_string()),
+            Lit::Float(lit) => Some(lit.base10_digits().to_string()),
+            Lit::Bool(lit) => Some(lit.value.to_string()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let expr: syn::Expr = syn::parse2(tokens.clone())?;
+    let text = match &expr {
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_text(expr).map(|text| format!("-{text}")),
+        expr => literal_text(expr),
+    };
+    let Some(text) = text else {
+        return Err(syn::Error::new_spanned(
+            tokens,
+            "Expected a literal default for a `with` field, which is exported as a string",
+        ));
+    };
+    Ok(quote! { #properties::TiledDefaultValue::String(#text) })
+}
+
 /// Generate default `TiledDefaultValue` based on type alone
 fn generate_type_default(ty: &Type, paths: &CratePaths) -> syn::Result<proc_macro2::TokenStream> {
     let properties = &paths.properties;
@@ -1285,7 +2133,7 @@ fn generate_type_default(ty: &Type, paths: &CratePaths) -> syn::Result<proc_macr
             "f32" | "f64" => quote! {
                 #properties::TiledDefaultValue::Float(0.0)
             },
-            "Color" => quote! {
+            "Color" | "TiledColor" => quote! {
                 #properties::TiledDefaultValue::Color { r: 255, g: 255, b: 255, a: 255 }
             },
             _ => quote! {
@@ -10,7 +10,8 @@
 //! ## Planned Architecture
 //!
 //! When Bevy's native tilemap rendering is ready, this crate will:
-//! - Observe the same events from `bevy_tiledmap_core` as `bevy_tiledmap_tilemap`
+//! - Implement the same `TiledRenderBackend` from `bevy_tiledmap_core` as `bevy_tiledmap_tilemap`
+//!   (see [`NativeTilemapBackend`])
 //! - Provide the same API surface (same features, similar configuration)
 //! - Use Bevy's built-in tilemap components instead of `bevy_ecs_tilemap`
 //! - Allow users to switch between rendering backends with minimal code changes
@@ -41,6 +42,8 @@
 //! high-performance tile rendering using `bevy_ecs_tilemap`.
 
 use bevy::prelude::*;
+use bevy_tiledmap_core::events::TileLayerSpawned;
+use bevy_tiledmap_core::render_backend::{RenderBackendAppExt, TiledRenderBackend};
 
 /// Placeholder plugin for Bevy native tilemap rendering.
 ///
@@ -48,15 +51,30 @@ use bevy::prelude::*;
 pub struct TiledmapNativePlugin;
 
 impl Plugin for TiledmapNativePlugin {
-    fn build(&self, _app: &mut App) {
+    fn build(&self, app: &mut App) {
         warn!(
             "bevy_tiledmap_native is not yet implemented! \
              Use bevy_tiledmap_tilemap for production rendering."
         );
+        app.add_render_backend(NativeTilemapBackend);
+    }
+}
+
+/// Placeholder render backend for Bevy native tilemaps.
+///
+/// **Not yet implemented:** tile layers are left undrawn.
+pub struct NativeTilemapBackend;
+
+impl TiledRenderBackend for NativeTilemapBackend {
+    fn tile_layer_spawned(&self, _world: &mut World, event: &TileLayerSpawned) {
+        debug!(
+            "bevy_tiledmap_native can't render tile layer {:?} yet",
+            event.entity
+        );
     }
 }
 
 /// Prelude module (placeholder)
 pub mod prelude {
-    pub use crate::TiledmapNativePlugin;
+    pub use crate::{NativeTilemapBackend, TiledmapNativePlugin};
 }
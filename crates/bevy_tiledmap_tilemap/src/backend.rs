@@ -0,0 +1,41 @@
+//! The `bevy_ecs_tilemap` render backend.
+
+use bevy::prelude::*;
+use bevy_tiledmap_core::events::{ImageLayerSpawned, TileLayerSpawned, TilesModified};
+use bevy_tiledmap_core::render_backend::TiledRenderBackend;
+
+use crate::images;
+use crate::tiles;
+
+/// Renders tile layers with `bevy_ecs_tilemap` and image layers as sprites.
+///
+/// Registered by [`TilemapPlugin`](crate::TilemapPlugin). Tilemaps and sprites
+/// are spawned as children of the layer entity, so despawning the layer cleans
+/// them up without any extra work.
+pub struct EcsTilemapBackend;
+
+impl TiledRenderBackend for EcsTilemapBackend {
+    fn tile_layer_spawned(&self, world: &mut World, event: &TileLayerSpawned) {
+        if let Err(error) =
+            world.run_system_cached_with(tiles::render::on_tile_layer_spawned, event.clone())
+        {
+            warn!("Failed to render tile layer {:?}: {error}", event.entity);
+        }
+    }
+
+    fn image_layer_spawned(&self, world: &mut World, event: &ImageLayerSpawned) {
+        if let Err(error) =
+            world.run_system_cached_with(images::on_image_layer_spawned, event.clone())
+        {
+            warn!("Failed to render image layer {:?}: {error}", event.entity);
+        }
+    }
+
+    fn tiles_changed(&self, world: &mut World, event: &TilesModified) {
+        if let Err(error) =
+            world.run_system_cached_with(tiles::render::on_tiles_modified, event.clone())
+        {
+            warn!("Failed to re-render tile layer {:?}: {error}", event.entity);
+        }
+    }
+}
@@ -6,21 +6,140 @@ use bevy::prelude::*;
 #[derive(Resource, Clone, Debug)]
 pub struct TilemapRenderConfig {
     /// Enable tile animations (default: true with "animations" feature)
+    ///
+    /// Can be changed at runtime: animated tiles freeze on their current frame
+    /// while disabled.
     pub enable_animations: bool,
 
+    /// Most animated tiles updated per frame (default: `None`, all of them)
+    ///
+    /// With a limit, consecutive frames update the next tiles in turn, so every
+    /// tile still advances, just less often.
+    pub max_animated_tiles_per_frame: Option<usize>,
+
     /// Enable parallax scrolling (default: true with "parallax" feature)
     pub enable_parallax: bool,
 
+    /// Move parallax layers every this many frames (default: 1, every frame)
+    pub parallax_frame_interval: u32,
+
     /// Enable debug shape rendering with gizmos (default: false)
     pub enable_debug_shapes: bool,
+
+    /// Enable filled meshes for polygon objects with a `fill_color` property
+    /// (default: true with "`polygon_fill`" feature)
+    pub enable_polygon_fill: bool,
+
+    /// Bake every tile layer into one mesh per tileset instead of a tilemap
+    /// with an entity per tile (default: false, needs the "`static_bake`"
+    /// feature). Layers can override this with a `bake_static` bool property.
+    pub bake_static_layers: bool,
+
+    /// Render tile layers as textured meshes on a plane of the 3D world, for
+    /// 3D cameras (default: `None`, needs the "`plane_3d`" feature). See
+    /// [`MapPlane`].
+    pub plane_3d: Option<MapPlane>,
+
+    /// Hide small tile objects when the camera is zoomed out (default: `None`).
+    /// See [`ObjectLod`].
+    pub object_lod: Option<ObjectLod>,
 }
 
 impl Default for TilemapRenderConfig {
     fn default() -> Self {
         Self {
             enable_animations: cfg!(feature = "animations"),
+            max_animated_tiles_per_frame: None,
             enable_parallax: cfg!(feature = "parallax"),
+            parallax_frame_interval: 1,
             enable_debug_shapes: cfg!(feature = "debug_shapes"),
+            enable_polygon_fill: cfg!(feature = "polygon_fill"),
+            bake_static_layers: false,
+            plane_3d: None,
+            object_lod: None,
+        }
+    }
+}
+
+impl TilemapRenderConfig {
+    /// Settings for mobile and other low-end devices: at most 256 animated
+    /// tiles updated per frame, parallax moved every other frame, and tile
+    /// objects up to 16 pixels hidden below half zoom.
+    pub fn low_end() -> Self {
+        Self {
+            max_animated_tiles_per_frame: Some(256),
+            parallax_frame_interval: 2,
+            object_lod: Some(ObjectLod::default()),
+            ..default()
+        }
+    }
+}
+
+/// Level of detail for tile object sprites.
+///
+/// While the camera's zoom is below [`min_zoom`](Self::min_zoom), tile objects
+/// no larger than [`max_object_size`](Self::max_object_size) are hidden, e.g.
+/// grass tufts and pebbles that are barely visible from afar. Zoom is the
+/// inverse of the orthographic projection's scale of the first active camera.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObjectLod {
+    /// Zoom below which small objects are hidden (default: 0.5)
+    pub min_zoom: f32,
+
+    /// Largest width or height, in map pixels, of objects that get hidden
+    /// (default: 16.0)
+    pub max_object_size: f32,
+}
+
+impl Default for ObjectLod {
+    fn default() -> Self {
+        Self {
+            min_zoom: 0.5,
+            max_object_size: 16.0,
+        }
+    }
+}
+
+/// Plane of the 3D world that maps are laid out on.
+///
+/// The map's layers are rotated from the XY plane onto the plane and scaled
+/// from pixels to world units. Layers keep their order along the plane's
+/// normal, one [`LayerZConfig::multiplier`](bevy_tiledmap_core::plugin::LayerZConfig)
+/// pixel apart by default.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MapPlane {
+    /// Rotation from the map's XY plane onto the target plane
+    /// (default: the ground plane, see [`MapPlane::xz`])
+    pub rotation: Quat,
+
+    /// World units per map pixel (default: 1/16)
+    pub scale: f32,
+}
+
+impl Default for MapPlane {
+    fn default() -> Self {
+        Self::xz()
+    }
+}
+
+impl MapPlane {
+    /// The XZ ground plane, facing up: the top of the map points to -Z and
+    /// later layers are stacked along +Y.
+    pub fn xz() -> Self {
+        Self {
+            rotation: Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2),
+            scale: 1.0 / 16.0,
         }
     }
+
+    /// Builder method: Set the world units per map pixel.
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Transform from map space to the plane.
+    pub fn transform(&self) -> Transform {
+        Transform::from_rotation(self.rotation).with_scale(Vec3::splat(self.scale))
+    }
 }
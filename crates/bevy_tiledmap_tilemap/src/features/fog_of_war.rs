@@ -0,0 +1,435 @@
+//! Fog of war over maps.
+//!
+//! [`FogOfWar`] holds a visibility grid per map, one cell per tile, that game
+//! code updates as units move. Each enabled map gets an overlay covering its
+//! area, drawn with a [`FogOfWarMaterial`]: hidden tiles are covered, explored
+//! tiles are darkened, and visible tiles are clear, with soft edges between
+//! them.
+//!
+//! A common pattern is to cover the map each frame (visible tiles become
+//! explored) and reveal the area around each unit:
+//!
+//! ```rust,ignore
+//! fn enable_fog(mut fog: ResMut<FogOfWar>, map: Single<Entity, Added<TiledMap>>) {
+//!     fog.enable(*map);
+//! }
+//!
+//! fn reveal_around_units(
+//!     mut fog: ResMut<FogOfWar>,
+//!     map: Single<(Entity, &GlobalTransform), With<TiledMap>>,
+//!     units: Query<&GlobalTransform, With<Unit>>,
+//! ) {
+//!     let (map, map_transform) = *map;
+//!     let Some(grid) = fog.grid_mut(map) else {
+//!         return;
+//!     };
+//!     grid.cover();
+//!     let to_map = map_transform.affine().inverse();
+//!     for unit in &units {
+//!         let position = to_map.transform_point3(unit.translation()).truncate();
+//!         grid.reveal_circle(position, 96.0);
+//!     }
+//! }
+//! ```
+//!
+//! Positions are in the map's local space, in pixels with Y up (the space of
+//! [`MapGeometry::bounds`]); tile coordinates follow Tiled, with `(0, 0)` at
+//! the top-left. [`FogGrid::save_explored`] and [`FogGrid::load_explored`]
+//! store which tiles were explored, e.g. in save files. The overlay is drawn
+//! above the map's layers at [`FogOfWarSettings::z`]; it covers the map's
+//! rectangle, so it suits orthogonal maps.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, Extent3d, TextureDimension, TextureFormat};
+use bevy::shader::ShaderRef;
+use bevy::sprite_render::{AlphaMode2d, Material2d};
+use bevy_tiledmap_core::components::MapGeometry;
+
+/// Visibility of one tile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum FogState {
+    /// Never seen
+    #[default]
+    Hidden,
+    /// Seen before, but not currently visible
+    Explored,
+    /// Currently visible
+    Visible,
+}
+
+/// Visibility grid of one map, one cell per tile.
+///
+/// See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct FogGrid {
+    size: UVec2,
+    tile_size: Vec2,
+    bounds: Rect,
+    cells: Vec<FogState>,
+    changed: bool,
+}
+
+impl FogGrid {
+    /// Create a fully hidden grid covering a map.
+    pub fn new(geometry: &MapGeometry) -> Self {
+        Self {
+            size: geometry.size,
+            tile_size: geometry.tile_size,
+            bounds: geometry.bounds,
+            cells: vec![FogState::Hidden; (geometry.size.x * geometry.size.y) as usize],
+            changed: true,
+        }
+    }
+
+    /// Grid size in tiles.
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    /// Get the state of a tile (`None` outside the grid).
+    pub fn get(&self, tile: UVec2) -> Option<FogState> {
+        self.index(tile).map(|index| self.cells[index])
+    }
+
+    /// Set the state of a tile (ignored outside the grid).
+    pub fn set(&mut self, tile: UVec2, state: FogState) {
+        if let Some(index) = self.index(tile) {
+            if self.cells[index] != state {
+                self.cells[index] = state;
+                self.changed = true;
+            }
+        }
+    }
+
+    /// Whether a tile is visible or was explored before.
+    pub fn is_explored(&self, tile: UVec2) -> bool {
+        self.get(tile)
+            .is_some_and(|state| state != FogState::Hidden)
+    }
+
+    /// Get the tile at a map-local position.
+    pub fn tile_at(&self, position: Vec2) -> Option<UVec2> {
+        let offset = Vec2::new(
+            position.x - self.bounds.min.x,
+            self.bounds.max.y - position.y,
+        ) / self.tile_size;
+        if offset.x < 0.0 || offset.y < 0.0 {
+            return None;
+        }
+        let tile = offset.as_uvec2();
+        (tile.x < self.size.x && tile.y < self.size.y).then_some(tile)
+    }
+
+    /// Map-local center of a tile.
+    pub fn tile_center(&self, tile: UVec2) -> Vec2 {
+        Vec2::new(
+            self.bounds.min.x + (tile.x as f32 + 0.5) * self.tile_size.x,
+            self.bounds.max.y - (tile.y as f32 + 0.5) * self.tile_size.y,
+        )
+    }
+
+    /// Make the tiles whose centers are within `radius` pixels of a map-local
+    /// position visible.
+    pub fn reveal_circle(&mut self, center: Vec2, radius: f32) {
+        let area = Rect::from_center_half_size(center, Vec2::splat(radius));
+        for tile in self.tiles_in(area) {
+            if self.tile_center(tile).distance_squared(center) <= radius * radius {
+                self.set(tile, FogState::Visible);
+            }
+        }
+    }
+
+    /// Make the tiles whose centers are inside a map-local rect visible.
+    pub fn reveal_rect(&mut self, rect: Rect) {
+        for tile in self.tiles_in(rect) {
+            if rect.contains(self.tile_center(tile)) {
+                self.set(tile, FogState::Visible);
+            }
+        }
+    }
+
+    /// Make every tile visible.
+    pub fn reveal_all(&mut self) {
+        self.fill(|_| FogState::Visible);
+    }
+
+    /// Turn visible tiles into explored ones, e.g. before revealing the
+    /// current view each frame.
+    pub fn cover(&mut self) {
+        self.fill(|state| match state {
+            FogState::Visible => FogState::Explored,
+            state => state,
+        });
+    }
+
+    /// Hide every tile, forgetting what was explored.
+    pub fn reset(&mut self) {
+        self.fill(|_| FogState::Hidden);
+    }
+
+    /// Which tiles were explored, as a bitset: one bit per tile, row by row
+    /// from the top-left, least significant bit first.
+    pub fn save_explored(&self) -> Vec<u8> {
+        let mut bytes = vec![0; self.cells.len().div_ceil(8)];
+        for (index, state) in self.cells.iter().enumerate() {
+            if *state != FogState::Hidden {
+                bytes[index / 8] |= 1 << (index % 8);
+            }
+        }
+        bytes
+    }
+
+    /// Restore explored tiles saved with [`save_explored`](Self::save_explored).
+    ///
+    /// Explored tiles become [`FogState::Explored`], all others
+    /// [`FogState::Hidden`]. Missing bytes count as unexplored.
+    pub fn load_explored(&mut self, bytes: &[u8]) {
+        for (index, cell) in self.cells.iter_mut().enumerate() {
+            let explored = bytes
+                .get(index / 8)
+                .is_some_and(|byte| byte & (1 << (index % 8)) != 0);
+            *cell = if explored {
+                FogState::Explored
+            } else {
+                FogState::Hidden
+            };
+        }
+        self.changed = true;
+    }
+
+    fn index(&self, tile: UVec2) -> Option<usize> {
+        (tile.x < self.size.x && tile.y < self.size.y)
+            .then(|| (tile.y * self.size.x + tile.x) as usize)
+    }
+
+    /// Tiles overlapping a map-local rect.
+    fn tiles_in(&self, rect: Rect) -> impl Iterator<Item = UVec2> {
+        let min = Vec2::new(
+            rect.min.x - self.bounds.min.x,
+            self.bounds.max.y - rect.max.y,
+        ) / self.tile_size;
+        let max = Vec2::new(
+            rect.max.x - self.bounds.min.x,
+            self.bounds.max.y - rect.min.y,
+        ) / self.tile_size;
+        let min = min.floor().max(Vec2::ZERO).as_uvec2();
+        let max = max.ceil().max(Vec2::ZERO).as_uvec2().min(self.size);
+        (min.y..max.y).flat_map(move |y| (min.x..max.x).map(move |x| UVec2::new(x, y)))
+    }
+
+    fn fill(&mut self, state: impl Fn(FogState) -> FogState) {
+        for cell in &mut self.cells {
+            let new_state = state(*cell);
+            if *cell != new_state {
+                *cell = new_state;
+                self.changed = true;
+            }
+        }
+    }
+}
+
+/// A map's grid and the texture it is drawn from.
+#[derive(Debug)]
+struct FogMap {
+    grid: FogGrid,
+    texture: Handle<Image>,
+}
+
+/// Fog of war grids of all maps that have fog enabled.
+///
+/// See the [module docs](self).
+#[derive(Resource, Debug, Default)]
+pub struct FogOfWar {
+    maps: HashMap<Entity, FogMap>,
+    /// Enabled maps waiting to be spawned, with explored tiles to restore
+    pending: Vec<(Entity, Option<Vec<u8>>)>,
+}
+
+impl FogOfWar {
+    /// Cover a map with fog.
+    ///
+    /// Its grid is created, fully hidden, once the map is spawned.
+    pub fn enable(&mut self, map: Entity) {
+        self.pending.push((map, None));
+    }
+
+    /// Cover a map with fog, restoring explored tiles saved with
+    /// [`FogGrid::save_explored`].
+    pub fn enable_with_explored(&mut self, map: Entity, explored: Vec<u8>) {
+        self.pending.push((map, Some(explored)));
+    }
+
+    /// Remove the fog of a map.
+    pub fn disable(&mut self, map: Entity) {
+        self.maps.remove(&map);
+        self.pending.retain(|(pending, _)| *pending != map);
+    }
+
+    /// Get the grid of a map (`None` if fog isn't enabled, or the map isn't
+    /// spawned yet).
+    pub fn grid(&self, map: Entity) -> Option<&FogGrid> {
+        self.maps.get(&map).map(|fog| &fog.grid)
+    }
+
+    /// Get the grid of a map mutably.
+    pub fn grid_mut(&mut self, map: Entity) -> Option<&mut FogGrid> {
+        self.maps.get_mut(&map).map(|fog| &mut fog.grid)
+    }
+}
+
+/// Look of the fog overlays.
+#[derive(Resource, Debug, Clone)]
+pub struct FogOfWarSettings {
+    /// Color of hidden tiles (default: black)
+    pub color: Color,
+
+    /// Opacity of the fog over explored tiles (default: 0.6)
+    pub explored_opacity: f32,
+
+    /// Z of the overlay relative to its map (default: 1000.0)
+    pub z: f32,
+}
+
+impl Default for FogOfWarSettings {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            explored_opacity: 0.6,
+            z: 1000.0,
+        }
+    }
+}
+
+/// Material drawing a fog overlay from a fog texture.
+///
+/// The texture has one texel per tile holding the fog's opacity, and is
+/// sampled with linear filtering for soft edges.
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
+pub struct FogOfWarMaterial {
+    /// Fog color
+    #[uniform(0)]
+    pub color: LinearRgba,
+    /// Fog opacity per tile
+    #[texture(1)]
+    #[sampler(2)]
+    pub fog: Handle<Image>,
+}
+
+impl Material2d for FogOfWarMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_tiledmap_tilemap/features/fog_of_war.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+/// Overlay entity drawing the fog of a map (a child of the map).
+#[derive(Component, Debug)]
+pub struct FogOverlay {
+    /// The map covered
+    pub map: Entity,
+}
+
+/// Fog opacity of each tile, as texture data.
+fn fog_texels(grid: &FogGrid, settings: &FogOfWarSettings) -> Vec<u8> {
+    let explored = (settings.explored_opacity.clamp(0.0, 1.0) * 255.0) as u8;
+    grid.cells
+        .iter()
+        .map(|state| match state {
+            FogState::Hidden => 255,
+            FogState::Explored => explored,
+            FogState::Visible => 0,
+        })
+        .collect()
+}
+
+fn fog_texture(grid: &FogGrid, settings: &FogOfWarSettings) -> Image {
+    let mut image = Image::new(
+        Extent3d {
+            width: grid.size.x.max(1),
+            height: grid.size.y.max(1),
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        fog_texels(grid, settings),
+        TextureFormat::R8Unorm,
+        RenderAssetUsages::default(),
+    );
+    image.sampler = ImageSampler::linear();
+    image
+}
+
+/// System creating fog overlays for enabled maps and keeping their textures in
+/// sync with the grids.
+pub fn sync_fog_of_war(
+    mut commands: Commands,
+    mut fog: ResMut<FogOfWar>,
+    settings: Res<FogOfWarSettings>,
+    maps: Query<Option<&MapGeometry>>,
+    overlays: Query<(Entity, &FogOverlay, &MeshMaterial2d<FogOfWarMaterial>)>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<FogOfWarMaterial>>,
+) {
+    let fog = &mut *fog;
+
+    // Create the grids of enabled maps once they are spawned
+    for (map, explored) in std::mem::take(&mut fog.pending) {
+        let geometry = match maps.get(map) {
+            Ok(Some(geometry)) => geometry,
+            // Not spawned yet
+            Ok(None) => {
+                fog.pending.push((map, explored));
+                continue;
+            }
+            // Despawned
+            Err(_) => continue,
+        };
+        let mut grid = FogGrid::new(geometry);
+        if let Some(explored) = explored {
+            grid.load_explored(&explored);
+        }
+        grid.changed = false;
+        let texture = images.add(fog_texture(&grid, &settings));
+        let material = materials.add(FogOfWarMaterial {
+            color: settings.color.into(),
+            fog: texture.clone(),
+        });
+        commands.spawn((
+            FogOverlay { map },
+            Mesh2d(meshes.add(Rectangle::from_size(geometry.bounds.size()))),
+            MeshMaterial2d(material),
+            Transform::from_translation(geometry.bounds.center().extend(settings.z)),
+            ChildOf(map),
+        ));
+        fog.maps.insert(map, FogMap { grid, texture });
+    }
+
+    // Forget despawned maps, and remove the overlays of disabled ones
+    fog.maps.retain(|map, _| maps.contains(*map));
+    for (overlay, fog_overlay, material) in &overlays {
+        if !fog.maps.contains_key(&fog_overlay.map) {
+            commands.entity(overlay).despawn();
+        } else if settings.is_changed() {
+            if let Some(material) = materials.get_mut(&material.0) {
+                material.color = settings.color.into();
+            }
+        }
+    }
+
+    // Upload changed grids
+    for fog_map in fog.maps.values_mut() {
+        if !fog_map.grid.changed && !settings.is_changed() {
+            continue;
+        }
+        fog_map.grid.changed = false;
+        if let Some(image) = images.get_mut(&fog_map.texture) {
+            image.data = Some(fog_texels(&fog_map.grid, &settings));
+        }
+    }
+}
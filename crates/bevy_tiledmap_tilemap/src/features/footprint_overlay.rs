@@ -0,0 +1,130 @@
+//! On-screen panel with the footprint of every spawned map.
+//!
+//! Insert [`FootprintOverlay`] to show a UI panel listing, for each map, what
+//! [`map_footprint`] reports: layers, entities by kind, colliders, tile data
+//! size and texture memory. Remove the resource to hide it again.
+//!
+//! ```rust,ignore
+//! fn toggle_footprints(
+//!     mut commands: Commands,
+//!     keys: Res<ButtonInput<KeyCode>>,
+//!     overlay: Option<Res<FootprintOverlay>>,
+//! ) {
+//!     if keys.just_pressed(KeyCode::F3) {
+//!         match overlay {
+//!             Some(_) => commands.remove_resource::<FootprintOverlay>(),
+//!             None => commands.insert_resource(FootprintOverlay::default()),
+//!         }
+//!     }
+//! }
+//! ```
+
+use bevy::prelude::*;
+use bevy_tiledmap_core::components::TiledMap;
+use bevy_tiledmap_core::footprint::map_footprint;
+
+/// Shows the footprint panel while present.
+///
+/// See the [module docs](self).
+#[derive(Resource, Debug, Clone)]
+pub struct FootprintOverlay {
+    /// How often the panel is refreshed (default: every 0.5 seconds)
+    pub refresh: Timer,
+
+    /// Font size of the panel text (default: 14.0)
+    pub font_size: f32,
+}
+
+impl Default for FootprintOverlay {
+    fn default() -> Self {
+        Self {
+            refresh: Timer::from_seconds(0.5, TimerMode::Repeating),
+            font_size: 14.0,
+        }
+    }
+}
+
+/// Marker for the footprint panel entity.
+#[derive(Component, Debug)]
+pub struct FootprintOverlayPanel;
+
+/// Text of the footprint panel: one section per spawned map.
+pub fn footprint_report(world: &mut World) -> String {
+    let mut maps: Vec<(Entity, String)> = world
+        .query::<(Entity, &TiledMap)>()
+        .iter(world)
+        .map(|(entity, map)| {
+            let name = map
+                .handle
+                .path()
+                .map_or_else(|| format!("{entity}"), ToString::to_string);
+            (entity, name)
+        })
+        .collect();
+    maps.sort();
+
+    let sections: Vec<String> = maps
+        .into_iter()
+        .filter_map(|(entity, name)| {
+            let footprint = map_footprint(world, entity)?;
+            Some(format!("{name}\n{footprint}"))
+        })
+        .collect();
+    if sections.is_empty() {
+        "No maps spawned".to_string()
+    } else {
+        sections.join("\n\n")
+    }
+}
+
+/// System spawning and refreshing the footprint panel.
+pub fn update_footprint_overlay(world: &mut World) {
+    let delta = world.resource::<Time>().delta();
+    let panel = world
+        .query_filtered::<Entity, With<FootprintOverlayPanel>>()
+        .iter(world)
+        .next();
+    let mut overlay = world.resource_mut::<FootprintOverlay>();
+    overlay.refresh.tick(delta);
+    if panel.is_some() && !overlay.refresh.just_finished() {
+        return;
+    }
+    let font_size = overlay.font_size;
+
+    let report = footprint_report(world);
+    match panel {
+        Some(panel) => {
+            if let Some(mut text) = world.get_mut::<Text>(panel) {
+                text.0 = report;
+            }
+        }
+        None => {
+            world.spawn((
+                FootprintOverlayPanel,
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(8.0),
+                    left: Val::Px(8.0),
+                    padding: UiRect::all(Val::Px(6.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                Text(report),
+                TextFont {
+                    font_size,
+                    ..default()
+                },
+            ));
+        }
+    }
+}
+
+/// System despawning the footprint panel once [`FootprintOverlay`] is removed.
+pub fn remove_footprint_overlay(
+    mut commands: Commands,
+    panels: Query<Entity, With<FootprintOverlayPanel>>,
+) {
+    for panel in &panels {
+        commands.entity(panel).despawn();
+    }
+}
@@ -1,9 +1,29 @@
 //! Optional rendering features for `bevy_tiledmap_tilemap`.
 
 pub mod animation_state;
+#[cfg(feature = "fog_of_war")]
+pub mod fog_of_war;
+#[cfg(feature = "footprint_overlay")]
+pub mod footprint_overlay;
+#[cfg(feature = "palette")]
+pub mod palette;
 pub mod parallax;
+#[cfg(feature = "picking")]
+pub mod picking;
+#[cfg(feature = "thumbnail")]
+pub mod thumbnail;
 pub mod z_ordering;
 
 pub use animation_state::{AnimationSpeed, AnimationsPaused};
-pub use parallax::{ParallaxCamera, ParallaxLayer};
-pub use z_ordering::ZOrderConfig;
+#[cfg(feature = "fog_of_war")]
+pub use fog_of_war::{FogGrid, FogOfWar, FogOfWarMaterial, FogOfWarSettings, FogState};
+#[cfg(feature = "footprint_overlay")]
+pub use footprint_overlay::{footprint_report, FootprintOverlay};
+#[cfg(feature = "palette")]
+pub use palette::{TiledPalette, TiledPaletteMaterial};
+pub use parallax::{ParallaxCamera, ParallaxLayer, PARALLAX_X_PROPERTY, PARALLAX_Y_PROPERTY};
+#[cfg(feature = "picking")]
+pub use picking::{TilePickingSettings, TiledPickHit, TiledPicker};
+#[cfg(feature = "thumbnail")]
+pub use thumbnail::{render_map_thumbnail, MapThumbnailSettings, MapThumbnails};
+pub use z_ordering::{ObjectSortMode, ObjectSortZ, ZOrderConfig};
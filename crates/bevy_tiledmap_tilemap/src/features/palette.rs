@@ -0,0 +1,153 @@
+//! Palette swaps and hue shifts for tile layers.
+//!
+//! Add [`TiledPalette`] to a map or tile layer entity to render its tile layers
+//! with a [`TiledPaletteMaterial`] instead of the standard tilemap material. The
+//! material can replace colors through a lookup texture and/or rotate hues, so a
+//! variant of the same map (e.g. a corrupted world) needs no extra tileset art.
+//!
+//! The palette closest to a tilemap wins: one on a layer overrides the map's.
+//! Change the material asset to update the look at runtime, or remove the
+//! component to go back to the original colors. Tiles from image collection
+//! tilesets are drawn as sprites and are not affected.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn corrupt_world(
+//!     mut commands: Commands,
+//!     mut materials: ResMut<Assets<TiledPaletteMaterial>>,
+//!     asset_server: Res<AssetServer>,
+//!     map: Single<Entity, With<TiledMap>>,
+//! ) {
+//!     let material = TiledPaletteMaterial::swap(asset_server.load("palettes/corrupted.png"));
+//!     commands.entity(*map).insert(TiledPalette(materials.add(material)));
+//! }
+//! ```
+
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_resource::{AsBindGroup, AsBindGroupShaderType, ShaderType};
+use bevy::render::texture::GpuImage;
+use bevy::shader::ShaderRef;
+use bevy_ecs_tilemap::prelude::{MaterialTilemap, MaterialTilemapHandle, StandardTilemapMaterial};
+
+use crate::tiles::TilesetReference;
+
+/// Tilemap material that recolors tiles.
+///
+/// The palette lookup texture is two pixels high: each column maps the color
+/// in its top row to the color in its bottom row. Tile pixels matching no
+/// column keep their color. The hue shift is applied after the palette swap.
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
+#[uniform(0, TiledPaletteUniform)]
+pub struct TiledPaletteMaterial {
+    /// Hue rotation in degrees (0 leaves hues alone).
+    pub hue_shift: f32,
+    /// Largest color distance (RGB, 0.0 to 1.0 per channel) still matching a
+    /// palette color.
+    ///
+    /// Default: `0.01`
+    pub tolerance: f32,
+    /// Palette lookup texture (`None` disables the palette swap).
+    #[texture(1)]
+    pub palette: Option<Handle<Image>>,
+}
+
+impl Default for TiledPaletteMaterial {
+    fn default() -> Self {
+        Self {
+            hue_shift: 0.0,
+            tolerance: 0.01,
+            palette: None,
+        }
+    }
+}
+
+impl TiledPaletteMaterial {
+    /// Create a material rotating every hue by `degrees`.
+    pub fn hue_shift(degrees: f32) -> Self {
+        Self {
+            hue_shift: degrees,
+            ..default()
+        }
+    }
+
+    /// Create a material swapping colors through a palette lookup texture.
+    pub fn swap(palette: Handle<Image>) -> Self {
+        Self {
+            palette: Some(palette),
+            ..default()
+        }
+    }
+}
+
+/// GPU representation of [`TiledPaletteMaterial`].
+#[derive(Clone, Default, ShaderType)]
+pub struct TiledPaletteUniform {
+    /// Hue rotation in radians
+    pub hue_shift: f32,
+    /// Color match tolerance
+    pub tolerance: f32,
+    /// 1 if a palette texture is set
+    pub use_palette: u32,
+    // WebGL2 requires 16 byte alignment
+    _padding: f32,
+}
+
+impl AsBindGroupShaderType<TiledPaletteUniform> for TiledPaletteMaterial {
+    fn as_bind_group_shader_type(&self, _images: &RenderAssets<GpuImage>) -> TiledPaletteUniform {
+        TiledPaletteUniform {
+            hue_shift: self.hue_shift.to_radians(),
+            tolerance: self.tolerance,
+            use_palette: u32::from(self.palette.is_some()),
+            _padding: 0.0,
+        }
+    }
+}
+
+impl MaterialTilemap for TiledPaletteMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_tiledmap_tilemap/features/palette.wgsl".into()
+    }
+}
+
+/// Recolors the tile layers of a map or layer entity.
+#[derive(Component, Debug, Clone)]
+pub struct TiledPalette(pub Handle<TiledPaletteMaterial>);
+
+/// System that applies the closest [`TiledPalette`] to each tilemap.
+///
+/// Swaps tilemaps between the standard and palette materials as palettes are
+/// added, changed or removed.
+pub fn apply_tiled_palettes(
+    tilemap_query: Query<
+        (Entity, Option<&MaterialTilemapHandle<TiledPaletteMaterial>>),
+        With<TilesetReference>,
+    >,
+    parent_query: Query<&ChildOf>,
+    palette_query: Query<&TiledPalette>,
+    mut commands: Commands,
+) {
+    for (tilemap, current) in &tilemap_query {
+        let palette = parent_query
+            .iter_ancestors(tilemap)
+            .find_map(|ancestor| palette_query.get(ancestor).ok());
+
+        match (palette, current) {
+            (Some(palette), Some(current)) if current.0 == palette.0 => {}
+            (Some(palette), _) => {
+                commands
+                    .entity(tilemap)
+                    .remove::<MaterialTilemapHandle<StandardTilemapMaterial>>()
+                    .insert(MaterialTilemapHandle::from(palette.0.clone()));
+            }
+            (None, Some(_)) => {
+                commands
+                    .entity(tilemap)
+                    .remove::<MaterialTilemapHandle<TiledPaletteMaterial>>()
+                    .insert(MaterialTilemapHandle::<StandardTilemapMaterial>::default());
+            }
+            (None, None) => {}
+        }
+    }
+}
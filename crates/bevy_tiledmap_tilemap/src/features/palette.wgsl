@@ -0,0 +1,46 @@
+#import bevy_ecs_tilemap::common::process_fragment
+#import bevy_ecs_tilemap::vertex_output::MeshVertexOutput
+
+struct TiledPaletteMaterial {
+    hue_shift: f32,
+    tolerance: f32,
+    use_palette: u32,
+    _padding: f32,
+};
+
+@group(3) @binding(0)
+var<uniform> material: TiledPaletteMaterial;
+@group(3) @binding(1)
+var palette_texture: texture_2d<f32>;
+
+// Rotate a color around the gray axis (Rodrigues' rotation formula)
+fn rotate_hue(color: vec3<f32>, angle: f32) -> vec3<f32> {
+    let axis = vec3<f32>(0.57735027);
+    let cos_angle = cos(angle);
+    return color * cos_angle
+        + cross(axis, color) * sin(angle)
+        + axis * dot(axis, color) * (1.0 - cos_angle);
+}
+
+@fragment
+fn fragment(in: MeshVertexOutput) -> @location(0) vec4<f32> {
+    var color = process_fragment(in);
+
+    if (material.use_palette != 0u) {
+        let columns = textureDimensions(palette_texture).x;
+        for (var x = 0u; x < columns; x++) {
+            let from = textureLoad(palette_texture, vec2<u32>(x, 0u), 0);
+            if (distance(from.rgb, color.rgb) <= material.tolerance) {
+                let to = textureLoad(palette_texture, vec2<u32>(x, 1u), 0);
+                color = vec4<f32>(to.rgb, color.a);
+                break;
+            }
+        }
+    }
+
+    if (material.hue_shift != 0.0) {
+        color = vec4<f32>(clamp(rotate_hue(color.rgb, material.hue_shift), vec3<f32>(0.0), vec3<f32>(1.0)), color.a);
+    }
+
+    return color;
+}
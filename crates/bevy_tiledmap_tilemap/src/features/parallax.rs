@@ -5,9 +5,16 @@
 
 use bevy::prelude::*;
 use bevy_tiledmap_core::events::{ImageLayerSpawned, TileLayerSpawned};
+use bevy_tiledmap_core::properties::PropertyKeyConfig;
+use tiled::{Properties, PropertyValue};
 
-// Re-export from bevy_tiledmap_core or use directly from tiled
-use tiled::PropertyValue;
+use crate::config::TilemapRenderConfig;
+
+/// Layer property holding the horizontal parallax factor.
+pub const PARALLAX_X_PROPERTY: &str = "parallaxX";
+
+/// Layer property holding the vertical parallax factor.
+pub const PARALLAX_Y_PROPERTY: &str = "parallaxY";
 
 /// Marker component for the main camera that parallax layers follow.
 ///
@@ -50,30 +57,26 @@ impl ParallaxLayer {
     }
 }
 
+/// Read a layer's parallax factors (1.0 when unset).
+fn parallax_factors(properties: &Properties, keys: &PropertyKeyConfig) -> (f32, f32) {
+    let factor = |name| match keys.get(properties, name) {
+        Some(PropertyValue::FloatValue(f)) => *f,
+        Some(PropertyValue::IntValue(i)) => *i as f32,
+        _ => 1.0,
+    };
+    (factor(PARALLAX_X_PROPERTY), factor(PARALLAX_Y_PROPERTY))
+}
+
 /// Observer that checks tile layers for parallax properties and adds `ParallaxLayer` component.
-pub fn add_parallax_to_tile_layer(trigger: On<TileLayerSpawned>, mut commands: Commands) {
+pub fn add_parallax_to_tile_layer(
+    trigger: On<TileLayerSpawned>,
+    keys: Res<PropertyKeyConfig>,
+    mut commands: Commands,
+) {
     let event = trigger.event();
 
     // Check for parallax properties
-    let parallax_x = event
-        .properties
-        .get("parallaxX")
-        .and_then(|v| match v {
-            PropertyValue::FloatValue(f) => Some(*f),
-            PropertyValue::IntValue(i) => Some(*i as f32),
-            _ => None,
-        })
-        .unwrap_or(1.0);
-
-    let parallax_y = event
-        .properties
-        .get("parallaxY")
-        .and_then(|v| match v {
-            PropertyValue::FloatValue(f) => Some(*f),
-            PropertyValue::IntValue(i) => Some(*i as f32),
-            _ => None,
-        })
-        .unwrap_or(1.0);
+    let (parallax_x, parallax_y) = parallax_factors(&event.properties, &keys);
 
     // Only add component if parallax is different from default (1.0)
     if (parallax_x - 1.0_f32).abs() > f32::EPSILON || (parallax_y - 1.0_f32).abs() > f32::EPSILON {
@@ -88,29 +91,15 @@ pub fn add_parallax_to_tile_layer(trigger: On<TileLayerSpawned>, mut commands: C
 }
 
 /// Observer that checks image layers for parallax properties and adds `ParallaxLayer` component.
-pub fn add_parallax_to_image_layer(trigger: On<ImageLayerSpawned>, mut commands: Commands) {
+pub fn add_parallax_to_image_layer(
+    trigger: On<ImageLayerSpawned>,
+    keys: Res<PropertyKeyConfig>,
+    mut commands: Commands,
+) {
     let event = trigger.event();
 
     // Check for parallax properties
-    let parallax_x = event
-        .properties
-        .get("parallaxX")
-        .and_then(|v| match v {
-            PropertyValue::FloatValue(f) => Some(*f),
-            PropertyValue::IntValue(i) => Some(*i as f32),
-            _ => None,
-        })
-        .unwrap_or(1.0);
-
-    let parallax_y = event
-        .properties
-        .get("parallaxY")
-        .and_then(|v| match v {
-            PropertyValue::FloatValue(f) => Some(*f),
-            PropertyValue::IntValue(i) => Some(*i as f32),
-            _ => None,
-        })
-        .unwrap_or(1.0);
+    let (parallax_x, parallax_y) = parallax_factors(&event.properties, &keys);
 
     // Only add component if parallax is different from default (1.0)
     if (parallax_x - 1.0_f32).abs() > f32::EPSILON || (parallax_y - 1.0_f32).abs() > f32::EPSILON {
@@ -129,9 +118,17 @@ pub fn add_parallax_to_image_layer(trigger: On<ImageLayerSpawned>, mut commands:
 /// Moves layers with `ParallaxLayer` component based on the delta movement of
 /// the `ParallaxCamera`, scaled by their parallax factors.
 pub fn update_parallax_layers(
+    config: Res<TilemapRenderConfig>,
+    mut frame: Local<u32>,
     camera_query: Query<&Transform, (With<ParallaxCamera>, Without<ParallaxLayer>)>,
     mut layer_query: Query<(&mut Transform, &mut ParallaxLayer)>,
 ) {
+    // Layers catch up with the camera's whole movement on the frames they move
+    *frame = frame.wrapping_add(1);
+    if !frame.is_multiple_of(config.parallax_frame_interval.max(1)) {
+        return;
+    }
+
     // Get the camera position
     let Ok(camera_transform) = camera_query.single() else {
         return;
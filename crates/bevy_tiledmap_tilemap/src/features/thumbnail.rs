@@ -0,0 +1,428 @@
+//! Map preview thumbnails.
+//!
+//! [`render_map_thumbnail`] composites a loaded map into a small [`Image`] on the
+//! CPU, without spawning it, e.g. for level-select menus. [`MapThumbnails`] is a
+//! system param doing the same from a map handle and adding the image as an asset.
+//!
+//! Tile layers, image layers and tile objects are drawn in map order, with layer
+//! visibility, offsets, opacity and tint. Thumbnails approximate the spawned map:
+//! - animated tiles show their base tile,
+//! - parallax, image layer repeats and tile object rotation are ignored,
+//! - other objects (shapes, text) aren't drawn.
+//!
+//! Images are read on the CPU, so tileset and image layer images need CPU-side
+//! data (the default `RenderAssetUsages`).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn show_level_preview(
+//!     mut commands: Commands,
+//!     mut thumbnails: MapThumbnails,
+//!     level: Res<SelectedLevel>,
+//!     asset_server: Res<AssetServer>,
+//! ) {
+//!     if !asset_server.is_loaded_with_dependencies(&level.map) {
+//!         return;
+//!     }
+//!     if let Some(image) = thumbnails.create(&level.map, &MapThumbnailSettings::default()) {
+//!         commands.spawn(ImageNode::new(image));
+//!     }
+//! }
+//! ```
+
+use bevy::asset::RenderAssetUsages;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_tiledmap_assets::loaders::map::for_each_tile;
+use bevy_tiledmap_assets::prelude::{TiledMapAsset, TiledTilesetAsset};
+use bevy_tiledmap_core::properties::TiledColor;
+use tiled::{LayerType, ObjectShape, TilesetLocation};
+
+use crate::tiles::tilemap_builder::flip_uv;
+
+/// Settings for [`render_map_thumbnail`].
+#[derive(Debug, Clone)]
+pub struct MapThumbnailSettings {
+    /// Largest thumbnail size in pixels (default: 256x256)
+    ///
+    /// The map is scaled down to fit, keeping its aspect ratio. It is never scaled up.
+    pub max_size: UVec2,
+
+    /// Samples per thumbnail pixel along each axis, averaged to smooth the
+    /// downscaled map (default: 2)
+    pub samples: u32,
+
+    /// Whether tile objects are drawn (default: true)
+    pub tile_objects: bool,
+
+    /// Background of maps without a background color (default: transparent)
+    pub background: Color,
+}
+
+impl Default for MapThumbnailSettings {
+    fn default() -> Self {
+        Self {
+            max_size: UVec2::splat(256),
+            samples: 2,
+            tile_objects: true,
+            background: Color::NONE,
+        }
+    }
+}
+
+/// System param rendering map thumbnails into image assets.
+///
+/// See the [module docs](self).
+#[derive(SystemParam)]
+pub struct MapThumbnails<'w> {
+    maps: Res<'w, Assets<TiledMapAsset>>,
+    tilesets: Res<'w, Assets<TiledTilesetAsset>>,
+    images: ResMut<'w, Assets<Image>>,
+}
+
+impl MapThumbnails<'_> {
+    /// Render a thumbnail of a map and add it to the image assets.
+    ///
+    /// Returns `None` until the map, its tilesets and their images are loaded.
+    pub fn create(
+        &mut self,
+        map: &Handle<TiledMapAsset>,
+        settings: &MapThumbnailSettings,
+    ) -> Option<Handle<Image>> {
+        let map = self.maps.get(map)?;
+        let thumbnail = render_map_thumbnail(map, &self.tilesets, &self.images, settings)?;
+        Some(self.images.add(thumbnail))
+    }
+}
+
+/// Render a thumbnail of a loaded map on the CPU.
+///
+/// Returns `None` if one of the map's tilesets or images isn't loaded (yet).
+pub fn render_map_thumbnail(
+    map_asset: &TiledMapAsset,
+    tilesets: &Assets<TiledTilesetAsset>,
+    images: &Assets<Image>,
+    settings: &MapThumbnailSettings,
+) -> Option<Image> {
+    let map = &map_asset.map;
+    let map_size = map_asset.rect.size().max(Vec2::ONE);
+    let scale = (settings.max_size.as_vec2() / map_size)
+        .min_element()
+        .min(1.0);
+    let size = (map_size * scale).round().as_uvec2().max(UVec2::ONE);
+    let samples = settings.samples.max(1);
+
+    // Infinite maps start at their top-left chunk
+    let origin = if map.infinite() {
+        let (chunk_x, chunk_y) = map_asset.topleft_chunk;
+        Vec2::new(
+            (chunk_x * tiled::ChunkData::WIDTH as i32) as f32 * map.tile_width as f32,
+            (chunk_y * tiled::ChunkData::HEIGHT as i32) as f32 * map.tile_height as f32,
+        )
+    } else {
+        Vec2::ZERO
+    };
+
+    let background = match map.background_color {
+        Some(color) => tiled_color(color),
+        None => settings.background.to_srgba().to_vec4(),
+    };
+    let canvas_size = size * samples;
+    let mut canvas = Canvas {
+        map_asset,
+        tilesets,
+        images,
+        settings,
+        origin,
+        scale: canvas_size.as_vec2() / map_size,
+        size: canvas_size,
+        pixels: vec![premultiply(background); (canvas_size.x * canvas_size.y) as usize],
+        ready: true,
+    };
+    canvas.draw_layers(map.layers(), Vec2::ZERO, Vec4::ONE);
+    if !canvas.ready {
+        return None;
+    }
+
+    // Average the samples of each thumbnail pixel
+    let mut data = Vec::with_capacity((size.x * size.y * 4) as usize);
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let mut sum = Vec4::ZERO;
+            for sample_y in 0..samples {
+                for sample_x in 0..samples {
+                    let canvas_x = x * samples + sample_x;
+                    let canvas_y = y * samples + sample_y;
+                    sum += canvas.pixels[(canvas_y * canvas_size.x + canvas_x) as usize];
+                }
+            }
+            let color = unpremultiply(sum / (samples * samples) as f32);
+            data.extend(
+                color
+                    .to_array()
+                    .map(|channel| (channel * 255.0).round() as u8),
+            );
+        }
+    }
+
+    Some(Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ))
+}
+
+/// Premultiplied sRGB pixels of a thumbnail being rendered, at `samples` times its size.
+struct Canvas<'a> {
+    map_asset: &'a TiledMapAsset,
+    tilesets: &'a Assets<TiledTilesetAsset>,
+    images: &'a Assets<Image>,
+    settings: &'a MapThumbnailSettings,
+    /// Map position (pixels, Y-down) of the canvas' top-left corner
+    origin: Vec2,
+    /// Canvas pixels per map pixel
+    scale: Vec2,
+    size: UVec2,
+    pixels: Vec<Vec4>,
+    /// Cleared when a tileset or image isn't loaded
+    ready: bool,
+}
+
+/// Part of an image drawn into a [`Canvas`].
+struct Source<'a> {
+    image: &'a Image,
+    /// Texels of the drawn part
+    rect: Rect,
+    flip_h: bool,
+    flip_v: bool,
+    flip_d: bool,
+}
+
+impl<'a> Canvas<'a> {
+    /// Draw layers bottom to top, inside groups with the given offset and tint.
+    fn draw_layers<'map>(
+        &mut self,
+        layers: impl Iterator<Item = tiled::Layer<'map>>,
+        offset: Vec2,
+        tint: Vec4,
+    ) {
+        for layer in layers {
+            if !layer.visible {
+                continue;
+            }
+            let offset = offset + Vec2::new(layer.offset_x, layer.offset_y);
+            let mut tint = tint * layer.tint_color.map_or(Vec4::ONE, tiled_color);
+            tint.w *= layer.opacity;
+
+            match layer.layer_type() {
+                LayerType::Tiles(tile_layer) => {
+                    let tile_size = Vec2::new(
+                        self.map_asset.map.tile_width as f32,
+                        self.map_asset.map.tile_height as f32,
+                    );
+                    for_each_tile(&tile_layer, |x, y, tile| {
+                        // Tiles are drawn from the bottom-left of their cell
+                        let cell_bottom_left = Vec2::new(x as f32, (y + 1) as f32) * tile_size;
+                        let flips = (tile.flip_h, tile.flip_v, tile.flip_d);
+                        self.draw_tile(
+                            tile.tileset_index() as u32,
+                            tile.id(),
+                            flips,
+                            |source_size| {
+                                Rect::from_corners(
+                                    offset + cell_bottom_left - Vec2::new(0.0, source_size.y),
+                                    offset + cell_bottom_left + Vec2::new(source_size.x, 0.0),
+                                )
+                            },
+                            tint,
+                        );
+                    });
+                }
+                LayerType::Objects(object_layer) => {
+                    if !self.settings.tile_objects {
+                        continue;
+                    }
+                    for object in object_layer.objects() {
+                        let Some(tile_data) = object.tile_data() else {
+                            continue;
+                        };
+                        let ObjectShape::Rect { width, height } = object.shape else {
+                            continue;
+                        };
+                        if !object.visible {
+                            continue;
+                        }
+                        let Some(tileset_index) = self.tileset_index(tile_data.tileset_location())
+                        else {
+                            continue;
+                        };
+                        let Some(tileset) = self.tileset(tileset_index) else {
+                            continue;
+                        };
+
+                        // The object's position is its tileset's alignment point
+                        let size = Vec2::new(width, height);
+                        let top_left = offset + Vec2::new(object.x, object.y)
+                            - tileset.object_alignment.origin() * size;
+                        let flips = (tile_data.flip_h, tile_data.flip_v, tile_data.flip_d);
+                        self.draw_tile(
+                            tileset_index,
+                            tile_data.id(),
+                            flips,
+                            |_| Rect::from_corners(top_left, top_left + size),
+                            tint,
+                        );
+                    }
+                }
+                LayerType::Image(_) => {
+                    let images = self.images;
+                    let Some(handle) = self.map_asset.images.get(&layer.id()) else {
+                        continue;
+                    };
+                    let Some(image) = images.get(handle) else {
+                        self.ready = false;
+                        continue;
+                    };
+                    let image_size = image.size_f32();
+                    self.draw(
+                        Rect::from_corners(offset, offset + image_size),
+                        &Source {
+                            image,
+                            rect: Rect::from_corners(Vec2::ZERO, image_size),
+                            flip_h: false,
+                            flip_v: false,
+                            flip_d: false,
+                        },
+                        tint,
+                    );
+                }
+                LayerType::Group(group) => self.draw_layers(group.layers(), offset, tint),
+            }
+        }
+    }
+
+    /// Draw a tile of a tileset, into the map rect computed from its image size.
+    fn draw_tile(
+        &mut self,
+        tileset_index: u32,
+        tile_id: u32,
+        (flip_h, flip_v, flip_d): (bool, bool, bool),
+        target: impl FnOnce(Vec2) -> Rect,
+        tint: Vec4,
+    ) {
+        let Some(tileset) = self.tileset(tileset_index) else {
+            return;
+        };
+        let (image_handle, rect) = match &tileset.atlas_image {
+            Some(atlas_image) => {
+                let columns = tileset.tileset.columns.max(1);
+                let texel = UVec2::new(tile_id % columns, tile_id / columns)
+                    * (tileset.tile_size + tileset.spacing)
+                    + tileset.margin;
+                let min = texel.as_vec2();
+                let rect = Rect::from_corners(min, min + tileset.tile_size.as_vec2());
+                (atlas_image, Some(rect))
+            }
+            None => match tileset.tile_images.get(&tile_id) {
+                Some(image_handle) => (image_handle, None),
+                None => return,
+            },
+        };
+        let Some(image) = self.images.get(image_handle) else {
+            self.ready = false;
+            return;
+        };
+        // Image collection tiles are drawn whole
+        let rect = rect.unwrap_or(Rect::from_corners(Vec2::ZERO, image.size_f32()));
+
+        let drawing_offset = tileset.tile_offset.as_vec2();
+        let target = target(rect.size());
+        self.draw(
+            Rect::from_corners(target.min + drawing_offset, target.max + drawing_offset),
+            &Source {
+                image,
+                rect,
+                flip_h,
+                flip_v,
+                flip_d,
+            },
+            tint,
+        );
+    }
+
+    /// Point-sample `source` into the canvas pixels whose centers lie in `target`
+    /// (map pixels, Y-down), blending it over what is drawn.
+    fn draw(&mut self, target: Rect, source: &Source, tint: Vec4) {
+        let min = (target.min - self.origin) * self.scale;
+        let max = (target.max - self.origin) * self.scale;
+        let size = max - min;
+        if size.x <= 0.0 || size.y <= 0.0 {
+            return;
+        }
+
+        // Pixel (x, y) is covered if its center (x + 0.5, y + 0.5) is in [min, max)
+        let first = (min - 0.5).ceil().max(Vec2::ZERO).as_uvec2();
+        let last = (max - 0.5).ceil().max(Vec2::ZERO).as_uvec2().min(self.size);
+        let source_max = source.rect.max - 1.0;
+        for y in first.y..last.y {
+            for x in first.x..last.x {
+                let uv = (Vec2::new(x as f32, y as f32) + 0.5 - min) / size;
+                let uv = flip_uv(uv, source.flip_h, source.flip_v, source.flip_d);
+                let texel = (source.rect.min + uv * source.rect.size()).min(source_max);
+                let Ok(color) = source.image.get_color_at(texel.x as u32, texel.y as u32) else {
+                    continue;
+                };
+                let color = premultiply(color.to_srgba().to_vec4() * tint);
+                let pixel = &mut self.pixels[(y * self.size.x + x) as usize];
+                *pixel = color + *pixel * (1.0 - color.w);
+            }
+        }
+    }
+
+    /// Get a map tileset by index, clearing `ready` if it isn't loaded.
+    fn tileset(&mut self, tileset_index: u32) -> Option<&'a TiledTilesetAsset> {
+        let tileset_ref = self.map_asset.tilesets.get(&tileset_index)?;
+        let tileset = self.tilesets.get(&tileset_ref.handle);
+        self.ready &= tileset.is_some();
+        tileset
+    }
+
+    /// Find the map tileset index of a tile object's tileset.
+    fn tileset_index(&mut self, location: &TilesetLocation) -> Option<u32> {
+        match location {
+            TilesetLocation::Map(tileset_index) => Some(*tileset_index as u32),
+            // Template tileset - find the map tileset with the same source
+            TilesetLocation::Template(template_tileset) => {
+                let map_asset = self.map_asset;
+                map_asset.tilesets.keys().copied().find(|tileset_index| {
+                    self.tileset(*tileset_index)
+                        .is_some_and(|tileset| tileset.tileset.source == template_tileset.source)
+                })
+            }
+        }
+    }
+}
+
+fn tiled_color(color: tiled::Color) -> Vec4 {
+    TiledColor(color).to_color().to_srgba().to_vec4()
+}
+
+fn premultiply(color: Vec4) -> Vec4 {
+    (color.xyz() * color.w).extend(color.w)
+}
+
+fn unpremultiply(color: Vec4) -> Vec4 {
+    if color.w <= 0.0 {
+        Vec4::ZERO
+    } else {
+        (color.xyz() / color.w).extend(color.w)
+    }
+}
@@ -1,8 +1,12 @@
 //! Z-ordering for layers and objects.
 //!
 //! Automatically sets Transform.z values based on layer IDs and hierarchy.
+//!
+//! Tile objects within a layer also get small Z offsets so overlapping sprites
+//! draw in a stable order (see [`ObjectSortMode`]).
 
 use bevy::prelude::*;
+use bevy_tiledmap_core::components::object::{ObjectId, TiledObject};
 use bevy_tiledmap_core::events::{
     ImageLayerSpawned, ObjectLayerSpawned, ObjectSpawned, TileLayerSpawned,
 };
@@ -21,6 +25,16 @@ pub struct ZOrderConfig {
     ///
     /// Objects get z = `parent_layer_z` + `object_z_offset`
     pub object_z_offset: f32,
+
+    /// How overlapping tile objects within a layer are ordered (default: `ObjectId`)
+    pub object_sort: ObjectSortMode,
+
+    /// Range of the Z offsets given to sorted tile objects (default: 0.5)
+    ///
+    /// Objects are spread evenly within `(0, object_sort_range)` above their layer.
+    /// Keep it below the spacing between layers so objects never cross into the
+    /// next layer.
+    pub object_sort_range: f32,
 }
 
 impl Default for ZOrderConfig {
@@ -28,10 +42,29 @@ impl Default for ZOrderConfig {
         Self {
             layer_separation: 10.0,
             object_z_offset: 1.0,
+            object_sort: ObjectSortMode::ObjectId,
+            object_sort_range: 0.5,
         }
     }
 }
 
+/// Draw order of overlapping tile objects within an object layer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum ObjectSortMode {
+    /// Leave every object at its layer's Z
+    None,
+    /// Higher object IDs draw on top (Tiled's "index" draw order)
+    #[default]
+    ObjectId,
+    /// Objects lower on screen draw on top (Tiled's "top down" draw order)
+    Y,
+}
+
+/// Z offset chosen for a tile object within its layer, for debugging draw order.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct ObjectSortZ(pub f32);
+
 /// Observer that sets z-order for tile layers.
 ///
 /// Sets Transform.z = `layer_id` * `layer_separation`
@@ -106,3 +139,67 @@ pub fn set_object_z_order(
         transform.translation.z = parent_z + config.object_z_offset;
     }
 }
+
+/// System that gives tile objects deterministic Z offsets within their layer.
+///
+/// Re-sorts a layer when tile objects are added to it, or when they move in
+/// [`ObjectSortMode::Y`] mode. Ties are broken by object ID.
+pub fn sort_tile_objects(
+    config: Res<ZOrderConfig>,
+    changed_query: Query<
+        (&ChildOf, Ref<TiledObject>),
+        Or<(Added<TiledObject>, Changed<Transform>)>,
+    >,
+    children_query: Query<&Children>,
+    mut object_query: Query<(&TiledObject, &ObjectId, &mut Transform)>,
+    mut commands: Commands,
+) {
+    if config.object_sort == ObjectSortMode::None {
+        return;
+    }
+
+    let mut layers: Vec<Entity> = changed_query
+        .iter()
+        .filter(|(_, object)| {
+            matches!(**object, TiledObject::Tile { .. })
+                && (object.is_added() || config.object_sort == ObjectSortMode::Y)
+        })
+        .map(|(parent, _)| parent.parent())
+        .collect();
+    layers.sort();
+    layers.dedup();
+
+    for layer in layers {
+        let Ok(children) = children_query.get(layer) else {
+            continue;
+        };
+
+        let mut sorted: Vec<(Entity, u32, f32)> = children
+            .iter()
+            .filter_map(|child| {
+                let (object, id, transform) = object_query.get(child).ok()?;
+                matches!(object, TiledObject::Tile { .. }).then_some((
+                    child,
+                    id.0,
+                    transform.translation.y,
+                ))
+            })
+            .collect();
+        match config.object_sort {
+            ObjectSortMode::Y => sorted.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.1.cmp(&b.1))),
+            _ => sorted.sort_by_key(|(_, id, _)| *id),
+        }
+
+        let step = config.object_sort_range / (sorted.len() + 1) as f32;
+        for (rank, (entity, _, _)) in sorted.into_iter().enumerate() {
+            let z = (rank + 1) as f32 * step;
+            let Ok((_, _, mut transform)) = object_query.get_mut(entity) else {
+                continue;
+            };
+            if transform.translation.z != z {
+                transform.translation.z = z;
+                commands.entity(entity).insert(ObjectSortZ(z));
+            }
+        }
+    }
+}
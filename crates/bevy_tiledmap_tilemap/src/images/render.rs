@@ -1,25 +1,23 @@
-//! Observer for image layer spawning events.
+//! Image layer rendering, run by [`EcsTilemapBackend`](crate::backend::EcsTilemapBackend).
 
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 use bevy_tiledmap_core::components::layer::ImageLayerData;
 use bevy_tiledmap_core::events::ImageLayerSpawned;
 
-/// Observer that renders image layers as sprites.
+/// Renders an image layer as a sprite.
 ///
-/// When an image layer is spawned by Layer 2, this observer:
+/// When an image layer is spawned by Layer 2, this system:
 /// 1. Reads the `ImageLayerData` component
 /// 2. Creates a Sprite with the image
 /// 3. Adjusts transform to use Bevy coordinates (positive Y)
 /// 4. Sets anchor to `BottomLeft` (images extend up and right in Bevy's Y-up space)
 pub fn on_image_layer_spawned(
-    trigger: On<ImageLayerSpawned>,
+    In(event): In<ImageLayerSpawned>,
     layer_query: Query<(&ImageLayerData, &Transform, Option<&Name>)>,
     images: Res<Assets<Image>>,
     mut commands: Commands,
 ) {
-    let event = trigger.event();
-
     let Ok((image_data, transform, name)) = layer_query.get(event.entity) else {
         warn!(
             "ImageLayerSpawned event for entity {:?} but no ImageLayerData component found",
@@ -2,17 +2,26 @@
 //!
 //! High-performance tile layer rendering for `bevy_tiled` using `bevy_ecs_tilemap`.
 //!
-//! This crate is a Layer 3 plugin that observes spawning events from `bevy_tiledmap_core`
-//! and adds rendering components using `bevy_ecs_tilemap` for optimal batched rendering.
+//! This crate is a Layer 3 plugin that implements `bevy_tiledmap_core`'s `TiledRenderBackend`
+//! (see [`EcsTilemapBackend`]) and adds rendering components using `bevy_ecs_tilemap` for
+//! optimal batched rendering.
 //!
 //! ## Features
 //!
 //! - **Tile layers**: Batched rendering with `bevy_ecs_tilemap`
 //! - **Multi-tileset support**: Handles layers using multiple tilesets
 //! - **Tile animations**: Automatic frame cycling based on tileset animation data
-//! - **Object rendering**: Sprites for tile objects, debug shapes for collision geometry
+//! - **Object rendering**: Sprites for tile objects, filled polygons, debug shapes for collision geometry
 //! - **Image layers**: Simple sprite rendering
 //! - **Parallax scrolling**: Layer parallax based on Tiled properties
+//! - **Static baking**: Optional single-mesh rendering of large static tile layers
+//! - **Picking**: Per-pixel picking of tiles and tile objects
+//! - **3D plane rendering**: Tile layers as 3D meshes on the ground plane (or any plane)
+//! - **Palette swaps**: Runtime recoloring and hue shifts of tile layers
+//! - **Thumbnails**: CPU-rendered map previews, e.g. for level-select menus
+//! - **Fog of war**: Per-map visibility grids drawn as soft fog over hidden and explored tiles
+//! - **Footprint overlay**: On-screen per-map memory and entity counts, for content budgets
+//! - **Scalability**: Animated tile budgets, throttled parallax and small-object LOD for low-end devices ([`TilemapRenderConfig::low_end`])
 //! - **Z-ordering**: Automatic depth sorting
 //!
 //! ## Quick Start
@@ -40,6 +49,7 @@
 //! }
 //! ```
 
+pub mod backend;
 pub mod config;
 pub mod features;
 pub mod images;
@@ -47,12 +57,37 @@ pub mod objects;
 pub mod plugin;
 pub mod tiles;
 
+pub use backend::EcsTilemapBackend;
 pub use config::TilemapRenderConfig;
-pub use plugin::TilemapPlugin;
+pub use plugin::{TiledRenderSystems, TilemapPlugin};
 
 /// Prelude module for convenient imports
 pub mod prelude {
-    pub use crate::config::TilemapRenderConfig;
-    pub use crate::features::{AnimationSpeed, AnimationsPaused, ParallaxCamera, ZOrderConfig};
-    pub use crate::plugin::TilemapPlugin;
+    pub use crate::backend::EcsTilemapBackend;
+    pub use crate::config::{MapPlane, ObjectLod, TilemapRenderConfig};
+    #[cfg(feature = "footprint_overlay")]
+    pub use crate::features::FootprintOverlay;
+    pub use crate::features::{
+        AnimationSpeed, AnimationsPaused, ObjectSortMode, ObjectSortZ, ParallaxCamera, ZOrderConfig,
+    };
+    #[cfg(feature = "fog_of_war")]
+    pub use crate::features::{FogGrid, FogOfWar, FogOfWarSettings, FogState};
+    #[cfg(feature = "thumbnail")]
+    pub use crate::features::{MapThumbnailSettings, MapThumbnails};
+    #[cfg(feature = "picking")]
+    pub use crate::features::{TilePickingSettings, TiledPickHit, TiledPicker};
+    #[cfg(feature = "palette")]
+    pub use crate::features::{TiledPalette, TiledPaletteMaterial};
+    pub use crate::objects::SpriteStyle;
+    #[cfg(feature = "animations")]
+    pub use crate::objects::{ObjectAnimation, ObjectAnimationClip, ObjectAnimationFinished};
+    pub use crate::plugin::{TiledRenderSystems, TilemapPlugin};
+    #[cfg(feature = "static_bake")]
+    pub use crate::tiles::BakedTileLayer;
+    #[cfg(feature = "plane_3d")]
+    pub use crate::tiles::PlaneTileLayer;
+    pub use crate::tiles::{
+        AnimationClock, TileAnimation, TileAnimationFinished, TileAnimationPlayback,
+        TileAnimationSync,
+    };
 }
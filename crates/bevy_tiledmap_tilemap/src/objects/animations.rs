@@ -0,0 +1,402 @@
+//! Playback control for animated tile objects.
+//!
+//! Tile objects whose tile is animated in the tileset get an [`ObjectAnimation`]
+//! that plays the animation on their sprite. Unlike animated tiles, which follow
+//! the global [`AnimationClock`](crate::tiles::AnimationClock), each object has
+//! its own playhead that gameplay can play, pause, seek or restrict to a range
+//! of frames.
+//!
+//! Named clips are authored on the animated tile with the `animation_clips`
+//! string property: comma-separated `name=first-last` frame ranges (inclusive,
+//! 0-based), optionally followed by `once` for clips that stop on their last
+//! frame instead of looping:
+//!
+//! ```text
+//! idle=0-3, open=4-7 once, opened=7
+//! ```
+//!
+//! An object can start on a clip with the `animation_clip` object property;
+//! otherwise it loops the whole animation. When a `once` clip (or range) ends,
+//! [`ObjectAnimationFinished`] is triggered on the object:
+//!
+//! ```rust,ignore
+//! fn open_chest(mut chests: Query<&mut ObjectAnimation, With<Chest>>) {
+//!     for mut animation in &mut chests {
+//!         animation.play_clip("open");
+//!     }
+//! }
+//!
+//! app.add_observer(|finished: On<ObjectAnimationFinished>, mut animations: Query<&mut ObjectAnimation>| {
+//!     if finished.clip.as_deref() == Some("open") {
+//!         animations.get_mut(finished.entity).unwrap().play_clip("opened");
+//!     }
+//! });
+//! ```
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+use bevy_tiledmap_core::components::object::TiledObject;
+use bevy_tiledmap_core::events::ObjectSpawned;
+use bevy_tiledmap_core::properties::PropertyKeyConfig;
+use tiled::PropertyValue;
+
+use super::tile_objects::calculate_tile_rect;
+use crate::config::TilemapRenderConfig;
+use crate::features::{AnimationSpeed, AnimationsPaused};
+use crate::tiles::AnimationFrame;
+
+/// Tileset tile property defining the named clips of an animated tile object.
+pub const ANIMATION_CLIPS_PROPERTY: &str = "animation_clips";
+
+/// Object property naming the clip an animated tile object starts on.
+pub const ANIMATION_CLIP_PROPERTY: &str = "animation_clip";
+
+/// A named range of frames of an [`ObjectAnimation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectAnimationClip {
+    /// Frame indices of the clip
+    pub frames: Range<usize>,
+    /// Whether the clip loops, or stops on its last frame
+    pub looping: bool,
+}
+
+impl ObjectAnimationClip {
+    /// Parse an `animation_clips` value (`"idle=0-3, open=4-7 once"`) for an
+    /// animation of `frame_count` frames, warning about invalid clips.
+    pub fn parse_clips(value: &str, frame_count: usize) -> HashMap<String, Self> {
+        let mut clips = HashMap::new();
+        for entry in value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            match Self::parse_clip(entry, frame_count) {
+                Some((name, clip)) => {
+                    clips.insert(name.to_string(), clip);
+                }
+                None => warn!(
+                    "Invalid {} entry '{}', expected \"name=first-last\" within {} frames",
+                    ANIMATION_CLIPS_PROPERTY, entry, frame_count
+                ),
+            }
+        }
+        clips
+    }
+
+    fn parse_clip(entry: &str, frame_count: usize) -> Option<(&str, Self)> {
+        let (name, range) = entry.split_once('=')?;
+        let (range, looping) = match range.trim().strip_suffix("once") {
+            Some(range) => (range.trim(), false),
+            None => (range.trim(), true),
+        };
+        let (first, last) = range.split_once('-').unwrap_or((range, range));
+        let first: usize = first.trim().parse().ok()?;
+        let last: usize = last.trim().parse().ok()?;
+        if name.trim().is_empty() || first > last || last >= frame_count {
+            return None;
+        }
+        Some((
+            name.trim(),
+            Self {
+                frames: first..last + 1,
+                looping,
+            },
+        ))
+    }
+}
+
+/// Animation playback of a tile object, with play/pause/seek controls.
+///
+/// See the [module docs](self).
+#[derive(Component, Debug, Clone)]
+pub struct ObjectAnimation {
+    /// Frames of the tile's animation.
+    pub frames: Vec<AnimationFrame>,
+    /// Named clips, from the `animation_clips` tile property.
+    pub clips: HashMap<String, ObjectAnimationClip>,
+    /// Playback speed multiplier, on top of [`AnimationSpeed`] (default: 1.0).
+    pub speed: f32,
+    range: Range<usize>,
+    looping: bool,
+    clip: Option<String>,
+    current_frame: usize,
+    elapsed_ms: f32,
+    playing: bool,
+}
+
+impl ObjectAnimation {
+    /// Loop over all `frames`.
+    pub fn new(frames: Vec<AnimationFrame>) -> Self {
+        Self {
+            range: 0..frames.len(),
+            frames,
+            clips: HashMap::new(),
+            speed: 1.0,
+            looping: true,
+            clip: None,
+            current_frame: 0,
+            elapsed_ms: 0.0,
+            playing: true,
+        }
+    }
+
+    /// Set the named clips.
+    pub fn with_clips(mut self, clips: HashMap<String, ObjectAnimationClip>) -> Self {
+        self.clips = clips;
+        self
+    }
+
+    /// Resume playback.
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    /// Pause playback on the current frame.
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Whether the animation is playing.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Play a named clip from its first frame.
+    ///
+    /// Returns `false` (leaving playback unchanged) if there is no such clip.
+    pub fn play_clip(&mut self, name: &str) -> bool {
+        let Some(clip) = self.clips.get(name) else {
+            return false;
+        };
+        self.range = clip.frames.clone();
+        self.looping = clip.looping;
+        self.clip = Some(name.to_string());
+        self.current_frame = self.range.start;
+        self.elapsed_ms = 0.0;
+        self.playing = true;
+        true
+    }
+
+    /// Name of the clip playing, if any.
+    pub fn clip(&self) -> Option<&str> {
+        self.clip.as_deref()
+    }
+
+    /// Restrict playback to a range of frames (clamped to the animation),
+    /// looping over it or stopping on its last frame.
+    pub fn set_range(&mut self, frames: Range<usize>, looping: bool) {
+        let end = frames.end.min(self.frames.len());
+        self.range = frames.start.min(end)..end;
+        self.looping = looping;
+        self.clip = None;
+        if !self.range.contains(&self.current_frame) {
+            self.current_frame = self.range.start;
+            self.elapsed_ms = 0.0;
+        }
+    }
+
+    /// The range of frames played.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Show a frame (clamped to the played range) from its start.
+    pub fn set_frame(&mut self, frame: usize) {
+        self.current_frame = frame.clamp(
+            self.range.start,
+            self.range.end.saturating_sub(1).max(self.range.start),
+        );
+        self.elapsed_ms = 0.0;
+    }
+
+    /// Index of the frame shown.
+    pub fn current_frame(&self) -> usize {
+        self.current_frame
+    }
+
+    /// Move to the frame shown `time_ms` after the start of the played range.
+    pub fn seek(&mut self, time_ms: f32) {
+        let duration = self.range_duration_ms();
+        if duration <= 0.0 {
+            return;
+        }
+        let mut remaining = if self.looping {
+            time_ms.max(0.0) % duration
+        } else {
+            time_ms.clamp(0.0, duration)
+        };
+        for index in self.range.clone() {
+            let frame_duration = self.frames[index].duration_ms as f32;
+            if remaining < frame_duration || index + 1 == self.range.end {
+                self.current_frame = index;
+                self.elapsed_ms = remaining.min(frame_duration);
+                return;
+            }
+            remaining -= frame_duration;
+        }
+    }
+
+    /// Duration of the played range in milliseconds.
+    pub fn range_duration_ms(&self) -> f32 {
+        self.frames[self.range.clone()]
+            .iter()
+            .map(|frame| frame.duration_ms as f32)
+            .sum()
+    }
+
+    /// Tile ID of the frame shown, if there are frames.
+    pub fn current_tile_id(&self) -> Option<u32> {
+        self.frames
+            .get(self.current_frame)
+            .map(|frame| frame.tile_id)
+    }
+
+    /// Advance playback by `delta_ms` (scaled by [`speed`](Self::speed)).
+    ///
+    /// Returns `true` once, when a non-looping range reaches its end.
+    pub fn advance(&mut self, delta_ms: f32) -> bool {
+        if !self.playing || self.range_duration_ms() <= 0.0 {
+            return false;
+        }
+        self.elapsed_ms += delta_ms * self.speed.max(0.0);
+        loop {
+            let duration = self.frames[self.current_frame].duration_ms as f32;
+            if self.elapsed_ms < duration {
+                return false;
+            }
+            if self.current_frame + 1 < self.range.end {
+                self.elapsed_ms -= duration;
+                self.current_frame += 1;
+            } else if self.looping {
+                self.elapsed_ms -= duration;
+                self.current_frame = self.range.start;
+            } else {
+                self.elapsed_ms = duration;
+                self.playing = false;
+                return true;
+            }
+        }
+    }
+}
+
+/// Fired on a tile object when its [`ObjectAnimation`] reaches the end of a
+/// non-looping clip or range.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct ObjectAnimationFinished {
+    /// The tile object entity
+    #[event_target]
+    pub entity: Entity,
+    /// The clip that finished, if playback was on a named clip
+    pub clip: Option<String>,
+}
+
+/// Observer giving tile objects with an animated tile an [`ObjectAnimation`].
+pub fn on_animated_tile_object_spawned(
+    trigger: On<ObjectSpawned>,
+    object_query: Query<&TiledObject>,
+    tileset_assets: Res<Assets<TiledTilesetAsset>>,
+    keys: Res<PropertyKeyConfig>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let Ok(TiledObject::Tile {
+        tile_id,
+        tileset_handle,
+        ..
+    }) = object_query.get(event.entity)
+    else {
+        return;
+    };
+    let Some(tile) = tileset_assets
+        .get(tileset_handle)
+        .and_then(|tileset| tileset.tileset.get_tile(*tile_id))
+    else {
+        return;
+    };
+    let Some(frames) = tile.animation.as_ref().filter(|frames| !frames.is_empty()) else {
+        return;
+    };
+
+    let frames: Vec<AnimationFrame> = frames
+        .iter()
+        .map(|frame| AnimationFrame {
+            tile_id: frame.tile_id,
+            duration_ms: frame.duration,
+        })
+        .collect();
+    let clips = match keys.get(&tile.properties, ANIMATION_CLIPS_PROPERTY) {
+        Some(PropertyValue::StringValue(value)) => {
+            ObjectAnimationClip::parse_clips(value, frames.len())
+        }
+        _ => HashMap::new(),
+    };
+    let mut animation = ObjectAnimation::new(frames).with_clips(clips);
+    if let Some(PropertyValue::StringValue(clip)) =
+        keys.get(&event.properties, ANIMATION_CLIP_PROPERTY)
+    {
+        if !animation.play_clip(clip) {
+            warn!(
+                "Tile object {} starts on unknown animation clip '{}'",
+                event.object_id, clip
+            );
+        }
+    }
+    commands.entity(event.entity).insert(animation);
+}
+
+/// System advancing [`ObjectAnimation`]s and showing their frame on the
+/// object's sprite.
+///
+/// Playback stops while [`AnimationsPaused`] is present or
+/// [`TilemapRenderConfig::enable_animations`] is off, but seeking still
+/// updates the sprite. Triggers [`ObjectAnimationFinished`] for non-looping
+/// clips that end.
+pub fn update_object_animations(
+    time: Res<Time>,
+    speed: Res<AnimationSpeed>,
+    paused: Option<Res<AnimationsPaused>>,
+    config: Res<TilemapRenderConfig>,
+    tileset_assets: Res<Assets<TiledTilesetAsset>>,
+    mut animated_objects: Query<(Entity, &mut ObjectAnimation, &mut Sprite, &TiledObject)>,
+    mut commands: Commands,
+) {
+    let delta_ms = if paused.is_some() || !config.enable_animations {
+        0.0
+    } else {
+        time.delta_secs() * 1000.0 * speed.0
+    };
+
+    for (entity, mut animation, mut sprite, object) in &mut animated_objects {
+        if delta_ms > 0.0 && animation.advance(delta_ms) {
+            commands.trigger(ObjectAnimationFinished {
+                entity,
+                clip: animation.clip.clone(),
+            });
+        }
+
+        let TiledObject::Tile { tileset_handle, .. } = object else {
+            continue;
+        };
+        let (Some(tile_id), Some(tileset)) = (
+            animation.current_tile_id(),
+            tileset_assets.get(tileset_handle),
+        ) else {
+            continue;
+        };
+        if tileset.is_image_collection() {
+            if let Some(image) = tileset.get_tile_image(tile_id) {
+                if sprite.image != *image {
+                    sprite.image = image.clone();
+                }
+            }
+        } else {
+            let rect = Some(calculate_tile_rect(tileset, tile_id));
+            if sprite.rect != rect {
+                sprite.rect = rect;
+            }
+        }
+    }
+}
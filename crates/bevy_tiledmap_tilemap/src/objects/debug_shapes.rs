@@ -91,7 +91,7 @@ pub fn render_object_shapes(mut gizmos: Gizmos, objects: Query<(&TiledObject, &G
                 );
             }
 
-            TiledObject::Text {} => {
+            _ => {
                 // No debug rendering for text objects yet
             }
         }
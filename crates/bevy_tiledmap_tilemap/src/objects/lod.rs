@@ -0,0 +1,49 @@
+//! Level of detail for tile object sprites.
+//!
+//! With [`TilemapRenderConfig::object_lod`] set, small tile objects are hidden
+//! while the camera is zoomed out and shown again when it zooms back in (see
+//! [`ObjectLod`](crate::config::ObjectLod)). Only objects hidden by the LOD
+//! system are shown again, so objects hidden by game code stay hidden.
+
+use bevy::prelude::*;
+use bevy_tiledmap_core::components::object::TiledObject;
+
+use crate::config::TilemapRenderConfig;
+
+/// Marker for tile objects hidden by [`apply_object_lod`].
+#[derive(Component, Debug)]
+pub struct ObjectLodHidden;
+
+/// System hiding small tile objects while the camera is zoomed out.
+pub fn apply_object_lod(
+    mut commands: Commands,
+    config: Res<TilemapRenderConfig>,
+    cameras: Query<(&Camera, &Projection)>,
+    mut objects: Query<(Entity, &TiledObject, &mut Visibility, Has<ObjectLodHidden>), With<Sprite>>,
+) {
+    let zoom = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .find_map(|(_, projection)| match projection {
+            Projection::Orthographic(orthographic) => Some(1.0 / orthographic.scale),
+            _ => None,
+        });
+    let max_hidden_size = match (config.object_lod, zoom) {
+        (Some(lod), Some(zoom)) if zoom < lod.min_zoom => Some(lod.max_object_size),
+        _ => None,
+    };
+
+    for (entity, object, mut visibility, lod_hidden) in &mut objects {
+        let TiledObject::Tile { width, height, .. } = object else {
+            continue;
+        };
+        let hide = max_hidden_size.is_some_and(|max_size| width.max(*height) <= max_size);
+        if hide && !lod_hidden && *visibility != Visibility::Hidden {
+            *visibility = Visibility::Hidden;
+            commands.entity(entity).insert(ObjectLodHidden);
+        } else if !hide && lod_hidden {
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<ObjectLodHidden>();
+        }
+    }
+}
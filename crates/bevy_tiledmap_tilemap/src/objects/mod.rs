@@ -1,11 +1,32 @@
 //! Object rendering for Tiled objects.
 
+#[cfg(feature = "animations")]
+pub mod animations;
+pub mod lod;
 pub mod tile_objects;
 
+#[cfg(feature = "text")]
+pub mod text_objects;
+
+#[cfg(feature = "polygon_fill")]
+pub mod polygon_fill;
+
 #[cfg(feature = "debug_shapes")]
 pub mod debug_shapes;
 
-pub use tile_objects::on_tile_object_spawned;
+#[cfg(feature = "animations")]
+pub use animations::{
+    on_animated_tile_object_spawned, update_object_animations, ObjectAnimation,
+    ObjectAnimationClip, ObjectAnimationFinished,
+};
+pub use lod::{apply_object_lod, ObjectLodHidden};
+pub use tile_objects::{on_tile_object_spawned, SpriteStyle};
+
+#[cfg(feature = "text")]
+pub use text_objects::on_text_object_spawned;
+
+#[cfg(feature = "polygon_fill")]
+pub use polygon_fill::on_polygon_object_spawned;
 
 #[cfg(feature = "debug_shapes")]
 pub use debug_shapes::render_object_shapes;
@@ -0,0 +1,160 @@
+//! Filled mesh rendering for polygon objects.
+//!
+//! Polygon objects with a `fill_color` color property (set on the object or as a
+//! default of its class) are drawn as a filled 2D mesh, e.g. for water volumes or
+//! shadow regions drawn directly in Tiled. Concave polygons are supported, in
+//! either winding order.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::prelude::*;
+use bevy_tiledmap_core::components::object::TiledObject;
+use bevy_tiledmap_core::events::ObjectSpawned;
+use bevy_tiledmap_core::properties::{PropertyKeyConfig, TiledColor};
+use tiled::PropertyValue;
+
+/// Object property holding a polygon's fill color.
+pub const FILL_COLOR_PROPERTY: &str = "fill_color";
+
+/// Observer that renders polygon objects with a `fill_color` as filled meshes.
+///
+/// The mesh is inserted on the object entity itself, so it follows the object's
+/// transform. Self-intersecting polygons are filled as far as triangulation gets.
+pub fn on_polygon_object_spawned(
+    trigger: On<ObjectSpawned>,
+    object_query: Query<&TiledObject>,
+    keys: Res<PropertyKeyConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+
+    let Some(PropertyValue::ColorValue(color)) = keys.get(&event.properties, FILL_COLOR_PROPERTY)
+    else {
+        return;
+    };
+    let Ok(TiledObject::Polygon { vertices }) = object_query.get(event.entity) else {
+        return;
+    };
+
+    let indices = triangulate_polygon(vertices);
+    if indices.is_empty() {
+        warn!(
+            "Could not triangulate polygon object {:?} for filling",
+            event.object_id
+        );
+        return;
+    }
+
+    let color = TiledColor(*color).to_color();
+    commands.entity(event.entity).insert((
+        Mesh2d(meshes.add(polygon_mesh(vertices, indices))),
+        MeshMaterial2d(materials.add(color)),
+    ));
+}
+
+/// Build a flat mesh from polygon vertices and triangle indices.
+///
+/// UVs span the polygon's bounding box.
+fn polygon_mesh(vertices: &[Vec2], indices: Vec<u32>) -> Mesh {
+    let min = vertices.iter().copied().fold(Vec2::MAX, Vec2::min);
+    let max = vertices.iter().copied().fold(Vec2::MIN, Vec2::max);
+    let size = (max - min).max(Vec2::splat(f32::EPSILON));
+
+    let positions: Vec<[f32; 3]> = vertices.iter().map(|v| [v.x, v.y, 0.0]).collect();
+    let normals = vec![[0.0, 0.0, 1.0]; vertices.len()];
+    // Bevy Y is up, texture V is down
+    let uvs: Vec<[f32; 2]> = vertices
+        .iter()
+        .map(|v| {
+            let uv = (*v - min) / size;
+            [uv.x, 1.0 - uv.y]
+        })
+        .collect();
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+/// Triangulate a simple (possibly concave) polygon by ear clipping.
+///
+/// Accepts either winding order and returns counter-clockwise triangles as a
+/// flat index list into `vertices`. Returns an empty list for polygons with
+/// fewer than three vertices or no area.
+pub fn triangulate_polygon(vertices: &[Vec2]) -> Vec<u32> {
+    if vertices.len() < 3 {
+        return Vec::new();
+    }
+
+    // Shoelace formula: positive for counter-clockwise polygons
+    let area: f32 = (0..vertices.len())
+        .map(|i| vertices[i].perp_dot(vertices[(i + 1) % vertices.len()]))
+        .sum();
+    if area.abs() <= f32::EPSILON {
+        return Vec::new();
+    }
+    let mut remaining: Vec<u32> = if area > 0.0 {
+        (0..vertices.len() as u32).collect()
+    } else {
+        (0..vertices.len() as u32).rev().collect()
+    };
+
+    let mut indices = Vec::with_capacity((vertices.len() - 2) * 3);
+    let mut i = 0;
+    let mut attempts = 0;
+    while remaining.len() > 3 {
+        let len = remaining.len();
+        let prev = remaining[(i + len - 1) % len];
+        let curr = remaining[i];
+        let next = remaining[(i + 1) % len];
+
+        if is_ear(vertices, &remaining, prev, curr, next) {
+            indices.extend([prev, curr, next]);
+            remaining.remove(i);
+            i %= remaining.len();
+            attempts = 0;
+        } else {
+            i = (i + 1) % len;
+            attempts += 1;
+            // No ear left: the polygon intersects itself
+            if attempts > len {
+                break;
+            }
+        }
+    }
+    if remaining.len() == 3 {
+        indices.extend(remaining);
+    }
+    indices
+}
+
+/// Check whether the corner `curr` of a counter-clockwise polygon can be clipped.
+fn is_ear(vertices: &[Vec2], remaining: &[u32], prev: u32, curr: u32, next: u32) -> bool {
+    let (a, b, c) = (
+        vertices[prev as usize],
+        vertices[curr as usize],
+        vertices[next as usize],
+    );
+    // Reflex (or collinear) corners are never ears
+    if (b - a).perp_dot(c - b) <= 0.0 {
+        return false;
+    }
+    !remaining
+        .iter()
+        .filter(|&&index| index != prev && index != curr && index != next)
+        .any(|&index| point_in_triangle(vertices[index as usize], a, b, c))
+}
+
+/// Check whether `p` lies inside or on the edge of the counter-clockwise triangle `abc`.
+fn point_in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    (b - a).perp_dot(p - a) >= 0.0
+        && (c - b).perp_dot(p - b) >= 0.0
+        && (a - c).perp_dot(p - c) >= 0.0
+}
@@ -0,0 +1,54 @@
+//! `Text2d` rendering for text objects.
+
+use bevy::prelude::*;
+use bevy::text::TextBounds;
+use bevy_tiledmap_core::components::object::{TextHorizontalAlign, TiledObject};
+use bevy_tiledmap_core::events::ObjectSpawned;
+
+/// Observer that renders text objects as `Text2d`.
+///
+/// The text (already localized by core) is laid out inside the object's text box,
+/// with the object's font size, color, horizontal alignment and wrapping. Font
+/// family, styles and vertical alignment are not applied yet; Bevy's default
+/// font is used.
+pub fn on_text_object_spawned(
+    trigger: On<ObjectSpawned>,
+    object_query: Query<&TiledObject>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+
+    let Ok(TiledObject::Text {
+        text,
+        pixel_size,
+        color,
+        width,
+        height,
+        wrap,
+        halign,
+        ..
+    }) = object_query.get(event.entity)
+    else {
+        return;
+    };
+
+    let justify = match halign {
+        TextHorizontalAlign::Center => Justify::Center,
+        TextHorizontalAlign::Right => Justify::Right,
+        TextHorizontalAlign::Justify => Justify::Justified,
+        TextHorizontalAlign::Left => Justify::Left,
+    };
+    let linebreak = if *wrap {
+        LineBreak::WordBoundary
+    } else {
+        LineBreak::NoWrap
+    };
+
+    commands.entity(event.entity).insert((
+        Text2d::new(text.clone()),
+        TextFont::from_font_size(*pixel_size),
+        TextColor(*color),
+        TextBounds::new(*width, *height),
+        TextLayout::new(justify, linebreak),
+    ));
+}
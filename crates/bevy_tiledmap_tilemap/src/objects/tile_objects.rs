@@ -1,21 +1,99 @@
 //! Sprite rendering for tile objects.
+//!
+//! Per-instance looks can be authored on the object (or as defaults of its
+//! class) with the `tint` color, `flip_x`/`flip_y` bool and `opacity` float
+//! properties. Flip properties toggle the flips set in Tiled, and `opacity`
+//! multiplies the tint's alpha.
 
 use bevy::prelude::*;
 use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
 use bevy_tiledmap_core::components::object::TiledObject;
 use bevy_tiledmap_core::events::ObjectSpawned;
+use bevy_tiledmap_core::properties::{PropertyKeyConfig, TiledColor};
+use tiled::{Properties, PropertyValue};
+
+/// Object property tinting a tile object's sprite.
+pub const TINT_PROPERTY: &str = "tint";
+
+/// Object property flipping a tile object's sprite horizontally.
+pub const FLIP_X_PROPERTY: &str = "flip_x";
+
+/// Object property flipping a tile object's sprite vertically.
+pub const FLIP_Y_PROPERTY: &str = "flip_y";
+
+/// Object property setting a tile object's sprite opacity (0.0 to 1.0).
+pub const OPACITY_PROPERTY: &str = "opacity";
+
+/// Sprite color and flips authored with object properties.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpriteStyle {
+    /// Sprite color: the `tint` (white by default) with `opacity` applied
+    pub color: Color,
+    /// Whether `flip_x` toggles the horizontal flip
+    pub flip_x: bool,
+    /// Whether `flip_y` toggles the vertical flip
+    pub flip_y: bool,
+}
+
+impl SpriteStyle {
+    /// Read the style properties, warning about values of the wrong type.
+    pub fn from_properties(properties: &Properties, keys: &PropertyKeyConfig) -> Self {
+        let mut color = match keys.get(properties, TINT_PROPERTY) {
+            Some(PropertyValue::ColorValue(color)) => TiledColor(*color).to_color(),
+            Some(other) => {
+                warn!(
+                    "{} property should be a color, got {:?}",
+                    TINT_PROPERTY, other
+                );
+                Color::WHITE
+            }
+            None => Color::WHITE,
+        };
+        match keys.get(properties, OPACITY_PROPERTY) {
+            Some(PropertyValue::FloatValue(opacity)) => {
+                color.set_alpha(color.alpha() * opacity.clamp(0.0, 1.0));
+            }
+            Some(PropertyValue::IntValue(opacity)) => {
+                color.set_alpha(color.alpha() * (*opacity as f32).clamp(0.0, 1.0));
+            }
+            Some(other) => {
+                warn!(
+                    "{} property should be a float, got {:?}",
+                    OPACITY_PROPERTY, other
+                );
+            }
+            None => {}
+        }
+        let flag = |name: &str| match keys.get(properties, name) {
+            Some(PropertyValue::BoolValue(value)) => *value,
+            Some(other) => {
+                warn!("{} property should be a bool, got {:?}", name, other);
+                false
+            }
+            None => false,
+        };
+        Self {
+            color,
+            flip_x: flag(FLIP_X_PROPERTY),
+            flip_y: flag(FLIP_Y_PROPERTY),
+        }
+    }
+}
 
 /// Observer that renders tile objects as sprites.
 ///
 /// When an object with a Tile variant is spawned, this observer:
 /// 1. Extracts the texture from the tileset
 /// 2. Calculates the texture atlas rectangle (for atlas tilesets)
-/// 3. Spawns a Sprite component with the correct texture and size
+/// 3. Spawns a Sprite component with the correct texture, size and flips
+///    (diagonal flips are not supported by sprites and are ignored), styled by
+///    the object's [`SpriteStyle`] properties
 pub fn on_tile_object_spawned(
     trigger: On<ObjectSpawned>,
     object_query: Query<&TiledObject>,
     mut transform_query: Query<&mut Transform>,
     tileset_assets: Res<Assets<TiledTilesetAsset>>,
+    keys: Res<PropertyKeyConfig>,
     mut commands: Commands,
 ) {
     let event = trigger.event();
@@ -30,6 +108,9 @@ pub fn on_tile_object_spawned(
         tileset_handle,
         width,
         height,
+        flip_h,
+        flip_v,
+        ..
     } = object
     else {
         return;
@@ -59,12 +140,18 @@ pub fn on_tile_object_spawned(
         transform.scale = scale.extend(1.0);
     }
 
+    let style = SpriteStyle::from_properties(&event.properties, &keys);
+    let sprite = Sprite {
+        image: image_handle.clone(),
+        color: style.color,
+        flip_x: *flip_h != style.flip_x,
+        flip_y: *flip_v != style.flip_y,
+        ..default()
+    };
+
     // For image collection tilesets, use the tile's individual image
     if tileset.is_image_collection() {
-        commands.entity(event.entity).insert(Sprite {
-            image: image_handle.clone(),
-            ..default()
-        });
+        commands.entity(event.entity).insert(sprite);
 
         info!(
             "Created sprite for image collection tile object {:?}",
@@ -77,9 +164,8 @@ pub fn on_tile_object_spawned(
     let texture_rect = calculate_tile_rect(tileset, *tile_id);
 
     commands.entity(event.entity).insert(Sprite {
-        image: image_handle.clone(),
         rect: Some(texture_rect),
-        ..default()
+        ..sprite
     });
 
     info!(
@@ -91,7 +177,7 @@ pub fn on_tile_object_spawned(
 /// Calculate the texture rectangle for a tile in a texture atlas.
 ///
 /// Takes into account margin, spacing, and grid layout.
-fn calculate_tile_rect(tileset: &TiledTilesetAsset, tile_id: u32) -> Rect {
+pub(crate) fn calculate_tile_rect(tileset: &TiledTilesetAsset, tile_id: u32) -> Rect {
     let columns = tileset.grid_size.x;
     let tile_width = tileset.tile_size.x as f32;
     let tile_height = tileset.tile_size.y as f32;
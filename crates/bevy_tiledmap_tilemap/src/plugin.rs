@@ -2,9 +2,11 @@
 
 use bevy::prelude::*;
 
+use bevy_tiledmap_core::render_backend::RenderBackendAppExt;
+
+use crate::backend::EcsTilemapBackend;
 use crate::config::TilemapRenderConfig;
-use crate::features::{z_ordering, ZOrderConfig};
-use crate::images;
+use crate::features::{z_ordering, ObjectSortZ, ZOrderConfig};
 use crate::objects;
 use crate::tiles;
 
@@ -14,10 +16,22 @@ use crate::features::AnimationSpeed;
 #[cfg(feature = "parallax")]
 use crate::features::parallax;
 
+#[cfg(feature = "palette")]
+use crate::features::palette;
+
+/// System set containing the tilemap plugin's per-frame rendering systems.
+///
+/// Used in several schedules: tile animations, parallax and debug shapes run in
+/// `Update`, tile object sorting in `PostUpdate` (before transform propagation).
+/// Tilemaps and sprites themselves are built by [`EcsTilemapBackend`] during
+/// `TiledSpawnSystems`.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TiledRenderSystems;
+
 /// Plugin for rendering Tiled maps with `bevy_ecs_tilemap`.
 ///
-/// This Layer 3 plugin observes events from `bevy_tiledmap_core` and adds
-/// rendering components to entities.
+/// This Layer 3 plugin registers [`EcsTilemapBackend`] as a render backend
+/// and adds rendering components to entities.
 ///
 /// # Example
 ///
@@ -53,39 +67,128 @@ impl Plugin for TilemapPlugin {
         // Insert z-order config
         app.init_resource::<ZOrderConfig>();
 
-        // Register tile layer rendering observer
-        app.add_observer(tiles::render::on_tile_layer_spawned);
+        // Render tile and image layers
+        app.add_render_backend(EcsTilemapBackend);
 
         // Register object rendering observer
         app.add_observer(objects::on_tile_object_spawned);
-
-        // Register image layer rendering observer
-        app.add_observer(images::on_image_layer_spawned);
+        #[cfg(feature = "text")]
+        app.add_observer(objects::on_text_object_spawned);
+        #[cfg(feature = "polygon_fill")]
+        if self.config.enable_polygon_fill {
+            app.add_observer(objects::on_polygon_object_spawned);
+        }
 
         // Z-ordering is now handled by Layer 2 (bevy_tiledmap_core) which assigns
         // sequential Z values based on layer order within and across maps.
         // The ZOrderConfig resource is still used by core for configuration.
         let _ = z_ordering::set_tile_layer_z_order; // Suppress unused warning
 
-        // Add animation systems if enabled
+        // Stable draw order for overlapping tile objects within a layer
+        app.register_type::<ObjectSortZ>();
+        app.add_systems(
+            PostUpdate,
+            z_ordering::sort_tile_objects
+                .in_set(TiledRenderSystems)
+                .before(TransformSystems::Propagate),
+        );
+
+        // Add animation systems (they check `enable_animations` every frame, so
+        // animations can be turned off and on at runtime)
         #[cfg(feature = "animations")]
-        if self.config.enable_animations {
+        {
             app.init_resource::<AnimationSpeed>();
-            app.add_systems(Update, tiles::update_tile_animations);
+            app.init_resource::<tiles::AnimationClock>();
+            app.register_type::<tiles::TileAnimationSync>();
+            app.add_systems(
+                Update,
+                tiles::update_tile_animations.in_set(TiledRenderSystems),
+            );
+
+            // Per-object playback for animated tile objects
+            app.add_observer(objects::on_animated_tile_object_spawned);
+            app.add_systems(
+                Update,
+                objects::update_object_animations.in_set(TiledRenderSystems),
+            );
         }
 
         // Add debug shape rendering if enabled
         #[cfg(feature = "debug_shapes")]
         if self.config.enable_debug_shapes {
-            app.add_systems(Update, objects::render_object_shapes);
+            app.add_systems(
+                Update,
+                objects::render_object_shapes.in_set(TiledRenderSystems),
+            );
         }
 
+        // Hide small tile objects when zoomed out (see `TilemapRenderConfig::object_lod`)
+        app.add_systems(Update, objects::apply_object_lod.in_set(TiledRenderSystems));
+
         // Add parallax scrolling if enabled
         #[cfg(feature = "parallax")]
         if self.config.enable_parallax {
             app.add_observer(parallax::add_parallax_to_tile_layer);
             app.add_observer(parallax::add_parallax_to_image_layer);
-            app.add_systems(Update, parallax::update_parallax_layers);
+            app.add_systems(
+                Update,
+                parallax::update_parallax_layers.in_set(TiledRenderSystems),
+            );
+        }
+
+        // Recolor tile layers that have a palette
+        #[cfg(feature = "palette")]
+        {
+            bevy::asset::embedded_asset!(app, "features/palette.wgsl");
+            app.add_plugins(bevy_ecs_tilemap::prelude::MaterialTilemapPlugin::<
+                palette::TiledPaletteMaterial,
+            >::default());
+            app.add_systems(
+                Update,
+                palette::apply_tiled_palettes.in_set(TiledRenderSystems),
+            );
+        }
+
+        // Lay maps out on a 3D plane
+        #[cfg(feature = "plane_3d")]
+        if self.config.plane_3d.is_some() {
+            app.add_observer(tiles::plane::on_map_spawned_plane);
+        }
+
+        // Per-pixel tile picking
+        #[cfg(feature = "picking")]
+        app.init_resource::<crate::features::TilePickingSettings>();
+
+        // Fog of war overlays over maps with fog enabled
+        #[cfg(feature = "fog_of_war")]
+        {
+            use crate::features::fog_of_war;
+            bevy::asset::embedded_asset!(app, "features/fog_of_war.wgsl");
+            app.add_plugins(bevy::sprite_render::Material2dPlugin::<
+                fog_of_war::FogOfWarMaterial,
+            >::default());
+            app.init_resource::<fog_of_war::FogOfWar>();
+            app.init_resource::<fog_of_war::FogOfWarSettings>();
+            app.add_systems(
+                PostUpdate,
+                fog_of_war::sync_fog_of_war.in_set(TiledRenderSystems),
+            );
+        }
+
+        // Map footprint panel, shown while `FootprintOverlay` exists
+        #[cfg(feature = "footprint_overlay")]
+        {
+            use crate::features::footprint_overlay;
+            app.add_systems(
+                Update,
+                (
+                    footprint_overlay::update_footprint_overlay
+                        .run_if(resource_exists::<footprint_overlay::FootprintOverlay>),
+                    footprint_overlay::remove_footprint_overlay
+                        .run_if(resource_removed::<footprint_overlay::FootprintOverlay>),
+                )
+                    .in_set(TiledRenderSystems),
+            );
         }
 
         info!("TilemapPlugin initialized");
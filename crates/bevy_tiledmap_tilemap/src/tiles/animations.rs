@@ -1,10 +1,138 @@
 //! Tile animation component and update system.
+//!
+//! All animated tiles follow the global [`AnimationClock`], so tiles showing the
+//! same animation stay in sync no matter when they were spawned. Tiles can get a
+//! random phase instead through the `animation_sync` property (see
+//! [`TileAnimationSync`]).
+//!
+//! Tiles can also play their animation just once, from the moment they spawn,
+//! through the `animation_playback` tile property (see
+//! [`TileAnimationPlayback`]). A [`TileAnimationFinished`] event is triggered on
+//! the tile layer when a one-shot animation ends, so gameplay can chain logic
+//! (e.g. a chest opening animation authored in the tileset).
 
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
+use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+use bevy_tiledmap_core::properties::PropertyKeyConfig;
+use tiled::{Properties, PropertyValue};
 
+use crate::config::TilemapRenderConfig;
 use crate::features::{AnimationSpeed, AnimationsPaused};
 
+/// Tileset or tile layer property selecting the [`TileAnimationSync`] mode
+/// (`"synced"` or `"random"`).
+pub const ANIMATION_SYNC_PROPERTY: &str = "animation_sync";
+
+/// How animated tiles are timed relative to each other.
+///
+/// Chosen per tile layer and tileset with the `animation_sync` property; a
+/// layer's setting overrides its tilesets'. Inserted on tile layer entities that
+/// set the property.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum TileAnimationSync {
+    /// Every tile with the same animation shows the same frame.
+    #[default]
+    Synced,
+    /// Each tile starts at a random point of its animation, so large fields of
+    /// the same tile (grass, water) don't pulse in unison. The phase is derived
+    /// from the tile's position, so it survives layer rebuilds.
+    RandomPhase,
+}
+
+impl TileAnimationSync {
+    /// Read the `animation_sync` property, warning about unknown values.
+    pub fn from_properties(properties: &Properties, keys: &PropertyKeyConfig) -> Option<Self> {
+        let PropertyValue::StringValue(value) = keys.get(properties, ANIMATION_SYNC_PROPERTY)?
+        else {
+            return None;
+        };
+        match value.as_str() {
+            "synced" => Some(Self::Synced),
+            "random" => Some(Self::RandomPhase),
+            other => {
+                warn!(
+                    "Unknown {} value '{}', expected \"synced\" or \"random\"",
+                    ANIMATION_SYNC_PROPERTY, other
+                );
+                None
+            }
+        }
+    }
+
+    /// Resolve the mode for tiles of `tileset` in a layer with `layer_sync`.
+    pub fn resolve(
+        layer_sync: Option<Self>,
+        tileset: &TiledTilesetAsset,
+        keys: &PropertyKeyConfig,
+    ) -> Self {
+        layer_sync
+            .or_else(|| Self::from_properties(&tileset.tileset.properties, keys))
+            .unwrap_or_default()
+    }
+}
+
+/// Tileset tile property selecting the [`TileAnimationPlayback`] mode
+/// (`"loop"`, `"once"` or `"once_revert"`).
+pub const ANIMATION_PLAYBACK_PROPERTY: &str = "animation_playback";
+
+/// Whether a tile animation loops or plays once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum TileAnimationPlayback {
+    /// Loop forever, following the animation clock.
+    #[default]
+    Loop,
+    /// Play once from when the tile spawns, then hold the last frame.
+    Once,
+    /// Play once from when the tile spawns, then go back to the first frame.
+    OnceRevert,
+}
+
+impl TileAnimationPlayback {
+    /// Read the `animation_playback` property, warning about unknown values.
+    pub fn from_properties(properties: &Properties, keys: &PropertyKeyConfig) -> Self {
+        let Some(PropertyValue::StringValue(value)) =
+            keys.get(properties, ANIMATION_PLAYBACK_PROPERTY)
+        else {
+            return Self::Loop;
+        };
+        match value.as_str() {
+            "loop" => Self::Loop,
+            "once" => Self::Once,
+            "once_revert" => Self::OnceRevert,
+            other => {
+                warn!(
+                    "Unknown {} value '{}', expected \"loop\", \"once\" or \"once_revert\"",
+                    ANIMATION_PLAYBACK_PROPERTY, other
+                );
+                Self::Loop
+            }
+        }
+    }
+}
+
+/// Fired on a tile layer entity when a one-shot tile animation finishes.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct TileAnimationFinished {
+    /// The tile layer entity
+    #[event_target]
+    pub entity: Entity,
+    /// The rendered tile entity whose animation finished
+    pub tile: Entity,
+    /// Grid position of the tile (Tiled coordinates, Y-down)
+    pub position: UVec2,
+}
+
+/// Global clock driving all tile animations.
+///
+/// Advances with [`AnimationSpeed`] and stops while [`AnimationsPaused`] is present.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AnimationClock {
+    /// Animation time elapsed since startup (milliseconds).
+    pub elapsed_ms: f64,
+}
+
 /// Component attached to animated tiles.
 ///
 /// Contains the animation sequence and current playback state.
@@ -16,6 +144,14 @@ pub struct TileAnimation {
     pub current_frame: usize,
     /// Time elapsed in current frame (milliseconds).
     pub elapsed_ms: f32,
+    /// Offset of this tile into the animation cycle (milliseconds).
+    pub phase_ms: f32,
+    /// Whether the animation loops or plays once.
+    pub playback: TileAnimationPlayback,
+    /// Clock time a one-shot animation started at (set on its first update).
+    pub started_ms: Option<f64>,
+    /// Whether a one-shot animation has finished.
+    pub finished: bool,
 }
 
 impl TileAnimation {
@@ -25,6 +161,92 @@ impl TileAnimation {
             frames,
             current_frame: 0,
             elapsed_ms: 0.0,
+            phase_ms: 0.0,
+            playback: TileAnimationPlayback::Loop,
+            started_ms: None,
+            finished: false,
+        }
+    }
+
+    /// Set whether the animation loops or plays once.
+    pub fn with_playback(mut self, playback: TileAnimationPlayback) -> Self {
+        self.playback = playback;
+        self
+    }
+
+    /// Play a one-shot animation again from the start.
+    pub fn restart(&mut self) {
+        self.started_ms = None;
+        self.finished = false;
+    }
+
+    /// Set the phase for the tile at `position` according to `sync`.
+    pub fn with_sync(mut self, sync: TileAnimationSync, position: UVec2) -> Self {
+        self.phase_ms = match sync {
+            TileAnimationSync::Synced => 0.0,
+            TileAnimationSync::RandomPhase => {
+                // SplitMix64 finalizer over the position
+                let mut hash = (u64::from(position.x) << 32) | u64::from(position.y);
+                hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                hash ^= hash >> 31;
+                (hash >> 40) as f32 / (1u64 << 24) as f32 * self.cycle_duration_ms()
+            }
+        };
+        self
+    }
+
+    /// Get the duration of one full animation cycle in milliseconds.
+    pub fn cycle_duration_ms(&self) -> f32 {
+        self.frames
+            .iter()
+            .map(|frame| frame.duration_ms as f32)
+            .sum()
+    }
+
+    /// Move to the frame shown at `time_ms` on the animation clock.
+    pub fn seek(&mut self, time_ms: f64) {
+        self.show_cycle_time(time_ms + f64::from(self.phase_ms));
+    }
+
+    /// Advance a one-shot animation to `time_ms` on the animation clock.
+    ///
+    /// Returns `true` once, when the animation finishes.
+    pub fn play_once(&mut self, time_ms: f64) -> bool {
+        if self.finished {
+            return false;
+        }
+        let started_ms = *self.started_ms.get_or_insert(time_ms);
+        let time_ms = time_ms - started_ms;
+        if time_ms < f64::from(self.cycle_duration_ms()) {
+            self.show_cycle_time(time_ms);
+            return false;
+        }
+
+        self.finished = true;
+        self.elapsed_ms = 0.0;
+        self.current_frame = match self.playback {
+            TileAnimationPlayback::OnceRevert => 0,
+            _ => self.frames.len() - 1,
+        };
+        true
+    }
+
+    /// Move to the frame shown `time_ms` into the animation cycle.
+    fn show_cycle_time(&mut self, time_ms: f64) {
+        let cycle = f64::from(self.cycle_duration_ms());
+        if cycle <= 0.0 {
+            return;
+        }
+        let mut remaining = (time_ms % cycle) as f32;
+        for (index, frame) in self.frames.iter().enumerate() {
+            let duration = frame.duration_ms as f32;
+            if remaining < duration || index == self.frames.len() - 1 {
+                self.current_frame = index;
+                self.elapsed_ms = remaining;
+                return;
+            }
+            remaining -= duration;
         }
     }
 
@@ -56,27 +278,77 @@ pub struct AnimationFrame {
 
 /// System that updates all animated tiles.
 ///
-/// Advances animation frames based on elapsed time and updates `TileTextureIndex`.
+/// Advances the [`AnimationClock`] and shows each tile's frame for the current
+/// clock time in its `TileTextureIndex`. Triggers [`TileAnimationFinished`] for
+/// one-shot animations that end.
+///
+/// Does nothing while [`TilemapRenderConfig::enable_animations`] is off, and
+/// updates at most [`TilemapRenderConfig::max_animated_tiles_per_frame`] tiles,
+/// continuing with the next ones on the following frame.
 pub fn update_tile_animations(
     time: Res<Time>,
     speed: Res<AnimationSpeed>,
     paused: Option<Res<AnimationsPaused>>,
-    mut animated_tiles: Query<(&mut TileAnimation, &mut TileTextureIndex)>,
+    config: Res<TilemapRenderConfig>,
+    mut clock: ResMut<AnimationClock>,
+    mut next_tile: Local<usize>,
+    mut animated_tiles: Query<(
+        Entity,
+        &mut TileAnimation,
+        &mut TileTextureIndex,
+        &TilePos,
+        &TilemapId,
+    )>,
+    tilemap_query: Query<(&TilemapSize, &ChildOf)>,
+    mut commands: Commands,
 ) {
-    // Skip if animations are paused
-    if paused.is_some() {
+    // Skip if animations are paused or disabled
+    if paused.is_some() || !config.enable_animations {
         return;
     }
 
-    let delta_ms = time.delta_secs() * 1000.0 * speed.0;
+    clock.elapsed_ms += f64::from(time.delta_secs() * 1000.0 * speed.0);
 
-    for (mut animation, mut texture_index) in &mut animated_tiles {
-        animation.elapsed_ms += delta_ms;
+    // Window of tiles updated this frame, wrapping around the end
+    let count = animated_tiles.iter().len();
+    let budget = config
+        .max_animated_tiles_per_frame
+        .unwrap_or(count)
+        .min(count);
+    let start = if budget < count {
+        *next_tile % count
+    } else {
+        0
+    };
+    *next_tile = start + budget;
 
-        // Advance frames as needed
-        while animation.elapsed_ms >= animation.current_duration_ms() {
-            animation.next_frame();
-            texture_index.0 = animation.current_tile_id();
+    for (index, (tile, mut animation, mut texture_index, tile_pos, tilemap_id)) in
+        animated_tiles.iter_mut().enumerate()
+    {
+        if (index + count - start) % count >= budget {
+            continue;
+        }
+        let finished = match animation.playback {
+            TileAnimationPlayback::Loop => {
+                animation.seek(clock.elapsed_ms);
+                false
+            }
+            TileAnimationPlayback::Once | TileAnimationPlayback::OnceRevert => {
+                animation.play_once(clock.elapsed_ms)
+            }
+        };
+        texture_index.set_if_neq(TileTextureIndex(animation.current_tile_id()));
+
+        if !finished {
+            continue;
+        }
+        if let Ok((size, layer)) = tilemap_query.get(tilemap_id.0) {
+            // Tilemaps are Y-up, Tiled positions Y-down
+            commands.trigger(TileAnimationFinished {
+                entity: layer.parent(),
+                tile,
+                position: UVec2::new(tile_pos.x, size.y - 1 - tile_pos.y),
+            });
         }
     }
 }
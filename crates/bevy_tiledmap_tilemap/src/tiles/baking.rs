@@ -0,0 +1,78 @@
+//! Static tile layer baking.
+//!
+//! A baked tile layer is drawn as one mesh per atlas tileset (quad positions and
+//! UVs into the tileset texture) instead of a `bevy_ecs_tilemap` tilemap with an
+//! entity per tile. This removes the per-tile entity overhead of very large
+//! static backgrounds.
+//!
+//! Enable it for every tile layer with
+//! [`TilemapRenderConfig::bake_static_layers`](crate::config::TilemapRenderConfig::bake_static_layers),
+//! or per layer with a bool property `bake_static` (which also overrides the
+//! config). Baked layers are still rebuilt when their tiles change, but:
+//! - animated tiles show their base tile,
+//! - palettes ([`TiledPalette`](crate::features::TiledPalette)) don't apply,
+//! - image collection tilesets are still drawn as sprites.
+
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+use bevy_tiledmap_core::components::tile::TileInstance;
+use bevy_tiledmap_core::properties::PropertyKeyConfig;
+use tiled::{Properties, PropertyValue};
+
+use super::tilemap_builder::{tile_layer_mesh, TileLayerRender};
+use crate::config::TilemapRenderConfig;
+
+/// Tile layer property selecting whether the layer is baked into meshes.
+pub const BAKE_STATIC_PROPERTY: &str = "bake_static";
+
+/// Marker for tile layer entities rendered as baked meshes.
+///
+/// Inserted on layers that are baked when spawned, so rebuilds bake them again.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct BakedTileLayer;
+
+/// Check whether a tile layer should be baked, from its `bake_static` property
+/// and the render config.
+pub fn should_bake_layer(
+    properties: &Properties,
+    config: &TilemapRenderConfig,
+    keys: &PropertyKeyConfig,
+) -> bool {
+    match keys.get(properties, BAKE_STATIC_PROPERTY) {
+        Some(PropertyValue::BoolValue(bake)) => *bake,
+        _ => config.bake_static_layers,
+    }
+}
+
+/// Spawn the baked mesh of an atlas tileset's tiles as a child of the layer.
+pub fn bake_atlas_tiles(
+    commands: &mut Commands,
+    layer_entity: Entity,
+    tiles: &[(u32, u32, TileInstance)],
+    tileset: &TiledTilesetAsset,
+    height: u32,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) {
+    let (Some(atlas_image), Some(mesh)) = (
+        tileset.atlas_image.as_ref(),
+        tile_layer_mesh(tiles, tileset, height),
+    ) else {
+        warn!("Could not bake tiles of tileset '{}'", tileset.tileset.name);
+        return;
+    };
+
+    commands.spawn((
+        Mesh2d(meshes.add(mesh)),
+        MeshMaterial2d(materials.add(ColorMaterial::from(atlas_image.clone()))),
+        Transform::default(),
+        TileLayerRender,
+        ChildOf(layer_entity),
+    ));
+
+    info!(
+        "Baked {} tiles of tileset '{}' into one mesh",
+        tiles.len(),
+        tileset.tileset.name
+    );
+}
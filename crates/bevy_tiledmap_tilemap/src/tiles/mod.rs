@@ -1,9 +1,20 @@
 //! Tile layer rendering module.
 
 pub mod animations;
+#[cfg(feature = "static_bake")]
+pub mod baking;
+#[cfg(feature = "plane_3d")]
+pub mod plane;
 pub mod render;
 pub mod tilemap_builder;
 
-pub use animations::{update_tile_animations, AnimationFrame, TileAnimation};
-pub use render::on_tile_layer_spawned;
-pub use tilemap_builder::{TilemapBuilder, TilesetReference};
+pub use animations::{
+    update_tile_animations, AnimationClock, AnimationFrame, TileAnimation, TileAnimationFinished,
+    TileAnimationPlayback, TileAnimationSync,
+};
+#[cfg(feature = "static_bake")]
+pub use baking::{BakedTileLayer, BAKE_STATIC_PROPERTY};
+#[cfg(feature = "plane_3d")]
+pub use plane::PlaneTileLayer;
+pub use render::{on_tile_layer_spawned, on_tiles_modified};
+pub use tilemap_builder::{TileLayerRender, TilemapBuilder, TilesetReference};
@@ -0,0 +1,91 @@
+//! 3D plane rendering of tile layers.
+//!
+//! With [`TilemapRenderConfig::plane_3d`](crate::config::TilemapRenderConfig::plane_3d)
+//! set, tile layers are drawn as one textured 3D mesh per atlas tileset and the
+//! map's layers are laid out on the configured
+//! [`MapPlane`](crate::config::MapPlane), so Tiled-authored
+//! ground layers can sit under 3D models and be seen by a 3D camera.
+//!
+//! Only tile layers get 3D meshes. Objects and image layers keep their
+//! transforms on the plane (e.g. to spawn 3D models at spawn points), but their
+//! sprites are only drawn by 2D cameras. Like baked layers, 3D tile layers are
+//! rebuilt when their tiles change, but:
+//! - animated tiles show their base tile,
+//! - palettes don't apply,
+//! - image collection tilesets are skipped.
+
+use bevy::prelude::*;
+use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+use bevy_tiledmap_core::components::tile::TileInstance;
+use bevy_tiledmap_core::components::TiledLayerMapOf;
+use bevy_tiledmap_core::events::MapSpawned;
+
+use super::tilemap_builder::{tile_layer_mesh, TileLayerRender};
+use crate::config::TilemapRenderConfig;
+
+/// Marker for tile layer entities rendered as 3D meshes.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct PlaneTileLayer;
+
+/// Spawn the 3D mesh of an atlas tileset's tiles as a child of the layer.
+///
+/// Transparent pixels are cut out with an alpha mask, so stacked layers don't
+/// need sorting.
+pub fn plane_atlas_tiles(
+    commands: &mut Commands,
+    layer_entity: Entity,
+    tiles: &[(u32, u32, TileInstance)],
+    tileset: &TiledTilesetAsset,
+    height: u32,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    let (Some(atlas_image), Some(mesh)) = (
+        tileset.atlas_image.as_ref(),
+        tile_layer_mesh(tiles, tileset, height),
+    ) else {
+        warn!(
+            "Could not build 3D tiles of tileset '{}'",
+            tileset.tileset.name
+        );
+        return;
+    };
+
+    commands.spawn((
+        Mesh3d(meshes.add(mesh)),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color_texture: Some(atlas_image.clone()),
+            alpha_mode: AlphaMode::Mask(0.5),
+            perceptual_roughness: 1.0,
+            ..default()
+        })),
+        Transform::default(),
+        TileLayerRender,
+        ChildOf(layer_entity),
+    ));
+}
+
+/// Observer that lays a spawned map's layers out on the configured
+/// [`MapPlane`](crate::config::MapPlane).
+///
+/// Only the map's top-level layers are moved; nested layers and objects follow
+/// through the hierarchy. The map entity's own transform is left to the game.
+pub fn on_map_spawned_plane(
+    trigger: On<MapSpawned>,
+    config: Res<TilemapRenderConfig>,
+    children_query: Query<&Children>,
+    mut layer_query: Query<&mut Transform, With<TiledLayerMapOf>>,
+) {
+    let Some(plane) = config.plane_3d else {
+        return;
+    };
+    let Ok(children) = children_query.get(trigger.event().entity) else {
+        return;
+    };
+
+    for child in children {
+        if let Ok(mut transform) = layer_query.get_mut(*child) {
+            *transform = plane.transform().mul_transform(*transform);
+        }
+    }
+}
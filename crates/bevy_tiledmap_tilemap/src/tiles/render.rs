@@ -1,28 +1,43 @@
-//! Observer for tile layer spawning events.
+//! Tile layer rendering, run by [`EcsTilemapBackend`](crate::backend::EcsTilemapBackend).
 
 use bevy::prelude::*;
 use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
 use bevy_tiledmap_core::components::tile::TileLayerData;
-use bevy_tiledmap_core::events::TileLayerSpawned;
+use bevy_tiledmap_core::events::{TileLayerSpawned, TilesModified};
+use bevy_tiledmap_core::properties::PropertyKeyConfig;
 
-use super::tilemap_builder::TilemapBuilder;
+use super::animations::TileAnimationSync;
+#[cfg(feature = "static_bake")]
+use super::baking::{self, BakedTileLayer};
+#[cfg(feature = "plane_3d")]
+use super::plane::PlaneTileLayer;
+use super::tilemap_builder::{TileLayerRender, TilemapBuilder};
+#[cfg(any(feature = "static_bake", feature = "plane_3d"))]
+use crate::config::TilemapRenderConfig;
 
-/// Observer that renders tile layers when spawned by Layer 2.
+/// Renders a tile layer spawned by Layer 2.
 ///
 /// This is the main entry point for tile layer rendering. When Layer 2 spawns
-/// a tile layer entity with `TileLayerData`, this observer:
+/// a tile layer entity with `TileLayerData`, this system:
 /// 1. Reads the pre-processed tile data
 /// 2. Groups tiles by tileset
 /// 3. Creates `bevy_ecs_tilemap` structures
 /// 4. Spawns tilemap entities as children
+///
+/// With the `static_bake` feature, layers selected for baking are drawn as
+/// meshes instead (see `baking`). With the `plane_3d` feature and a configured
+/// plane, all tile layers are drawn as 3D meshes (see `plane`).
 pub fn on_tile_layer_spawned(
-    trigger: On<TileLayerSpawned>,
+    In(event): In<TileLayerSpawned>,
     layer_query: Query<&TileLayerData>,
     tileset_assets: Res<Assets<TiledTilesetAsset>>,
+    keys: Res<PropertyKeyConfig>,
+    #[cfg(any(feature = "static_bake", feature = "plane_3d"))] config: Res<TilemapRenderConfig>,
+    #[cfg(any(feature = "static_bake", feature = "plane_3d"))] mut meshes: ResMut<Assets<Mesh>>,
+    #[cfg(feature = "static_bake")] mut materials: ResMut<Assets<ColorMaterial>>,
+    #[cfg(feature = "plane_3d")] mut materials_3d: ResMut<Assets<StandardMaterial>>,
     mut commands: Commands,
 ) {
-    let event = trigger.event();
-
     let Ok(tile_data) = layer_query.get(event.entity) else {
         warn!(
             "TileLayerSpawned event for entity {:?} but no TileLayerData component found",
@@ -36,6 +51,123 @@ pub fn on_tile_layer_spawned(
         event.entity, tile_data.width, tile_data.height
     );
 
+    #[cfg(feature = "trace")]
+    let _span = info_span!("build_tilemap", layer = ?event.entity).entered();
+
+    #[cfg(feature = "plane_3d")]
+    if config.plane_3d.is_some() {
+        // Remember the layer is drawn in 3D for rebuilds
+        commands.entity(event.entity).insert(PlaneTileLayer);
+        TilemapBuilder::build_plane(
+            &mut commands,
+            event.entity,
+            tile_data,
+            &tileset_assets,
+            &mut meshes,
+            &mut materials_3d,
+        );
+        return;
+    }
+
+    #[cfg(feature = "static_bake")]
+    if baking::should_bake_layer(&event.properties, &config, &keys) {
+        // Remember the layer is baked for rebuilds
+        commands.entity(event.entity).insert(BakedTileLayer);
+        TilemapBuilder::build_baked(
+            &mut commands,
+            event.entity,
+            tile_data,
+            &tileset_assets,
+            &mut meshes,
+            &mut materials,
+        );
+        return;
+    }
+
+    // Remember the layer's animation sync mode for rebuilds
+    let layer_sync = TileAnimationSync::from_properties(&event.properties, &keys);
+    if let Some(sync) = layer_sync {
+        commands.entity(event.entity).insert(sync);
+    }
+
     // Build tilemap structures from tile data
-    TilemapBuilder::build(&mut commands, event.entity, tile_data, &tileset_assets);
+    TilemapBuilder::build(
+        &mut commands,
+        event.entity,
+        tile_data,
+        &tileset_assets,
+        layer_sync,
+        &keys,
+    );
+}
+
+/// Re-renders a tile layer when its tiles change at runtime.
+///
+/// Despawns the layer's tilemaps, tile sprites and baked meshes and rebuilds
+/// them from the current `TileLayerData`.
+pub fn on_tiles_modified(
+    In(event): In<TilesModified>,
+    layer_query: Query<(
+        &TileLayerData,
+        Option<&Children>,
+        Option<&TileAnimationSync>,
+    )>,
+    render_query: Query<(), With<TileLayerRender>>,
+    tileset_assets: Res<Assets<TiledTilesetAsset>>,
+    keys: Res<PropertyKeyConfig>,
+    #[cfg(feature = "static_bake")] baked_query: Query<(), With<BakedTileLayer>>,
+    #[cfg(feature = "plane_3d")] plane_query: Query<(), With<PlaneTileLayer>>,
+    #[cfg(any(feature = "static_bake", feature = "plane_3d"))] mut meshes: ResMut<Assets<Mesh>>,
+    #[cfg(feature = "static_bake")] mut materials: ResMut<Assets<ColorMaterial>>,
+    #[cfg(feature = "plane_3d")] mut materials_3d: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let layer_entity = event.entity;
+    let Ok((tile_data, children, layer_sync)) = layer_query.get(layer_entity) else {
+        return;
+    };
+
+    #[cfg(feature = "trace")]
+    let _span = info_span!("rebuild_tilemap", layer = ?layer_entity).entered();
+
+    for child in children.into_iter().flatten() {
+        if render_query.contains(*child) {
+            commands.entity(*child).despawn();
+        }
+    }
+
+    #[cfg(feature = "plane_3d")]
+    if plane_query.contains(layer_entity) {
+        TilemapBuilder::build_plane(
+            &mut commands,
+            layer_entity,
+            tile_data,
+            &tileset_assets,
+            &mut meshes,
+            &mut materials_3d,
+        );
+        return;
+    }
+
+    #[cfg(feature = "static_bake")]
+    if baked_query.contains(layer_entity) {
+        TilemapBuilder::build_baked(
+            &mut commands,
+            layer_entity,
+            tile_data,
+            &tileset_assets,
+            &mut meshes,
+            &mut materials,
+        );
+        return;
+    }
+
+    TilemapBuilder::build(
+        &mut commands,
+        layer_entity,
+        tile_data,
+        &tileset_assets,
+        layer_sync.copied(),
+        &keys,
+    );
 }
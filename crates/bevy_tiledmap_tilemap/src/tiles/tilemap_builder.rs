@@ -2,12 +2,17 @@
 
 use std::collections::HashMap;
 
+#[cfg(any(feature = "static_bake", feature = "plane_3d"))]
+use bevy::asset::RenderAssetUsages;
+#[cfg(any(feature = "static_bake", feature = "plane_3d"))]
+use bevy::mesh::{Indices, PrimitiveTopology};
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
-use bevy_tiledmap_assets::prelude::TiledTilesetAsset;
+use bevy_tiledmap_assets::prelude::{TiledTilesetAsset, MISSING_TEXTURE_COLOR};
 use bevy_tiledmap_core::components::tile::{TileInstance, TileLayerData};
+use bevy_tiledmap_core::properties::PropertyKeyConfig;
 
-use super::animations::{AnimationFrame, TileAnimation};
+use super::animations::{AnimationFrame, TileAnimation, TileAnimationPlayback, TileAnimationSync};
 
 /// Builds `bevy_ecs_tilemap` structures from Layer 2's `TileLayerData`.
 ///
@@ -27,11 +32,15 @@ impl TilemapBuilder {
     /// * `layer_entity` - The layer entity to attach tilemaps to
     /// * `tile_data` - Pre-processed tile data from Layer 2
     /// * `tileset_assets` - Access to tileset assets
+    /// * `layer_sync` - The layer's animation sync mode, overriding its tilesets'
+    /// * `keys` - Property names of the animation settings
     pub fn build(
         commands: &mut Commands,
         layer_entity: Entity,
         tile_data: &TileLayerData,
         tileset_assets: &Assets<TiledTilesetAsset>,
+        layer_sync: Option<TileAnimationSync>,
+        keys: &PropertyKeyConfig,
     ) {
         // Group tiles by tileset for multi-tileset support
         let tiles_by_tileset = Self::group_by_tileset(tile_data);
@@ -59,6 +68,93 @@ impl TilemapBuilder {
                 tileset_handle,
                 tile_data.width,
                 tile_data.height,
+                layer_sync,
+                keys,
+            );
+        }
+    }
+
+    /// Build baked meshes from tile layer data.
+    ///
+    /// Like [`build`](Self::build), but atlas tilesets are drawn as one mesh each
+    /// (see [`super::baking`]) instead of tilemaps.
+    #[cfg(feature = "static_bake")]
+    pub fn build_baked(
+        commands: &mut Commands,
+        layer_entity: Entity,
+        tile_data: &TileLayerData,
+        tileset_assets: &Assets<TiledTilesetAsset>,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<ColorMaterial>,
+    ) {
+        for (tileset_handle, tiles) in Self::group_by_tileset(tile_data) {
+            let Some(tileset) = tileset_assets.get(&tileset_handle) else {
+                warn!(
+                    "Tileset not loaded yet for handle {:?}, skipping",
+                    tileset_handle
+                );
+                continue;
+            };
+
+            if tileset.atlas_image.is_some() {
+                super::baking::bake_atlas_tiles(
+                    commands,
+                    layer_entity,
+                    &tiles,
+                    tileset,
+                    tile_data.height,
+                    meshes,
+                    materials,
+                );
+            } else {
+                Self::create_image_collection_tilemap(
+                    commands,
+                    layer_entity,
+                    tiles,
+                    tileset,
+                    tile_data.height,
+                );
+            }
+        }
+    }
+
+    /// Build 3D meshes from tile layer data.
+    ///
+    /// Like [`build_baked`](Self::build_baked), but the meshes are 3D (see
+    /// [`super::plane`]). Image collection tilesets are skipped.
+    #[cfg(feature = "plane_3d")]
+    pub fn build_plane(
+        commands: &mut Commands,
+        layer_entity: Entity,
+        tile_data: &TileLayerData,
+        tileset_assets: &Assets<TiledTilesetAsset>,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) {
+        for (tileset_handle, tiles) in Self::group_by_tileset(tile_data) {
+            let Some(tileset) = tileset_assets.get(&tileset_handle) else {
+                warn!(
+                    "Tileset not loaded yet for handle {:?}, skipping",
+                    tileset_handle
+                );
+                continue;
+            };
+
+            if tileset.atlas_image.is_none() {
+                warn!(
+                    "Image collection tileset '{}' can't be drawn on a 3D plane, skipping",
+                    tileset.tileset.name
+                );
+                continue;
+            }
+            super::plane::plane_atlas_tiles(
+                commands,
+                layer_entity,
+                &tiles,
+                tileset,
+                tile_data.height,
+                meshes,
+                materials,
             );
         }
     }
@@ -86,7 +182,11 @@ impl TilemapBuilder {
     ///
     /// Returns None if the tile is not animated.
     #[cfg(feature = "animations")]
-    fn get_tile_animation(tileset: &TiledTilesetAsset, tile_id: u32) -> Option<TileAnimation> {
+    fn get_tile_animation(
+        tileset: &TiledTilesetAsset,
+        tile_id: u32,
+        keys: &PropertyKeyConfig,
+    ) -> Option<TileAnimation> {
         // Find the tile in the tileset's tile data and extract animation
         tileset
             .tileset
@@ -102,7 +202,9 @@ impl TilemapBuilder {
                         })
                         .collect();
 
-                    TileAnimation::new(animation_frames)
+                    TileAnimation::new(animation_frames).with_playback(
+                        TileAnimationPlayback::from_properties(&tile.properties, keys),
+                    )
                 })
             })
     }
@@ -116,6 +218,8 @@ impl TilemapBuilder {
         tileset_handle: Handle<TiledTilesetAsset>,
         width: u32,
         height: u32,
+        layer_sync: Option<TileAnimationSync>,
+        keys: &PropertyKeyConfig,
     ) {
         // Check if this is an image collection or atlas tileset
         if tileset.atlas_image.is_some() {
@@ -128,6 +232,8 @@ impl TilemapBuilder {
                 tileset_handle,
                 width,
                 height,
+                layer_sync,
+                keys,
             );
         } else {
             // Use simple sprites for image collection tilesets
@@ -136,6 +242,9 @@ impl TilemapBuilder {
     }
 
     /// Create tilemap using simple sprites for image collection tilesets.
+    ///
+    /// Tiles without an image (missing files in lenient mode, or placeholder
+    /// tilesets) are drawn as [`MISSING_TEXTURE_COLOR`] squares.
     fn create_image_collection_tilemap(
         commands: &mut Commands,
         layer_entity: Entity,
@@ -144,28 +253,36 @@ impl TilemapBuilder {
         height: u32,
     ) {
         let tile_size = tileset.tile_size;
+        let render_offset = tileset.render_offset();
         let tile_count = tiles.len();
 
         for (x, y, tile_instance) in tiles {
             // Get the image handle for this specific tile
-            let Some(tile_image_handle) = tileset.tile_images.get(&tile_instance.tile_id) else {
-                warn!("Tile ID {} not found in tileset", tile_instance.tile_id);
-                continue;
-            };
+            let tile_image = tileset.tile_images.get(&tile_instance.render_tile_id());
+            if tile_image.is_none() {
+                warn!(
+                    "Tile ID {} has no image, drawing a placeholder",
+                    tile_instance.render_tile_id()
+                );
+            }
 
             // Calculate local position for this tile relative to the layer
             // Flip Y: Tiled y=0 is top, Bevy y=0 is bottom
             // Use positive Y coordinates to match MapGeometry bounds
+            // Shift by the tileset's drawing offset (tileoffset)
             let flipped_y = height - 1 - y;
-            let world_x = (x as f32 + 0.5) * tile_size.x as f32;
-            let world_y = (flipped_y as f32 + 0.5) * tile_size.y as f32;
+            let world_x = (x as f32 + 0.5) * tile_size.x as f32 + render_offset.x;
+            let world_y = (flipped_y as f32 + 0.5) * tile_size.y as f32 + render_offset.y;
 
             // Spawn a sprite for this tile
-            let mut sprite_bundle = Sprite {
-                image: tile_image_handle.clone(),
-                flip_x: tile_instance.flipped_h,
-                flip_y: tile_instance.flipped_v,
-                ..default()
+            let mut sprite_bundle = match tile_image {
+                Some(tile_image_handle) => Sprite {
+                    image: tile_image_handle.clone(),
+                    flip_x: tile_instance.flipped_h,
+                    flip_y: tile_instance.flipped_v,
+                    ..default()
+                },
+                None => Sprite::from_color(MISSING_TEXTURE_COLOR, tile_size.as_vec2()),
             };
 
             // Handle diagonal flip (requires rotation + flip)
@@ -177,7 +294,7 @@ impl TilemapBuilder {
             }
 
             commands.entity(layer_entity).with_children(|parent| {
-                parent.spawn((sprite_bundle, transform));
+                parent.spawn((sprite_bundle, transform, TileLayerRender));
             });
         }
 
@@ -196,6 +313,8 @@ impl TilemapBuilder {
         tileset_handle: Handle<TiledTilesetAsset>,
         width: u32,
         height: u32,
+        layer_sync: Option<TileAnimationSync>,
+        keys: &PropertyKeyConfig,
     ) {
         let Some(ref atlas_image) = tileset.atlas_image else {
             warn!("Expected atlas tileset but atlas_image is None");
@@ -229,6 +348,11 @@ impl TilemapBuilder {
         // Collect tile entities to parent them to the tilemap
         let mut tile_entities = Vec::with_capacity(tiles.len());
 
+        #[cfg(feature = "animations")]
+        let sync = TileAnimationSync::resolve(layer_sync, tileset, keys);
+        #[cfg(not(feature = "animations"))]
+        let _ = (layer_sync, keys);
+
         // Spawn individual tiles with correct TilemapId
         // Flip Y coordinate: Tiled has (0,0) at top-left, bevy_ecs_tilemap at bottom-left
         for (x, y, tile_instance) in tiles {
@@ -237,7 +361,7 @@ impl TilemapBuilder {
 
             let mut entity_commands = commands.spawn(TileBundle {
                 position: tile_pos,
-                texture_index: TileTextureIndex(tile_instance.tile_id),
+                texture_index: TileTextureIndex(tile_instance.render_tile_id()),
                 tilemap_id: TilemapId(tilemap_entity),
                 flip: TileFlip {
                     x: tile_instance.flipped_h,
@@ -249,8 +373,10 @@ impl TilemapBuilder {
 
             // Add animation if this tile is animated
             #[cfg(feature = "animations")]
-            if let Some(animation) = Self::get_tile_animation(tileset, tile_instance.tile_id) {
-                entity_commands.insert(animation);
+            if let Some(animation) =
+                Self::get_tile_animation(tileset, tile_instance.render_tile_id(), keys)
+            {
+                entity_commands.insert(animation.with_sync(sync, UVec2::new(x, y)));
             }
 
             let tile_entity = entity_commands.id();
@@ -263,8 +389,11 @@ impl TilemapBuilder {
 
         // Now insert the TilemapBundle with populated storage
         // Position tilemap so tiles render with positive Y matching MapGeometry bounds
-        // bevy_ecs_tilemap places TilePos y=0 at tilemap origin, so we offset by half tile
-        let tilemap_y = tile_size.y / 2.0;
+        // bevy_ecs_tilemap places TilePos y=0 at tilemap origin, so we offset by half tile,
+        // plus the tileset's drawing offset (tileoffset)
+        let render_offset = tileset.render_offset();
+        let tilemap_x = tile_size.x / 2.0 + render_offset.x;
+        let tilemap_y = tile_size.y / 2.0 + render_offset.y;
         let texture = TilemapTexture::Single(atlas_image.clone());
         commands.entity(tilemap_entity).insert((
             TilemapBundle {
@@ -274,16 +403,101 @@ impl TilemapBuilder {
                 texture,
                 tile_size,
                 map_type: TilemapType::Square,
-                transform: Transform::from_xyz(tile_size.x / 2.0, tilemap_y, 0.0),
+                transform: Transform::from_xyz(tilemap_x, tilemap_y, 0.0),
                 ..default()
             },
             TilesetReference(tileset_handle),
+            TileLayerRender,
         ));
 
         // info!("Created tilemap for tileset with {} tiles, tilemap_y={}, layer_pixel_height={}", tile_count, tilemap_y, layer_pixel_height);
     }
 }
 
+/// Get the texture position shown at `uv` of a tile's cell, accounting for flips.
+///
+/// Both are fractions of the tile from its top-left corner (Y-down). Tiled
+/// applies the diagonal flip first, then the horizontal and vertical flips, so
+/// the texture is sampled through them in reverse order.
+pub fn flipped_tile_uv(uv: Vec2, tile: &TileInstance) -> Vec2 {
+    flip_uv(uv, tile.flipped_h, tile.flipped_v, tile.flipped_d)
+}
+
+/// Like [`flipped_tile_uv`], from Tiled's flip flags.
+pub fn flip_uv(uv: Vec2, flip_h: bool, flip_v: bool, flip_d: bool) -> Vec2 {
+    let mut uv = uv;
+    if flip_v {
+        uv.y = 1.0 - uv.y;
+    }
+    if flip_h {
+        uv.x = 1.0 - uv.x;
+    }
+    if flip_d {
+        uv = uv.yx();
+    }
+    uv
+}
+
+/// Build a mesh with one textured quad per tile, in layer space.
+///
+/// Tile positions match the tilemap renderer: positive Y, tile `(0, 0)` at the
+/// top-left of the layer, shifted by the tileset's drawing offset. Returns
+/// `None` if the tileset has no atlas image.
+#[cfg(any(feature = "static_bake", feature = "plane_3d"))]
+pub fn tile_layer_mesh(
+    tiles: &[(u32, u32, TileInstance)],
+    tileset: &TiledTilesetAsset,
+    height: u32,
+) -> Option<Mesh> {
+    let image = tileset.tileset.image.as_ref()?;
+    let image_size = Vec2::new(image.width as f32, image.height as f32);
+    let columns = tileset.tileset.columns.max(1);
+    let tile_size = tileset.tile_size.as_vec2();
+    let render_offset = tileset.render_offset();
+
+    let mut positions = Vec::with_capacity(tiles.len() * 4);
+    let mut uvs = Vec::with_capacity(tiles.len() * 4);
+    let mut indices = Vec::with_capacity(tiles.len() * 6);
+
+    for (x, y, tile) in tiles {
+        let tile_id = tile.render_tile_id();
+        let texel = UVec2::new(tile_id % columns, tile_id / columns)
+            * (tileset.tile_size + tileset.spacing)
+            + tileset.margin;
+        // Flip Y: Tiled y=0 is top, Bevy y=0 is bottom
+        let min = Vec2::new(*x as f32, (height - 1 - y) as f32) * tile_size + render_offset;
+
+        // Corners in Tiled's Y-down tile space: top-left, top-right, bottom-right, bottom-left
+        let base = positions.len() as u32;
+        for corner in [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y] {
+            let position = min + Vec2::new(corner.x, 1.0 - corner.y) * tile_size;
+            positions.push([position.x, position.y, 0.0]);
+            let uv = (texel.as_vec2() + flipped_tile_uv(corner, tile) * tile_size) / image_size;
+            uvs.push([uv.x, uv.y]);
+        }
+        // Counter-clockwise in Bevy's Y-up space
+        indices.extend([base, base + 3, base + 2, base, base + 2, base + 1]);
+    }
+
+    let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+    Some(
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices)),
+    )
+}
+
+/// Marker for entities spawned to render a tile layer (tilemaps and tile sprites).
+///
+/// Children of the layer entity; despawned and rebuilt when the layer's tiles change.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct TileLayerRender;
+
 /// Component that tracks which tileset a tilemap uses.
 ///
 /// Used for animation lookups and debugging.
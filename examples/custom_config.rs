@@ -24,10 +24,22 @@ fn main() {
                 .with_tilemap(TilemapRenderConfig {
                     // Enable tile animations
                     enable_animations: true,
+                    // Update every animated tile each frame (limit it on low-end devices)
+                    max_animated_tiles_per_frame: None,
                     // Enable parallax scrolling for layers
                     enable_parallax: true,
+                    // Move parallax layers every frame
+                    parallax_frame_interval: 1,
                     // Disable debug shape rendering
                     enable_debug_shapes: false,
+                    // Fill polygons that have a `fill_color` property
+                    enable_polygon_fill: true,
+                    // Keep per-tile entities (set to bake static layers into meshes)
+                    bake_static_layers: false,
+                    // Render in 2D (set a `MapPlane` to lay maps out for 3D cameras)
+                    plane_3d: None,
+                    // Keep small tile objects visible at every zoom level
+                    object_lod: None,
                 }),
         )
         .add_systems(Startup, (setup_camera, spawn_map))
@@ -30,6 +30,7 @@ fn main() {
             core: TiledmapCoreConfig {
                 export_target: Some(TypeExportTarget::JsonFile("assets/tiled_types.json".into())),
                 project_path: Some("everything.tiled-project".into()),
+                ..default()
             },
             ..default()
         })
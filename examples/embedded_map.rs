@@ -0,0 +1,32 @@
+//! Embedded map example: the map, its tileset and the tileset image are
+//! compiled into the binary, so it runs without the `assets` folder.
+//!
+//! Useful for jam games and WASM builds. Embedded files are loaded through the
+//! `embedded://` asset source, with the same loaders as files on disk.
+//!
+//! Run with:
+//! ```bash
+//! cargo run --example embedded_map
+//! ```
+
+use bevy::prelude::*;
+use bevy_tiledmap::prelude::*;
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugins(BevyTiledmapPlugin::default())
+        .add_systems(Startup, spawn_map);
+
+    // Paths are relative to this crate's `assets` folder
+    embed_tiled_assets!(&mut app, "infinite_map.tmx", "terrains.tsx", "terrains.png");
+
+    app.run();
+}
+
+fn spawn_map(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((Camera2d, Transform::from_xyz(384.0, 384.0, 0.0)));
+    commands.spawn(TiledMap {
+        handle: asset_server.load("embedded://infinite_map.tmx"),
+    });
+}
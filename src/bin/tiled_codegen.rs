@@ -0,0 +1,70 @@
+//! `tiled-codegen`: generate Rust types from a Tiled project.
+//!
+//! Reads the custom classes and enums of a `.tiled-project` and writes
+//! `#[derive(TiledClass)]` mirror types for them, so adopting this crate in an
+//! existing project doesn't mean writing them by hand. Warnings about types
+//! that couldn't be mirrored exactly go to stderr.
+//!
+//! ```bash
+//! cargo run --features cli --bin tiled-codegen -- \
+//!     assets/game.tiled-project -o src/tiled_types.rs --skip avian::
+//! ```
+//!
+//! Without `-o`, the code is written to stdout. See
+//! `bevy_tiledmap::core::project::codegen` to run it from a build script instead.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use bevy_tiledmap::core::project::TiledProjectAsset;
+use bevy_tiledmap::core::project::codegen::RustTypeCodegen;
+
+const USAGE: &str =
+    "usage: tiled-codegen <project> [-o <output>] [--prelude <path>] [--skip <prefix>]...";
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            let _ = writeln!(std::io::stderr(), "{error}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    let mut project = None;
+    let mut output: Option<PathBuf> = None;
+    let mut codegen = RustTypeCodegen::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => output = Some(args.next().ok_or(USAGE)?.into()),
+            "--prelude" => codegen = codegen.with_prelude(args.next().ok_or(USAGE)?),
+            "--skip" => codegen = codegen.skip(args.next().ok_or(USAGE)?),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if project.is_none() => project = Some(PathBuf::from(arg)),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let project_path = project.ok_or(USAGE)?;
+
+    let json = std::fs::read_to_string(&project_path)
+        .map_err(|error| format!("Failed to read {}: {error}", project_path.display()))?;
+    let project: TiledProjectAsset = serde_json::from_str(&json)
+        .map_err(|error| format!("Failed to parse {}: {error}", project_path.display()))?;
+    let generated = codegen.generate(&project);
+
+    let mut stderr = std::io::stderr().lock();
+    for warning in &generated.warnings {
+        let _ = writeln!(stderr, "warning: {warning}");
+    }
+    match output {
+        Some(path) => std::fs::write(&path, &generated.code)
+            .map_err(|error| format!("Failed to write {}: {error}", path.display())),
+        None => std::io::stdout()
+            .write_all(generated.code.as_bytes())
+            .map_err(|error| error.to_string()),
+    }
+}
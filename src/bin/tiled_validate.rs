@@ -0,0 +1,286 @@
+//! `tiled-validate`: check Tiled maps before they are merged.
+//!
+//! Loads maps headlessly through the same asset loaders as games do, and
+//! reports as JSON on stdout:
+//!
+//! - maps that fail to load
+//! - missing tilesets, images and `file` properties
+//! - tiles outside their tileset and unsupported tile data encodings
+//! - classes, members and enum values not matching the `.tiled-project`
+//! - features that aren't supported yet (as warnings)
+//!
+//! Exits with status 1 if any map has an error, so CI can reject broken maps.
+//!
+//! ```bash
+//! cargo run --features cli --bin tiled-validate -- \
+//!     --project everything.tiled-project maps/level1.tmx maps/dungeon
+//! ```
+//!
+//! Paths are relative to the `assets` folder; directories are searched for
+//! `.tmx` files. Run from the folder containing `assets`, or pass `--root`.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use bevy_tiledmap::assets::prelude::*;
+use bevy_tiledmap::core::project::{TiledProjectAsset, TiledProjectProperties};
+use serde::Serialize;
+use tiled::{Properties, PropertyValue};
+
+/// Updates to run before reporting maps that are still loading as failed.
+const MAX_UPDATES: usize = 10_000;
+
+const USAGE: &str = "usage: tiled-validate [--root <dir>] [--project <file>] <map or directory>...";
+
+/// Validation results of all maps.
+#[derive(Serialize)]
+struct Report {
+    /// Whether no map has an error
+    ok: bool,
+    maps: Vec<MapReport>,
+}
+
+/// Validation results of one map.
+#[derive(Serialize)]
+struct MapReport {
+    /// Map path relative to the `assets` folder
+    path: String,
+    /// Whether the map has no error
+    ok: bool,
+    errors: Vec<Issue>,
+    warnings: Vec<Issue>,
+}
+
+#[derive(Serialize)]
+struct Issue {
+    /// Machine-readable issue category
+    kind: &'static str,
+    message: String,
+}
+
+impl Issue {
+    fn new(kind: &'static str, message: impl ToString) -> Self {
+        Self {
+            kind,
+            message: message.to_string(),
+        }
+    }
+}
+
+struct Args {
+    root: Option<PathBuf>,
+    project: Option<PathBuf>,
+    maps: Vec<PathBuf>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        root: None,
+        project: None,
+        maps: Vec::new(),
+    };
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--root" => args.root = Some(iter.next().ok_or(USAGE)?.into()),
+            "--project" => args.project = Some(iter.next().ok_or(USAGE)?.into()),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ => args.maps.push(arg.into()),
+        }
+    }
+    if args.maps.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    Ok(args)
+}
+
+/// Collect the `.tmx` files at `path` (relative to `assets`), searching directories.
+fn collect_maps(assets: &Path, path: &Path, maps: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let full_path = assets.join(path);
+    if !full_path.is_dir() {
+        maps.push(path.to_path_buf());
+        return Ok(());
+    }
+    let mut entries = std::fs::read_dir(full_path)?
+        .map(|entry| entry.map(|entry| entry.file_name()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+    for name in entries {
+        let entry = path.join(name);
+        let entry = entry.strip_prefix(".").unwrap_or(&entry);
+        if assets.join(entry).is_dir() || entry.extension().is_some_and(|ext| ext == "tmx") {
+            collect_maps(assets, entry, maps)?;
+        }
+    }
+    Ok(())
+}
+
+fn load_project(assets: &Path, path: &Path) -> Result<TiledProjectProperties, String> {
+    let json = std::fs::read_to_string(assets.join(path))
+        .map_err(|error| format!("Failed to read project {}: {error}", path.display()))?;
+    let asset: TiledProjectAsset = serde_json::from_str(&json)
+        .map_err(|error| format!("Failed to parse project {}: {error}", path.display()))?;
+    Ok(TiledProjectProperties::from_asset(&asset))
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            let _ = writeln!(std::io::stderr(), "{error}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run() -> Result<bool, String> {
+    let args = parse_args()?;
+    if let Some(root) = &args.root {
+        std::env::set_current_dir(root)
+            .map_err(|error| format!("Failed to enter {}: {error}", root.display()))?;
+    }
+    // The map loaders read `assets/...` relative to the working directory, so
+    // point Bevy at the same folder
+    let assets = std::env::current_dir()
+        .map_err(|error| error.to_string())?
+        .join("assets");
+
+    let mut paths = Vec::new();
+    for path in &args.maps {
+        collect_maps(&assets, path, &mut paths).map_err(|error| error.to_string())?;
+    }
+    let project = match &args.project {
+        Some(path) => Some(load_project(&assets, path)?),
+        None => None,
+    };
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin {
+            file_path: assets.to_string_lossy().into_owned(),
+            ..default()
+        },
+        TiledmapAssetsPlugin,
+    ))
+    // Tilesets load their images, which only need to exist (not be decoded)
+    .init_asset::<Image>();
+
+    // Lenient loading records missing tilesets and images instead of failing
+    let handles: Vec<Handle<TiledMapAsset>> = paths
+        .iter()
+        .map(|path| {
+            app.world().resource::<AssetServer>().load_with_settings(
+                path.to_string_lossy().replace('\\', "/"),
+                |settings: &mut TiledMapLoaderSettings| settings.lenient = true,
+            )
+        })
+        .collect();
+
+    for _ in 0..MAX_UPDATES {
+        let server = app.world().resource::<AssetServer>();
+        let loading = handles.iter().any(|handle| {
+            matches!(
+                server.get_load_state(handle),
+                Some(LoadState::Loading | LoadState::NotLoaded)
+            )
+        });
+        if !loading {
+            break;
+        }
+        app.update();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    let server = app.world().resource::<AssetServer>();
+    let map_assets = app.world().resource::<Assets<TiledMapAsset>>();
+    let maps: Vec<MapReport> = paths
+        .iter()
+        .zip(&handles)
+        .map(|(path, handle)| {
+            let mut errors = Vec::new();
+            let mut warnings = Vec::new();
+            match (map_assets.get(handle), server.get_load_state(handle)) {
+                (Some(map), _) => {
+                    check_map(map, &assets, project.as_ref(), &mut errors, &mut warnings);
+                }
+                (None, Some(LoadState::Failed(error))) => errors.push(Issue::new("load", error)),
+                (None, _) => errors.push(Issue::new("load", "map did not finish loading")),
+            }
+            MapReport {
+                path: path.to_string_lossy().replace('\\', "/"),
+                ok: errors.is_empty(),
+                errors,
+                warnings,
+            }
+        })
+        .collect();
+
+    let report = Report {
+        ok: maps.iter().all(|map| map.ok),
+        maps,
+    };
+    let json = serde_json::to_string_pretty(&report).map_err(|error| error.to_string())?;
+    writeln!(std::io::stdout(), "{json}").map_err(|error| error.to_string())?;
+    Ok(report.ok)
+}
+
+/// Check a loaded map, sorting its issues into errors and warnings.
+fn check_map(
+    map: &TiledMapAsset,
+    assets: &Path,
+    project: Option<&TiledProjectProperties>,
+    errors: &mut Vec<Issue>,
+    warnings: &mut Vec<Issue>,
+) {
+    for issue in map.load_issues.iter() {
+        let kind = match issue {
+            TiledMapLoadIssue::MissingTileset { .. } => "missing_tileset",
+            TiledMapLoadIssue::MissingImage { .. } => "missing_image",
+            TiledMapLoadIssue::UnsupportedEncoding { .. } => "unsupported_encoding",
+            TiledMapLoadIssue::InvalidTile { .. } => "invalid_tile",
+        };
+        errors.push(Issue::new(kind, issue));
+    }
+
+    // File properties were normalized to asset-root-relative paths by the loader
+    let properties = std::iter::once(&map.properties)
+        .chain(map.layer_properties.values())
+        .chain(map.object_properties.values());
+    for properties in properties {
+        check_file_properties(properties, assets, errors);
+    }
+
+    if let Some(project) = project {
+        for issue in project.validate_map(&map.map) {
+            errors.push(Issue::new("schema", issue));
+        }
+    }
+
+    for issue in &map.compatibility_issues {
+        warnings.push(Issue::new("compatibility", issue));
+    }
+}
+
+/// Report `file` properties (including class members) pointing at missing files.
+fn check_file_properties(properties: &Properties, assets: &Path, errors: &mut Vec<Issue>) {
+    for (name, value) in properties {
+        match value {
+            PropertyValue::FileValue(path) if !path.is_empty() && !assets.join(path).exists() => {
+                errors.push(Issue::new(
+                    "missing_file",
+                    format!("property '{name}' references missing file {path}"),
+                ));
+            }
+            PropertyValue::ClassValue { properties, .. } => {
+                check_file_properties(properties, assets, errors);
+            }
+            _ => {}
+        }
+    }
+}
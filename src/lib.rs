@@ -32,6 +32,9 @@
 //! - **tilemap**: Tile layer rendering using `bevy_ecs_tilemap` (recommended)
 //! - **avian**: Physics collider generation using `avian2d`
 //! - **native**: Bevy native tilemap rendering (placeholder for future)
+//! - **`test_utils`**: [`test_utils`] module with a headless app for testing map-driven logic
+//! - **cli**: `tiled-validate` binary checking maps in CI, and `tiled-codegen` binary generating
+//!   `TiledClass` types from a `.tiled-project`
 //!
 //! ## Architecture
 //!
@@ -63,6 +66,8 @@
 //! ```
 
 pub mod plugin;
+#[cfg(feature = "test_utils")]
+pub mod test_utils;
 
 // Re-export sub-crates for advanced usage
 pub use bevy_tiledmap_assets as assets;
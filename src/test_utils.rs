@@ -0,0 +1,221 @@
+//! Headless test harness for map-driven game logic.
+//!
+//! [`TiledTestApp`] is a minimal [`App`] with the asset and core plugins (no
+//! window, renderer or physics) that loads maps synchronously: spawning a map
+//! runs the app until the map and everything it references has loaded and its
+//! layers and objects are spawned. Add your own plugins and observers to
+//! [`TiledTestApp::app`] before spawning to test them against real maps:
+//!
+//! ```rust,ignore
+//! #[test]
+//! fn chests_spawn_closed() {
+//!     let mut test = TiledTestApp::new();
+//!     test.app.add_observer(on_chest_spawned);
+//!     test.spawn_map("maps/dungeon.tmx");
+//!
+//!     let world = test.world_mut();
+//!     let mut chests = world.query::<&Chest>();
+//!     assert!(chests.iter(world).all(|chest| !chest.open));
+//! }
+//! ```
+//!
+//! Maps are read from the test crate's `assets` folder, like in the game.
+//! [`TiledTestApp::spawn_map_bytes`] spawns a map from memory instead (e.g.
+//! an `include_bytes!` fixture or a map string written in the test); the
+//! tilesets and templates it references must then be added with
+//! [`TiledTestApp::add_file`], or be on disk. Images are never decoded: they
+//! load as 1x1 placeholders, so tests don't need Bevy's image formats.
+
+use std::time::Duration;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, RecursiveDependencyLoadState};
+use bevy::prelude::*;
+use bevy_tiledmap_assets::TiledmapAssetsPlugin;
+use bevy_tiledmap_assets::embedded;
+use bevy_tiledmap_assets::prelude::TiledMapAsset;
+use bevy_tiledmap_core::prelude::*;
+
+/// Updates to run before giving up on a map spawning.
+const MAX_UPDATES: usize = 1000;
+
+/// Maps spawned so far, collected by an observer.
+#[derive(Resource, Default)]
+struct SpawnedMaps(Vec<Entity>);
+
+/// Loads images as 1x1 placeholders without decoding them.
+#[derive(Default, TypePath)]
+struct PlaceholderImageLoader;
+
+impl AssetLoader for PlaceholderImageLoader {
+    type Asset = Image;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        _reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        Ok(Image::default())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["png", "jpg", "jpeg", "bmp", "gif", "tga", "webp"]
+    }
+}
+
+/// Headless app for testing map-driven logic. See the [module docs](self).
+pub struct TiledTestApp {
+    /// The app, for adding plugins, systems and observers under test
+    pub app: App,
+}
+
+impl Default for TiledTestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TiledTestApp {
+    /// Create a test app with the default core configuration.
+    pub fn new() -> Self {
+        Self::with_config(TiledmapCoreConfig::default())
+    }
+
+    /// Create a test app with a custom core configuration.
+    pub fn with_config(config: TiledmapCoreConfig) -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            TransformPlugin,
+            TiledmapAssetsPlugin,
+            TiledmapCorePlugin::new(config),
+        ))
+        .init_asset::<Image>()
+        .init_asset_loader::<PlaceholderImageLoader>()
+        .init_resource::<SpawnedMaps>()
+        .add_observer(|spawned: On<MapSpawned>, mut maps: ResMut<SpawnedMaps>| {
+            maps.0.push(spawned.entity);
+        });
+        Self { app }
+    }
+
+    /// Add an in-memory file (a tileset, template or image referenced by an
+    /// in-memory map) at a path relative to the `assets` folder.
+    pub fn add_file(&mut self, path: &str, bytes: &'static [u8]) -> &mut Self {
+        embedded::embed(&mut self.app, path, path, bytes);
+        self
+    }
+
+    /// Load the map at `path` (relative to the `assets` folder) and spawn it,
+    /// returning the map entity once its layers and objects are spawned.
+    ///
+    /// # Panics
+    ///
+    /// If the map or a file it references fails to load, or the map doesn't
+    /// spawn within a thousand updates.
+    #[track_caller]
+    pub fn spawn_map(&mut self, path: &str) -> Entity {
+        let handle = self.app.world().resource::<AssetServer>().load(path.to_string());
+        self.spawn(path, handle)
+    }
+
+    /// Spawn a map from memory, as if it were at `path` (relative to the
+    /// `assets` folder, which the files it references are relative to).
+    ///
+    /// # Panics
+    ///
+    /// Like [`spawn_map`](Self::spawn_map).
+    #[track_caller]
+    pub fn spawn_map_bytes(&mut self, path: &str, bytes: &'static [u8]) -> Entity {
+        self.add_file(path, bytes);
+        let handle = self
+            .app
+            .world()
+            .resource::<AssetServer>()
+            .load(format!("embedded://{path}"));
+        self.spawn(path, handle)
+    }
+
+    #[track_caller]
+    fn spawn(&mut self, path: &str, handle: Handle<TiledMapAsset>) -> Entity {
+        let map = self
+            .app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                Visibility::default(),
+                TiledMap {
+                    handle: handle.clone(),
+                },
+            ))
+            .id();
+
+        let mut updates = 0;
+        while !self.app.world().resource::<SpawnedMaps>().0.contains(&map) {
+            let server = self.app.world().resource::<AssetServer>();
+            if let Some(RecursiveDependencyLoadState::Failed(error)) =
+                server.get_recursive_dependency_load_state(&handle)
+            {
+                panic!("{path} failed to load: {error}");
+            }
+            assert!(updates < MAX_UPDATES, "{path} did not spawn");
+            self.app.update();
+            updates += 1;
+            // Loading happens on other threads
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        // Let observers' commands apply and transforms propagate
+        self.app.update();
+        map
+    }
+
+    /// Run the app's schedules once.
+    pub fn update(&mut self) -> &mut Self {
+        self.app.update();
+        self
+    }
+
+    /// The app's world.
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    /// The app's world, mutably.
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    /// Take the app's world, e.g. to return it from a test helper.
+    pub fn into_world(mut self) -> World {
+        std::mem::take(self.app.world_mut())
+    }
+}
+
+/// Spawn the map at `path` (relative to the `assets` folder) in a
+/// [`TiledTestApp`] and return its world.
+///
+/// # Panics
+///
+/// Like [`TiledTestApp::spawn_map`].
+#[track_caller]
+pub fn spawn_test_map(path: &str) -> World {
+    let mut test = TiledTestApp::new();
+    test.spawn_map(path);
+    test.into_world()
+}
+
+/// Spawn a map from memory in a [`TiledTestApp`] and return its world.
+///
+/// # Panics
+///
+/// Like [`TiledTestApp::spawn_map`].
+#[track_caller]
+pub fn spawn_test_map_bytes(path: &str, bytes: &'static [u8]) -> World {
+    let mut test = TiledTestApp::new();
+    test.spawn_map_bytes(path, bytes);
+    test.into_world()
+}